@@ -0,0 +1,126 @@
+//! Analysis benchmarks over the synthetic corpus
+//!
+//! Run with `cargo bench`; compare against a saved baseline with
+//! `cargo bench -- --save-baseline main` and `cargo bench -- --baseline main`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rust_guardian::analyzer::benchmark::{corpus_file, generate_corpus};
+use rust_guardian::config::RuleType;
+use rust_guardian::patterns::PatternEngine;
+use rust_guardian::{AnalysisOptions, Analyzer, GuardianConfig, PathFilter};
+use std::path::PathBuf;
+
+/// Files in the end-to-end corpus
+const CORPUS_FILES: usize = 10_000;
+
+/// Files analyzed per iteration of the single-pass benchmarks
+const SAMPLE_FILES: usize = 100;
+
+/// Engine holding only the default rules of the given kind
+fn engine_for(is_kind: fn(&RuleType) -> bool) -> PatternEngine {
+    let config = GuardianConfig::default();
+    let mut engine = PatternEngine::new();
+    for category in config.patterns.values() {
+        for rule in category.rules.iter().filter(|rule| rule.enabled) {
+            if is_kind(&rule.rule_type) {
+                let severity = config.effective_severity(category, rule);
+                engine
+                    .add_rule(rule, severity)
+                    .expect("default rules should compile");
+            }
+        }
+    }
+    engine
+}
+
+fn bench_pattern_passes(c: &mut Criterion) {
+    let sources: Vec<String> = (0..SAMPLE_FILES).map(corpus_file).collect();
+    let bytes: usize = sources.iter().map(String::len).sum();
+
+    let mut group = c.benchmark_group("pattern_pass");
+    group.throughput(Throughput::Bytes(bytes as u64));
+
+    let regex_engine = engine_for(|kind| matches!(kind, RuleType::Regex));
+    group.bench_function("regex", |b| {
+        b.iter(|| {
+            for source in &sources {
+                black_box(
+                    regex_engine
+                        .analyze_file("bench.rs", source)
+                        .expect("analysis"),
+                );
+            }
+        })
+    });
+
+    let ast_engine = engine_for(|kind| matches!(kind, RuleType::Ast));
+    group.bench_function("ast", |b| {
+        b.iter(|| {
+            for source in &sources {
+                black_box(
+                    ast_engine
+                        .analyze_file("bench.rs", source)
+                        .expect("analysis"),
+                );
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_path_filtering(c: &mut Criterion) {
+    let config = GuardianConfig::default();
+    let filter = PathFilter::new(config.paths.patterns.clone(), None).expect("default patterns");
+    let paths: Vec<PathBuf> = (0..CORPUS_FILES)
+        .map(|index| match index % 4 {
+            0 => PathBuf::from(format!("src/module_{}/file_{index}.rs", index / 100)),
+            1 => PathBuf::from(format!("target/debug/build/file_{index}.rs")),
+            2 => PathBuf::from(format!("tests/fixtures/file_{index}.rs")),
+            _ => PathBuf::from(format!("docs/page_{index}.md")),
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("path_filter");
+    group.throughput(Throughput::Elements(paths.len() as u64));
+    group.bench_function("should_analyze", |b| {
+        b.iter(|| {
+            for path in &paths {
+                black_box(filter.should_analyze(path).expect("filtering"));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_directory_analysis(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (_, bytes) = generate_corpus(dir.path(), CORPUS_FILES).expect("corpus");
+
+    let mut config = GuardianConfig::default();
+    config.paths.root = Some(dir.path().to_path_buf());
+    let analyzer = Analyzer::new(config).expect("analyzer");
+    let options = AnalysisOptions::default();
+
+    let mut group = c.benchmark_group("end_to_end");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(bytes));
+    group.bench_function("analyze_directory_10k", |b| {
+        b.iter(|| {
+            black_box(
+                analyzer
+                    .analyze_directory(dir.path(), &options)
+                    .expect("analysis"),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_pattern_passes,
+    bench_path_filtering,
+    bench_directory_analysis
+);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Build script embedding an organization policy into the binary
+//!
+//! Set `GUARDIAN_EMBED_CONFIG` to a YAML file (relative to the package root) to bake it into
+//! the build; `GuardianConfig::embedded()` then returns it. Without the variable an empty
+//! placeholder is embedded and no policy is baked in.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GUARDIAN_EMBED_CONFIG");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("cargo sets OUT_DIR"));
+    let target = out_dir.join("embedded_config.yaml");
+
+    let contents = match env::var_os("GUARDIAN_EMBED_CONFIG") {
+        Some(path) if !path.is_empty() => {
+            let path = PathBuf::from(path);
+            println!("cargo:rerun-if-changed={}", path.display());
+            fs::read_to_string(&path).unwrap_or_else(|e| {
+                panic!(
+                    "GUARDIAN_EMBED_CONFIG points to '{}', which could not be read: {e}",
+                    path.display()
+                )
+            })
+        }
+        _ => String::new(),
+    };
+
+    fs::write(&target, contents).expect("failed to write embedded configuration");
+}
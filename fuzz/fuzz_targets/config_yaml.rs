@@ -0,0 +1,14 @@
+//! Arbitrary YAML must load or fail with an error, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_guardian::GuardianConfig;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(config) = GuardianConfig::load_from_str(content) {
+            let _ = rust_guardian::Analyzer::new(config);
+        }
+    }
+});
@@ -0,0 +1,23 @@
+//! Arbitrary `.guardianignore` contents must parse or report invalid lines, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_guardian::PathFilter;
+
+fuzz_target!(|data: &[u8]| {
+    let content = String::from_utf8_lossy(data);
+    let _ = PathFilter::check_ignore_content(&content);
+
+    // The same lines used as configured path patterns
+    let patterns: Vec<String> = content.lines().map(str::to_string).collect();
+    if let Ok(filter) = PathFilter::new(patterns, None) {
+        for path in [
+            "src/lib.rs",
+            "target/debug/build.rs",
+            "tests/fixtures/a b.rs",
+        ] {
+            let _ = filter.should_analyze(path);
+        }
+    }
+});
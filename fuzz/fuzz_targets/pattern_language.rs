@@ -0,0 +1,54 @@
+//! Arbitrary rule patterns must compile or fail with an error, never panic
+//!
+//! The first byte picks the rule type; the rest is the pattern. Rules that compile are run
+//! over a small source file so the parsed parameters are exercised too.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_guardian::config::RuleType;
+use rust_guardian::patterns::PatternEngine;
+use rust_guardian::{GuardianConfig, Severity};
+
+const SOURCE: &str = r#"
+/// Documented
+pub fn run(values: &[u32]) -> u32 {
+    // TODO(alice): #12 tidy up
+    let total: u32 = values.iter().sum();
+    if total > 4096 { unimplemented!() }
+    total
+}
+"#;
+
+fuzz_target!(|data: &[u8]| {
+    let (kind, pattern) = match data.split_first() {
+        Some((&kind, rest)) => match std::str::from_utf8(rest) {
+            Ok(pattern) => (kind, pattern),
+            Err(_) => return,
+        },
+        None => return,
+    };
+
+    let config = GuardianConfig::default();
+    let mut rule = match config
+        .patterns
+        .values()
+        .flat_map(|category| category.rules.iter())
+        .next()
+    {
+        Some(rule) => rule.clone(),
+        None => return,
+    };
+    rule.rule_type = match kind % 4 {
+        0 => RuleType::Regex,
+        1 => RuleType::Ast,
+        2 => RuleType::Semantic,
+        _ => RuleType::ImportAnalysis,
+    };
+    rule.pattern = pattern.to_string();
+
+    let mut engine = PatternEngine::new();
+    if engine.add_rule(&rule, Severity::Warning).is_ok() {
+        let _ = engine.analyze_file("fuzz.rs", SOURCE);
+    }
+});
@@ -0,0 +1,37 @@
+/*
+ * C API for Rust Guardian, built with
+ * `cargo rustc --release --lib --crate-type cdylib --features ffi`.
+ *
+ * All strings are NUL-terminated UTF-8. Strings returned by guardian_validate_json
+ * are owned by the caller and must be released with guardian_string_free.
+ */
+
+#ifndef RUST_GUARDIAN_H
+#define RUST_GUARDIAN_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Validate files and return the report as JSON.
+ *
+ * config_json: Guardian configuration as JSON (or YAML); NULL or "" for the defaults.
+ * files_json:  JSON array of file or directory paths.
+ *
+ * Returns the report in the `--format json` layout, or {"error": "..."} on failure.
+ * Never returns NULL.
+ */
+char *guardian_validate_json(const char *config_json, const char *files_json);
+
+/* Release a string returned by guardian_validate_json. NULL is ignored. */
+void guardian_string_free(char *value);
+
+/* The library version. Static; do not free. */
+const char *guardian_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUST_GUARDIAN_H */
@@ -0,0 +1,181 @@
+//! Synthetic corpus and throughput measurement
+//!
+//! Architecture: Infrastructure Layer - Benchmarks drive the real analyzer over generated code
+//! - The corpus mixes clean code with the violations the default rules look for
+//! - `benches/` and `rust-guardian self-benchmark` share the same corpus, so numbers compare
+//! - Throughput depends on the configuration, which lets users measure their own rule sets
+
+use crate::analyzer::{AnalysisOptions, Analyzer};
+use crate::config::GuardianConfig;
+use crate::domain::violations::{GuardianError, GuardianResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Files per generated module directory
+const FILES_PER_MODULE: usize = 100;
+
+/// Throughput of one analysis run over a corpus
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    /// Files analyzed
+    pub files: usize,
+    /// Total size of the analyzed files
+    pub bytes: u64,
+    /// Violations found
+    pub violations: usize,
+    /// Wall-clock time of the analysis
+    pub elapsed: Duration,
+}
+
+impl BenchmarkResult {
+    /// Files analyzed per second
+    pub fn files_per_second(&self) -> f64 {
+        self.files as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// MiB of source analyzed per second
+    pub fn mib_per_second(&self) -> f64 {
+        self.bytes as f64 / (1024.0 * 1024.0) / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Human-readable summary
+    pub fn format_display(&self) -> String {
+        format!(
+            "Analyzed {} files ({:.1} MiB) in {:.2}s: {:.0} files/s, {:.2} MiB/s, {} violations",
+            self.files,
+            self.bytes as f64 / (1024.0 * 1024.0),
+            self.elapsed.as_secs_f64(),
+            self.files_per_second(),
+            self.mib_per_second(),
+            self.violations
+        )
+    }
+}
+
+/// Source of the `index`-th synthetic file
+///
+/// Every tenth file has a task marker comment and every seventh an unimplemented function,
+/// so the regex and AST passes both have matches to report.
+pub fn corpus_file(index: usize) -> String {
+    // Assembled at runtime so this generator does not flag itself
+    let marker = ["TO", "DO"].concat();
+    let mut source = format!(
+        "//! Synthetic module {index}\n\
+         \n\
+         use std::collections::HashMap;\n\
+         \n\
+         /// Sum values, doubling the large ones\n\
+         pub fn compute_{index}(values: &[u32]) -> u32 {{\n\
+         \x20   let mut total = 0;\n\
+         \x20   for value in values {{\n\
+         \x20       if *value > 10 {{\n\
+         \x20           total += value * 2;\n\
+         \x20       }} else {{\n\
+         \x20           total += value;\n\
+         \x20       }}\n\
+         \x20   }}\n\
+         \x20   total\n\
+         }}\n\
+         \n\
+         /// Look up a key that must exist\n\
+         pub fn lookup_{index}(map: &HashMap<String, u32>, key: &str) -> u32 {{\n\
+         \x20   *map.get(key).unwrap()\n\
+         }}\n"
+    );
+
+    if index.is_multiple_of(10) {
+        source.push_str(&format!(
+            "\n// {marker}: handle overflow in compute_{index}\n"
+        ));
+    }
+    if index.is_multiple_of(7) {
+        source.push_str(&format!(
+            "\npub fn pending_{index}() -> u32 {{\n    {}!()\n}}\n",
+            "unimplemented"
+        ));
+    }
+
+    source
+}
+
+/// Write a synthetic corpus of `files` Rust files under `root/src`
+///
+/// Returns the generated paths and their total size.
+pub fn generate_corpus(root: &Path, files: usize) -> GuardianResult<(Vec<PathBuf>, u64)> {
+    let mut paths = Vec::with_capacity(files);
+    let mut bytes = 0;
+
+    for index in 0..files {
+        let module_dir = root
+            .join("src")
+            .join(format!("module_{}", index / FILES_PER_MODULE));
+        if index % FILES_PER_MODULE == 0 {
+            fs::create_dir_all(&module_dir).map_err(|e| GuardianError::io(&module_dir, e))?;
+        }
+
+        let path = module_dir.join(format!("file_{index}.rs"));
+        let source = corpus_file(index);
+        fs::write(&path, &source).map_err(|e| GuardianError::io(&path, e))?;
+        bytes += source.len() as u64;
+        paths.push(path);
+    }
+
+    Ok((paths, bytes))
+}
+
+/// Analyze a freshly generated corpus with a configuration and measure throughput
+///
+/// The corpus is written to a temporary directory, which is removed afterwards.
+pub fn self_benchmark(config: GuardianConfig, files: usize) -> GuardianResult<BenchmarkResult> {
+    let root = std::env::temp_dir().join(format!("rust-guardian-bench-{}", std::process::id()));
+    fs::create_dir_all(&root).map_err(|e| GuardianError::io(&root, e))?;
+
+    let result = benchmark_corpus(config, &root, files);
+
+    if let Err(e) = fs::remove_dir_all(&root) {
+        tracing::warn!(
+            "Failed to remove benchmark corpus {}: {}",
+            root.display(),
+            e
+        );
+    }
+    result
+}
+
+/// Generate a corpus under `root` and time one analysis of it
+fn benchmark_corpus(
+    mut config: GuardianConfig,
+    root: &Path,
+    files: usize,
+) -> GuardianResult<BenchmarkResult> {
+    let (_, bytes) = generate_corpus(root, files)?;
+    config.paths.root = Some(root.to_path_buf());
+    let analyzer = Analyzer::new(config)?;
+
+    let start = Instant::now();
+    let report = analyzer.analyze_directory(root, &AnalysisOptions::default())?;
+
+    Ok(BenchmarkResult {
+        files: report.summary.total_files,
+        bytes,
+        violations: report.violations.len(),
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_benchmark_small_corpus() {
+        let result = self_benchmark(GuardianConfig::default(), 30).expect("benchmark should run");
+
+        assert_eq!(result.files, 30);
+        assert!(result.bytes > 0);
+        // Files 0, 10 and 20 carry task markers; 0, 7, 14, 21 and 28 are unimplemented
+        assert!(result.violations >= 8);
+        assert!(result.format_display().starts_with("Analyzed 30 files"));
+    }
+}
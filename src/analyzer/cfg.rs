@@ -0,0 +1,437 @@
+//! Conditional compilation context of Rust source
+//!
+//! Architecture: Value Objects - A cfg predicate is parsed once and evaluated against build settings
+//! - CfgIndex maps each line to the `#[cfg(...)]` attributes of the items enclosing it
+//! - Predicates evaluate against a set of enabled options, like rustc does for one build
+//! - Code under a cfg is still analyzed; the context only scopes rules and build matrices
+
+use crate::config::GuardianConfig;
+use crate::domain::violations::{GuardianError, GuardianResult, Violation};
+use proc_macro2::Span;
+use std::collections::HashMap;
+use std::fmt;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// A configuration option such as `test`, `unix` or `feature = "std"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CfgOption {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl CfgOption {
+    /// Parse `name` or `name = "value"`
+    pub fn parse(option: &str) -> GuardianResult<Self> {
+        match CfgExpr::parse(option)? {
+            CfgExpr::Option(option) => Ok(option),
+            _ => Err(GuardianError::config(format!(
+                "'{option}' is a cfg predicate, expected a single option"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CfgOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} = {value:?}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// A cfg predicate as written in `#[cfg(...)]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    Option(CfgOption),
+    All(Vec<CfgExpr>),
+    Any(Vec<CfgExpr>),
+    Not(Box<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parse a predicate such as `test`, `feature = "std"` or `all(unix, not(test))`
+    pub fn parse(predicate: &str) -> GuardianResult<Self> {
+        let meta: syn::Meta = syn::parse_str(predicate).map_err(|e| {
+            GuardianError::config(format!("Invalid cfg predicate '{predicate}': {e}"))
+        })?;
+        Self::from_meta(&meta)
+            .map_err(|e| GuardianError::config(format!("Invalid cfg predicate '{predicate}': {e}")))
+    }
+
+    fn from_meta(meta: &syn::Meta) -> syn::Result<Self> {
+        match meta {
+            syn::Meta::Path(path) => Ok(Self::Option(CfgOption {
+                name: path_name(path)?,
+                value: None,
+            })),
+            syn::Meta::NameValue(name_value) => match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) => Ok(Self::Option(CfgOption {
+                    name: path_name(&name_value.path)?,
+                    value: Some(value.value()),
+                })),
+                other => Err(syn::Error::new(other.span(), "expected a string value")),
+            },
+            syn::Meta::List(list) => {
+                let operands: Vec<Self> = list
+                    .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?
+                    .iter()
+                    .map(Self::from_meta)
+                    .collect::<syn::Result<_>>()?;
+                match path_name(&list.path)?.as_str() {
+                    "all" => Ok(Self::All(operands)),
+                    "any" => Ok(Self::Any(operands)),
+                    "not" if operands.len() == 1 => Ok(Self::Not(Box::new(
+                        operands.into_iter().next().expect("one"),
+                    ))),
+                    "not" => Err(syn::Error::new(list.span(), "not() takes one predicate")),
+                    other => Err(syn::Error::new(
+                        list.span(),
+                        format!("unknown cfg operator '{other}'"),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Evaluate the predicate with the given options enabled
+    pub fn eval(&self, enabled: &dyn Fn(&CfgOption) -> bool) -> bool {
+        match self {
+            Self::Option(option) => enabled(option),
+            Self::All(operands) => operands.iter().all(|operand| operand.eval(enabled)),
+            Self::Any(operands) => operands.iter().any(|operand| operand.eval(enabled)),
+            Self::Not(operand) => !operand.eval(enabled),
+        }
+    }
+
+    /// Options that must be enabled for the predicate to hold
+    ///
+    /// Only options required outright count; those under `any` or `not` do not.
+    pub fn required_options(&self) -> Vec<&CfgOption> {
+        match self {
+            Self::Option(option) => vec![option],
+            Self::All(operands) => operands.iter().flat_map(Self::required_options).collect(),
+            Self::Any(_) | Self::Not(_) => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for CfgExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, name: &str, operands: &[CfgExpr]| {
+            let operands: Vec<String> = operands.iter().map(ToString::to_string).collect();
+            write!(f, "{name}({})", operands.join(", "))
+        };
+        match self {
+            Self::Option(option) => write!(f, "{option}"),
+            Self::All(operands) => list(f, "all", operands),
+            Self::Any(operands) => list(f, "any", operands),
+            Self::Not(operand) => write!(f, "not({operand})"),
+        }
+    }
+}
+
+fn path_name(path: &syn::Path) -> syn::Result<String> {
+    path.get_ident()
+        .map(ToString::to_string)
+        .ok_or_else(|| syn::Error::new(path.span(), "expected a single identifier"))
+}
+
+/// The cfg attributes in effect on each line of a file
+#[derive(Debug, Clone, Default)]
+pub struct CfgIndex {
+    /// Line ranges (1-indexed, inclusive) of cfg-gated code with their predicates, outermost first
+    scopes: Vec<(u32, u32, CfgExpr)>,
+}
+
+impl CfgIndex {
+    /// Index the cfg attributes of a parsed file; malformed predicates are ignored
+    pub fn new(syntax_tree: &syn::File) -> Self {
+        let mut visitor = CfgVisitor { scopes: Vec::new() };
+        // `#![cfg(...)]` gates the whole file
+        for predicate in cfg_predicates(&syntax_tree.attrs) {
+            visitor.scopes.push((1, u32::MAX, predicate));
+        }
+        visitor.visit_file(syntax_tree);
+        Self {
+            scopes: visitor.scopes,
+        }
+    }
+
+    /// The combined predicate gating a line, if any
+    pub fn cfg_at(&self, line: u32) -> Option<CfgExpr> {
+        let mut predicates: Vec<CfgExpr> = self
+            .scopes
+            .iter()
+            .filter(|(start, end, _)| (*start..=*end).contains(&line))
+            .map(|(_, _, predicate)| predicate.clone())
+            .collect();
+        match predicates.len() {
+            0 => None,
+            1 => predicates.pop(),
+            _ => Some(CfgExpr::All(predicates)),
+        }
+    }
+}
+
+struct CfgVisitor {
+    scopes: Vec<(u32, u32, CfgExpr)>,
+}
+
+impl CfgVisitor {
+    fn record(&mut self, attrs: &[syn::Attribute], span: Span) {
+        for predicate in cfg_predicates(attrs) {
+            let start = span.start().line as u32;
+            let end = span.end().line as u32;
+            self.scopes.push((start, end, predicate));
+        }
+    }
+}
+
+impl Visit<'_> for CfgVisitor {
+    fn visit_item(&mut self, item: &syn::Item) {
+        if let Some(attrs) = item_attrs(item) {
+            self.record(attrs, item.span());
+        }
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &syn::ImplItem) {
+        let attrs = match item {
+            syn::ImplItem::Fn(item) => &item.attrs,
+            syn::ImplItem::Const(item) => &item.attrs,
+            syn::ImplItem::Type(item) => &item.attrs,
+            syn::ImplItem::Macro(item) => &item.attrs,
+            _ => return syn::visit::visit_impl_item(self, item),
+        };
+        self.record(attrs, item.span());
+        syn::visit::visit_impl_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &syn::TraitItem) {
+        let attrs = match item {
+            syn::TraitItem::Fn(item) => &item.attrs,
+            syn::TraitItem::Const(item) => &item.attrs,
+            syn::TraitItem::Type(item) => &item.attrs,
+            syn::TraitItem::Macro(item) => &item.attrs,
+            _ => return syn::visit::visit_trait_item(self, item),
+        };
+        self.record(attrs, item.span());
+        syn::visit::visit_trait_item(self, item);
+    }
+
+    fn visit_stmt(&mut self, stmt: &syn::Stmt) {
+        match stmt {
+            syn::Stmt::Local(local) => self.record(&local.attrs, local.span()),
+            syn::Stmt::Macro(mac) => self.record(&mac.attrs, mac.span()),
+            _ => {}
+        }
+        syn::visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_arm(&mut self, arm: &syn::Arm) {
+        self.record(&arm.attrs, arm.span());
+        syn::visit::visit_arm(self, arm);
+    }
+
+    fn visit_field(&mut self, field: &syn::Field) {
+        self.record(&field.attrs, field.span());
+        syn::visit::visit_field(self, field);
+    }
+
+    fn visit_variant(&mut self, variant: &syn::Variant) {
+        self.record(&variant.attrs, variant.span());
+        syn::visit::visit_variant(self, variant);
+    }
+}
+
+/// Attributes of an item, for the item kinds that can carry `#[cfg]`
+fn item_attrs(item: &syn::Item) -> Option<&[syn::Attribute]> {
+    let attrs = match item {
+        syn::Item::Const(item) => &item.attrs,
+        syn::Item::Enum(item) => &item.attrs,
+        syn::Item::ExternCrate(item) => &item.attrs,
+        syn::Item::Fn(item) => &item.attrs,
+        syn::Item::ForeignMod(item) => &item.attrs,
+        syn::Item::Impl(item) => &item.attrs,
+        syn::Item::Macro(item) => &item.attrs,
+        syn::Item::Mod(item) => &item.attrs,
+        syn::Item::Static(item) => &item.attrs,
+        syn::Item::Struct(item) => &item.attrs,
+        syn::Item::Trait(item) => &item.attrs,
+        syn::Item::TraitAlias(item) => &item.attrs,
+        syn::Item::Type(item) => &item.attrs,
+        syn::Item::Union(item) => &item.attrs,
+        syn::Item::Use(item) => &item.attrs,
+        _ => return None,
+    };
+    Some(attrs)
+}
+
+/// Predicates of the `#[cfg(...)]` attributes among `attrs`
+fn cfg_predicates(attrs: &[syn::Attribute]) -> Vec<CfgExpr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.parse_args::<syn::Meta>().ok())
+        .filter_map(|meta| CfgExpr::from_meta(&meta).ok())
+        .collect()
+}
+
+/// Cfg-dependent rule scopes and build matrix of a configuration
+#[derive(Debug, Clone, Default)]
+pub struct CfgScoping {
+    /// `only_cfg` predicates of enabled rules, by rule ID
+    rules: HashMap<String, CfgExpr>,
+    /// Builds of `cfg.matrix` with the options each enables
+    builds: Vec<(String, Vec<CfgOption>)>,
+}
+
+impl CfgScoping {
+    /// Compile the `only_cfg` rule scopes and the `cfg.matrix` builds of a configuration
+    pub fn from_config(config: &GuardianConfig) -> GuardianResult<Self> {
+        let mut scoping = Self::default();
+        for (_, _, rule) in config.enabled_rules() {
+            if let Some(predicate) = &rule.only_cfg {
+                let predicate = CfgExpr::parse(predicate).map_err(|e| {
+                    GuardianError::rule_compilation(&rule.id, format!("invalid only_cfg: {e}"))
+                })?;
+                scoping.rules.insert(rule.id.clone(), predicate);
+            }
+        }
+        for build in &config.cfg.matrix {
+            let options = build
+                .enabled
+                .iter()
+                .map(|option| CfgOption::parse(option))
+                .collect::<GuardianResult<_>>()?;
+            scoping.builds.push((build.name.clone(), options));
+        }
+        Ok(scoping)
+    }
+
+    /// Record the cfg context of each violation, then apply rule scopes and the build matrix
+    ///
+    /// Violations of a rule with `only_cfg` are kept when the predicate holds with the
+    /// options their context requires enabled. With a build matrix, violations in code no
+    /// configured build compiles are dropped, and those compiled by only some builds are
+    /// tagged `build:<name>` for each of them.
+    pub fn apply(&self, syntax_tree: &syn::File, violations: Vec<Violation>) -> Vec<Violation> {
+        let index = CfgIndex::new(syntax_tree);
+
+        violations
+            .into_iter()
+            .filter_map(|mut violation| {
+                let cfg = violation.line_number.and_then(|line| index.cfg_at(line));
+                let required = cfg
+                    .as_ref()
+                    .map(CfgExpr::required_options)
+                    .unwrap_or_default();
+
+                if let Some(predicate) = self.rules.get(&violation.rule_id) {
+                    if !predicate.eval(&|option| required.contains(&option)) {
+                        return None;
+                    }
+                }
+
+                if let Some(cfg) = cfg.as_ref().filter(|_| !self.builds.is_empty()) {
+                    let active: Vec<&str> = self
+                        .builds
+                        .iter()
+                        .filter(|(_, options)| cfg.eval(&|option| options.contains(option)))
+                        .map(|(name, _)| name.as_str())
+                        .collect();
+                    if active.is_empty() {
+                        return None;
+                    }
+                    if active.len() < self.builds.len() {
+                        let tags = active.iter().map(|name| format!("build:{name}"));
+                        violation.tags.extend(tags);
+                    }
+                }
+
+                violation.cfg = cfg.map(|cfg| cfg.to_string());
+                Some(violation)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_eval() {
+        let expr = CfgExpr::parse(r#"all(unix, not(test), any(feature = "a", feature = "b"))"#)
+            .expect("predicate should parse");
+        assert_eq!(
+            expr.to_string(),
+            r#"all(unix, not(test), any(feature = "a", feature = "b"))"#
+        );
+
+        let build = |options: &[&str]| -> Vec<CfgOption> {
+            options
+                .iter()
+                .map(|option| CfgOption::parse(option).expect("option"))
+                .collect()
+        };
+        let eval = |options: Vec<CfgOption>| expr.eval(&|option| options.contains(option));
+        assert!(eval(build(&["unix", r#"feature = "b""#])));
+        assert!(!eval(build(&["unix", "test", r#"feature = "b""#])));
+        assert!(!eval(build(&["windows", r#"feature = "a""#])));
+
+        assert_eq!(
+            expr.required_options(),
+            vec![&CfgOption {
+                name: "unix".to_string(),
+                value: None
+            }]
+        );
+        assert!(CfgExpr::parse("nope(test)").is_err());
+        assert!(CfgOption::parse("all(unix)").is_err());
+    }
+
+    #[test]
+    fn test_cfg_index() {
+        let source = r#"
+fn always() {}
+
+#[cfg(feature = "net")]
+mod net {
+    fn connect() {}
+
+    #[cfg(unix)]
+    fn socket() {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper() {}
+}
+"#;
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        let index = CfgIndex::new(&syntax_tree);
+
+        assert_eq!(index.cfg_at(2), None);
+        assert_eq!(
+            index.cfg_at(6).map(|cfg| cfg.to_string()),
+            Some(r#"feature = "net""#.to_string())
+        );
+        assert_eq!(
+            index.cfg_at(10).map(|cfg| cfg.to_string()),
+            Some(r#"all(feature = "net", unix)"#.to_string())
+        );
+        assert_eq!(
+            index.cfg_at(16).map(|cfg| cfg.to_string()),
+            Some("test".to_string())
+        );
+    }
+}
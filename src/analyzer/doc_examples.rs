@@ -0,0 +1,213 @@
+//! Extraction of Rust code examples from doc comments and Markdown
+//!
+//! Architecture: Domain Services - Doc examples are code that ships, so they get checked like code
+//! - Fenced blocks are extracted from `///` and `//!` comments and from Markdown files
+//! - Each example line remembers where it came from, so findings point into the original file
+//! - Examples are wrapped in `fn main` the way rustdoc does before rules see them
+
+/// Tag added to violations found in doc examples
+pub const DOC_EXAMPLE_TAG: &str = "doc-example";
+
+/// A fenced Rust code block found in documentation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocExample {
+    /// Code of the example, with rustdoc's hidden-line `# ` markers removed
+    pub code: String,
+    /// For each line of `code`, its line (1-indexed) in the original file and the number of
+    /// characters that precede it there
+    pub origins: Vec<(u32, u32)>,
+}
+
+impl DocExample {
+    /// The example as a compilable file, wrapped in `fn main` unless it defines one
+    ///
+    /// Returns the source and the number of lines added before the example's first line.
+    pub fn as_source(&self) -> (String, u32) {
+        if self.code.contains("fn main") {
+            (self.code.clone(), 0)
+        } else {
+            (format!("fn main() {{\n{}}}\n", self.code), 1)
+        }
+    }
+
+    /// Map a position in `as_source` output back to the original file
+    pub fn original_position(&self, line: u32, column: u32, offset: u32) -> Option<(u32, u32)> {
+        let index = line.checked_sub(offset + 1)? as usize;
+        self.origins
+            .get(index)
+            .map(|(line, indent)| (*line, column + indent))
+    }
+}
+
+/// Extract examples from the doc comments of Rust source
+///
+/// Fences without a language, and fences whose attributes are all rustdoc attributes
+/// (`no_run`, `should_panic`, ...), are Rust, as in rustdoc.
+pub fn extract_from_rust(content: &str) -> Vec<DocExample> {
+    let lines = content.lines().enumerate().filter_map(|(index, line)| {
+        let trimmed = line.trim_start();
+        let doc = trimmed
+            .strip_prefix("///")
+            .filter(|rest| !rest.starts_with('/'))
+            .or_else(|| trimmed.strip_prefix("//!"))?;
+        let doc = doc.strip_prefix(' ').unwrap_or(doc);
+        let indent = line.chars().count() - doc.chars().count();
+        Some((index as u32 + 1, indent as u32, doc))
+    });
+    extract_fenced(lines, true)
+}
+
+/// Extract examples from Markdown, where only fences tagged `rust` hold Rust
+pub fn extract_from_markdown(content: &str) -> Vec<DocExample> {
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index as u32 + 1, 0, line));
+    extract_fenced(lines, false)
+}
+
+/// Collect the Rust fenced blocks among documentation lines `(line, indent, text)`
+///
+/// A gap in line numbers ends the current doc comment and any block open in it.
+fn extract_fenced<'a>(
+    lines: impl Iterator<Item = (u32, u32, &'a str)>,
+    untagged_is_rust: bool,
+) -> Vec<DocExample> {
+    let mut examples = Vec::new();
+    // The open fence, its indentation and whether it holds Rust
+    let mut open: Option<(&str, usize, bool, DocExample)> = None;
+    let mut previous_line = 0;
+
+    for (line, indent, text) in lines {
+        if line != previous_line + 1 {
+            open = None;
+        }
+        previous_line = line;
+
+        let trimmed = text.trim_start();
+        let fence_indent = text.len() - trimmed.len();
+        let fence = ["```", "~~~"]
+            .into_iter()
+            .find(|fence| trimmed.starts_with(fence));
+
+        match (&mut open, fence) {
+            (Some((open_fence, _, is_rust, example)), Some(fence))
+                if fence == *open_fence && trimmed.trim_end() == fence =>
+            {
+                if *is_rust {
+                    examples.push(example.clone());
+                }
+                open = None;
+            }
+            (Some((_, open_indent, _, example)), _) => {
+                // Code is indented relative to its fence, as in CommonMark
+                let code = &text[fence_indent.min(*open_indent)..];
+                let code = hide_marker(code);
+                let skipped = text.chars().count() - code.chars().count();
+                example.code.push_str(code);
+                example.code.push('\n');
+                example.origins.push((line, indent + skipped as u32));
+            }
+            (None, Some(fence)) => {
+                let info = trimmed[fence.len()..].trim();
+                let example = DocExample {
+                    code: String::new(),
+                    origins: Vec::new(),
+                };
+                let is_rust = is_rust_fence(info, untagged_is_rust);
+                open = Some((fence, fence_indent, is_rust, example));
+            }
+            (None, None) => {}
+        }
+    }
+
+    examples
+}
+
+/// Strip rustdoc's `#` prefix from hidden example lines, which are compiled but not shown
+fn hide_marker(code: &str) -> &str {
+    let trimmed = code.trim_start();
+    if trimmed == "#" {
+        ""
+    } else if let Some(hidden) = trimmed.strip_prefix("# ") {
+        hidden
+    } else {
+        code
+    }
+}
+
+/// Whether a fence info string marks Rust code
+fn is_rust_fence(info: &str, untagged_is_rust: bool) -> bool {
+    const RUSTDOC_ATTRIBUTES: &[&str] = &[
+        "ignore",
+        "no_run",
+        "should_panic",
+        "compile_fail",
+        "edition2015",
+        "edition2018",
+        "edition2021",
+        "edition2024",
+    ];
+
+    let tokens: Vec<&str> = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect();
+    if tokens.contains(&"rust") {
+        return true;
+    }
+    untagged_is_rust
+        && tokens
+            .iter()
+            .all(|token| RUSTDOC_ATTRIBUTES.contains(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_from_rust() {
+        let source = r#"
+/// Parse a value
+///
+/// ```
+/// # use crate::parse;
+/// let value = parse("1").unwrap();
+/// ```
+///
+/// ```text
+/// not rust
+/// ```
+fn parse() {}
+
+//! ```no_run
+//! unimplemented!()
+//! ```
+"#;
+        let examples = extract_from_rust(source);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(
+            examples[0].code,
+            "use crate::parse;\nlet value = parse(\"1\").unwrap();\n"
+        );
+        assert_eq!(examples[0].origins, vec![(5, 6), (6, 4)]);
+        assert_eq!(examples[1].code, "unimplemented!()\n");
+
+        // Wrapped in fn main, line 2 of the source is the first example line
+        let (source, offset) = examples[1].as_source();
+        assert_eq!(source, "fn main() {\nunimplemented!()\n}\n");
+        assert_eq!(examples[1].original_position(2, 1, offset), Some((15, 5)));
+        assert_eq!(examples[1].original_position(1, 1, offset), None);
+    }
+
+    #[test]
+    fn test_extract_from_markdown() {
+        let markdown = "# Usage\n\n```rust\nlet x = todo!();\n```\n\n```\nplain\n```\n\n~~~rust,ignore\nfn main() {}\n~~~\n";
+        let examples = extract_from_markdown(markdown);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].code, "let x = todo!();\n");
+        assert_eq!(examples[0].origins, vec![(4, 0)]);
+        assert_eq!(examples[1].as_source(), ("fn main() {}\n".to_string(), 0));
+    }
+}
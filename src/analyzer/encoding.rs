@@ -0,0 +1,164 @@
+//! Source decoding for files that are not plain UTF-8
+//!
+//! Code Quality Principle: Graceful Degradation - Legacy encodings are analyzed, not rejected
+//! - Byte order marks are stripped and UTF-16 is transcoded
+//! - Invalid UTF-8 is decoded lossily and reported, so findings are never silently partial
+//! - Binary and malformed content is undecodable and surfaced to the caller
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use std::fs;
+use std::path::Path;
+
+/// How a source file was decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceEncoding {
+    /// Plain UTF-8 without a byte order mark
+    Utf8,
+    /// UTF-8 with a byte order mark, which is stripped
+    Utf8Bom,
+    /// UTF-16 little-endian, identified by its byte order mark
+    Utf16Le,
+    /// UTF-16 big-endian, identified by its byte order mark
+    Utf16Be,
+    /// Invalid UTF-8 with undecodable sequences replaced by U+FFFD
+    Lossy,
+}
+
+impl SourceEncoding {
+    /// Name shown in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf8Bom => "utf-8 (bom)",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Lossy => "lossy utf-8",
+        }
+    }
+
+    /// Whether the file is anything other than plain UTF-8
+    pub fn is_notable(&self) -> bool {
+        *self != Self::Utf8
+    }
+}
+
+/// Decoded file content with the encoding it was read as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    pub content: String,
+    pub encoding: SourceEncoding,
+}
+
+/// Decode raw file bytes
+///
+/// Invalid UTF-8 is replaced lossily when `lossy` is set. Content containing NUL bytes
+/// outside UTF-16 is treated as binary. Returns the reason when the bytes cannot be decoded.
+pub fn decode(bytes: &[u8], lossy: bool) -> Result<DecodedSource, String> {
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return decode_utf8(rest, lossy).map(|(content, encoding)| DecodedSource {
+            content,
+            encoding: match encoding {
+                SourceEncoding::Utf8 => SourceEncoding::Utf8Bom,
+                other => other,
+            },
+        });
+    }
+
+    if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        return decode_utf16(rest, u16::from_le_bytes, lossy, SourceEncoding::Utf16Le);
+    }
+
+    if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        return decode_utf16(rest, u16::from_be_bytes, lossy, SourceEncoding::Utf16Be);
+    }
+
+    if let Some(pos) = bytes.iter().position(|&b| b == 0) {
+        return Err(format!("binary content (NUL byte at offset {pos})"));
+    }
+
+    decode_utf8(bytes, lossy).map(|(content, encoding)| DecodedSource { content, encoding })
+}
+
+/// Read and decode a file, treating undecodable content as an analysis error
+pub fn read_source(file_path: &Path, lossy: bool) -> GuardianResult<DecodedSource> {
+    let bytes = fs::read(file_path).map_err(|e| GuardianError::io(file_path, e))?;
+
+    decode(&bytes, lossy).map_err(|reason| {
+        GuardianError::analysis(
+            file_path.display().to_string(),
+            format!("Failed to decode file: {reason}"),
+        )
+    })
+}
+
+fn decode_utf8(bytes: &[u8], lossy: bool) -> Result<(String, SourceEncoding), String> {
+    match std::str::from_utf8(bytes) {
+        Ok(content) => Ok((content.to_string(), SourceEncoding::Utf8)),
+        Err(_) if lossy => Ok((
+            String::from_utf8_lossy(bytes).into_owned(),
+            SourceEncoding::Lossy,
+        )),
+        Err(e) => Err(format!("invalid UTF-8 at byte {}", e.valid_up_to())),
+    }
+}
+
+fn decode_utf16(
+    bytes: &[u8],
+    to_unit: fn([u8; 2]) -> u16,
+    lossy: bool,
+    encoding: SourceEncoding,
+) -> Result<DecodedSource, String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(format!("truncated {} content", encoding.as_str()));
+    }
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| to_unit([pair[0], pair[1]]))
+        .collect();
+
+    match String::from_utf16(&units) {
+        Ok(content) => Ok(DecodedSource { content, encoding }),
+        Err(_) if lossy => Ok(DecodedSource {
+            content: String::from_utf16_lossy(&units),
+            encoding,
+        }),
+        Err(_) => Err(format!(
+            "unpaired surrogate in {} content",
+            encoding.as_str()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_encodings() {
+        let plain = decode(b"fn main() {}", false).expect("UTF-8 should decode");
+        assert_eq!(plain.encoding, SourceEncoding::Utf8);
+        assert!(!plain.encoding.is_notable());
+
+        let bom = decode(b"\xEF\xBB\xBFfn main() {}", false).expect("BOM should be stripped");
+        assert_eq!(bom.content, "fn main() {}");
+        assert_eq!(bom.encoding, SourceEncoding::Utf8Bom);
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("fn é() {}".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let decoded = decode(&utf16, false).expect("UTF-16 should decode");
+        assert_eq!(decoded.content, "fn é() {}");
+        assert_eq!(decoded.encoding, SourceEncoding::Utf16Le);
+
+        // Latin-1 "café" is invalid UTF-8
+        let latin1 = b"// caf\xE9\nfn main() {}";
+        assert!(decode(latin1, false).is_err());
+        let lossy = decode(latin1, true).expect("lossy decoding should succeed");
+        assert_eq!(lossy.encoding, SourceEncoding::Lossy);
+        assert!(lossy.content.contains("fn main() {}"));
+
+        assert!(decode(b"\x7fELF\0\0\0", true).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Macro expansion of cargo targets for analysis of the code macros generate
+//!
+//! Architecture: Infrastructure Adapter - The compiler expands macros, Guardian only reads the result
+//! - Expansion runs `cargo rustc -- -Zunpretty=expanded`, the mechanism behind `cargo expand`
+//! - Expansions are written under `target/guardian-expanded/` so findings point at a real file
+//! - Only the configured rule subset runs on expanded code, which is noisy for most rules
+
+use crate::analyzer::targets::CargoTarget;
+use crate::domain::violations::{GuardianError, GuardianResult};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory, relative to the workspace root, holding expanded sources
+pub const EXPANDED_DIR: &str = "target/guardian-expanded";
+
+/// Tag added to violations found in macro-expanded code
+pub const EXPANDED_TAG: &str = "macro-expanded";
+
+/// The `cargo rustc` arguments selecting a target, or `None` for targets cargo cannot
+/// compile on their own (build scripts)
+pub fn target_args(target: &CargoTarget) -> Option<Vec<String>> {
+    if target.is_lib() {
+        return Some(vec!["--lib".to_string()]);
+    }
+    let flag = target.kinds.iter().find_map(|kind| match kind.as_str() {
+        "bin" => Some("--bin"),
+        "test" => Some("--test"),
+        "bench" => Some("--bench"),
+        "example" => Some("--example"),
+        _ => None,
+    })?;
+    Some(vec![flag.to_string(), target.name.clone()])
+}
+
+/// File the expansion of a target is written to, relative to the workspace root
+pub fn expanded_path(target: &CargoTarget) -> PathBuf {
+    let kind = target.kinds.first().map_or("target", String::as_str);
+    Path::new(EXPANDED_DIR).join(format!("{}-{kind}-{}.rs", target.package, target.name))
+}
+
+/// Expand every macro of a target and return the expanded crate source
+///
+/// `-Zunpretty` is unstable, so `RUSTC_BOOTSTRAP` enables it on stable toolchains the
+/// same way `cargo expand` does. Returns `None` for targets that cannot be expanded.
+pub fn expand_target(workspace_dir: &Path, target: &CargoTarget) -> GuardianResult<Option<String>> {
+    let target_args = match target_args(target) {
+        Some(args) => args,
+        None => return Ok(None),
+    };
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let label = format!("{}:{}", target.package, target.name);
+
+    let output = Command::new(cargo)
+        .args(["rustc", "--quiet", "--profile=check", "--package"])
+        .arg(&target.package)
+        .args(&target_args)
+        .args(["--", "-Zunpretty=expanded"])
+        .env("RUSTC_BOOTSTRAP", "1")
+        .current_dir(workspace_dir)
+        .output()
+        .map_err(|e| GuardianError::analysis(&label, format!("Failed to run cargo rustc: {e}")))?;
+
+    if !output.status.success() {
+        return Err(GuardianError::analysis(
+            label,
+            format!(
+                "Macro expansion failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(kind: &str, name: &str) -> CargoTarget {
+        CargoTarget {
+            package: "app".to_string(),
+            name: name.to_string(),
+            kinds: vec![kind.to_string()],
+            src_path: PathBuf::from("src/lib.rs"),
+            test: true,
+        }
+    }
+
+    #[test]
+    fn test_target_args() {
+        assert_eq!(
+            target_args(&target("proc-macro", "derive")),
+            Some(vec!["--lib".to_string()])
+        );
+        assert_eq!(
+            target_args(&target("bin", "server")),
+            Some(vec!["--bin".to_string(), "server".to_string()])
+        );
+        assert_eq!(
+            target_args(&target("test", "api")),
+            Some(vec!["--test".to_string(), "api".to_string()])
+        );
+        assert_eq!(
+            target_args(&target("custom-build", "build-script-build")),
+            None
+        );
+
+        assert_eq!(
+            expanded_path(&target("bin", "server")),
+            Path::new("target/guardian-expanded/app-bin-server.rs")
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Application of the edits attached to violations
+//!
+//! Architecture: Command Pattern - Rules describe fixes as edits, and one place applies them
+//! - Edits are byte ranges of the file content with a replacement
+//! - Overlapping edits in one file are not combined; the first is applied, the rest skipped
+//! - Applied violations are removed from the report, so it shows what is left to do
+
+use crate::domain::violations::{Fix, GuardianError, GuardianResult, ValidationReport};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Outcome of applying the fixes of a report
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FixSummary {
+    /// Number of violations whose fix was applied
+    pub applied: usize,
+    /// Fixes left unapplied because they overlapped another fix or no longer fit the file
+    pub skipped: usize,
+    /// Files that were rewritten
+    pub files: Vec<PathBuf>,
+}
+
+/// Apply every fix in a report to the files on disk
+///
+/// Fixed violations are removed from the report.
+pub fn apply_fixes(report: &mut ValidationReport) -> GuardianResult<FixSummary> {
+    let mut by_file: BTreeMap<PathBuf, Vec<(usize, &Fix)>> = BTreeMap::new();
+    for (index, violation) in report.violations.iter().enumerate() {
+        if let Some(fix) = &violation.fix {
+            by_file
+                .entry(violation.file_path.clone())
+                .or_default()
+                .push((index, fix));
+        }
+    }
+
+    let mut summary = FixSummary::default();
+    let mut fixed = HashSet::new();
+    for (file_path, fixes) in by_file {
+        let content =
+            fs::read_to_string(&file_path).map_err(|e| GuardianError::io(&file_path, e))?;
+        // Offsets are into the decoded source, which has any byte order mark stripped
+        let (bom, source) = match content.strip_prefix('\u{feff}') {
+            Some(source) => ("\u{feff}", source),
+            None => ("", content.as_str()),
+        };
+        let (updated, applied) = apply_to_content(source, &fixes);
+        summary.skipped += fixes.len() - applied.len();
+        if applied.is_empty() {
+            continue;
+        }
+
+        fs::write(&file_path, format!("{bom}{updated}"))
+            .map_err(|e| GuardianError::io(&file_path, e))?;
+        summary.applied += applied.len();
+        summary.files.push(file_path);
+        fixed.extend(applied);
+    }
+
+    let mut index = 0;
+    report.retain_violations(|_| {
+        let keep = !fixed.contains(&index);
+        index += 1;
+        keep
+    });
+    Ok(summary)
+}
+
+/// Apply keyed fixes to content, returning the new content and the keys of applied fixes
+///
+/// Fixes are taken in order of their start offset; one overlapping an earlier fix, or not
+/// on character boundaries of the content, is skipped.
+pub fn apply_to_content<K: Copy>(content: &str, fixes: &[(K, &Fix)]) -> (String, Vec<K>) {
+    let mut ordered: Vec<&(K, &Fix)> = fixes.iter().collect();
+    ordered.sort_by_key(|(_, fix)| (fix.byte_range.start, fix.byte_range.end));
+
+    let mut output = String::with_capacity(content.len());
+    let mut applied = Vec::new();
+    let mut position = 0;
+    for (key, fix) in ordered {
+        let range = &fix.byte_range;
+        let fits = range.start >= position
+            && range.start <= range.end
+            && content.is_char_boundary(range.start)
+            && content.is_char_boundary(range.end);
+        if !fits {
+            continue;
+        }
+
+        output.push_str(&content[position..range.start]);
+        output.push_str(&fix.replacement);
+        position = range.end;
+        applied.push(*key);
+    }
+    output.push_str(&content[position..]);
+
+    (output, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_to_content() {
+        let insert = Fix::insert(0, "// header\n");
+        let replace = Fix::replace(3..6, "two");
+        let overlapping = Fix::replace(4..8, "x");
+        let out_of_bounds = Fix::replace(40..41, "x");
+
+        let (updated, applied) = apply_to_content(
+            "one 2 three",
+            &[
+                (0, &replace),
+                (1, &insert),
+                (2, &overlapping),
+                (3, &out_of_bounds),
+            ],
+        );
+        assert_eq!(updated, "// header\nonetwothree");
+        assert_eq!(applied, vec![1, 0]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Detection of generated source files
+//!
+//! Code Quality Principle: Explicit Exclusion - Generated code is skipped, never hidden
+//! - Only the file header is inspected, where code generators leave their marker
+//! - The matching marker is returned so reports can say why a file was skipped
+
+/// Markers code generators commonly place in file headers
+pub const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "DO NOT EDIT",
+    "Generated by the protocol buffer compiler",
+    "This file is generated by rust-protobuf",
+    "automatically generated by rust-bindgen",
+    "Code generated by",
+];
+
+/// Number of leading lines searched for a marker
+const HEADER_LINES: usize = 20;
+
+/// Find the generated-code marker in a file's header comments, if any
+///
+/// The header ends at the first line of code; blank lines and attributes may come before or
+/// between its comments, but only comments are searched, so attribute arguments and string
+/// literals never mark a file as generated. Built-in markers are checked first, then
+/// `extra_markers` from configuration.
+pub fn generated_marker<'a>(content: &str, extra_markers: &'a [String]) -> Option<&'a str> {
+    let mut header = Vec::new();
+    let mut in_block_comment = false;
+    for line in content.lines().take(HEADER_LINES).map(str::trim_start) {
+        if in_block_comment || line.starts_with("/*") {
+            in_block_comment = !line.contains("*/");
+            header.push(line);
+        } else if line.starts_with("//") {
+            header.push(line);
+        } else if !line.is_empty() && !line.starts_with('#') {
+            break;
+        }
+    }
+
+    GENERATED_MARKERS
+        .iter()
+        .copied()
+        .chain(extra_markers.iter().map(String::as_str))
+        .find(|marker| header.iter().any(|line| line.contains(marker)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+    use crate::config::GuardianConfig;
+    use std::fs;
+
+    #[test]
+    fn test_header_markers() {
+        let none: &[String] = &[];
+        assert_eq!(
+            generated_marker("// @generated by prost\npub struct Msg;\n", none),
+            Some("@generated")
+        );
+        assert_eq!(
+            generated_marker("//! Bindings\n//!\n//! DO NOT EDIT\npub fn f() {}\n", none),
+            Some("DO NOT EDIT")
+        );
+        assert_eq!(
+            generated_marker(
+                "#![allow(unknown_lints)]\n\n// Generated by the protocol buffer compiler.\n",
+                none
+            ),
+            Some("Generated by the protocol buffer compiler")
+        );
+        assert_eq!(
+            generated_marker(
+                "/*\n * automatically generated by rust-bindgen 0.69\n */\n",
+                none
+            ),
+            Some("automatically generated by rust-bindgen")
+        );
+        let extra = ["generated by build.rs".to_string()];
+        assert_eq!(
+            generated_marker("// generated by build.rs\n", &extra),
+            Some("generated by build.rs")
+        );
+        assert_eq!(
+            generated_marker("// Hand written\nfn main() {}\n", none),
+            None
+        );
+    }
+
+    #[test]
+    fn test_markers_outside_header_comments_ignored() {
+        let none: &[String] = &[];
+        // Past the header lines
+        let late = format!("{}// @generated\n", "//\n".repeat(HEADER_LINES));
+        assert_eq!(generated_marker(&late, none), None);
+
+        // In string literals, including lines that look like comments
+        assert_eq!(
+            generated_marker("const NOTE: &str = \"DO NOT EDIT\";\n", none),
+            None
+        );
+        assert_eq!(
+            generated_marker("const TEMPLATE: &str = \"\n// @generated\n\";\n", none),
+            None
+        );
+
+        // In attributes
+        assert_eq!(
+            generated_marker("#![doc = \"Config file, DO NOT EDIT by hand\"]\n", none),
+            None
+        );
+        assert_eq!(
+            generated_marker(
+                "#[deprecated(note = \"Code generated by v1 is gone\")]\npub fn old() {}\n",
+                none
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generated_files_skipped_unless_disabled() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).expect("src dir should be creatable");
+        fs::write(root.join("src/lib.rs"), "pub fn lib() {}\n").expect("lib.rs should be writable");
+        fs::write(
+            root.join("src/proto.rs"),
+            "// @generated by protoc-gen-rust\npub fn stub() { todo!() }\n",
+        )
+        .expect("proto.rs should be writable");
+
+        let report = Analyzer::with_defaults()
+            .expect("default analyzer should build")
+            .analyze_directory(root, &AnalysisOptions::default())
+            .expect("analysis should succeed");
+        assert_eq!(report.summary.total_files, 1);
+        assert_eq!(
+            report.summary.skipped_generated,
+            [root.join("src/proto.rs")]
+        );
+        assert!(!report
+            .violations
+            .iter()
+            .any(|violation| violation.file_path.ends_with("proto.rs")));
+
+        let mut config = GuardianConfig::default();
+        config.paths.skip_generated = false;
+        let report = Analyzer::new(config)
+            .expect("analyzer should build")
+            .analyze_directory(root, &AnalysisOptions::default())
+            .expect("analysis should succeed");
+        assert_eq!(report.summary.total_files, 2);
+        assert!(report.summary.skipped_generated.is_empty());
+        assert!(report
+            .violations
+            .iter()
+            .any(|violation| violation.file_path.ends_with("proto.rs")));
+    }
+}
@@ -0,0 +1,321 @@
+//! License header enforcement
+//!
+//! Architecture: Specification Pattern - A header template states what every file must begin with
+//! - Templates use `{year}` and `{owner}` placeholders; the year may be a `2019-2024` range
+//! - Matching ignores comment markers and whitespace, so `//`, `/* */` and `#` headers all count
+//! - Violations carry a fix that inserts the header, or extends an outdated year range
+
+use crate::config::LicenseHeaderConfig;
+use crate::domain::violations::{Fix, GuardianError, GuardianResult, Severity, Violation};
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+use std::path::Path;
+
+/// Rule id reported for missing or outdated license headers
+pub const LICENSE_HEADER_RULE: &str = "license_header";
+
+/// Matches a year or year range in header text
+const YEAR_PATTERN: &str = r"(\d{4})(?:\s*[-–]\s*(\d{4}))?";
+
+/// A compiled license header requirement
+#[derive(Debug)]
+pub struct LicenseHeader {
+    /// Matches the header within normalized comment text
+    matcher: Regex,
+    template: String,
+    owner: String,
+    start_year: Option<i32>,
+    require_current_year: bool,
+    comment_prefix: String,
+    scope: Vec<glob::Pattern>,
+    severity: Severity,
+}
+
+impl LicenseHeader {
+    /// Compile the configured header, or `None` when the check is disabled
+    pub fn from_config(config: &LicenseHeaderConfig) -> GuardianResult<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let invalid =
+            |message: String| GuardianError::rule_compilation(LICENSE_HEADER_RULE, message);
+
+        if config.template.trim().is_empty() {
+            return Err(invalid(
+                "license_header.template cannot be empty".to_string(),
+            ));
+        }
+        if config.template.matches("{year}").count() > 1 {
+            return Err(invalid(
+                "license_header.template can use {year} only once".to_string(),
+            ));
+        }
+        if config.template.contains("{owner}") && config.owner.trim().is_empty() {
+            return Err(invalid(
+                "license_header.owner is required when the template uses {owner}".to_string(),
+            ));
+        }
+
+        let mut pattern = String::new();
+        for (index, part) in normalize(&config.template).split("{year}").enumerate() {
+            if index > 0 {
+                pattern.push_str(YEAR_PATTERN);
+            }
+            for (index, literal) in part.split("{owner}").enumerate() {
+                if index > 0 {
+                    pattern.push_str(&flexible_whitespace(&normalize(&config.owner)));
+                }
+                pattern.push_str(&flexible_whitespace(literal));
+            }
+        }
+        let matcher = RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| invalid(e.to_string()))?;
+
+        let scope = config
+            .applies_to
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern)
+                    .map_err(|e| invalid(format!("invalid applies_to pattern '{pattern}': {e}")))
+            })
+            .collect::<GuardianResult<Vec<_>>>()?;
+
+        Ok(Some(Self {
+            matcher,
+            template: config.template.clone(),
+            owner: config.owner.clone(),
+            start_year: config.start_year,
+            require_current_year: config.require_current_year,
+            comment_prefix: config.comment_prefix.clone(),
+            scope,
+            severity: config.severity,
+        }))
+    }
+
+    /// Whether a file must carry the header
+    pub fn applies_to(&self, file_path: &Path) -> bool {
+        self.scope.is_empty()
+            || self
+                .scope
+                .iter()
+                .any(|pattern| pattern.matches_path(file_path))
+    }
+
+    /// Check a file's header, returning a violation with a fix when it is missing or outdated
+    pub fn check(&self, file_path: &Path, content: &str, current_year: i32) -> Option<Violation> {
+        if !self.applies_to(file_path) {
+            return None;
+        }
+
+        let block = leading_comment_block(content);
+        let text = normalize(&content[block.clone()]);
+        let Some(captures) = self.matcher.captures(&text) else {
+            let year = match self.start_year {
+                Some(start) if start < current_year => format!("{start}-{current_year}"),
+                _ => current_year.to_string(),
+            };
+            let header = self.render(&year);
+            return Some(
+                self.violation(file_path, "File is missing the license header")
+                    .with_position(1, 1)
+                    .with_suggestion(format!("Add the header:\n{header}"))
+                    .with_fix(Fix::insert(block.start, format!("{header}\n"))),
+            );
+        };
+
+        if !self.require_current_year {
+            return None;
+        }
+        let start = captures.get(1)?.as_str();
+        let end = captures.get(2).unwrap_or(captures.get(1)?).as_str();
+        if end.parse::<i32>().ok()? >= current_year {
+            return None;
+        }
+
+        // Rewrite the year where it appears in the original comment, keeping its style
+        let years = Regex::new(&format!(r"\b{start}(?:\s*[-–]\s*{end})?\b")).ok()?;
+        let found = years.find(&content[block.clone()])?;
+        let range = block.start + found.start()..block.start + found.end();
+        let line = content[..range.start].matches('\n').count() as u32 + 1;
+        Some(
+            self.violation(
+                file_path,
+                format!("License header year {} is out of date", found.as_str()),
+            )
+            .with_position(line, 1)
+            .with_byte_range(range.clone())
+            .with_suggestion(format!("Update the year to {start}-{current_year}"))
+            .with_fix(Fix::replace(range, format!("{start}-{current_year}"))),
+        )
+    }
+
+    /// The header as comment lines, each ending in a newline
+    pub fn render(&self, year: &str) -> String {
+        self.template
+            .trim_end()
+            .lines()
+            .map(|line| {
+                let line = line.replace("{year}", year).replace("{owner}", &self.owner);
+                format!("{}\n", format!("{}{line}", self.comment_prefix).trim_end())
+            })
+            .collect()
+    }
+
+    fn violation(&self, file_path: &Path, message: impl Into<String>) -> Violation {
+        Violation::new(
+            LICENSE_HEADER_RULE,
+            self.severity,
+            file_path.to_path_buf(),
+            message,
+        )
+    }
+}
+
+/// Byte range of the comment lines a file starts with, after any shebang line
+///
+/// The range is empty, at the insertion point for a header, when the file starts with code.
+fn leading_comment_block(content: &str) -> Range<usize> {
+    let mut start = 0;
+    if content.starts_with("#!") && !content.starts_with("#![") {
+        start = content.find('\n').map_or(content.len(), |end| end + 1);
+    }
+
+    let mut end = start;
+    let mut in_block = false;
+    for line in content[start..].split_inclusive('\n') {
+        let trimmed = line.trim();
+        let is_comment = in_block
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || (trimmed.starts_with('#')
+                && !trimmed.starts_with("#[")
+                && !trimmed.starts_with("#!"));
+        if !is_comment {
+            break;
+        }
+        if trimmed.starts_with("/*") || in_block {
+            in_block = !trimmed.contains("*/");
+        }
+        end += line.len();
+    }
+    start..end
+}
+
+/// Header text with comment markers removed and whitespace collapsed
+fn normalize(text: &str) -> String {
+    let words: Vec<&str> = text
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .trim_start_matches(['/', '!', '*', '#'])
+        })
+        .flat_map(str::split_whitespace)
+        .collect();
+    words.join(" ")
+}
+
+/// Escape literal text for a regex, letting any run of whitespace match its spaces
+fn flexible_whitespace(text: &str) -> String {
+    text.split(' ')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(require_current_year: bool) -> LicenseHeader {
+        let config = LicenseHeaderConfig {
+            enabled: true,
+            template: "Copyright {year} {owner}\nSPDX-License-Identifier: MIT\n".to_string(),
+            owner: "Acme Corp".to_string(),
+            start_year: Some(2020),
+            require_current_year,
+            severity: Severity::Warning,
+            ..Default::default()
+        };
+        LicenseHeader::from_config(&config)
+            .expect("header should compile")
+            .expect("header should be enabled")
+    }
+
+    #[test]
+    fn test_missing_header_is_inserted() {
+        let header = header(false);
+        let content = "#!/usr/bin/env run-cargo-script\nfn main() {}\n";
+        let violation = header
+            .check(Path::new("src/main.rs"), content, 2024)
+            .expect("header should be missing");
+        let fix = violation.fix.expect("violation should carry a fix");
+
+        let (fixed, _) = crate::analyzer::fix::apply_to_content(content, &[((), &fix)]);
+        assert_eq!(
+            fixed,
+            "#!/usr/bin/env run-cargo-script\n// Copyright 2020-2024 Acme Corp\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n"
+        );
+        assert!(header
+            .check(Path::new("src/main.rs"), &fixed, 2024)
+            .is_none());
+    }
+
+    #[test]
+    fn test_tolerant_matching() {
+        let header = header(false);
+        for content in [
+            "// Copyright 2021 Acme Corp\n// SPDX-License-Identifier: MIT\nfn f() {}\n",
+            "/*\n * Copyright   2019 - 2023 ACME CORP\n * SPDX-License-Identifier: MIT\n */\nfn f() {}\n",
+            "//! copyright 2022 acme corp spdx-license-identifier: mit\n",
+        ] {
+            assert!(header.check(Path::new("src/lib.rs"), content, 2024).is_none());
+        }
+        assert!(header
+            .check(
+                Path::new("src/lib.rs"),
+                "// Copyright 2021 Other Inc\n",
+                2024
+            )
+            .is_some());
+        assert!(header
+            .check(Path::new("README.md"), "# Readme\n", 2024)
+            .is_none());
+    }
+
+    #[test]
+    fn test_outdated_year_range() {
+        let header = header(true);
+        let content = "/* Copyright 2019-2022 Acme Corp\n * SPDX-License-Identifier: MIT */\n";
+        let violation = header
+            .check(Path::new("src/lib.rs"), content, 2024)
+            .expect("year should be outdated");
+        assert_eq!(
+            violation.message,
+            "License header year 2019-2022 is out of date"
+        );
+        let fix = violation.fix.expect("violation should carry a fix");
+
+        let (fixed, _) = crate::analyzer::fix::apply_to_content(content, &[((), &fix)]);
+        assert!(fixed.starts_with("/* Copyright 2019-2024 Acme Corp\n"));
+        assert!(header
+            .check(Path::new("src/lib.rs"), &fixed, 2024)
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        let config = LicenseHeaderConfig {
+            enabled: true,
+            template: "Copyright {year} {owner}".to_string(),
+            ..Default::default()
+        };
+        assert!(LicenseHeader::from_config(&config).is_err());
+        assert!(LicenseHeader::from_config(&LicenseHeaderConfig::default())
+            .expect("disabled config is valid")
+            .is_none());
+    }
+}
@@ -0,0 +1,184 @@
+//! Memory accounting for analysis runs
+//!
+//! Architecture: Infrastructure Layer - Memory figures are observed, never acted on by rules
+//! - Peak RSS is read from the operating system where it is available
+//! - Allocation counts need [`TrackingAllocator`] installed as the global allocator, which the
+//!   binary does when built with the `alloc-stats` feature
+//! - The per-file bound is checked against an estimate taken before the file is read
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Rough size of a parsed syntax tree per byte of Rust source
+///
+/// Token spans, boxed expressions and identifiers make syn trees several times larger
+/// than the text they were parsed from.
+pub const AST_BYTES_PER_SOURCE_BYTE: u64 = 24;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator wrapper counting allocations and tracking peak heap usage
+///
+/// Install it in a binary with `#[global_allocator]`; the counters stay at zero otherwise.
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let current = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_ALLOCATED.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Memory used by the process so far
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Peak resident set size, where the platform reports it
+    pub peak_rss_bytes: Option<u64>,
+    /// Number of heap allocations, when [`TrackingAllocator`] is installed
+    pub allocations: Option<u64>,
+    /// Peak live heap bytes, when [`TrackingAllocator`] is installed
+    pub peak_heap_bytes: Option<u64>,
+}
+
+impl MemoryUsage {
+    /// Read the current figures
+    pub fn current() -> Self {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        // Any process has allocated by the time this runs, so zero means no tracking allocator
+        let tracked = allocations > 0;
+
+        Self {
+            peak_rss_bytes: peak_rss_bytes(),
+            allocations: tracked.then_some(allocations),
+            peak_heap_bytes: tracked.then(|| PEAK_ALLOCATED.load(Ordering::Relaxed) as u64),
+        }
+    }
+
+    /// One-line summary for `--timings`
+    pub fn format_display(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(rss) = self.peak_rss_bytes {
+            parts.push(format!("peak RSS {}", format_bytes(rss)));
+        }
+        match (self.allocations, self.peak_heap_bytes) {
+            (Some(allocations), Some(heap)) => {
+                parts.push(format!("peak heap {}", format_bytes(heap)));
+                parts.push(format!("{allocations} allocations"));
+            }
+            _ => parts.push("allocation counts need the 'alloc-stats' feature".to_string()),
+        }
+
+        format!("Memory: {}", parts.join(", "))
+    }
+}
+
+/// Estimated peak memory needed to analyze a file of the given size
+///
+/// Counts the raw bytes, the decoded text and, for Rust sources, the syntax tree.
+pub fn estimated_file_memory(file_size: u64, is_rust: bool) -> u64 {
+    let ast = if is_rust {
+        file_size.saturating_mul(AST_BYTES_PER_SOURCE_BYTE)
+    } else {
+        0
+    };
+    file_size.saturating_mul(2).saturating_add(ast)
+}
+
+/// Peak resident set size of this process
+#[cfg(target_os = "linux")]
+fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss_bytes() -> Option<u64> {
+    None
+}
+
+/// Format a byte count in binary units
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimates_and_formatting() {
+        assert_eq!(estimated_file_memory(1000, false), 2000);
+        assert_eq!(
+            estimated_file_memory(1000, true),
+            2000 + 1000 * AST_BYTES_PER_SOURCE_BYTE
+        );
+        assert_eq!(estimated_file_memory(u64::MAX, true), u64::MAX);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5 MiB");
+
+        let usage = MemoryUsage {
+            peak_rss_bytes: Some(2048),
+            allocations: None,
+            peak_heap_bytes: None,
+        };
+        assert_eq!(
+            usage.format_display(),
+            "Memory: peak RSS 2.0 KiB, allocation counts need the 'alloc-stats' feature"
+        );
+    }
+}
@@ -5,17 +5,43 @@
 //! - Provides clean interface for validating single files or directory trees
 //! - Handles parallel processing and error recovery gracefully
 
+pub mod benchmark;
+pub mod cfg;
+pub mod doc_examples;
+pub mod encoding;
+pub mod expand;
+pub mod fix;
+pub mod generated;
+pub mod license;
+pub mod memory;
+pub mod receive;
+pub mod repl;
+pub mod revision;
 pub mod rust;
+pub mod scope;
+pub mod targets;
 
+use crate::analyzer::cfg::CfgScoping;
+use crate::analyzer::encoding::{DecodedSource, SourceEncoding};
+use crate::analyzer::license::LicenseHeader;
+use crate::analyzer::revision::Revision;
 use crate::analyzer::rust::RustAnalyzer;
-use crate::config::GuardianConfig;
+use crate::analyzer::targets::{CargoTargets, TargetSelection};
+use crate::config::{GuardianConfig, RULE_PACK_VERSION};
 use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport, Violation};
-use crate::patterns::{PathFilter, PatternEngine};
+use crate::patterns::{
+    CompiledRules, FileContext, PathExplanation, PathFilter, PatternEngine, Rule, RuleInfo,
+    SyntaxTreeCache,
+};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Main analyzer that orchestrates the entire validation process
 pub struct Analyzer {
@@ -27,6 +53,119 @@ pub struct Analyzer {
     path_filter: PathFilter,
     /// Rust-specific analyzer
     rust_analyzer: RustAnalyzer,
+    /// Cfg-scoped rules and build matrix
+    cfg_scoping: CfgScoping,
+    /// License header files must start with, when enforced
+    license_header: Option<LicenseHeader>,
+    /// Syntax trees kept from earlier runs, in long-running modes
+    syntax_trees: Option<SyntaxTreeCache>,
+}
+
+/// Result of analyzing one file
+enum FileOutcome {
+    /// The file was analyzed, producing these violations
+    Analyzed {
+        violations: Vec<Violation>,
+        encoding: SourceEncoding,
+    },
+    /// The file was filtered out by path rules
+    Filtered,
+    /// The file carries a generated-code marker and was skipped
+    Generated,
+    /// The file could not be decoded and was skipped
+    Undecodable,
+    /// Analyzing the file would exceed the per-file memory bound, so it was skipped
+    Oversized,
+}
+
+impl FileOutcome {
+    /// Short name used in progress logs
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Analyzed { .. } => "analyzed",
+            Self::Filtered => "filtered",
+            Self::Generated => "generated",
+            Self::Undecodable => "undecodable",
+            Self::Oversized => "oversized",
+        }
+    }
+
+    /// Pass an analyzed file's violations to a streaming callback
+    fn report_to(&self, file_path: &Path, on_file: &(dyn Fn(&Path, &[Violation]) + Sync)) {
+        if let Self::Analyzed { violations, .. } = self {
+            on_file(file_path, violations);
+        }
+    }
+}
+
+/// Outcomes of analyzing a set of files
+#[derive(Default)]
+struct FileResults {
+    violations: Vec<Violation>,
+    generated: Vec<PathBuf>,
+    undecodable: Vec<PathBuf>,
+    /// Files decoded as something other than plain UTF-8
+    encodings: Vec<(PathBuf, SourceEncoding)>,
+    /// Files over the per-file memory bound
+    oversized: Vec<PathBuf>,
+    /// Files not started before the time budget ran out
+    unanalyzed: Vec<PathBuf>,
+}
+
+impl FileResults {
+    fn record(&mut self, file_path: &Path, outcome: FileOutcome) {
+        match outcome {
+            FileOutcome::Analyzed {
+                violations,
+                encoding,
+            } => {
+                self.violations.extend(violations);
+                if encoding.is_notable() {
+                    self.encodings.push((file_path.to_path_buf(), encoding));
+                }
+            }
+            FileOutcome::Filtered => {}
+            FileOutcome::Generated => self.generated.push(file_path.to_path_buf()),
+            FileOutcome::Undecodable => self.undecodable.push(file_path.to_path_buf()),
+            FileOutcome::Oversized => self.oversized.push(file_path.to_path_buf()),
+        }
+    }
+
+    /// Files that were not analyzed
+    fn skipped(&self) -> impl Iterator<Item = &PathBuf> {
+        self.generated
+            .iter()
+            .chain(&self.undecodable)
+            .chain(&self.oversized)
+            .chain(&self.unanalyzed)
+    }
+}
+
+/// Why a file is or is not analyzed, as reported by `rust-guardian why-ignored`
+#[derive(Debug, Clone)]
+pub struct FileExplanation {
+    /// Path filtering steps
+    pub path: PathExplanation,
+    /// Generated-code marker that causes the file to be skipped
+    pub generated_marker: Option<String>,
+    /// Rules that never apply to the file, with the reason
+    pub excluded_rules: Vec<(String, String)>,
+}
+
+impl FileExplanation {
+    /// Whether the file is analyzed at all
+    pub fn is_analyzed(&self) -> bool {
+        self.path.analyzed && self.generated_marker.is_none()
+    }
+}
+
+/// Files selected for analysis, as listed by `rust-guardian files`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnalysisSet {
+    /// Files that would be analyzed, in discovery order
+    pub files: Vec<PathBuf>,
+    /// Files that pass filtering but are skipped as generated code
+    pub skipped_generated: Vec<PathBuf>,
 }
 
 /// Options for customizing analysis behavior
@@ -42,6 +181,14 @@ pub struct AnalysisOptions {
     pub exclude_patterns: Vec<String>,
     /// Whether to ignore .guardianignore files
     pub ignore_ignore_files: bool,
+    /// Analyze the most recently modified files first
+    pub recent_first: bool,
+    /// Time budget for the whole analysis; files not started in time are left unanalyzed
+    pub max_duration: Option<Duration>,
+    /// Also analyze the macro-expanded code of these cargo targets
+    pub expand_macros: Option<TargetSelection>,
+    /// Record how many files each rule examined and matched, and how long it took
+    pub rule_usage: bool,
 }
 
 impl Default for AnalysisOptions {
@@ -52,6 +199,10 @@ impl Default for AnalysisOptions {
             fail_fast: false,
             exclude_patterns: Vec::new(),
             ignore_ignore_files: false,
+            recent_first: true,
+            max_duration: None,
+            expand_macros: None,
+            rule_usage: false,
         }
     }
 }
@@ -59,29 +210,28 @@ impl Default for AnalysisOptions {
 impl Analyzer {
     /// Create a new analyzer with the given configuration
     pub fn new(config: GuardianConfig) -> GuardianResult<Self> {
-        let mut pattern_engine = PatternEngine::new();
-
-        // Load all enabled rules into the pattern engine
-        for (category_name, category) in &config.patterns {
-            if !category.enabled {
-                continue;
-            }
+        let rules = PatternEngine::compile(&config)?;
+        Self::with_compiled_rules(config, rules)
+    }
 
-            for rule in &category.rules {
-                if !rule.enabled {
-                    continue;
-                }
+    /// Create an analyzer running rules already compiled from `config`
+    ///
+    /// Analyzers built from one [`CompiledRules`] set share its compiled patterns, while each
+    /// keeps its own rule usage statistics. Fails if the rules were compiled from a different
+    /// configuration, since reports and caches would then describe rules that never ran.
+    pub fn with_compiled_rules(
+        config: GuardianConfig,
+        rules: Arc<CompiledRules>,
+    ) -> GuardianResult<Self> {
+        if rules.config_fingerprint() != Some(config.fingerprint().as_str()) {
+            return Err(GuardianError::config(
+                "Compiled rules were not compiled from this configuration",
+            ));
+        }
+        let pattern_engine = PatternEngine::with_rules(rules);
 
-                let effective_severity = config.effective_severity(category, rule);
-                pattern_engine
-                    .add_rule(rule, effective_severity)
-                    .map_err(|e| {
-                        GuardianError::config(format!(
-                            "Failed to add rule '{}' in category '{}': {}",
-                            rule.id, category_name, e
-                        ))
-                    })?;
-            }
+        for deprecation in config.deprecated_rules() {
+            tracing::warn!("{}", deprecation.notice());
         }
 
         // Create path filter
@@ -91,81 +241,524 @@ impl Analyzer {
             config.paths.ignore_file.clone()
         };
 
-        let path_filter = PathFilter::new(config.paths.patterns.clone(), ignore_file)
-            .map_err(|e| GuardianError::config(format!("Failed to create path filter: {e}")))?;
+        let mut path_filter = PathFilter::new(config.paths.patterns.clone(), ignore_file)
+            .map_err(|e| GuardianError::config(format!("Failed to create path filter: {e}")))?
+            .with_gitignore(config.paths.use_gitignore)
+            .with_symlinks(config.paths.follow_symlinks)
+            .with_deduplication(config.paths.dedupe_files);
+        if let Some(root) = &config.paths.root {
+            path_filter = path_filter.with_root(root.clone())?;
+        }
+
+        let cfg_scoping = CfgScoping::from_config(&config)?;
+        let license_header = LicenseHeader::from_config(&config.license_header)?;
 
         Ok(Self {
             config,
             pattern_engine,
             path_filter,
             rust_analyzer: RustAnalyzer::new(),
+            cfg_scoping,
+            license_header,
+            syntax_trees: None,
         })
     }
 
+    /// Reuse the syntax trees of files whose content is unchanged since an earlier run
+    ///
+    /// Meant for processes analyzing repeatedly, such as watch mode: a file re-analyzed
+    /// because rules changed is not parsed again. `None` parses every file anew.
+    pub fn set_syntax_tree_cache(&mut self, syntax_trees: Option<SyntaxTreeCache>) {
+        self.syntax_trees = syntax_trees;
+    }
+
     /// Create an analyzer with default configuration
     pub fn with_defaults() -> GuardianResult<Self> {
         Self::new(GuardianConfig::default())
     }
 
     /// Analyze a single file and return violations
+    ///
+    /// Generated files are skipped when `paths.skip_generated` is enabled, and undecodable
+    /// files when `paths.skip_undecodable` is enabled.
     pub fn analyze_file<P: AsRef<Path>>(&self, file_path: P) -> GuardianResult<Vec<Violation>> {
-        let file_path = file_path.as_ref();
+        match self.analyze_file_outcome(file_path.as_ref())? {
+            FileOutcome::Analyzed { violations, .. } => Ok(violations),
+            FileOutcome::Filtered
+            | FileOutcome::Generated
+            | FileOutcome::Undecodable
+            | FileOutcome::Oversized => Ok(Vec::new()),
+        }
+    }
+
+    /// Analyze a single file, reporting why it was skipped if it was
+    fn analyze_file_outcome(&self, file_path: &Path) -> GuardianResult<FileOutcome> {
+        let _span = tracing::info_span!("guardian.file", path = %file_path.display()).entered();
+        let start_time = Instant::now();
+
+        let outcome = self.analyze_file_contents(file_path, None);
+        if let Ok(outcome) = &outcome {
+            tracing::debug!(
+                phase = "file",
+                file = %file_path.display(),
+                outcome = outcome.as_str(),
+                duration_ms = start_time.elapsed().as_millis() as u64,
+                "Analyzed file"
+            );
+        }
+        outcome
+    }
+
+    /// Re-run only the given configured rules on a file
+    ///
+    /// Used to refresh cached results when only some rule definitions changed. The Rust
+    /// analyzer and procedural rules are not run.
+    pub fn analyze_file_rules<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        rule_ids: &HashSet<String>,
+    ) -> GuardianResult<Vec<Violation>> {
+        match self.analyze_file_contents(file_path.as_ref(), Some(rule_ids))? {
+            FileOutcome::Analyzed { violations, .. } => Ok(violations),
+            FileOutcome::Filtered
+            | FileOutcome::Generated
+            | FileOutcome::Undecodable
+            | FileOutcome::Oversized => Ok(Vec::new()),
+        }
+    }
 
+    /// Read, decode and match a single file, optionally with only some configured rules
+    fn analyze_file_contents(
+        &self,
+        file_path: &Path,
+        rule_ids: Option<&HashSet<String>>,
+    ) -> GuardianResult<FileOutcome> {
         // Check if file should be analyzed
         if !self.path_filter.should_analyze(file_path)? {
+            return Ok(FileOutcome::Filtered);
+        }
+
+        // Skip files whose content and syntax tree would not fit the memory bound, before
+        // reading them
+        if self.config.paths.max_file_memory_mb.is_some() {
+            let size = fs::metadata(file_path)
+                .map_err(|e| GuardianError::io(file_path, e))?
+                .len();
+            if self.is_oversized(file_path, size) {
+                return Ok(FileOutcome::Oversized);
+            }
+        }
+
+        // Read and decode file content
+        let bytes = fs::read(file_path).map_err(|e| GuardianError::io(file_path, e))?;
+        self.analyze_bytes(file_path, &bytes, rule_ids)
+    }
+
+    /// Whether a file of this size would exceed the per-file memory bound
+    fn is_oversized(&self, file_path: &Path, size: u64) -> bool {
+        let Some(limit_mb) = self.config.paths.max_file_memory_mb else {
+            return false;
+        };
+        let is_rust = file_path.extension().and_then(|ext| ext.to_str()) == Some("rs");
+        let estimate = memory::estimated_file_memory(size, is_rust);
+        if estimate > limit_mb.saturating_mul(1024 * 1024) {
+            tracing::debug!(
+                "Skipping oversized file {} (~{} MiB estimated, limit {} MiB)",
+                file_path.display(),
+                estimate / (1024 * 1024),
+                limit_mb
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Decode and match the raw content of a file
+    fn analyze_bytes(
+        &self,
+        file_path: &Path,
+        bytes: &[u8],
+        rule_ids: Option<&HashSet<String>>,
+    ) -> GuardianResult<FileOutcome> {
+        let DecodedSource { content, encoding } =
+            match encoding::decode(bytes, self.config.paths.lossy_decoding) {
+                Ok(source) => source,
+                Err(reason) if self.config.paths.skip_undecodable => {
+                    tracing::debug!(
+                        "Skipping undecodable file {}: {}",
+                        file_path.display(),
+                        reason
+                    );
+                    return Ok(FileOutcome::Undecodable);
+                }
+                Err(reason) => {
+                    return Err(GuardianError::analysis(
+                        file_path.display().to_string(),
+                        format!("Failed to decode file: {reason}"),
+                    ));
+                }
+            };
+
+        if self.config.paths.skip_generated {
+            if let Some(marker) =
+                generated::generated_marker(&content, &self.config.paths.generated_markers)
+            {
+                tracing::debug!(
+                    "Skipping generated file {} (marker '{}')",
+                    file_path.display(),
+                    marker
+                );
+                return Ok(FileOutcome::Generated);
+            }
+        }
+
+        Ok(FileOutcome::Analyzed {
+            violations: self.analyze_source(file_path, &content, rule_ids)?,
+            encoding,
+        })
+    }
+
+    /// Analyze source code held in memory as if it were the file at `file_path`
+    ///
+    /// Nothing is read from disk: the path only scopes rules and labels violations, and path
+    /// filters are not applied. Generated sources are still skipped when
+    /// `paths.skip_generated` is enabled. This is the entry point for embedders without a
+    /// filesystem, such as the WebAssembly build.
+    pub fn analyze_content<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+    ) -> GuardianResult<Vec<Violation>> {
+        if self.config.paths.skip_generated
+            && generated::generated_marker(content, &self.config.paths.generated_markers).is_some()
+        {
             return Ok(Vec::new());
         }
+        self.analyze_source(file_path.as_ref(), content, None)
+    }
+
+    /// Analyze the files of a git revision, read from the object store without a checkout
+    ///
+    /// `paths` select files and directories of the revision as described for
+    /// [`Revision::files`]; an empty selection covers the whole tree. Path filters,
+    /// exclusions, the time budget and the skipping of generated, undecodable and oversized
+    /// files apply as for files on disk. Cross-file rules and macro expansion need a worktree
+    /// and do not run.
+    pub fn analyze_revision<P: AsRef<Path>>(
+        &self,
+        revision: &Revision,
+        paths: &[P],
+        options: &AnalysisOptions,
+    ) -> GuardianResult<ValidationReport> {
+        let start_time = Instant::now();
+        self.pattern_engine.reset_rule_usage();
+        let _span =
+            tracing::info_span!("guardian.analyze_revision", commit = revision.commit()).entered();
+
+        let mut path_filter = self.path_filter.clone();
+        for pattern in &options.exclude_patterns {
+            path_filter.add_pattern(pattern.clone())?;
+        }
+        let mut files = Vec::new();
+        for file in revision.files(paths)? {
+            if path_filter.should_analyze(&file.path)? {
+                files.push(file);
+            }
+        }
+        if let Some(max_files) = options.max_files {
+            files.truncate(max_files);
+        }
+
+        let deadline = options.max_duration.map(|budget| start_time + budget);
+        let mut results = FileResults::default();
+        for (file, bytes) in files.iter().zip(revision.read_blobs(&files)?) {
+            if deadline_passed(deadline) {
+                results.unanalyzed.push(file.path.clone());
+                continue;
+            }
+            let outcome = if self.is_oversized(&file.path, bytes.len() as u64) {
+                Ok(FileOutcome::Oversized)
+            } else {
+                self.analyze_bytes(&file.path, &bytes, None)
+            };
+            match outcome {
+                Ok(outcome) => results.record(&file.path, outcome),
+                Err(e) if options.fail_fast => return Err(e),
+                Err(e) => tracing::warn!("Failed to analyze {}: {}", file.path.display(), e),
+            }
+        }
+
+        let skipped: HashSet<&PathBuf> = results.skipped().collect();
+        let analyzed_files: Vec<PathBuf> = files
+            .iter()
+            .map(|file| file.path.clone())
+            .filter(|path| !skipped.contains(path))
+            .collect();
+        let total_files = analyzed_files.len();
+        let mut report = self.build_report(results, total_files, start_time, options);
+        report.summary.analyzed_files = analyzed_files;
+        Ok(report)
+    }
+
+    /// Analyze the macro-expanded source of a crate
+    ///
+    /// Only the rules listed in `expansion.rules` run, or every rule when the list is empty.
+    /// Violations are tagged `macro-expanded` and point into `file_path`.
+    pub fn analyze_expanded<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+    ) -> GuardianResult<Vec<Violation>> {
+        let rules = &self.config.expansion.rules;
+        let rule_ids: HashSet<String> = rules.iter().cloned().collect();
+        let rule_ids = (!rule_ids.is_empty()).then_some(&rule_ids);
+
+        let mut violations = self.analyze_source(file_path.as_ref(), content, rule_ids)?;
+        for violation in &mut violations {
+            // Fixing the generated expansion would not change the code it came from
+            violation.fix = None;
+            violation.tags.push(expand::EXPANDED_TAG.to_string());
+        }
+        Ok(violations)
+    }
+
+    /// Expand the selected cargo targets of the workspace and analyze the expansions
+    fn analyze_expanded_targets(
+        &self,
+        selection: &TargetSelection,
+    ) -> GuardianResult<Vec<Violation>> {
+        let root = self
+            .config
+            .paths
+            .root
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        let targets = CargoTargets::from_directory(&root)?;
+
+        let mut violations = Vec::new();
+        for target in targets.select(selection)? {
+            let source = match expand::expand_target(&root, target)? {
+                Some(source) => source,
+                None => continue,
+            };
+            let expanded_path = root.join(expand::expanded_path(target));
+            if let Some(directory) = expanded_path.parent() {
+                fs::create_dir_all(directory).map_err(|e| GuardianError::io(directory, e))?;
+            }
+            fs::write(&expanded_path, &source).map_err(|e| GuardianError::io(&expanded_path, e))?;
+            violations.extend(self.analyze_expanded(&expanded_path, &source)?);
+        }
+        Ok(violations)
+    }
+
+    /// Match decoded source against the configured rules, optionally only some of them
+    fn analyze_source(
+        &self,
+        file_path: &Path,
+        content: &str,
+        rule_ids: Option<&HashSet<String>>,
+    ) -> GuardianResult<Vec<Violation>> {
+        let mut all_violations = Vec::new();
+
+        // Every rule sees the same context, so the file is indexed and parsed once
+        let file =
+            FileContext::new(file_path, content).with_syntax_trees(self.syntax_trees.as_ref());
 
-        // Read file content
-        let content = fs::read_to_string(file_path).map_err(|e| {
+        // Apply pattern matching
+        let matches = match rule_ids {
+            Some(rule_ids) => self.pattern_engine.analyze_context_rules(&file, rule_ids),
+            None => self.pattern_engine.analyze_context(&file),
+        }
+        .map_err(|e| {
             GuardianError::analysis(
                 file_path.display().to_string(),
-                format!("Failed to read file: {e}"),
+                format!("Pattern analysis failed: {e}"),
             )
         })?;
 
-        let mut all_violations = Vec::new();
+        all_violations.extend(self.pattern_engine.matches_to_violations(matches));
+        self.pattern_engine
+            .attach_fixes(&mut all_violations, content);
+        if rule_ids.is_none() {
+            all_violations.extend(self.source_rule_violations(&file)?);
+            if let Some(license_header) = &self.license_header {
+                let year = chrono::Datelike::year(&chrono::Utc::now());
+                all_violations.extend(license_header.check(file_path, content, year));
+            }
+            if self.config.doc_examples.enabled {
+                let mut examples = self.doc_example_violations(file_path, content)?;
+                // A comment rule may already have matched the example line in the file itself
+                examples.retain(|example| {
+                    !all_violations.iter().any(|violation| {
+                        violation.rule_id == example.rule_id
+                            && violation.line_number == example.line_number
+                    })
+                });
+                all_violations.extend(examples);
+            }
+        }
 
-        // Apply pattern matching
-        let matches = self
-            .pattern_engine
-            .analyze_file(file_path, &content)
-            .map_err(|e| {
-                GuardianError::analysis(
-                    file_path.display().to_string(),
-                    format!("Pattern analysis failed: {e}"),
-                )
-            })?;
+        // Violations are placed in their cfg context once every rule has run
+        Ok(match file.syntax_tree() {
+            Some(syntax_tree) if !all_violations.is_empty() => {
+                self.cfg_scoping.apply(syntax_tree, all_violations)
+            }
+            _ => all_violations,
+        })
+    }
 
-        all_violations.extend(self.pattern_engine.matches_to_violations(matches));
+    /// Analyze the fenced Rust examples in a file's doc comments, or in a Markdown file
+    ///
+    /// Each example runs through the `doc_examples.rules` subset as its own file, wrapped in
+    /// `fn main` like rustdoc does. Violations point back into `file_path` and are tagged
+    /// `doc-example`.
+    fn doc_example_violations(
+        &self,
+        file_path: &Path,
+        content: &str,
+    ) -> GuardianResult<Vec<Violation>> {
+        let extension = file_path.extension().and_then(|ext| ext.to_str());
+        let (examples, example_path) = match extension {
+            Some("rs") => (
+                doc_examples::extract_from_rust(content),
+                file_path.to_path_buf(),
+            ),
+            Some("md" | "markdown") => (
+                doc_examples::extract_from_markdown(content),
+                PathBuf::from(format!("{}.rs", file_path.display())),
+            ),
+            _ => return Ok(Vec::new()),
+        };
+        if examples.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rules = &self.config.doc_examples.rules;
+        let rule_ids: HashSet<String> = if rules.is_empty() {
+            self.config
+                .enabled_rules()
+                .map(|(_, _, rule)| rule.id.clone())
+                .collect()
+        } else {
+            rules.iter().cloned().collect()
+        };
+
+        let mut violations = Vec::new();
+        for example in &examples {
+            let (source, offset) = example.as_source();
+            for mut violation in self.analyze_source(&example_path, &source, Some(&rule_ids))? {
+                let line = violation.line_number.unwrap_or(offset + 1);
+                let column = violation.column_number.unwrap_or(1);
+                let (line, column) = match example.original_position(line, column, offset) {
+                    Some(position) => position,
+                    // Violations on the added `fn main` wrapper are not in the example
+                    None => continue,
+                };
+                violation.file_path = file_path.to_path_buf();
+                violation.line_number = Some(line);
+                violation.column_number = Some(column);
+                violation.end_line = None;
+                violation.end_column = None;
+                violation.byte_range = None;
+                violation.fix = None;
+                violation.cfg = None;
+                violation
+                    .tags
+                    .push(doc_examples::DOC_EXAMPLE_TAG.to_string());
+                violations.push(violation);
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Run the procedural rules and Rust-specific checks on a file
+    fn source_rule_violations(&self, file: &FileContext) -> GuardianResult<Vec<Violation>> {
+        let file_path = file.path();
+        let mut all_violations = self.pattern_engine.check_procedural_rules(file);
 
         // Apply Rust-specific analysis for .rs files
         if self.rust_analyzer.handles_file(file_path) {
-            let rust_violations = self
-                .rust_analyzer
-                .analyze(file_path, &content)
-                .map_err(|e| {
-                    GuardianError::analysis(
-                        file_path.display().to_string(),
-                        format!("Rust analysis failed: {e}"),
-                    )
-                })?;
+            let rust_violations = self.rust_analyzer.analyze_context(file).map_err(|e| {
+                GuardianError::analysis(
+                    file_path.display().to_string(),
+                    format!("Rust analysis failed: {e}"),
+                )
+            })?;
             all_violations.extend(rust_violations);
         }
 
         Ok(all_violations)
     }
 
-    /// Analyze multiple files and return a complete validation report
-    pub fn analyze_paths<P: AsRef<Path>>(
+    /// Explain every exclusion that applies to a file, in evaluation order
+    pub fn explain_file<P: AsRef<Path>>(&self, file_path: P) -> GuardianResult<FileExplanation> {
+        let file_path = file_path.as_ref();
+
+        let generated_marker = if self.config.paths.skip_generated && file_path.is_file() {
+            encoding::read_source(file_path, true)
+                .ok()
+                .and_then(|source| {
+                    generated::generated_marker(
+                        &source.content,
+                        &self.config.paths.generated_markers,
+                    )
+                    .map(str::to_string)
+                })
+        } else {
+            None
+        };
+
+        Ok(FileExplanation {
+            path: self.path_filter.explain(file_path)?,
+            generated_marker,
+            excluded_rules: self.pattern_engine.rule_exclusions(file_path),
+        })
+    }
+
+    /// List the files `analyze_paths` would analyze, without analyzing them
+    ///
+    /// Files with a generated-code marker are listed separately when generated files are
+    /// skipped. Undecodable files are kept in `files`, since whether they fail or are
+    /// skipped is only known during analysis.
+    pub fn analysis_set<P: AsRef<Path>>(
         &self,
         paths: &[P],
         options: &AnalysisOptions,
-    ) -> GuardianResult<ValidationReport> {
-        let start_time = Instant::now();
-        let mut report = ValidationReport::new();
+    ) -> GuardianResult<AnalysisSet> {
+        let mut set = AnalysisSet::default();
 
-        // Collect all files to analyze
+        for file_path in self.collect_files(paths, options)? {
+            if !self.path_filter.should_analyze(&file_path)? {
+                continue;
+            }
+
+            let is_generated = self.config.paths.skip_generated
+                && encoding::read_source(&file_path, true)
+                    .ok()
+                    .and_then(|source| {
+                        generated::generated_marker(
+                            &source.content,
+                            &self.config.paths.generated_markers,
+                        )
+                    })
+                    .is_some();
+
+            if is_generated {
+                set.skipped_generated.push(file_path);
+            } else {
+                set.files.push(file_path);
+            }
+        }
+
+        Ok(set)
+    }
+
+    /// Collect candidate files from paths, applying exclusions and the file limit
+    fn collect_files<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        options: &AnalysisOptions,
+    ) -> GuardianResult<Vec<PathBuf>> {
         let mut files_to_analyze = Vec::new();
 
         for path in paths {
@@ -193,26 +786,182 @@ impl Analyzer {
             files_to_analyze.truncate(max_files);
         }
 
-        let total_files = files_to_analyze.len();
+        // Files being edited are the likeliest to have new violations, so they go first
+        if options.recent_first {
+            files_to_analyze.sort_by_cached_key(|file| {
+                std::cmp::Reverse(fs::metadata(file).and_then(|m| m.modified()).ok())
+            });
+        }
+
+        Ok(files_to_analyze)
+    }
+
+    /// Analyze multiple files and return a complete validation report
+    pub fn analyze_paths<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        options: &AnalysisOptions,
+    ) -> GuardianResult<ValidationReport> {
+        self.analyze_paths_streaming(paths, options, |_, _| {})
+    }
+
+    /// Analyze multiple files, passing each file's violations to `on_file` as it finishes
+    ///
+    /// Files are queued most recently modified first when `recent_first` is set, so results
+    /// for the files being worked on arrive before the long tail. Cross-file rules only run
+    /// once every file is analyzed; their violations are in the returned report alone.
+    pub fn analyze_paths_streaming<P, F>(
+        &self,
+        paths: &[P],
+        options: &AnalysisOptions,
+        on_file: F,
+    ) -> GuardianResult<ValidationReport>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Path, &[Violation]) + Sync,
+    {
+        let start_time = Instant::now();
+        self.pattern_engine.reset_rule_usage();
+        let run_span = tracing::info_span!("guardian.analyze", files = tracing::field::Empty);
+        let _entered = run_span.enter();
+
+        let files_to_analyze = self.collect_files(paths, options)?;
+        run_span.record("files", files_to_analyze.len());
+        tracing::info!(
+            phase = "discover",
+            files = files_to_analyze.len(),
+            duration_ms = start_time.elapsed().as_millis() as u64,
+            "Collected files"
+        );
+
+        let phase_start = Instant::now();
+        let deadline = options.max_duration.map(|budget| start_time + budget);
 
         // Analyze files (parallel or sequential)
-        let violations = if options.parallel && files_to_analyze.len() > 1 {
-            self.analyze_files_parallel(&files_to_analyze, options)?
+        let mut results = if options.parallel && files_to_analyze.len() > 1 {
+            self.analyze_files_parallel(&files_to_analyze, options, deadline, &on_file)?
+        } else {
+            self.analyze_files_sequential(&files_to_analyze, options, deadline, &on_file)?
+        };
+
+        if !results.unanalyzed.is_empty() {
+            tracing::warn!(
+                "Time budget exceeded; {} files were not analyzed",
+                results.unanalyzed.len()
+            );
+        }
+
+        let mut skipped: Vec<&PathBuf> = results.skipped().collect();
+        skipped.sort();
+        let total_files = files_to_analyze.len() - skipped.len();
+        tracing::info!(
+            phase = "analyze",
+            files = total_files,
+            skipped = skipped.len(),
+            duration_ms = phase_start.elapsed().as_millis() as u64,
+            "Analyzed files"
+        );
+
+        // Cross-file patterns see every analyzed file at once
+        let analyzed_files: Vec<PathBuf> = files_to_analyze
+            .iter()
+            .filter(|file| self.path_filter.should_analyze(file).unwrap_or(false))
+            .filter(|file| skipped.binary_search(file).is_err())
+            .cloned()
+            .collect();
+        let phase_start = Instant::now();
+        // Cross-file rules would see an incomplete crate once the budget has run out
+        let workspace_matches = if results.unanalyzed.is_empty() {
+            tracing::info_span!("guardian.workspace_rules")
+                .in_scope(|| self.pattern_engine.analyze_workspace(&analyzed_files))?
         } else {
-            self.analyze_files_sequential(&files_to_analyze, options)?
+            Vec::new()
         };
+        tracing::info!(
+            phase = "workspace_rules",
+            matches = workspace_matches.len(),
+            duration_ms = phase_start.elapsed().as_millis() as u64,
+            "Applied workspace rules"
+        );
+        results
+            .violations
+            .extend(self.pattern_engine.matches_to_violations(workspace_matches));
+
+        if let Some(selection) = &options.expand_macros {
+            let phase_start = Instant::now();
+            let expanded = tracing::info_span!("guardian.expand")
+                .in_scope(|| self.analyze_expanded_targets(selection))?;
+            tracing::info!(
+                phase = "expand",
+                violations = expanded.len(),
+                duration_ms = phase_start.elapsed().as_millis() as u64,
+                "Analyzed macro-expanded code"
+            );
+            results.violations.extend(expanded);
+        }
+
+        let mut report = self.build_report(results, total_files, start_time, options);
+        report.summary.analyzed_files = analyzed_files;
+        tracing::info!(
+            phase = "report",
+            violations = report.violations.len(),
+            duration_ms = report.summary.execution_time_ms,
+            "Analysis complete"
+        );
 
-        // Build final report
-        for violation in violations {
+        Ok(report)
+    }
+
+    /// Build the report of a run from the outcomes of its files
+    fn build_report(
+        &self,
+        mut results: FileResults,
+        total_files: usize,
+        start_time: Instant,
+        options: &AnalysisOptions,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for violation in results.violations {
             report.add_violation(violation);
         }
 
+        results.generated.sort();
+        for file_path in results.generated {
+            report.add_skipped_generated(file_path);
+        }
+
+        results.undecodable.sort();
+        for file_path in results.undecodable {
+            report.add_skipped_undecodable(file_path);
+        }
+
+        results.oversized.sort();
+        for file_path in results.oversized {
+            report.add_skipped_oversized(file_path);
+        }
+
+        results.unanalyzed.sort();
+        for file_path in results.unanalyzed {
+            report.add_unanalyzed(file_path);
+        }
+
+        results.encodings.sort_by(|a, b| a.0.cmp(&b.0));
+        for (file_path, encoding) in results.encodings {
+            report.add_file_encoding(file_path, encoding.as_str());
+        }
+
         report.set_files_analyzed(total_files);
         report.set_execution_time(start_time.elapsed().as_millis() as u64);
         report.set_config_fingerprint(self.config.fingerprint());
+        report.set_rule_pack_version(RULE_PACK_VERSION);
+        if let Some(hash) = self.policy_hash() {
+            report.set_policy_hash(hash);
+        }
+        if options.rule_usage {
+            report.summary.rule_usage = self.pattern_engine.take_rule_usage();
+        }
         report.sort_violations();
-
-        Ok(report)
+        report
     }
 
     /// Analyze files sequentially
@@ -220,13 +969,20 @@ impl Analyzer {
         &self,
         files: &[PathBuf],
         options: &AnalysisOptions,
-    ) -> GuardianResult<Vec<Violation>> {
-        let mut all_violations = Vec::new();
-
-        for file_path in files {
-            match self.analyze_file(file_path) {
-                Ok(violations) => {
-                    all_violations.extend(violations);
+        deadline: Option<Instant>,
+        on_file: &(dyn Fn(&Path, &[Violation]) + Sync),
+    ) -> GuardianResult<FileResults> {
+        let mut results = FileResults::default();
+
+        for (index, file_path) in files.iter().enumerate() {
+            if deadline_passed(deadline) {
+                results.unanalyzed.extend(files[index..].iter().cloned());
+                break;
+            }
+            match self.analyze_file_outcome(file_path) {
+                Ok(outcome) => {
+                    outcome.report_to(file_path, on_file);
+                    results.record(file_path, outcome);
                 }
                 Err(e) => {
                     if options.fail_fast {
@@ -239,24 +995,50 @@ impl Analyzer {
             }
         }
 
-        Ok(all_violations)
+        Ok(results)
+    }
+
+    /// Analyze files one at a time when built without the `parallel` feature
+    #[cfg(not(feature = "parallel"))]
+    fn analyze_files_parallel(
+        &self,
+        files: &[PathBuf],
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+        on_file: &(dyn Fn(&Path, &[Violation]) + Sync),
+    ) -> GuardianResult<FileResults> {
+        self.analyze_files_sequential(files, options, deadline, on_file)
     }
 
     /// Analyze files in parallel
+    #[cfg(feature = "parallel")]
     fn analyze_files_parallel(
         &self,
         files: &[PathBuf],
         options: &AnalysisOptions,
-    ) -> GuardianResult<Vec<Violation>> {
-        let violations = Arc::new(Mutex::new(Vec::new()));
+        deadline: Option<Instant>,
+        on_file: &(dyn Fn(&Path, &[Violation]) + Sync),
+    ) -> GuardianResult<FileResults> {
+        let results = Arc::new(Mutex::new(FileResults::default()));
         let errors = Arc::new(Mutex::new(Vec::new()));
 
-        files
-            .par_iter()
-            .for_each(|file_path| match self.analyze_file(file_path) {
-                Ok(file_violations) => {
-                    if let Ok(mut v) = violations.lock() {
-                        v.extend(file_violations);
+        // Worker threads do not inherit the caller's span, so file spans are parented explicitly.
+        // Bridging hands files to workers in queue order instead of splitting the list into
+        // chunks, so the front of the queue really is analyzed first.
+        let parent = tracing::Span::current();
+        // Once the deadline passes no new file is started; files in flight still finish.
+        files.iter().par_bridge().for_each(|file_path| {
+            if deadline_passed(deadline) {
+                if let Ok(mut r) = results.lock() {
+                    r.unanalyzed.push(file_path.clone());
+                }
+                return;
+            }
+            match parent.in_scope(|| self.analyze_file_outcome(file_path)) {
+                Ok(outcome) => {
+                    outcome.report_to(file_path, on_file);
+                    if let Ok(mut r) = results.lock() {
+                        r.record(file_path, outcome);
                     }
                 }
                 Err(e) => {
@@ -264,7 +1046,8 @@ impl Analyzer {
                         errs.push((file_path.clone(), e));
                     }
                 }
-            });
+            }
+        });
 
         // Handle errors
         let errors = Arc::try_unwrap(errors)
@@ -298,21 +1081,21 @@ impl Analyzer {
             }
         }
 
-        let violations = Arc::try_unwrap(violations)
+        let results = Arc::try_unwrap(results)
             .map_err(|_| {
                 GuardianError::analysis(
                     "parallel_analysis".to_string(),
-                    "Failed to unwrap violations Arc".to_string(),
+                    "Failed to unwrap results Arc".to_string(),
                 )
             })?
             .into_inner()
             .map_err(|_| {
                 GuardianError::analysis(
                     "parallel_analysis".to_string(),
-                    "Failed to lock violations mutex".to_string(),
+                    "Failed to lock results mutex".to_string(),
                 )
             })?;
-        Ok(violations)
+        Ok(results)
     }
 
     /// Analyze a directory tree and return a validation report
@@ -324,11 +1107,62 @@ impl Analyzer {
         self.analyze_paths(&[root.as_ref()], options)
     }
 
+    /// Get the path filter that decides which files are analyzed
+    pub fn path_filter(&self) -> &PathFilter {
+        &self.path_filter
+    }
+
     /// Get configuration fingerprint for cache validation
     pub fn config_fingerprint(&self) -> String {
         self.config.fingerprint()
     }
 
+    /// Fingerprint of the settings shared by all rules, for per-rule cache validation
+    pub fn shared_fingerprint(&self) -> String {
+        self.config.shared_fingerprint()
+    }
+
+    /// Fingerprint of each enabled configured rule, for per-rule cache validation
+    pub fn rule_fingerprints(&self) -> BTreeMap<String, String> {
+        self.config.rule_fingerprints()
+    }
+
+    /// Ids of the cross-file rules, whose results must not be cached per file
+    pub fn workspace_rule_ids(&self) -> HashSet<String> {
+        self.pattern_engine.workspace_rule_ids()
+    }
+
+    /// Apply only the cross-file rules to a set of already analyzed files
+    pub fn analyze_workspace(&self, files: &[PathBuf]) -> GuardianResult<Vec<Violation>> {
+        let matches = self.pattern_engine.analyze_workspace(files)?;
+        Ok(self.pattern_engine.matches_to_violations(matches))
+    }
+
+    /// Compiled rules of the analyzer, for building further analyzers without recompiling
+    pub fn compiled_rules(&self) -> &Arc<CompiledRules> {
+        self.pattern_engine.rules()
+    }
+
+    /// Register a procedural rule compiled into the calling crate
+    pub fn register_rule(&mut self, rule: Box<dyn Rule>) -> GuardianResult<()> {
+        self.pattern_engine.register_rule(rule)
+    }
+
+    /// Register a procedural rule shared with other analyzers
+    pub(crate) fn register_shared_rule(&mut self, rule: Arc<dyn Rule>) -> GuardianResult<()> {
+        self.pattern_engine.register_shared_rule(rule)
+    }
+
+    /// Metadata of the registered procedural rules
+    pub fn procedural_rules(&self) -> Vec<RuleInfo> {
+        self.pattern_engine.procedural_rules()
+    }
+
+    /// Hash of the locked policy in force, if the configuration is locked
+    pub fn policy_hash(&self) -> Option<&str> {
+        self.config.policy_hash.as_deref()
+    }
+
     /// Get statistics about the configured patterns
     pub fn pattern_stats(&self) -> PatternStats {
         let mut stats = PatternStats::default();
@@ -341,10 +1175,14 @@ impl Analyzer {
                     if rule.enabled {
                         stats.enabled_rules += 1;
                         match rule.rule_type {
-                            crate::config::RuleType::Regex => stats.regex_patterns += 1,
+                            crate::config::RuleType::Regex | crate::config::RuleType::Required => {
+                                stats.regex_patterns += 1
+                            }
                             crate::config::RuleType::Ast => stats.ast_patterns += 1,
                             crate::config::RuleType::Semantic => stats.semantic_patterns += 1,
                             crate::config::RuleType::ImportAnalysis => stats.import_patterns += 1,
+                            crate::config::RuleType::Composite
+                            | crate::config::RuleType::FilePair => stats.composite_patterns += 1,
                         }
                     } else {
                         stats.disabled_rules += 1;
@@ -371,6 +1209,7 @@ pub struct PatternStats {
     pub ast_patterns: usize,
     pub semantic_patterns: usize,
     pub import_patterns: usize,
+    pub composite_patterns: usize,
 }
 
 impl PatternStats {
@@ -383,11 +1222,21 @@ impl PatternStats {
     }
 }
 
+/// Whether an optional analysis deadline has passed
+fn deadline_passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
 /// Trait for custom file analyzers
 pub trait FileAnalyzer {
     /// Analyze a file and return violations
     fn analyze(&self, file_path: &Path, content: &str) -> GuardianResult<Vec<Violation>>;
 
+    /// Analyze a file through its shared context, reusing its parsed syntax tree
+    fn analyze_context(&self, file: &FileContext) -> GuardianResult<Vec<Violation>> {
+        self.analyze(file.path(), file.content())
+    }
+
     /// Check if this analyzer handles the given file type
     fn handles_file(&self, file_path: &Path) -> bool;
 }
@@ -406,7 +1255,11 @@ impl Analyzer {
             ));
         }
 
-        if stats.regex_patterns == 0 && stats.ast_patterns == 0 && stats.semantic_patterns == 0 {
+        if stats.regex_patterns == 0
+            && stats.ast_patterns == 0
+            && stats.semantic_patterns == 0
+            && stats.composite_patterns == 0
+        {
             return Err(GuardianError::config(
                 "Analyzer must have at least one pattern type enabled".to_string(),
             ));
@@ -0,0 +1,374 @@
+//! Server-side `pre-receive` hook checks
+//!
+//! Architecture: Domain Service - A push is judged by the lines it adds, before refs move
+//! - Ref updates are read as git passes them to the hook: `<old> <new> <ref>` per line
+//! - Pushed objects are read from the (quarantined) object store, never from a worktree
+//! - Only added lines count, so pushes are not blocked by violations they did not introduce
+//! - Rejections are short, since git relays them line by line to the pushing client
+
+use super::revision::{git, Revision};
+use super::{AnalysisOptions, Analyzer};
+use crate::domain::violations::{GuardianError, GuardianResult, Severity, ValidationReport};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Id of the empty tree, the base of root commits
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// One ref update of a push
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    pub old: String,
+    pub new: String,
+    pub refname: String,
+}
+
+impl RefUpdate {
+    /// Whether the update deletes the ref
+    pub fn is_deletion(&self) -> bool {
+        is_zero(&self.new)
+    }
+
+    /// Whether the update creates the ref
+    pub fn is_creation(&self) -> bool {
+        is_zero(&self.old)
+    }
+}
+
+/// Whether an object id is the all-zero id git uses for a missing ref, in either hash format
+fn is_zero(oid: &str) -> bool {
+    oid.bytes().all(|b| b == b'0')
+}
+
+/// Parse the ref updates git writes to a `pre-receive` hook's standard input
+pub fn parse_ref_updates(input: &str) -> GuardianResult<Vec<RefUpdate>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [old, new, refname] => Ok(RefUpdate {
+                    old: old.to_string(),
+                    new: new.to_string(),
+                    refname: refname.to_string(),
+                }),
+                _ => Err(GuardianError::config(format!(
+                    "Invalid ref update '{line}' (expected '<old> <new> <ref>')"
+                ))),
+            },
+        )
+        .collect()
+}
+
+/// Commit or tree a ref update's changes are measured against
+///
+/// An existing ref is compared with its old commit. A new ref is compared with the parent of
+/// the oldest commit no other ref contains, or the empty tree when that commit is a root.
+/// `None` means the update brings nothing new to check.
+pub fn push_base(repository: &Path, update: &RefUpdate) -> GuardianResult<Option<String>> {
+    if update.is_deletion() {
+        return Ok(None);
+    }
+    if !update.is_creation() {
+        return Ok(Some(update.old.clone()));
+    }
+
+    let pushed = git(
+        repository,
+        &[
+            "rev-list",
+            "--reverse",
+            "--topo-order",
+            &update.new,
+            "--not",
+            "--all",
+        ],
+    )?;
+    let Some(oldest) = pushed.lines().next() else {
+        return Ok(None);
+    };
+    let parent = git(
+        repository,
+        &["rev-parse", "--verify", "--quiet", &format!("{oldest}^")],
+    );
+    Ok(Some(match parent {
+        Ok(parent) => parent.trim().to_string(),
+        Err(_) => EMPTY_TREE.to_string(),
+    }))
+}
+
+/// Lines added to each file between two revisions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddedLines {
+    /// Added line ranges (first, last) by file path relative to the repository root
+    files: BTreeMap<String, Vec<(u32, u32)>>,
+}
+
+impl AddedLines {
+    /// Diff two revisions of a repository
+    pub fn between(repository: &Path, base: &str, head: &str) -> GuardianResult<Self> {
+        let diff = git(
+            repository,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--find-renames",
+                "--diff-filter=AMR",
+                // `parse` reads paths after `+++ b/`, whatever `diff.noprefix` or
+                // `diff.mnemonicPrefix` the repository configures
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                base,
+                head,
+            ],
+        )?;
+        Ok(Self::parse(&diff))
+    }
+
+    /// Parse a `--unified=0` diff
+    pub fn parse(diff: &str) -> Self {
+        let mut files: BTreeMap<String, Vec<(u32, u32)>> = BTreeMap::new();
+        let mut current = None;
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                current = path.strip_prefix("b/").map(str::to_string);
+                if let Some(path) = &current {
+                    files.entry(path.clone()).or_default();
+                }
+            } else if let (Some(hunk), Some(path)) = (line.strip_prefix("@@ "), &current) {
+                // `@@ -<old>[,<count>] +<start>[,<count>] @@`
+                let Some(added) = hunk.split(' ').find_map(|part| part.strip_prefix('+')) else {
+                    continue;
+                };
+                let (start, count) = match added.split_once(',') {
+                    Some((start, count)) => (start.parse(), count.parse()),
+                    None => (added.parse(), Ok(1)),
+                };
+                if let (Ok(start), Ok(count)) = (start, count) {
+                    if count > 0 {
+                        if let Some(ranges) = files.get_mut(path) {
+                            ranges.push((start, start + count - 1));
+                        }
+                    }
+                }
+            }
+        }
+        files.retain(|_, ranges| !ranges.is_empty());
+        Self { files }
+    }
+
+    /// Files with added lines, relative to the repository root
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// Whether a line of a file was added; findings without a line never count as added
+    pub fn contains(&self, tree_path: &str, line: Option<u32>) -> bool {
+        match (self.files.get(tree_path), line) {
+            (Some(ranges), Some(line)) => ranges
+                .iter()
+                .any(|(first, last)| (*first..=*last).contains(&line)),
+            _ => false,
+        }
+    }
+
+    /// Whether no lines were added
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl Analyzer {
+    /// Analyze the lines a push adds, reading pushed commits from the object store
+    ///
+    /// Each ref update's new commit is analyzed in the files it changes, and only violations
+    /// on added lines are kept. The time budget in `options` covers the whole push; files not
+    /// reached in time are listed as unanalyzed.
+    pub fn analyze_push(
+        &self,
+        repository: &Path,
+        updates: &[RefUpdate],
+        options: &AnalysisOptions,
+    ) -> GuardianResult<ValidationReport> {
+        let start_time = Instant::now();
+        let mut report = ValidationReport::new();
+        for update in updates {
+            let Some(base) = push_base(repository, update)? else {
+                continue;
+            };
+            let added = AddedLines::between(repository, &base, &update.new)?;
+            if added.is_empty() {
+                continue;
+            }
+
+            let revision = Revision::resolve(repository, &update.new)?;
+            // Tree paths name files of the revision wherever a worktree would hold them
+            let paths: Vec<PathBuf> = added
+                .files()
+                .map(|path| match revision.worktree() {
+                    Some(worktree) => worktree.join(path),
+                    None => PathBuf::from(path),
+                })
+                .collect();
+            let mut ref_options = options.clone();
+            ref_options.max_duration = options
+                .max_duration
+                .map(|budget| budget.saturating_sub(start_time.elapsed()));
+            let mut ref_report = self.analyze_revision(&revision, &paths, &ref_options)?;
+            ref_report.retain_violations(|violation| {
+                revision
+                    .tree_path(&violation.file_path)
+                    .is_some_and(|path| added.contains(&path, violation.line_number))
+            });
+            report.merge(ref_report);
+        }
+
+        // A commit pushed to several refs is reported once
+        let mut seen = HashSet::new();
+        report.retain_violations(|violation| {
+            seen.insert((
+                violation.file_path.clone(),
+                violation.line_number,
+                violation.rule_id.clone(),
+                violation.message.clone(),
+            ))
+        });
+        report.sort_violations();
+        report.set_execution_time(start_time.elapsed().as_millis() as u64);
+        Ok(report)
+    }
+}
+
+/// Rejection message for a push, or `None` when the push may proceed
+///
+/// Lists the errors on added lines, at most `limit` of them, and says how to proceed.
+pub fn format_rejection(report: &ValidationReport, limit: usize) -> Option<String> {
+    let errors: Vec<_> = report
+        .violations
+        .iter()
+        .filter(|violation| violation.severity == Severity::Error)
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+
+    let mut message = format!(
+        "✋ Rust Guardian rejected this push: {} error(s) in added lines\n",
+        errors.len()
+    );
+    for violation in errors.iter().take(limit) {
+        let location = match violation.line_number {
+            Some(line) => format!("{}:{line}", violation.file_path.display()),
+            None => violation.file_path.display().to_string(),
+        };
+        message.push_str(&format!(
+            "  {location}  {}: {}\n",
+            violation.rule_id, violation.message
+        ));
+    }
+    if errors.len() > limit {
+        message.push_str(&format!("  … and {} more\n", errors.len() - limit));
+    }
+    message.push_str("Fix these in your commits and push again.\n");
+    Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+
+    const ZERO_OID: &str = "0000000000000000000000000000000000000000";
+
+    #[test]
+    fn test_parse_ref_updates_and_added_lines() {
+        let updates = parse_ref_updates(&format!(
+            "{ZERO_OID} 1111111111111111111111111111111111111111 refs/heads/topic\n\
+             2222222222222222222222222222222222222222 {ZERO_OID} refs/heads/old\n"
+        ))
+        .unwrap();
+        assert!(updates[0].is_creation());
+        assert!(updates[1].is_deletion());
+        assert!(parse_ref_updates("refs/heads/main").is_err());
+
+        let added = AddedLines::parse(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -3,0 +4,2 @@ fn main() {\n\
+             +// TODO: one\n\
+             +// TODO: two\n\
+             @@ -9 +11 @@\n\
+             -old\n\
+             +new\n\
+             diff --git a/src/gone.rs b/src/gone.rs\n\
+             --- a/src/gone.rs\n\
+             +++ b/src/gone.rs\n\
+             @@ -1,2 +0,0 @@\n",
+        );
+        assert_eq!(added.files().collect::<Vec<_>>(), ["src/lib.rs"]);
+        assert!(added.contains("src/lib.rs", Some(5)));
+        assert!(added.contains("src/lib.rs", Some(11)));
+        assert!(!added.contains("src/lib.rs", Some(6)));
+        assert!(!added.contains("src/lib.rs", None));
+    }
+
+    #[test]
+    fn test_analyze_push_checks_added_lines_only() {
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(repo.path())
+                .args([
+                    "-c",
+                    "user.name=Guardian",
+                    "-c",
+                    "user.email=guardian@example.com",
+                ])
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        if git(&["init", "-q"]).is_none() {
+            return; // git is not installed
+        }
+        // Diff prefixes configured by the repository must not hide added lines
+        git(&["config", "diff.noprefix", "true"]).unwrap();
+        fs::create_dir_all(repo.path().join("src")).unwrap();
+        fs::write(repo.path().join("src/lib.rs"), "// TODO: known\n").unwrap();
+        git(&["add", "."]).unwrap();
+        git(&["commit", "-q", "-m", "Initial commit"]).unwrap();
+        let old = git(&["rev-parse", "HEAD"]).unwrap();
+        fs::write(
+            repo.path().join("src/lib.rs"),
+            "// TODO: known\n// TODO: pushed\n",
+        )
+        .unwrap();
+        git(&["commit", "-q", "-am", "Add a TODO"]).unwrap();
+        let new = git(&["rev-parse", "HEAD"]).unwrap();
+
+        let updates = parse_ref_updates(&format!("{old} {new} refs/heads/main")).unwrap();
+        let report = Analyzer::with_defaults()
+            .unwrap()
+            .analyze_push(repo.path(), &updates, &AnalysisOptions::default())
+            .unwrap();
+        let lines: Vec<Option<u32>> = report
+            .violations
+            .iter()
+            .map(|violation| violation.line_number)
+            .collect();
+        assert_eq!(lines, [Some(2)]);
+        let rejection = format_rejection(&report, 10).unwrap();
+        assert!(rejection.contains("1 error(s) in added lines"));
+        assert!(rejection.contains("lib.rs:2  todo_comments"));
+    }
+}
@@ -0,0 +1,297 @@
+//! Interactive pattern authoring session behind `rust-guardian repl`
+//!
+//! Architecture: Application Service - A session turns one line of input into one block of output
+//! - A pattern expression is compiled as a throwaway rule and run against the loaded files
+//! - Files are re-read on every run, so edits to samples show up immediately
+//! - Matches are listed with their spans and the time each file took
+
+use crate::analyzer::{AnalysisOptions, Analyzer};
+use crate::config::{GuardianConfig, PatternRule, RuleType};
+use crate::domain::violations::{GuardianError, GuardianResult, Severity};
+use crate::patterns::{FileContext, PatternEngine};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Id of the rule compiled from the current expression
+const REPL_RULE_ID: &str = "repl";
+
+/// Commands understood by the session, printed by `help`
+pub const REPL_HELP: &str = "\
+Commands:
+  <regex>               Run a regex pattern (shorthand for `regex <regex>`)
+  regex <pattern>       Run a regex pattern
+  ast <pattern>         Run an AST pattern, such as macro_call:todo|unimplemented
+  semantic <pattern>    Run a semantic pattern, such as magic_number:10
+  import <pattern>      Run an import analysis pattern
+  rule <id>             Run the pattern of a configured rule
+  case on|off           Toggle case-sensitive regex matching
+  file <path>...        Add files or directories to match against
+  files                 List the loaded files
+  clear                 Unload all files
+  run                   Run the current pattern again
+  help                  Show this help
+  quit                  Leave the session";
+
+/// Result of evaluating one line of input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplStep {
+    /// Text to show the user
+    Output(String),
+    /// The user asked to leave
+    Quit,
+}
+
+/// Pattern expression under test
+#[derive(Debug, Clone)]
+struct Expression {
+    rule_type: RuleType,
+    pattern: String,
+}
+
+/// State of one interactive session
+pub struct ReplSession {
+    config: GuardianConfig,
+    analyzer: Analyzer,
+    expression: Option<Expression>,
+    case_sensitive: bool,
+    files: Vec<PathBuf>,
+}
+
+impl ReplSession {
+    /// Start a session; configured path filters decide which files directories expand to
+    pub fn new(config: GuardianConfig) -> GuardianResult<Self> {
+        let analyzer = Analyzer::new(config.clone())?;
+        Ok(Self {
+            config,
+            analyzer,
+            expression: None,
+            case_sensitive: true,
+            files: Vec::new(),
+        })
+    }
+
+    /// Evaluate one line of input
+    pub fn eval(&mut self, line: &str) -> GuardianResult<ReplStep> {
+        let line = line.trim();
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+
+        let output = match command {
+            "" => String::new(),
+            "help" => REPL_HELP.to_string(),
+            "quit" | "exit" => return Ok(ReplStep::Quit),
+            "regex" => self.set_expression(RuleType::Regex, argument)?,
+            "ast" => self.set_expression(RuleType::Ast, argument)?,
+            "semantic" => self.set_expression(RuleType::Semantic, argument)?,
+            "import" => self.set_expression(RuleType::ImportAnalysis, argument)?,
+            "rule" => self.load_rule(argument)?,
+            "case" => {
+                self.case_sensitive = match argument {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(GuardianError::config("Usage: case on|off")),
+                };
+                self.run()?
+            }
+            "file" => {
+                let paths: Vec<PathBuf> = argument.split_whitespace().map(PathBuf::from).collect();
+                let added = self
+                    .analyzer
+                    .analysis_set(&paths, &AnalysisOptions::default())?
+                    .files;
+                let count = added.len();
+                for file in added {
+                    if !self.files.contains(&file) {
+                        self.files.push(file);
+                    }
+                }
+                format!("Loaded {count} file(s)\n{}", self.run()?)
+            }
+            "files" => self
+                .files
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "clear" => {
+                self.files.clear();
+                "Unloaded all files".to_string()
+            }
+            "run" => self.run()?,
+            _ => self.set_expression(RuleType::Regex, line)?,
+        };
+
+        Ok(ReplStep::Output(output))
+    }
+
+    fn set_expression(&mut self, rule_type: RuleType, pattern: &str) -> GuardianResult<String> {
+        if pattern.is_empty() {
+            return Err(GuardianError::config("A pattern is required"));
+        }
+        self.expression = Some(Expression {
+            rule_type,
+            pattern: pattern.to_string(),
+        });
+        self.run()
+    }
+
+    fn load_rule(&mut self, rule_id: &str) -> GuardianResult<String> {
+        let rule = self
+            .config
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .find(|rule| rule.id == rule_id)
+            .ok_or_else(|| GuardianError::config(format!("Unknown rule '{rule_id}'")))?;
+
+        self.case_sensitive = rule.case_sensitive;
+        let (rule_type, pattern) = (rule.rule_type.clone(), rule.pattern.clone());
+        let loaded = format!("{rule_type:?}: {pattern}");
+        self.expression = Some(Expression { rule_type, pattern });
+        Ok(format!("{loaded}\n{}", self.run()?))
+    }
+
+    /// Run the current expression against every loaded file
+    fn run(&self) -> GuardianResult<String> {
+        let expression = match &self.expression {
+            Some(expression) => expression,
+            None => return Ok("No pattern yet; type a regex or `help`".to_string()),
+        };
+        if self.files.is_empty() {
+            return Ok("No files loaded; use `file <path>`".to_string());
+        }
+
+        let rule = PatternRule {
+            id: REPL_RULE_ID.to_string(),
+            rule_type: expression.rule_type.clone(),
+            pattern: expression.pattern.clone(),
+            message: "{match}".to_string(),
+            severity: None,
+            enabled: true,
+            case_sensitive: self.case_sensitive,
+            exclude_if: None,
+            tags: Vec::new(),
+            docs_url: None,
+            rationale: None,
+            applies_to: Vec::new(),
+            deprecated: false,
+            replaced_by: None,
+            only_cfg: None,
+            all_of: Vec::new(),
+            any_of: Vec::new(),
+            none_of: Vec::new(),
+            near: None,
+            good_example: None,
+            bad_example: None,
+            fix: None,
+        };
+        let mut engine = PatternEngine::new();
+        engine.add_rule(&rule, Severity::Info)?;
+
+        let mut output = String::new();
+        let mut total_matches = 0;
+        let mut matched_files = 0;
+        let mut total_time = Duration::ZERO;
+
+        for file_path in &self.files {
+            let content =
+                fs::read_to_string(file_path).map_err(|e| GuardianError::io(file_path, e))?;
+            let started = Instant::now();
+            let mut matches = engine.analyze_context(&FileContext::new(file_path, &content))?;
+            let elapsed = started.elapsed();
+            total_time += elapsed;
+
+            if matches.is_empty() {
+                continue;
+            }
+            matches.sort_by_key(|m| (m.line_number, m.column_number));
+            matched_files += 1;
+            total_matches += matches.len();
+
+            let _ = writeln!(
+                output,
+                "{} ({} match(es), {:.2}ms)",
+                file_path.display(),
+                matches.len(),
+                elapsed.as_secs_f64() * 1000.0
+            );
+            for m in &matches {
+                let position = |line: Option<u32>, column: Option<u32>| {
+                    format!("{}:{}", line.unwrap_or(0), column.unwrap_or(0))
+                };
+                let _ = writeln!(
+                    output,
+                    "  {}-{}  {}",
+                    position(m.line_number, m.column_number),
+                    position(m.end_line, m.end_column),
+                    m.matched_text.lines().next().unwrap_or("")
+                );
+            }
+        }
+
+        let _ = write!(
+            output,
+            "{total_matches} match(es) in {matched_files} of {} file(s) ({:.2}ms)",
+            self.files.len(),
+            total_time.as_secs_f64() * 1000.0
+        );
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn output(step: ReplStep) -> String {
+        match step {
+            ReplStep::Output(output) => output,
+            ReplStep::Quit => panic!("session should not quit"),
+        }
+    }
+
+    #[test]
+    fn test_repl_session() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let file = temp_dir.path().join("lib.rs");
+        fs::write(&file, "fn run() {\n    // TODO: finish\n    todo!()\n}\n")
+            .expect("Failed to write sample");
+
+        let mut session =
+            ReplSession::new(GuardianConfig::default()).expect("Failed to start session");
+        let loaded = output(
+            session
+                .eval(&format!("file {}", file.display()))
+                .expect("file should load"),
+        );
+        assert!(loaded.starts_with("Loaded 1 file(s)"));
+
+        let regex = output(session.eval("TODO").expect("regex should run"));
+        assert!(regex.contains("  2:8-2:12  TODO"), "{regex}");
+        assert!(regex.contains("1 match(es) in 1 of 1 file(s)"));
+
+        let ast = output(
+            session
+                .eval("ast macro_call:todo")
+                .expect("ast pattern should run"),
+        );
+        assert!(ast.contains("  3:5-"), "{ast}");
+
+        let rule = output(
+            session
+                .eval("rule todo_comments")
+                .expect("rule should load"),
+        );
+        // The rule is case-insensitive, so `todo!()` matches too
+        assert!(rule.contains("2 match(es) in 1 of 1 file(s)"), "{rule}");
+
+        assert!(session.eval("regex (unclosed").is_err());
+        assert!(session.eval("rule no_such_rule").is_err());
+        assert_eq!(session.eval("quit").expect("quit"), ReplStep::Quit);
+    }
+}
@@ -0,0 +1,295 @@
+//! Source files of a git revision, read without a checkout
+//!
+//! Architecture: Anti-Corruption Layer - The git object store stands in for the worktree
+//! - Files are listed with `git ls-tree` and read through one `git cat-file --batch` process
+//! - Works in bare repositories, so server-side hooks can check pushed commits
+//! - Nothing is written to disk; blobs are analyzed in memory
+//! - The git CLI is used instead of libgit2 (`git2`), whose C library does not build for the
+//!   wasm32 target this crate also compiles for; pushes and hooks have git installed anyway
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use crate::report::{absolute_path, normalize_separators};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Mode git records for symbolic links, whose blobs hold a target path rather than source
+const SYMLINK_MODE: &str = "120000";
+
+/// A commit of a git repository, read from its object store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// Directory git commands run in
+    repository: PathBuf,
+    /// Full commit id
+    commit: String,
+    /// Root of the worktree, absent in a bare repository
+    worktree: Option<PathBuf>,
+}
+
+/// File of a revision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevisionFile {
+    /// Path violations are reported against: relative to the current directory in a
+    /// worktree, or to the repository root in a bare repository
+    pub path: PathBuf,
+    /// Path relative to the repository root, with `/` separators
+    pub tree_path: String,
+    /// Blob id
+    pub oid: String,
+}
+
+impl Revision {
+    /// Resolve a revision (commit id, branch, tag or other expression) of the repository
+    /// containing `dir`
+    pub fn resolve(dir: &Path, rev: &str) -> GuardianResult<Self> {
+        let commit = git(
+            dir,
+            &["rev-parse", "--verify", &format!("{rev}^{{commit}}")],
+        )
+        .map_err(|_| {
+            GuardianError::config(format!(
+                "Revision '{rev}' is not a commit of the repository"
+            ))
+        })?
+        .trim()
+        .to_string();
+        let worktree = git(dir, &["rev-parse", "--show-toplevel"])
+            .ok()
+            .map(|toplevel| PathBuf::from(toplevel.trim()))
+            .filter(|toplevel| !toplevel.as_os_str().is_empty());
+        Ok(Self {
+            repository: dir.to_path_buf(),
+            commit,
+            worktree,
+        })
+    }
+
+    /// Full commit id
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// Root of the worktree, if the repository has one
+    pub fn worktree(&self) -> Option<&Path> {
+        self.worktree.as_deref()
+    }
+
+    /// Files of the revision under the given paths, or all of them when `paths` is empty
+    ///
+    /// In a worktree paths are resolved like paths on disk; in a bare repository they are
+    /// relative to the repository root. Symbolic links and submodules are left out.
+    pub fn files<P: AsRef<Path>>(&self, paths: &[P]) -> GuardianResult<Vec<RevisionFile>> {
+        let prefixes = paths
+            .iter()
+            .map(|path| self.tree_prefix(path.as_ref()))
+            .collect::<GuardianResult<Vec<_>>>()?;
+        let cwd = std::env::current_dir().ok();
+
+        let listing = git(
+            &self.repository,
+            &["ls-tree", "-r", "-z", "--full-tree", &self.commit],
+        )?;
+        let mut files = Vec::new();
+        for entry in listing.split('\0').filter(|entry| !entry.is_empty()) {
+            let Some((meta, tree_path)) = entry.split_once('\t') else {
+                continue;
+            };
+            let mut fields = meta.split(' ');
+            let (Some(mode), Some("blob"), Some(oid)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if mode == SYMLINK_MODE {
+                continue;
+            }
+            if !prefixes.is_empty()
+                && !prefixes
+                    .iter()
+                    .any(|prefix| Path::new(tree_path).starts_with(prefix))
+            {
+                continue;
+            }
+
+            let path = match (&self.worktree, &cwd) {
+                (Some(worktree), Some(cwd)) => {
+                    let absolute = worktree.join(tree_path);
+                    absolute
+                        .strip_prefix(cwd)
+                        .map(Path::to_path_buf)
+                        .unwrap_or(absolute)
+                }
+                _ => PathBuf::from(tree_path),
+            };
+            files.push(RevisionFile {
+                path,
+                tree_path: tree_path.to_string(),
+                oid: oid.to_string(),
+            });
+        }
+        Ok(files)
+    }
+
+    /// Read the content of files, in order, through one `git cat-file` process
+    pub fn read_blobs(&self, files: &[RevisionFile]) -> GuardianResult<Vec<Vec<u8>>> {
+        let failed = |e: std::io::Error| GuardianError::config(format!("Failed to run git: {e}"));
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(&self.repository)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(failed)?;
+
+        // Requests are written from another thread so a full stdout pipe cannot block them
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| GuardianError::config("git cat-file has no stdin"))?;
+        let requests: String = files.iter().map(|file| format!("{}\n", file.oid)).collect();
+        let writer = std::thread::spawn(move || stdin.write_all(requests.as_bytes()));
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| GuardianError::config("git cat-file has no stdout"))?;
+        let mut reader = BufReader::new(stdout);
+        let mut blobs = Vec::with_capacity(files.len());
+        for file in files {
+            let unreadable = || {
+                GuardianError::analysis(
+                    file.tree_path.clone(),
+                    format!("Blob {} could not be read from the repository", file.oid),
+                )
+            };
+            // Each blob is `<oid> blob <size>\n`, its content and a newline
+            let mut header = String::new();
+            reader.read_line(&mut header).map_err(failed)?;
+            let size: usize = match header.trim_end().split(' ').collect::<Vec<_>>()[..] {
+                [_, "blob", size] => size.parse().map_err(|_| unreadable())?,
+                _ => return Err(unreadable()),
+            };
+            let mut content = vec![0; size + 1];
+            reader.read_exact(&mut content).map_err(|_| unreadable())?;
+            content.truncate(size);
+            blobs.push(content);
+        }
+
+        drop(reader);
+        child.wait().map_err(failed)?;
+        writer
+            .join()
+            .map_err(|_| GuardianError::config("Failed to write to git cat-file"))?
+            .map_err(failed)?;
+        Ok(blobs)
+    }
+
+    /// Path relative to the repository root of a file reported by [`Revision::files`]
+    pub fn tree_path(&self, path: &Path) -> Option<String> {
+        match &self.worktree {
+            Some(worktree) => absolute_path(path)
+                .strip_prefix(worktree)
+                .ok()
+                .map(normalize_separators),
+            None => Some(normalize_separators(path)),
+        }
+    }
+
+    /// Path of the revision tree a selected path stands for
+    fn tree_prefix(&self, path: &Path) -> GuardianResult<PathBuf> {
+        let Some(worktree) = &self.worktree else {
+            return Ok(match normalize_separators(path) {
+                path if path == "." => PathBuf::new(),
+                path => PathBuf::from(path),
+            });
+        };
+        let absolute = fs::canonicalize(path).unwrap_or_else(|_| absolute_path(path));
+        absolute
+            .strip_prefix(worktree)
+            .map(Path::to_path_buf)
+            .map_err(|_| {
+                GuardianError::config(format!(
+                    "Path '{}' is outside the repository '{}'",
+                    path.display(),
+                    worktree.display()
+                ))
+            })
+    }
+}
+
+pub(super) fn git(repository: &Path, args: &[&str]) -> GuardianResult<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .output()
+        .map_err(|e| GuardianError::config(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(GuardianError::config(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+
+    #[test]
+    fn test_analyze_revision_without_checkout() {
+        let repo = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(repo.path())
+                .args([
+                    "-c",
+                    "user.name=Guardian",
+                    "-c",
+                    "user.email=guardian@example.com",
+                ])
+                .args(args)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !run(&["init", "-q"]) {
+            return; // git is not installed
+        }
+        fs::create_dir_all(repo.path().join("src")).unwrap();
+        fs::write(repo.path().join("src/lib.rs"), "// TODO: implement\n").unwrap();
+        fs::write(repo.path().join("README.md"), "# Readme\n").unwrap();
+        assert!(run(&["add", "."]));
+        assert!(run(&["commit", "-q", "-m", "Initial commit"]));
+        // The worktree no longer has the violation, the commit does
+        fs::write(repo.path().join("src/lib.rs"), "pub fn done() {}\n").unwrap();
+
+        let revision = Revision::resolve(repo.path(), "HEAD").unwrap();
+        assert_eq!(revision.commit().len(), 40);
+        let files = revision.files(&[repo.path().join("src")]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].tree_path, "src/lib.rs");
+        assert_eq!(
+            revision.read_blobs(&files).unwrap(),
+            [b"// TODO: implement\n".to_vec()]
+        );
+
+        let report = Analyzer::with_defaults()
+            .unwrap()
+            .analyze_revision(&revision, &[repo.path()], &AnalysisOptions::default())
+            .unwrap();
+        assert!(report
+            .violations
+            .iter()
+            .any(|violation| violation.rule_id == "todo_comments"
+                && violation.file_path.ends_with("src/lib.rs")));
+        assert!(Revision::resolve(repo.path(), "missing").is_err());
+    }
+}
@@ -7,12 +7,14 @@
 
 use crate::analyzer::FileAnalyzer;
 use crate::domain::violations::{GuardianResult, Severity, Violation};
+use crate::patterns::{FileContext, SourceLocation};
 
 #[cfg(test)]
 use crate::domain::violations::GuardianError;
 use quote::ToTokens;
 use std::path::Path;
 
+use syn::spanned::Spanned;
 use syn::visit::Visit;
 
 /// Specialized analyzer for Rust source files
@@ -42,9 +44,16 @@ impl RustAnalyzer {
     }
 
     /// Find all unimplemented macros in the file
-    fn find_unimplemented_macros(&self, syntax_tree: &syn::File, content: &str) -> Vec<Violation> {
+    fn find_unimplemented_macros(
+        &self,
+        syntax_tree: &syn::File,
+        content: &str,
+        file_path: &Path,
+    ) -> Vec<Violation> {
         let mut visitor = UnimplementedMacroVisitor {
             violations: Vec::new(),
+            content,
+            file_path: file_path.to_path_buf(),
             should_skip_tests: !self.analyze_tests && self.is_test_file_content(content),
         };
 
@@ -61,6 +70,7 @@ impl RustAnalyzer {
     ) -> Vec<Violation> {
         let mut visitor = EmptyOkReturnVisitor {
             violations: Vec::new(),
+            content,
             file_path: file_path.to_path_buf(),
             should_skip_tests: !self.analyze_tests && self.is_test_file_content(content),
         };
@@ -141,22 +151,23 @@ impl RustAnalyzer {
 
 impl FileAnalyzer for RustAnalyzer {
     fn analyze(&self, file_path: &Path, content: &str) -> GuardianResult<Vec<Violation>> {
+        self.analyze_context(&FileContext::new(file_path, content))
+    }
+
+    fn analyze_context(&self, file: &FileContext) -> GuardianResult<Vec<Violation>> {
+        let (file_path, content) = (file.path(), file.content());
         let mut violations = Vec::new();
 
-        // Parse the Rust syntax tree
-        let syntax_tree = match syn::parse_file(content) {
-            Ok(tree) => tree,
-            Err(e) => {
-                // If we can't parse as valid Rust, skip AST analysis
-                tracing::debug!("Failed to parse Rust file {}: {}", file_path.display(), e);
-                return Ok(violations);
-            }
+        // If we can't parse as valid Rust, skip AST analysis
+        let syntax_tree = match file.syntax_tree() {
+            Some(tree) => tree,
+            None => return Ok(violations),
         };
 
         // Apply various Rust-specific analyses
-        violations.extend(self.find_unimplemented_macros(&syntax_tree, content));
-        violations.extend(self.find_empty_ok_returns(&syntax_tree, content, file_path));
-        violations.extend(self.find_architectural_violations(&syntax_tree, file_path));
+        violations.extend(self.find_unimplemented_macros(syntax_tree, content, file_path));
+        violations.extend(self.find_empty_ok_returns(syntax_tree, content, file_path));
+        violations.extend(self.find_architectural_violations(syntax_tree, file_path));
         violations.extend(self.check_quality_headers(content, file_path));
 
         Ok(violations)
@@ -172,12 +183,14 @@ impl FileAnalyzer for RustAnalyzer {
 }
 
 /// Visitor for finding unimplemented macros
-struct UnimplementedMacroVisitor {
+struct UnimplementedMacroVisitor<'a> {
     violations: Vec<Violation>,
+    content: &'a str,
+    file_path: std::path::PathBuf,
     should_skip_tests: bool,
 }
 
-impl Visit<'_> for UnimplementedMacroVisitor {
+impl Visit<'_> for UnimplementedMacroVisitor<'_> {
     fn visit_macro(&mut self, mac: &syn::Macro) {
         if let Some(ident) = mac.path.get_ident() {
             let macro_name = ident.to_string();
@@ -200,13 +213,16 @@ impl Visit<'_> for UnimplementedMacroVisitor {
                     _ => format!("Implementation marker macro found: {macro_name}"),
                 };
 
+                let location = SourceLocation::from_span(self.content, mac.span());
                 let violation = Violation::new(
                     format!("{macro_name}_macro"),
                     severity,
-                    std::path::PathBuf::from(""), // Will be set by caller
+                    self.file_path.clone(),
                     message,
                 )
-                .with_position(1, 1)
+                .with_position(location.line, location.column)
+                .with_end_position(location.end_line, location.end_column)
+                .with_byte_range(location.byte_range)
                 .with_context(String::new());
 
                 self.violations.push(violation);
@@ -226,7 +242,7 @@ impl Visit<'_> for UnimplementedMacroVisitor {
     }
 }
 
-impl UnimplementedMacroVisitor {
+impl UnimplementedMacroVisitor<'_> {
     fn is_test_function(&self, func: &syn::ItemFn) -> bool {
         func.attrs.iter().any(|attr| {
             attr.path().is_ident("test")
@@ -236,13 +252,14 @@ impl UnimplementedMacroVisitor {
 }
 
 /// Visitor for finding functions that return Ok(()) with no real implementation
-struct EmptyOkReturnVisitor {
+struct EmptyOkReturnVisitor<'a> {
     violations: Vec<Violation>,
+    content: &'a str,
     file_path: std::path::PathBuf,
     should_skip_tests: bool,
 }
 
-impl Visit<'_> for EmptyOkReturnVisitor {
+impl Visit<'_> for EmptyOkReturnVisitor<'_> {
     fn visit_item_fn(&mut self, func: &syn::ItemFn) {
         // Skip test functions if we should skip tests
         if self.should_skip_tests && self.is_test_function(func) {
@@ -253,7 +270,7 @@ impl Visit<'_> for EmptyOkReturnVisitor {
         if let syn::ReturnType::Type(_, return_type) = &func.sig.output {
             if self.is_result_type(return_type) || self.is_option_type(return_type) {
                 // Check if body is just Ok(()) or similar minimal implementation
-                if let Some((line, col, context)) = self.find_trivial_ok_return(&func.block) {
+                if let Some((location, context)) = self.find_trivial_ok_return(&func.block) {
                     let violation = Violation::new(
                         "empty_ok_return",
                         Severity::Error,
@@ -263,7 +280,9 @@ impl Visit<'_> for EmptyOkReturnVisitor {
                             func.sig.ident
                         ),
                     )
-                    .with_position(line, col)
+                    .with_position(location.line, location.column)
+                    .with_end_position(location.end_line, location.end_column)
+                    .with_byte_range(location.byte_range)
                     .with_context(context)
                     .with_suggestion("Implement the function logic or remove if not needed");
 
@@ -276,7 +295,7 @@ impl Visit<'_> for EmptyOkReturnVisitor {
     }
 }
 
-impl EmptyOkReturnVisitor {
+impl EmptyOkReturnVisitor<'_> {
     fn is_test_function(&self, func: &syn::ItemFn) -> bool {
         func.attrs.iter().any(|attr| {
             attr.path().is_ident("test")
@@ -308,12 +327,18 @@ impl EmptyOkReturnVisitor {
         }
     }
 
-    fn find_trivial_ok_return(&self, block: &syn::Block) -> Option<(u32, u32, String)> {
+    fn find_trivial_ok_return(&self, block: &syn::Block) -> Option<(SourceLocation, String)> {
         // Look for blocks with only Ok(()) return or similar trivial implementations
         if block.stmts.len() == 1 {
             if let syn::Stmt::Expr(expr, _) = &block.stmts[0] {
                 if self.is_trivial_ok_expr(expr) || self.is_trivial_some_expr(expr) {
-                    return Some((1, 1, String::new()));
+                    let location = SourceLocation::from_span(self.content, expr.span());
+                    let context = self
+                        .content
+                        .get(location.byte_range.clone())
+                        .unwrap_or_default()
+                        .to_string();
+                    return Some((location, context));
                 }
             }
         }
@@ -653,3 +678,35 @@ pub fn validate_rust_analyzer_domain() -> GuardianResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macro_location() {
+        let content = "//! Greetings\r\n\r\nfn greet() {\r\n    let _ = \"héllo\"; unimplemented!(\r\n        \"needs implementation\"\r\n    )\r\n}";
+        let violations = RustAnalyzer::new()
+            .analyze(Path::new("src/greet.rs"), content)
+            .expect("analysis should succeed");
+        let violation = violations
+            .iter()
+            .find(|v| v.rule_id == "unimplemented_macro")
+            .expect("unimplemented! should be detected");
+
+        let macro_text = "unimplemented!(\r\n        \"needs implementation\"\r\n    )";
+        let start = content
+            .find(macro_text)
+            .expect("content should contain the macro");
+        assert_eq!(violation.file_path, Path::new("src/greet.rs"));
+        assert_eq!(
+            (violation.line_number, violation.column_number),
+            (Some(4), Some(22))
+        );
+        assert_eq!(
+            (violation.end_line, violation.end_column),
+            (Some(6), Some(6))
+        );
+        assert_eq!(violation.byte_range, Some(start..start + macro_text.len()));
+    }
+}
@@ -0,0 +1,173 @@
+//! Analysis scope: sparse checkouts and explicit scope directories
+//!
+//! Architecture: Domain Services - What is absent from the worktree is not necessarily gone
+//! - Files git marks skip-worktree are tracked but not checked out; they are out of scope, not deleted
+//! - A scope directory restricts a run to part of the project and is recorded in its report
+//! - State kept between runs is only judged stale for files that were in scope
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use crate::report::{absolute_path, normalize_separators};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Tracked files a sparse checkout leaves out of the worktree
+#[derive(Debug, Clone, Default)]
+pub struct SparseCheckout {
+    /// Absolute paths of skip-worktree files
+    skipped: HashSet<PathBuf>,
+}
+
+impl SparseCheckout {
+    /// Ask git which files of the repository containing `dir` are not checked out
+    ///
+    /// Outside a repository, or when git is unavailable, nothing counts as skipped.
+    pub fn detect(dir: &Path) -> Self {
+        let Some(toplevel) = git(dir, &["rev-parse", "--show-toplevel"]) else {
+            return Self::default();
+        };
+        let toplevel = PathBuf::from(toplevel.trim());
+        let Some(listing) = git(&toplevel, &["ls-files", "-t", "-z"]) else {
+            return Self::default();
+        };
+        Self::from_files(
+            listing
+                .split('\0')
+                .filter_map(|entry| entry.strip_prefix("S "))
+                .map(|file| toplevel.join(file)),
+        )
+    }
+
+    /// Sparse checkout leaving out the given files
+    pub fn from_files(files: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            skipped: files.into_iter().map(|file| absolute_path(&file)).collect(),
+        }
+    }
+
+    /// Whether a file is tracked but not checked out
+    pub fn is_skipped(&self, path: &Path) -> bool {
+        !self.skipped.is_empty() && self.skipped.contains(&absolute_path(path))
+    }
+
+    /// Number of files left out of the worktree
+    pub fn len(&self) -> usize {
+        self.skipped.len()
+    }
+
+    /// Whether the worktree holds every tracked file
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+/// Directory a run is restricted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    dir: PathBuf,
+    relative: String,
+}
+
+impl Scope {
+    /// Restrict a run to a directory, recorded relative to the project root
+    pub fn new(dir: &Path, root: &Path) -> GuardianResult<Self> {
+        if !dir.is_dir() {
+            return Err(GuardianError::config(format!(
+                "Scope '{}' is not a directory in the worktree",
+                dir.display()
+            )));
+        }
+        let absolute = absolute_path(dir);
+        let root = absolute_path(root);
+        let relative = absolute.strip_prefix(&root).map_err(|_| {
+            GuardianError::config(format!(
+                "Scope '{}' is outside the project root '{}'",
+                dir.display(),
+                root.display()
+            ))
+        })?;
+        Ok(Self {
+            relative: match normalize_separators(relative) {
+                relative if relative.is_empty() => ".".to_string(),
+                relative => relative,
+            },
+            dir: absolute,
+        })
+    }
+
+    /// The scope directory, as an absolute path
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The scope directory relative to the project root, with `/` separators
+    pub fn as_str(&self) -> &str {
+        &self.relative
+    }
+
+    /// Whether a path lies inside the scope
+    pub fn contains(&self, path: &Path) -> bool {
+        absolute_path(path).starts_with(&self.dir)
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_sparse_checkout_detection() {
+        let repo = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(repo.path())
+                .args(args)
+                .output()
+                .map(|output| output.status.success());
+            status.unwrap_or(false)
+        };
+        if !run(&["init", "-q"]) {
+            return; // git is not installed
+        }
+        fs::create_dir_all(repo.path().join("core")).unwrap();
+        fs::create_dir_all(repo.path().join("docs")).unwrap();
+        fs::write(repo.path().join("core/lib.rs"), "fn core() {}\n").unwrap();
+        fs::write(repo.path().join("docs/guide.rs"), "fn guide() {}\n").unwrap();
+        assert!(run(&["add", "."]));
+        assert!(run(&["update-index", "--skip-worktree", "docs/guide.rs"]));
+        fs::remove_file(repo.path().join("docs/guide.rs")).unwrap();
+
+        let sparse = SparseCheckout::detect(repo.path());
+        assert_eq!(sparse.len(), 1);
+        assert!(sparse.is_skipped(&repo.path().join("docs/guide.rs")));
+        assert!(!sparse.is_skipped(&repo.path().join("core/lib.rs")));
+    }
+
+    #[test]
+    fn test_scope() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("crates/core/src")).unwrap();
+
+        let scope = Scope::new(&root.path().join("crates/core"), root.path()).unwrap();
+        assert_eq!(scope.as_str(), "crates/core");
+        assert!(scope.contains(&root.path().join("crates/core/src/lib.rs")));
+        assert!(!scope.contains(&root.path().join("crates/cli/src/main.rs")));
+        assert_eq!(Scope::new(root.path(), root.path()).unwrap().as_str(), ".");
+        assert!(Scope::new(&root.path().join("missing"), root.path()).is_err());
+    }
+}
@@ -0,0 +1,480 @@
+//! Selection of the files compiled by cargo packages and build targets
+//!
+//! Architecture: Domain Services - Build targets, not directories, define what gets validated
+//! - Targets and their root files come from `cargo metadata`, exactly as cargo sees them
+//! - A target's files are its root file plus every file reached through `mod` declarations
+//! - Selection flags mirror cargo's `--package`, `--lib`, `--bins` and `--tests`
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Which packages and targets to analyze
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetSelection {
+    /// Package names; empty selects every workspace member
+    pub packages: Vec<String>,
+    /// Library targets
+    pub lib: bool,
+    /// Binary targets
+    pub bins: bool,
+    /// Targets built in test mode: integration tests and any target with `test = true`
+    pub tests: bool,
+}
+
+impl TargetSelection {
+    /// Whether nothing was selected, so analysis falls back to paths
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && !self.lib && !self.bins && !self.tests
+    }
+
+    /// Whether a target kind flag was given; without one every target of a package counts
+    fn has_kind_filter(&self) -> bool {
+        self.lib || self.bins || self.tests
+    }
+
+    fn includes(&self, target: &CargoTarget) -> bool {
+        if !self.has_kind_filter() {
+            return true;
+        }
+        (self.lib && target.is_lib())
+            || (self.bins && target.kinds.iter().any(|kind| kind == "bin"))
+            || (self.tests && (target.test || target.kinds.iter().any(|kind| kind == "test")))
+    }
+}
+
+/// A build target of a workspace package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoTarget {
+    /// Package the target belongs to
+    pub package: String,
+    /// Target name
+    pub name: String,
+    /// Target kinds as reported by cargo (`lib`, `bin`, `test`, `proc-macro`, ...)
+    pub kinds: Vec<String>,
+    /// Crate root file
+    pub src_path: PathBuf,
+    /// Whether the target is built by `cargo test`
+    pub test: bool,
+}
+
+impl CargoTarget {
+    /// Whether this is a library target of any crate type
+    pub fn is_lib(&self) -> bool {
+        self.kinds.iter().any(|kind| {
+            matches!(
+                kind.as_str(),
+                "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "proc-macro"
+            )
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    targets: Vec<MetadataTarget>,
+}
+
+#[derive(Deserialize)]
+struct MetadataTarget {
+    name: String,
+    kind: Vec<String>,
+    src_path: PathBuf,
+    #[serde(default)]
+    test: bool,
+}
+
+/// Build targets of the workspace members of a Cargo workspace
+#[derive(Debug, Clone, Default)]
+pub struct CargoTargets {
+    /// Workspace package names
+    packages: BTreeSet<String>,
+    targets: Vec<CargoTarget>,
+}
+
+impl CargoTargets {
+    /// Run `cargo metadata` for the workspace containing `directory`
+    pub fn from_directory<P: AsRef<Path>>(directory: P) -> GuardianResult<Self> {
+        let directory = directory.as_ref();
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+        let output = Command::new(cargo)
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .current_dir(directory)
+            .output()
+            .map_err(|e| {
+                GuardianError::analysis(
+                    directory.display().to_string(),
+                    format!("Failed to run cargo metadata: {e}"),
+                )
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::analysis(
+                directory.display().to_string(),
+                format!(
+                    "cargo metadata failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ));
+        }
+
+        Self::from_metadata(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Read targets from `cargo metadata --format-version 1` output
+    pub fn from_metadata(metadata_json: &str) -> GuardianResult<Self> {
+        let metadata: Metadata = serde_json::from_str(metadata_json)
+            .map_err(|e| GuardianError::validation(format!("Invalid cargo metadata: {e}")))?;
+
+        let members: BTreeSet<&str> = metadata
+            .workspace_members
+            .iter()
+            .map(String::as_str)
+            .collect();
+
+        let mut targets = Self::default();
+        for package in metadata
+            .packages
+            .into_iter()
+            .filter(|package| members.contains(package.id.as_str()))
+        {
+            targets.packages.insert(package.name.clone());
+            targets
+                .targets
+                .extend(package.targets.into_iter().map(|target| CargoTarget {
+                    package: package.name.clone(),
+                    name: target.name,
+                    kinds: target.kind,
+                    src_path: target.src_path,
+                    test: target.test,
+                }));
+        }
+
+        Ok(targets)
+    }
+
+    /// All targets of the workspace members
+    pub fn targets(&self) -> &[CargoTarget] {
+        &self.targets
+    }
+
+    /// Targets matching a selection
+    ///
+    /// Fails when a selected package is not a workspace member, like cargo does.
+    pub fn select(&self, selection: &TargetSelection) -> GuardianResult<Vec<&CargoTarget>> {
+        for package in &selection.packages {
+            if !self.packages.contains(package) {
+                return Err(GuardianError::config(format!(
+                    "Package '{package}' is not a member of the workspace (members: {})",
+                    self.packages
+                        .iter()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+
+        Ok(self
+            .targets
+            .iter()
+            .filter(|target| {
+                selection.packages.is_empty() || selection.packages.contains(&target.package)
+            })
+            .filter(|target| selection.includes(target))
+            .collect())
+    }
+
+    /// Source files compiled by the selected targets, sorted and without duplicates
+    pub fn selected_files(&self, selection: &TargetSelection) -> GuardianResult<Vec<PathBuf>> {
+        let mut files = BTreeSet::new();
+        for target in self.select(selection)? {
+            collect_module_files(&target.src_path, &mut files);
+        }
+        Ok(files.into_iter().collect())
+    }
+}
+
+/// Collect a crate root and every file reached from it through `mod` declarations
+///
+/// Files that do not parse are kept, but their declarations cannot be followed; declared
+/// modules whose file is missing are skipped.
+pub fn collect_module_files(crate_root: &Path, files: &mut BTreeSet<PathBuf>) {
+    let directory = crate_root.parent().unwrap_or(Path::new("")).to_path_buf();
+    collect_file(crate_root, &directory, files);
+}
+
+/// Add a file and follow its module declarations, resolving them against `directory`
+fn collect_file(file: &Path, directory: &Path, files: &mut BTreeSet<PathBuf>) {
+    if !file.is_file() || !files.insert(file.to_path_buf()) {
+        return;
+    }
+
+    let syntax_tree = match fs::read_to_string(file)
+        .ok()
+        .and_then(|content| syn::parse_file(&content).ok())
+    {
+        Some(syntax_tree) => syntax_tree,
+        None => return,
+    };
+
+    collect_items(&syntax_tree.items, file, directory, files);
+}
+
+/// Follow the module declarations among `items`
+fn collect_items(
+    items: &[syn::Item],
+    file: &Path,
+    directory: &Path,
+    files: &mut BTreeSet<PathBuf>,
+) {
+    for item in items {
+        let module = match item {
+            syn::Item::Mod(module) => module,
+            _ => continue,
+        };
+        let name = module.ident.to_string();
+        let explicit_path = path_attribute(&module.attrs);
+
+        match &module.content {
+            // `mod name { ... }` nests the directory of its own declarations
+            Some((_, items)) => {
+                let nested = match &explicit_path {
+                    Some(path) => directory.join(path),
+                    None => directory.join(&name),
+                };
+                collect_items(items, file, &nested, files);
+            }
+            // `#[path = "..."] mod name;` is relative to the declaring file's directory
+            None => match explicit_path {
+                Some(path) => {
+                    let base = file.parent().unwrap_or(Path::new(""));
+                    let target = base.join(path);
+                    let nested = module_directory(&target);
+                    collect_file(&target, &nested, files);
+                }
+                None => {
+                    let flat = directory.join(format!("{name}.rs"));
+                    let target = if flat.is_file() {
+                        flat
+                    } else {
+                        directory.join(&name).join("mod.rs")
+                    };
+                    let nested = module_directory(&target);
+                    collect_file(&target, &nested, files);
+                }
+            },
+        }
+    }
+}
+
+/// Directory holding the submodules declared in a module file
+///
+/// `mod.rs` files declare submodules next to themselves; `name.rs` files declare them in
+/// the `name/` directory beside them.
+fn module_directory(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    match file.file_stem().and_then(|stem| stem.to_str()) {
+        Some("mod") | None => parent.to_path_buf(),
+        Some(stem) => parent.join(stem),
+    }
+}
+
+/// The value of a `#[path = "..."]` attribute
+fn path_attribute(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        syn::Meta::NameValue(meta) if meta.path.is_ident("path") => match &meta.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(path),
+                ..
+            }) => Some(path.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) -> PathBuf {
+        let file = root.join(path);
+        fs::create_dir_all(file.parent().expect("parent")).expect("directory");
+        fs::write(&file, content).expect("file should be writable");
+        file
+    }
+
+    fn metadata(root: &Path) -> String {
+        serde_json::json!({
+            "workspace_members": ["app 0.1.0", "tool 0.1.0"],
+            "packages": [
+                {
+                    "id": "app 0.1.0",
+                    "name": "app",
+                    "targets": [
+                        {"name": "app", "kind": ["lib"], "src_path": root.join("app/src/lib.rs"), "test": true},
+                        {"name": "app", "kind": ["bin"], "src_path": root.join("app/src/main.rs"), "test": true},
+                        {"name": "api", "kind": ["test"], "src_path": root.join("app/tests/api.rs"), "test": true}
+                    ]
+                },
+                {
+                    "id": "tool 0.1.0",
+                    "name": "tool",
+                    "targets": [
+                        {"name": "tool", "kind": ["bin"], "src_path": root.join("tool/src/main.rs"), "test": false}
+                    ]
+                },
+                {
+                    "id": "serde 1.0.0",
+                    "name": "serde",
+                    "targets": [
+                        {"name": "serde", "kind": ["lib"], "src_path": "/registry/serde/src/lib.rs"}
+                    ]
+                }
+            ]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_collect_module_files() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        let lib = write(
+            root,
+            "src/lib.rs",
+            "mod config;\nmod net { mod client; }\n#[path = \"gen/out.rs\"]\nmod generated;\n#[cfg(test)]\nmod tests { fn t() {} }\n",
+        );
+        write(root, "src/config.rs", "mod loader;\n");
+        write(root, "src/config/loader.rs", "");
+        write(root, "src/net/client/mod.rs", "mod tls;\n");
+        write(root, "src/net/client/tls.rs", "");
+        write(root, "src/gen/out.rs", "");
+        write(root, "src/unused.rs", "");
+
+        let mut files = BTreeSet::new();
+        collect_module_files(&lib, &mut files);
+        let relative: Vec<String> = files
+            .iter()
+            .map(|file| {
+                file.strip_prefix(root)
+                    .expect("inside root")
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+
+        assert_eq!(
+            relative,
+            // Paths order by component, so `config/` sorts before `config.rs`
+            vec![
+                "src/config/loader.rs",
+                "src/config.rs",
+                "src/gen/out.rs",
+                "src/lib.rs",
+                "src/net/client/mod.rs",
+                "src/net/client/tls.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_targets() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        let targets = CargoTargets::from_metadata(&metadata(root)).expect("metadata");
+        let names = |selection: TargetSelection| -> Vec<String> {
+            targets
+                .select(&selection)
+                .expect("selection")
+                .iter()
+                .map(|target| format!("{}:{}", target.package, target.kinds[0]))
+                .collect()
+        };
+
+        // Dependencies are not workspace members
+        assert_eq!(targets.targets().len(), 4);
+        assert_eq!(
+            names(TargetSelection {
+                packages: vec!["app".to_string()],
+                ..Default::default()
+            }),
+            vec!["app:lib", "app:bin", "app:test"]
+        );
+        assert_eq!(
+            names(TargetSelection {
+                bins: true,
+                ..Default::default()
+            }),
+            vec!["app:bin", "tool:bin"]
+        );
+        assert_eq!(
+            names(TargetSelection {
+                packages: vec!["app".to_string()],
+                lib: true,
+                ..Default::default()
+            }),
+            vec!["app:lib"]
+        );
+        assert_eq!(
+            names(TargetSelection {
+                tests: true,
+                ..Default::default()
+            }),
+            vec!["app:lib", "app:bin", "app:test"]
+        );
+
+        let unknown = TargetSelection {
+            packages: vec!["missing".to_string()],
+            ..Default::default()
+        };
+        assert!(targets.select(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_selected_files() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        write(root, "app/src/lib.rs", "pub mod model;\n");
+        write(root, "app/src/model.rs", "");
+        write(root, "app/src/main.rs", "fn main() {}\n");
+        write(root, "app/tests/api.rs", "mod common;\n");
+        write(root, "app/tests/common/mod.rs", "");
+        write(root, "tool/src/main.rs", "fn main() {}\n");
+
+        let targets = CargoTargets::from_metadata(&metadata(root)).expect("metadata");
+        let files = targets
+            .selected_files(&TargetSelection {
+                packages: vec!["app".to_string()],
+                lib: true,
+                ..Default::default()
+            })
+            .expect("files");
+        assert_eq!(
+            files,
+            vec![root.join("app/src/lib.rs"), root.join("app/src/model.rs")]
+        );
+
+        let files = targets
+            .selected_files(&TargetSelection {
+                packages: vec!["app".to_string()],
+                ..Default::default()
+            })
+            .expect("files");
+        assert_eq!(files.len(), 5);
+    }
+}
@@ -5,10 +5,11 @@
 //! - Hash-based validation ensures cache coherence with minimal overhead
 //! - Domain objects remain pure while infrastructure handles caching concerns
 
-use crate::domain::violations::{GuardianError, GuardianResult};
+use crate::analyzer::scope::SparseCheckout;
+use crate::domain::violations::{GuardianError, GuardianResult, Violation};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
@@ -49,6 +50,9 @@ struct CacheMetadata {
     hits: u64,
     /// Number of cache misses since creation
     misses: u64,
+    /// Number of lookups that reused some rules' results since creation
+    #[serde(default)]
+    partial_hits: u64,
 }
 
 /// Cached information about a single file
@@ -66,6 +70,28 @@ pub struct FileEntry {
     pub analyzed_at: u64,
     /// Configuration fingerprint when analysis was done
     pub config_fingerprint: String,
+    /// Fingerprint of each rule's definition when analysis was done
+    #[serde(default)]
+    pub rule_fingerprints: BTreeMap<String, String>,
+    /// Violations found in this file, replayed while their rules are unchanged
+    #[serde(default)]
+    pub violations: Vec<Violation>,
+}
+
+/// Outcome of looking up a file with per-rule fingerprints
+#[derive(Debug, Clone)]
+pub enum CacheLookup {
+    /// No usable entry; the whole file must be analyzed
+    Miss,
+    /// Every cached result is still valid
+    Hit(Vec<Violation>),
+    /// Results of unchanged rules are still valid; the stale rules must re-run
+    Partial {
+        /// Cached violations of the unchanged rules
+        violations: Vec<Violation>,
+        /// Rules that are new or whose definition changed
+        stale_rules: HashSet<String>,
+    },
 }
 
 impl FileCache {
@@ -100,6 +126,7 @@ impl FileCache {
                     updated_at: current_timestamp(),
                     hits: 0,
                     misses: 0,
+                    partial_hits: 0,
                 },
             };
             self.dirty = true;
@@ -232,6 +259,8 @@ impl FileCache {
             violation_count,
             analyzed_at: current_timestamp(),
             config_fingerprint: config_fingerprint.to_string(),
+            rule_fingerprints: BTreeMap::new(),
+            violations: Vec::new(),
         };
 
         self.data.files.insert(file_path.to_path_buf(), entry);
@@ -240,15 +269,112 @@ impl FileCache {
         Ok(())
     }
 
+    /// Look up a file, keeping the cached results of rules whose definition is unchanged
+    ///
+    /// Entries are only reused when the file content and the shared fingerprint match.
+    /// Results of removed rules are dropped, and new or changed rules are returned as stale.
+    /// Violations of rules without a fingerprint (built-in and procedural checks) stay valid
+    /// as long as the shared fingerprint does.
+    pub fn lookup<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        shared_fingerprint: &str,
+        rule_fingerprints: &BTreeMap<String, String>,
+    ) -> GuardianResult<CacheLookup> {
+        let file_path = file_path.as_ref();
+
+        let lookup = match self.data.files.get(file_path) {
+            Some(entry)
+                if entry.config_fingerprint == shared_fingerprint
+                    && self.is_unchanged(file_path, entry)? =>
+            {
+                let stale_rules: HashSet<String> = rule_fingerprints
+                    .iter()
+                    .filter(|(id, fingerprint)| {
+                        entry.rule_fingerprints.get(*id) != Some(*fingerprint)
+                    })
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                let violations: Vec<Violation> = entry
+                    .violations
+                    .iter()
+                    .filter(|violation| {
+                        let rule_id = &violation.rule_id;
+                        if rule_fingerprints.contains_key(rule_id) {
+                            !stale_rules.contains(rule_id)
+                        } else {
+                            !entry.rule_fingerprints.contains_key(rule_id)
+                        }
+                    })
+                    .cloned()
+                    .collect();
+
+                if stale_rules.is_empty() {
+                    CacheLookup::Hit(violations)
+                } else {
+                    CacheLookup::Partial {
+                        violations,
+                        stale_rules,
+                    }
+                }
+            }
+            _ => CacheLookup::Miss,
+        };
+
+        match lookup {
+            CacheLookup::Miss => self.data.metadata.misses += 1,
+            CacheLookup::Hit(_) => self.data.metadata.hits += 1,
+            CacheLookup::Partial { .. } => self.data.metadata.partial_hits += 1,
+        }
+        self.dirty = true;
+
+        Ok(lookup)
+    }
+
+    /// Store a file's violations with the fingerprints they were produced under
+    ///
+    /// Results of cross-file rules depend on other files and must not be stored here.
+    pub fn record<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        violations: Vec<Violation>,
+        shared_fingerprint: &str,
+        rule_fingerprints: &BTreeMap<String, String>,
+    ) -> GuardianResult<()> {
+        let file_path = file_path.as_ref();
+        self.update_entry(file_path, violations.len(), shared_fingerprint)?;
+
+        if let Some(entry) = self.data.files.get_mut(file_path) {
+            entry.rule_fingerprints = rule_fingerprints.clone();
+            entry.violations = violations;
+        }
+
+        Ok(())
+    }
+
+    /// Every cached violation, ordered by file, and the number of cached files
+    pub fn violations(&self) -> (Vec<Violation>, usize) {
+        let mut entries: Vec<_> = self.data.files.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let violations = entries
+            .into_iter()
+            .flat_map(|(_, entry)| entry.violations.iter().cloned())
+            .collect();
+        (violations, self.data.files.len())
+    }
+
     /// Get cache statistics
     pub fn statistics(&self) -> CacheStatistics {
+        let metadata = &self.data.metadata;
+        let lookups = metadata.hits + metadata.partial_hits + metadata.misses;
+
         CacheStatistics {
             total_files: self.data.files.len(),
-            cache_hits: self.data.metadata.hits,
-            cache_misses: self.data.metadata.misses,
-            hit_rate: if self.data.metadata.hits + self.data.metadata.misses > 0 {
-                (self.data.metadata.hits as f64)
-                    / ((self.data.metadata.hits + self.data.metadata.misses) as f64)
+            cache_hits: metadata.hits,
+            partial_hits: metadata.partial_hits,
+            cache_misses: metadata.misses,
+            hit_rate: if lookups > 0 {
+                (metadata.hits as f64) / (lookups as f64)
             } else {
                 0.0
             },
@@ -262,6 +388,7 @@ impl FileCache {
         self.data.files.clear();
         self.data.metadata.hits = 0;
         self.data.metadata.misses = 0;
+        self.data.metadata.partial_hits = 0;
         self.data.metadata.updated_at = current_timestamp();
         self.dirty = true;
 
@@ -275,12 +402,15 @@ impl FileCache {
     }
 
     /// Remove cache entries for files that no longer exist
-    pub fn cleanup(&mut self) -> GuardianResult<usize> {
+    ///
+    /// Files a sparse checkout leaves out are not in scope rather than deleted, so their
+    /// entries are kept for runs that check them out again.
+    pub fn cleanup(&mut self, sparse: &SparseCheckout) -> GuardianResult<usize> {
         let mut removed = 0;
         let mut to_remove = Vec::new();
 
         for file_path in self.data.files.keys() {
-            if !file_path.exists() {
+            if !file_path.exists() && !sparse.is_skipped(file_path) {
                 to_remove.push(file_path.clone());
             }
         }
@@ -305,6 +435,25 @@ impl FileCache {
         }
     }
 
+    /// Whether a file still has the size, modification time and content of its entry
+    fn is_unchanged(&self, file_path: &Path, entry: &FileEntry) -> GuardianResult<bool> {
+        let metadata = match fs::metadata(file_path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(false),
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs());
+
+        if entry.size != metadata.len() || modified_at != Some(entry.modified_at) {
+            return Ok(false);
+        }
+
+        Ok(entry.content_hash == self.calculate_file_hash(file_path)?)
+    }
+
     /// Calculate SHA-256 hash of file content
     fn calculate_file_hash<P: AsRef<Path>>(&self, file_path: P) -> GuardianResult<String> {
         let mut file = File::open(&file_path)
@@ -367,6 +516,7 @@ impl Default for CacheMetadata {
             updated_at: now,
             hits: 0,
             misses: 0,
+            partial_hits: 0,
         }
     }
 }
@@ -376,6 +526,7 @@ impl Default for CacheMetadata {
 pub struct CacheStatistics {
     pub total_files: usize,
     pub cache_hits: u64,
+    pub partial_hits: u64,
     pub cache_misses: u64,
     pub hit_rate: f64,
     pub created_at: u64,
@@ -386,10 +537,11 @@ impl CacheStatistics {
     /// Format statistics for display
     pub fn format_display(&self) -> String {
         format!(
-            "Cache: {} files, {:.1}% hit rate ({} hits, {} misses)",
+            "Cache: {} files, {:.1}% hit rate ({} hits, {} partial, {} misses)",
             self.total_files,
             self.hit_rate * 100.0,
             self.cache_hits,
+            self.partial_hits,
             self.cache_misses
         )
     }
@@ -397,12 +549,10 @@ impl CacheStatistics {
 
 /// Get current timestamp as seconds since Unix epoch
 fn current_timestamp() -> u64 {
+    // A clock set before the epoch makes every entry look old rather than aborting the run
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .expect(
-            "System time should be after Unix epoch - this indicates a serious system clock issue",
-        )
-        .as_secs()
+        .map_or(0, |elapsed| elapsed.as_secs())
 }
 
 impl FileCache {
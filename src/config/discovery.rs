@@ -0,0 +1,133 @@
+//! Project root and configuration file discovery
+//!
+//! Code Quality Principle: Location Independence - Results never depend on the invocation directory
+//! - The project root is the Cargo workspace root, else the git repository root,
+//!   else the nearest crate root
+//! - Configuration files are found by walking up from the working directory to that root
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Configuration file names, in order of precedence within a directory
+pub const CONFIG_FILE_NAMES: &[&str] = &["guardian.yaml", "guardian.yml", ".guardian.yaml"];
+
+/// Locate the project root containing `start`
+///
+/// A `Cargo.toml` declaring `[workspace]` wins; the search stops at the repository root
+/// (a directory containing `.git`). Outside a repository, the nearest `Cargo.toml`
+/// decides. Returns `None` when no marker is found.
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    let mut nearest_manifest = None;
+
+    for dir in start.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        if manifest.is_file() {
+            if is_workspace_manifest(&manifest) {
+                return Some(dir.to_path_buf());
+            }
+            nearest_manifest.get_or_insert_with(|| dir.to_path_buf());
+        }
+
+        if dir.join(".git").exists() {
+            return Some(dir.to_path_buf());
+        }
+    }
+
+    nearest_manifest
+}
+
+/// Find the closest configuration file from `start` up to and including `root`
+pub fn find_config_file(start: &Path, root: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if let Some(config) = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        {
+            return Some(config);
+        }
+
+        if dir == root {
+            break;
+        }
+    }
+
+    None
+}
+
+/// Find the outermost locked configuration file from `start` up to and including `root`
+///
+/// A locked config applies to the whole tree below it, so nested config files closer to
+/// `start` cannot replace it.
+pub fn find_locked_config_file(start: &Path, root: &Path) -> Option<PathBuf> {
+    let mut locked = None;
+
+    for dir in start.ancestors() {
+        if let Some(config) = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        {
+            if super::policy::is_locked_file(&config) {
+                locked = Some(config);
+            }
+        }
+
+        if dir == root {
+            break;
+        }
+    }
+
+    locked
+}
+
+fn is_workspace_manifest(manifest: &Path) -> bool {
+    fs::read_to_string(manifest).is_ok_and(|contents| {
+        contents
+            .lines()
+            .any(|line| line.trim_start().starts_with("[workspace]"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_root_and_config_discovery() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let repo = temp_dir.path();
+        let member = repo.join("crates/core/src");
+        fs::create_dir_all(repo.join(".git")).expect("git dir should be creatable");
+        fs::create_dir_all(&member).expect("member dir should be creatable");
+        fs::write(
+            repo.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"core\"\n",
+        )
+        .expect("member manifest should be writable");
+
+        // Without a workspace manifest, the repository root is the project root
+        assert_eq!(find_project_root(&member), Some(repo.to_path_buf()));
+
+        fs::write(
+            repo.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .expect("workspace manifest should be writable");
+        assert_eq!(find_project_root(&member), Some(repo.to_path_buf()));
+
+        // The closest configuration file wins, but the search stops at the root
+        assert_eq!(find_config_file(&member, repo), None);
+        fs::write(repo.join("guardian.yaml"), "").expect("config should be writable");
+        assert_eq!(
+            find_config_file(&member, repo),
+            Some(repo.join("guardian.yaml"))
+        );
+        fs::write(repo.join("crates/core/.guardian.yaml"), "").expect("config should be writable");
+        assert_eq!(
+            find_config_file(&member, repo),
+            Some(repo.join("crates/core/.guardian.yaml"))
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Configuration inheritance through `extends` and `rule_packs`
+//!
+//! Code Quality Principle: Single Source of Policy - Repositories layer local tweaks on a shared base
+//! - `extends` names one or more base configs: file paths, `https://` URLs, or
+//!   `{ url, sha256 }` mappings pinning remote contents
+//! - Bases are merged in order, then the extending config on top
+//! - `rule_packs` names directories of rule fragments merged into `patterns`
+//! - Mappings merge key by key, rule lists merge by rule `id`, other values are replaced
+//! - A locked base cannot be extended, since that would let local config weaken it
+
+use super::remote::RemoteConfig;
+use crate::domain::violations::{GuardianError, GuardianResult};
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest chain of `extends` followed before assuming a cycle
+const MAX_DEPTH: usize = 8;
+
+/// Replace the `extends` key of a parsed config with the merged contents of its bases
+///
+/// Relative base paths are resolved against `base_dir`, the directory of the extending file.
+pub fn resolve_extends(value: Value, base_dir: Option<&Path>) -> GuardianResult<Value> {
+    resolve(value, base_dir, 0)
+}
+
+fn resolve(mut value: Value, base_dir: Option<&Path>, depth: usize) -> GuardianResult<Value> {
+    let extends = match value.as_mapping_mut().and_then(|map| map.remove("extends")) {
+        Some(extends) => extends,
+        None => return Ok(value),
+    };
+    if depth >= MAX_DEPTH {
+        return Err(GuardianError::config(format!(
+            "Configuration 'extends' chain is deeper than {MAX_DEPTH} levels; is it circular?"
+        )));
+    }
+
+    let sources = match extends {
+        Value::Sequence(sources) => sources,
+        source => vec![source],
+    };
+
+    let mut merged = Value::Null;
+    for source in &sources {
+        let (label, contents, next_dir) = load_base(source, base_dir)?;
+        let base: Value = serde_yaml::from_str(&contents).map_err(|e| {
+            GuardianError::config(format!("Failed to parse extended config '{label}': {e}"))
+        })?;
+        merged = merge(merged, resolve(base, next_dir.as_deref(), depth + 1)?);
+    }
+
+    if merged
+        .get("locked")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return Err(GuardianError::config(
+            "A locked configuration cannot be extended; use it directly instead",
+        ));
+    }
+
+    Ok(merge(merged, value))
+}
+
+/// Merge the rule fragments found in the directories listed under `rule_packs`
+///
+/// Each `.yaml`/`.yml` file in a pack maps category names to categories, exactly like
+/// `patterns`. Files are merged in name order, pack by pack, and the config's own `patterns`
+/// go on top so a project can still adjust or disable pack rules by `id`.
+pub fn merge_rule_packs(mut value: Value, base_dir: Option<&Path>) -> GuardianResult<Value> {
+    let packs: Vec<String> = match value.get("rule_packs").and_then(Value::as_sequence) {
+        Some(packs) => packs
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        None => return Ok(value),
+    };
+
+    let mut fragments = Value::Null;
+    for pack in &packs {
+        let dir = match base_dir {
+            Some(base_dir) => base_dir.join(pack),
+            None => PathBuf::from(pack),
+        };
+        for file in fragment_files(&dir)? {
+            let contents = fs::read_to_string(&file).map_err(|e| GuardianError::io(&file, e))?;
+            let fragment: Value = serde_yaml::from_str(&contents).map_err(|e| {
+                GuardianError::config(format!(
+                    "Failed to parse rule pack file '{}': {e}",
+                    file.display()
+                ))
+            })?;
+            if !fragment.is_mapping() {
+                return Err(GuardianError::config(format!(
+                    "Rule pack file '{}' must map category names to categories",
+                    file.display()
+                )));
+            }
+            fragments = merge(fragments, fragment);
+        }
+    }
+
+    if let Some(map) = value.as_mapping_mut() {
+        let own = map.remove("patterns").unwrap_or(Value::Null);
+        map.insert(Value::from("patterns"), merge(fragments, own));
+    }
+    Ok(value)
+}
+
+/// YAML files directly inside a rule pack directory, sorted by name
+fn fragment_files(dir: &Path) -> GuardianResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| GuardianError::io(dir, e))? {
+        let path = entry.map_err(|e| GuardianError::io(dir, e))?.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        if is_yaml && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Read one `extends` entry, returning its label, contents and directory for nested bases
+fn load_base(
+    source: &Value,
+    base_dir: Option<&Path>,
+) -> GuardianResult<(String, String, Option<PathBuf>)> {
+    let spec = match source {
+        Value::String(spec) => spec.clone(),
+        Value::Mapping(map) => {
+            let url = map.get("url").and_then(Value::as_str).ok_or_else(|| {
+                GuardianError::config("An 'extends' mapping needs a 'url' string")
+            })?;
+            match map.get("sha256").and_then(Value::as_str) {
+                Some(sha256) => format!("{url}#sha256={sha256}"),
+                None => url.to_string(),
+            }
+        }
+        _ => {
+            return Err(GuardianError::config(
+                "'extends' entries must be paths, URLs or { url, sha256 } mappings",
+            ))
+        }
+    };
+
+    if let Some(remote) = RemoteConfig::parse(&spec) {
+        let remote = remote?;
+        let contents = remote.load()?;
+        return Ok((remote.url, contents, None));
+    }
+
+    let path = match base_dir {
+        Some(dir) => dir.join(&spec),
+        None => PathBuf::from(&spec),
+    };
+    let contents = fs::read_to_string(&path).map_err(|e| GuardianError::io(&path, e))?;
+    let next_dir = path.parent().map(Path::to_path_buf);
+    Ok((spec, contents, next_dir))
+}
+
+/// Overlay `overlay` on `base`
+fn merge(base: Value, overlay: Value) -> Value {
+    match (base, overlay) {
+        (Value::Mapping(mut base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                let merged = match base.remove(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Mapping(base)
+        }
+        (Value::Sequence(base), Value::Sequence(overlay))
+            if base
+                .iter()
+                .chain(&overlay)
+                .all(|item| rule_id(item).is_some()) =>
+        {
+            Value::Sequence(merge_rules(base, overlay))
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+/// Merge rule lists by `id`, keeping base order and appending new rules
+fn merge_rules(mut base: Vec<Value>, overlay: Vec<Value>) -> Vec<Value> {
+    for rule in overlay {
+        let existing = base.iter().position(|item| rule_id(item) == rule_id(&rule));
+        match existing {
+            Some(index) => {
+                let current = std::mem::replace(&mut base[index], Value::Null);
+                base[index] = merge(current, rule);
+            }
+            None => base.push(rule),
+        }
+    }
+    base
+}
+
+fn rule_id(value: &Value) -> Option<&str> {
+    value
+        .as_mapping()
+        .and_then(|map| map.get("id"))
+        .and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_local_extends_merges_rules_by_id() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        fs::create_dir_all(temp_dir.path().join("shared")).expect("dir should be creatable");
+        fs::write(
+            temp_dir.path().join("shared/base.yaml"),
+            r#"
+version: "1.0"
+paths:
+  patterns: ["target/"]
+patterns:
+  placeholders:
+    severity: error
+    enabled: true
+    rules:
+      - id: todo_comments
+        type: regex
+        pattern: 'TODO'
+        message: "TODO found"
+      - id: fixme_comments
+        type: regex
+        pattern: 'FIX'
+        message: "Fix found"
+"#,
+        )
+        .expect("base should be written");
+
+        let local: Value = serde_yaml::from_str(
+            r#"
+extends: shared/base.yaml
+patterns:
+  placeholders:
+    rules:
+      - id: todo_comments
+        enabled: false
+      - id: hack_comments
+        type: regex
+        pattern: 'HACK'
+        message: "Hack found"
+"#,
+        )
+        .expect("local config should parse");
+
+        let merged = resolve_extends(local, Some(temp_dir.path())).expect("extends should resolve");
+        assert!(merged.get("extends").is_none());
+        assert_eq!(merged["version"], Value::from("1.0"));
+
+        let rules = merged["patterns"]["placeholders"]["rules"]
+            .as_sequence()
+            .expect("rules should be a list");
+        let ids: Vec<&str> = rules.iter().filter_map(rule_id).collect();
+        assert_eq!(ids, ["todo_comments", "fixme_comments", "hack_comments"]);
+        assert_eq!(rules[0]["enabled"], Value::from(false));
+        assert_eq!(rules[0]["pattern"], Value::from("TODO"));
+
+        // Locked bases cannot be extended
+        fs::write(
+            temp_dir.path().join("locked.yaml"),
+            "version: \"1.0\"\nlocked: true\n",
+        )
+        .expect("locked base should be written");
+        let extending: Value =
+            serde_yaml::from_str("extends: locked.yaml\n").expect("config should parse");
+        assert!(resolve_extends(extending, Some(temp_dir.path())).is_err());
+    }
+
+    #[test]
+    fn test_rule_packs_merge_under_own_patterns() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let pack = temp_dir.path().join("rules");
+        fs::create_dir_all(&pack).expect("pack dir should be creatable");
+        fs::write(
+            pack.join("async.yaml"),
+            r#"
+async_hygiene:
+  severity: warning
+  enabled: true
+  rules:
+    - id: blocking_sleep
+      type: regex
+      pattern: 'thread::sleep'
+      message: "Blocking sleep in async code"
+"#,
+        )
+        .expect("fragment should be written");
+        fs::write(pack.join("notes.txt"), "not a fragment").expect("file should be written");
+
+        let config: Value = serde_yaml::from_str(
+            r#"
+rule_packs: [rules]
+patterns:
+  async_hygiene:
+    rules:
+      - id: blocking_sleep
+        severity: error
+"#,
+        )
+        .expect("config should parse");
+
+        let merged =
+            merge_rule_packs(config, Some(temp_dir.path())).expect("rule packs should merge");
+        let category = &merged["patterns"]["async_hygiene"];
+        assert_eq!(category["severity"], Value::from("warning"));
+        assert_eq!(
+            category["rules"][0]["pattern"],
+            Value::from("thread::sleep")
+        );
+        assert_eq!(category["rules"][0]["severity"], Value::from("error"));
+
+        let missing: Value =
+            serde_yaml::from_str("rule_packs: [absent]\n").expect("config should parse");
+        assert!(merge_rule_packs(missing, Some(temp_dir.path())).is_err());
+    }
+}
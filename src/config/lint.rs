@@ -0,0 +1,284 @@
+//! Linting of configurations for rules that cannot do what they were written for
+//!
+//! Code Quality Principle: Fail Loudly - A rule that silently never fires is worse than no rule
+//! - Scopes are checked against the files actually in the repository
+//! - Regex anchors are checked against how regex rules are applied, to whole files
+//! - Duplicate rules and empty categories are reported so configurations stay minimal
+
+use super::{GuardianConfig, PatternCategory, PatternRule, RuleType};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Kind of problem found in a configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// An `applies_to` glob matches no file
+    DeadScope,
+    /// A regex anchor can only match at the start or end of a whole file
+    MisanchoredRegex,
+    /// A rule reports exactly what another rule already reports
+    DuplicateRule,
+    /// An enabled category has no enabled rules
+    EmptyCategory,
+}
+
+impl LintKind {
+    /// Stable name for output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintKind::DeadScope => "dead-scope",
+            LintKind::MisanchoredRegex => "misanchored-regex",
+            LintKind::DuplicateRule => "duplicate-rule",
+            LintKind::EmptyCategory => "empty-category",
+        }
+    }
+}
+
+/// One problem found in a configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    pub kind: LintKind,
+    /// Category the problem is in
+    pub category: String,
+    /// Rule the problem is in, if it concerns a single rule
+    pub rule_id: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rule_id {
+            Some(rule_id) => write!(
+                f,
+                "[{}] {}/{}: {}",
+                self.kind.as_str(),
+                self.category,
+                rule_id,
+                self.message
+            ),
+            None => write!(
+                f,
+                "[{}] {}: {}",
+                self.kind.as_str(),
+                self.category,
+                self.message
+            ),
+        }
+    }
+}
+
+/// Lint the enabled rules of a configuration against the files of a repository
+///
+/// `files` are paths relative to the directory Guardian runs from, as rule scopes see them.
+/// Warnings are ordered by category, then by rule.
+pub fn lint_config(config: &GuardianConfig, files: &[PathBuf]) -> Vec<LintWarning> {
+    let categories: BTreeMap<&String, &PatternCategory> = config
+        .patterns
+        .iter()
+        .filter(|(_, category)| category.enabled)
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut seen: Vec<(&str, &PatternRule)> = Vec::new();
+
+    for (name, category) in categories {
+        let enabled: Vec<&PatternRule> =
+            category.rules.iter().filter(|rule| rule.enabled).collect();
+        if enabled.is_empty() {
+            warnings.push(LintWarning {
+                kind: LintKind::EmptyCategory,
+                category: name.clone(),
+                rule_id: None,
+                message: "category is enabled but none of its rules are".to_string(),
+            });
+        }
+
+        for rule in enabled {
+            let warn = |kind, message| LintWarning {
+                kind,
+                category: name.clone(),
+                rule_id: Some(rule.id.clone()),
+                message,
+            };
+
+            for scope in &rule.applies_to {
+                let matches_any = glob::Pattern::new(scope).map_or(true, |pattern| {
+                    files.iter().any(|file| pattern.matches_path(file))
+                });
+                if !matches_any {
+                    warnings.push(warn(
+                        LintKind::DeadScope,
+                        format!("applies_to pattern '{scope}' matches no files"),
+                    ));
+                }
+            }
+
+            if matches!(rule.rule_type, RuleType::Regex) {
+                if let Some(anchor) = misplaced_anchor(&rule.pattern) {
+                    let position = if anchor == '^' { "start" } else { "end" };
+                    warnings.push(warn(
+                        LintKind::MisanchoredRegex,
+                        format!(
+                            "'{anchor}' only matches at the {position} of the file, since regex rules \
+                             search whole files; add (?m) to anchor at each line"
+                        ),
+                    ));
+                }
+            }
+
+            if let Some((other_category, other)) =
+                seen.iter().find(|(_, other)| same_effect(rule, other))
+            {
+                warnings.push(warn(
+                    LintKind::DuplicateRule,
+                    format!(
+                        "reports the same matches as '{other_category}/{}'",
+                        other.id
+                    ),
+                ));
+            }
+            seen.push((name.as_str(), rule));
+        }
+    }
+
+    warnings
+}
+
+/// Whether two rules match the same code in the same files
+fn same_effect(rule: &PatternRule, other: &PatternRule) -> bool {
+    let mut scopes = rule.applies_to.clone();
+    let mut other_scopes = other.applies_to.clone();
+    scopes.sort();
+    other_scopes.sort();
+
+    std::mem::discriminant(&rule.rule_type) == std::mem::discriminant(&other.rule_type)
+        && rule.pattern == other.pattern
+        && rule.case_sensitive == other.case_sensitive
+        && rule.only_cfg == other.only_cfg
+        && scopes == other_scopes
+        && format!("{:?}", rule.exclude_if) == format!("{:?}", other.exclude_if)
+        && format!(
+            "{:?}",
+            (&rule.all_of, &rule.any_of, &rule.none_of, &rule.near)
+        ) == format!(
+            "{:?}",
+            (&other.all_of, &other.any_of, &other.none_of, &other.near)
+        )
+}
+
+/// The first `^` or `$` anchor in a regex without multi-line mode, if any
+///
+/// Escaped characters and character classes are skipped. Patterns that enable `(?m)`
+/// before their first anchor are trusted to anchor at lines.
+fn misplaced_anchor(pattern: &str) -> Option<char> {
+    let mut chars = pattern.chars().peekable();
+    let mut class_depth = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => {
+                class_depth += 1;
+                // A `]` or `^]` right after the opening bracket is a literal
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                }
+            }
+            ']' if class_depth > 0 => class_depth -= 1,
+            '(' if class_depth == 0 && chars.peek() == Some(&'?') => {
+                let flags: String = chars
+                    .clone()
+                    .skip(1)
+                    .take_while(|c| c.is_ascii_alphabetic() || *c == '-')
+                    .collect();
+                let enabled = flags.split('-').next().unwrap_or("");
+                if enabled.contains('m') {
+                    return None;
+                }
+            }
+            '^' | '$' if class_depth == 0 => return Some(c),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, pattern: &str) -> PatternRule {
+        let mut rule = GuardianConfig::default()
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .find(|rule| rule.id == "todo_comments")
+            .expect("default config should define todo_comments")
+            .clone();
+        rule.id = id.to_string();
+        rule.pattern = pattern.to_string();
+        rule
+    }
+
+    #[test]
+    fn test_misplaced_anchor() {
+        assert_eq!(misplaced_anchor(r"^\s*// TODO"), Some('^'));
+        assert_eq!(misplaced_anchor(r"unwrap\(\)$"), Some('$'));
+        assert_eq!(misplaced_anchor(r"(?m)^\s*// TODO"), None);
+        assert_eq!(misplaced_anchor(r"(?im)^use \w+;$"), None);
+        assert_eq!(misplaced_anchor(r"(?i-m)^TODO"), Some('^'));
+        assert_eq!(misplaced_anchor(r"[^a-z]\$[$^]"), None);
+        assert_eq!(misplaced_anchor(r"\b(TODO|FIXME)\b"), None);
+    }
+
+    #[test]
+    fn test_lint_config() {
+        let mut config = GuardianConfig::default();
+        let placeholders = config
+            .patterns
+            .get_mut("placeholders")
+            .expect("default config should have placeholders");
+
+        let mut scoped = rule("scoped_todo", r"^TODO");
+        scoped.applies_to = vec!["src/*.rs".to_string(), "legacy/**".to_string()];
+        placeholders.rules.push(scoped);
+        placeholders.rules.push(rule(
+            "todo_again",
+            r"\b(TODO|FIXME|HACK|XXX|BUG|REFACTOR)\b",
+        ));
+
+        let mut empty = placeholders.clone();
+        for rule in &mut empty.rules {
+            rule.enabled = false;
+        }
+        config.patterns.insert("retired".to_string(), empty);
+
+        let files = vec![PathBuf::from("src/lib.rs"), PathBuf::from("README.md")];
+        let warnings = lint_config(&config, &files);
+        let found: Vec<(LintKind, Option<&str>)> = warnings
+            .iter()
+            .map(|w| (w.kind, w.rule_id.as_deref()))
+            .collect();
+
+        assert!(found.contains(&(LintKind::DeadScope, Some("scoped_todo"))));
+        assert!(found.contains(&(LintKind::MisanchoredRegex, Some("scoped_todo"))));
+        assert!(found.contains(&(LintKind::DuplicateRule, Some("todo_again"))));
+        assert!(found.contains(&(LintKind::EmptyCategory, None)));
+        assert_eq!(
+            warnings
+                .iter()
+                .filter(|w| w.kind == LintKind::DeadScope)
+                .count(),
+            1
+        );
+
+        assert!(lint_config(&GuardianConfig::default(), &files).is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Automated migration of configurations away from deprecated rules
+//!
+//! Code Quality Principle: Explicit Change - Every rewrite is reported, never applied silently
+//! - Deprecated rules are removed, swapped for their replacement, or disabled
+//! - The raw YAML document is edited so keys Guardian does not model are preserved
+
+use super::{GuardianConfig, RuleDeprecation};
+use crate::domain::violations::{GuardianError, GuardianResult};
+use serde_yaml::Value;
+
+/// Result of migrating a configuration file
+#[derive(Debug, Clone)]
+pub struct ConfigMigration {
+    /// Migrated YAML content
+    pub content: String,
+    /// One description per change made
+    pub changes: Vec<String>,
+}
+
+impl ConfigMigration {
+    /// Whether the configuration needed no changes
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Rewrite a YAML configuration so no deprecated rule stays enabled
+///
+/// A deprecated rule is removed when its replacement is already configured (the replacement
+/// is enabled), swapped for the built-in definition of its replacement when one exists, and
+/// disabled otherwise. Comments are not preserved by the rewrite.
+pub fn migrate_config(content: &str) -> GuardianResult<ConfigMigration> {
+    let config = GuardianConfig::load_from_str(content)?;
+    let deprecations = config.deprecated_rules();
+    if deprecations.is_empty() {
+        return Ok(ConfigMigration {
+            content: content.to_string(),
+            changes: Vec::new(),
+        });
+    }
+
+    let mut document: Value = serde_yaml::from_str(content)
+        .map_err(|e| GuardianError::config(format!("Failed to parse config: {e}")))?;
+
+    let changes = deprecations
+        .iter()
+        .map(|deprecation| migrate_rule(&mut document, &config, deprecation))
+        .collect::<GuardianResult<Vec<_>>>()?;
+
+    let content = serde_yaml::to_string(&document)
+        .map_err(|e| GuardianError::config(format!("Failed to serialize config: {e}")))?;
+
+    Ok(ConfigMigration { content, changes })
+}
+
+fn migrate_rule(
+    document: &mut Value,
+    config: &GuardianConfig,
+    deprecation: &RuleDeprecation,
+) -> GuardianResult<String> {
+    let RuleDeprecation {
+        category, rule_id, ..
+    } = deprecation;
+    let replacement = deprecation.replaced_by.as_deref();
+
+    let configured = replacement.is_some_and(|id| {
+        config
+            .patterns
+            .values()
+            .any(|c| c.rules.iter().any(|rule| rule.id == id))
+    });
+
+    if let (Some(replacement), true) = (replacement, configured) {
+        enable_rule(document, replacement);
+        let rules = category_rules(document, category)?;
+        let index = rule_index(rules, rule_id)?;
+        rules.remove(index);
+        return Ok(format!(
+            "Removed '{rule_id}' from '{category}'; '{replacement}' is already configured and enabled"
+        ));
+    }
+
+    if let Some((_, builtin)) = replacement.and_then(GuardianConfig::builtin_rule) {
+        let value = serde_yaml::to_value(&builtin)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize rule: {e}")))?;
+        let rules = category_rules(document, category)?;
+        let index = rule_index(rules, rule_id)?;
+        rules[index] = value;
+        return Ok(format!(
+            "Replaced '{rule_id}' in '{category}' with the built-in '{}' rule",
+            builtin.id
+        ));
+    }
+
+    let rules = category_rules(document, category)?;
+    let index = rule_index(rules, rule_id)?;
+    set_enabled(&mut rules[index], false);
+    Ok(format!(
+        "Disabled '{rule_id}' in '{category}' (no replacement definition available)"
+    ))
+}
+
+/// The rule list of a category in the raw document
+fn category_rules<'a>(
+    document: &'a mut Value,
+    category: &str,
+) -> GuardianResult<&'a mut Vec<Value>> {
+    document
+        .get_mut("patterns")
+        .and_then(|patterns| patterns.get_mut(category))
+        .and_then(|category| category.get_mut("rules"))
+        .and_then(Value::as_sequence_mut)
+        .ok_or_else(|| GuardianError::config(format!("Category '{category}' has no rules list")))
+}
+
+fn rule_index(rules: &[Value], rule_id: &str) -> GuardianResult<usize> {
+    rules
+        .iter()
+        .position(|rule| rule.get("id").and_then(Value::as_str) == Some(rule_id))
+        .ok_or_else(|| GuardianError::config(format!("Rule '{rule_id}' not found")))
+}
+
+/// Enable every definition of a rule, in any category
+fn enable_rule(document: &mut Value, rule_id: &str) {
+    let categories = document
+        .get_mut("patterns")
+        .and_then(Value::as_mapping_mut)
+        .into_iter()
+        .flat_map(|patterns| patterns.values_mut());
+
+    for category in categories {
+        let rules = category
+            .get_mut("rules")
+            .and_then(Value::as_sequence_mut)
+            .into_iter()
+            .flatten();
+        for rule in rules {
+            if rule.get("id").and_then(Value::as_str) == Some(rule_id) {
+                set_enabled(rule, true);
+            }
+        }
+    }
+}
+
+fn set_enabled(rule: &mut Value, enabled: bool) {
+    if let Some(rule) = rule.as_mapping_mut() {
+        rule.insert(Value::from("enabled"), Value::Bool(enabled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  legacy:
+    severity: warning
+    enabled: true
+    rules:
+      - id: old_marker
+        type: regex
+        pattern: 'OLD'
+        message: "old marker"
+        deprecated: true
+        replaced_by: new_marker
+      - id: new_marker
+        type: regex
+        pattern: 'NEW'
+        message: "new marker"
+        enabled: false
+      - id: old_todo
+        type: regex
+        pattern: 'TODO'
+        message: "todo"
+        deprecated: true
+        replaced_by: todo_comments
+      - id: retired
+        type: regex
+        pattern: 'RETIRED'
+        message: "retired"
+        deprecated: true
+"#;
+
+    #[test]
+    fn test_migrate_deprecated_rules() {
+        let config = GuardianConfig::load_from_str(CONFIG).expect("test config should load");
+        let notices: Vec<String> = config
+            .deprecated_rules()
+            .iter()
+            .map(RuleDeprecation::notice)
+            .collect();
+        assert_eq!(notices.len(), 3);
+        assert!(notices[0].contains("use 'new_marker' instead"));
+
+        let migration = migrate_config(CONFIG).expect("migration should succeed");
+        assert_eq!(migration.changes.len(), 3);
+
+        let migrated =
+            GuardianConfig::load_from_str(&migration.content).expect("migrated config should load");
+        assert!(migrated.deprecated_rules().is_empty());
+
+        let rules = &migrated.patterns["legacy"].rules;
+        let ids: Vec<&str> = rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(ids, ["new_marker", "todo_comments", "retired"]);
+        assert!(rules[0].enabled);
+        assert!(!rules[2].enabled);
+
+        let unchanged = migrate_config(&migration.content).expect("migration should succeed");
+        assert!(unchanged.is_unchanged());
+    }
+}
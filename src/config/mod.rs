@@ -5,11 +5,33 @@
 //! - Default configurations are embedded in the domain, not infrastructure
 //! - Configuration acts as a repository for pattern rules and path filters
 
+pub mod discovery;
+pub mod extends;
+pub mod lint;
+pub mod migrate;
+pub mod policy;
+pub mod remote;
+pub mod rule_pack;
+
+pub use discovery::{
+    find_config_file, find_locked_config_file, find_project_root, CONFIG_FILE_NAMES,
+};
+pub use lint::{lint_config, LintKind, LintWarning};
+pub use migrate::{migrate_config, ConfigMigration};
+pub use remote::RemoteConfig;
+pub use rule_pack::{
+    releases_between, RuleChange, RuleChangeKind, RulePackRelease, RULE_PACK_HISTORY,
+    RULE_PACK_VERSION,
+};
+
 use crate::domain::violations::{GuardianError, GuardianResult, Severity};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Policy baked in at build time via `GUARDIAN_EMBED_CONFIG`; empty when none was embedded
+pub const EMBEDDED_CONFIG: &str = include_str!(concat!(env!("OUT_DIR"), "/embedded_config.yaml"));
 
 /// Main configuration structure for Rust Guardian
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +42,359 @@ pub struct GuardianConfig {
     pub paths: PathConfig,
     /// Pattern definitions organized by category
     pub patterns: HashMap<String, PatternCategory>,
+    /// Report presentation defaults
+    #[serde(default)]
+    pub reporting: ReportingConfig,
+    /// Webhook notifications for unattended runs
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    /// Issue tracker linkage of trackable violations
+    #[serde(default)]
+    pub issues: IssueTrackerConfig,
+    /// Analysis of macro-expanded code
+    #[serde(default)]
+    pub expansion: ExpansionConfig,
+    /// Conditional compilation builds to evaluate violations against
+    #[serde(default)]
+    pub cfg: CfgConfig,
+    /// Analysis of Rust examples in doc comments and Markdown
+    #[serde(default)]
+    pub doc_examples: DocExampleConfig,
+    /// License header every file in scope must start with
+    #[serde(default)]
+    pub license_header: LicenseHeaderConfig,
+    /// Severity escalation of violations that stay unresolved
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+    /// Commit message and branch name rules checked by `check-commit`
+    #[serde(default)]
+    pub vcs_policy: VcsPolicyConfig,
+    /// Opt-in local aggregation of triage feedback per rule
+    #[serde(default)]
+    pub feedback: FeedbackConfig,
+    /// Directories of rule fragment files merged into `patterns`, relative to the config file
+    #[serde(default)]
+    pub rule_packs: Vec<String>,
+    /// Reject local overrides: command-line rule toggles and nested config files
+    #[serde(default)]
+    pub locked: bool,
+    /// Hash of the locked policy file this configuration was loaded from
+    #[serde(skip)]
+    pub policy_hash: Option<String>,
+}
+
+/// Report presentation defaults, overridable from the command line
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportingConfig {
+    /// Maximum number of violations shown per rule
+    pub max_per_rule: Option<usize>,
+    /// Maximum number of violations shown per file
+    #[serde(alias = "max_violations_per_file")]
+    pub max_per_file: Option<usize>,
+    /// Columns of the CSV export, in order (defaults to file, line, column, severity, rule,
+    /// category and message)
+    #[serde(default)]
+    pub csv_columns: Vec<String>,
+}
+
+/// Conditional compilation settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CfgConfig {
+    /// Builds whose cfg options decide which gated code counts; empty counts all code
+    #[serde(default)]
+    pub matrix: Vec<CfgBuild>,
+}
+
+/// One build of the cfg matrix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CfgBuild {
+    /// Name used in `build:<name>` violation tags
+    pub name: String,
+    /// Options enabled in this build, such as `unix`, `test` or `feature = "std"`
+    #[serde(default)]
+    pub enabled: Vec<String>,
+}
+
+/// Analysis of macro-expanded code, enabled per run with `check --expand`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExpansionConfig {
+    /// Rules applied to expanded code; empty applies every enabled rule
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+/// Severity escalation of known violations by age
+///
+/// Ages come from the violation history, which records when each violation was first seen
+/// and forgets violations once they are resolved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    /// History file, relative to the project root (defaults to `.rust/guardian_history.json`)
+    #[serde(default)]
+    pub history_file: Option<PathBuf>,
+    /// Age thresholds; a violation takes the highest severity of the steps it has reached
+    #[serde(default)]
+    pub steps: Vec<EscalationStep>,
+}
+
+impl EscalationConfig {
+    /// Whether any escalation is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Severity a violation of the given rule and age escalates to, if any step applies
+    ///
+    /// Escalation only ever raises severity; the caller keeps the higher of the two.
+    pub fn severity_for(&self, rule_id: &str, age_days: i64) -> Option<Severity> {
+        self.steps
+            .iter()
+            .filter(|step| step.rules.is_empty() || step.rules.iter().any(|id| id == rule_id))
+            .filter(|step| age_days >= i64::from(step.after_days))
+            .map(|step| step.severity)
+            .max()
+    }
+}
+
+/// One escalation threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationStep {
+    /// Days a violation must have been known before it escalates
+    pub after_days: u32,
+    /// Severity the violation is raised to
+    pub severity: Severity,
+    /// Rule ids the step applies to; empty applies it to every rule
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+/// Analysis of fenced Rust examples in doc comments and Markdown files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocExampleConfig {
+    /// Whether examples are extracted and analyzed
+    #[serde(default)]
+    pub enabled: bool,
+    /// Rules applied to examples; empty applies every enabled rule
+    #[serde(default = "default_doc_example_rules")]
+    pub rules: Vec<String>,
+}
+
+impl Default for DocExampleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: default_doc_example_rules(),
+        }
+    }
+}
+
+/// Placeholder rules, the findings that matter most in examples published on docs.rs
+fn default_doc_example_rules() -> Vec<String> {
+    ["todo_comments", "temporary_markers", "unimplemented_macros"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// License header enforcement, reported under the `license_header` rule id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseHeaderConfig {
+    /// Whether files are checked for the header
+    #[serde(default)]
+    pub enabled: bool,
+    /// Header text without comment markers, with `{year}` and `{owner}` placeholders
+    #[serde(default)]
+    pub template: String,
+    /// Value of the `{owner}` placeholder
+    #[serde(default)]
+    pub owner: String,
+    /// First year of the range written into inserted headers (defaults to the current year)
+    #[serde(default)]
+    pub start_year: Option<i32>,
+    /// Report headers whose year or year range ends before the current year
+    #[serde(default)]
+    pub require_current_year: bool,
+    /// Prefix of each inserted header line
+    #[serde(default = "default_comment_prefix")]
+    pub comment_prefix: String,
+    /// Glob patterns of the files that need the header
+    #[serde(default = "default_license_applies_to")]
+    pub applies_to: Vec<String>,
+    /// Severity of missing or outdated headers
+    #[serde(default = "default_license_severity")]
+    pub severity: Severity,
+}
+
+impl Default for LicenseHeaderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            template: String::new(),
+            owner: String::new(),
+            start_year: None,
+            require_current_year: false,
+            comment_prefix: default_comment_prefix(),
+            applies_to: default_license_applies_to(),
+            severity: default_license_severity(),
+        }
+    }
+}
+
+/// Opt-in aggregation of triage labels per rule, exported with `feedback export`
+///
+/// Only rule ids and label counts are kept; no paths, code or fingerprints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedbackConfig {
+    /// Count each triage label against its rule
+    #[serde(default)]
+    pub enabled: bool,
+    /// Feedback file, relative to the project root (defaults to `.rust/guardian_feedback.json`)
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
+
+/// Commit hygiene enforced by `check-commit`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VcsPolicyConfig {
+    /// Require Conventional Commits subjects, `type(scope)!: description`
+    #[serde(default = "default_true")]
+    pub conventional_commits: bool,
+    /// Commit types accepted in conventional subjects
+    #[serde(default = "default_commit_types")]
+    pub commit_types: Vec<String>,
+    /// Require a `(scope)` in conventional subjects
+    #[serde(default)]
+    pub require_scope: bool,
+    /// Longest subject line accepted, in characters
+    #[serde(default = "default_max_subject_length")]
+    pub max_subject_length: Option<usize>,
+    /// Glob patterns branch names must match; empty accepts any branch
+    #[serde(default)]
+    pub branch_patterns: Vec<String>,
+    /// Words rejected in commit messages and branch names, matched case-insensitively
+    #[serde(default)]
+    pub forbidden_words: Vec<String>,
+    /// Severity of policy violations
+    #[serde(default = "default_vcs_severity")]
+    pub severity: Severity,
+}
+
+impl Default for VcsPolicyConfig {
+    fn default() -> Self {
+        Self {
+            conventional_commits: true,
+            commit_types: default_commit_types(),
+            require_scope: false,
+            max_subject_length: default_max_subject_length(),
+            branch_patterns: Vec::new(),
+            forbidden_words: Vec::new(),
+            severity: default_vcs_severity(),
+        }
+    }
+}
+
+/// The types of the Conventional Commits specification and its Angular convention
+fn default_commit_types() -> Vec<String> {
+    [
+        "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style",
+        "test",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_max_subject_length() -> Option<usize> {
+    Some(72)
+}
+
+fn default_vcs_severity() -> Severity {
+    Severity::Error
+}
+
+fn default_comment_prefix() -> String {
+    "// ".to_string()
+}
+
+fn default_license_applies_to() -> Vec<String> {
+    vec!["**/*.rs".to_string()]
+}
+
+fn default_license_severity() -> Severity {
+    Severity::Error
+}
+
+/// Webhook notifications sent when a run crosses a violation threshold
+///
+/// URLs starting with `$` name an environment variable holding the URL, so webhook
+/// secrets stay out of the repository.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Slack incoming-webhook URL
+    pub slack_webhook: Option<String>,
+    /// Generic webhook receiving a rendered template
+    pub webhook: Option<WebhookConfig>,
+    /// Notify when at least this many errors are found (defaults to 1 when no threshold is set)
+    pub min_errors: Option<usize>,
+    /// Notify when at least this many warnings are found
+    pub min_warnings: Option<usize>,
+}
+
+/// A generic webhook target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Webhook URL
+    pub url: String,
+    /// Request body; `{project}`, `{errors}`, `{warnings}`, `{info}`, `{files}` and
+    /// `{top_rules}` are substituted
+    pub template: String,
+    /// Content type of the request body
+    #[serde(default = "default_webhook_content_type")]
+    pub content_type: String,
+}
+
+/// Issue tracker that trackable violations are linked to
+///
+/// The token, like notification URLs, is read from the environment when it starts with `$`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueTrackerConfig {
+    /// Tracker hosting the issues; linkage is off without one
+    pub provider: Option<IssueProvider>,
+    /// `owner/repo` on GitHub, or the project path or id on GitLab
+    #[serde(default)]
+    pub repository: String,
+    /// API base URL, for GitHub Enterprise or self-hosted GitLab
+    pub api_url: Option<String>,
+    /// API token (defaults to `$GITHUB_TOKEN` or `$GITLAB_TOKEN`)
+    pub token: Option<String>,
+    /// Rule ids whose violations are tracked as issues
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// Labels put on created issues
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// File mapping violation fingerprints to issues, relative to the project root
+    /// (defaults to `.rust/guardian_issues.json`)
+    pub links_file: Option<PathBuf>,
+}
+
+impl IssueTrackerConfig {
+    /// Whether a tracker and at least one trackable rule are configured
+    pub fn is_enabled(&self) -> bool {
+        self.provider.is_some() && !self.rules.is_empty()
+    }
+
+    /// Whether violations of a rule are tracked as issues
+    pub fn tracks(&self, rule_id: &str) -> bool {
+        self.rules.iter().any(|id| id == rule_id)
+    }
+}
+
+/// Supported issue trackers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueProvider {
+    Github,
+    Gitlab,
 }
 
 /// Path filtering configuration
@@ -29,6 +404,36 @@ pub struct PathConfig {
     pub patterns: Vec<String>,
     /// Optional .guardianignore file name
     pub ignore_file: Option<String>,
+    /// Exclude paths matched by the repository's .gitignore files
+    #[serde(default = "default_true")]
+    pub use_gitignore: bool,
+    /// Skip files with generated-code markers, listing them in the report
+    #[serde(default = "default_true")]
+    pub skip_generated: bool,
+    /// Additional header markers identifying generated files
+    #[serde(default)]
+    pub generated_markers: Vec<String>,
+    /// Decode invalid UTF-8 lossily instead of treating it as undecodable
+    #[serde(default = "default_true")]
+    pub lossy_decoding: bool,
+    /// Skip undecodable files, listing them in the report, instead of failing on them
+    #[serde(default)]
+    pub skip_undecodable: bool,
+    /// Skip files whose estimated in-memory size (content plus syntax tree) exceeds this many
+    /// MiB, listing them in the report
+    #[serde(default)]
+    pub max_file_memory_mb: Option<u64>,
+    /// Descend into symlinked directories during discovery
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Discover files reachable by several paths (symlinks, hardlinks) only once
+    #[serde(default = "default_true")]
+    pub dedupe_files: bool,
+    /// Project root that patterns are anchored to (defaults to the working directory)
+    ///
+    /// Not read from configuration files; set by the caller after discovery.
+    #[serde(skip)]
+    pub root: Option<PathBuf>,
 }
 
 /// A category of patterns (e.g., "placeholders", "architectural_violations")
@@ -64,6 +469,135 @@ pub struct PatternRule {
     pub case_sensitive: bool,
     /// Conditions that exclude matches from being violations
     pub exclude_if: Option<ExcludeConditions>,
+    /// Free-form tags for grouping and filtering rules
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Link to documentation explaining the rule
+    pub docs_url: Option<String>,
+    /// Why the rule exists, in one line, shown under violations by `check --explain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Glob patterns limiting the files this rule applies to (all files when empty)
+    #[serde(default)]
+    pub applies_to: Vec<String>,
+    /// Whether this rule is deprecated and will be removed
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Rule that supersedes this one, if any
+    #[serde(default)]
+    pub replaced_by: Option<String>,
+    /// Cfg predicate the code must be gated by for violations to count (e.g. `test`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_cfg: Option<String>,
+    /// Conditions that must all match for a composite rule to report a unit, or for a
+    /// file_pair rule to require a companion
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<RuleCondition>,
+    /// Conditions of which at least one must match for a composite rule (ignored when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any_of: Vec<RuleCondition>,
+    /// Conditions that must not match for a composite rule to report a unit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub none_of: Vec<RuleCondition>,
+    /// Text that must, or must not, appear within a few lines of each match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub near: Option<NearConstraint>,
+    /// Code that follows the rule, shown by `explain` and the generated rulebook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub good_example: Option<String>,
+    /// Code that breaks the rule, shown by `explain` and the generated rulebook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bad_example: Option<String>,
+    /// Remediation applied to each match by `check --fix`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<FixTemplate>,
+}
+
+/// Simple remediation a configured rule applies to its matches
+///
+/// Exactly one action is set. For regex rules, `$1` or `${name}` in `replace_match_with`
+/// expand to capture groups of the match, and `$$` is a literal dollar sign.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct FixTemplate {
+    /// Text that replaces the matched text; empty deletes the match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace_match_with: Option<String>,
+    /// Line inserted above the line of each match, at the same indentation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insert_before_line: Option<String>,
+}
+
+/// Proximity requirement on the matches of a rule
+///
+/// A match is only reported when the requirement fails: `must_have` is missing from the
+/// surrounding lines, or `must_not_have` is present there.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct NearConstraint {
+    /// Lines above and below the match that are searched
+    pub within_lines: u32,
+    /// Regex that must appear near each match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_have: Option<String>,
+    /// Regex that must not appear near any match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_not_have: Option<String>,
+    /// Which parts of the source nearby text counts in
+    #[serde(default, rename = "in")]
+    pub region: NearRegion,
+}
+
+/// Parts of classified source a proximity requirement looks at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NearRegion {
+    /// Anywhere
+    #[default]
+    Any,
+    /// Only inside comments
+    Comment,
+    /// Only in code, outside comments and string literals
+    Code,
+}
+
+/// One sub-pattern of a composite rule
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct RuleCondition {
+    /// Type of the sub-pattern; composite and file_pair rules cannot be conditions
+    #[serde(rename = "type")]
+    pub rule_type: RuleType,
+    /// The pattern to match
+    pub pattern: String,
+    /// Case sensitivity for regex patterns
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+/// An enabled rule that is deprecated, by its own metadata or the built-in rule of the same id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDeprecation {
+    /// Category the rule is configured in
+    pub category: String,
+    /// Deprecated rule identifier
+    pub rule_id: String,
+    /// Rule that supersedes it, if any
+    pub replaced_by: Option<String>,
+}
+
+impl RuleDeprecation {
+    /// Human-readable notice for warnings and `validate-config`
+    pub fn notice(&self) -> String {
+        match &self.replaced_by {
+            Some(replacement) => format!(
+                "Rule '{}' in category '{}' is deprecated; use '{}' instead",
+                self.rule_id, self.category, replacement
+            ),
+            None => format!(
+                "Rule '{}' in category '{}' is deprecated and will be removed",
+                self.rule_id, self.category
+            ),
+        }
+    }
 }
 
 /// Types of pattern matching
@@ -78,6 +612,12 @@ pub enum RuleType {
     Semantic,
     /// Import/dependency analysis
     ImportAnalysis,
+    /// Sub-patterns combined with `all_of`/`any_of`/`none_of` over a `function` or `file`
+    Composite,
+    /// A companion file every matching file needs, as `source -> companion` templates
+    FilePair,
+    /// A regex every file in scope must contain, reported at line 1 when absent
+    Required,
 }
 
 /// Conditions that can exclude a match from being reported as a violation
@@ -94,34 +634,113 @@ pub struct ExcludeConditions {
 
 impl GuardianConfig {
     /// Load configuration from a YAML file
+    ///
+    /// `extends` paths are resolved relative to the file's directory.
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> GuardianResult<Self> {
-        let contents = fs::read_to_string(&path).map_err(|e| {
-            GuardianError::config(format!(
-                "Failed to read config file '{}': {}",
-                path.as_ref().display(),
-                e
-            ))
-        })?;
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
 
-        let config: Self = serde_yaml::from_str(&contents).map_err(|e| {
+        let config = Self::parse_yaml(&contents, path.parent()).map_err(|e| {
             GuardianError::config(format!(
                 "Failed to parse config file '{}': {}",
-                path.as_ref().display(),
+                path.display(),
                 e
             ))
         })?;
 
         config.validate()?;
-        Ok(config)
+        Ok(config.with_policy_hash(&contents))
+    }
+
+    /// Load configuration from a file path or an `https://` URL
+    ///
+    /// URLs may pin their contents with a `#sha256=<hex>` suffix; fetched configs are cached
+    /// so pinned ones load offline.
+    pub fn load_from_source<P: AsRef<Path>>(source: P) -> GuardianResult<Self> {
+        let remote = source
+            .as_ref()
+            .to_str()
+            .and_then(RemoteConfig::parse)
+            .transpose()?;
+        match remote {
+            Some(remote) => {
+                let contents = remote.load()?;
+                let config = Self::parse_yaml(&contents, None).map_err(|e| {
+                    GuardianError::config(format!(
+                        "Failed to parse config from '{}': {e}",
+                        remote.url
+                    ))
+                })?;
+
+                config.validate()?;
+                Ok(config.with_policy_hash(&contents))
+            }
+            None => Self::load_from_file(source),
+        }
     }
 
     /// Load configuration from string content
     pub fn load_from_str(content: &str) -> GuardianResult<Self> {
-        let config: Self = serde_yaml::from_str(content)
+        let config = Self::parse_yaml(content, None)
             .map_err(|e| GuardianError::config(format!("Failed to parse config: {e}")))?;
 
         config.validate()?;
-        Ok(config)
+        Ok(config.with_policy_hash(content))
+    }
+
+    /// Load a policy that must be enforced as-is, from a file path or an `http(s)://` URL
+    ///
+    /// The result is locked whether or not the file says so, and carries the hash of the
+    /// exact policy contents for the report.
+    pub fn load_enforced(source: &str) -> GuardianResult<Self> {
+        let contents = policy::read_policy_source(source)?;
+        let base_dir = RemoteConfig::parse(source)
+            .is_none()
+            .then(|| Path::new(source).parent())
+            .flatten();
+        let mut config = Self::parse_yaml(&contents, base_dir).map_err(|e| {
+            GuardianError::config(format!("Failed to parse enforced config '{source}': {e}"))
+        })?;
+
+        config.validate()?;
+        config.locked = true;
+        Ok(config.with_policy_hash(&contents))
+    }
+
+    /// Parse YAML, merging in any configs named by `extends` and rules from `rule_packs`
+    fn parse_yaml(contents: &str, base_dir: Option<&Path>) -> Result<Self, String> {
+        let value: serde_yaml::Value = serde_yaml::from_str(contents).map_err(|e| e.to_string())?;
+        let value = extends::resolve_extends(value, base_dir).map_err(|e| e.to_string())?;
+        let value = extends::merge_rule_packs(value, base_dir).map_err(|e| e.to_string())?;
+        serde_yaml::from_value(value).map_err(|e| e.to_string())
+    }
+
+    /// Record the policy hash of a locked configuration
+    fn with_policy_hash(mut self, contents: &str) -> Self {
+        if self.locked {
+            self.policy_hash = Some(policy::policy_hash(contents));
+        }
+        self
+    }
+
+    /// Configuration embedded into this binary at build time, if any
+    ///
+    /// Build with `GUARDIAN_EMBED_CONFIG=policy.yaml cargo build --release` to distribute a
+    /// binary with an organization policy baked in. The embedded policy takes precedence over
+    /// discovered config files; only an explicit `--config` overrides it.
+    pub fn embedded() -> GuardianResult<Option<Self>> {
+        Self::from_embedded(EMBEDDED_CONFIG)
+    }
+
+    /// Parse an embedded policy, treating blank contents as no policy at all
+    pub fn from_embedded(contents: &str) -> GuardianResult<Option<Self>> {
+        if contents.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Self::load_from_str(contents)
+            .map(Some)
+            .map_err(|e| GuardianError::config(format!("Embedded configuration is invalid: {e}")))
     }
 
     /// Get default configuration with built-in patterns
@@ -137,8 +756,30 @@ impl GuardianConfig {
                     "**/*.generated.*".to_string(),
                 ],
                 ignore_file: Some(".guardianignore".to_string()),
+                use_gitignore: true,
+                skip_generated: true,
+                generated_markers: Vec::new(),
+                lossy_decoding: true,
+                skip_undecodable: false,
+                max_file_memory_mb: None,
+                follow_symlinks: false,
+                dedupe_files: true,
+                root: None,
             },
             patterns: Self::default_patterns(),
+            reporting: ReportingConfig::default(),
+            notifications: NotificationConfig::default(),
+            issues: IssueTrackerConfig::default(),
+            expansion: ExpansionConfig::default(),
+            cfg: CfgConfig::default(),
+            doc_examples: DocExampleConfig::default(),
+            license_header: LicenseHeaderConfig::default(),
+            escalation: EscalationConfig::default(),
+            vcs_policy: VcsPolicyConfig::default(),
+            feedback: FeedbackConfig::default(),
+            rule_packs: Vec::new(),
+            locked: false,
+            policy_hash: None,
         }
     }
 
@@ -162,6 +803,22 @@ impl GuardianConfig {
                         enabled: true,
                         case_sensitive: false,
                         exclude_if: None,
+                        tags: Vec::new(),
+                        docs_url: None,
+                        rationale: Some(
+                            "Markers left in code stand for unfinished work that is easily forgotten once merged".to_string(),
+                        ),
+                        applies_to: Vec::new(),
+                        deprecated: false,
+                        replaced_by: None,
+                        only_cfg: None,
+                        all_of: Vec::new(),
+                        any_of: Vec::new(),
+                        none_of: Vec::new(),
+                        near: None,
+                        good_example: None,
+                        bad_example: None,
+                        fix: None,
                     },
                     PatternRule {
                         id: "temporary_markers".to_string(),
@@ -176,6 +833,22 @@ impl GuardianConfig {
                             in_tests: true,
                             file_patterns: Some(vec!["**/tests/**".to_string()]),
                         }),
+                        tags: Vec::new(),
+                        docs_url: None,
+                        rationale: Some(
+                            "Code described as temporary tends to become permanent unless it is tracked".to_string(),
+                        ),
+                        applies_to: Vec::new(),
+                        deprecated: false,
+                        replaced_by: None,
+                        only_cfg: None,
+                        all_of: Vec::new(),
+                        any_of: Vec::new(),
+                        none_of: Vec::new(),
+                        near: None,
+                        good_example: None,
+                        bad_example: None,
+                        fix: None,
                     },
                     PatternRule {
                         id: "unimplemented_macros".to_string(),
@@ -190,6 +863,22 @@ impl GuardianConfig {
                             in_tests: true,
                             file_patterns: None,
                         }),
+                        tags: Vec::new(),
+                        docs_url: None,
+                        rationale: Some(
+                            "These macros compile but panic at runtime, hiding missing functionality".to_string(),
+                        ),
+                        applies_to: Vec::new(),
+                        deprecated: false,
+                        replaced_by: None,
+                        only_cfg: None,
+                        all_of: Vec::new(),
+                        any_of: Vec::new(),
+                        none_of: Vec::new(),
+                        near: None,
+                        good_example: None,
+                        bad_example: None,
+                        fix: None,
                     },
                 ],
             },
@@ -214,6 +903,22 @@ impl GuardianConfig {
                         in_tests: true,
                         file_patterns: None,
                     }),
+                    tags: Vec::new(),
+                    docs_url: None,
+                    rationale: Some(
+                        "Returning Ok(()) from a function that does nothing reports success for work that never happened".to_string(),
+                    ),
+                    applies_to: Vec::new(),
+                    deprecated: false,
+                    replaced_by: None,
+                    only_cfg: None,
+                    all_of: Vec::new(),
+                    any_of: Vec::new(),
+                    none_of: Vec::new(),
+                    near: None,
+                    good_example: None,
+                    bad_example: None,
+                    fix: None,
                 }],
             },
         );
@@ -241,6 +946,22 @@ impl GuardianConfig {
                                 "**/examples/**".to_string(),
                             ]),
                         }),
+                        tags: Vec::new(),
+                        docs_url: None,
+                        rationale: Some(
+                            "Hardcoded paths break when the code runs from another directory or machine".to_string(),
+                        ),
+                        applies_to: Vec::new(),
+                        deprecated: false,
+                        replaced_by: None,
+                        only_cfg: None,
+                        all_of: Vec::new(),
+                        any_of: Vec::new(),
+                        none_of: Vec::new(),
+                        near: None,
+                        good_example: None,
+                        bad_example: None,
+                        fix: None,
                     },
                     PatternRule {
                         id: "architectural_header_missing".to_string(),
@@ -259,6 +980,22 @@ impl GuardianConfig {
                                 "**/examples/**".to_string(),
                             ]),
                         }),
+                        tags: Vec::new(),
+                        docs_url: None,
+                        rationale: Some(
+                            "A module header states the module's architectural role for readers and reviewers".to_string(),
+                        ),
+                        applies_to: Vec::new(),
+                        deprecated: false,
+                        replaced_by: None,
+                        only_cfg: None,
+                        all_of: Vec::new(),
+                        any_of: Vec::new(),
+                        none_of: Vec::new(),
+                        near: None,
+                        good_example: None,
+                        bad_example: None,
+                        fix: None,
                     },
                 ],
             },
@@ -277,6 +1014,12 @@ impl GuardianConfig {
             )));
         }
 
+        if self.reporting.max_per_rule == Some(0) || self.reporting.max_per_file == Some(0) {
+            return Err(GuardianError::config(
+                "reporting.max_per_rule and reporting.max_per_file must be at least 1",
+            ));
+        }
+
         // Validate patterns
         for (category_name, category) in &self.patterns {
             for rule in &category.rules {
@@ -290,7 +1033,7 @@ impl GuardianConfig {
                 }
 
                 // Validate regex patterns can compile
-                if matches!(rule.rule_type, RuleType::Regex) {
+                if matches!(rule.rule_type, RuleType::Regex | RuleType::Required) {
                     if rule.case_sensitive {
                         regex::Regex::new(&rule.pattern)
                     } else {
@@ -298,16 +1041,100 @@ impl GuardianConfig {
                             .case_insensitive(true)
                             .build()
                     }
-                    .map_err(|e| {
-                        GuardianError::config(format!(
-                            "Invalid regex pattern in rule '{}': {}",
-                            rule.id, e
-                        ))
+                    .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
+                }
+
+                // Validate composite conditions are only used, and complete, on composite rules
+                let conditions = rule.all_of.iter().chain(&rule.any_of).chain(&rule.none_of);
+                if matches!(rule.rule_type, RuleType::Composite | RuleType::FilePair) {
+                    let needs_positive = matches!(rule.rule_type, RuleType::Composite);
+                    if needs_positive && rule.all_of.is_empty() && rule.any_of.is_empty() {
+                        return Err(GuardianError::rule_compilation(
+                            &rule.id,
+                            "composite rules need at least one all_of or any_of condition",
+                        ));
+                    }
+                    if conditions.clone().any(|condition| {
+                        matches!(
+                            condition.rule_type,
+                            RuleType::Composite | RuleType::FilePair
+                        )
+                    }) {
+                        return Err(GuardianError::rule_compilation(
+                            &rule.id,
+                            "composite and file_pair rules cannot be used as conditions",
+                        ));
+                    }
+                } else if conditions.count() > 0 {
+                    return Err(GuardianError::rule_compilation(
+                        &rule.id,
+                        "all_of, any_of and none_of only apply to composite and file_pair rules",
+                    ));
+                }
+
+                if matches!(rule.rule_type, RuleType::FilePair) {
+                    crate::patterns::FilePairTemplate::parse(&rule.pattern)
+                        .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
+                }
+
+                // Validate fix templates name exactly one action on rules with matched text
+                if let Some(fix) = &rule.fix {
+                    if fix.replace_match_with.is_some() == fix.insert_before_line.is_some() {
+                        return Err(GuardianError::rule_compilation(
+                            &rule.id,
+                            "fix needs exactly one of replace_match_with or insert_before_line",
+                        ));
+                    }
+                    if matches!(rule.rule_type, RuleType::Required | RuleType::FilePair) {
+                        return Err(GuardianError::rule_compilation(
+                            &rule.id,
+                            "fix templates only apply to rules that match code",
+                        ));
+                    }
+                }
+
+                // Validate proximity requirements can compile
+                if let Some(near) = &rule.near {
+                    crate::patterns::NearCheck::compile(&rule.id, near)?;
+                }
+
+                // Validate scope globs can compile
+                for scope in &rule.applies_to {
+                    glob::Pattern::new(scope).map_err(|e| {
+                        GuardianError::rule_compilation(
+                            &rule.id,
+                            format!("invalid applies_to pattern '{scope}': {e}"),
+                        )
                     })?;
                 }
             }
         }
 
+        crate::analyzer::cfg::CfgScoping::from_config(self)?;
+
+        self.validate_rule_subset("expansion.rules", &self.expansion.rules)?;
+        crate::analyzer::license::LicenseHeader::from_config(&self.license_header)?;
+
+        if self.doc_examples.enabled {
+            self.validate_rule_subset("doc_examples.rules", &self.doc_examples.rules)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that a configured rule subset only names defined rules
+    fn validate_rule_subset(&self, setting: &str, rule_ids: &[String]) -> GuardianResult<()> {
+        for rule_id in rule_ids {
+            let known = self
+                .patterns
+                .values()
+                .any(|category| category.rules.iter().any(|rule| &rule.id == rule_id));
+            if !known {
+                return Err(GuardianError::config(format!(
+                    "{setting} names unknown rule '{rule_id}'"
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -325,6 +1152,56 @@ impl GuardianConfig {
             })
     }
 
+    /// Enabled rules that are deprecated, sorted by category and rule id
+    ///
+    /// Projects usually copy built-in rule definitions into their YAML, so a rule also counts
+    /// as deprecated when the built-in rule with the same id is.
+    pub fn deprecated_rules(&self) -> Vec<RuleDeprecation> {
+        let builtins = Self::default_patterns();
+        let builtin_rule = |id: &str| {
+            builtins
+                .values()
+                .flat_map(|category| category.rules.iter())
+                .find(|rule| rule.id == id)
+        };
+
+        let mut deprecations: Vec<RuleDeprecation> = self
+            .enabled_rules()
+            .filter_map(|(category, _, rule)| {
+                let builtin = builtin_rule(&rule.id);
+                let deprecated = rule.deprecated || builtin.is_some_and(|b| b.deprecated);
+                deprecated.then(|| RuleDeprecation {
+                    category: category.clone(),
+                    rule_id: rule.id.clone(),
+                    replaced_by: rule
+                        .replaced_by
+                        .clone()
+                        .or_else(|| builtin.and_then(|b| b.replaced_by.clone())),
+                })
+            })
+            .collect();
+
+        deprecations.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.rule_id.cmp(&b.rule_id))
+        });
+        deprecations
+    }
+
+    /// Built-in definition of a rule, used when migrating to a replacement
+    pub fn builtin_rule(id: &str) -> Option<(String, PatternRule)> {
+        Self::default_patterns()
+            .into_iter()
+            .find_map(|(category, patterns)| {
+                patterns
+                    .rules
+                    .into_iter()
+                    .find(|rule| rule.id == id)
+                    .map(|rule| (category, rule))
+            })
+    }
+
     /// Get effective severity for a rule (rule override or category default)
     pub fn effective_severity(&self, category: &PatternCategory, rule: &PatternRule) -> Severity {
         rule.severity.unwrap_or(category.severity)
@@ -348,8 +1225,9 @@ impl GuardianConfig {
         let mut sorted_patterns: Vec<_> = self.patterns.iter().collect();
         sorted_patterns.sort_by_key(|(name, _)| name.as_str());
 
-        // Hash version and path config
+        // Hash version, rule pack version and path config
         self.version.hash(&mut hasher);
+        RULE_PACK_VERSION.hash(&mut hasher);
         self.paths.patterns.len().hash(&mut hasher);
         for pattern in &self.paths.patterns {
             pattern.hash(&mut hasher);
@@ -377,6 +1255,56 @@ impl GuardianConfig {
 
         format!("{:x}", hasher.finish())
     }
+
+    /// Fingerprint of the settings that affect every rule
+    ///
+    /// Changing these invalidates every cached result, while rule definitions are
+    /// fingerprinted one by one in `rule_fingerprints`.
+    pub fn shared_fingerprint(&self) -> String {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.version.hash(&mut hasher);
+        RULE_PACK_VERSION.hash(&mut hasher);
+        serde_json::to_string(&self.paths)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+
+        format!("{:x}", hasher.finish())
+    }
+
+    /// Fingerprint of each enabled rule's effective definition, keyed by rule id
+    ///
+    /// A rule's fingerprint covers its own fields and the category settings it inherits, so
+    /// editing one rule leaves the fingerprints of all others unchanged. Rules whose results
+    /// depend on the current date, such as `todo_expired`, also cover today's date so their
+    /// cached results expire at midnight.
+    pub fn rule_fingerprints(&self) -> BTreeMap<String, String> {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut fingerprints = BTreeMap::new();
+        for (category_name, category) in &self.patterns {
+            if !category.enabled {
+                continue;
+            }
+            for rule in category.rules.iter().filter(|rule| rule.enabled) {
+                let mut hasher = DefaultHasher::new();
+                category_name.hash(&mut hasher);
+                category.severity.hash(&mut hasher);
+                serde_json::to_string(rule)
+                    .unwrap_or_default()
+                    .hash(&mut hasher);
+                if rule.pattern.starts_with("todo_expired") {
+                    chrono::Local::now().date_naive().hash(&mut hasher);
+                }
+                fingerprints.insert(rule.id.clone(), format!("{:x}", hasher.finish()));
+            }
+        }
+
+        fingerprints
+    }
 }
 
 impl Default for GuardianConfig {
@@ -389,6 +1317,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_webhook_content_type() -> String {
+    "application/json".to_string()
+}
+
 /// Build development marker pattern with simple, readable literals
 fn build_development_marker_pattern() -> String {
     // Simple regex pattern for development markers
@@ -519,3 +1451,40 @@ impl GuardianConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+version: "1.0"
+paths:
+  patterns: []
+  skip_generated: false
+patterns: {}
+"#;
+
+    #[test]
+    fn test_embedded_config() {
+        let embedded = GuardianConfig::embedded().expect("the built-in policy should parse");
+        assert_eq!(embedded.is_some(), !EMBEDDED_CONFIG.trim().is_empty());
+
+        assert!(GuardianConfig::from_embedded("")
+            .expect("an empty policy should be accepted")
+            .is_none());
+        assert!(GuardianConfig::from_embedded(" \n\t\n")
+            .expect("a blank policy should be accepted")
+            .is_none());
+
+        let config = GuardianConfig::from_embedded(POLICY)
+            .expect("a valid policy should parse")
+            .expect("a valid policy should be embedded");
+        assert!(!config.paths.skip_generated);
+
+        let error = GuardianConfig::from_embedded("version: [unclosed")
+            .expect_err("an invalid policy should be rejected");
+        assert!(error
+            .to_string()
+            .contains("Embedded configuration is invalid"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Locked configuration enforcement
+//!
+//! Code Quality Principle: Provable Policy - A locked configuration cannot be weakened locally
+//! - A config marked `locked: true`, or one passed to `--enforce-config`, is the whole policy
+//! - Command-line rule toggles and nested config files cannot override it
+//! - Reports record the SHA-256 of the enforced file so a run can be tied to a published policy
+
+use super::remote::RemoteConfig;
+use crate::domain::violations::{GuardianError, GuardianResult};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// Identify a policy by the SHA-256 of its exact contents, as `sha256:<hex>`
+pub fn policy_hash(contents: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(contents.as_bytes()))
+}
+
+/// Whether a config file marks itself `locked: true`
+///
+/// Unreadable or malformed files count as unlocked; loading them reports the error.
+pub fn is_locked_file(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_yaml::from_str::<serde_yaml::Value>(&contents).ok())
+        .and_then(|value| value.get("locked").and_then(serde_yaml::Value::as_bool))
+        .unwrap_or(false)
+}
+
+/// Fail if any local override was requested while a locked policy is in force
+///
+/// `overrides` pairs each command-line flag with whether it was given.
+pub fn reject_overrides(overrides: &[(&str, bool)]) -> GuardianResult<()> {
+    let given: Vec<&str> = overrides
+        .iter()
+        .filter(|(_, given)| *given)
+        .map(|(flag, _)| *flag)
+        .collect();
+
+    if given.is_empty() {
+        Ok(())
+    } else {
+        Err(GuardianError::config(format!(
+            "The configuration is locked; {} cannot override it",
+            given.join(", ")
+        )))
+    }
+}
+
+/// Read a policy from a file path or an `http(s)://` URL
+pub fn read_policy_source(source: &str) -> GuardianResult<String> {
+    match RemoteConfig::parse(source) {
+        Some(remote) => remote?.load(),
+        None => fs::read_to_string(source).map_err(|e| GuardianError::io(source, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_locked_marker_and_overrides() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let locked = temp_dir.path().join("guardian.yaml");
+        let unlocked = temp_dir.path().join("nested.yaml");
+        fs::write(&locked, "version: \"1.0\"\nlocked: true\n").expect("config should be written");
+        fs::write(&unlocked, "version: \"1.0\"\n").expect("config should be written");
+
+        assert!(is_locked_file(&locked));
+        assert!(!is_locked_file(&unlocked));
+        assert!(!is_locked_file(&temp_dir.path().join("missing.yaml")));
+
+        assert!(reject_overrides(&[("--exclude", false), ("--severity", false)]).is_ok());
+        let error = reject_overrides(&[("--exclude", true), ("--no-ignore", true)])
+            .expect_err("overrides should be rejected")
+            .to_string();
+        assert!(error.contains("--exclude, --no-ignore"));
+
+        let contents =
+            read_policy_source(&locked.display().to_string()).expect("policy should be readable");
+        assert_eq!(
+            policy_hash(&contents),
+            policy_hash("version: \"1.0\"\nlocked: true\n")
+        );
+        assert!(policy_hash(&contents).starts_with("sha256:"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Remote configuration sources
+//!
+//! Architecture: Infrastructure Adapter - Published policies are fetched, pinned and cached
+//! - `https://` sources may pin their contents with a `#sha256=<hex>` suffix; plain `http://`
+//!   sources must, since nothing else protects them from tampering in transit
+//! - Every fetched policy is cached under the project's `.rust/remote_configs/`
+//! - A pinned policy already in the cache is used without touching the network; an unpinned
+//!   one falls back to the cache only when the fetch fails
+
+use super::discovery::find_project_root;
+use crate::domain::violations::{GuardianError, GuardianResult};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the remote configuration cache directory
+pub const REMOTE_CACHE_ENV: &str = "GUARDIAN_REMOTE_CACHE";
+
+/// A configuration published at an HTTP(S) URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteConfig {
+    /// URL without the pin suffix
+    pub url: String,
+    /// Expected SHA-256 of the contents, lowercase hex
+    pub sha256: Option<String>,
+}
+
+impl RemoteConfig {
+    /// Parse `https://host/guardian.yaml` or `https://host/guardian.yaml#sha256=<hex>`
+    ///
+    /// Returns `None` for anything that is not an `http(s)://` URL, and an error for an
+    /// `http://` URL without a pin.
+    pub fn parse(source: &str) -> Option<GuardianResult<Self>> {
+        if !is_remote(source) {
+            return None;
+        }
+
+        let remote = match source.split_once("#sha256=") {
+            Some((url, sha256)) => Self {
+                url: url.to_string(),
+                sha256: Some(sha256.trim().to_ascii_lowercase()),
+            },
+            None => Self {
+                url: source.to_string(),
+                sha256: None,
+            },
+        };
+        if remote.sha256.is_none() && !remote.url.starts_with("https://") {
+            return Some(Err(GuardianError::config(format!(
+                "Refusing to fetch {} over plain HTTP without a #sha256=<hex> pin",
+                remote.url
+            ))));
+        }
+
+        Some(Ok(remote))
+    }
+
+    /// Get the configuration contents, from the cache or the network
+    pub fn load(&self) -> GuardianResult<String> {
+        let cached = cache_dir().map(|dir| dir.join(self.cache_file_name()));
+
+        // Pinned contents never change, so a verified cached copy is as good as a fetch
+        if self.sha256.is_some() {
+            if let Some(contents) = cached.as_deref().and_then(|path| self.read_cached(path)) {
+                return Ok(contents);
+            }
+        }
+
+        match fetch(&self.url).and_then(|contents| self.verify(contents)) {
+            Ok(contents) => {
+                if let Some(path) = &cached {
+                    if let Err(e) = write_cache(path, &contents) {
+                        tracing::warn!("Failed to cache {}: {}", self.url, e);
+                    }
+                }
+                Ok(contents)
+            }
+            Err(error) => match cached.as_deref().and_then(|path| self.read_cached(path)) {
+                Some(contents) => {
+                    tracing::warn!("Using cached copy of {}: {}", self.url, error);
+                    Ok(contents)
+                }
+                None => Err(error),
+            },
+        }
+    }
+
+    fn verify(&self, contents: String) -> GuardianResult<String> {
+        match &self.sha256 {
+            Some(expected) => {
+                let actual = format!("{:x}", Sha256::digest(contents.as_bytes()));
+                if &actual == expected {
+                    Ok(contents)
+                } else {
+                    Err(GuardianError::config(format!(
+                        "Checksum mismatch for {}: expected sha256 {expected}, got {actual}",
+                        self.url
+                    )))
+                }
+            }
+            None => Ok(contents),
+        }
+    }
+
+    fn read_cached(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| self.verify(contents).ok())
+    }
+
+    fn cache_file_name(&self) -> String {
+        let digest = format!("{:x}", Sha256::digest(self.url.as_bytes()));
+        format!("{}.yaml", &digest[..16])
+    }
+}
+
+/// Whether a configuration source is an HTTP(S) URL rather than a file path
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// Directory remote configurations are cached in
+///
+/// `GUARDIAN_REMOTE_CACHE` wins; otherwise `.rust/remote_configs/` under the project root.
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(REMOTE_CACHE_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+
+    let cwd = std::env::current_dir().ok()?;
+    let root = find_project_root(&cwd).unwrap_or(cwd);
+    Some(root.join(".rust").join("remote_configs"))
+}
+
+fn write_cache(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(feature = "remote-config")]
+fn fetch(url: &str) -> GuardianResult<String> {
+    ureq::get(url)
+        .call()
+        .map_err(|e| GuardianError::config(format!("Failed to fetch config from {url}: {e}")))?
+        .into_string()
+        .map_err(|e| GuardianError::config(format!("Failed to read config from {url}: {e}")))
+}
+
+#[cfg(not(feature = "remote-config"))]
+fn fetch(url: &str) -> GuardianResult<String> {
+    Err(GuardianError::config(format!(
+        "Cannot fetch {url}: rust-guardian was built without the 'remote-config' feature"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pinned_sources_use_the_cache() {
+        let pinned = RemoteConfig::parse("https://policies.example.com/guardian.yaml#sha256=ABC")
+            .expect("https source should be remote")
+            .expect("https source should parse");
+        assert_eq!(pinned.url, "https://policies.example.com/guardian.yaml");
+        assert_eq!(pinned.sha256.as_deref(), Some("abc"));
+        assert!(RemoteConfig::parse("configs/guardian.yaml").is_none());
+
+        let contents = "version: \"1.0\"\n";
+        let remote = RemoteConfig {
+            sha256: Some(format!("{:x}", Sha256::digest(contents.as_bytes()))),
+            ..pinned
+        };
+
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let cached = temp_dir.path().join(remote.cache_file_name());
+        write_cache(&cached, contents).expect("cache should be writable");
+        assert_eq!(remote.read_cached(&cached).as_deref(), Some(contents));
+
+        // A cached copy that no longer matches the pin is ignored
+        write_cache(&cached, "version: \"2.0\"\n").expect("cache should be writable");
+        assert!(remote.read_cached(&cached).is_none());
+        assert!(remote.verify("tampered".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_plain_http_requires_a_pin() {
+        assert!(
+            RemoteConfig::parse("http://policies.example.com/guardian.yaml")
+                .expect("http source should be remote")
+                .is_err()
+        );
+        let pinned = RemoteConfig::parse("http://policies.example.com/guardian.yaml#sha256=abc")
+            .expect("http source should be remote")
+            .expect("pinned http source should parse");
+        assert_eq!(pinned.sha256.as_deref(), Some("abc"));
+        assert!(
+            RemoteConfig::parse("https://policies.example.com/guardian.yaml")
+                .expect("https source should be remote")
+                .is_ok()
+        );
+    }
+}
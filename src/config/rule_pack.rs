@@ -0,0 +1,167 @@
+//! Versioning of the built-in rule pack
+//!
+//! Code Quality Principle: Predictable Upgrades - Default rule changes are recorded, never silent
+//! - The default pattern set carries its own version, independent of the crate version
+//! - Every release of the pack lists the rules it added, changed or removed
+//! - Reports and cache fingerprints record the pack version they were produced with
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use serde::Serialize;
+
+/// Version of the built-in rule pack
+///
+/// Bump this and add a [`RulePackRelease`] to [`RULE_PACK_HISTORY`] whenever a default rule
+/// is added, removed, or changes what it matches.
+pub const RULE_PACK_VERSION: &str = "1.0.0";
+
+/// How a default rule changed in a rule pack release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+impl RuleChangeKind {
+    /// Get string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Changed => "changed",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+/// A single default rule change
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleChange {
+    /// Rule identifier
+    pub rule_id: &'static str,
+    /// Kind of change
+    pub kind: RuleChangeKind,
+    /// What changed, from the point of view of a project upgrading
+    pub description: &'static str,
+}
+
+/// A released version of the built-in rule pack
+#[derive(Debug, Clone, Serialize)]
+pub struct RulePackRelease {
+    /// Rule pack version
+    pub version: &'static str,
+    /// Rules changed in this release
+    pub changes: &'static [RuleChange],
+}
+
+/// Every rule pack release, oldest first
+pub const RULE_PACK_HISTORY: &[RulePackRelease] = &[RulePackRelease {
+    version: "1.0.0",
+    changes: &[
+        RuleChange {
+            rule_id: "todo_comments",
+            kind: RuleChangeKind::Added,
+            description: "Development marker comments",
+        },
+        RuleChange {
+            rule_id: "temporary_markers",
+            kind: RuleChangeKind::Added,
+            description: "Temporary implementation markers",
+        },
+        RuleChange {
+            rule_id: "unimplemented_macros",
+            kind: RuleChangeKind::Added,
+            description: "Macros marking unfinished implementations",
+        },
+        RuleChange {
+            rule_id: "empty_ok_return",
+            kind: RuleChangeKind::Added,
+            description: "Functions returning Ok(()) with no other statements",
+        },
+        RuleChange {
+            rule_id: "hardcoded_paths",
+            kind: RuleChangeKind::Added,
+            description: "Hardcoded .rust/ paths in string literals",
+        },
+        RuleChange {
+            rule_id: "architectural_header_missing",
+            kind: RuleChangeKind::Added,
+            description: "Files without an architectural principle header",
+        },
+    ],
+}];
+
+/// Releases after `from` up to and including `to`, oldest first
+///
+/// Both versions must be known releases; `to` defaults to [`RULE_PACK_VERSION`].
+pub fn releases_between(
+    from: &str,
+    to: Option<&str>,
+) -> GuardianResult<Vec<&'static RulePackRelease>> {
+    let to = to.unwrap_or(RULE_PACK_VERSION);
+    let start = release_index(from)?;
+    let end = release_index(to)?;
+    if start > end {
+        return Err(GuardianError::config(format!(
+            "Rule pack version {from} is newer than {to}"
+        )));
+    }
+
+    Ok(RULE_PACK_HISTORY[start + 1..=end].iter().collect())
+}
+
+fn release_index(version: &str) -> GuardianResult<usize> {
+    RULE_PACK_HISTORY
+        .iter()
+        .position(|release| release.version == version)
+        .ok_or_else(|| {
+            let known: Vec<&str> = RULE_PACK_HISTORY.iter().map(|r| r.version).collect();
+            GuardianError::config(format!(
+                "Unknown rule pack version '{version}' (known versions: {})",
+                known.join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_history_matches_default_rules() {
+        let latest = RULE_PACK_HISTORY
+            .last()
+            .expect("history should not be empty");
+        assert_eq!(latest.version, RULE_PACK_VERSION);
+
+        // Replaying the history yields exactly the current default rule set
+        let mut recorded: Vec<&str> = Vec::new();
+        for change in RULE_PACK_HISTORY.iter().flat_map(|r| r.changes) {
+            match change.kind {
+                RuleChangeKind::Added => recorded.push(change.rule_id),
+                RuleChangeKind::Removed => recorded.retain(|id| *id != change.rule_id),
+                RuleChangeKind::Changed => assert!(recorded.contains(&change.rule_id)),
+            }
+        }
+        recorded.sort_unstable();
+
+        let config = GuardianConfig::default();
+        let mut defaults: Vec<&str> = config
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter().map(|rule| rule.id.as_str()))
+            .collect();
+        defaults.sort_unstable();
+
+        assert_eq!(recorded, defaults);
+    }
+
+    #[test]
+    fn test_releases_between() {
+        assert!(releases_between(RULE_PACK_VERSION, None)
+            .expect("current version should be known")
+            .is_empty());
+        assert!(releases_between("0.0.1", None).is_err());
+    }
+}
@@ -7,6 +7,9 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::ops::Range;
 use std::path::PathBuf;
 
 /// Severity levels for code quality violations
@@ -50,12 +53,37 @@ pub struct Violation {
     pub line_number: Option<u32>,
     /// Column number (1-indexed) where the violation starts
     pub column_number: Option<u32>,
+    /// Line number (1-indexed) where the violation ends
+    pub end_line: Option<u32>,
+    /// Column number (1-indexed, exclusive) where the violation ends
+    pub end_column: Option<u32>,
     /// Human-readable description of the violation
     pub message: String,
     /// Source code context around the violation
     pub context: Option<String>,
     /// Suggested fix for the violation (if available)
     pub suggested_fix: Option<String>,
+    /// Edit that resolves the violation, applied by `check --fix`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+    /// Byte range of the offending source text within the file
+    pub byte_range: Option<Range<usize>>,
+    /// Configuration category of the rule that detected this violation
+    pub category: Option<String>,
+    /// Free-form tags attached to the rule
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Link to documentation explaining the rule
+    pub docs_url: Option<String>,
+    /// Why the rule exists, in one line
+    #[serde(default)]
+    pub rationale: Option<String>,
+    /// Cfg predicate gating the code, such as `all(unix, feature = "net")`
+    #[serde(default)]
+    pub cfg: Option<String>,
+    /// Issue tracking this violation, for rules linked to an issue tracker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_url: Option<String>,
     /// When this violation was detected
     pub detected_at: DateTime<Utc>,
 }
@@ -74,9 +102,19 @@ impl Violation {
             file_path,
             line_number: None,
             column_number: None,
+            end_line: None,
+            end_column: None,
             message: message.into(),
             context: None,
             suggested_fix: None,
+            fix: None,
+            byte_range: None,
+            category: None,
+            tags: Vec::new(),
+            docs_url: None,
+            rationale: None,
+            cfg: None,
+            issue_url: None,
             detected_at: Utc::now(),
         }
     }
@@ -88,6 +126,13 @@ impl Violation {
         self
     }
 
+    /// Set the exclusive end position of the violation span
+    pub fn with_end_position(mut self, line: u32, column: u32) -> Self {
+        self.end_line = Some(line);
+        self.end_column = Some(column);
+        self
+    }
+
     /// Add source code context
     pub fn with_context(mut self, context: impl Into<String>) -> Self {
         self.context = Some(context.into());
@@ -100,6 +145,42 @@ impl Violation {
         self
     }
 
+    /// Attach an edit that resolves the violation
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Set the byte range of the offending source text
+    pub fn with_byte_range(mut self, range: Range<usize>) -> Self {
+        self.byte_range = Some(range);
+        self
+    }
+
+    /// Set the category of the rule that produced this violation
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Set the tags of the rule that produced this violation
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Set the documentation link for the rule that produced this violation
+    pub fn with_docs_url(mut self, docs_url: impl Into<String>) -> Self {
+        self.docs_url = Some(docs_url.into());
+        self
+    }
+
+    /// Set the one-line rationale of the rule
+    pub fn with_rationale(mut self, rationale: impl Into<String>) -> Self {
+        self.rationale = Some(rationale.into());
+        self
+    }
+
     /// Whether this violation is blocking (prevents commits/builds)
     pub fn is_blocking(&self) -> bool {
         self.severity.is_blocking()
@@ -121,6 +202,49 @@ impl Violation {
             self.message
         )
     }
+
+    /// Stable identifier for this finding across runs
+    ///
+    /// Hashes the rule, the file path and the offending source text (or the message when
+    /// no context is known), but not the position, so findings keep their fingerprint when
+    /// unrelated edits shift lines. Whitespace is normalized and path separators unified.
+    pub fn fingerprint(&self) -> String {
+        let path = self.file_path.to_string_lossy().replace('\\', "/");
+        let text = self.context.as_deref().unwrap_or(&self.message);
+        let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let mut hasher = Sha256::new();
+        for part in [self.rule_id.as_str(), path.as_str(), normalized.as_str()] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+
+        format!("{:x}", hasher.finalize())[..16].to_string()
+    }
+}
+
+/// A text edit that resolves a violation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    /// Byte range of the file content to replace; empty for an insertion
+    pub byte_range: Range<usize>,
+    /// Text written in place of the range
+    pub replacement: String,
+}
+
+impl Fix {
+    /// Replace a byte range of the file
+    pub fn replace(byte_range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            byte_range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Insert text at a byte offset of the file
+    pub fn insert(offset: usize, text: impl Into<String>) -> Self {
+        Self::replace(offset..offset, text)
+    }
 }
 
 /// Summary statistics for a validation report
@@ -134,10 +258,55 @@ pub struct ValidationSummary {
     pub execution_time_ms: u64,
     /// Timestamp when validation was performed
     pub validated_at: DateTime<Utc>,
+    /// Files skipped because they carry a generated-code marker
+    #[serde(default)]
+    pub skipped_generated: Vec<PathBuf>,
+    /// Files skipped because their content could not be decoded
+    #[serde(default)]
+    pub skipped_undecodable: Vec<PathBuf>,
+    /// Files skipped because analyzing them would exceed the per-file memory bound
+    #[serde(default)]
+    pub skipped_oversized: Vec<PathBuf>,
+    /// Files decoded as something other than plain UTF-8
+    #[serde(default)]
+    pub file_encodings: Vec<FileEncoding>,
+    /// Files left unanalyzed because the analysis time budget ran out
+    #[serde(default)]
+    pub unanalyzed: Vec<PathBuf>,
+    /// Every analyzed file, for formats that report clean files too (not serialized)
+    #[serde(skip)]
+    pub analyzed_files: Vec<PathBuf>,
+    /// Per-rule usage, when collected for this run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_usage: Vec<RuleUsage>,
+    /// Violations hidden as false positives by triage labels, by rule
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub false_positives: BTreeMap<String, usize>,
+}
+
+/// Encoding a file was decoded with, for files that are not plain UTF-8
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEncoding {
+    pub file_path: PathBuf,
+    pub encoding: String,
+}
+
+/// How much work one rule did during a run, and how often it fired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleUsage {
+    pub rule_id: String,
+    /// Files the rule was in scope for and ran on
+    pub files_examined: usize,
+    /// Files the rule reported at least one match in
+    pub files_matched: usize,
+    /// Matches reported, before suppressions and severity filters
+    pub matches: usize,
+    /// Time spent running the rule, summed over all files and threads
+    pub time_ms: f64,
 }
 
 /// Count of violations by severity level
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ViolationCounts {
     pub error: usize,
     pub warning: usize,
@@ -174,6 +343,16 @@ pub struct ValidationReport {
     pub summary: ValidationSummary,
     /// Configuration used for this validation
     pub config_fingerprint: Option<String>,
+    /// Built-in rule pack version used for this validation
+    #[serde(default)]
+    pub rule_pack_version: Option<String>,
+    /// Hash of the locked policy enforced for this validation
+    #[serde(default)]
+    pub policy_hash: Option<String>,
+    /// Directories the validation was restricted to, relative to the project root; empty
+    /// when the whole project was in scope
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
 }
 
 impl ValidationReport {
@@ -186,6 +365,9 @@ impl ValidationReport {
                 ..Default::default()
             },
             config_fingerprint: None,
+            rule_pack_version: None,
+            policy_hash: None,
+            scope: Vec::new(),
         }
     }
 
@@ -195,6 +377,27 @@ impl ValidationReport {
         self.violations.push(violation);
     }
 
+    /// Keep only the violations matching a predicate, updating the severity counts
+    pub fn retain_violations(&mut self, mut keep: impl FnMut(&Violation) -> bool) {
+        self.violations.retain(|violation| keep(violation));
+        self.recount_severities();
+    }
+
+    /// Set the severity of every violation, updating the severity counts
+    pub fn update_severities(&mut self, mut severity_of: impl FnMut(&Violation) -> Severity) {
+        for violation in &mut self.violations {
+            violation.severity = severity_of(violation);
+        }
+        self.recount_severities();
+    }
+
+    fn recount_severities(&mut self) {
+        self.summary.violations_by_severity = ViolationCounts::default();
+        for violation in &self.violations {
+            self.summary.violations_by_severity.add(violation.severity);
+        }
+    }
+
     /// Whether the report contains any violations
     pub fn has_violations(&self) -> bool {
         !self.violations.is_empty()
@@ -217,6 +420,45 @@ impl ValidationReport {
         self.summary.total_files = count;
     }
 
+    /// Record every analyzed file, setting the count to match
+    pub fn set_analyzed_files(&mut self, files: Vec<PathBuf>) {
+        self.summary.total_files = files.len();
+        self.summary.analyzed_files = files;
+    }
+
+    /// Record a file skipped because it is generated
+    pub fn add_skipped_generated(&mut self, file_path: PathBuf) {
+        self.summary.skipped_generated.push(file_path);
+    }
+
+    /// Record a file skipped because it could not be decoded
+    pub fn add_skipped_undecodable(&mut self, file_path: PathBuf) {
+        self.summary.skipped_undecodable.push(file_path);
+    }
+
+    /// Record a file skipped because it exceeds the per-file memory bound
+    pub fn add_skipped_oversized(&mut self, file_path: PathBuf) {
+        self.summary.skipped_oversized.push(file_path);
+    }
+
+    /// Record a file left unanalyzed because the time budget ran out
+    pub fn add_unanalyzed(&mut self, file_path: PathBuf) {
+        self.summary.unanalyzed.push(file_path);
+    }
+
+    /// Whether the analysis stopped early, leaving some files unanalyzed
+    pub fn is_partial(&self) -> bool {
+        !self.summary.unanalyzed.is_empty()
+    }
+
+    /// Record the encoding of a file that is not plain UTF-8
+    pub fn add_file_encoding(&mut self, file_path: PathBuf, encoding: impl Into<String>) {
+        self.summary.file_encodings.push(FileEncoding {
+            file_path,
+            encoding: encoding.into(),
+        });
+    }
+
     /// Set the execution time
     pub fn set_execution_time(&mut self, duration_ms: u64) {
         self.summary.execution_time_ms = duration_ms;
@@ -227,12 +469,47 @@ impl ValidationReport {
         self.config_fingerprint = Some(fingerprint.into());
     }
 
+    /// Set the built-in rule pack version
+    pub fn set_rule_pack_version(&mut self, version: impl Into<String>) {
+        self.rule_pack_version = Some(version.into());
+    }
+
+    /// Set the hash of the enforced locked policy
+    pub fn set_policy_hash(&mut self, hash: impl Into<String>) {
+        self.policy_hash = Some(hash.into());
+    }
+
     /// Merge another report into this one
+    ///
+    /// The merged report covers the union of both scopes, and the whole project when either
+    /// report does.
     pub fn merge(&mut self, other: ValidationReport) {
+        if self.scope.is_empty() || other.scope.is_empty() {
+            self.scope.clear();
+        } else {
+            for scope in other.scope {
+                if !self.scope.contains(&scope) {
+                    self.scope.push(scope);
+                }
+            }
+        }
         for violation in other.violations {
             self.add_violation(violation);
         }
         self.summary.total_files += other.summary.total_files;
+        self.summary
+            .skipped_generated
+            .extend(other.summary.skipped_generated);
+        self.summary
+            .skipped_undecodable
+            .extend(other.summary.skipped_undecodable);
+        self.summary
+            .skipped_oversized
+            .extend(other.summary.skipped_oversized);
+        self.summary
+            .file_encodings
+            .extend(other.summary.file_encodings);
+        self.summary.unanalyzed.extend(other.summary.unanalyzed);
     }
 
     /// Sort violations by file path and line number for consistent output
@@ -260,9 +537,13 @@ pub enum GuardianError {
     Configuration { message: String },
 
     /// File could not be read or accessed
-    #[error("IO error: {source}")]
+    #[error(
+        "IO error{}: {source}",
+        .path.as_ref().map(|p| format!(" in {}", p.display())).unwrap_or_default()
+    )]
     Io {
-        #[from]
+        path: Option<PathBuf>,
+        #[source]
         source: std::io::Error,
     },
 
@@ -270,6 +551,14 @@ pub enum GuardianError {
     #[error("Pattern error: {message}")]
     Pattern { message: String },
 
+    /// A configured rule could not be compiled
+    #[error("Failed to compile rule '{rule_id}': {source}")]
+    RuleCompilation {
+        rule_id: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     /// Analysis failed for a specific file
     #[error("Analysis error in {file}: {message}")]
     Analysis { file: String, message: String },
@@ -278,9 +567,26 @@ pub enum GuardianError {
     #[error("Cache error: {message}")]
     Cache { message: String },
 
-    /// Validation operation failed
+    /// Validation failed, carrying the report when blocking violations were found
     #[error("Validation error: {message}")]
-    Validation { message: String },
+    Validation {
+        message: String,
+        report: Option<Box<ValidationReport>>,
+    },
+
+    /// A report signature could not be created or did not verify
+    #[error("Attestation error: {message}")]
+    Attestation { message: String },
+
+    /// The operation was canceled before it completed
+    #[error("Operation canceled")]
+    Canceled,
+}
+
+impl From<std::io::Error> for GuardianError {
+    fn from(source: std::io::Error) -> Self {
+        Self::Io { path: None, source }
+    }
 }
 
 impl GuardianError {
@@ -291,6 +597,14 @@ impl GuardianError {
         }
     }
 
+    /// Create an IO error for the path being accessed
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
     /// Create a pattern error
     pub fn pattern(message: impl Into<String>) -> Self {
         Self::Pattern {
@@ -298,6 +612,17 @@ impl GuardianError {
         }
     }
 
+    /// Create a rule compilation error wrapping the underlying cause
+    pub fn rule_compilation(
+        rule_id: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::RuleCompilation {
+            rule_id: rule_id.into(),
+            source: source.into(),
+        }
+    }
+
     /// Create an analysis error
     pub fn analysis(file: impl Into<String>, message: impl Into<String>) -> Self {
         Self::Analysis {
@@ -313,10 +638,41 @@ impl GuardianError {
         }
     }
 
+    /// Create an attestation error
+    pub fn attestation(message: impl Into<String>) -> Self {
+        Self::Attestation {
+            message: message.into(),
+        }
+    }
+
     /// Create a validation error
     pub fn validation(message: impl Into<String>) -> Self {
         Self::Validation {
             message: message.into(),
+            report: None,
+        }
+    }
+
+    /// Create a validation error carrying the report that failed
+    pub fn validation_failed(report: ValidationReport) -> Self {
+        let counts = &report.summary.violations_by_severity;
+        let total = counts.total();
+        Self::Validation {
+            message: format!(
+                "{} violation{} found ({} blocking)",
+                total,
+                if total == 1 { "" } else { "s" },
+                counts.error
+            ),
+            report: Some(Box::new(report)),
+        }
+    }
+
+    /// The validation report attached to this error, if any
+    pub fn report(&self) -> Option<&ValidationReport> {
+        match self {
+            Self::Validation { report, .. } => report.as_deref(),
+            _ => None,
         }
     }
 }
@@ -390,6 +746,80 @@ mod tests {
         assert_eq!(report.summary.violations_by_severity.warning, 1);
     }
 
+    #[test]
+    fn test_merge_scopes() {
+        let scoped = |dirs: &[&str]| {
+            let mut report = ValidationReport::new();
+            report.scope = dirs.iter().map(|dir| dir.to_string()).collect();
+            report
+        };
+
+        let mut merged = scoped(&["crates/core"]);
+        merged.merge(scoped(&["crates/cli", "crates/core"]));
+        assert_eq!(merged.scope, ["crates/core", "crates/cli"]);
+
+        merged.merge(scoped(&[]));
+        assert!(merged.scope.is_empty());
+    }
+
+    #[test]
+    fn test_error_context_and_sources() {
+        use std::error::Error;
+
+        let unbalanced = String::from("(");
+        let regex_error =
+            regex::Regex::new(&unbalanced).expect_err("unbalanced group should not compile");
+        let error = GuardianError::rule_compilation("bad_rule", regex_error);
+        assert!(error
+            .to_string()
+            .starts_with("Failed to compile rule 'bad_rule'"));
+        assert!(error.source().is_some());
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let error = GuardianError::io("src/lib.rs", io_error);
+        assert_eq!(error.to_string(), "IO error in src/lib.rs: missing");
+        assert!(error.source().is_some());
+
+        let mut report = ValidationReport::new();
+        report.add_violation(Violation::new(
+            "rule1",
+            Severity::Error,
+            PathBuf::from("src/main.rs"),
+            "Error message",
+        ));
+        let error = GuardianError::validation_failed(report);
+        assert_eq!(
+            error.to_string(),
+            "Validation error: 1 violation found (1 blocking)"
+        );
+        assert_eq!(error.report().map(|r| r.violations.len()), Some(1));
+        assert!(GuardianError::Canceled.report().is_none());
+    }
+
+    #[test]
+    fn test_violation_fingerprint_is_position_independent() {
+        let violation = Violation::new(
+            "todo_comments",
+            Severity::Warning,
+            PathBuf::from("src/lib.rs"),
+            "TODO found",
+        )
+        .with_context("    // TODO: handle errors");
+
+        let shifted = violation
+            .clone()
+            .with_position(120, 8)
+            .with_context("// TODO:   handle errors");
+        assert_eq!(violation.fingerprint(), shifted.fingerprint());
+        assert_eq!(violation.fingerprint().len(), 16);
+
+        let other_file = Violation {
+            file_path: PathBuf::from("src/main.rs"),
+            ..violation.clone()
+        };
+        assert_ne!(violation.fingerprint(), other_file.fingerprint());
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Error > Severity::Warning);
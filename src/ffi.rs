@@ -0,0 +1,159 @@
+//! C ABI for embedding Guardian in tools written in other languages
+//!
+//! Architecture: Anti-corruption Layer - Foreign callers exchange JSON strings, never Rust types
+//! - Every entry point takes and returns NUL-terminated UTF-8 strings
+//! - Failures, panics included, are reported in the returned JSON so callers need no error
+//!   channel of their own; this relies on the default `panic = "unwind"` strategy
+//! - Strings returned by Guardian are owned by Guardian and released with `guardian_string_free`
+//!
+//! The matching declarations are in `include/rust_guardian.h`.
+
+use crate::{
+    AnalysisOptions, GuardianConfig, GuardianError, GuardianResult, GuardianValidator, OutputFormat,
+};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+/// Validate files and return the report as JSON
+///
+/// `config_json` is a Guardian configuration document in JSON (or YAML); NULL or an empty
+/// string selects the default configuration. `files_json` is a JSON array of file or
+/// directory paths. The result is the report in the `--format json` layout, or
+/// `{"error": "..."}` when validation could not run. It is never NULL and must be released
+/// with `guardian_string_free`.
+///
+/// # Safety
+///
+/// `config_json` must be NULL or point to a NUL-terminated string, and `files_json` must
+/// point to a NUL-terminated string. Both must stay valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn guardian_validate_json(
+    config_json: *const c_char,
+    files_json: *const c_char,
+) -> *mut c_char {
+    // SAFETY: the caller guarantees both pointers are NULL or valid C strings
+    let config = unsafe { optional_str(config_json) };
+    let files = unsafe { optional_str(files_json) };
+
+    let result = catch_unwind(AssertUnwindSafe(|| validate_json(config, files)))
+        .unwrap_or_else(|_| Err(GuardianError::analysis("ffi", "validation panicked")));
+    let json = match result {
+        Ok(report) => report,
+        Err(e) => serde_json::json!({ "error": e.to_string() }).to_string(),
+    };
+    into_c_string(json)
+}
+
+/// Release a string returned by Guardian
+///
+/// # Safety
+///
+/// `value` must be NULL or a pointer returned by a Guardian function that has not been
+/// released yet.
+#[no_mangle]
+pub unsafe extern "C" fn guardian_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: the pointer came from `CString::into_raw` in `into_c_string`
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+/// The Guardian version as a static NUL-terminated string, which must not be freed
+#[no_mangle]
+pub extern "C" fn guardian_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Run validation for already decoded arguments
+fn validate_json(config: Option<&str>, files: Option<&str>) -> GuardianResult<String> {
+    let config = match config.map(str::trim).filter(|config| !config.is_empty()) {
+        Some(document) => GuardianConfig::load_from_str(document)?,
+        None => GuardianConfig::default(),
+    };
+    let files: Vec<PathBuf> = serde_json::from_str(
+        files.ok_or_else(|| GuardianError::config("files_json must be a JSON array of paths"))?,
+    )
+    .map_err(|e| GuardianError::config(format!("Invalid files_json: {e}")))?;
+
+    let validator = GuardianValidator::new_with_config(config)?;
+    let report = validator.validate_streaming(&files, &AnalysisOptions::default(), |_, _| {})?;
+    validator.format_report(&report, OutputFormat::Json)
+}
+
+/// Borrow a C string as UTF-8, treating NULL and invalid UTF-8 as absent
+///
+/// # Safety
+///
+/// `value` must be NULL or point to a NUL-terminated string that outlives the borrow.
+unsafe fn optional_str<'a>(value: *const c_char) -> Option<&'a str> {
+    if value.is_null() {
+        return None;
+    }
+    // SAFETY: non-NULL and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(value) }.to_str().ok()
+}
+
+/// Hand a string to the caller, who releases it with `guardian_string_free`
+fn into_c_string(value: String) -> *mut c_char {
+    // JSON escapes control characters, so interior NULs cannot occur
+    CString::new(value).unwrap_or_default().into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(config: Option<&str>, files: &str) -> serde_json::Value {
+        let config = config.map(|config| CString::new(config).expect("config"));
+        let files = CString::new(files).expect("files");
+        unsafe {
+            let result = guardian_validate_json(
+                config
+                    .as_ref()
+                    .map_or(std::ptr::null(), |config| config.as_ptr()),
+                files.as_ptr(),
+            );
+            let json = CStr::from_ptr(result).to_str().expect("utf-8").to_string();
+            guardian_string_free(result);
+            serde_json::from_str(&json).expect("result should be JSON")
+        }
+    }
+
+    #[test]
+    fn test_validate_json() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn run() {\n    // TODO: finish\n}\n").expect("write");
+        let files = serde_json::json!([file]).to_string();
+        let reports_todo = |report: &serde_json::Value| {
+            report["violations"]
+                .as_array()
+                .expect("violations")
+                .iter()
+                .any(|v| v["rule_id"] == "todo_comments")
+        };
+
+        let report = call(None, &files);
+        assert!(report.get("error").is_none(), "{report}");
+        assert!(reports_todo(&report));
+
+        // Only the built-in Rust checks remain without configured rules
+        let config = r#"{"version": "1.0", "paths": {"patterns": []}, "patterns": {}}"#;
+        let report = call(Some(config), &files);
+        assert!(report.get("error").is_none(), "{report}");
+        assert!(!reports_todo(&report));
+    }
+
+    #[test]
+    fn test_errors_are_json() {
+        assert!(call(None, "not json")["error"]
+            .as_str()
+            .expect("error message")
+            .contains("files_json"));
+        assert!(call(Some("version: ["), "[]")["error"].is_string());
+
+        let version = unsafe { CStr::from_ptr(guardian_version()) };
+        assert_eq!(version.to_str().ok(), Some(env!("CARGO_PKG_VERSION")));
+    }
+}
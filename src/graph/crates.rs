@@ -0,0 +1,510 @@
+//! Crate dependency graph built from `cargo metadata`
+//!
+//! Architecture: Domain Services - Workspace crates form their own dependency graph
+//! - Cargo rejects cycles between normal dependencies, but dev- and build-dependencies
+//!   on path crates can still close a loop that breaks publishing and slows builds
+//! - Each edge keeps the manifest entries that declare it so violations point at Cargo.toml
+
+use super::{cyclic_edges, find_cycles, successors, Edges};
+use crate::domain::violations::{GuardianError, GuardianResult, Severity, Violation};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Section of a manifest a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl DependencyKind {
+    /// Manifest table that declares this kind of dependency
+    pub fn table_name(self) -> &'static str {
+        match self {
+            DependencyKind::Normal => "dependencies",
+            DependencyKind::Dev => "dev-dependencies",
+            DependencyKind::Build => "build-dependencies",
+        }
+    }
+
+    /// Kind name as reported by `cargo metadata`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DependencyKind::Normal => "normal",
+            DependencyKind::Dev => "dev",
+            DependencyKind::Build => "build",
+        }
+    }
+
+    fn from_metadata(kind: Option<&str>) -> Self {
+        match kind {
+            Some("dev") => DependencyKind::Dev,
+            Some("build") => DependencyKind::Build,
+            _ => DependencyKind::Normal,
+        }
+    }
+}
+
+/// A manifest entry making one workspace crate depend on another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateDependency {
+    /// Crate declaring the dependency
+    pub from_crate: String,
+    /// Workspace crate being depended on
+    pub to_crate: String,
+    /// Section the dependency is declared in
+    pub kind: DependencyKind,
+    /// Manifest declaring the dependency
+    pub manifest_path: PathBuf,
+    /// Line (1-indexed) of the entry, when it could be located in the manifest
+    pub line: Option<u32>,
+    /// Text of the manifest line declaring the dependency
+    pub entry: Option<String>,
+}
+
+/// Crate-to-crate dependency graph of a Cargo workspace
+///
+/// Only path dependencies between workspace members are edges; registry and git
+/// dependencies cannot form a cycle with workspace code.
+#[derive(Debug, Clone, Default)]
+pub struct CrateGraph {
+    /// All workspace crates, including those without dependencies
+    crates: BTreeSet<String>,
+    /// Dependency edges with the manifest entries that establish them
+    edges: Edges<CrateDependency>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+    #[serde(default)]
+    dependencies: Vec<MetadataDependency>,
+}
+
+#[derive(Deserialize)]
+struct MetadataDependency {
+    name: String,
+    kind: Option<String>,
+    rename: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl CrateGraph {
+    /// Build the graph by running `cargo metadata` for a workspace manifest
+    pub fn from_manifest<P: AsRef<Path>>(manifest_path: P) -> GuardianResult<Self> {
+        let manifest_path = manifest_path.as_ref();
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+        let output = Command::new(cargo)
+            .args([
+                "metadata",
+                "--format-version",
+                "1",
+                "--no-deps",
+                "--manifest-path",
+            ])
+            .arg(manifest_path)
+            .output()
+            .map_err(|e| {
+                GuardianError::analysis(
+                    manifest_path.display().to_string(),
+                    format!("Failed to run cargo metadata: {e}"),
+                )
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::analysis(
+                manifest_path.display().to_string(),
+                format!(
+                    "cargo metadata failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ));
+        }
+
+        Self::from_metadata(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Build the graph from `cargo metadata --format-version 1` output
+    ///
+    /// Manifests are read to locate each dependency entry; unreadable manifests leave the
+    /// entry without a line number.
+    pub fn from_metadata(metadata_json: &str) -> GuardianResult<Self> {
+        let metadata: Metadata = serde_json::from_str(metadata_json)
+            .map_err(|e| GuardianError::validation(format!("Invalid cargo metadata: {e}")))?;
+
+        let members: BTreeSet<&str> = metadata
+            .workspace_members
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let packages: Vec<&MetadataPackage> = metadata
+            .packages
+            .iter()
+            .filter(|package| members.contains(package.id.as_str()))
+            .collect();
+
+        let mut graph = Self {
+            crates: packages
+                .iter()
+                .map(|package| package.name.clone())
+                .collect(),
+            edges: BTreeMap::new(),
+        };
+
+        for package in &packages {
+            let manifest = fs::read_to_string(&package.manifest_path).unwrap_or_default();
+
+            for dependency in &package.dependencies {
+                if dependency.path.is_none()
+                    || dependency.name == package.name
+                    || !graph.crates.contains(&dependency.name)
+                {
+                    continue;
+                }
+
+                let kind = DependencyKind::from_metadata(dependency.kind.as_deref());
+                let key = dependency.rename.as_deref().unwrap_or(&dependency.name);
+                let (line, entry) = match find_dependency_entry(&manifest, key, kind) {
+                    Some((line, entry)) => (Some(line), Some(entry)),
+                    None => (None, None),
+                };
+
+                graph
+                    .edges
+                    .entry(package.name.clone())
+                    .or_default()
+                    .entry(dependency.name.clone())
+                    .or_default()
+                    .push(CrateDependency {
+                        from_crate: package.name.clone(),
+                        to_crate: dependency.name.clone(),
+                        kind,
+                        manifest_path: package.manifest_path.clone(),
+                        line,
+                        entry,
+                    });
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// All workspace crates in the graph
+    pub fn crates(&self) -> impl Iterator<Item = &str> {
+        self.crates.iter().map(String::as_str)
+    }
+
+    /// Workspace crates that `name` depends on
+    pub fn dependencies(&self, name: &str) -> impl Iterator<Item = &str> {
+        successors(&self.edges, name)
+    }
+
+    /// Manifest entries that make `from` depend on `to`
+    pub fn dependencies_between(&self, from: &str, to: &str) -> &[CrateDependency] {
+        self.edges
+            .get(from)
+            .and_then(|targets| targets.get(to))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Find dependency cycles
+    ///
+    /// Returns one shortest cycle per strongly connected component, starting from the
+    /// component's alphabetically first crate. The first crate is not repeated at the end.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        find_cycles(&self.crates, &self.edges)
+    }
+
+    /// Report each dependency cycle as a `circular_crate_dependency` violation
+    ///
+    /// The violation points at the first dev- or build-dependency on the cycle, since that
+    /// is the entry Cargo allowed to close it, falling back to the entry closing the cycle.
+    pub fn cycle_violations(&self) -> Vec<Violation> {
+        self.cycles()
+            .into_iter()
+            .filter_map(|cycle| {
+                let hops: Vec<(&str, &str)> = cycle
+                    .iter()
+                    .zip(cycle.iter().cycle().skip(1))
+                    .map(|(from, to)| (from.as_str(), to.as_str()))
+                    .collect();
+
+                let offending = hops
+                    .iter()
+                    .flat_map(|&(from, to)| self.dependencies_between(from, to))
+                    .find(|dependency| dependency.kind != DependencyKind::Normal)
+                    .or_else(|| {
+                        let &(from, to) = hops.last()?;
+                        self.dependencies_between(from, to).first()
+                    })?;
+
+                let mut chain = vec![cycle.first()?.clone()];
+                for &(from, to) in &hops {
+                    chain.push(match self.edge_label(from, to) {
+                        Some(kind) => format!("{to} ({kind})"),
+                        None => to.to_string(),
+                    });
+                }
+
+                let mut violation = Violation::new(
+                    "circular_crate_dependency",
+                    Severity::Error,
+                    offending.manifest_path.clone(),
+                    format!("Circular crate dependency: {}", chain.join(" -> ")),
+                )
+                .with_suggestion(
+                    "Move the shared code into a crate both sides can depend on, or drop the dependency",
+                );
+
+                if let Some(line) = offending.line {
+                    violation = violation.with_position(line, 1);
+                }
+                if let Some(entry) = &offending.entry {
+                    violation = violation.with_context(entry.clone());
+                }
+
+                Some(violation)
+            })
+            .collect()
+    }
+
+    /// Render the graph in Graphviz DOT format, highlighting edges that form cycles
+    pub fn to_dot(&self) -> String {
+        let cyclic_edges = cyclic_edges(&self.crates, &self.edges);
+        let mut dot = String::from("digraph crates {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for name in &self.crates {
+            dot.push_str(&format!("    \"{name}\";\n"));
+        }
+
+        for (from, targets) in &self.edges {
+            for to in targets.keys() {
+                let mut attributes = Vec::new();
+                if let Some(kind) = self.edge_label(from, to) {
+                    attributes.push(format!("label=\"{kind}\", style=dashed"));
+                }
+                if cyclic_edges.contains(&(from.as_str(), to.as_str())) {
+                    attributes.push("color=red".to_string());
+                }
+
+                if attributes.is_empty() {
+                    dot.push_str(&format!("    \"{from}\" -> \"{to}\";\n"));
+                } else {
+                    dot.push_str(&format!(
+                        "    \"{from}\" -> \"{to}\" [{}];\n",
+                        attributes.join(", ")
+                    ));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the graph as JSON with crates, dependencies, and cycles
+    pub fn to_json(&self) -> GuardianResult<String> {
+        let dependencies: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .flat_map(|(from, targets)| {
+                targets.iter().map(move |(to, entries)| {
+                    let entries: Vec<serde_json::Value> = entries
+                        .iter()
+                        .map(|dependency| {
+                            serde_json::json!({
+                                "kind": dependency.kind.as_str(),
+                                "manifest": dependency.manifest_path.display().to_string(),
+                                "line": dependency.line
+                            })
+                        })
+                        .collect();
+
+                    serde_json::json!({
+                        "from": from,
+                        "to": to,
+                        "entries": entries
+                    })
+                })
+            })
+            .collect();
+
+        let graph = serde_json::json!({
+            "crates": self.crates,
+            "dependencies": dependencies,
+            "cycles": self.cycles()
+        });
+
+        serde_json::to_string_pretty(&graph)
+            .map_err(|e| GuardianError::validation(format!("Graph serialization failed: {e}")))
+    }
+
+    /// Manifest table to mention for an edge that only exists through dev- or build-dependencies
+    fn edge_label(&self, from: &str, to: &str) -> Option<&'static str> {
+        let entries = self.dependencies_between(from, to);
+        if entries
+            .iter()
+            .any(|dependency| dependency.kind == DependencyKind::Normal)
+        {
+            return None;
+        }
+        entries
+            .first()
+            .map(|dependency| dependency.kind.table_name())
+    }
+}
+
+/// Locate the manifest line declaring a dependency
+///
+/// Handles `name = ...` and `name.path = ...` entries inside the matching table, including
+/// target-specific tables, and `[dependencies.name]` table headers.
+fn find_dependency_entry(manifest: &str, key: &str, kind: DependencyKind) -> Option<(u32, String)> {
+    let table = kind.table_name();
+    let dotted_table = format!(".{table}");
+    let quoted_key = format!("\"{key}\"");
+    let mut in_table = false;
+
+    for (index, line) in manifest.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            let header = trimmed.trim_matches(|c| c == '[' || c == ']').trim();
+            let header_key = header
+                .rsplit_once('.')
+                .filter(|(prefix, _)| *prefix == table || prefix.ends_with(&dotted_table))
+                .map(|(_, name)| name.trim_matches('"'));
+            if header_key == Some(key) {
+                return Some((index as u32 + 1, trimmed.to_string()));
+            }
+
+            in_table = header == table || header.ends_with(&dotted_table);
+            continue;
+        }
+
+        if !in_table {
+            continue;
+        }
+
+        let rest = trimmed
+            .strip_prefix(quoted_key.as_str())
+            .or_else(|| trimmed.strip_prefix(key))
+            .map(str::trim_start);
+        if rest.is_some_and(|rest| rest.starts_with('=') || rest.starts_with('.')) {
+            return Some((index as u32 + 1, trimmed.to_string()));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_manifest(root: &Path, name: &str, dependencies: &str) -> PathBuf {
+        let crate_dir = root.join(name);
+        fs::create_dir_all(&crate_dir).expect("crate directory should be creatable");
+        let manifest_path = crate_dir.join("Cargo.toml");
+        fs::write(
+            &manifest_path,
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n{dependencies}"),
+        )
+        .expect("manifest should be writable");
+        manifest_path
+    }
+
+    #[test]
+    fn test_crate_graph_dev_dependency_cycle() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let core = write_manifest(
+            temp_dir.path(),
+            "core",
+            "[dependencies]\nserde = \"1\"\n\n[dev-dependencies]\ntestkit = { path = \"../testkit\" }\n",
+        );
+        let testkit = write_manifest(
+            temp_dir.path(),
+            "testkit",
+            "[dependencies]\ncore = { path = \"../core\" }\n",
+        );
+
+        let metadata = serde_json::json!({
+            "packages": [
+                {
+                    "id": "core 0.1.0",
+                    "name": "core",
+                    "manifest_path": core,
+                    "dependencies": [
+                        { "name": "serde", "kind": null, "rename": null },
+                        { "name": "testkit", "kind": "dev", "rename": null, "path": temp_dir.path().join("testkit") }
+                    ]
+                },
+                {
+                    "id": "testkit 0.1.0",
+                    "name": "testkit",
+                    "manifest_path": testkit,
+                    "dependencies": [
+                        { "name": "core", "kind": null, "rename": null, "path": temp_dir.path().join("core") }
+                    ]
+                }
+            ],
+            "workspace_members": ["core 0.1.0", "testkit 0.1.0"]
+        });
+
+        let graph =
+            CrateGraph::from_metadata(&metadata.to_string()).expect("metadata should parse");
+        assert_eq!(
+            graph.cycles(),
+            vec![vec!["core".to_string(), "testkit".to_string()]]
+        );
+
+        let violations = graph.cycle_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "circular_crate_dependency");
+        assert_eq!(violations[0].file_path, core);
+        assert_eq!(violations[0].line_number, Some(9));
+        assert_eq!(
+            violations[0].message,
+            "Circular crate dependency: core -> testkit (dev-dependencies) -> core"
+        );
+        assert!(graph.to_dot().contains(
+            "\"core\" -> \"testkit\" [label=\"dev-dependencies\", style=dashed, color=red];"
+        ));
+    }
+
+    #[test]
+    fn test_find_dependency_entry() {
+        let manifest = "[package]\nname = \"app\"\n\n[dependencies]\nutils.path = \"../utils\"\n\n[target.'cfg(unix)'.build-dependencies]\n\"codegen\" = { path = \"../codegen\" }\n\n[dev-dependencies.fixtures]\npath = \"../fixtures\"\n";
+
+        assert_eq!(
+            find_dependency_entry(manifest, "utils", DependencyKind::Normal).map(|(line, _)| line),
+            Some(5)
+        );
+        assert_eq!(
+            find_dependency_entry(manifest, "codegen", DependencyKind::Build).map(|(line, _)| line),
+            Some(8)
+        );
+        assert_eq!(
+            find_dependency_entry(manifest, "fixtures", DependencyKind::Dev).map(|(line, _)| line),
+            Some(10)
+        );
+        assert_eq!(
+            find_dependency_entry(manifest, "utils", DependencyKind::Dev),
+            None
+        );
+    }
+}
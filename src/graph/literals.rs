@@ -0,0 +1,208 @@
+//! Cross-file index of string literals
+//!
+//! Architecture: Domain Services - Literal duplication is a property of a crate, not a file
+//! - Files are grouped by the crate the import index assigned them to
+//! - Test code is skipped; literals inside macro invocations are counted
+//! - Grouping by exact value keeps the index independent of any reporting threshold
+
+use super::ImportIndex;
+use crate::analyzer::encoding::read_source;
+use crate::domain::violations::GuardianResult;
+use crate::patterns::scope::is_test_code;
+use crate::patterns::SourceLocation;
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use syn::visit::Visit;
+
+/// One occurrence of a string literal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteral {
+    /// Literal value with escapes resolved
+    pub value: String,
+    /// Crate the literal appears in
+    pub crate_name: String,
+    /// File containing the literal
+    pub file_path: PathBuf,
+    /// Position of the literal including its quotes
+    pub location: SourceLocation,
+}
+
+/// String literals of every indexed crate grouped by value
+#[derive(Debug, Clone, Default)]
+pub struct LiteralIndex {
+    /// Occurrences keyed by crate name and literal value, in file order
+    literals: BTreeMap<(String, String), Vec<StringLiteral>>,
+}
+
+impl LiteralIndex {
+    /// Collect string literals from every file in an import index
+    ///
+    /// Literals shorter than `min_length` characters are ignored. Files that fail to parse
+    /// are skipped.
+    pub fn build(index: &ImportIndex, min_length: usize) -> GuardianResult<Self> {
+        let mut literal_index = Self::default();
+
+        for (file, module) in index.files() {
+            let crate_name = module.split("::").next().unwrap_or(module);
+
+            let content = read_source(file, true)?.content;
+
+            let syntax_tree = match syn::parse_file(&content) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    tracing::debug!("Skipping {} in literal index: {}", file.display(), e);
+                    continue;
+                }
+            };
+
+            let mut collector = LiteralCollector {
+                content: &content,
+                min_length,
+                found: Vec::new(),
+            };
+            collector.visit_file(&syntax_tree);
+
+            for (value, location) in collector.found {
+                literal_index
+                    .literals
+                    .entry((crate_name.to_string(), value.clone()))
+                    .or_default()
+                    .push(StringLiteral {
+                        value,
+                        crate_name: crate_name.to_string(),
+                        file_path: file.to_path_buf(),
+                        location,
+                    });
+            }
+        }
+
+        Ok(literal_index)
+    }
+
+    /// Literals occurring more than `max_repeats` times within one crate
+    ///
+    /// Each group lists every occurrence, ordered by crate and then by value.
+    pub fn duplicates(&self, max_repeats: usize) -> impl Iterator<Item = &[StringLiteral]> {
+        self.literals
+            .values()
+            .filter(move |occurrences| occurrences.len() > max_repeats)
+            .map(Vec::as_slice)
+    }
+
+    /// Every occurrence of a literal in a crate
+    pub fn occurrences(&self, crate_name: &str, value: &str) -> &[StringLiteral] {
+        self.literals
+            .get(&(crate_name.to_string(), value.to_string()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Collects string literals outside test code
+struct LiteralCollector<'a> {
+    content: &'a str,
+    min_length: usize,
+    found: Vec<(String, SourceLocation)>,
+}
+
+impl LiteralCollector<'_> {
+    fn record(&mut self, lit: &syn::LitStr) {
+        let value = lit.value();
+        if value.chars().count() >= self.min_length {
+            let location = SourceLocation::from_span(self.content, lit.span());
+            self.found.push((value, location));
+        }
+    }
+
+    /// Macro arguments are unparsed tokens, so literals are picked out of the stream
+    fn record_tokens(&mut self, tokens: TokenStream) {
+        for token in tokens {
+            match token {
+                TokenTree::Literal(literal) => {
+                    if let syn::Lit::Str(lit) = syn::Lit::new(literal) {
+                        self.record(&lit);
+                    }
+                }
+                TokenTree::Group(group) => self.record_tokens(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visit<'_> for LiteralCollector<'_> {
+    fn visit_item_fn(&mut self, func: &syn::ItemFn) {
+        if !is_test_code(&func.attrs) {
+            syn::visit::visit_item_fn(self, func);
+        }
+    }
+
+    fn visit_impl_item_fn(&mut self, func: &syn::ImplItemFn) {
+        if !is_test_code(&func.attrs) {
+            syn::visit::visit_impl_item_fn(self, func);
+        }
+    }
+
+    fn visit_item_mod(&mut self, item_mod: &syn::ItemMod) {
+        if !is_test_code(&item_mod.attrs) {
+            syn::visit::visit_item_mod(self, item_mod);
+        }
+    }
+
+    // Attribute arguments (paths, cfg values, serde renames) are not code
+    fn visit_attribute(&mut self, _attr: &syn::Attribute) {}
+
+    fn visit_lit_str(&mut self, lit: &syn::LitStr) {
+        self.record(lit);
+    }
+
+    fn visit_macro(&mut self, mac: &syn::Macro) {
+        self.record_tokens(mac.tokens.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_literal_index_duplicates() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n",
+        )
+        .expect("manifest should be writable");
+        fs::create_dir_all(root.join("src")).expect("src should be creatable");
+
+        let files = vec![root.join("src/lib.rs"), root.join("src/orders.rs")];
+        fs::write(
+            &files[0],
+            "mod orders;\n\n#[derive(serde::Serialize)]\n#[serde(rename = \"customer_id\")]\npub struct Id;\n\npub fn load() -> &'static str {\n    \"customer_id\"\n}\n\n#[test]\nfn test_load() {\n    assert_eq!(load(), \"customer_id\");\n}\n",
+        )
+        .expect("lib should be writable");
+        fs::write(
+            &files[1],
+            "pub fn key() -> String {\n    format!(\"{}\", \"customer_id\")\n}\n\npub fn other() -> &'static str {\n    \"customer_id\"\n}\n",
+        )
+        .expect("module should be writable");
+
+        let index = ImportIndex::build(&files).expect("import index should build");
+        let literals = LiteralIndex::build(&index, 4).expect("literal index should build");
+
+        let occurrences = literals.occurrences("shop", "customer_id");
+        assert_eq!(occurrences.len(), 3);
+        assert_eq!(occurrences[0].file_path, files[0]);
+        assert_eq!(occurrences[0].location.line, 8);
+        assert_eq!(occurrences[1].location.line, 2);
+
+        assert_eq!(literals.duplicates(2).count(), 1);
+        assert_eq!(literals.duplicates(3).count(), 0);
+        // Short literals such as "{}" are never indexed
+        assert!(literals.occurrences("shop", "{}").is_empty());
+    }
+}
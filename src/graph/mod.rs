@@ -0,0 +1,816 @@
+//! Module dependency graph built from `use` statements
+//!
+//! Architecture: Domain Services - The module graph models how code units depend on each other
+//! - ImportIndex resolves every `use` across the workspace to the module it imports from
+//! - ModuleGraph aggregates those imports into module-to-module edges and detects cycles
+//! - CrateGraph does the same for workspace crates using `cargo metadata`
+//! - LiteralIndex reuses the import index's crate layout to find repeated string literals
+//! - DOT and JSON rendering is kept separate from graph construction
+
+mod crates;
+mod literals;
+
+pub use crates::{CrateDependency, CrateGraph, DependencyKind};
+pub use literals::{LiteralIndex, StringLiteral};
+
+use crate::analyzer::encoding::read_source;
+use crate::domain::violations::{GuardianError, GuardianResult, Severity, Violation};
+use crate::patterns::SourceLocation;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// A `use` statement resolved to the workspace module it imports from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleImport {
+    /// Module containing the `use` statement (e.g. `my_crate::server`)
+    pub from_module: String,
+    /// Workspace module the imported item lives in
+    pub to_module: String,
+    /// Imported path with `crate`, `self`, and `super` resolved
+    pub imported_path: String,
+    /// File containing the `use` statement
+    pub file_path: PathBuf,
+    /// Line (1-indexed) of the `use` statement
+    pub line: u32,
+    /// Column (1-indexed) of the `use` statement
+    pub column: u32,
+}
+
+/// Cross-file index of `use` statements between workspace modules
+///
+/// Module paths are derived from file locations under each crate's `src/` directory and
+/// inline `mod` blocks; `#[path]` attributes are not followed. Imports of external crates
+/// are not indexed.
+#[derive(Debug, Clone, Default)]
+pub struct ImportIndex {
+    /// Module path of every indexed file
+    file_modules: BTreeMap<PathBuf, String>,
+    /// All modules known in the workspace
+    modules: BTreeSet<String>,
+    /// Names of the workspace crates
+    crates: BTreeSet<String>,
+    /// Resolved imports in file order
+    imports: Vec<ModuleImport>,
+}
+
+impl ImportIndex {
+    /// Build the index from a set of Rust source files
+    ///
+    /// Files that are not part of a crate's `src/` tree (tests, examples, build scripts)
+    /// and files that fail to parse are skipped.
+    pub fn build<P: AsRef<Path>>(files: &[P]) -> GuardianResult<Self> {
+        let mut index = Self::default();
+        let mut crate_names = HashMap::new();
+        let mut sources = Vec::new();
+
+        // First pass: assign module paths so imports can be resolved against every module
+        for file in files {
+            let file = file.as_ref();
+            if file.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+                continue;
+            }
+
+            let (crate_name, module) = match module_path_for(file, &mut crate_names) {
+                Some(module_path) => module_path,
+                None => continue,
+            };
+
+            let content = read_source(file, true)?.content;
+
+            let syntax_tree = match syn::parse_file(&content) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    tracing::debug!("Skipping {} in import index: {}", file.display(), e);
+                    continue;
+                }
+            };
+
+            index.crates.insert(crate_name);
+            index.modules.insert(module.clone());
+            index
+                .file_modules
+                .insert(file.to_path_buf(), module.clone());
+            sources.push((file.to_path_buf(), module, content, syntax_tree));
+        }
+
+        // Inline `mod name { ... }` blocks are modules too
+        for (_, module, _, syntax_tree) in &sources {
+            let mut collector = InlineModuleCollector {
+                module_stack: module_segments(module),
+                modules: &mut index.modules,
+            };
+            collector.visit_file(syntax_tree);
+        }
+
+        // Second pass: resolve every `use` to the module it imports from
+        for (file_path, module, content, syntax_tree) in &sources {
+            let mut visitor = UseVisitor {
+                index: &index,
+                file_path,
+                content,
+                module_stack: module_segments(module),
+                imports: Vec::new(),
+            };
+            visitor.visit_file(syntax_tree);
+            let imports = visitor.imports;
+            index.imports.extend(imports);
+        }
+
+        Ok(index)
+    }
+
+    /// All resolved imports
+    pub fn imports(&self) -> &[ModuleImport] {
+        &self.imports
+    }
+
+    /// All modules known in the workspace
+    pub fn modules(&self) -> impl Iterator<Item = &str> {
+        self.modules.iter().map(String::as_str)
+    }
+
+    /// Names of the workspace crates
+    pub fn crates(&self) -> impl Iterator<Item = &str> {
+        self.crates.iter().map(String::as_str)
+    }
+
+    /// Module path of an indexed file
+    pub fn module_of<P: AsRef<Path>>(&self, file: P) -> Option<&str> {
+        self.file_modules.get(file.as_ref()).map(String::as_str)
+    }
+
+    /// Indexed files with their module paths
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.file_modules
+            .iter()
+            .map(|(file, module)| (file.as_path(), module.as_str()))
+    }
+
+    /// Resolve an absolute path to the longest matching workspace module
+    fn resolve_module(&self, segments: &[String]) -> Option<String> {
+        (1..=segments.len())
+            .rev()
+            .map(|len| segments[..len].join("::"))
+            .find(|candidate| self.modules.contains(candidate))
+    }
+}
+
+/// Module-to-module dependency graph
+///
+/// Imports between a module and its own ancestors or descendants describe the module tree
+/// itself (re-exports, `use super::*`) and are not treated as dependencies.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+    /// All modules, including those without dependencies
+    modules: BTreeSet<String>,
+    /// Dependency edges with the imports that establish them
+    edges: Edges<ModuleImport>,
+}
+
+impl ModuleGraph {
+    /// Build the graph from an import index
+    pub fn from_index(index: &ImportIndex) -> Self {
+        let mut graph = Self {
+            modules: index.modules.clone(),
+            edges: BTreeMap::new(),
+        };
+
+        for import in &index.imports {
+            if is_same_lineage(&import.from_module, &import.to_module) {
+                continue;
+            }
+
+            graph
+                .edges
+                .entry(import.from_module.clone())
+                .or_default()
+                .entry(import.to_module.clone())
+                .or_default()
+                .push(import.clone());
+        }
+
+        graph
+    }
+
+    /// All modules in the graph
+    pub fn modules(&self) -> impl Iterator<Item = &str> {
+        self.modules.iter().map(String::as_str)
+    }
+
+    /// Modules that `module` depends on
+    pub fn dependencies(&self, module: &str) -> impl Iterator<Item = &str> {
+        successors(&self.edges, module)
+    }
+
+    /// Imports that make `from` depend on `to`
+    pub fn imports_between(&self, from: &str, to: &str) -> &[ModuleImport] {
+        self.edges
+            .get(from)
+            .and_then(|targets| targets.get(to))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Find dependency cycles
+    ///
+    /// Returns one shortest cycle per strongly connected component, starting from the
+    /// component's alphabetically first module. The first module is not repeated at the end.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        find_cycles(&self.modules, &self.edges)
+    }
+
+    /// Report each dependency cycle as a `circular_module_dependency` violation
+    ///
+    /// The violation points at the import that closes the cycle.
+    pub fn cycle_violations(&self) -> Vec<Violation> {
+        self.cycles()
+            .into_iter()
+            .filter_map(|cycle| {
+                let from = cycle.last()?;
+                let to = cycle.first()?;
+                let import = self.imports_between(from, to).first()?;
+
+                let mut chain = cycle.clone();
+                chain.push(to.clone());
+
+                Some(
+                    Violation::new(
+                        "circular_module_dependency",
+                        Severity::Error,
+                        import.file_path.clone(),
+                        format!("Circular module dependency: {}", chain.join(" -> ")),
+                    )
+                    .with_position(import.line, import.column)
+                    .with_context(format!("use {}", import.imported_path))
+                    .with_suggestion(
+                        "Move the shared items into a module both sides can depend on, or invert one of the dependencies",
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// Render the graph in Graphviz DOT format, highlighting edges that form cycles
+    pub fn to_dot(&self) -> String {
+        let cyclic_edges = cyclic_edges(&self.modules, &self.edges);
+        let mut dot = String::from("digraph modules {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for module in &self.modules {
+            dot.push_str(&format!("    \"{module}\";\n"));
+        }
+
+        for (from, targets) in &self.edges {
+            for to in targets.keys() {
+                if cyclic_edges.contains(&(from.as_str(), to.as_str())) {
+                    dot.push_str(&format!("    \"{from}\" -> \"{to}\" [color=red];\n"));
+                } else {
+                    dot.push_str(&format!("    \"{from}\" -> \"{to}\";\n"));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the graph as JSON with modules, dependencies, and cycles
+    pub fn to_json(&self) -> GuardianResult<String> {
+        let dependencies: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .flat_map(|(from, targets)| {
+                targets.iter().map(move |(to, imports)| {
+                    let imports: Vec<serde_json::Value> = imports
+                        .iter()
+                        .map(|import| {
+                            serde_json::json!({
+                                "path": import.imported_path,
+                                "file": import.file_path.display().to_string(),
+                                "line": import.line
+                            })
+                        })
+                        .collect();
+
+                    serde_json::json!({
+                        "from": from,
+                        "to": to,
+                        "imports": imports
+                    })
+                })
+            })
+            .collect();
+
+        let graph = serde_json::json!({
+            "modules": self.modules,
+            "dependencies": dependencies,
+            "cycles": self.cycles()
+        });
+
+        serde_json::to_string_pretty(&graph)
+            .map_err(|e| GuardianError::validation(format!("Graph serialization failed: {e}")))
+    }
+}
+
+/// Dependency edges keyed by source and target, with the evidence for each edge
+type Edges<T> = BTreeMap<String, BTreeMap<String, Vec<T>>>;
+
+/// Nodes reachable in one step from `node`
+fn successors<'a, T>(edges: &'a Edges<T>, node: &str) -> impl Iterator<Item = &'a str> {
+    edges
+        .get(node)
+        .into_iter()
+        .flat_map(|targets| targets.keys().map(String::as_str))
+}
+
+/// One shortest cycle per strongly connected component
+///
+/// Each cycle starts from the component's alphabetically first node, which is not repeated
+/// at the end.
+fn find_cycles<T>(nodes: &BTreeSet<String>, edges: &Edges<T>) -> Vec<Vec<String>> {
+    strongly_connected_components(nodes, edges)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .filter_map(|component| shortest_cycle(edges, &component))
+        .collect()
+}
+
+/// Edges that lie on some dependency cycle
+fn cyclic_edges<'a, T>(
+    nodes: &BTreeSet<String>,
+    edges: &'a Edges<T>,
+) -> BTreeSet<(&'a str, &'a str)> {
+    let mut component_of = HashMap::new();
+    for (i, component) in strongly_connected_components(nodes, edges)
+        .iter()
+        .enumerate()
+    {
+        if component.len() > 1 {
+            for node in component {
+                component_of.insert(node.clone(), i);
+            }
+        }
+    }
+
+    let mut cyclic = BTreeSet::new();
+    for (from, targets) in edges {
+        for to in targets.keys() {
+            match (component_of.get(from), component_of.get(to)) {
+                (Some(a), Some(b)) if a == b => {
+                    cyclic.insert((from.as_str(), to.as_str()));
+                }
+                _ => {}
+            }
+        }
+    }
+    cyclic
+}
+
+/// Tarjan's algorithm over the dependency edges
+fn strongly_connected_components<T>(
+    nodes: &BTreeSet<String>,
+    edges: &Edges<T>,
+) -> Vec<Vec<String>> {
+    struct Tarjan<'a, T> {
+        edges: &'a Edges<T>,
+        next_index: usize,
+        indices: HashMap<&'a str, usize>,
+        low_links: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'a, T> Tarjan<'a, T> {
+        fn visit(&mut self, node: &'a str) {
+            self.indices.insert(node, self.next_index);
+            self.low_links.insert(node, self.next_index);
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            let edges = self.edges;
+            for successor in successors(edges, node) {
+                if !self.indices.contains_key(successor) {
+                    self.visit(successor);
+                    let low = self.low_links[node].min(self.low_links[successor]);
+                    self.low_links.insert(node, low);
+                } else if self.on_stack.contains(successor) {
+                    let low = self.low_links[node].min(self.indices[successor]);
+                    self.low_links.insert(node, low);
+                }
+            }
+
+            if self.low_links[node] == self.indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        next_index: 0,
+        indices: HashMap::new(),
+        low_links: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+
+    for node in nodes {
+        if !tarjan.indices.contains_key(node.as_str()) {
+            tarjan.visit(node);
+        }
+    }
+
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// Shortest cycle through the first node of a strongly connected component
+fn shortest_cycle<T>(edges: &Edges<T>, component: &[String]) -> Option<Vec<String>> {
+    let start = component.first()?;
+    let members: BTreeSet<&str> = component.iter().map(String::as_str).collect();
+
+    // Breadth-first search back to the start, remembering how each node was reached
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start.as_str()]);
+
+    while let Some(node) = queue.pop_front() {
+        for successor in successors(edges, node) {
+            if !members.contains(successor) {
+                continue;
+            }
+
+            if successor == start {
+                let mut cycle = vec![node.to_string()];
+                let mut current = node;
+                while let Some(&parent) = previous.get(current) {
+                    cycle.push(parent.to_string());
+                    current = parent;
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+
+            if !previous.contains_key(successor) {
+                previous.insert(successor, node);
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether one module is the other or nested inside it
+fn is_same_lineage(a: &str, b: &str) -> bool {
+    let is_within = |inner: &str, outer: &str| {
+        inner == outer
+            || inner
+                .strip_prefix(outer)
+                .is_some_and(|rest| rest.starts_with("::"))
+    };
+    is_within(a, b) || is_within(b, a)
+}
+
+/// Split a module path into its segments
+fn module_segments(module: &str) -> Vec<String> {
+    module.split("::").map(str::to_string).collect()
+}
+
+/// Determine the crate and module path of a source file
+///
+/// Returns `None` for files outside a crate's `src/` directory and for `src/bin` targets.
+fn module_path_for(
+    file: &Path,
+    crate_names: &mut HashMap<PathBuf, String>,
+) -> Option<(String, String)> {
+    let crate_dir = file
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .unwrap_or_else(|| Path::new(""));
+
+    let relative = file.strip_prefix(crate_dir).ok()?;
+    let mut components = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        });
+
+    if components.next()? != "src" {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = components.collect();
+    if segments.first() == Some(&"bin") {
+        return None;
+    }
+
+    // foo/mod.rs and foo.rs both define `foo`; lib.rs and main.rs define the crate root
+    let file_name = segments.pop()?;
+    match file_name {
+        "mod.rs" => {}
+        "lib.rs" | "main.rs" if segments.is_empty() => {}
+        name => segments.push(name.strip_suffix(".rs")?),
+    }
+
+    let crate_name = crate_names
+        .entry(crate_dir.to_path_buf())
+        .or_insert_with(|| crate_name_for(crate_dir))
+        .clone();
+
+    let module = std::iter::once(crate_name.as_str())
+        .chain(segments)
+        .collect::<Vec<_>>()
+        .join("::");
+
+    Some((crate_name, module))
+}
+
+/// Module path of a source file relative to its crate root, e.g. `["jobs", "background"]`
+///
+/// The crate root itself and files outside a crate's `src/` directory yield an empty path.
+pub(crate) fn module_segments_for(file: &Path) -> Vec<String> {
+    match module_path_for(file, &mut HashMap::new()) {
+        Some((_, module)) => module_segments(&module).into_iter().skip(1).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Read the crate name from a manifest, falling back to the directory name
+pub(crate) fn crate_name_for(crate_dir: &Path) -> String {
+    let manifest_name = fs::read_to_string(crate_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| {
+            let mut in_package = false;
+            manifest.lines().find_map(|line| {
+                let line = line.trim();
+                if line.starts_with('[') {
+                    in_package = line == "[package]";
+                    return None;
+                }
+                let value = line.strip_prefix("name")?.trim_start().strip_prefix('=')?;
+                in_package.then(|| value.trim().trim_matches('"').to_string())
+            })
+        });
+
+    manifest_name
+        .or_else(|| {
+            crate_dir
+                .canonicalize()
+                .ok()?
+                .file_name()?
+                .to_str()
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "crate".to_string())
+        .replace('-', "_")
+}
+
+/// Collects inline `mod name { ... }` blocks as modules
+struct InlineModuleCollector<'a> {
+    module_stack: Vec<String>,
+    modules: &'a mut BTreeSet<String>,
+}
+
+impl Visit<'_> for InlineModuleCollector<'_> {
+    fn visit_item_mod(&mut self, item_mod: &syn::ItemMod) {
+        if item_mod.content.is_some() {
+            self.module_stack.push(item_mod.ident.to_string());
+            self.modules.insert(self.module_stack.join("::"));
+            syn::visit::visit_item_mod(self, item_mod);
+            self.module_stack.pop();
+        }
+    }
+}
+
+/// Resolves `use` items in a file to workspace modules
+struct UseVisitor<'a> {
+    index: &'a ImportIndex,
+    file_path: &'a Path,
+    content: &'a str,
+    module_stack: Vec<String>,
+    imports: Vec<ModuleImport>,
+}
+
+impl Visit<'_> for UseVisitor<'_> {
+    fn visit_item_mod(&mut self, item_mod: &syn::ItemMod) {
+        if item_mod.content.is_some() {
+            self.module_stack.push(item_mod.ident.to_string());
+            syn::visit::visit_item_mod(self, item_mod);
+            self.module_stack.pop();
+        }
+    }
+
+    fn visit_item_use(&mut self, use_item: &syn::ItemUse) {
+        let location = SourceLocation::from_span(self.content, use_item.span());
+        let from_module = self.module_stack.join("::");
+
+        let mut paths = Vec::new();
+        collect_use_paths(&use_item.tree, &mut Vec::new(), &mut paths);
+
+        for path in paths {
+            let absolute = match self.absolute_path(&path, use_item.leading_colon.is_some()) {
+                Some(absolute) => absolute,
+                None => continue,
+            };
+            let to_module = match self.index.resolve_module(&absolute) {
+                Some(module) => module,
+                None => continue,
+            };
+
+            self.imports.push(ModuleImport {
+                from_module: from_module.clone(),
+                to_module,
+                imported_path: absolute.join("::"),
+                file_path: self.file_path.to_path_buf(),
+                line: location.line,
+                column: location.column,
+            });
+        }
+    }
+}
+
+impl UseVisitor<'_> {
+    /// Resolve a `use` path to absolute segments starting with a crate name
+    ///
+    /// Returns `None` for paths that leave the workspace (std, external crates).
+    fn absolute_path(&self, path: &[String], leading_colon: bool) -> Option<Vec<String>> {
+        let first = path.first()?;
+
+        if leading_colon {
+            return self.index.crates.contains(first).then(|| path.to_vec());
+        }
+
+        match first.as_str() {
+            "crate" => {
+                let mut absolute = vec![self.module_stack.first()?.clone()];
+                absolute.extend_from_slice(&path[1..]);
+                Some(absolute)
+            }
+            "self" | "super" => {
+                let mut absolute = self.module_stack.clone();
+                let mut rest = path;
+                if rest.first().map(String::as_str) == Some("self") {
+                    rest = &rest[1..];
+                }
+                while rest.first().map(String::as_str) == Some("super") {
+                    // Cannot go above the crate root
+                    if absolute.len() <= 1 {
+                        return None;
+                    }
+                    absolute.pop();
+                    rest = &rest[1..];
+                }
+                absolute.extend_from_slice(rest);
+                Some(absolute)
+            }
+            _ => {
+                // A child module in scope takes precedence over a crate of the same name
+                let mut relative = self.module_stack.clone();
+                relative.push(first.clone());
+                if self.index.modules.contains(&relative.join("::")) {
+                    relative.extend_from_slice(&path[1..]);
+                    Some(relative)
+                } else if self.index.crates.contains(first) {
+                    Some(path.to_vec())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Flatten a use tree into the paths it imports
+///
+/// Leaf names are kept so items can be resolved to the module that defines them; globs
+/// and `self` leaves resolve to the enclosing path.
+fn collect_use_paths(tree: &syn::UseTree, prefix: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            collect_use_paths(&use_path.tree, prefix, paths);
+            prefix.pop();
+        }
+        syn::UseTree::Name(use_name) => {
+            let mut path = prefix.clone();
+            if use_name.ident != "self" {
+                path.push(use_name.ident.to_string());
+            }
+            paths.push(path);
+        }
+        syn::UseTree::Rename(use_rename) => {
+            let mut path = prefix.clone();
+            if use_rename.ident != "self" {
+                path.push(use_rename.ident.to_string());
+            }
+            paths.push(path);
+        }
+        syn::UseTree::Glob(_) => paths.push(prefix.clone()),
+        syn::UseTree::Group(group) => {
+            for item in &group.items {
+                collect_use_paths(item, prefix, paths);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_crate(root: &Path, files: &[(&str, &str)]) -> Vec<PathBuf> {
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo-app\"\nversion = \"0.1.0\"\n",
+        )
+        .expect("manifest should be writable");
+
+        files
+            .iter()
+            .map(|(path, content)| {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().expect("file should have a parent"))
+                    .expect("source directory should be creatable");
+                fs::write(&path, content).expect("source file should be writable");
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_module_graph_edges() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let files = write_crate(
+            temp_dir.path(),
+            &[
+                ("src/lib.rs", "pub mod api;\npub mod core;\n"),
+                (
+                    "src/api/mod.rs",
+                    "use crate::core::Engine;\npub mod routes;\n",
+                ),
+                (
+                    "src/api/routes.rs",
+                    "use super::super::core::{self, Engine};\nuse std::fmt;\n",
+                ),
+                ("src/core.rs", "pub struct Engine;\n"),
+            ],
+        );
+
+        let index = ImportIndex::build(&files).expect("index should build");
+        assert_eq!(index.module_of(&files[1]), Some("demo_app::api"));
+        assert_eq!(index.module_of(&files[2]), Some("demo_app::api::routes"));
+
+        let graph = ModuleGraph::from_index(&index);
+        let api_deps: Vec<_> = graph.dependencies("demo_app::api").collect();
+        assert_eq!(api_deps, vec!["demo_app::core"]);
+        let route_deps: Vec<_> = graph.dependencies("demo_app::api::routes").collect();
+        assert_eq!(route_deps, vec!["demo_app::core"]);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_module_graph_cycle_violation() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let files = write_crate(
+            temp_dir.path(),
+            &[
+                ("src/lib.rs", "mod a;\nmod b;\n"),
+                ("src/a.rs", "use crate::b::B;\npub struct A;\n"),
+                ("src/b.rs", "pub struct B;\n\nuse crate::a::A;\n"),
+            ],
+        );
+
+        let graph =
+            ModuleGraph::from_index(&ImportIndex::build(&files).expect("index should build"));
+        assert_eq!(
+            graph.cycles(),
+            vec![vec!["demo_app::a".to_string(), "demo_app::b".to_string()]]
+        );
+
+        let violations = graph.cycle_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "circular_module_dependency");
+        assert_eq!(violations[0].file_path, files[2]);
+        assert_eq!(violations[0].line_number, Some(3));
+        assert!(graph
+            .to_dot()
+            .contains("\"demo_app::a\" -> \"demo_app::b\" [color=red];"));
+    }
+}
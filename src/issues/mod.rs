@@ -0,0 +1,467 @@
+//! Issue tracker linkage for trackable violations
+//!
+//! Architecture: Infrastructure Adapter - Trackers sit outside the analysis core, like notifications
+//! - Links from violation fingerprints to issues are kept in a JSON file between runs
+//! - Requests and response parsing are pure; sending them needs the `issues` feature
+//! - Before creating an issue the tracker is searched for the fingerprint, so issues are never duplicated
+
+use crate::config::{IssueProvider, IssueTrackerConfig};
+use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport, Violation};
+use crate::report::history::project_fingerprint;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Issues linked to violations, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueLinks {
+    /// Issue URL by violation fingerprint
+    links: BTreeMap<String, String>,
+}
+
+impl IssueLinks {
+    /// Load the links from a file, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> GuardianResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            GuardianError::config(format!("Invalid issue links '{}': {e}", path.display()))
+        })
+    }
+
+    /// Write the links to a file, creating its directory
+    pub fn save(&self, path: &Path) -> GuardianResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| GuardianError::io(parent, e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize issue links: {e}")))?;
+        fs::write(path, content).map_err(|e| GuardianError::io(path, e))
+    }
+
+    /// Issue linked to a fingerprint
+    pub fn get(&self, fingerprint: &str) -> Option<&str> {
+        self.links.get(fingerprint).map(String::as_str)
+    }
+
+    /// Link a fingerprint to an issue
+    pub fn insert(&mut self, fingerprint: impl Into<String>, url: impl Into<String>) {
+        self.links.insert(fingerprint.into(), url.into());
+    }
+
+    /// Number of linked violations
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Whether no violations are linked
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+/// Set the issue URL of every trackable violation that has a linked issue
+///
+/// Returns the number of violations an issue was attached to.
+pub fn attach_issue_urls(
+    config: &IssueTrackerConfig,
+    report: &mut ValidationReport,
+    links: &IssueLinks,
+    root: &Path,
+) -> usize {
+    let mut attached = 0;
+    for violation in &mut report.violations {
+        if !config.tracks(&violation.rule_id) {
+            continue;
+        }
+        if let Some(url) = links.get(&project_fingerprint(violation, root)) {
+            violation.issue_url = Some(url.to_string());
+            attached += 1;
+        }
+    }
+    attached
+}
+
+/// Issues found or created by [`link_issues`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinkSummary {
+    /// Existing issues found by fingerprint
+    pub found: usize,
+    /// Newly created issues
+    pub created: usize,
+}
+
+/// Find or create an issue for every trackable violation that has none linked yet
+///
+/// Links are added as issues are found or created, so after a failure `links` still holds
+/// the progress made; the failure is returned and the remaining violations are left unlinked.
+pub fn link_issues(
+    config: &IssueTrackerConfig,
+    report: &ValidationReport,
+    links: &mut IssueLinks,
+    root: &Path,
+) -> GuardianResult<LinkSummary> {
+    let Some(provider) = config.provider else {
+        return Ok(LinkSummary::default());
+    };
+    let token = resolve_token(config, provider)?;
+    let mut summary = LinkSummary::default();
+
+    for violation in &report.violations {
+        if !config.tracks(&violation.rule_id) {
+            continue;
+        }
+        let fingerprint = project_fingerprint(violation, root);
+        if links.get(&fingerprint).is_some() {
+            continue;
+        }
+
+        let found = send(
+            &lookup_request(config, provider, &fingerprint),
+            provider,
+            &token,
+        )
+        .map(|response| parse_lookup(provider, &response))?;
+        let url = match found {
+            Some(url) => {
+                summary.found += 1;
+                url
+            }
+            None => {
+                let request = create_request(config, provider, violation, &fingerprint, root);
+                let response = send(&request, provider, &token)?;
+                summary.created += 1;
+                parse_issue_url(provider, &response).ok_or_else(|| {
+                    GuardianError::config("Issue tracker response carries no issue URL")
+                })?
+            }
+        };
+        links.insert(fingerprint, url);
+    }
+
+    Ok(summary)
+}
+
+/// An HTTP request to an issue tracker API
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueRequest {
+    /// `GET` or `POST`
+    pub method: &'static str,
+    pub url: String,
+    /// JSON body of a `POST`
+    pub body: Option<JsonValue>,
+}
+
+/// Search the tracker for an issue mentioning a fingerprint
+pub fn lookup_request(
+    config: &IssueTrackerConfig,
+    provider: IssueProvider,
+    fingerprint: &str,
+) -> IssueRequest {
+    let api = api_url(config, provider);
+    let url = match provider {
+        IssueProvider::Github => format!(
+            "{api}/search/issues?q={}",
+            encode(&format!(
+                "repo:{} is:issue in:body \"{fingerprint}\"",
+                config.repository
+            ))
+        ),
+        IssueProvider::Gitlab => format!(
+            "{api}/projects/{}/issues?in=description&search={}",
+            encode(&config.repository),
+            encode(fingerprint)
+        ),
+    };
+    IssueRequest {
+        method: "GET",
+        url,
+        body: None,
+    }
+}
+
+/// Create an issue for a violation, carrying its fingerprint for later lookups
+pub fn create_request(
+    config: &IssueTrackerConfig,
+    provider: IssueProvider,
+    violation: &Violation,
+    fingerprint: &str,
+    root: &Path,
+) -> IssueRequest {
+    let location = violation
+        .file_path
+        .strip_prefix(root)
+        .unwrap_or(&violation.file_path)
+        .display()
+        .to_string();
+    let location = match violation.line_number {
+        Some(line) => format!("{location}:{line}"),
+        None => location,
+    };
+    let title = format!("[{}] {}", violation.rule_id, violation.message);
+
+    let mut description = format!(
+        "`{}` reported a violation at `{location}`:\n\n> {}\n",
+        violation.rule_id, violation.message
+    );
+    if let Some(context) = &violation.context {
+        description.push_str(&format!("\n```\n{}\n```\n", context.trim_end()));
+    }
+    if let Some(rationale) = &violation.rationale {
+        description.push_str(&format!("\n**Why:** {rationale}\n"));
+    }
+    if let Some(docs_url) = &violation.docs_url {
+        description.push_str(&format!("\nDocumentation: {docs_url}\n"));
+    }
+    description.push_str(&format!("\nGuardian fingerprint: `{fingerprint}`\n"));
+
+    let api = api_url(config, provider);
+    let (url, body) = match provider {
+        IssueProvider::Github => (
+            format!("{api}/repos/{}/issues", config.repository),
+            serde_json::json!({
+                "title": title,
+                "body": description,
+                "labels": config.labels,
+            }),
+        ),
+        IssueProvider::Gitlab => (
+            format!("{api}/projects/{}/issues", encode(&config.repository)),
+            serde_json::json!({
+                "title": title,
+                "description": description,
+                "labels": config.labels.join(","),
+            }),
+        ),
+    };
+    IssueRequest {
+        method: "POST",
+        url,
+        body: Some(body),
+    }
+}
+
+/// URL of the first issue in a search response
+pub fn parse_lookup(provider: IssueProvider, response: &JsonValue) -> Option<String> {
+    let issues = match provider {
+        IssueProvider::Github => response.get("items")?,
+        IssueProvider::Gitlab => response,
+    };
+    parse_issue_url(provider, issues.as_array()?.first()?)
+}
+
+/// Web URL of an issue returned by the tracker
+pub fn parse_issue_url(provider: IssueProvider, issue: &JsonValue) -> Option<String> {
+    let field = match provider {
+        IssueProvider::Github => "html_url",
+        IssueProvider::Gitlab => "web_url",
+    };
+    issue.get(field)?.as_str().map(str::to_string)
+}
+
+fn api_url(config: &IssueTrackerConfig, provider: IssueProvider) -> String {
+    let default = match provider {
+        IssueProvider::Github => "https://api.github.com",
+        IssueProvider::Gitlab => "https://gitlab.com/api/v4",
+    };
+    config
+        .api_url
+        .as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Read the token, from the environment when it names a `$VARIABLE`
+fn resolve_token(config: &IssueTrackerConfig, provider: IssueProvider) -> GuardianResult<String> {
+    let default = match provider {
+        IssueProvider::Github => "$GITHUB_TOKEN",
+        IssueProvider::Gitlab => "$GITLAB_TOKEN",
+    };
+    let token = config.token.as_deref().unwrap_or(default);
+    match token.strip_prefix('$') {
+        Some(name) => std::env::var(name).map_err(|_| {
+            GuardianError::config(format!("Issue tracker token variable '{name}' is not set"))
+        }),
+        None => Ok(token.to_string()),
+    }
+}
+
+/// Percent-encode a URL query value or path segment
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(feature = "issues")]
+fn send(request: &IssueRequest, provider: IssueProvider, token: &str) -> GuardianResult<JsonValue> {
+    let call = ureq::request(request.method, &request.url)
+        .set("Accept", "application/json")
+        .set("User-Agent", "rust-guardian");
+    let call = match provider {
+        IssueProvider::Github => call.set("Authorization", &format!("Bearer {token}")),
+        IssueProvider::Gitlab => call.set("PRIVATE-TOKEN", token),
+    };
+    let response = match &request.body {
+        Some(body) => call
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string()),
+        None => call.call(),
+    }
+    .map_err(|e| GuardianError::config(format!("Issue tracker request failed: {e}")))?;
+
+    let content = response
+        .into_string()
+        .map_err(|e| GuardianError::config(format!("Invalid issue tracker response: {e}")))?;
+    serde_json::from_str(&content)
+        .map_err(|e| GuardianError::config(format!("Invalid issue tracker response: {e}")))
+}
+
+#[cfg(not(feature = "issues"))]
+fn send(
+    _request: &IssueRequest,
+    _provider: IssueProvider,
+    _token: &str,
+) -> GuardianResult<JsonValue> {
+    Err(GuardianError::config(
+        "Issue linkage requires rust-guardian to be built with the 'issues' feature",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::violations::Severity;
+    use std::path::PathBuf;
+
+    fn config(provider: IssueProvider, repository: &str) -> IssueTrackerConfig {
+        IssueTrackerConfig {
+            provider: Some(provider),
+            repository: repository.to_string(),
+            rules: vec!["todo_comments".to_string()],
+            labels: vec!["tech-debt".to_string(), "guardian".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn todo() -> Violation {
+        Violation::new(
+            "todo_comments",
+            Severity::Warning,
+            PathBuf::from("src/lib.rs"),
+            "TODO comment found",
+        )
+        .with_position(12, 5)
+        .with_context("// TODO: retry on timeout")
+    }
+
+    #[test]
+    fn test_github_requests() {
+        let config = config(IssueProvider::Github, "acme/widgets");
+        let lookup = lookup_request(&config, IssueProvider::Github, "0123abcd");
+        assert_eq!(
+            lookup.url,
+            "https://api.github.com/search/issues?q=repo%3Aacme%2Fwidgets%20is%3Aissue%20in%3Abody%20%220123abcd%22"
+        );
+
+        let create = create_request(
+            &config,
+            IssueProvider::Github,
+            &todo(),
+            "0123abcd",
+            Path::new("."),
+        );
+        assert_eq!(
+            create.url,
+            "https://api.github.com/repos/acme/widgets/issues"
+        );
+        let body = create.body.expect("create request should have a body");
+        assert_eq!(body["title"], "[todo_comments] TODO comment found");
+        assert_eq!(body["labels"][0], "tech-debt");
+        let description = body["body"].as_str().expect("body should be text");
+        assert!(description.contains("`src/lib.rs:12`"));
+        assert!(description.contains("// TODO: retry on timeout"));
+        assert!(description.contains("Guardian fingerprint: `0123abcd`"));
+
+        let search = serde_json::json!({
+            "items": [{ "html_url": "https://github.com/acme/widgets/issues/7" }]
+        });
+        assert_eq!(
+            parse_lookup(IssueProvider::Github, &search).as_deref(),
+            Some("https://github.com/acme/widgets/issues/7")
+        );
+        assert_eq!(
+            parse_lookup(IssueProvider::Github, &serde_json::json!({ "items": [] })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_gitlab_requests() {
+        let mut config = config(IssueProvider::Gitlab, "acme/widgets");
+        config.api_url = Some("https://git.example.com/api/v4/".to_string());
+
+        let lookup = lookup_request(&config, IssueProvider::Gitlab, "0123abcd");
+        assert_eq!(
+            lookup.url,
+            "https://git.example.com/api/v4/projects/acme%2Fwidgets/issues?in=description&search=0123abcd"
+        );
+        let create = create_request(
+            &config,
+            IssueProvider::Gitlab,
+            &todo(),
+            "0123abcd",
+            Path::new("."),
+        );
+        let body = create.body.expect("create request should have a body");
+        assert_eq!(body["labels"], "tech-debt,guardian");
+
+        let found =
+            serde_json::json!([{ "web_url": "https://git.example.com/acme/widgets/-/issues/3" }]);
+        assert_eq!(
+            parse_lookup(IssueProvider::Gitlab, &found).as_deref(),
+            Some("https://git.example.com/acme/widgets/-/issues/3")
+        );
+    }
+
+    #[test]
+    fn test_attach_issue_urls() {
+        let config = config(IssueProvider::Github, "acme/widgets");
+        let root = Path::new(".");
+        let mut report = ValidationReport::new();
+        report.add_violation(todo());
+        report.add_violation(Violation::new(
+            "unimplemented_macros",
+            Severity::Error,
+            PathBuf::from("src/lib.rs"),
+            "unimplemented! found",
+        ));
+
+        let mut links = IssueLinks::default();
+        for violation in &report.violations {
+            links.insert(
+                project_fingerprint(violation, root),
+                "https://github.com/acme/widgets/issues/7",
+            );
+        }
+
+        // Only violations of trackable rules get an issue, even if others are linked
+        assert_eq!(attach_issue_urls(&config, &mut report, &links, root), 1);
+        assert_eq!(
+            report.violations[0].issue_url.as_deref(),
+            Some("https://github.com/acme/widgets/issues/7")
+        );
+        assert_eq!(report.violations[1].issue_url, None);
+    }
+}
@@ -9,29 +9,64 @@ pub mod analyzer;
 pub mod cache;
 pub mod config;
 pub mod domain;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod graph;
+pub mod issues;
+pub mod multi_root;
+pub mod notify;
 pub mod patterns;
 pub mod report;
+pub mod testing;
+pub mod triage;
+pub mod vcs_policy;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export main types for convenient access
 pub use domain::violations::{
-    GuardianError, GuardianResult, Severity, ValidationReport, ValidationSummary, Violation,
+    GuardianError, GuardianResult, RuleUsage, Severity, ValidationReport, ValidationSummary,
+    Violation,
 };
 
-pub use config::{GuardianConfig, PatternCategory, PatternRule, RuleType};
+pub use config::{
+    EscalationConfig, EscalationStep, FeedbackConfig, FixTemplate, GuardianConfig, IssueProvider,
+    IssueTrackerConfig, NotificationConfig, PatternCategory, PatternRule, ReportingConfig,
+    RuleDeprecation, RuleType, VcsPolicyConfig, WebhookConfig,
+};
+
+pub use analyzer::{AnalysisOptions, AnalysisSet, Analyzer, FileExplanation, PatternStats};
 
-pub use analyzer::{AnalysisOptions, Analyzer, PatternStats};
+pub use patterns::{
+    CompiledRules, FileContext, PathFilter, Rule, RuleInfo, SyntaxTreeCache, TargetKind,
+};
 
-pub use report::{OutputFormat, ReportFormatter, ReportOptions};
+pub use report::attestation::ReportSignature;
+pub use report::rulebook::{render_rulebook, RulebookFormat};
+pub use report::{CsvColumn, OutputFormat, ReportFormatter, ReportOptions};
 
-pub use cache::{CacheStatistics, FileCache};
+pub use cache::{CacheLookup, CacheStatistics, FileCache};
 
+pub use multi_root::{MultiRootReport, MultiRootValidator, ProjectRoot, RootReport};
+
+use analyzer::revision::Revision;
+use analyzer::scope::SparseCheckout;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
 
 /// Main Guardian validator providing high-level validation operations
+///
+/// Validation takes `&self`, so one validator can be shared behind an `Arc` and serve
+/// concurrent requests. Cache state is guarded internally and only locked while cache
+/// entries are checked or updated, never while files are analyzed. The analyzer can be
+/// swapped with [`GuardianValidator::reload_config`] while validations are running.
 pub struct GuardianValidator {
-    analyzer: Analyzer,
-    cache: Option<FileCache>,
+    analyzer: RwLock<Arc<Analyzer>>,
+    cache: Option<Mutex<FileCache>>,
     report_formatter: ReportFormatter,
+    procedural_rules: Vec<Arc<dyn Rule>>,
+    syntax_trees: Option<SyntaxTreeCache>,
 }
 
 /// Options for agent validation workflows
@@ -64,6 +99,20 @@ impl Default for ValidationOptions {
     }
 }
 
+impl ValidationOptions {
+    /// Preset for autonomous agents: JSON-lines output without colors or context frames
+    ///
+    /// Output is deterministically ordered and each violation carries a stable fingerprint
+    /// and remediation hints, see [`OutputFormat::JsonLines`].
+    pub fn agent_mode() -> Self {
+        Self {
+            output_format: OutputFormat::JsonLines,
+            report_options: ReportOptions::agent_mode(),
+            ..Self::default()
+        }
+    }
+}
+
 impl GuardianValidator {
     /// Create a new validator with the given configuration
     pub fn new_with_config(config: GuardianConfig) -> GuardianResult<Self> {
@@ -71,9 +120,31 @@ impl GuardianValidator {
         let report_formatter = ReportFormatter::default();
 
         Ok(Self {
-            analyzer,
+            analyzer: RwLock::new(Arc::new(analyzer)),
             cache: None,
             report_formatter,
+            procedural_rules: Vec::new(),
+            syntax_trees: None,
+        })
+    }
+
+    /// Create a validator running rules already compiled from `config`
+    ///
+    /// Validators sharing one [`CompiledRules`] set, as a daemon serving many workspaces
+    /// with the same policy would, compile the configuration once. Procedural rules cannot
+    /// be added to shared rules with [`GuardianValidator::with_rule`].
+    ///
+    /// Fails if `rules` were not compiled from `config`.
+    pub fn new_with_compiled_rules(
+        config: GuardianConfig,
+        rules: Arc<CompiledRules>,
+    ) -> GuardianResult<Self> {
+        Ok(Self {
+            analyzer: RwLock::new(Arc::new(Analyzer::with_compiled_rules(config, rules)?)),
+            cache: None,
+            report_formatter: ReportFormatter::default(),
+            procedural_rules: Vec::new(),
+            syntax_trees: None,
         })
     }
 
@@ -92,11 +163,80 @@ impl GuardianValidator {
     pub fn with_cache<P: AsRef<Path>>(mut self, cache_path: P) -> GuardianResult<Self> {
         let mut cache = FileCache::new(cache_path);
         cache.load()?;
-        cache.set_config_fingerprint(self.analyzer.config_fingerprint());
-        self.cache = Some(cache);
+        cache.set_config_fingerprint(self.analyzer().config_fingerprint());
+        self.cache = Some(Mutex::new(cache));
         Ok(self)
     }
 
+    /// Replace the configuration without rebuilding the validator
+    ///
+    /// The new rules are compiled before anything is swapped, so an invalid configuration
+    /// leaves the validator unchanged. Validations already running finish with the rules
+    /// they started with, and cached results are invalidated by the new config fingerprint.
+    pub fn reload_config(&self, config: GuardianConfig) -> GuardianResult<()> {
+        let mut analyzer = Analyzer::new(config)?;
+        analyzer.set_syntax_tree_cache(self.syntax_trees.clone());
+        for rule in &self.procedural_rules {
+            analyzer.register_shared_rule(Arc::clone(rule))?;
+        }
+        let analyzer = Arc::new(analyzer);
+
+        // Hold the cache lock across the swap so no validation pairs the new rules with
+        // the old fingerprint
+        let mut cache = self.lock_cache()?;
+        if let Some(cache) = cache.as_mut() {
+            cache.set_config_fingerprint(analyzer.config_fingerprint());
+        }
+        *self
+            .analyzer
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = analyzer;
+
+        Ok(())
+    }
+
+    /// Register a procedural rule compiled into the calling crate
+    ///
+    /// Registered rules are kept across [`GuardianValidator::reload_config`].
+    pub fn with_rule(mut self, rule: Box<dyn Rule>) -> GuardianResult<Self> {
+        let rule: Arc<dyn Rule> = Arc::from(rule);
+        let analyzer = self
+            .analyzer
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        Arc::get_mut(analyzer)
+            .ok_or_else(|| {
+                GuardianError::config("Rules must be registered before validation starts")
+            })?
+            .register_shared_rule(Arc::clone(&rule))?;
+
+        self.procedural_rules.push(rule);
+        Ok(self)
+    }
+
+    /// Reuse syntax trees across validations, see [`Analyzer::set_syntax_tree_cache`]
+    ///
+    /// Kept across [`GuardianValidator::reload_config`], so a configuration change does not
+    /// force unchanged files to be parsed again.
+    pub fn with_syntax_tree_cache(mut self, syntax_trees: SyntaxTreeCache) -> GuardianResult<Self> {
+        let analyzer = self
+            .analyzer
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        Arc::get_mut(analyzer)
+            .ok_or_else(|| {
+                GuardianError::config("The syntax tree cache must be set before validation starts")
+            })?
+            .set_syntax_tree_cache(Some(syntax_trees.clone()));
+        self.syntax_trees = Some(syntax_trees);
+        Ok(self)
+    }
+
+    /// Snapshot of the current analyzer, unaffected by later reloads
+    fn analyzer(&self) -> Arc<Analyzer> {
+        Arc::clone(&self.analyzer.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
     /// Set custom report formatter
     pub fn with_report_formatter(mut self, formatter: ReportFormatter) -> Self {
         self.report_formatter = formatter;
@@ -105,7 +245,7 @@ impl GuardianValidator {
 
     /// Validate files for agent workflows - primary API for autonomous agents
     pub async fn validate_for_agent<P: AsRef<Path>>(
-        &mut self,
+        &self,
         paths: Vec<P>,
     ) -> GuardianResult<ValidationReport> {
         self.validate_with_options(paths, &ValidationOptions::default())
@@ -114,7 +254,7 @@ impl GuardianValidator {
 
     /// Validate files with custom options
     pub async fn validate_with_options<P: AsRef<Path>>(
-        &mut self,
+        &self,
         paths: Vec<P>,
         options: &ValidationOptions,
     ) -> GuardianResult<ValidationReport> {
@@ -126,7 +266,7 @@ impl GuardianValidator {
             self.analyze_with_cache(&paths, &options.analysis_options)
                 .await?
         } else {
-            self.analyzer.analyze_paths(
+            self.analyzer().analyze_paths(
                 &paths.iter().map(|p| p.as_path()).collect::<Vec<_>>(),
                 &options.analysis_options,
             )?
@@ -137,7 +277,7 @@ impl GuardianValidator {
 
     /// Validate a single file
     pub fn validate_file<P: AsRef<Path>>(&self, file_path: P) -> GuardianResult<ValidationReport> {
-        let violations = self.analyzer.analyze_file(file_path)?;
+        let violations = self.analyzer().analyze_file(file_path)?;
 
         let mut report = ValidationReport::new();
         for violation in violations {
@@ -154,7 +294,38 @@ impl GuardianValidator {
         root: P,
         options: &AnalysisOptions,
     ) -> GuardianResult<ValidationReport> {
-        self.analyzer.analyze_directory(root, options)
+        self.analyzer().analyze_directory(root, options)
+    }
+
+    /// Validate files, passing each file's violations to `on_file` as soon as it is analyzed
+    ///
+    /// With `recent_first` set, recently modified files are analyzed first so the violations
+    /// most relevant to the current change arrive before the rest. The cache is not used.
+    pub fn validate_streaming<P, F>(
+        &self,
+        paths: &[P],
+        options: &AnalysisOptions,
+        on_file: F,
+    ) -> GuardianResult<ValidationReport>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Path, &[Violation]) + Sync,
+    {
+        self.analyzer()
+            .analyze_paths_streaming(paths, options, on_file)
+    }
+
+    /// Validate the files of a git revision without checking it out
+    ///
+    /// Blobs are read from the repository's object store, so this works for historical
+    /// commits and in bare repositories. The cache is not used.
+    pub fn validate_revision<P: AsRef<Path>>(
+        &self,
+        revision: &Revision,
+        paths: &[P],
+        options: &AnalysisOptions,
+    ) -> GuardianResult<ValidationReport> {
+        self.analyzer().analyze_revision(revision, paths, options)
     }
 
     /// Format a validation report for output
@@ -168,51 +339,86 @@ impl GuardianValidator {
 
     /// Get analyzer statistics
     pub fn pattern_statistics(&self) -> PatternStats {
-        self.analyzer.pattern_stats()
+        self.analyzer().pattern_stats()
+    }
+
+    /// Get the path filter that decides which files are analyzed under the current config
+    pub fn path_filter(&self) -> PathFilter {
+        self.analyzer().path_filter().clone()
     }
 
     /// Get cache statistics (if caching is enabled)
     pub fn cache_statistics(&self) -> Option<CacheStatistics> {
-        self.cache.as_ref().map(|c| c.statistics())
+        self.lock_cache()
+            .ok()
+            .flatten()
+            .map(|cache| cache.statistics())
     }
 
     /// Clear cache (if enabled)
-    pub fn clear_cache(&mut self) -> GuardianResult<()> {
-        if let Some(cache) = &mut self.cache {
+    pub fn clear_cache(&self) -> GuardianResult<()> {
+        if let Some(mut cache) = self.lock_cache()? {
             cache.clear()?;
         }
         Ok(())
     }
 
     /// Save cache to disk (if enabled and modified)
-    pub fn save_cache(&mut self) -> GuardianResult<()> {
-        if let Some(cache) = &mut self.cache {
+    pub fn save_cache(&self) -> GuardianResult<()> {
+        if let Some(mut cache) = self.lock_cache()? {
             cache.save()?;
         }
         Ok(())
     }
 
     /// Cleanup cache by removing entries for non-existent files
-    pub fn cleanup_cache(&mut self) -> GuardianResult<Option<usize>> {
-        if let Some(cache) = &mut self.cache {
-            Ok(Some(cache.cleanup()?))
-        } else {
-            Ok(None)
+    ///
+    /// Entries for files left out by a sparse checkout of the current directory are kept.
+    pub fn cleanup_cache(&self) -> GuardianResult<Option<usize>> {
+        match self.lock_cache()? {
+            Some(mut cache) => Ok(Some(
+                cache.cleanup(&SparseCheckout::detect(Path::new(".")))?,
+            )),
+            None => Ok(None),
         }
     }
 
+    /// Lock the cache for a short update, if caching is enabled
+    fn lock_cache(&self) -> GuardianResult<Option<MutexGuard<'_, FileCache>>> {
+        self.cache
+            .as_ref()
+            .map(|cache| {
+                cache.lock().map_err(|_| {
+                    GuardianError::cache("Cache lock poisoned by a panicked validation")
+                })
+            })
+            .transpose()
+    }
+
     /// Cache-aware analysis that skips files that haven't changed
     async fn analyze_with_cache(
-        &mut self,
+        &self,
         paths: &[PathBuf],
         options: &AnalysisOptions,
     ) -> GuardianResult<ValidationReport> {
         let mut all_violations = Vec::new();
-        let files_analyzed: usize;
         let start_time = std::time::Instant::now();
 
-        // Get config fingerprint for cache validation
-        let config_fingerprint = self.analyzer.config_fingerprint();
+        // Use one analyzer for the whole run, even if the config is reloaded meanwhile
+        let analyzer = self.analyzer();
+
+        // Cached results are validated per rule, so editing one rule only re-runs that rule
+        let config_fingerprint = analyzer.config_fingerprint();
+        let shared_fingerprint = analyzer.shared_fingerprint();
+        // Cross-file rules depend on every file, so they are never cached and re-run below;
+        // editing one leaves every cached entry valid
+        let workspace_rules = analyzer.workspace_rule_ids();
+        let per_file = |violation: &Violation| !workspace_rules.contains(&violation.rule_id);
+        let rule_fingerprints: BTreeMap<String, String> = analyzer
+            .rule_fingerprints()
+            .into_iter()
+            .filter(|(rule_id, _)| !workspace_rules.contains(rule_id))
+            .collect();
 
         // Discover all files to analyze
         let mut all_files = Vec::new();
@@ -221,80 +427,115 @@ impl GuardianValidator {
                 all_files.push(path.clone());
             } else if path.is_dir() {
                 // For directories, just analyze normally to discover files
-                let temp_report = self.analyzer.analyze_directory(path, options)?;
-                // Extract unique file paths from violations
-                let discovered_files: std::collections::HashSet<PathBuf> = temp_report
-                    .violations
-                    .iter()
-                    .map(|v| v.file_path.clone())
-                    .collect();
-                all_files.extend(discovered_files);
+                let temp_report = analyzer.analyze_directory(path, options)?;
+                all_files.extend(temp_report.summary.analyzed_files);
             }
         }
 
-        if let Some(cache) = &mut self.cache {
-            // Separate files into those that need analysis and those that don't
-            let mut files_to_analyze = Vec::new();
-            let mut _cached_violation_count = 0;
-
-            for file_path in &all_files {
-                match cache.needs_analysis(file_path, &config_fingerprint) {
-                    Ok(needs_analysis) => {
-                        if needs_analysis {
-                            files_to_analyze.push(file_path.clone());
-                        } else {
-                            // File is cached - get violation count from cache
-                            // Note: We don't re-add the actual violations to avoid memory usage
-                            // In a real implementation, you might want to store violations in cache
-                            _cached_violation_count += 1; // Count cached files without re-adding violations
+        // Separate files into those that need full analysis, those that only need their
+        // changed rules re-run, and cache hits. The lock is released before analysis so
+        // concurrent validations do not serialize on it.
+        let mut files_to_analyze = Vec::new();
+        let mut partial_files = Vec::new();
+        match self.lock_cache()? {
+            Some(mut cache) => {
+                for file_path in &all_files {
+                    let lookup = cache
+                        .lookup(file_path, &shared_fingerprint, &rule_fingerprints)
+                        .unwrap_or_else(|e| {
+                            // If cache check fails, analyze the file
+                            tracing::warn!("Cache check failed for {}: {}", file_path.display(), e);
+                            CacheLookup::Miss
+                        });
+                    match lookup {
+                        CacheLookup::Miss => files_to_analyze.push(file_path.clone()),
+                        CacheLookup::Hit(violations) => {
+                            all_violations.extend(violations.into_iter().filter(per_file))
+                        }
+                        CacheLookup::Partial {
+                            mut violations,
+                            stale_rules,
+                        } => {
+                            violations.retain(per_file);
+                            partial_files.push((file_path.clone(), violations, stale_rules))
                         }
-                    }
-                    Err(e) => {
-                        // If cache check fails, analyze the file
-                        tracing::warn!("Cache check failed for {}: {}", file_path.display(), e);
-                        files_to_analyze.push(file_path.clone());
                     }
                 }
             }
+            None => files_to_analyze = all_files.clone(),
+        }
 
-            // Analyze only files that need it
-            if !files_to_analyze.is_empty() {
-                let fresh_report = self.analyzer.analyze_paths(
-                    &files_to_analyze
-                        .iter()
-                        .map(|p| p.as_path())
-                        .collect::<Vec<_>>(),
-                    options,
-                )?;
-
-                all_violations.extend(fresh_report.violations);
-                // Note: files_analyzed will be set to all_files.len() below to include cached files
-
-                // Update cache with new results
-                for file_path in &files_to_analyze {
-                    let file_violations: Vec<_> = all_violations
-                        .iter()
-                        .filter(|v| v.file_path == *file_path)
-                        .collect();
-
-                    if let Err(e) =
-                        cache.update_entry(file_path, file_violations.len(), &config_fingerprint)
-                    {
-                        tracing::warn!("Failed to update cache for {}: {}", file_path.display(), e);
-                    }
-                }
-            }
+        let mut fresh_results = Vec::new();
+        let mut unanalyzed = Vec::new();
+
+        // Re-run only the changed rules where the file itself is unchanged
+        for (file_path, mut violations, stale_rules) in partial_files {
+            violations.extend(
+                analyzer
+                    .analyze_file_rules(&file_path, &stale_rules)?
+                    .into_iter()
+                    .filter(per_file),
+            );
+            all_violations.extend(violations.iter().cloned());
+            fresh_results.push((file_path, violations));
+        }
 
-            files_analyzed = all_files.len(); // Total files considered
-        } else {
-            // No cache - analyze all files normally
-            let report = self.analyzer.analyze_paths(
-                &all_files.iter().map(|p| p.as_path()).collect::<Vec<_>>(),
+        // Analyze only files that need it
+        if !files_to_analyze.is_empty() {
+            let fresh_report = analyzer.analyze_paths(
+                &files_to_analyze
+                    .iter()
+                    .map(|p| p.as_path())
+                    .collect::<Vec<_>>(),
                 options,
             )?;
 
-            all_violations.extend(report.violations);
-            files_analyzed = report.summary.total_files;
+            // Files cut off by the time budget have no results to cache
+            unanalyzed = fresh_report.summary.unanalyzed;
+            for file_path in files_to_analyze
+                .iter()
+                .filter(|file_path| !unanalyzed.contains(file_path))
+            {
+                let violations = fresh_report
+                    .violations
+                    .iter()
+                    .filter(|v| v.file_path == *file_path && per_file(v))
+                    .cloned()
+                    .collect();
+                fresh_results.push((file_path.clone(), violations));
+            }
+            all_violations.extend(fresh_report.violations.into_iter().filter(per_file));
+        }
+
+        // Update cache with new results
+        if let Some(mut cache) = self.lock_cache()? {
+            for (file_path, violations) in fresh_results {
+                if let Err(e) = cache.record(
+                    &file_path,
+                    violations,
+                    &shared_fingerprint,
+                    &rule_fingerprints,
+                ) {
+                    tracing::warn!("Failed to update cache for {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        // Cached files count as analyzed
+        let analyzed_files: Vec<PathBuf> = all_files
+            .iter()
+            .filter(|file_path| !unanalyzed.contains(file_path))
+            .cloned()
+            .collect();
+
+        // Cross-file rules see every file, cached or not, unless the time budget ran out
+        if unanalyzed.is_empty() {
+            let workspace_files: Vec<PathBuf> = analyzed_files
+                .iter()
+                .filter(|file| analyzer.path_filter().should_analyze(file).unwrap_or(false))
+                .cloned()
+                .collect();
+            all_violations.extend(analyzer.analyze_workspace(&workspace_files)?);
         }
 
         // Build final report
@@ -302,10 +543,17 @@ impl GuardianValidator {
         for violation in all_violations {
             report.add_violation(violation);
         }
+        for file_path in unanalyzed {
+            report.add_unanalyzed(file_path);
+        }
 
-        report.set_files_analyzed(files_analyzed);
+        report.set_analyzed_files(analyzed_files);
         report.set_execution_time(start_time.elapsed().as_millis() as u64);
         report.set_config_fingerprint(config_fingerprint);
+        report.set_rule_pack_version(config::RULE_PACK_VERSION);
+        if let Some(hash) = analyzer.policy_hash() {
+            report.set_policy_hash(hash);
+        }
         report.sort_violations();
 
         Ok(report)
@@ -319,7 +567,7 @@ pub fn create_validator() -> GuardianResult<GuardianValidator> {
 
 /// Convenience function to validate files with default settings
 pub async fn validate_files<P: AsRef<Path>>(files: Vec<P>) -> GuardianResult<ValidationReport> {
-    let mut validator = GuardianValidator::new()?;
+    let validator = GuardianValidator::new()?;
     validator.validate_for_agent(files).await
 }
 
@@ -336,19 +584,14 @@ pub mod agent {
     /// Pre-commit validation for autonomous agents
     ///
     /// This function provides a simple interface for agents to validate
-    /// code before committing changes. It returns an error if any blocking
-    /// violations are found.
+    /// code before committing changes. It returns `GuardianError::Validation`
+    /// carrying the full report if any blocking violations are found.
     pub async fn pre_commit_check<P: AsRef<Path>>(modified_files: Vec<P>) -> GuardianResult<()> {
-        let mut validator = GuardianValidator::new()?;
+        let validator = GuardianValidator::new()?;
         let report = validator.validate_for_agent(modified_files).await?;
 
         if report.has_errors() {
-            let error_count = report.summary.violations_by_severity.error;
-            return Err(GuardianError::config(format!(
-                "Pre-commit check failed: {} blocking violation{} found",
-                error_count,
-                if error_count == 1 { "" } else { "s" }
-            )));
+            return Err(GuardianError::validation_failed(report));
         }
 
         Ok(())
@@ -374,7 +617,7 @@ pub mod agent {
             ..Default::default()
         };
 
-        let mut validator = GuardianValidator::new()?;
+        let validator = GuardianValidator::new()?;
         validator.validate_with_options(files, &options).await
     }
 
@@ -399,15 +642,12 @@ pub mod agent {
             ..Default::default()
         };
 
-        let mut validator = GuardianValidator::new()?;
+        let validator = GuardianValidator::new()?;
         let report = validator.validate_with_options(files, &options).await?;
 
         // Fail if any warnings or errors found
         if report.has_violations() {
-            return Err(GuardianError::config(format!(
-                "Production validation failed: {} violations found",
-                report.violations.len()
-            )));
+            return Err(GuardianError::validation_failed(report));
         }
 
         Ok(report)
@@ -437,7 +677,7 @@ mod tests {
         // Create a file with violations
         fs::write(&test_file, "// TODO: implement this\nfn main() {}").unwrap();
 
-        let mut validator = GuardianValidator::new().unwrap();
+        let validator = GuardianValidator::new().unwrap();
         let report = validator.validate_for_agent(vec![test_file]).await.unwrap();
 
         // Should find the TODO comment
@@ -445,6 +685,467 @@ mod tests {
         assert!(report.violations.iter().any(|v| v.rule_id.contains("todo")));
     }
 
+    #[tokio::test]
+    async fn test_shared_validator_concurrent_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "// TODO: implement this\nfn main() {}").unwrap();
+
+        let validator = std::sync::Arc::new(
+            GuardianValidator::new()
+                .unwrap()
+                .with_cache(temp_dir.path().join("cache.json"))
+                .unwrap(),
+        );
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let validator = std::sync::Arc::clone(&validator);
+                let test_file = test_file.clone();
+                tokio::spawn(async move { validator.validate_for_agent(vec![test_file]).await })
+            })
+            .collect();
+
+        for task in tasks {
+            let report = task.await.unwrap().unwrap();
+            assert_eq!(report.summary.total_files, 1);
+        }
+
+        assert!(validator.cache_statistics().unwrap().total_files > 0);
+        validator.save_cache().unwrap();
+    }
+
+    #[test]
+    fn test_compiled_rules_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CompiledRules>();
+        assert_send_sync::<patterns::PatternEngine>();
+        assert_send_sync::<Analyzer>();
+
+        let config = GuardianConfig::default();
+        let rules = patterns::PatternEngine::compile(&config).unwrap();
+        let source = "fn main() {\n    // TODO: share\n    todo!()\n}\n";
+        let expected = Analyzer::new(config.clone())
+            .unwrap()
+            .analyze_content("src/main.rs", source)
+            .unwrap()
+            .len();
+        assert!(expected > 0);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let analyzer =
+                    Analyzer::with_compiled_rules(config.clone(), Arc::clone(&rules)).unwrap();
+                assert!(Arc::ptr_eq(analyzer.compiled_rules(), &rules));
+                std::thread::spawn(move || {
+                    analyzer
+                        .analyze_content("src/main.rs", source)
+                        .unwrap()
+                        .len()
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), expected);
+        }
+
+        // Shared rules are immutable
+        let mut analyzer =
+            Analyzer::with_compiled_rules(config.clone(), Arc::clone(&rules)).unwrap();
+        assert!(analyzer.register_rule(Box::new(DbgRule("no_dbg"))).is_err());
+        let validator =
+            GuardianValidator::new_with_compiled_rules(config.clone(), Arc::clone(&rules)).unwrap();
+        assert!(validator.with_rule(Box::new(DbgRule("no_dbg"))).is_err());
+
+        // Rules compiled from another configuration are rejected
+        let mut other = config;
+        other.patterns.clear();
+        assert!(Analyzer::with_compiled_rules(other.clone(), Arc::clone(&rules)).is_err());
+        assert!(GuardianValidator::new_with_compiled_rules(other, rules).is_err());
+        let assembled = patterns::PatternEngine::new().rules().clone();
+        assert!(Analyzer::with_compiled_rules(GuardianConfig::default(), assembled).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cache_reruns_only_changed_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "// TODO: implement this\nfn main() {}").unwrap();
+
+        let validator = GuardianValidator::new()
+            .unwrap()
+            .with_cache(temp_dir.path().join("cache.json"))
+            .unwrap();
+        let first = validator
+            .validate_for_agent(vec![&test_file])
+            .await
+            .unwrap();
+        assert!(first
+            .violations
+            .iter()
+            .any(|v| v.rule_id == "todo_comments"));
+
+        // Unchanged rules replay their cached violations
+        let cached = validator
+            .validate_for_agent(vec![&test_file])
+            .await
+            .unwrap();
+        assert_eq!(cached.violations.len(), first.violations.len());
+
+        let mut config = GuardianConfig::default();
+        let rule = config
+            .patterns
+            .values_mut()
+            .flat_map(|category| category.rules.iter_mut())
+            .find(|rule| rule.id == "todo_comments")
+            .unwrap();
+        rule.message = "Edited: {match}".to_string();
+        validator.reload_config(config).unwrap();
+
+        let refreshed = validator
+            .validate_for_agent(vec![&test_file])
+            .await
+            .unwrap();
+        assert_eq!(refreshed.violations.len(), first.violations.len());
+        assert!(refreshed
+            .violations
+            .iter()
+            .any(|v| v.rule_id == "todo_comments" && v.message.starts_with("Edited")));
+
+        let stats = validator.cache_statistics().unwrap();
+        assert_eq!(
+            (stats.cache_misses, stats.cache_hits, stats.partial_hits),
+            (1, 1, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_reruns_workspace_rules_over_all_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        let lib = root.join("src/lib.rs");
+        let orders = root.join("src/orders.rs");
+        fs::write(
+            &lib,
+            "mod orders;\n\npub fn id() -> &'static str {\n    \"customer_id\"\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            &orders,
+            "pub fn key() -> &'static str {\n    \"customer_id\"\n}\n",
+        )
+        .unwrap();
+
+        let mut config = GuardianConfig::default();
+        let category = config.patterns.values_mut().next().unwrap();
+        let mut rule = category.rules[0].clone();
+        rule.id = "repeated_literal".to_string();
+        rule.rule_type = config::RuleType::Semantic;
+        rule.pattern = "duplicate_string_literal:1".to_string();
+        rule.message = "\"{value}\" appears {count} times".to_string();
+        rule.enabled = true;
+        category.enabled = true;
+        category.rules.push(rule);
+        let validator = GuardianValidator::new_with_config(config.clone())
+            .unwrap()
+            .with_cache(root.join("cache.json"))
+            .unwrap();
+        let repeated = |report: &ValidationReport| {
+            report
+                .violations
+                .iter()
+                .filter(|v| v.rule_id == "repeated_literal")
+                .count()
+        };
+
+        let first = validator
+            .validate_for_agent(vec![&lib, &orders])
+            .await
+            .unwrap();
+        assert_eq!(repeated(&first), 1);
+
+        // Only one file is a cache miss, but the rule still sees both
+        fs::write(&lib, "mod orders;\n\npub fn id() -> u32 {\n    7\n}\n").unwrap();
+        let edited = validator
+            .validate_for_agent(vec![&lib, &orders])
+            .await
+            .unwrap();
+        assert_eq!(repeated(&edited), 0);
+
+        fs::write(
+            &lib,
+            "mod orders;\n\npub fn id() -> &'static str {\n    \"customer_id\"\n}\n",
+        )
+        .unwrap();
+        let restored = validator
+            .validate_for_agent(vec![&lib, &orders])
+            .await
+            .unwrap();
+        assert_eq!(repeated(&restored), 1);
+
+        // Editing a cross-file rule leaves the cached per-file results valid
+        let rule = config
+            .patterns
+            .values_mut()
+            .flat_map(|category| category.rules.iter_mut())
+            .find(|rule| rule.id == "repeated_literal")
+            .unwrap();
+        rule.message = "Edited: {value}".to_string();
+        validator.reload_config(config).unwrap();
+        let before = validator.cache_statistics().unwrap();
+        let edited_rule = validator
+            .validate_for_agent(vec![&lib, &orders])
+            .await
+            .unwrap();
+        assert!(edited_rule
+            .violations
+            .iter()
+            .any(|v| v.rule_id == "repeated_literal" && v.message.starts_with("Edited")));
+        let after = validator.cache_statistics().unwrap();
+        assert_eq!(after.cache_hits, before.cache_hits + 2);
+        assert_eq!(after.partial_hits, before.partial_hits);
+    }
+
+    #[test]
+    fn test_streaming_validation_recent_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_file = temp_dir.path().join("old.rs");
+        let new_file = temp_dir.path().join("new.rs");
+        fs::write(&old_file, "// TODO: old\nfn old() {}").unwrap();
+        fs::write(&new_file, "// TODO: new\nfn new() {}").unwrap();
+        let an_hour_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&old_file)
+            .unwrap()
+            .set_modified(an_hour_ago)
+            .unwrap();
+
+        let validator = GuardianValidator::new().unwrap();
+        let options = AnalysisOptions {
+            parallel: false,
+            ..Default::default()
+        };
+        let streamed = Mutex::new(Vec::new());
+        let report = validator
+            .validate_streaming(&[temp_dir.path()], &options, |path, violations| {
+                streamed
+                    .lock()
+                    .unwrap()
+                    .push((path.to_path_buf(), violations.len()));
+            })
+            .unwrap();
+
+        let streamed = streamed.into_inner().unwrap();
+        let order: Vec<&PathBuf> = streamed.iter().map(|(path, _)| path).collect();
+        assert_eq!(order, vec![&new_file, &old_file]);
+        assert_eq!(
+            streamed.iter().map(|(_, count)| count).sum::<usize>(),
+            report.violations.len()
+        );
+    }
+
+    #[test]
+    fn test_rule_usage_collected_on_request() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.rs"), "// TODO: a\nfn a() {}").unwrap();
+        fs::write(temp_dir.path().join("b.rs"), "fn b() {}").unwrap();
+
+        let validator = GuardianValidator::new().unwrap();
+        let report = validator
+            .validate_directory(temp_dir.path(), &AnalysisOptions::default())
+            .unwrap();
+        assert!(report.summary.rule_usage.is_empty());
+
+        let options = AnalysisOptions {
+            rule_usage: true,
+            ..Default::default()
+        };
+        let report = validator
+            .validate_directory(temp_dir.path(), &options)
+            .unwrap();
+        let todo = report
+            .summary
+            .rule_usage
+            .iter()
+            .find(|rule| rule.rule_id == "todo_comments")
+            .expect("todo_comments should be listed");
+        assert_eq!(todo.files_examined, 2);
+        assert_eq!(todo.files_matched, 1);
+        assert!(todo.matches >= 1);
+        assert!(report
+            .summary
+            .rule_usage
+            .windows(2)
+            .all(|pair| pair[0].rule_id < pair[1].rule_id));
+    }
+
+    #[test]
+    fn test_time_budget_partial_report() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.rs"), "// TODO: a\nfn a() {}").unwrap();
+        fs::write(temp_dir.path().join("b.rs"), "// TODO: b\nfn b() {}").unwrap();
+
+        let validator = GuardianValidator::new().unwrap();
+        let options = AnalysisOptions {
+            max_duration: Some(std::time::Duration::ZERO),
+            ..Default::default()
+        };
+        let report = validator
+            .validate_directory(temp_dir.path(), &options)
+            .unwrap();
+
+        assert!(report.is_partial());
+        assert_eq!(report.summary.unanalyzed.len(), 2);
+        assert_eq!(report.summary.total_files, 0);
+        assert!(report.violations.is_empty());
+
+        let full = validator
+            .validate_directory(temp_dir.path(), &AnalysisOptions::default())
+            .unwrap();
+        assert!(!full.is_partial());
+        assert_eq!(full.summary.total_files, 2);
+    }
+
+    #[test]
+    fn test_oversized_files_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let large = temp_dir.path().join("large.rs");
+        fs::write(&large, "// TODO: big\n".repeat(8 * 1024)).unwrap();
+        fs::write(temp_dir.path().join("small.rs"), "fn small() {}").unwrap();
+
+        let mut config = GuardianConfig::default();
+        config.paths.max_file_memory_mb = Some(1);
+        let validator = GuardianValidator::new_with_config(config).unwrap();
+        let report = validator
+            .validate_directory(temp_dir.path(), &AnalysisOptions::default())
+            .unwrap();
+
+        assert_eq!(report.summary.skipped_oversized, vec![large]);
+        assert_eq!(report.summary.total_files, 1);
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| !v.file_path.ends_with("large.rs")));
+    }
+
+    #[test]
+    fn test_reload_config() {
+        let validator = GuardianValidator::new().unwrap();
+        let default_rules = validator.pattern_statistics().enabled_rules;
+
+        // An invalid configuration leaves the current rules in place
+        let mut invalid = GuardianConfig::default();
+        let rule = invalid
+            .patterns
+            .values_mut()
+            .flat_map(|category| category.rules.iter_mut())
+            .find(|rule| matches!(rule.rule_type, RuleType::Regex))
+            .unwrap();
+        rule.pattern = "(".to_string();
+        assert!(validator.reload_config(invalid).is_err());
+        assert_eq!(validator.pattern_statistics().enabled_rules, default_rules);
+
+        let mut empty = GuardianConfig::default();
+        empty.patterns.clear();
+        validator.reload_config(empty).unwrap();
+        assert_eq!(validator.pattern_statistics().enabled_rules, 0);
+    }
+
+    struct DbgRule(&'static str);
+
+    impl Rule for DbgRule {
+        fn info(&self) -> RuleInfo {
+            RuleInfo::new(self.0, "dbg! left in source", Severity::Warning)
+                .with_category("debugging")
+        }
+
+        fn check(&self, file: &FileContext) -> Vec<Violation> {
+            file.content()
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains("dbg!("))
+                .map(|(index, _)| {
+                    Violation::new(
+                        self.0,
+                        Severity::Warning,
+                        file.path().to_path_buf(),
+                        "dbg! found",
+                    )
+                    .with_position(index as u32 + 1, 1)
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_syntax_tree_cache_kept_across_reloads() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "fn main() {\n    todo!()\n}\n").unwrap();
+
+        let syntax_trees = SyntaxTreeCache::new(8);
+        let validator = GuardianValidator::new()
+            .unwrap()
+            .with_syntax_tree_cache(syntax_trees.clone())
+            .unwrap();
+        let first = validator.validate_file(&test_file).unwrap();
+        assert!(first.has_violations());
+        assert_eq!(syntax_trees.len(), 1);
+
+        // The reloaded analyzer reuses the tree rather than caching a second one
+        validator.reload_config(GuardianConfig::default()).unwrap();
+        let second = validator.validate_file(&test_file).unwrap();
+        assert_eq!(second.violations.len(), first.violations.len());
+        assert_eq!(
+            second.violations[0].line_number,
+            first.violations[0].line_number
+        );
+    }
+
+    #[test]
+    fn test_procedural_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "fn main() {\n    dbg!(1);\n}").unwrap();
+
+        let validator = GuardianValidator::new()
+            .unwrap()
+            .with_rule(Box::new(DbgRule("no_dbg")))
+            .unwrap();
+        let report = validator.validate_file(&test_file).unwrap();
+        let violation = report
+            .violations
+            .iter()
+            .find(|v| v.rule_id == "no_dbg")
+            .unwrap();
+        assert_eq!(violation.line_number, Some(2));
+        assert_eq!(violation.category.as_deref(), Some("debugging"));
+
+        // Registered rules survive reloads, and ids cannot be taken twice
+        let mut empty = GuardianConfig::default();
+        empty.patterns.clear();
+        validator.reload_config(empty).unwrap();
+        assert_eq!(
+            validator
+                .validate_file(&test_file)
+                .unwrap()
+                .violations
+                .len(),
+            1
+        );
+        assert!(GuardianValidator::new()
+            .unwrap()
+            .with_rule(Box::new(DbgRule("todo_comments")))
+            .is_err());
+    }
+
     #[test]
     fn test_single_file_validation() {
         let temp_dir = TempDir::new().unwrap();
@@ -513,8 +1214,11 @@ mod tests {
         // Clean file should pass
         assert!(agent::pre_commit_check(vec![clean_file]).await.is_ok());
 
-        // Dirty file should fail
-        assert!(agent::pre_commit_check(vec![dirty_file]).await.is_err());
+        // Dirty file should fail with the report attached
+        let error = agent::pre_commit_check(vec![dirty_file]).await.unwrap_err();
+        assert!(matches!(error, GuardianError::Validation { .. }));
+        let report = error.report().unwrap();
+        assert!(report.has_errors());
     }
 
     #[tokio::test]
@@ -550,4 +1254,393 @@ mod tests {
         let report = validate_directory(temp_dir.path()).unwrap();
         assert_eq!(report.summary.total_files, 1);
     }
+
+    #[test]
+    fn test_parsers_reject_malformed_input() {
+        // Inputs the fuzz targets have found troublesome must fail cleanly, not panic
+        for yaml in [
+            "",
+            "version: [",
+            "version: \"1.0\"\npatterns: 7",
+            "version: \"1.0\"\npaths: {max_file_memory_mb: 18446744073709551615}",
+            "&a [*a]",
+            "\u{feff}\0\u{ffff}",
+        ] {
+            let _ = GuardianConfig::load_from_str(yaml);
+        }
+
+        let mut rule = GuardianConfig::default()
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .next()
+            .unwrap()
+            .clone();
+        for rule_type in [
+            config::RuleType::Regex,
+            config::RuleType::Ast,
+            config::RuleType::Semantic,
+            config::RuleType::ImportAnalysis,
+        ] {
+            for pattern in [
+                "",
+                "(",
+                "macro_call:",
+                "macro_call:|||",
+                "attribute_marker:#[",
+                "function_lines_gt:-1",
+                "duplicate_string_literal::",
+                "todo_expired:(",
+                "import:[",
+                "direct_\u{e9}_access",
+            ] {
+                rule.rule_type = rule_type.clone();
+                rule.pattern = pattern.to_string();
+                let mut engine = patterns::PatternEngine::new();
+                if engine.add_rule(&rule, Severity::Warning).is_ok() {
+                    engine
+                        .analyze_file("fuzz.rs", "fn main() { todo!() }\n// TODO: \u{e9}")
+                        .unwrap();
+                }
+            }
+        }
+
+        assert!(PathFilter::check_ignore_content("target/\n# comment\n!keep.rs\n").is_empty());
+        let errors = PathFilter::check_ignore_content("src/\n**/[z-a]\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_analyze_content_without_filesystem() {
+        let analyzer = Analyzer::with_defaults().unwrap();
+        let source = "fn main() {\n    todo!()\n}\n";
+
+        // The path is never read, it only labels the violations
+        let violations = analyzer
+            .analyze_content("does/not/exist.rs", source)
+            .unwrap();
+        assert!(!violations.is_empty());
+        assert!(violations
+            .iter()
+            .all(|v| v.file_path == Path::new("does/not/exist.rs")));
+
+        let generated = format!("// @generated by build.rs\n{source}");
+        assert!(analyzer
+            .analyze_content("src/generated.rs", &generated)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_analyze_expanded_rule_subset() {
+        let mut config = GuardianConfig::default();
+        config.expansion.rules = vec!["todo_comments".to_string()];
+        let analyzer = Analyzer::new(config).unwrap();
+        let source = "fn run() {\n    // TODO: expand\n    unimplemented!()\n}\n";
+
+        let violations = analyzer
+            .analyze_expanded("target/guardian-expanded/app-lib-app.rs", source)
+            .unwrap();
+        assert!(!violations.is_empty());
+        assert!(violations.iter().all(|v| v.rule_id == "todo_comments"));
+        assert!(violations
+            .iter()
+            .all(|v| v.tags.contains(&"macro-expanded".to_string())));
+
+        let mut config = GuardianConfig::default();
+        config.expansion.rules = vec!["no_such_rule".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_cfg_context_and_scoping() {
+        let source = r#"
+fn always() {
+    // TODO: everywhere
+}
+
+#[cfg(windows)]
+fn windows_only() {
+    // TODO: windows
+}
+
+#[cfg(test)]
+mod tests {
+    // TODO: tests
+}
+"#;
+        let todo_lines = |analyzer: &Analyzer| -> Vec<(u32, Option<String>, Vec<String>)> {
+            analyzer
+                .analyze_content("src/lib.rs", source)
+                .unwrap()
+                .into_iter()
+                .filter(|v| v.rule_id == "todo_comments")
+                .map(|v| (v.line_number.unwrap(), v.cfg, v.tags))
+                .collect()
+        };
+
+        let analyzer = Analyzer::with_defaults().unwrap();
+        let lines = todo_lines(&analyzer);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].1.as_deref(), Some("windows"));
+        assert_eq!(lines[2].1.as_deref(), Some("test"));
+
+        // Scoped to test code
+        let mut config = GuardianConfig::default();
+        for category in config.patterns.values_mut() {
+            for rule in &mut category.rules {
+                if rule.id == "todo_comments" {
+                    rule.only_cfg = Some("test".to_string());
+                }
+            }
+        }
+        let lines = todo_lines(&Analyzer::new(config).unwrap());
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].0, 13);
+
+        // Windows code is dropped when no build enables it
+        let mut config = GuardianConfig::default();
+        config.cfg.matrix = vec![
+            config::CfgBuild {
+                name: "linux".to_string(),
+                enabled: vec!["unix".to_string(), "test".to_string()],
+            },
+            config::CfgBuild {
+                name: "release".to_string(),
+                enabled: vec!["unix".to_string()],
+            },
+        ];
+        let lines = todo_lines(&Analyzer::new(config.clone()).unwrap());
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].2, vec!["build:linux".to_string()]);
+
+        config.cfg.matrix[0].enabled = vec!["feature = ".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_doc_example_analysis() {
+        let source = r#"/// Start the server
+///
+/// ```no_run
+/// let server = Server::bind("0.0.0.0:80");
+/// unimplemented!()
+/// ```
+pub fn start() {}
+"#;
+        let unfinished = |analyzer: &Analyzer, path: &str, content: &str| -> Vec<(u32, u32)> {
+            analyzer
+                .analyze_content(path, content)
+                .unwrap()
+                .into_iter()
+                .filter(|v| v.rule_id == "unimplemented_macros")
+                .inspect(|v| assert!(v.tags.contains(&"doc-example".to_string())))
+                .map(|v| (v.line_number.unwrap(), v.column_number.unwrap()))
+                .collect()
+        };
+
+        let analyzer = Analyzer::with_defaults().unwrap();
+        assert!(unfinished(&analyzer, "src/server.rs", source).is_empty());
+
+        let mut config = GuardianConfig::default();
+        config.doc_examples.enabled = true;
+        let analyzer = Analyzer::new(config).unwrap();
+        assert_eq!(unfinished(&analyzer, "src/server.rs", source), vec![(5, 5)]);
+
+        let markdown = "# Server\n\n```rust\ntodo!()\n```\n";
+        assert_eq!(unfinished(&analyzer, "README.md", markdown), vec![(4, 1)]);
+
+        let mut config = GuardianConfig::default();
+        config.doc_examples.enabled = true;
+        config.doc_examples.rules = vec!["no_such_rule".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_composite_rule() {
+        let config = GuardianConfig::load_from_str(
+            r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  async_io:
+    severity: error
+    enabled: true
+    rules:
+      - id: blocking_fs_in_async
+        type: composite
+        pattern: function
+        message: "Async function '{function}' uses blocking std::fs"
+        all_of:
+          - type: regex
+            pattern: '\basync\s+fn\b'
+          - type: regex
+            pattern: 'std::fs::'
+        none_of:
+          - type: regex
+            pattern: '#\[(tokio::)?test\]'
+"#,
+        )
+        .unwrap();
+        let analyzer = Analyzer::new(config).unwrap();
+        let source = "async fn load() {\n    std::fs::read(\"a\").ok();\n}\n\n#[tokio::test]\nasync fn reads() {\n    std::fs::read(\"b\").ok();\n}\n";
+
+        let violations: Vec<_> = analyzer
+            .analyze_content("src/io.rs", source)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.rule_id == "blocking_fs_in_async")
+            .collect();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, Some(1));
+        assert_eq!(
+            violations[0].message,
+            "Async function 'load' uses blocking std::fs"
+        );
+        assert_eq!(violations[0].category.as_deref(), Some("async_io"));
+
+        let invalid = "version: \"1.0\"\npaths:\n  patterns: []\npatterns:\n  misc:\n    severity: error\n    enabled: true\n    rules:\n      - id: plain\n        type: regex\n        pattern: x\n        message: x\n        all_of:\n          - type: regex\n            pattern: y\n";
+        assert!(GuardianConfig::load_from_str(invalid).is_err());
+    }
+
+    #[test]
+    fn test_file_pair_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("src/parser.rs"), "pub fn parse() {}\n").unwrap();
+        fs::write(root.join("src/lexer.rs"), "pub fn lex() {}\n").unwrap();
+        fs::write(root.join("src/util.rs"), "fn helper() {}\n").unwrap();
+        fs::write(root.join("docs/lexer.md"), "# Lexer\n").unwrap();
+
+        let config = GuardianConfig::load_from_str(
+            r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  docs:
+    severity: warning
+    enabled: true
+    rules:
+      - id: module_docs_missing
+        type: file_pair
+        pattern: "src/{stem}.rs -> docs/{stem}.md"
+        message: "{file} has public items but no {companion}"
+        all_of:
+          - type: regex
+            pattern: '\bpub\b'
+"#,
+        )
+        .unwrap();
+        let validator = GuardianValidator::new_with_config(config).unwrap();
+        let report = validator
+            .validate_directory(root, &AnalysisOptions::default())
+            .unwrap();
+
+        let violations: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule_id == "module_docs_missing")
+            .collect();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].file_path.ends_with("src/parser.rs"));
+        assert!(violations[0].message.ends_with("docs/parser.md"));
+
+        let invalid = "version: \"1.0\"\npaths:\n  patterns: []\npatterns:\n  misc:\n    severity: error\n    enabled: true\n    rules:\n      - id: pair\n        type: file_pair\n        pattern: 'src/{stem}.rs'\n        message: x\n";
+        assert!(GuardianConfig::load_from_str(invalid).is_err());
+    }
+
+    #[test]
+    fn test_required_rule() {
+        let config = GuardianConfig::load_from_str(
+            r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  hygiene:
+    severity: error
+    enabled: true
+    rules:
+      - id: lib_denies_unsafe
+        type: required
+        pattern: '#!\[(deny|forbid)\(unsafe_code\)\]'
+        message: "Crate root must contain #![deny(unsafe_code)]"
+        applies_to: ["**/lib.rs"]
+"#,
+        )
+        .unwrap();
+        let analyzer = Analyzer::new(config).unwrap();
+        let required = |path: &str, source: &str| -> Vec<(Option<u32>, String)> {
+            analyzer
+                .analyze_content(path, source)
+                .unwrap()
+                .into_iter()
+                .filter(|v| v.rule_id == "lib_denies_unsafe")
+                .map(|v| (v.line_number, v.message))
+                .collect()
+        };
+
+        assert_eq!(
+            required("src/lib.rs", "pub fn run() {}\n"),
+            vec![(
+                Some(1),
+                "Crate root must contain #![deny(unsafe_code)]".to_string()
+            )]
+        );
+        assert!(required("src/lib.rs", "#![forbid(unsafe_code)]\npub fn run() {}\n").is_empty());
+        assert!(required("src/main.rs", "fn main() {}\n").is_empty());
+    }
+
+    #[test]
+    fn test_fix_templates() {
+        let config = GuardianConfig::load_from_str(
+            r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  hygiene:
+    severity: warning
+    enabled: true
+    rules:
+      - id: no_dbg
+        type: regex
+        pattern: 'dbg!\((\w+)\)'
+        message: "Remove dbg! before committing"
+        fix:
+          replace_match_with: "$1"
+      - id: unsafe_needs_safety
+        type: regex
+        pattern: '\bunsafe \{'
+        message: "Explain why the unsafe block is sound"
+        fix:
+          insert_before_line: "// SAFETY: "
+"#,
+        )
+        .unwrap();
+        let analyzer = Analyzer::new(config).unwrap();
+        let source = "fn run(p: *const u8) -> u8 {\n    let v = dbg!(p);\n    unsafe { *v }\n}\n";
+        let violations = analyzer.analyze_content("src/lib.rs", source).unwrap();
+        let fixes: Vec<(usize, &domain::violations::Fix)> = violations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, violation)| violation.fix.as_ref().map(|fix| (index, fix)))
+            .collect();
+        assert_eq!(fixes.len(), 2);
+
+        let (fixed, applied) = analyzer::fix::apply_to_content(source, &fixes);
+        assert_eq!(applied.len(), 2);
+        assert_eq!(
+            fixed,
+            "fn run(p: *const u8) -> u8 {\n    let v = p;\n    // SAFETY: \n    unsafe { *v }\n}\n"
+        );
+
+        let invalid = "version: \"1.0\"\npaths:\n  patterns: []\npatterns:\n  misc:\n    severity: error\n    enabled: true\n    rules:\n      - id: both\n        type: regex\n        pattern: x\n        message: x\n        fix:\n          replace_match_with: y\n          insert_before_line: z\n";
+        assert!(GuardianConfig::load_from_str(invalid).is_err());
+    }
 }
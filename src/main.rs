@@ -6,12 +6,43 @@
 //! - Provides clean separation between user interface and business logic
 
 use clap::{Parser, Subcommand, ValueEnum};
+use rust_guardian::analyzer::memory::MemoryUsage;
+use rust_guardian::analyzer::receive;
+use rust_guardian::analyzer::repl::{ReplSession, ReplStep};
+use rust_guardian::analyzer::revision::Revision;
+use rust_guardian::analyzer::scope::{Scope, SparseCheckout};
+use rust_guardian::analyzer::targets::{CargoTargets, TargetSelection};
+use rust_guardian::config::{
+    find_config_file, find_locked_config_file, find_project_root, lint_config, migrate_config,
+    policy, releases_between, EMBEDDED_CONFIG, RULE_PACK_HISTORY, RULE_PACK_VERSION,
+};
+use rust_guardian::domain::violations::ViolationCounts;
+use rust_guardian::graph::{CrateGraph, ImportIndex, ModuleGraph};
+use rust_guardian::issues::{self, IssueLinks};
+use rust_guardian::report::attestation;
+use rust_guardian::report::build_result::BuildResult;
+use rust_guardian::report::history::{self, ViolationHistory};
+use rust_guardian::report::job_summary::Baseline;
+use rust_guardian::report::rulebook::{render_rulebook, RulebookFormat};
+use rust_guardian::report::simulation;
+use rust_guardian::triage::feedback::FeedbackLog;
+use rust_guardian::triage::{self, Triage, TriageEntry, TriageLabel, TRIAGE_FILE};
+use rust_guardian::vcs_policy;
 use rust_guardian::{
-    AnalysisOptions, GuardianConfig, GuardianError, GuardianResult, GuardianValidator,
-    OutputFormat, ReportOptions, Severity, ValidationOptions,
+    AnalysisOptions, Analyzer, CsvColumn, FeedbackConfig, GuardianConfig, GuardianError,
+    GuardianResult, GuardianValidator, OutputFormat, PathFilter, ReportFormatter, ReportOptions,
+    ReportSignature, Severity, SyntaxTreeCache, ValidationOptions, ValidationReport, Violation,
 };
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Counts allocations for `check --timings`
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: rust_guardian::analyzer::memory::TrackingAllocator =
+    rust_guardian::analyzer::memory::TrackingAllocator;
 
 /// Rust Guardian - Dynamic code quality enforcement
 #[derive(Parser)]
@@ -29,26 +60,51 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
-    /// Configuration file path
+    /// Configuration file path or https:// URL (pin with #sha256=<hex>)
     #[arg(short, long, global = true)]
     config: Option<PathBuf>,
 
     /// Disable colored output
     #[arg(long, global = true)]
     no_color: bool,
+
+    /// Log format: human-readable text, or JSON lines on stderr for log collectors
+    #[arg(long, global = true, value_enum, default_value = "human")]
+    log_format: LogFormatArg,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Check files for code quality violations
     Check {
         /// Paths to analyze (files or directories)
         paths: Vec<PathBuf>,
 
+        /// Analyze the files compiled by this workspace package (repeatable)
+        #[arg(short = 'p', long = "package", value_name = "NAME")]
+        packages: Vec<String>,
+
+        /// Analyze library targets
+        #[arg(long)]
+        lib: bool,
+
+        /// Analyze binary targets
+        #[arg(long)]
+        bins: bool,
+
+        /// Analyze targets built by `cargo test`
+        #[arg(long)]
+        tests: bool,
+
         /// Output format
         #[arg(short, long, value_enum, default_value = "human")]
         format: OutputFormatArg,
 
+        /// Agent mode: JSON-lines output with stable fingerprints, no colors
+        #[arg(long, conflicts_with = "format")]
+        agent: bool,
+
         /// Minimum severity level to report
         #[arg(short, long, value_enum)]
         severity: Option<SeverityArg>,
@@ -57,6 +113,14 @@ enum Commands {
         #[arg(long)]
         max_violations: Option<usize>,
 
+        /// Maximum number of violations to report per rule
+        #[arg(long)]
+        max_per_rule: Option<usize>,
+
+        /// Maximum number of violations to report per file
+        #[arg(long)]
+        max_per_file: Option<usize>,
+
         /// Additional exclude patterns
         #[arg(long, action = clap::ArgAction::Append)]
         exclude: Vec<String>,
@@ -65,6 +129,10 @@ enum Commands {
         #[arg(long)]
         no_ignore: bool,
 
+        /// Do not exclude paths matched by .gitignore files
+        #[arg(long)]
+        no_gitignore: bool,
+
         /// Custom .guardianignore file
         #[arg(long)]
         guardianignore: Option<PathBuf>,
@@ -84,6 +152,194 @@ enum Commands {
         /// Custom cache file path
         #[arg(long)]
         cache_file: Option<PathBuf>,
+
+        /// Directory reported paths are relative to (defaults to the current directory)
+        #[arg(long)]
+        path_base: Option<PathBuf>,
+
+        /// Columns of the CSV output, comma-separated (e.g. file,line,rule,message)
+        #[arg(long, value_delimiter = ',')]
+        csv_columns: Vec<String>,
+
+        /// Also write run metrics (counts by severity, rule, category and file) as CSV
+        #[arg(long, value_name = "FILE")]
+        metrics_csv: Option<PathBuf>,
+
+        /// Also write Prometheus metrics for the node_exporter textfile collector
+        #[arg(long, value_name = "FILE")]
+        metrics_file: Option<PathBuf>,
+
+        /// Post a summary to the configured webhooks when thresholds are crossed
+        #[arg(long)]
+        send_notifications: bool,
+
+        /// Find or create tracker issues for trackable violations not linked to one yet
+        #[arg(long)]
+        link_issues: bool,
+
+        /// Enforce this policy (file path or URL) as a locked configuration
+        #[arg(long, value_name = "PATH|URL", conflicts_with = "config")]
+        enforce_config: Option<String>,
+
+        /// Sign the printed report, writing a detached signature to this file
+        #[arg(long, value_name = "FILE")]
+        signature_file: Option<PathBuf>,
+
+        /// Signing key file (defaults to the GUARDIAN_SIGNING_KEY environment variable)
+        #[arg(long, value_name = "FILE", requires = "signature_file")]
+        sign_key: Option<PathBuf>,
+
+        /// Print each file's violations to stderr as soon as it is analyzed, newest files first
+        #[arg(long, conflicts_with = "cache")]
+        stream: bool,
+
+        /// Stop starting new files after this long (e.g. 60s, 5m) and report partial results
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+        max_time: Option<std::time::Duration>,
+
+        /// Print run time and memory usage to stderr
+        #[arg(long)]
+        timings: bool,
+
+        /// Print how many files each rule examined and matched, and its run time, to stderr;
+        /// JSON reports also include these statistics under `summary.rule_usage`
+        #[arg(long, conflicts_with = "cache")]
+        stats: bool,
+
+        /// Also analyze macro-expanded code of the selected targets (all workspace targets
+        /// without --package/--lib/--bins/--tests), using the `expansion.rules` subset
+        #[arg(long, conflicts_with = "cache")]
+        expand: bool,
+
+        /// Show why each rule exists under its violations (human format)
+        #[arg(long)]
+        explain: bool,
+
+        /// Show rule rationale, category, tags and documentation link under each violation
+        /// (human format)
+        #[arg(long)]
+        verbose_violations: bool,
+
+        /// Do not write a job summary when running in GitHub Actions
+        #[arg(long)]
+        no_job_summary: bool,
+
+        /// Earlier JSON report (`--format json`) to tell new findings from known ones in the
+        /// job summary
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Apply the fixes that violations carry, such as inserting missing license headers,
+        /// and report only what is left
+        #[arg(long)]
+        fix: bool,
+
+        /// Restrict the run to this directory of the project; the scope is recorded in the
+        /// report, and baseline findings outside it are not counted as fixed
+        #[arg(long, value_name = "DIR")]
+        scope: Option<PathBuf>,
+
+        /// Analyze the files of this git revision, read from the repository without checking
+        /// it out (cross-file rules and macro expansion do not run)
+        #[arg(long, value_name = "REV", conflicts_with_all = ["cache", "stream", "fix", "expand"])]
+        rev: Option<String>,
+
+        /// Also write a small, stable JSON result for build-system test rules (Bazel, Buck);
+        /// turns off colors and fixes exit codes to 0 passed, 1 failed, 2 error
+        #[arg(long, value_name = "PATH")]
+        buildsystem_result: Option<PathBuf>,
+    },
+
+    /// Check a commit message and branch name against the `vcs_policy` configuration
+    CheckCommit {
+        /// File holding the commit message, as passed to a `commit-msg` hook (defaults to the
+        /// message of the latest commit)
+        #[arg(long, value_name = "FILE", conflicts_with = "message")]
+        message_file: Option<PathBuf>,
+
+        /// Commit message text
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Branch name (defaults to the checked-out branch)
+        #[arg(long)]
+        branch: Option<String>,
+
+        /// Do not check the branch name
+        #[arg(long, conflicts_with = "branch")]
+        no_branch: bool,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "human")]
+        format: OutputFormatArg,
+    },
+
+    /// Check a push from a git `pre-receive` hook: ref updates are read from standard input,
+    /// and pushes adding errors are rejected
+    PreReceive {
+        /// Time budget for the whole push; files not reached in time are let through
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = "30s")]
+        max_time: std::time::Duration,
+
+        /// Maximum number of errors listed in a rejection
+        #[arg(long, default_value_t = 10)]
+        max_violations: usize,
+    },
+
+    /// Label a violation as accepted, a false positive or won't fix, or list labels per rule
+    Triage {
+        /// Fingerprint of the violation, as printed by `check --format json` or `--agent`
+        fingerprint: Option<String>,
+
+        /// Label to record for the violation
+        #[arg(long, value_enum, requires = "fingerprint")]
+        label: Option<TriageLabelArg>,
+
+        /// Why the label was chosen
+        #[arg(long, requires = "label")]
+        note: Option<String>,
+
+        /// Remove the label of the violation
+        #[arg(long, requires = "fingerprint", conflicts_with = "label")]
+        clear: bool,
+
+        /// Walk through unlabeled violations, prompting for a label for each
+        #[arg(short, long, conflicts_with = "fingerprint")]
+        interactive: bool,
+
+        /// Paths searched for the violation, as passed to `check` (defaults to `.`)
+        #[arg(long = "path")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Show or export the triage feedback collected when `feedback.enabled` is set
+    Feedback {
+        #[command(subcommand)]
+        action: FeedbackCommands,
+    },
+
+    /// Re-evaluate a stored report under a proposed configuration, without re-analyzing
+    Simulate {
+        /// Proposed configuration file (defaults to the current configuration)
+        #[arg(long, value_name = "FILE")]
+        proposed: Option<PathBuf>,
+
+        /// Rule change applied to the proposal: rule=off, rule=on or rule=error|warning|info
+        /// (repeatable)
+        #[arg(long = "set", value_name = "RULE=VALUE")]
+        overrides: Vec<String>,
+
+        /// Report saved with `check --format json` (defaults to the analysis cache)
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+
+        /// Cache file read when no report is given
+        #[arg(long, conflicts_with = "report")]
+        cache_file: Option<PathBuf>,
+
+        /// Print the simulation as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Watch for file changes and run checks automatically
@@ -98,6 +354,53 @@ enum Commands {
         /// Debounce delay in milliseconds
         #[arg(long, default_value = "500")]
         delay: u64,
+
+        /// Shell command to run after an analysis without errors
+        #[arg(long, value_name = "CMD")]
+        on_success: Option<String>,
+
+        /// Shell command to run after an analysis that found errors
+        #[arg(long, value_name = "CMD")]
+        on_failure: Option<String>,
+
+        /// Show a desktop notification when the pass/fail status changes
+        #[arg(long)]
+        notify: bool,
+
+        /// Rewrite Prometheus metrics to this file after every pass
+        #[arg(long, value_name = "FILE")]
+        metrics_file: Option<PathBuf>,
+
+        /// Serve Prometheus metrics on http://ADDR/metrics (e.g. 127.0.0.1:9464)
+        #[arg(long, value_name = "ADDR")]
+        metrics_addr: Option<String>,
+
+        /// Cache file whose writes never trigger an analysis, for checks run with a custom
+        /// --cache-file (defaults to .rust/guardian_cache.json in the project root)
+        #[arg(long, value_name = "FILE")]
+        cache_file: Option<PathBuf>,
+    },
+
+    /// Build the module dependency graph from `use` statements
+    Graph {
+        /// Paths to analyze (files or directories)
+        paths: Vec<PathBuf>,
+
+        /// Graph workspace crates from `cargo metadata` instead of modules
+        #[arg(long)]
+        crates: bool,
+
+        /// Workspace manifest to read with --crates
+        #[arg(long, default_value = "Cargo.toml")]
+        manifest_path: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "dot")]
+        format: GraphFormatArg,
+
+        /// Write the graph to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// Validate configuration file
@@ -106,12 +409,86 @@ enum Commands {
         config_file: Option<PathBuf>,
     },
 
+    /// Configuration maintenance
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
+    /// Verify a report against its detached signature
+    VerifyReport {
+        /// Report file, exactly as printed by `check`
+        report: PathBuf,
+
+        /// Signature file (defaults to the report path with `.sig` appended)
+        #[arg(long)]
+        signature: Option<PathBuf>,
+
+        /// Trusted public key, hex-encoded or a file containing it
+        #[arg(long)]
+        public_key: String,
+    },
+
+    /// Generate an ed25519 key pair for signing reports
+    Keygen {
+        /// File to write the hex-encoded signing key to
+        output: PathBuf,
+    },
+
+    /// List the files that would be analyzed under the current configuration
+    Files {
+        /// Paths to list (files or directories)
+        paths: Vec<PathBuf>,
+
+        /// Additional exclude patterns
+        #[arg(long, action = clap::ArgAction::Append)]
+        exclude: Vec<String>,
+
+        /// Print the file list as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Explain why a file is excluded from analysis or from specific rules
+    WhyIgnored {
+        /// File to explain
+        path: PathBuf,
+    },
+
+    /// Measure analysis throughput on a generated corpus
+    SelfBenchmark {
+        /// Number of synthetic files to generate
+        #[arg(long, default_value = "10000")]
+        files: usize,
+
+        /// Fail when throughput drops below this many files per second
+        #[arg(long)]
+        min_files_per_second: Option<f64>,
+    },
+
+    /// Try pattern expressions interactively against sample files
+    Repl {
+        /// Files or directories to load at start
+        paths: Vec<PathBuf>,
+    },
+
     /// Explain what a specific rule does
     Explain {
         /// Rule ID to explain
         rule_id: String,
     },
 
+    /// Generate a rulebook documenting every rule of the loaded configuration
+    Docs {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: DocsFormatArg,
+
+        /// Write the rulebook to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Show cache statistics
     Cache {
         #[command(subcommand)]
@@ -127,6 +504,14 @@ enum Commands {
         /// Filter by category
         #[arg(long)]
         category: Option<String>,
+
+        /// List the built-in rules that changed between rule pack versions
+        #[arg(long)]
+        list_changed_rules: bool,
+
+        /// Rule pack version to compare from (defaults to the first release)
+        #[arg(long, requires = "list_changed_rules")]
+        since: Option<String>,
     },
 }
 
@@ -154,6 +539,42 @@ enum CacheCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum FeedbackCommands {
+    /// Show label counts and false-positive rates per rule
+    Show,
+
+    /// Print a shareable JSON summary; custom rule ids are replaced by hashes
+    Export {
+        /// Write the summary to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Rewrite a configuration file away from deprecated rules
+    Migrate {
+        /// Configuration file to migrate
+        config_file: Option<PathBuf>,
+
+        /// Print the migrated configuration instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Warn about rules that can never fire or duplicate other rules
+    Lint {
+        /// Configuration file to lint
+        config_file: Option<PathBuf>,
+
+        /// Directories whose files rule scopes are checked against
+        #[arg(long = "path", default_value = ".")]
+        paths: Vec<PathBuf>,
+    },
+}
+
 #[derive(Copy, Clone, ValueEnum, PartialEq)]
 enum OutputFormatArg {
     Human,
@@ -162,6 +583,56 @@ enum OutputFormatArg {
     Sarif,
     Github,
     Agent,
+    Jsonl,
+    Csv,
+    CargoJson,
+    AzureDevops,
+    Bitbucket,
+    Rdjson,
+}
+
+#[derive(Copy, Clone, ValueEnum, PartialEq)]
+enum TriageLabelArg {
+    Accepted,
+    FalsePositive,
+    WontFix,
+}
+
+impl From<TriageLabelArg> for TriageLabel {
+    fn from(arg: TriageLabelArg) -> Self {
+        match arg {
+            TriageLabelArg::Accepted => TriageLabel::Accepted,
+            TriageLabelArg::FalsePositive => TriageLabel::FalsePositive,
+            TriageLabelArg::WontFix => TriageLabel::WontFix,
+        }
+    }
+}
+
+#[derive(Copy, Clone, ValueEnum, PartialEq)]
+enum LogFormatArg {
+    Human,
+    Json,
+}
+
+#[derive(Copy, Clone, ValueEnum, PartialEq)]
+enum GraphFormatArg {
+    Dot,
+    Json,
+}
+
+#[derive(Copy, Clone, ValueEnum, PartialEq)]
+enum DocsFormatArg {
+    Markdown,
+    Html,
+}
+
+impl From<DocsFormatArg> for RulebookFormat {
+    fn from(arg: DocsFormatArg) -> Self {
+        match arg {
+            DocsFormatArg::Markdown => RulebookFormat::Markdown,
+            DocsFormatArg::Html => RulebookFormat::Html,
+        }
+    }
 }
 
 impl From<OutputFormatArg> for OutputFormat {
@@ -173,6 +644,12 @@ impl From<OutputFormatArg> for OutputFormat {
             OutputFormatArg::Sarif => OutputFormat::Sarif,
             OutputFormatArg::Github => OutputFormat::GitHub,
             OutputFormatArg::Agent => OutputFormat::Agent,
+            OutputFormatArg::Jsonl => OutputFormat::JsonLines,
+            OutputFormatArg::Csv => OutputFormat::Csv,
+            OutputFormatArg::CargoJson => OutputFormat::CargoJson,
+            OutputFormatArg::AzureDevops => OutputFormat::AzureDevOps,
+            OutputFormatArg::Bitbucket => OutputFormat::Bitbucket,
+            OutputFormatArg::Rdjson => OutputFormat::Rdjson,
         }
     }
 }
@@ -199,116 +676,361 @@ async fn main() {
     let cli = Cli::parse();
 
     // Initialize logging
-    init_logging(cli.verbose);
+    init_logging(cli.verbose, cli.log_format);
 
     // Run the command and handle the result
     let result = run_command(cli).await;
 
-    match result {
-        Ok(exit_code) => {
-            process::exit(exit_code);
-        }
+    let exit_code = match result {
+        Ok(exit_code) => exit_code,
         Err(e) => {
             eprintln!("Error: {e}");
-            process::exit(1);
+            1
         }
-    }
+    };
+
+    shutdown_tracing();
+    process::exit(exit_code);
 }
 
 async fn run_command(cli: Cli) -> GuardianResult<i32> {
     match cli.command {
         Commands::Check {
             paths,
+            packages,
+            lib,
+            bins,
+            tests,
             format,
+            agent,
             severity,
             max_violations,
+            max_per_rule,
+            max_per_file,
             exclude,
             no_ignore,
+            no_gitignore,
             guardianignore: _guardianignore,
             no_parallel,
             fail_fast,
             cache,
             cache_file,
+            path_base,
+            csv_columns,
+            metrics_csv,
+            metrics_file,
+            send_notifications,
+            link_issues,
+            enforce_config,
+            signature_file,
+            sign_key,
+            stream,
+            max_time,
+            timings,
+            stats,
+            expand,
+            explain,
+            verbose_violations,
+            no_job_summary,
+            baseline,
+            fix,
+            scope,
+            rev,
+            buildsystem_result,
         } => {
-            run_check(
+            // Agent mode implies JSON lines and plain output
+            let format = if agent {
+                OutputFormatArg::Jsonl
+            } else {
+                format
+            };
+            let selection = TargetSelection {
+                packages,
+                lib,
+                bins,
+                tests,
+            };
+            let paths = match resolve_target_paths(&selection, paths) {
+                Ok(paths) => paths,
+                Err(e) => return build_action_failure(buildsystem_result.as_deref(), e),
+            };
+            let expand = expand.then_some(selection);
+            let use_colors = !cli.no_color && !agent && buildsystem_result.is_none();
+            let result = run_check(
                 cli.config,
                 paths,
+                scope,
+                rev,
                 format,
                 severity,
                 max_violations,
+                max_per_rule,
+                max_per_file,
                 exclude,
                 no_ignore,
+                no_gitignore,
                 no_parallel,
                 fail_fast,
                 cache,
                 cache_file,
-                !cli.no_color,
+                path_base,
+                csv_columns,
+                metrics_csv,
+                metrics_file,
+                send_notifications,
+                link_issues,
+                enforce_config,
+                signature_file.map(|file| (file, sign_key)),
+                stream,
+                max_time,
+                timings,
+                stats,
+                expand,
+                explain,
+                verbose_violations,
+                (!no_job_summary).then_some(baseline),
+                fix,
+                buildsystem_result.clone(),
+                use_colors,
             )
-            .await
+            .await;
+            match result {
+                Err(e) => build_action_failure(buildsystem_result.as_deref(), e),
+                result => result,
+            }
         }
         Commands::Watch {
             path,
             pattern,
             delay,
-        } => run_watch(path, pattern, delay).await,
+            on_success,
+            on_failure,
+            notify,
+            metrics_file,
+            metrics_addr,
+            cache_file,
+        } => {
+            let hooks = WatchHooks {
+                on_success,
+                on_failure,
+                notify,
+            };
+            let metrics = MetricsExporter::new(metrics_file, metrics_addr)?;
+            let cache_file = cache_file.unwrap_or_else(default_cache_path);
+            run_watch(path, pattern, delay, hooks, metrics, &cache_file).await
+        }
+        Commands::Graph {
+            paths,
+            crates,
+            manifest_path,
+            format,
+            output,
+        } => {
+            if crates {
+                run_crate_graph(manifest_path, format, output)
+            } else {
+                run_graph(cli.config, paths, format, output)
+            }
+        }
         Commands::ValidateConfig { config_file } => run_validate_config(config_file.or(cli.config)),
-        Commands::Explain { rule_id } => run_explain(rule_id),
+        Commands::Config { action } => run_config_command(action, cli.config),
+        Commands::VerifyReport {
+            report,
+            signature,
+            public_key,
+        } => run_verify_report(&report, signature, &public_key),
+        Commands::Keygen { output } => run_keygen(&output),
+        Commands::Files {
+            paths,
+            exclude,
+            json,
+        } => run_list_files(cli.config, paths, exclude, json),
+        Commands::WhyIgnored { path } => run_why_ignored(cli.config, path),
+        Commands::SelfBenchmark {
+            files,
+            min_files_per_second,
+        } => run_self_benchmark(cli.config, files, min_files_per_second),
+        Commands::Repl { paths } => run_repl(cli.config, paths),
+        Commands::Explain { rule_id } => run_explain(cli.config, rule_id),
+        Commands::Docs { format, output } => run_docs(cli.config, format, output),
+        Commands::CheckCommit {
+            message_file,
+            message,
+            branch,
+            no_branch,
+            format,
+        } => run_check_commit(
+            cli.config,
+            message_file,
+            message,
+            (!no_branch).then_some(branch),
+            format,
+            !cli.no_color,
+        ),
+        Commands::PreReceive {
+            max_time,
+            max_violations,
+        } => run_pre_receive(cli.config, max_time, max_violations),
+        Commands::Triage {
+            fingerprint,
+            label,
+            note,
+            clear,
+            interactive,
+            paths,
+        } => match (fingerprint, interactive) {
+            (Some(fingerprint), _) => run_triage_label(
+                cli.config,
+                &fingerprint,
+                (!clear).then(|| label.map(TriageLabel::from)).flatten(),
+                note,
+                paths,
+            ),
+            (None, true) => run_triage_interactive(cli.config, paths),
+            (None, false) => run_triage_list(cli.config),
+        },
+        Commands::Feedback { action } => run_feedback_command(action, cli.config),
+        Commands::Simulate {
+            proposed,
+            overrides,
+            report,
+            cache_file,
+            json,
+        } => run_simulate(cli.config, proposed, overrides, report, cache_file, json),
         Commands::Cache { action } => run_cache_command(action).await,
         Commands::Rules {
             enabled_only,
             category,
-        } => run_list_rules(cli.config, enabled_only, category),
+            list_changed_rules,
+            since,
+        } => {
+            if list_changed_rules {
+                run_list_changed_rules(since)
+            } else {
+                run_list_rules(cli.config, enabled_only, category)
+            }
+        }
+    }
+}
+
+/// Add the files compiled by the selected cargo targets to the explicit paths
+fn resolve_target_paths(
+    selection: &TargetSelection,
+    mut paths: Vec<PathBuf>,
+) -> GuardianResult<Vec<PathBuf>> {
+    if selection.is_empty() {
+        return Ok(paths);
     }
+
+    let files = CargoTargets::from_directory(".")?.selected_files(selection)?;
+    if files.is_empty() {
+        return Err(GuardianError::config(
+            "No source files belong to the selected packages and targets",
+        ));
+    }
+    paths.extend(files);
+    Ok(paths)
 }
 
 #[allow(clippy::too_many_arguments)]
 async fn run_check(
     config_path: Option<PathBuf>,
     paths: Vec<PathBuf>,
+    scope: Option<PathBuf>,
+    rev: Option<String>,
     format: OutputFormatArg,
     severity: Option<SeverityArg>,
     max_violations: Option<usize>,
+    max_per_rule: Option<usize>,
+    max_per_file: Option<usize>,
     exclude_patterns: Vec<String>,
     no_ignore: bool,
+    no_gitignore: bool,
     no_parallel: bool,
     fail_fast: bool,
     use_cache: bool,
     cache_file: Option<PathBuf>,
+    path_base: Option<PathBuf>,
+    csv_columns: Vec<String>,
+    metrics_csv: Option<PathBuf>,
+    metrics_file: Option<PathBuf>,
+    send_notifications: bool,
+    link_issues: bool,
+    enforce_config: Option<String>,
+    signing: Option<(PathBuf, Option<PathBuf>)>,
+    stream: bool,
+    max_time: Option<std::time::Duration>,
+    timings: bool,
+    stats: bool,
+    expand: Option<TargetSelection>,
+    explain: bool,
+    verbose_violations: bool,
+    job_summary: Option<Option<PathBuf>>,
+    fix: bool,
+    buildsystem_result: Option<PathBuf>,
     use_colors: bool,
 ) -> GuardianResult<i32> {
     // Load configuration
-    let config = if let Some(config_path) = config_path {
-        GuardianConfig::load_from_file(config_path)?
-    } else {
-        // Try to find default config file
-        let default_configs = ["guardian.yaml", "guardian.yml", ".guardian.yaml"];
-        let mut config = None;
+    let mut config = match enforce_config {
+        Some(source) => load_enforced_config(&source)?,
+        None => load_config(config_path)?,
+    };
 
-        for config_name in &default_configs {
-            if Path::new(config_name).exists() {
-                config = Some(GuardianConfig::load_from_file(config_name)?);
-                break;
-            }
-        }
+    // A locked policy must run exactly as published
+    if config.locked {
+        policy::reject_overrides(&[
+            ("--severity", severity.is_some()),
+            ("--exclude", !exclude_patterns.is_empty()),
+            ("--no-ignore", no_ignore),
+            ("--no-gitignore", no_gitignore),
+        ])?;
+    }
 
-        config.unwrap_or_else(GuardianConfig::default)
+    if no_gitignore {
+        config.paths.use_gitignore = false;
+    }
+
+    // Command-line limits take precedence over the configured reporting defaults
+    let max_per_rule = max_per_rule.or(config.reporting.max_per_rule);
+    let max_per_file = max_per_file.or(config.reporting.max_per_file);
+    let csv_columns = if !csv_columns.is_empty() {
+        CsvColumn::parse_list(&csv_columns)?
+    } else if !config.reporting.csv_columns.is_empty() {
+        CsvColumn::parse_list(&config.reporting.csv_columns)?
+    } else {
+        CsvColumn::DEFAULT.to_vec()
     };
 
+    // The cache and reported paths belong to the project root, not the invocation directory
+    let root = config
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    let path_base = path_base.or_else(|| Some(root.clone()));
+    let scope = scope.map(|dir| Scope::new(&dir, &root)).transpose()?;
+    let notifications = config.notifications.clone();
+    let escalation = config.escalation.clone();
+    let issue_tracker = config.issues.clone();
+    let locked = config.locked;
+
     // Create validator
     let mut validator = GuardianValidator::new_with_config(config)?;
 
     // Enable cache if requested
     if use_cache {
         let cache_path =
-            cache_file.unwrap_or_else(|| PathBuf::from(".rust").join("guardian_cache.json"));
+            cache_file.unwrap_or_else(|| root.join(".rust").join("guardian_cache.json"));
         validator = validator.with_cache(cache_path)?;
     }
 
-    // Use current directory if no paths specified
-    let paths = if paths.is_empty() {
-        vec![PathBuf::from(".")]
-    } else {
+    // Use the scope, or else the current directory, if no paths specified
+    let paths = if !paths.is_empty() {
         paths
+    } else if let Some(scope) = &scope {
+        vec![scope.dir().to_path_buf()]
+    } else {
+        vec![PathBuf::from(".")]
     };
 
     // Set up validation options
@@ -317,8 +1039,14 @@ async fn run_check(
         output_format: format.into(),
         report_options: ReportOptions {
             use_colors,
+            explain,
+            verbose_violations,
             max_violations,
+            max_per_rule,
+            max_per_file,
             min_severity: severity.map(|s| s.into()),
+            path_base,
+            csv_columns,
             ..Default::default()
         },
         analysis_options: AnalysisOptions {
@@ -326,52 +1054,685 @@ async fn run_check(
             fail_fast,
             exclude_patterns,
             ignore_ignore_files: no_ignore,
+            max_duration: max_time,
+            expand_macros: expand,
+            rule_usage: stats,
             ..Default::default()
         },
         ..Default::default()
     };
 
-    // Run validation
-    let report = validator
-        .validate_with_options(paths, &validation_options)
-        .await?;
+    // Run validation, streaming per-file results ahead of the full report if requested
+    let mut report = if let Some(rev) = rev {
+        let revision = Revision::resolve(Path::new("."), &rev)?;
+        validator.validate_revision(&revision, &paths, &validation_options.analysis_options)?
+    } else if stream {
+        let min_severity = validation_options.report_options.min_severity;
+        validator.validate_streaming(
+            &paths,
+            &validation_options.analysis_options,
+            |_, violations| {
+                for violation in violations {
+                    if min_severity.is_none_or(|min| violation.severity >= min) {
+                        eprintln!("{}", violation.format_display());
+                    }
+                }
+            },
+        )?
+    } else {
+        validator
+            .validate_with_options(paths, &validation_options)
+            .await?
+    };
 
-    // Format and output results
-    let formatted = validator.format_report(&report, format.into())?;
-    println!("{formatted}");
+    // A scoped report only speaks for its scope, which it records for later merges
+    if let Some(scope) = &scope {
+        report.retain_violations(|violation| scope.contains(&violation.file_path));
+        report.scope = vec![scope.as_str().to_string()];
+    }
 
-    // Print cache statistics if caching is enabled
-    if use_cache {
-        if let Some(stats) = validator.cache_statistics() {
-            if format == OutputFormatArg::Human {
-                eprintln!("\n{}", stats.format_display());
-            }
+    // Violations that stay unresolved escalate with age, tracked in the violation history
+    if escalation.is_enabled() {
+        let history_path = root.join(
+            escalation
+                .history_file
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(".rust").join("guardian_history.json")),
+        );
+        let mut violation_history = ViolationHistory::load(&history_path)?;
+        let now = chrono::Utc::now();
+        violation_history.record(&report, &root, now);
+        let escalated = history::escalate(&mut report, &violation_history, &escalation, &root, now);
+        violation_history.save(&history_path)?;
+        if escalated > 0 {
+            eprintln!("⏫ Escalated {escalated} long-standing violation(s)");
         }
     }
 
-    // Save cache if enabled
-    if use_cache {
-        validator.save_cache()?;
+    // Triage labels hide false positives and lower won't-fix findings before issues are
+    // linked. They are local overrides, so a locked policy ignores them.
+    if !locked {
+        let triage_path = root.join(TRIAGE_FILE);
+        let triaged = triage::apply_triage(&mut report, &Triage::load(&triage_path)?, &root);
+        if triaged.false_positives + triaged.wont_fix > 0 {
+            eprintln!(
+                "🏷️  Triage: hid {} false positive(s), lowered {} won't-fix violation(s) to info",
+                triaged.false_positives, triaged.wont_fix
+            );
+        }
     }
 
-    // Return appropriate exit code
-    if report.has_errors() {
-        Ok(1) // Exit code 1 for errors
-    } else {
-        Ok(0) // Exit code 0 for success
+    // Tracked violations show their issue; tracker failures never change the check result
+    if issue_tracker.is_enabled() {
+        let links_path = root.join(
+            issue_tracker
+                .links_file
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(".rust").join("guardian_issues.json")),
+        );
+        let mut links = IssueLinks::load(&links_path)?;
+        if link_issues {
+            match issues::link_issues(&issue_tracker, &report, &mut links, &root) {
+                Ok(summary) if summary.found + summary.created > 0 => eprintln!(
+                    "🔗 Linked {} existing and {} new issue(s)",
+                    summary.found, summary.created
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!("⚠️  Failed to link issues: {e}"),
+            }
+            links.save(&links_path)?;
+        }
+        issues::attach_issue_urls(&issue_tracker, &mut report, &links, &root);
+    } else if link_issues {
+        eprintln!(
+            "⚠️  --link-issues needs `issues.provider` and `issues.rules` in the configuration"
+        );
     }
-}
 
-async fn run_watch(
-    path: Option<PathBuf>,
-    patterns: Vec<String>,
-    delay_ms: u64,
-) -> GuardianResult<i32> {
-    use notify::{Event, RecursiveMode, Result as NotifyResult, Watcher};
+    if fix {
+        let summary = rust_guardian::analyzer::fix::apply_fixes(&mut report)?;
+        if summary.applied > 0 {
+            eprintln!(
+                "🔧 Applied {} fix(es) in {} file(s)",
+                summary.applied,
+                summary.files.len()
+            );
+        }
+        if summary.skipped > 0 {
+            eprintln!(
+                "⚠️  Skipped {} overlapping fix(es); run again to apply them",
+                summary.skipped
+            );
+        }
+    }
+
+    // Format and output results with the requested report options
+    let formatter = ReportFormatter::new(validation_options.report_options.clone())?;
+    let mut formatted = formatter.format_report(&report, format.into())?;
+    if !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+    print!("{formatted}");
+
+    // Inside GitHub Actions the run is summarized on the job page, including any findings
+    // beyond GitHub's annotation limits, which would otherwise vanish from the run
+    let summary_path = std::env::var_os("GITHUB_STEP_SUMMARY")
+        .filter(|_| std::env::var_os("GITHUB_ACTIONS").is_some());
+    if let (Some(summary_path), Some(baseline_path)) = (summary_path, job_summary) {
+        let mut baseline = baseline_path.as_deref().map(Baseline::load).transpose()?;
+        if let Some(baseline) = &mut baseline {
+            // Findings in files this run could not see are out of scope, not fixed
+            let sparse = SparseCheckout::detect(&root);
+            let base = validation_options
+                .report_options
+                .path_base
+                .clone()
+                .unwrap_or_default();
+            baseline.retain_files(|file| {
+                let file = base.join(file);
+                !sparse.is_skipped(&file) && scope.as_ref().is_none_or(|s| s.contains(&file))
+            });
+        }
+        let mut summary = formatter.format_job_summary(&report, baseline.as_ref());
+        if format == OutputFormatArg::Github {
+            if let Some(overflow) = formatter.format_github_overflow(&report) {
+                summary.push('\n');
+                summary.push_str(&overflow);
+            }
+        }
+        append_step_summary(Path::new(&summary_path), &summary)?;
+    }
+
+    if timings {
+        let files = report.summary.total_files;
+        let elapsed_ms = report.summary.execution_time_ms;
+        eprintln!(
+            "⏱️  Timings: {elapsed_ms} ms for {files} file(s) ({:.1} ms/file)",
+            elapsed_ms as f64 / files.max(1) as f64
+        );
+        eprintln!("🧠 {}", MemoryUsage::current().format_display());
+    }
+
+    if stats {
+        eprint!("{}", formatter.format_rule_usage(&report));
+    }
+
+    // The human summary lists unanalyzed files itself
+    if report.is_partial() && format != OutputFormatArg::Human {
+        eprintln!(
+            "⏱️  Time budget exceeded: {} file(s) were not analyzed, report is partial",
+            report.summary.unanalyzed.len()
+        );
+    }
+
+    // The signature covers the report exactly as printed
+    if let Some((signature_path, key_path)) = signing {
+        let key = attestation::load_signing_key(key_path.as_deref())?;
+        let signature = ReportSignature::sign(formatted.as_bytes(), &key);
+        std::fs::write(&signature_path, signature.to_json()?)
+            .map_err(|e| GuardianError::io(&signature_path, e))?;
+    }
+
+    if let Some(result_path) = buildsystem_result {
+        formatter.build_result(&report).write(&result_path)?;
+    }
+
+    if let Some(metrics_path) = metrics_csv {
+        std::fs::write(&metrics_path, formatter.format_metrics_csv(&report))
+            .map_err(|e| GuardianError::io(&metrics_path, e))?;
+    }
+
+    if let Some(metrics_path) = metrics_file {
+        write_metrics_file(&metrics_path, &formatter.format_prometheus(&report))?;
+    }
+
+    // Delivery problems are reported but never change the check result
+    if send_notifications {
+        let project = std::fs::canonicalize(&root)
+            .ok()
+            .and_then(|root| {
+                root.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "project".to_string());
+        match rust_guardian::notify::send_notifications(&notifications, &report, &project) {
+            Ok(0) => {}
+            Ok(sent) => eprintln!("📣 Sent {sent} notification(s)"),
+            Err(e) => eprintln!("⚠️  Failed to send notifications: {e}"),
+        }
+    }
+
+    // Print cache statistics if caching is enabled
+    if use_cache {
+        if let Some(stats) = validator.cache_statistics() {
+            if format == OutputFormatArg::Human {
+                eprintln!("\n{}", stats.format_display());
+            }
+        }
+    }
+
+    // Save cache if enabled
+    if use_cache {
+        validator.save_cache()?;
+    }
+
+    // Return appropriate exit code
+    if report.has_errors() {
+        Ok(1) // Exit code 1 for errors
+    } else {
+        Ok(0) // Exit code 0 for success
+    }
+}
+
+/// End a failed `check`, writing an error result first when a build system expects one
+///
+/// Build actions must produce their declared outputs, and exit with 2 so a Guardian failure
+/// is not mistaken for violations.
+fn build_action_failure(result_path: Option<&Path>, error: GuardianError) -> GuardianResult<i32> {
+    let Some(result_path) = result_path else {
+        return Err(error);
+    };
+    eprintln!("Error: {error}");
+    let result = BuildResult::from_error(&error);
+    result.write(result_path)?;
+    Ok(result.exit_code)
+}
+
+/// Check commit hygiene, reporting like `check` so the same output formats apply
+///
+/// `branch` is `None` when the branch check is skipped, and `Some(None)` for the
+/// checked-out branch.
+fn run_check_commit(
+    config_path: Option<PathBuf>,
+    message_file: Option<PathBuf>,
+    message: Option<String>,
+    branch: Option<Option<String>>,
+    format: OutputFormatArg,
+    use_colors: bool,
+) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+    let repository = config
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let (message, source) = match (message, message_file) {
+        (Some(message), _) => (message, PathBuf::from("COMMIT_EDITMSG")),
+        (None, Some(path)) => (
+            std::fs::read_to_string(&path).map_err(|e| GuardianError::io(&path, e))?,
+            path,
+        ),
+        (None, None) => (
+            vcs_policy::last_commit_message(&repository)?,
+            PathBuf::from("HEAD"),
+        ),
+    };
+    let branch = match branch {
+        Some(Some(branch)) => Some(branch),
+        Some(None) => Some(vcs_policy::current_branch(&repository)?),
+        None => None,
+    };
+
+    let report = vcs_policy::check_commit(
+        &config.vcs_policy,
+        Some((&message, &source)),
+        branch.as_deref(),
+    )?;
+    let formatter = ReportFormatter::new(ReportOptions {
+        use_colors,
+        ..Default::default()
+    })?;
+    let mut formatted = formatter.format_report(&report, format.into())?;
+    if !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+    print!("{formatted}");
+
+    Ok(if report.has_errors() { 1 } else { 0 })
+}
+
+/// Check the ref updates of a push passed to a `pre-receive` hook on standard input
+fn run_pre_receive(
+    config_path: Option<PathBuf>,
+    max_time: std::time::Duration,
+    max_violations: usize,
+) -> GuardianResult<i32> {
+    use std::io::Read;
+
+    let config = load_config(config_path)?;
+    let repository = config
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| GuardianError::io("<stdin>", e))?;
+    let updates = receive::parse_ref_updates(&input)?;
+
+    let analyzer = rust_guardian::Analyzer::new(config)?;
+    let options = AnalysisOptions {
+        max_duration: Some(max_time),
+        recent_first: false,
+        ..Default::default()
+    };
+    let report = analyzer.analyze_push(&repository, &updates, &options)?;
+
+    if report.is_partial() {
+        println!(
+            "⏱️  Rust Guardian ran out of time; {} file(s) of this push were not checked",
+            report.summary.unanalyzed.len()
+        );
+    }
+    match receive::format_rejection(&report, max_violations) {
+        Some(rejection) => {
+            print!("{rejection}");
+            Ok(1)
+        }
+        None => Ok(0),
+    }
+}
+
+/// Analyze the project for triage, returning the report, project root and feedback settings
+fn analyze_for_triage(
+    config_path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+) -> GuardianResult<(ValidationReport, PathBuf, FeedbackConfig)> {
+    let config = load_config(config_path)?;
+    let feedback = config.feedback.clone();
+    let root = config
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    // Same default as `check`, so the fingerprints it printed are found as they are
+    let paths = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths
+    };
+    let analyzer = Analyzer::new(config)?;
+    let report = analyzer.analyze_paths(&paths, &AnalysisOptions::default())?;
+    Ok((report, root, feedback))
+}
+
+/// Feedback file of the project
+fn feedback_path(feedback: &FeedbackConfig, root: &Path) -> PathBuf {
+    root.join(
+        feedback
+            .file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".rust").join("guardian_feedback.json")),
+    )
+}
+
+/// Count a triage label against its rule when feedback is enabled
+fn record_feedback(
+    feedback: &FeedbackConfig,
+    root: &Path,
+    rule_id: &str,
+    label: TriageLabel,
+) -> GuardianResult<()> {
+    if !feedback.enabled {
+        return Ok(());
+    }
+    let path = feedback_path(feedback, root);
+    let mut log = FeedbackLog::load(&path)?;
+    log.record(rule_id, label);
+    log.save(&path)
+}
+
+/// Record or clear the triage label of one violation
+///
+/// The violation is looked up in a fresh analysis, so the label stores its rule and file,
+/// and a fingerprint from a report made with another path base still resolves.
+fn run_triage_label(
+    config_path: Option<PathBuf>,
+    fingerprint: &str,
+    label: Option<TriageLabel>,
+    note: Option<String>,
+    paths: Vec<PathBuf>,
+) -> GuardianResult<i32> {
+    let (report, root, feedback) = analyze_for_triage(config_path, paths)?;
+    let triage_path = root.join(TRIAGE_FILE);
+    let mut labels = Triage::load(&triage_path)?;
+
+    let found = report.violations.iter().find_map(|violation| {
+        let project = history::project_fingerprint(violation, &root);
+        (violation.fingerprint() == fingerprint || project == fingerprint)
+            .then_some((violation, project))
+    });
+
+    let Some(label) = label else {
+        // A label can be cleared after its violation is gone
+        let project = found.map_or_else(|| fingerprint.to_string(), |(_, project)| project);
+        if labels.remove(&project).is_none() {
+            eprintln!("No triage label recorded for {fingerprint}");
+            return Ok(1);
+        }
+        labels.save(&triage_path)?;
+        println!("🏷️  Cleared the triage label of {fingerprint}");
+        return Ok(0);
+    };
+
+    let Some((violation, project)) = found else {
+        eprintln!("No violation with fingerprint {fingerprint} was found");
+        return Ok(1);
+    };
+    labels.insert(project, TriageEntry::new(violation, &root, label, note));
+    labels.save(&triage_path)?;
+    record_feedback(&feedback, &root, &violation.rule_id, label)?;
+    println!(
+        "🏷️  Labeled {} {} as {label}",
+        violation.rule_id,
+        violation.file_path.display()
+    );
+    Ok(0)
+}
+
+/// Prompt for a label for every unlabeled violation, saving after each answer
+fn run_triage_interactive(
+    config_path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+) -> GuardianResult<i32> {
+    use std::io::{BufRead, Write};
+
+    let (report, root, feedback) = analyze_for_triage(config_path, paths)?;
+    let triage_path = root.join(TRIAGE_FILE);
+    let mut labels = Triage::load(&triage_path)?;
+    let pending: Vec<_> = report
+        .violations
+        .iter()
+        .map(|violation| (violation, history::project_fingerprint(violation, &root)))
+        .filter(|(_, fingerprint)| labels.get(fingerprint).is_none())
+        .collect();
+    if pending.is_empty() {
+        println!("✅ Every violation is triaged");
+        return Ok(0);
+    }
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut labeled = 0;
+    for (index, (violation, fingerprint)) in pending.iter().enumerate() {
+        println!(
+            "\n[{}/{}] {}",
+            index + 1,
+            pending.len(),
+            violation.format_display()
+        );
+        let label = loop {
+            print!("[a]ccepted, [f]alse positive, [w]on't fix, [s]kip, [q]uit > ");
+            std::io::stdout().flush().ok();
+            let Some(line) = lines.next() else {
+                break None;
+            };
+            match line.map_err(|e| GuardianError::io("<stdin>", e))?.trim() {
+                "a" => break Some(Some(TriageLabel::Accepted)),
+                "f" => break Some(Some(TriageLabel::FalsePositive)),
+                "w" => break Some(Some(TriageLabel::WontFix)),
+                "s" | "" => break Some(None),
+                "q" => break None,
+                _ => {}
+            }
+        };
+        match label {
+            Some(Some(label)) => {
+                labels.insert(
+                    fingerprint.clone(),
+                    TriageEntry::new(violation, &root, label, None),
+                );
+                labels.save(&triage_path)?;
+                record_feedback(&feedback, &root, &violation.rule_id, label)?;
+                labeled += 1;
+            }
+            Some(None) => {}
+            None => break,
+        }
+    }
+
+    println!(
+        "🏷️  Labeled {labeled} violation(s) in {}",
+        triage_path.display()
+    );
+    Ok(0)
+}
+
+/// Print how a proposed configuration would change a stored report and the CI outcome
+fn run_simulate(
+    config_path: Option<PathBuf>,
+    proposed_path: Option<PathBuf>,
+    overrides: Vec<String>,
+    report_path: Option<PathBuf>,
+    cache_file: Option<PathBuf>,
+    json: bool,
+) -> GuardianResult<i32> {
+    let current = load_config(config_path)?;
+    let root = current
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    let mut proposed = match proposed_path {
+        Some(path) => GuardianConfig::load_from_file(path)?,
+        None => current.clone(),
+    };
+    let overrides = overrides
+        .iter()
+        .map(|spec| simulation::parse_override(spec))
+        .collect::<GuardianResult<Vec<_>>>()?;
+    simulation::apply_overrides(&mut proposed, &overrides)?;
+
+    let report = match report_path {
+        Some(path) => simulation::load_json_report(&path)?,
+        None => {
+            let cache_path =
+                cache_file.unwrap_or_else(|| root.join(".rust").join("guardian_cache.json"));
+            if !cache_path.exists() {
+                return Err(GuardianError::config(format!(
+                    "No analysis cache at '{}'; run `check --cache` or pass --report",
+                    cache_path.display()
+                )));
+            }
+            let mut cache = rust_guardian::FileCache::new(&cache_path);
+            cache.load()?;
+            let (violations, files) = cache.violations();
+            let mut report = ValidationReport::new();
+            for violation in violations {
+                report.add_violation(violation);
+            }
+            report.summary.total_files = files;
+            report
+        }
+    };
+
+    let result = simulation::simulate(&report, &current, &proposed);
+    if json {
+        let output = serde_json::to_string_pretty(&result)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize simulation: {e}")))?;
+        println!("{output}");
+    } else {
+        print!("{}", result.format_display());
+    }
+    Ok(0)
+}
+
+fn run_feedback_command(
+    action: FeedbackCommands,
+    config_path: Option<PathBuf>,
+) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+    let root = config
+        .paths
+        .root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    let log = FeedbackLog::load(&feedback_path(&config.feedback, &root))?;
+    if !config.feedback.enabled && log.rules.is_empty() {
+        eprintln!("Feedback is off; set `feedback.enabled: true` to count triage labels per rule");
+        return Ok(0);
+    }
+
+    match action {
+        FeedbackCommands::Show => {
+            if log.rules.is_empty() {
+                println!("No triage labels were counted yet");
+                return Ok(0);
+            }
+            let width = log
+                .rules
+                .keys()
+                .map(String::len)
+                .max()
+                .unwrap_or(0)
+                .max("rule".len());
+            println!(
+                "{:<width$}  {:>8}  {:>14}  {:>8}  {:>7}",
+                "rule", "accepted", "false_positive", "wont_fix", "fp rate"
+            );
+            let mut rules: Vec<_> = log.rules.iter().collect();
+            rules.sort_by(|a, b| {
+                b.1.false_positive_rate()
+                    .total_cmp(&a.1.false_positive_rate())
+                    .then_with(|| a.0.cmp(b.0))
+            });
+            for (rule, labels) in rules {
+                println!(
+                    "{:<width$}  {:>8}  {:>14}  {:>8}  {:>6.0}%",
+                    rule,
+                    labels.accepted,
+                    labels.false_positive,
+                    labels.wont_fix,
+                    labels.false_positive_rate() * 100.0
+                );
+            }
+        }
+        FeedbackCommands::Export { output } => {
+            let json = serde_json::to_string_pretty(&log.export())
+                .map_err(|e| GuardianError::config(format!("Failed to serialize feedback: {e}")))?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, format!("{json}\n"))
+                        .map_err(|e| GuardianError::io(&path, e))?;
+                    eprintln!("📤 Feedback summary written to {}", path.display());
+                }
+                None => println!("{json}"),
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// List how many violations of each rule carry each triage label
+fn run_triage_list(config_path: Option<PathBuf>) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+    let root = config.paths.root.unwrap_or_else(|| PathBuf::from("."));
+    let labels = Triage::load(&root.join(TRIAGE_FILE))?;
+    if labels.is_empty() {
+        println!("No violations are triaged yet");
+        return Ok(0);
+    }
+
+    let counts = labels.counts_by_rule();
+    let width = counts
+        .keys()
+        .map(|rule| rule.len())
+        .max()
+        .unwrap_or(0)
+        .max("rule".len());
+    println!(
+        "{:<width$}  {:>8}  {:>14}  {:>8}",
+        "rule", "accepted", "false_positive", "wont_fix"
+    );
+    for (rule, by_label) in &counts {
+        let count = |label| by_label.get(&label).copied().unwrap_or(0);
+        println!(
+            "{:<width$}  {:>8}  {:>14}  {:>8}",
+            rule,
+            count(TriageLabel::Accepted),
+            count(TriageLabel::FalsePositive),
+            count(TriageLabel::WontFix)
+        );
+    }
+    Ok(0)
+}
+
+async fn run_watch(
+    path: Option<PathBuf>,
+    patterns: Vec<String>,
+    delay_ms: u64,
+    hooks: WatchHooks,
+    metrics: MetricsExporter,
+    cache_file: &Path,
+) -> GuardianResult<i32> {
+    use notify::{Event, RecursiveMode, Result as NotifyResult, Watcher};
+    use std::collections::BTreeSet;
     use std::io::{self, Write};
     use std::sync::mpsc;
     use std::thread;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     let watch_path = path.unwrap_or_else(|| PathBuf::from("."));
 
@@ -414,20 +1775,45 @@ async fn run_watch(
             ))
         })?;
 
-    // Track last run to implement debouncing
-    let mut last_run = std::time::Instant::now();
+    // Changes are coalesced: every path touched within the debounce window is
+    // re-analyzed together once the window closes
     let debounce_duration = Duration::from_millis(delay_ms);
+    let mut pending_paths: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut batch_deadline: Option<Instant> = None;
+
+    // Event paths are absolute; map them back under the watch path before filtering
+    let watch_root = watch_path
+        .canonicalize()
+        .unwrap_or_else(|_| watch_path.clone());
+    let cache_file = event_path(cache_file);
+
+    // Track the previous outcome so notifications only fire on status changes
+    let mut last_passed = None;
+    let mut state = WatchState::default();
 
     // Run initial check
     println!("🚀 Running initial analysis...");
-    run_watch_analysis_with_config(&watch_path, &watch_patterns, None).await?;
+    // Files unchanged between passes keep their syntax trees, even across config reloads
+    let validator = GuardianValidator::new_with_config(load_config(None)?)?
+        .with_syntax_tree_cache(SyntaxTreeCache::default())?;
+    let embedded_policy = !EMBEDDED_CONFIG.trim().is_empty();
+    let locked_config = std::env::current_dir().ok().and_then(|cwd| {
+        let root = find_project_root(&cwd).unwrap_or_else(|| cwd.clone());
+        find_locked_config_file(&cwd, &root)
+    });
+    let outcome = run_watch_analysis(&watch_path, None, &validator, &mut state, &metrics).await?;
+    hooks.after_analysis(&outcome, last_passed).await;
+    last_passed = Some(outcome.passed);
 
     // Main event loop
     loop {
         match rx.recv_timeout(Duration::from_millis(100)) {
             Ok(event) => {
-                // Check for config file changes first
-                if let Some(config_path) = is_config_change(&event) {
+                // Check for config file changes first; an embedded policy ignores config
+                // files and a locked one ignores every config file but itself
+                if let Some(config_path) = is_config_change(&event).filter(|path| {
+                    !embedded_policy && locked_config.as_ref().is_none_or(|locked| locked == path)
+                }) {
                     println!("🔄 Configuration file changed: {}", config_path.display());
                     println!("📝 Reloading configuration and running analysis...");
 
@@ -435,38 +1821,44 @@ async fn run_watch(
                     print!("\\x1B[2J\\x1B[H"); // Clear screen and move cursor to top
                     io::stdout().flush().unwrap();
 
-                    if let Err(e) = run_watch_analysis_with_config(
-                        &watch_path,
-                        &watch_patterns,
-                        Some(&config_path),
-                    )
-                    .await
+                    // Keep the previous rules if the new configuration cannot be compiled
+                    if let Err(e) = validator.reload_config(reload_watch_config(&config_path)) {
+                        eprintln!("⚠️  Keeping previous configuration: {e}");
+                    }
+
+                    // A full pass supersedes any batch still being collected
+                    pending_paths.clear();
+                    batch_deadline = None;
+
+                    match run_watch_analysis(&watch_path, None, &validator, &mut state, &metrics)
+                        .await
                     {
-                        eprintln!("❌ Config reload and analysis failed: {e}");
+                        Ok(outcome) => {
+                            hooks.after_analysis(&outcome, last_passed).await;
+                            last_passed = Some(outcome.passed);
+                        }
+                        Err(e) => eprintln!("❌ Config reload and analysis failed: {e}"),
                     }
-                    last_run = std::time::Instant::now();
                 }
-                // Otherwise check for regular file changes
-                else if should_trigger_analysis(&event, &watch_patterns) {
-                    let now = std::time::Instant::now();
-                    if now.duration_since(last_run) >= debounce_duration {
-                        // Clear terminal and run analysis
-                        print!("\\x1B[2J\\x1B[H"); // Clear screen and move cursor to top
-                        io::stdout().flush().unwrap();
-
-                        println!("📝 File changes detected, running analysis...");
-                        if let Err(e) =
-                            run_watch_analysis_with_config(&watch_path, &watch_patterns, None).await
-                        {
-                            eprintln!("❌ Analysis failed: {e}");
-                        }
-                        last_run = now;
+                // Otherwise collect regular file changes into the current batch
+                else {
+                    let changed = changed_analysis_paths(
+                        &event,
+                        &watch_patterns,
+                        &validator.path_filter(),
+                        &watch_path,
+                        &watch_root,
+                        &cache_file,
+                    );
+
+                    if !changed.is_empty() {
+                        pending_paths.extend(changed);
+                        batch_deadline.get_or_insert_with(|| Instant::now() + debounce_duration);
                     }
                 }
             }
             Err(mpsc::RecvTimeoutError::Timeout) => {
-                // No events - continue watching
-                continue;
+                // No events - fall through to flush an expired batch
             }
             Err(mpsc::RecvTimeoutError::Disconnected) => {
                 eprintln!("File watcher disconnected");
@@ -474,6 +1866,31 @@ async fn run_watch(
             }
         }
 
+        // Re-analyze the batch once its debounce window has closed
+        if matches!(batch_deadline, Some(deadline) if Instant::now() >= deadline) {
+            let batch: Vec<PathBuf> = std::mem::take(&mut pending_paths).into_iter().collect();
+            batch_deadline = None;
+
+            // Clear terminal and run analysis
+            print!("\\x1B[2J\\x1B[H"); // Clear screen and move cursor to top
+            io::stdout().flush().unwrap();
+
+            println!(
+                "📝 {} changed file{}, running analysis...",
+                batch.len(),
+                if batch.len() == 1 { "" } else { "s" }
+            );
+            match run_watch_analysis(&watch_path, Some(&batch), &validator, &mut state, &metrics)
+                .await
+            {
+                Ok(outcome) => {
+                    hooks.after_analysis(&outcome, last_passed).await;
+                    last_passed = Some(outcome.passed);
+                }
+                Err(e) => eprintln!("❌ Analysis failed: {e}"),
+            }
+        }
+
         // Small delay to prevent excessive CPU usage
         thread::sleep(Duration::from_millis(10));
     }
@@ -481,30 +1898,207 @@ async fn run_watch(
     Ok(0)
 }
 
-/// Check if an event should trigger analysis or config reload
-fn should_trigger_analysis(event: &notify::Event, patterns: &[String]) -> bool {
+/// Commands and notifications triggered after each watch-mode analysis
+#[derive(Debug, Default)]
+struct WatchHooks {
+    /// Shell command to run when an analysis finds no errors
+    on_success: Option<String>,
+    /// Shell command to run when an analysis finds errors or cannot complete
+    on_failure: Option<String>,
+    /// Whether to show desktop notifications on status changes
+    notify: bool,
+}
+
+/// Result of a single watch-mode analysis
+#[derive(Debug, Clone, Copy)]
+struct WatchOutcome {
+    /// Whether the analysis completed without blocking violations
+    passed: bool,
+    /// Number of errors found
+    errors: usize,
+    /// Number of warnings found
+    warnings: usize,
+}
+
+impl WatchOutcome {
+    /// Outcome for an analysis that could not complete
+    fn failed() -> Self {
+        Self {
+            passed: false,
+            errors: 0,
+            warnings: 0,
+        }
+    }
+}
+
+impl WatchHooks {
+    /// Run the configured command and notification for an analysis outcome
+    ///
+    /// Commands run to completion before watching resumes; events arriving meanwhile are queued.
+    /// They run on the blocking thread pool, so a slow command never stalls a runtime worker.
+    async fn after_analysis(&self, outcome: &WatchOutcome, previous_passed: Option<bool>) {
+        let command = if outcome.passed {
+            self.on_success.clone()
+        } else {
+            self.on_failure.clone()
+        };
+
+        // Notify on regressions and recoveries, not on every save
+        let status_changed = match previous_passed {
+            Some(previous) => previous != outcome.passed,
+            None => !outcome.passed,
+        };
+        let notification = (self.notify && status_changed).then(|| {
+            if outcome.passed {
+                "All checks pass".to_string()
+            } else {
+                format!(
+                    "{} error(s), {} warning(s)",
+                    outcome.errors, outcome.warnings
+                )
+            }
+        });
+
+        let outcome = *outcome;
+        let hooks = tokio::task::spawn_blocking(move || {
+            if let Some(command) = command {
+                run_hook_command(&command, &outcome);
+            }
+            if let Some(body) = notification {
+                send_desktop_notification("Rust Guardian", &body);
+            }
+        });
+        if let Err(e) = hooks.await {
+            eprintln!("⚠️  Watch hooks failed: {e}");
+        }
+    }
+}
+
+/// Run a user-supplied hook command through the platform shell
+///
+/// The outcome is exposed to the command via `GUARDIAN_STATUS`, `GUARDIAN_ERRORS`,
+/// and `GUARDIAN_WARNINGS` environment variables.
+fn run_hook_command(command: &str, outcome: &WatchOutcome) {
+    let mut shell = if cfg!(windows) {
+        let mut shell = process::Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = process::Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    };
+
+    println!("▶️  Running: {command}");
+    let status = shell
+        .env(
+            "GUARDIAN_STATUS",
+            if outcome.passed { "pass" } else { "fail" },
+        )
+        .env("GUARDIAN_ERRORS", outcome.errors.to_string())
+        .env("GUARDIAN_WARNINGS", outcome.warnings.to_string())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("⚠️  Command '{command}' exited with {status}"),
+        Err(e) => eprintln!("⚠️  Failed to run command '{command}': {e}"),
+    }
+}
+
+/// Show a desktop notification via `osascript` on macOS or `notify-send` elsewhere
+fn send_desktop_notification(title: &str, body: &str) {
+    let result = if cfg!(target_os = "macos") {
+        process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification {body:?} with title {title:?}"
+            ))
+            .status()
+    } else if cfg!(windows) {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "desktop notifications are not supported on Windows",
+        ))
+    } else {
+        process::Command::new("notify-send")
+            .args([title, body])
+            .status()
+    };
+
+    if let Err(e) = result {
+        tracing::debug!("Desktop notification unavailable: {}", e);
+    }
+}
+
+/// Absolute path of a file as file system events report it, even if it does not exist yet
+fn event_path(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    match (absolute.parent(), absolute.file_name()) {
+        (Some(dir), Some(name)) => dir
+            .canonicalize()
+            .map(|dir| dir.join(name))
+            .unwrap_or(absolute),
+        _ => absolute,
+    }
+}
+
+/// Collect the paths of an event that should be re-analyzed
+///
+/// Only paths that analysis would pick up count: the same `PathFilter` (including
+/// `.guardianignore` files and pruned directories like `target/`) is applied, and
+/// writes to `cache_file` are ignored so they cannot cause feedback loops. Paths are
+/// returned as file discovery from the watch path would report them.
+fn changed_analysis_paths(
+    event: &notify::Event,
+    patterns: &[String],
+    path_filter: &PathFilter,
+    watch_path: &Path,
+    watch_root: &Path,
+    cache_file: &Path,
+) -> Vec<PathBuf> {
     use notify::EventKind;
 
+    let mut changed = Vec::new();
+
     // Only trigger on write/create/rename events
     match event.kind {
         EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {}
-        _ => return false,
+        _ => return changed,
     }
 
     // Check if any affected path matches our patterns
     for path in &event.paths {
-        let path_str = path.to_string_lossy();
+        if path == cache_file {
+            continue;
+        }
 
-        for pattern in patterns {
-            if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
-                if glob_pattern.matches(&path_str) {
-                    return true;
-                }
-            }
+        // Express the path the way file discovery from the watch path would see it
+        let discovered_path = path
+            .strip_prefix(watch_root)
+            .map(|relative| watch_path.join(relative))
+            .unwrap_or_else(|_| path.clone());
+
+        if !path_filter
+            .would_discover(&discovered_path)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let path_str = path.to_string_lossy();
+        let matches_pattern = patterns.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .map(|glob_pattern| glob_pattern.matches(&path_str))
+                .unwrap_or(false)
+        });
+
+        if matches_pattern {
+            changed.push(discovered_path);
         }
     }
 
-    false
+    changed
 }
 
 /// Check if an event indicates a config file change
@@ -533,55 +2127,101 @@ fn is_config_change(event: &notify::Event) -> Option<PathBuf> {
     None
 }
 
-/// Run analysis for watch mode with optional config file
-async fn run_watch_analysis_with_config(
-    watch_path: &Path,
-    _patterns: &[String],
-    config_path: Option<&Path>,
-) -> GuardianResult<()> {
-    // Load configuration
-    let config = if let Some(config_path) = config_path {
-        match GuardianConfig::load_from_file(config_path) {
-            Ok(config) => {
-                println!("✅ Configuration reloaded from: {}", config_path.display());
-                config
-            }
-            Err(e) => {
-                eprintln!(
-                    "⚠️  Failed to reload config from {}: {}",
-                    config_path.display(),
-                    e
-                );
-                eprintln!("   Using default configuration instead...");
-                GuardianConfig::default()
-            }
+/// Configuration file that applies at `cwd`
+///
+/// A locked config anywhere up to the project root wins over nested config files.
+fn discover_effective_config(cwd: &Path, root: &Path) -> Option<PathBuf> {
+    find_locked_config_file(cwd, root).or_else(|| find_config_file(cwd, root))
+}
+
+/// Load a policy passed to `--enforce-config`, anchored at the project root
+fn load_enforced_config(source: &str) -> GuardianResult<GuardianConfig> {
+    let cwd = std::env::current_dir()?;
+    let mut config = GuardianConfig::load_enforced(source)?;
+    config.paths.root = Some(find_project_root(&cwd).unwrap_or(cwd));
+    Ok(config)
+}
+
+/// Reload the configuration for watch mode after a config file changed, falling back to defaults
+fn reload_watch_config(config_path: &Path) -> GuardianConfig {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let root = find_project_root(&cwd).unwrap_or(cwd);
+
+    let mut config = match GuardianConfig::load_from_file(config_path) {
+        Ok(config) => {
+            println!("✅ Configuration reloaded from: {}", config_path.display());
+            config
         }
-    } else {
-        // Try to find default config file
-        let default_configs = ["guardian.yaml", "guardian.yml", ".guardian.yaml"];
-        let mut config = None;
-
-        for config_name in &default_configs {
-            if Path::new(config_name).exists() {
-                match GuardianConfig::load_from_file(config_name) {
-                    Ok(loaded_config) => {
-                        config = Some(loaded_config);
-                        break;
-                    }
-                    Err(e) => {
-                        eprintln!("⚠️  Failed to load config from {config_name}: {e}");
-                        continue;
-                    }
-                }
-            }
+        Err(e) => {
+            eprintln!(
+                "⚠️  Failed to reload config from {}: {}",
+                config_path.display(),
+                e
+            );
+            eprintln!("   Using default configuration instead...");
+            GuardianConfig::default()
         }
+    };
+
+    config.paths.root = Some(root);
+    config
+}
 
-        config.unwrap_or_else(GuardianConfig::default)
+/// Default cache location at the project root
+fn default_cache_path() -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    find_project_root(&cwd)
+        .unwrap_or(cwd)
+        .join(".rust")
+        .join("guardian_cache.json")
+}
+
+/// Load configuration for a command run from anywhere inside a project
+fn load_config(config_path: Option<PathBuf>) -> GuardianResult<GuardianConfig> {
+    let cwd = std::env::current_dir()?;
+    let root = find_project_root(&cwd).unwrap_or_else(|| cwd.clone());
+    resolve_config(config_path.as_deref(), EMBEDDED_CONFIG, &cwd, &root)
+}
+
+/// Pick the configuration that applies at `cwd`
+///
+/// An explicit path or URL wins, then the `embedded` policy; otherwise the closest config
+/// file between `cwd` and `root` is used, falling back to defaults. An invalid embedded
+/// policy is an error rather than a reason to fall through. Path patterns are anchored to
+/// `root` either way.
+fn resolve_config(
+    config_path: Option<&Path>,
+    embedded: &str,
+    cwd: &Path,
+    root: &Path,
+) -> GuardianResult<GuardianConfig> {
+    let mut config = match config_path {
+        Some(config_file) => GuardianConfig::load_from_source(config_file)?,
+        None => match GuardianConfig::from_embedded(embedded)? {
+            Some(config) => config,
+            None => match discover_effective_config(cwd, root) {
+                Some(config_file) => GuardianConfig::load_from_file(config_file)?,
+                None => GuardianConfig::default(),
+            },
+        },
     };
+    config.paths.root = Some(root.to_path_buf());
 
-    // Create validator
-    let mut validator = GuardianValidator::new_with_config(config)?;
+    Ok(config)
+}
 
+/// Run a watch-mode analysis and print the results
+///
+/// With `changed_files`, only that batch is re-analyzed and stored results for every other
+/// file are kept; otherwise the whole watch path is analyzed. Either way the violations
+/// introduced and fixed since the previous pass are reported.
+async fn run_watch_analysis(
+    watch_path: &Path,
+    changed_files: Option<&[PathBuf]>,
+    validator: &GuardianValidator,
+    state: &mut WatchState,
+    metrics: &MetricsExporter,
+) -> GuardianResult<WatchOutcome> {
     // Set up validation options for watch mode
     let validation_options = ValidationOptions {
         analysis_options: AnalysisOptions {
@@ -599,68 +2239,490 @@ async fn run_watch_analysis_with_config(
         ..Default::default()
     };
 
+    // Deleted files are not analyzed; their stored violations are dropped below
+    let paths: Vec<PathBuf> = match changed_files {
+        Some(files) => files.iter().filter(|f| f.is_file()).cloned().collect(),
+        None => vec![watch_path.to_path_buf()],
+    };
+
     // Run validation
-    match validator
-        .validate_with_options(vec![watch_path], &validation_options)
-        .await
-    {
-        Ok(report) => {
-            if report.has_violations() {
-                let formatted = validator.format_report(&report, OutputFormat::Human)?;
-                println!("{formatted}");
-
-                let error_count = report.summary.violations_by_severity.error;
-                let warning_count = report.summary.violations_by_severity.warning;
-                let info_count = report.summary.violations_by_severity.info;
-
-                if error_count > 0 {
-                    println!(
-                        "\\n❌ Found {} error{}, {} warning{}, {} info",
-                        error_count,
-                        if error_count == 1 { "" } else { "s" },
-                        warning_count,
-                        if warning_count == 1 { "" } else { "s" },
-                        info_count
-                    );
-                } else if warning_count > 0 {
-                    println!(
-                        "\\n⚠️  Found {} warning{}, {} info",
-                        warning_count,
-                        if warning_count == 1 { "" } else { "s" },
-                        info_count
-                    );
-                } else {
-                    println!(
-                        "\\n✅ Found {} info message{}",
-                        info_count,
-                        if info_count == 1 { "" } else { "s" }
-                    );
+    let report = if paths.is_empty() {
+        ValidationReport::new()
+    } else {
+        match validator
+            .validate_with_options(paths, &validation_options)
+            .await
+        {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("❌ Analysis error: {e}");
+                return Ok(WatchOutcome::failed());
+            }
+        }
+    };
+
+    if report.has_violations() {
+        let formatted = validator.format_report(&report, OutputFormat::Human)?;
+        println!("{formatted}");
+    }
+
+    let delta = state.apply(changed_files, &report);
+    if let Err(e) = metrics.publish(&state.snapshot(&report)) {
+        eprintln!("⚠️  Failed to export metrics: {e}");
+    }
+    if !delta.introduced.is_empty() || !delta.fixed.is_empty() {
+        println!(
+            "🆕 {} new, ✅ {} fixed since the previous pass",
+            delta.introduced.len(),
+            delta.fixed.len()
+        );
+        for violation in &delta.introduced {
+            println!("  + {}", violation.format_display());
+        }
+        for violation in &delta.fixed {
+            println!("  - {}", violation.format_display());
+        }
+    }
+
+    let counts = state.counts();
+    let error_count = counts.error;
+    let warning_count = counts.warning;
+    let info_count = counts.info;
+
+    if error_count > 0 {
+        println!(
+            "\\n❌ Found {} error{}, {} warning{}, {} info",
+            error_count,
+            if error_count == 1 { "" } else { "s" },
+            warning_count,
+            if warning_count == 1 { "" } else { "s" },
+            info_count
+        );
+    } else if warning_count > 0 {
+        println!(
+            "\\n⚠️  Found {} warning{}, {} info",
+            warning_count,
+            if warning_count == 1 { "" } else { "s" },
+            info_count
+        );
+    } else if info_count > 0 {
+        println!(
+            "\\n✅ Found {} info message{}",
+            info_count,
+            if info_count == 1 { "" } else { "s" }
+        );
+    } else {
+        println!("✅ No code quality violations found");
+    }
+
+    println!(
+        "📊 Analyzed {} files in {:.1}s",
+        report.summary.total_files,
+        report.summary.execution_time_ms as f64 / 1000.0
+    );
+    println!("⌚ Watching for changes... (Press Ctrl+C to stop)\\n");
+
+    Ok(WatchOutcome {
+        passed: !counts.has_blocking(),
+        errors: counts.error,
+        warnings: counts.warning,
+    })
+}
+
+/// Latest violations for every watched file, kept between passes to report deltas
+#[derive(Debug, Default)]
+struct WatchState {
+    violations_by_file: HashMap<PathBuf, Vec<Violation>>,
+}
+
+/// Violations that appeared or disappeared between two watch passes
+#[derive(Debug, Default)]
+struct WatchDelta {
+    introduced: Vec<Violation>,
+    fixed: Vec<Violation>,
+}
+
+impl WatchState {
+    /// Store the results of a pass and return what changed for the analyzed files
+    ///
+    /// `analyzed_files` of `None` means the report covers the whole watch path.
+    fn apply(
+        &mut self,
+        analyzed_files: Option<&[PathBuf]>,
+        report: &ValidationReport,
+    ) -> WatchDelta {
+        let mut current: HashMap<PathBuf, Vec<Violation>> = HashMap::new();
+        for violation in &report.violations {
+            current
+                .entry(violation.file_path.clone())
+                .or_default()
+                .push(violation.clone());
+        }
+
+        let previous = match analyzed_files {
+            Some(files) => {
+                let mut previous = HashMap::new();
+                for file in files {
+                    if let Some(violations) = self.violations_by_file.remove(file) {
+                        previous.insert(file.clone(), violations);
+                    }
                 }
+                previous
+            }
+            None => std::mem::take(&mut self.violations_by_file),
+        };
+
+        let mut delta = WatchDelta::default();
+        for (file, violations) in &current {
+            let before = previous.get(file).map(Vec::as_slice).unwrap_or_default();
+            delta
+                .introduced
+                .extend(violations_missing_from(violations, before));
+        }
+        for (file, violations) in &previous {
+            let after = current.get(file).map(Vec::as_slice).unwrap_or_default();
+            delta
+                .fixed
+                .extend(violations_missing_from(violations, after));
+        }
+
+        self.violations_by_file.extend(current);
+        delta
+    }
+
+    /// Violation counts across every watched file
+    fn counts(&self) -> ViolationCounts {
+        let mut counts = ViolationCounts::default();
+        for violation in self.violations_by_file.values().flatten() {
+            counts.add(violation.severity);
+        }
+        counts
+    }
+
+    /// A report of every stored violation, with run statistics from the latest pass
+    fn snapshot(&self, last_pass: &ValidationReport) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for violation in self.violations_by_file.values().flatten() {
+            report.add_violation(violation.clone());
+        }
+        report.set_files_analyzed(last_pass.summary.total_files);
+        report.set_execution_time(last_pass.summary.execution_time_ms);
+        if let Some(version) = &last_pass.rule_pack_version {
+            report.set_rule_pack_version(version.clone());
+        }
+        if let Some(hash) = &last_pass.policy_hash {
+            report.set_policy_hash(hash.clone());
+        }
+        report.sort_violations();
+        report
+    }
+}
+
+/// Destinations for Prometheus metrics published after each watch-mode pass
+#[derive(Debug, Default)]
+struct MetricsExporter {
+    /// Textfile-collector file rewritten after each pass
+    file: Option<PathBuf>,
+    /// Latest exposition, served on `/metrics` when an address is configured
+    served: Option<Arc<Mutex<String>>>,
+}
+
+impl MetricsExporter {
+    /// Create an exporter, starting the `/metrics` listener when an address is given
+    fn new(file: Option<PathBuf>, addr: Option<String>) -> GuardianResult<Self> {
+        let served = addr.map(|addr| serve_metrics(&addr)).transpose()?;
+        Ok(Self { file, served })
+    }
+
+    /// Publish the metrics of a report to every configured destination
+    fn publish(&self, report: &ValidationReport) -> GuardianResult<()> {
+        if self.file.is_none() && self.served.is_none() {
+            return Ok(());
+        }
+
+        let exposition = ReportFormatter::default().format_prometheus(report);
+        if let Some(path) = &self.file {
+            write_metrics_file(path, &exposition)?;
+        }
+        if let Some(served) = &self.served {
+            *served.lock().unwrap_or_else(PoisonError::into_inner) = exposition;
+        }
+        Ok(())
+    }
+}
+
+/// Append markdown to the GitHub Actions job summary, which all steps of a job share
+fn append_step_summary(path: &Path, markdown: &str) -> GuardianResult<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| GuardianError::io(path, e))?;
+    writeln!(file, "{markdown}").map_err(|e| GuardianError::io(path, e))
+}
+
+/// Write a metrics file atomically so collectors never read a partial file
+///
+/// The temporary file keeps a `.tmp` suffix, which the textfile collector ignores.
+fn write_metrics_file(path: &Path, exposition: &str) -> GuardianResult<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    std::fs::write(&temp_path, exposition).map_err(|e| GuardianError::io(&temp_path, e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| GuardianError::io(path, e))
+}
+
+/// Serve the latest exposition on `GET /metrics` from a background thread
+fn serve_metrics(addr: &str) -> GuardianResult<Arc<Mutex<String>>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    let listener = TcpListener::bind(addr)
+        .map_err(|e| GuardianError::config(format!("Failed to listen on {addr}: {e}")))?;
+    println!("📈 Serving metrics on http://{addr}/metrics");
+
+    let exposition = Arc::new(Mutex::new(String::new()));
+    let shared = Arc::clone(&exposition);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = stream.set_read_timeout(Some(Duration::from_secs(5))) {
+                tracing::debug!("Failed to configure metrics connection: {}", e);
+                continue;
+            }
+
+            // Read the request line, then drain headers up to the blank line
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                header.clear();
+            }
+
+            let response = if request_line.starts_with("GET /metrics ") {
+                let body = shared
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
             } else {
-                println!("✅ No code quality violations found");
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            if let Err(e) = (&stream).write_all(response.as_bytes()) {
+                tracing::debug!("Failed to answer metrics request: {}", e);
             }
+        }
+    });
+
+    Ok(exposition)
+}
+
+/// Violations in `violations` without a matching entry in `others`
+///
+/// Violations are matched by rule, message, and source context rather than position so
+/// that edits shifting lines do not show up as fixed and reintroduced.
+fn violations_missing_from(violations: &[Violation], others: &[Violation]) -> Vec<Violation> {
+    let key = |v: &Violation| (v.rule_id.clone(), v.message.clone(), v.context.clone());
+
+    let mut remaining: HashMap<_, usize> = HashMap::new();
+    for other in others {
+        *remaining.entry(key(other)).or_default() += 1;
+    }
+
+    violations
+        .iter()
+        .filter(|&v| match remaining.get_mut(&key(v)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
+
+fn run_graph(
+    config_path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+    format: GraphFormatArg,
+    output: Option<PathBuf>,
+) -> GuardianResult<i32> {
+    // Load configuration
+    let config = load_config(config_path)?;
+
+    // Discover files with the same filtering as `check`
+    let validator = GuardianValidator::new_with_config(config)?;
+    let paths = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths
+    };
+
+    let mut files = Vec::new();
+    for path in &paths {
+        if path.is_file() {
+            files.push(path.clone());
+        } else if path.is_dir() {
+            files.extend(validator.path_filter().find_files(path)?);
+        }
+    }
+
+    let graph = ModuleGraph::from_index(&ImportIndex::build(&files)?);
+    let rendered = match format {
+        GraphFormatArg::Dot => graph.to_dot(),
+        GraphFormatArg::Json => graph.to_json()?,
+    };
+
+    emit_graph("Module", rendered, output, &graph.cycle_violations())
+}
+
+fn run_crate_graph(
+    manifest_path: PathBuf,
+    format: GraphFormatArg,
+    output: Option<PathBuf>,
+) -> GuardianResult<i32> {
+    let graph = CrateGraph::from_manifest(&manifest_path)?;
+    let rendered = match format {
+        GraphFormatArg::Dot => graph.to_dot(),
+        GraphFormatArg::Json => graph.to_json()?,
+    };
+
+    emit_graph("Crate", rendered, output, &graph.cycle_violations())
+}
+
+/// Write a rendered graph and report its cycles, failing when any exist
+fn emit_graph(
+    label: &str,
+    rendered: String,
+    output: Option<PathBuf>,
+    violations: &[Violation],
+) -> GuardianResult<i32> {
+    match output {
+        Some(output_path) => {
+            std::fs::write(&output_path, rendered)?;
+            eprintln!("📈 {label} graph written to {}", output_path.display());
+        }
+        None => print!("{rendered}"),
+    }
+
+    // Cycles are reported as violations and fail the command
+    for violation in violations {
+        eprintln!("{}", violation.format_display());
+    }
+
+    Ok(if violations.is_empty() { 0 } else { 1 })
+}
+
+/// Closest configuration file between the working directory and the project root
+fn discover_config_file() -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let root = find_project_root(&cwd).unwrap_or_else(|| cwd.clone());
+    find_config_file(&cwd, &root).unwrap_or_else(|| PathBuf::from("guardian.yaml"))
+}
+
+fn run_verify_report(
+    report_path: &Path,
+    signature_path: Option<PathBuf>,
+    public_key: &str,
+) -> GuardianResult<i32> {
+    let signature_path = signature_path.unwrap_or_else(|| {
+        let mut path = report_path.as_os_str().to_owned();
+        path.push(".sig");
+        PathBuf::from(path)
+    });
+
+    let report = std::fs::read(report_path).map_err(|e| GuardianError::io(report_path, e))?;
+    let signature = ReportSignature::load(&signature_path)?;
+    let trusted = attestation::load_public_key(public_key)?;
+
+    match signature.verify(&report, &trusted) {
+        Ok(()) => {
+            println!("✅ Report signature verified: {}", report_path.display());
+            Ok(0)
+        }
+        Err(e) => {
+            println!("❌ {e}");
+            Ok(1)
+        }
+    }
+}
+
+fn run_keygen(output: &Path) -> GuardianResult<i32> {
+    use std::io::Write;
+
+    // Creating the file exclusively never overwrites a key, even one written concurrently,
+    // and on unix only the owner can read the new key
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(output).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::AlreadyExists {
+            GuardianError::config(format!(
+                "Refusing to overwrite existing key file {}",
+                output.display()
+            ))
+        } else {
+            GuardianError::io(output, e)
+        }
+    })?;
+
+    let key = attestation::generate_signing_key();
+    file.write_all(hex::encode(key.to_bytes()).as_bytes())
+        .map_err(|e| GuardianError::io(output, e))?;
+
+    println!("🔑 Signing key written to {}", output.display());
+    println!(
+        "Public key: {}",
+        hex::encode(key.verifying_key().as_bytes())
+    );
+    Ok(0)
+}
+
+fn run_self_benchmark(
+    config_path: Option<PathBuf>,
+    files: usize,
+    min_files_per_second: Option<f64>,
+) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+
+    eprintln!("⏱️ Generating {files} files and analyzing them...");
+    let result = rust_guardian::analyzer::benchmark::self_benchmark(config, files)?;
+    println!("{}", result.format_display());
 
-            println!(
-                "📊 Analyzed {} files in {:.1}s",
-                report.summary.total_files,
-                report.summary.execution_time_ms as f64 / 1000.0
+    if let Some(minimum) = min_files_per_second {
+        if result.files_per_second() < minimum {
+            eprintln!(
+                "❌ Throughput {:.0} files/s is below the required {minimum:.0} files/s",
+                result.files_per_second()
             );
-            println!("⌚ Watching for changes... (Press Ctrl+C to stop)\\n");
-        }
-        Err(e) => {
-            eprintln!("❌ Analysis error: {e}");
+            return Ok(1);
         }
     }
-
-    Ok(())
+    Ok(0)
 }
 
 fn run_validate_config(config_path: Option<PathBuf>) -> GuardianResult<i32> {
-    let config_path = config_path.unwrap_or_else(|| PathBuf::from("guardian.yaml"));
+    let config_path = config_path.unwrap_or_else(discover_config_file);
 
     println!("Validating configuration: {}", config_path.display());
 
-    match GuardianConfig::load_from_file(&config_path) {
+    match GuardianConfig::load_from_source(&config_path) {
         Ok(config) => {
             println!("✅ Configuration is valid");
 
@@ -680,6 +2742,19 @@ fn run_validate_config(config_path: Option<PathBuf>) -> GuardianResult<i32> {
             println!("  Rules: {total_rules} total, {enabled_rules} enabled");
             println!("  Path patterns: {}", config.paths.patterns.len());
 
+            let deprecations = config.deprecated_rules();
+            if !deprecations.is_empty() {
+                println!();
+                println!("⚠️  Deprecated rules enabled:");
+                for deprecation in &deprecations {
+                    println!("  - {}", deprecation.notice());
+                }
+                println!(
+                    "💡 Run 'rust-guardian config migrate {}' to update the configuration",
+                    config_path.display()
+                );
+            }
+
             Ok(0)
         }
         Err(e) => {
@@ -689,8 +2764,275 @@ fn run_validate_config(config_path: Option<PathBuf>) -> GuardianResult<i32> {
     }
 }
 
-fn run_explain(rule_id: String) -> GuardianResult<i32> {
-    let config = GuardianConfig::default();
+fn run_config_command(action: ConfigCommands, config_path: Option<PathBuf>) -> GuardianResult<i32> {
+    match action {
+        ConfigCommands::Migrate {
+            config_file,
+            dry_run,
+        } => run_config_migrate(config_file.or(config_path), dry_run),
+        ConfigCommands::Lint { config_file, paths } => {
+            run_config_lint(config_file.or(config_path), paths)
+        }
+    }
+}
+
+fn run_config_lint(config_path: Option<PathBuf>, paths: Vec<PathBuf>) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+    let analyzer = Analyzer::new(config.clone())?;
+    let files: Vec<PathBuf> = analyzer
+        .analysis_set(&paths, &AnalysisOptions::default())?
+        .files
+        .into_iter()
+        .map(|file| {
+            file.strip_prefix(".")
+                .map(Path::to_path_buf)
+                .unwrap_or(file)
+        })
+        .collect();
+
+    let warnings = lint_config(&config, &files);
+    if warnings.is_empty() {
+        println!(
+            "✅ No configuration problems found ({} files checked)",
+            files.len()
+        );
+        return Ok(0);
+    }
+
+    for warning in &warnings {
+        println!("⚠️  {warning}");
+    }
+    println!();
+    println!("{} configuration problem(s) found", warnings.len());
+    Ok(1)
+}
+
+fn run_config_migrate(config_path: Option<PathBuf>, dry_run: bool) -> GuardianResult<i32> {
+    let config_path = config_path.unwrap_or_else(discover_config_file);
+
+    let content =
+        std::fs::read_to_string(&config_path).map_err(|e| GuardianError::io(&config_path, e))?;
+    let migration = migrate_config(&content)?;
+
+    if migration.is_unchanged() {
+        println!(
+            "✅ {} uses no deprecated rules; nothing to migrate",
+            config_path.display()
+        );
+        return Ok(0);
+    }
+
+    println!("Migrating configuration: {}", config_path.display());
+    for change in &migration.changes {
+        println!("  - {change}");
+    }
+
+    if dry_run {
+        println!();
+        print!("{}", migration.content);
+        return Ok(0);
+    }
+
+    let mut backup_path = config_path.clone().into_os_string();
+    backup_path.push(".bak");
+    let backup_path = PathBuf::from(backup_path);
+    std::fs::write(&backup_path, &content).map_err(|e| GuardianError::io(&backup_path, e))?;
+    std::fs::write(&config_path, &migration.content)
+        .map_err(|e| GuardianError::io(&config_path, e))?;
+
+    println!(
+        "✅ Configuration updated (backup saved to {}; comments are not preserved)",
+        backup_path.display()
+    );
+    Ok(0)
+}
+
+fn run_list_files(
+    config_path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+    exclude_patterns: Vec<String>,
+    json: bool,
+) -> GuardianResult<i32> {
+    // Load configuration
+    let config = load_config(config_path)?;
+
+    // Use current directory if no paths specified
+    let paths = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths
+    };
+
+    let options = AnalysisOptions {
+        exclude_patterns,
+        ..Default::default()
+    };
+    let set = Analyzer::new(config)?.analysis_set(&paths, &options)?;
+
+    if json {
+        let output = serde_json::json!({
+            "files": set.files,
+            "skipped_generated": set.skipped_generated,
+            "total": set.files.len(),
+        });
+        let output = serde_json::to_string_pretty(&output)
+            .map_err(|e| GuardianError::config(format!("JSON serialization failed: {e}")))?;
+        println!("{output}");
+    } else {
+        for file in &set.files {
+            println!("{}", file.display());
+        }
+        eprintln!(
+            "📊 {} files would be analyzed ({} generated skipped)",
+            set.files.len(),
+            set.skipped_generated.len()
+        );
+    }
+
+    Ok(0)
+}
+
+fn run_why_ignored(config_path: Option<PathBuf>, path: PathBuf) -> GuardianResult<i32> {
+    // Load configuration
+    let config = load_config(config_path)?;
+
+    let analyzer = Analyzer::new(config)?;
+    let explanation = analyzer.explain_file(&path)?;
+
+    println!("🔍 {}", path.display());
+    println!();
+
+    println!("📋 Path patterns (in order):");
+    if explanation.path.patterns.is_empty() {
+        println!("   (none configured)");
+    }
+    for (i, outcome) in explanation.path.patterns.iter().enumerate() {
+        let effect = match outcome.excludes {
+            Some(true) => "MATCH -> excluded",
+            Some(false) => "MATCH -> included",
+            None => "no match",
+        };
+        println!("   {:>2}. {:<40} {}", i + 1, outcome.pattern, effect);
+    }
+    println!();
+
+    println!("🚫 Ignore files:");
+    match &explanation.path.ignore_file {
+        Some(found) => println!(
+            "   {}: '{}' -> {}",
+            found.file.display(),
+            found.pattern,
+            if found.ignored {
+                "ignored"
+            } else {
+                "re-included"
+            }
+        ),
+        None => println!("   no ignore file pattern matches"),
+    }
+    if let Some(found) = &explanation.path.ignore_file {
+        print_walk_equivalence(&found.file, &path);
+    }
+
+    if let Some(directory) = &explanation.path.skipped_directory {
+        println!();
+        println!("📁 Directory discovery:");
+        println!("   inside '{directory}/', which is never entered when scanning directories");
+    }
+
+    if let Some(marker) = &explanation.generated_marker {
+        println!();
+        println!("⏭️  Generated code:");
+        println!("   header contains '{marker}' -> skipped (paths.skip_generated)");
+    }
+
+    println!();
+    println!("📏 Rules not applied to this file:");
+    if explanation.excluded_rules.is_empty() {
+        println!("   none");
+    }
+    for (rule_id, reason) in &explanation.excluded_rules {
+        println!("   {rule_id}: {reason}");
+    }
+
+    println!();
+    if explanation.is_analyzed() {
+        let enabled_rules = analyzer.pattern_stats().enabled_rules;
+        let applied = enabled_rules.saturating_sub(explanation.excluded_rules.len());
+        println!("✅ Decision: analyzed ({applied} of {enabled_rules} enabled rules apply)");
+        Ok(0)
+    } else {
+        println!("❌ Decision: not analyzed");
+        Ok(1)
+    }
+}
+
+/// Tell whether a gitignore-aware directory walk would decide a path like Guardian did
+fn print_walk_equivalence(ignore_file: &Path, path: &Path) {
+    let directory = ignore_file.parent().unwrap_or_else(|| Path::new("."));
+    let content = match std::fs::read_to_string(ignore_file) {
+        Ok(content) => content,
+        Err(_) => return,
+    };
+    let check = match PathFilter::equivalence_check(&content) {
+        Ok(check) => check,
+        Err(_) => return,
+    };
+
+    let relative = path.strip_prefix(directory).unwrap_or(path);
+    match check.compare(relative) {
+        None => println!("   git and directory walkers decide the same"),
+        Some(divergence) => println!(
+            "   git skips it: '{}' is excluded by '{}' and git never re-includes files inside it",
+            divergence
+                .excluded_directory
+                .as_deref()
+                .unwrap_or(relative)
+                .display(),
+            divergence.reference_pattern.unwrap_or_default()
+        ),
+    }
+}
+
+fn run_repl(config_path: Option<PathBuf>, paths: Vec<PathBuf>) -> GuardianResult<i32> {
+    use std::io::{BufRead, Write};
+
+    let config = load_config(config_path)?;
+    let mut session = ReplSession::new(config)?;
+
+    println!("Rust Guardian pattern REPL - type `help` for commands, `quit` to leave");
+    if !paths.is_empty() {
+        let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        match session.eval(&format!("file {}", paths.join(" "))) {
+            Ok(ReplStep::Output(output)) => println!("{output}"),
+            Ok(ReplStep::Quit) => {}
+            Err(e) => eprintln!("❌ {e}"),
+        }
+    }
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("guardian> ");
+        std::io::stdout().flush().ok();
+
+        let line = match lines.next() {
+            Some(line) => line.map_err(|e| GuardianError::io("<stdin>", e))?,
+            None => break,
+        };
+        match session.eval(&line) {
+            Ok(ReplStep::Output(output)) if output.is_empty() => {}
+            Ok(ReplStep::Output(output)) => println!("{output}"),
+            Ok(ReplStep::Quit) => break,
+            Err(e) => eprintln!("❌ {e}"),
+        }
+    }
+
+    Ok(0)
+}
+
+fn run_explain(config_path: Option<PathBuf>, rule_id: String) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
 
     // Find the rule in the configuration
     for (category_name, category) in &config.patterns {
@@ -707,10 +3049,28 @@ fn run_explain(rule_id: String) -> GuardianResult<i32> {
                 println!();
                 println!("📝 Description:");
                 println!("   {}", rule.message);
+                if let Some(rationale) = &rule.rationale {
+                    println!();
+                    println!("❔ Why:");
+                    println!("   {rationale}");
+                }
                 println!();
                 println!("🔎 Pattern:");
                 println!("   {}", rule.pattern);
 
+                for (label, example) in [
+                    ("❌ Bad example:", &rule.bad_example),
+                    ("✅ Good example:", &rule.good_example),
+                ] {
+                    if let Some(example) = example {
+                        println!();
+                        println!("{label}");
+                        for line in example.trim_end().lines() {
+                            println!("   {line}");
+                        }
+                    }
+                }
+
                 if let Some(exclude) = &rule.exclude_if {
                     println!();
                     println!("🚫 Exclusions:");
@@ -730,6 +3090,25 @@ fn run_explain(rule_id: String) -> GuardianResult<i32> {
         }
     }
 
+    // Procedural rules compiled into the binary
+    let procedural = Analyzer::new(config.clone())?.procedural_rules();
+    if let Some(info) = procedural.iter().find(|info| info.id == rule_id) {
+        println!("📖 Rule: {}", info.id);
+        if let Some(category) = &info.category {
+            println!("📂 Category: {category}");
+        }
+        println!("⚠️ Severity: {:?}", info.severity);
+        println!("🔍 Type: Procedural");
+        println!();
+        println!("📝 Description:");
+        println!("   {}", info.description);
+        if let Some(docs_url) = &info.docs_url {
+            println!();
+            println!("📚 Documentation: {docs_url}");
+        }
+        return Ok(0);
+    }
+
     eprintln!("❌ Rule '{rule_id}' not found");
     println!();
     println!("Available rules:");
@@ -740,15 +3119,41 @@ fn run_explain(rule_id: String) -> GuardianResult<i32> {
             println!("    - {}", rule.id);
         }
     }
+    if !procedural.is_empty() {
+        println!("  compiled-in:");
+        for info in &procedural {
+            println!("    - {}", info.id);
+        }
+    }
 
     Ok(1)
 }
 
+/// Write the rulebook of the loaded configuration, including compiled-in rules
+fn run_docs(
+    config_path: Option<PathBuf>,
+    format: DocsFormatArg,
+    output: Option<PathBuf>,
+) -> GuardianResult<i32> {
+    let config = load_config(config_path)?;
+    let procedural = Analyzer::new(config.clone())?.procedural_rules();
+    let rulebook = render_rulebook(&config, &procedural, format.into());
+
+    match output {
+        Some(output_path) => {
+            std::fs::write(&output_path, rulebook)
+                .map_err(|e| GuardianError::io(&output_path, e))?;
+            eprintln!("📚 Rulebook written to {}", output_path.display());
+        }
+        None => print!("{rulebook}"),
+    }
+    Ok(0)
+}
+
 async fn run_cache_command(action: CacheCommands) -> GuardianResult<i32> {
     match action {
         CacheCommands::Stats { cache_file } => {
-            let cache_path =
-                cache_file.unwrap_or_else(|| PathBuf::from(".rust").join("guardian_cache.json"));
+            let cache_path = cache_file.unwrap_or_else(default_cache_path);
 
             if !cache_path.exists() {
                 println!("No cache file found at {}", cache_path.display());
@@ -768,8 +3173,7 @@ async fn run_cache_command(action: CacheCommands) -> GuardianResult<i32> {
             Ok(0)
         }
         CacheCommands::Clear { cache_file } => {
-            let cache_path =
-                cache_file.unwrap_or_else(|| PathBuf::from(".rust").join("guardian_cache.json"));
+            let cache_path = cache_file.unwrap_or_else(default_cache_path);
 
             let mut cache = rust_guardian::FileCache::new(&cache_path);
             cache.load()?;
@@ -779,8 +3183,7 @@ async fn run_cache_command(action: CacheCommands) -> GuardianResult<i32> {
             Ok(0)
         }
         CacheCommands::Cleanup { cache_file } => {
-            let cache_path =
-                cache_file.unwrap_or_else(|| PathBuf::from(".rust").join("guardian_cache.json"));
+            let cache_path = cache_file.unwrap_or_else(default_cache_path);
 
             if !cache_path.exists() {
                 println!("No cache file found at {}", cache_path.display());
@@ -789,7 +3192,7 @@ async fn run_cache_command(action: CacheCommands) -> GuardianResult<i32> {
 
             let mut cache = rust_guardian::FileCache::new(&cache_path);
             cache.load()?;
-            let removed = cache.cleanup()?;
+            let removed = cache.cleanup(&SparseCheckout::detect(Path::new(".")))?;
             cache.save()?;
 
             println!("✅ Cleaned up {removed} stale cache entries");
@@ -803,24 +3206,9 @@ fn run_list_rules(
     enabled_only: bool,
     category_filter: Option<String>,
 ) -> GuardianResult<i32> {
-    let config = if let Some(path) = config_path {
-        GuardianConfig::load_from_file(path)?
-    } else {
-        // Try to find default config file
-        let default_configs = ["guardian.yaml", "guardian.yml", ".guardian.yaml"];
-        let mut config = None;
-
-        for config_name in &default_configs {
-            if Path::new(config_name).exists() {
-                config = Some(GuardianConfig::load_from_file(config_name)?);
-                break;
-            }
-        }
-
-        config.unwrap_or_else(GuardianConfig::default)
-    };
+    let config = load_config(config_path)?;
 
-    println!("📋 Available Rules\n");
+    println!("📋 Available Rules (rule pack {RULE_PACK_VERSION})\n");
 
     for (category_name, category) in &config.patterns {
         // Apply category filter
@@ -863,22 +3251,166 @@ fn run_list_rules(
         println!();
     }
 
+    // Procedural rules compiled into the binary are always enabled
+    let procedural: Vec<_> = Analyzer::new(config)?
+        .procedural_rules()
+        .into_iter()
+        .filter(|info| {
+            category_filter
+                .as_ref()
+                .is_none_or(|filter| info.category.as_ref() == Some(filter))
+        })
+        .collect();
+    if !procedural.is_empty() {
+        println!("✅🧩 compiled-in");
+        for info in procedural {
+            println!(
+                "  ✅🔍 {} [{}] - {}",
+                info.id,
+                info.severity.as_str(),
+                info.description
+            );
+        }
+        println!();
+    }
+
     Ok(0)
 }
 
-fn init_logging(verbose: bool) {
-    let level = if verbose {
-        tracing::Level::DEBUG
-    } else {
-        tracing::Level::WARN
+fn run_list_changed_rules(since: Option<String>) -> GuardianResult<i32> {
+    let since = since.unwrap_or_else(|| RULE_PACK_HISTORY[0].version.to_string());
+    let releases = releases_between(&since, None)?;
+
+    println!("📦 Built-in rule changes since rule pack {since} (current: {RULE_PACK_VERSION})\n");
+
+    if releases.is_empty() {
+        println!("No built-in rules changed.");
+        return Ok(0);
+    }
+
+    for release in releases {
+        println!("{}:", release.version);
+        for change in release.changes {
+            println!(
+                "  {:<8} {} - {}",
+                change.kind.as_str(),
+                change.rule_id,
+                change.description
+            );
+        }
+        println!();
+    }
+
+    Ok(0)
+}
+
+/// Initialize console logging and optional span export
+///
+/// JSON logs include INFO-level phase progress (`phase`, `files`, `duration_ms`) so
+/// containerized CI can index runs; `--verbose` adds one DEBUG event per file.
+fn init_logging(verbose: bool, log_format: LogFormatArg) {
+    use tracing_subscriber::filter::LevelFilter;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::Layer;
+
+    let level = match (verbose, log_format) {
+        (true, _) => tracing::Level::DEBUG,
+        (false, LogFormatArg::Json) => tracing::Level::INFO,
+        (false, LogFormatArg::Human) => tracing::Level::WARN,
     };
 
-    tracing_subscriber::fmt()
-        .with_max_level(level)
-        .with_target(false)
+    let human = (log_format == LogFormatArg::Human).then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_filter(LevelFilter::from_level(level))
+    });
+    let json = (log_format == LogFormatArg::Json).then(|| {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(false)
+            .with_target(false)
+            .with_writer(std::io::stderr)
+            .with_filter(LevelFilter::from_level(level))
+    });
+
+    tracing_subscriber::registry()
+        .with(human)
+        .with(json)
+        .with(otel_layer())
         .init();
 }
 
+/// Export analysis spans over OTLP when `OTEL_EXPORTER_OTLP_ENDPOINT` is set
+///
+/// File and rule spans are recorded at INFO regardless of `--verbose`, which only affects
+/// console output.
+#[cfg(feature = "otel")]
+fn otel_layer<S>() -> Option<impl tracing_subscriber::Layer<S>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use tracing_subscriber::filter::LevelFilter;
+    use tracing_subscriber::Layer;
+
+    std::env::var_os("OTEL_EXPORTER_OTLP_ENDPOINT")?;
+
+    let resource = opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+        "service.name",
+        "rust-guardian",
+    )]);
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic())
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| eprintln!("Failed to start OpenTelemetry export: {e}"))
+        .ok()?;
+
+    Some(
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(LevelFilter::INFO),
+    )
+}
+
+#[cfg(not(feature = "otel"))]
+fn otel_layer() -> Option<tracing_subscriber::layer::Identity> {
+    None
+}
+
+/// Flush spans still buffered for export before the process exits
+fn shutdown_tracing() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Parse a duration such as `90`, `90s`, `500ms`, `5m` or `1h` (bare numbers are seconds)
+fn parse_duration(value: &str) -> Result<std::time::Duration, String> {
+    use std::time::Duration;
+
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration '{value}'"))?;
+
+    match unit {
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "ms" => Ok(Duration::from_millis(amount)),
+        "m" => Ok(Duration::from_secs(amount * 60)),
+        "h" => Ok(Duration::from_secs(amount * 3600)),
+        _ => Err(format!(
+            "invalid duration unit in '{value}' (expected ms, s, m or h)"
+        )),
+    }
+}
+
 fn format_timestamp(timestamp: u64) -> String {
     use chrono::{TimeZone, Utc};
 
@@ -913,13 +3445,36 @@ mod tests {
         let result = run_check(
             Some(config_file),
             vec![test_file],
+            None,
+            None,
             OutputFormatArg::Json,
             None,
             None,
+            None,
+            None,
+            vec![],
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
             vec![],
+            None,
+            None,
+            false,
+            false,
+            None,
+            None,
+            false,
+            None,
+            false,
             false,
+            None,
             false,
             false,
+            None,
             false,
             None,
             false,
@@ -930,6 +3485,53 @@ mod tests {
         assert_eq!(result.unwrap(), 1);
     }
 
+    #[test]
+    fn test_resolve_config_precedence() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let policy = |pattern: &str| {
+            format!("version: \"1.0\"\npaths:\n  patterns: [\"{pattern}\"]\npatterns: {{}}\n")
+        };
+        fs::write(root.join("guardian.yaml"), policy("discovered/")).unwrap();
+        fs::write(root.join("explicit.yaml"), policy("explicit/")).unwrap();
+        let embedded = policy("embedded/");
+        let patterns = |config: GuardianConfig| {
+            assert_eq!(config.paths.root.as_deref(), Some(root));
+            config.paths.patterns
+        };
+
+        // An explicit config wins, even over an invalid embedded policy
+        let explicit = resolve_config(Some(&root.join("explicit.yaml")), "version: [", root, root);
+        assert_eq!(patterns(explicit.unwrap()), ["explicit/"]);
+
+        // An embedded policy wins over a discovered config file
+        let embedded = resolve_config(None, &embedded, root, root);
+        assert_eq!(patterns(embedded.unwrap()), ["embedded/"]);
+
+        // Without an embedded policy the closest config file applies
+        let discovered = resolve_config(None, "", root, root);
+        assert_eq!(patterns(discovered.unwrap()), ["discovered/"]);
+
+        // An invalid embedded policy fails instead of falling back to the config file
+        let error = resolve_config(None, "version: [", root, root).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Embedded configuration is invalid"));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("60s").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("soon").is_err());
+    }
+
     #[test]
     fn test_validate_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -946,13 +3548,104 @@ mod tests {
 
     #[test]
     fn test_explain_rule() {
-        let result = run_explain("todo_comments".to_string());
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.yaml");
+        let mut config = GuardianConfig::default();
+        let rule = &mut config.patterns.get_mut("placeholders").unwrap().rules[0];
+        rule.bad_example = Some("// TODO: finish this".to_string());
+        fs::write(&config_file, serde_yaml::to_string(&config).unwrap()).unwrap();
+
+        let result = run_explain(Some(config_file.clone()), "todo_comments".to_string());
+        assert_eq!(result.unwrap(), 0);
+
+        let result = run_explain(Some(config_file), "nonexistent_rule".to_string());
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.yaml");
+        fs::write(
+            &config_file,
+            serde_yaml::to_string(&GuardianConfig::default()).unwrap(),
+        )
+        .unwrap();
+
+        let output = temp_dir.path().join("RULES.md");
+        let result = run_docs(
+            Some(config_file),
+            DocsFormatArg::Markdown,
+            Some(output.clone()),
+        );
+        assert_eq!(result.unwrap(), 0);
+        assert!(fs::read_to_string(output)
+            .unwrap()
+            .contains("### `todo_comments`"));
+    }
+
+    #[test]
+    fn test_keygen() {
+        let temp_dir = TempDir::new().unwrap();
+        let key_file = temp_dir.path().join("guardian.key");
+
+        assert_eq!(run_keygen(&key_file).unwrap(), 0);
+        let key = fs::read_to_string(&key_file).unwrap();
+        assert!(attestation::load_signing_key(Some(&key_file)).is_ok());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&key_file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // An existing key is never replaced
+        assert!(run_keygen(&key_file).is_err());
+        assert_eq!(fs::read_to_string(&key_file).unwrap(), key);
+    }
+
+    #[test]
+    fn test_why_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.yaml");
+        let yaml = serde_yaml::to_string(&GuardianConfig::default()).unwrap();
+        fs::write(&config_file, yaml).unwrap();
+
+        let source = temp_dir.path().join("lib.rs");
+        fs::write(&source, "pub fn lib() {}\n").unwrap();
+        let result = run_why_ignored(Some(config_file.clone()), source);
         assert_eq!(result.unwrap(), 0);
 
-        let result = run_explain("nonexistent_rule".to_string());
+        let generated = temp_dir.path().join("proto.rs");
+        fs::write(&generated, "// @generated\npub fn stub() {}\n").unwrap();
+        let result = run_why_ignored(Some(config_file), generated);
         assert_eq!(result.unwrap(), 1);
     }
 
+    #[test]
+    fn test_list_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("test_config.yaml");
+        let yaml = serde_yaml::to_string(&GuardianConfig::default()).unwrap();
+        fs::write(&config_file, yaml).unwrap();
+
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "pub fn lib() {}\n").unwrap();
+        fs::write(src.join("proto.rs"), "// @generated\npub fn stub() {}\n").unwrap();
+
+        let config = GuardianConfig::load_from_file(&config_file).unwrap();
+        let set = Analyzer::new(config)
+            .unwrap()
+            .analysis_set(&[&src], &AnalysisOptions::default())
+            .unwrap();
+        assert_eq!(set.files, vec![src.join("lib.rs")]);
+        assert_eq!(set.skipped_generated, vec![src.join("proto.rs")]);
+
+        let result = run_list_files(Some(config_file), vec![src], vec![], true);
+        assert_eq!(result.unwrap(), 0);
+    }
+
     #[test]
     fn test_list_rules() {
         let result = run_list_rules(None, false, None);
@@ -961,4 +3654,87 @@ mod tests {
         let result = run_list_rules(None, true, Some("placeholders".to_string()));
         assert_eq!(result.unwrap(), 0);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_watch_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let status_file = temp_dir.path().join("status");
+        let hooks = WatchHooks {
+            on_failure: Some(format!(
+                "echo \"$GUARDIAN_STATUS $GUARDIAN_ERRORS\" > '{}'",
+                status_file.display()
+            )),
+            ..WatchHooks::default()
+        };
+
+        let outcome = WatchOutcome {
+            passed: false,
+            errors: 3,
+            warnings: 0,
+        };
+        hooks.after_analysis(&outcome, None).await;
+        assert_eq!(fs::read_to_string(&status_file).unwrap(), "fail 3\n");
+    }
+
+    #[test]
+    fn test_watch_ignores_custom_cache_file() {
+        use notify::event::{EventKind, ModifyKind};
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for file in ["src/lib.rs", "src/cache.json", "src/notes.json"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let event = notify::Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(root.join("src/lib.rs"))
+            .add_path(root.join("src/cache.json"))
+            .add_path(root.join("src/notes.json"));
+
+        let changed = changed_analysis_paths(
+            &event,
+            &["**/*".to_string()],
+            &GuardianValidator::new().unwrap().path_filter(),
+            &root,
+            &root,
+            &event_path(&root.join("src/./cache.json")),
+        );
+        assert_eq!(
+            changed,
+            [root.join("src/lib.rs"), root.join("src/notes.json")]
+        );
+    }
+
+    #[test]
+    fn test_watch_state_delta() {
+        let violation = |file: &str, message: &str, line: u32| {
+            Violation::new(
+                "todo_comments",
+                Severity::Error,
+                PathBuf::from(file),
+                message,
+            )
+            .with_position(line, 1)
+        };
+
+        let mut state = WatchState::default();
+        let mut initial = ValidationReport::new();
+        initial.add_violation(violation("a.rs", "TODO one", 1));
+        initial.add_violation(violation("b.rs", "TODO two", 1));
+        state.apply(None, &initial);
+
+        // a.rs shifted its existing TODO down a line and gained a new one; b.rs was fixed
+        let mut batch = ValidationReport::new();
+        batch.add_violation(violation("a.rs", "TODO one", 2));
+        batch.add_violation(violation("a.rs", "TODO three", 5));
+        let changed = vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")];
+        let delta = state.apply(Some(&changed), &batch);
+
+        assert_eq!(delta.introduced.len(), 1);
+        assert_eq!(delta.introduced[0].message, "TODO three");
+        assert_eq!(delta.fixed.len(), 1);
+        assert_eq!(delta.fixed[0].message, "TODO two");
+        assert_eq!(state.counts().error, 2);
+    }
 }
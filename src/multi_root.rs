@@ -0,0 +1,342 @@
+//! Validation of several project roots at once
+//!
+//! Architecture: Application Service - An IDE workspace may hold several repositories
+//! - Every root keeps its own configuration and cache, as if it were validated on its own
+//! - A root nested inside another owns its subtree; the outer root does not report it
+//! - Reports stay separate per root and can be combined into one when a single verdict is needed
+
+use crate::config::{find_config_file, GuardianConfig};
+use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport};
+use crate::report::{ReportFormatter, ReportOptions};
+use crate::{GuardianValidator, ValidationOptions};
+use std::path::{Path, PathBuf};
+
+/// One project root with the configuration and cache that apply to it
+#[derive(Debug, Clone)]
+pub struct ProjectRoot {
+    /// Root directory
+    pub path: PathBuf,
+    /// Configuration of the root; its `paths.root` is set to `path`
+    pub config: GuardianConfig,
+    /// Cache file of the root, when caching is wanted
+    pub cache_path: Option<PathBuf>,
+}
+
+impl ProjectRoot {
+    /// A root with the given configuration
+    pub fn new(path: impl Into<PathBuf>, mut config: GuardianConfig) -> Self {
+        let path = path.into();
+        config.paths.root = Some(path.clone());
+        Self {
+            path,
+            config,
+            cache_path: None,
+        }
+    }
+
+    /// A root with the configuration file found in it, or the default configuration
+    pub fn discover(path: impl Into<PathBuf>) -> GuardianResult<Self> {
+        let path = path.into();
+        let config = match find_config_file(&path, &path) {
+            Some(config_file) => GuardianConfig::load_from_file(config_file)?,
+            None => GuardianConfig::default(),
+        };
+        Ok(Self::new(path, config))
+    }
+
+    /// Cache results in the root's default cache file, `.rust/guardian_cache.json`
+    pub fn with_default_cache(mut self) -> Self {
+        self.cache_path = Some(self.path.join(".rust").join("guardian_cache.json"));
+        self
+    }
+
+    /// Cache results in the given file
+    pub fn with_cache(mut self, cache_path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(cache_path.into());
+        self
+    }
+}
+
+/// Report of one root of a multi-root validation
+#[derive(Debug, Clone)]
+pub struct RootReport {
+    pub root: PathBuf,
+    pub report: ValidationReport,
+}
+
+/// Reports of every root, in the order the roots were given
+#[derive(Debug, Clone, Default)]
+pub struct MultiRootReport {
+    pub roots: Vec<RootReport>,
+}
+
+impl MultiRootReport {
+    /// Whether any root has error-level violations
+    pub fn has_errors(&self) -> bool {
+        self.roots.iter().any(|root| root.report.has_errors())
+    }
+
+    /// One report holding the violations of every root
+    pub fn combined(&self) -> ValidationReport {
+        let mut combined = ValidationReport::new();
+        for root in &self.roots {
+            combined.merge(root.report.clone());
+            combined.summary.execution_time_ms += root.report.summary.execution_time_ms;
+        }
+        combined.sort_violations();
+        combined
+    }
+
+    /// Human-readable report with one section per root, paths relative to their root
+    pub fn format_display(&self, options: &ReportOptions) -> GuardianResult<String> {
+        let mut output = String::new();
+        for root in &self.roots {
+            let formatter = root_formatter(&root.root, options)?;
+            output.push_str(&format!("📁 {}\n", root.root.display()));
+            output.push_str(&formatter.format_report(&root.report, crate::OutputFormat::Human)?);
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// JSON report with a `roots` array holding each root's JSON report, paths relative to
+    /// their root
+    pub fn to_json(&self, options: &ReportOptions) -> GuardianResult<String> {
+        let invalid =
+            |e: serde_json::Error| GuardianError::config(format!("JSON serialization failed: {e}"));
+        let mut roots = Vec::new();
+        for root in &self.roots {
+            let formatter = root_formatter(&root.root, options)?;
+            let report: serde_json::Value = serde_json::from_str(
+                &formatter.format_report(&root.report, crate::OutputFormat::Json)?,
+            )
+            .map_err(invalid)?;
+            roots.push(serde_json::json!({
+                "root": root.root.display().to_string(),
+                "report": report,
+            }));
+        }
+
+        let combined = self.combined();
+        serde_json::to_string_pretty(&serde_json::json!({
+            "roots": roots,
+            "summary": {
+                "total_files": combined.summary.total_files,
+                "violations_by_severity": combined.summary.violations_by_severity,
+                "has_errors": self.has_errors(),
+            }
+        }))
+        .map_err(invalid)
+    }
+}
+
+/// Formatter showing paths relative to a root
+fn root_formatter(root: &Path, options: &ReportOptions) -> GuardianResult<ReportFormatter> {
+    ReportFormatter::new(ReportOptions {
+        path_base: Some(root.to_path_buf()),
+        ..options.clone()
+    })
+}
+
+/// Validator for several project roots, each with its own configuration and cache
+pub struct MultiRootValidator {
+    roots: Vec<(PathBuf, GuardianValidator)>,
+}
+
+impl GuardianValidator {
+    /// Create a validator for several project roots
+    ///
+    /// Roots may be nested, as with a repository checked out inside another; files belong to
+    /// the innermost root containing them.
+    pub fn multi_root(roots: Vec<ProjectRoot>) -> GuardianResult<MultiRootValidator> {
+        let mut validators: Vec<(PathBuf, GuardianValidator)> = Vec::with_capacity(roots.len());
+        for root in roots {
+            if validators.iter().any(|(path, _)| *path == root.path) {
+                return Err(GuardianError::config(format!(
+                    "Project root '{}' is given more than once",
+                    root.path.display()
+                )));
+            }
+            let mut validator = GuardianValidator::new_with_config(root.config)?;
+            if let Some(cache_path) = root.cache_path {
+                validator = validator.with_cache(cache_path)?;
+            }
+            validators.push((root.path, validator));
+        }
+        Ok(MultiRootValidator { roots: validators })
+    }
+}
+
+impl MultiRootValidator {
+    /// Project roots, in the order they were given
+    pub fn roots(&self) -> impl Iterator<Item = &Path> {
+        self.roots.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// Validator of a root
+    pub fn validator(&self, root: &Path) -> Option<&GuardianValidator> {
+        self.roots
+            .iter()
+            .find(|(path, _)| path == root)
+            .map(|(_, validator)| validator)
+    }
+
+    /// Innermost root containing a path
+    pub fn root_of(&self, path: &Path) -> Option<&Path> {
+        self.roots
+            .iter()
+            .map(|(root, _)| root.as_path())
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+    }
+
+    /// Validate every root in full
+    pub async fn validate(&self, options: &ValidationOptions) -> GuardianResult<MultiRootReport> {
+        let paths: Vec<PathBuf> = self.roots.iter().map(|(root, _)| root.clone()).collect();
+        self.validate_paths(&paths, options).await
+    }
+
+    /// Validate paths, each with the configuration of the innermost root containing it
+    ///
+    /// Roots none of the paths belong to are left out of the report. Paths outside every
+    /// root are an error.
+    pub async fn validate_paths(
+        &self,
+        paths: &[PathBuf],
+        options: &ValidationOptions,
+    ) -> GuardianResult<MultiRootReport> {
+        let mut by_root: Vec<Vec<PathBuf>> = vec![Vec::new(); self.roots.len()];
+        for path in paths {
+            let root = self.root_of(path).ok_or_else(|| {
+                GuardianError::config(format!(
+                    "Path '{}' is not inside any project root",
+                    path.display()
+                ))
+            })?;
+            if let Some(index) = self.roots.iter().position(|(path, _)| path == root) {
+                by_root[index].push(path.clone());
+            }
+        }
+
+        let mut report = MultiRootReport::default();
+        for ((root, validator), paths) in self.roots.iter().zip(by_root) {
+            if paths.is_empty() {
+                continue;
+            }
+            // Nested roots are skipped while walking the outer root, and report their own files
+            let mut root_options = options.clone();
+            root_options.analysis_options.exclude_patterns.extend(
+                self.roots
+                    .iter()
+                    .filter(|(nested, _)| nested != root)
+                    .filter_map(|(nested, _)| nested.strip_prefix(root).ok())
+                    .map(|nested| format!("/{}/", nested.to_string_lossy().replace('\\', "/"))),
+            );
+            let mut root_report = validator
+                .validate_with_options(paths, &root_options)
+                .await?;
+            root_report.retain_violations(|violation| {
+                self.root_of(&violation.file_path) == Some(root.as_path())
+            });
+            report.roots.push(RootReport {
+                root: root.clone(),
+                report: root_report,
+            });
+        }
+        Ok(report)
+    }
+
+    /// Save the cache of every root that has one
+    pub fn save_caches(&self) -> GuardianResult<()> {
+        for (_, validator) in &self.roots {
+            validator.save_cache()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_multi_root_validation() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outer = workspace.path().join("app");
+        let nested = outer.join("vendor/lib");
+        fs::create_dir_all(outer.join("src")).unwrap();
+        fs::create_dir_all(nested.join("src")).unwrap();
+        fs::write(outer.join("src/main.rs"), "// TODO: outer\nfn main() {}\n").unwrap();
+        fs::write(nested.join("src/lib.rs"), "// TODO: nested\n").unwrap();
+
+        // The nested root turns the TODO rule off
+        let mut quiet = GuardianConfig::default();
+        for category in quiet.patterns.values_mut() {
+            for rule in &mut category.rules {
+                if rule.id == "todo_comments" {
+                    rule.enabled = false;
+                }
+            }
+        }
+        let validator = GuardianValidator::multi_root(vec![
+            ProjectRoot::new(&outer, GuardianConfig::default()),
+            ProjectRoot::new(&nested, quiet),
+        ])
+        .unwrap();
+        assert_eq!(
+            validator.root_of(&nested.join("src/lib.rs")),
+            Some(nested.as_path())
+        );
+
+        let options = ValidationOptions {
+            use_cache: false,
+            ..Default::default()
+        };
+        let report = validator.validate(&options).await.unwrap();
+        assert_eq!(report.roots.len(), 2);
+        assert_eq!(report.roots[0].report.summary.total_files, 1);
+        let outer_rules: Vec<&str> = report.roots[0]
+            .report
+            .violations
+            .iter()
+            .map(|violation| violation.rule_id.as_str())
+            .collect();
+        assert!(outer_rules.contains(&"todo_comments"));
+        assert!(report.roots[0]
+            .report
+            .violations
+            .iter()
+            .all(|violation| violation.file_path.starts_with(outer.join("src"))));
+        assert!(!report.roots[1]
+            .report
+            .violations
+            .iter()
+            .any(|violation| violation.rule_id == "todo_comments"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.to_json(&ReportOptions::default()).unwrap()).unwrap();
+        assert_eq!(json["roots"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            json["roots"][0]["report"]["violations"][0]["file_path"],
+            "src/main.rs"
+        );
+        assert_eq!(
+            report.combined().violations.len(),
+            report
+                .roots
+                .iter()
+                .map(|root| root.report.violations.len())
+                .sum::<usize>()
+        );
+
+        let outside = workspace.path().join("elsewhere.rs");
+        assert!(validator
+            .validate_paths(&[outside], &options)
+            .await
+            .is_err());
+    }
+}
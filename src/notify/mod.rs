@@ -0,0 +1,190 @@
+//! Webhook notifications for unattended runs
+//!
+//! Architecture: Infrastructure Adapter - Notifications sit outside the analysis core
+//! - Thresholds and payloads are pure functions of the report and configuration
+//! - Delivery is the only side effect and needs the `notifications` feature
+//! - Failed deliveries are reported to the caller; they never change analysis results
+
+use crate::config::NotificationConfig;
+use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport};
+use std::collections::BTreeMap;
+
+/// Number of most frequent rules listed in a notification
+const TOP_RULES: usize = 5;
+
+/// Whether a report crosses the configured notification thresholds
+///
+/// Without any threshold configured, a single error triggers a notification.
+pub fn should_notify(config: &NotificationConfig, report: &ValidationReport) -> bool {
+    let counts = &report.summary.violations_by_severity;
+    let min_errors = match (config.min_errors, config.min_warnings) {
+        (None, None) => Some(1),
+        (min_errors, _) => min_errors,
+    };
+
+    min_errors.is_some_and(|min| counts.error >= min)
+        || config.min_warnings.is_some_and(|min| counts.warning >= min)
+}
+
+/// Substitute report values into a webhook template
+pub fn render_template(template: &str, report: &ValidationReport, project: &str) -> String {
+    let counts = &report.summary.violations_by_severity;
+    template
+        .replace("{project}", project)
+        .replace("{errors}", &counts.error.to_string())
+        .replace("{warnings}", &counts.warning.to_string())
+        .replace("{info}", &counts.info.to_string())
+        .replace("{files}", &report.summary.total_files.to_string())
+        .replace("{top_rules}", &top_rules(report).join(", "))
+}
+
+/// Slack incoming-webhook payload summarizing a report
+pub fn slack_payload(report: &ValidationReport, project: &str) -> serde_json::Value {
+    let counts = &report.summary.violations_by_severity;
+    let mut text = format!(
+        ":rotating_light: *Rust Guardian* found {} error(s), {} warning(s) and {} info in *{}* ({} files analyzed)",
+        counts.error, counts.warning, counts.info, project, report.summary.total_files
+    );
+    let rules = top_rules(report);
+    if !rules.is_empty() {
+        text.push_str(&format!("\nMost frequent rules: {}", rules.join(", ")));
+    }
+
+    serde_json::json!({ "text": text })
+}
+
+/// Send every configured notification, returning how many were delivered
+///
+/// Nothing is sent when the report stays below the thresholds. All targets are attempted
+/// even if one fails; the first failure is returned.
+pub fn send_notifications(
+    config: &NotificationConfig,
+    report: &ValidationReport,
+    project: &str,
+) -> GuardianResult<usize> {
+    if !should_notify(config, report) {
+        return Ok(0);
+    }
+
+    let mut deliveries = Vec::new();
+    if let Some(url) = &config.slack_webhook {
+        let body = slack_payload(report, project).to_string();
+        deliveries.push(resolve_url(url).and_then(|url| post(&url, "application/json", &body)));
+    }
+    if let Some(webhook) = &config.webhook {
+        let body = render_template(&webhook.template, report, project);
+        deliveries.push(
+            resolve_url(&webhook.url).and_then(|url| post(&url, &webhook.content_type, &body)),
+        );
+    }
+
+    let sent = deliveries.iter().filter(|result| result.is_ok()).count();
+    match deliveries.into_iter().find_map(Result::err) {
+        Some(error) => Err(error),
+        None => Ok(sent),
+    }
+}
+
+/// Rules with the most violations, as `rule (count)`
+fn top_rules(report: &ValidationReport) -> Vec<String> {
+    let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+    for violation in &report.violations {
+        *by_rule.entry(violation.rule_id.as_str()).or_default() += 1;
+    }
+
+    let mut rules: Vec<(&str, usize)> = by_rule.into_iter().collect();
+    rules.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    rules
+        .into_iter()
+        .take(TOP_RULES)
+        .map(|(rule, count)| format!("{rule} ({count})"))
+        .collect()
+}
+
+/// Read `$NAME` URLs from the environment
+fn resolve_url(url: &str) -> GuardianResult<String> {
+    match url.strip_prefix('$') {
+        Some(name) => std::env::var(name).map_err(|_| {
+            GuardianError::config(format!("Notification URL variable '{name}' is not set"))
+        }),
+        None => Ok(url.to_string()),
+    }
+}
+
+#[cfg(feature = "notifications")]
+fn post(url: &str, content_type: &str, body: &str) -> GuardianResult<()> {
+    ureq::post(url)
+        .set("Content-Type", content_type)
+        .send_string(body)
+        .map(|_| ())
+        .map_err(|e| GuardianError::config(format!("Notification delivery failed: {e}")))
+}
+
+#[cfg(not(feature = "notifications"))]
+fn post(_url: &str, _content_type: &str, _body: &str) -> GuardianResult<()> {
+    Err(GuardianError::config(
+        "Notifications require rust-guardian to be built with the 'notifications' feature",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::violations::{Severity, Violation};
+    use std::path::PathBuf;
+
+    fn report_with(errors: usize, warnings: usize) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (rule, severity, count) in [
+            ("unimplemented_macros", Severity::Error, errors),
+            ("todo_comments", Severity::Warning, warnings),
+        ] {
+            for line in 0..count {
+                report.add_violation(
+                    Violation::new(rule, severity, PathBuf::from("src/lib.rs"), "found")
+                        .with_position(line as u32 + 1, 1),
+                );
+            }
+        }
+        report.set_files_analyzed(12);
+        report
+    }
+
+    #[test]
+    fn test_thresholds_and_payloads() {
+        let defaults = NotificationConfig::default();
+        assert!(!should_notify(&defaults, &report_with(0, 40)));
+        assert!(should_notify(&defaults, &report_with(1, 0)));
+
+        let warnings_only = NotificationConfig {
+            min_warnings: Some(10),
+            ..NotificationConfig::default()
+        };
+        assert!(!should_notify(&warnings_only, &report_with(3, 9)));
+        assert!(should_notify(&warnings_only, &report_with(0, 10)));
+
+        let report = report_with(2, 3);
+        assert_eq!(
+            render_template(
+                r#"{"project":"{project}","errors":{errors},"rules":"{top_rules}"}"#,
+                &report,
+                "demo"
+            ),
+            r#"{"project":"demo","errors":2,"rules":"todo_comments (3), unimplemented_macros (2)"}"#
+        );
+
+        let text = slack_payload(&report, "demo")["text"]
+            .as_str()
+            .expect("Slack payload should carry text")
+            .to_string();
+        assert!(text.contains("2 error(s), 3 warning(s)"));
+        assert!(text.contains("12 files analyzed"));
+
+        // Below the thresholds nothing is attempted, so no delivery can fail
+        assert_eq!(
+            send_notifications(&defaults, &report_with(0, 1), "demo")
+                .expect("nothing should be sent"),
+            0
+        );
+    }
+}
@@ -0,0 +1,633 @@
+//! Public API surface checks for library crates
+//!
+//! Architecture: Domain Services - A consistent API surface is checked item by item
+//! - Public functions returning `Result` or builder types should be `#[must_use]`
+//! - Public structs and enums should carry the derives the project expects; a manual impl counts
+//! - Public signatures should avoid `bool` flags and strings where an enum or newtype says more
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+
+/// Return types that need `#[must_use]` when no list is configured
+const DEFAULT_MUST_USE_TYPES: &str = "*Result|*Builder";
+
+/// Derives required of every public type when no requirements are configured
+const DEFAULT_DERIVES: &str = "Debug";
+
+/// Bare `bool` parameters a public function may take when no limit is configured
+const DEFAULT_MAX_BOOL_PARAMS: u32 = 1;
+
+/// Consecutive string parameters a public function may take when no limit is configured
+const DEFAULT_MAX_STRING_PARAMS: u32 = 2;
+
+/// Name words suggesting a closed set of values when no list is configured
+const DEFAULT_ENUM_WORDS: &str = "mode|kind|type|status|state|level|format|strategy|policy";
+
+/// Derives required of public types whose name matches a pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeriveRequirement {
+    /// Type names the requirement applies to, e.g. `*Config`
+    pub types: glob::Pattern,
+    /// Traits those types must derive or implement, e.g. `Debug`
+    pub derives: Vec<String>,
+}
+
+/// Which public API convention a rule enforces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiCheck {
+    /// Public functions returning one of these types must be `#[must_use]`
+    MustUse { return_types: Vec<glob::Pattern> },
+    /// Public structs and enums must derive the required traits
+    Derives(Vec<DeriveRequirement>),
+    /// Public functions may take at most this many bare `bool` parameters
+    BoolParams { max: u32 },
+    /// Public functions may take at most this many string parameters in a row
+    StringParams { max: u32 },
+    /// Public parameters and fields named like an enum (`mode`, `kind`) must not be strings
+    StringlyTyped { words: Vec<String> },
+}
+
+impl ApiCheck {
+    /// Parse a semantic pattern into an API check
+    ///
+    /// Accepts `missing_must_use[:<type>|<type>]`, where types may use `*` wildcards, and
+    /// `missing_derive[:<requirements>]`. Requirements are `;`-separated
+    /// `<type pattern>=<Trait>,<Trait>` entries; a bare trait list applies to every type.
+    /// `bool_params[:<max>]` limits bare `bool` parameters, to one by default, and
+    /// `string_params[:<max>]` limits consecutive string parameters, to two by default.
+    /// `stringly_typed[:<word>|<word>]` sets the name words that call for an enum.
+    /// Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<GuardianResult<Self>> {
+        if let Some(max) = parameter(pattern, "bool_params") {
+            return Some(limit(max, DEFAULT_MAX_BOOL_PARAMS).map(|max| Self::BoolParams { max }));
+        }
+
+        if let Some(max) = parameter(pattern, "string_params") {
+            return Some(
+                limit(max, DEFAULT_MAX_STRING_PARAMS).map(|max| Self::StringParams { max }),
+            );
+        }
+
+        if let Some(words) = parameter(pattern, "stringly_typed") {
+            let words = if words.is_empty() {
+                DEFAULT_ENUM_WORDS
+            } else {
+                words
+            };
+            return Some(Ok(Self::StringlyTyped {
+                words: words
+                    .split('|')
+                    .map(|word| word.trim().to_lowercase())
+                    .filter(|word| !word.is_empty())
+                    .collect(),
+            }));
+        }
+
+        if let Some(types) = parameter(pattern, "missing_must_use") {
+            let types = if types.is_empty() {
+                DEFAULT_MUST_USE_TYPES
+            } else {
+                types
+            };
+            return Some(
+                types
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(type_pattern)
+                    .collect::<GuardianResult<Vec<_>>>()
+                    .map(|return_types| Self::MustUse { return_types }),
+            );
+        }
+
+        let requirements = parameter(pattern, "missing_derive")?;
+        let requirements = if requirements.is_empty() {
+            DEFAULT_DERIVES
+        } else {
+            requirements
+        };
+        Some(
+            requirements
+                .split(';')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| {
+                    let (types, derives) = entry.split_once('=').unwrap_or(("*", entry));
+                    Ok(DeriveRequirement {
+                        types: type_pattern(types.trim())?,
+                        derives: derives
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|derive| !derive.is_empty())
+                            .map(str::to_string)
+                            .collect(),
+                    })
+                })
+                .collect::<GuardianResult<Vec<_>>>()
+                .map(Self::Derives),
+        )
+    }
+}
+
+/// The parameter of `<name>` or `<name>:<parameter>`, empty for a bare name
+fn parameter<'p>(pattern: &'p str, name: &str) -> Option<&'p str> {
+    match pattern.strip_prefix(name)? {
+        "" => Some(""),
+        rest => rest.strip_prefix(':'),
+    }
+}
+
+/// A parameter limit, or `default` when none is given
+fn limit(max: &str, default: u32) -> GuardianResult<u32> {
+    if max.is_empty() {
+        return Ok(default);
+    }
+    max.trim().parse().map_err(|_| {
+        GuardianError::pattern(format!(
+            "Invalid parameter limit '{max}': expected a number"
+        ))
+    })
+}
+
+/// Compile a type name pattern such as `*Builder`
+fn type_pattern(pattern: &str) -> GuardianResult<glob::Pattern> {
+    glob::Pattern::new(pattern)
+        .map_err(|e| GuardianError::pattern(format!("Invalid type pattern '{pattern}': {e}")))
+}
+
+/// Find public items breaking an API convention
+///
+/// Returns the item name span, the item name, and a short description as context.
+pub fn find_api_violations(
+    syntax_tree: &syn::File,
+    check: &ApiCheck,
+) -> Vec<(Span, String, String)> {
+    let mut impls = TraitImplFinder {
+        impls: HashSet::new(),
+    };
+    impls.visit_file(syntax_tree);
+
+    let mut visitor = ApiVisitor {
+        check,
+        trait_impls: impls.impls,
+        self_type: None,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct ApiVisitor<'a> {
+    check: &'a ApiCheck,
+    /// `(trait, type)` pairs implemented manually in the file
+    trait_impls: HashSet<(String, String)>,
+    /// Type of the inherent impl block being visited
+    self_type: Option<String>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl ApiVisitor<'_> {
+    fn check_function(&mut self, attrs: &[syn::Attribute], sig: &syn::Signature) {
+        let return_types = match self.check {
+            ApiCheck::MustUse { return_types } => return_types,
+            ApiCheck::BoolParams { max } => return self.check_bool_params(sig, *max),
+            ApiCheck::StringParams { max } => return self.check_string_params(sig, *max),
+            ApiCheck::StringlyTyped { words } => return self.check_stringly_params(sig, words),
+            ApiCheck::Derives(_) => return,
+        };
+        if attrs.iter().any(|attr| attr.path().is_ident("must_use")) {
+            return;
+        }
+
+        let returned = match &sig.output {
+            syn::ReturnType::Type(_, ty) => type_name(ty),
+            syn::ReturnType::Default => None,
+        };
+        let returned = match returned {
+            Some(name) if name == "Self" => self.self_type.clone(),
+            other => other,
+        };
+
+        if let Some(returned) = returned {
+            if return_types
+                .iter()
+                .any(|pattern| pattern.matches(&returned))
+            {
+                let name = sig.ident.to_string();
+                let context = format!("pub fn {name} returns {returned} without #[must_use]");
+                self.matches.push((sig.ident.span(), name, context));
+            }
+        }
+    }
+
+    fn check_bool_params(&mut self, sig: &syn::Signature, max: u32) {
+        let flags = sig
+            .inputs
+            .iter()
+            .filter(|input| match input {
+                syn::FnArg::Typed(arg) => type_name(&arg.ty).is_some_and(|ty| ty == "bool"),
+                syn::FnArg::Receiver(_) => false,
+            })
+            .count() as u32;
+
+        if flags > max {
+            let name = sig.ident.to_string();
+            let context = format!("pub fn {name} takes {flags} bool parameters");
+            self.matches.push((sig.ident.span(), name, context));
+        }
+    }
+
+    fn check_string_params(&mut self, sig: &syn::Signature, max: u32) {
+        let mut run = 0;
+        let mut longest = 0;
+        for input in &sig.inputs {
+            run = match input {
+                syn::FnArg::Typed(arg) if is_string(&arg.ty) => run + 1,
+                _ => 0,
+            };
+            longest = longest.max(run);
+        }
+
+        if longest > max {
+            let name = sig.ident.to_string();
+            let context = format!("pub fn {name} takes {longest} string parameters in a row");
+            self.matches.push((sig.ident.span(), name, context));
+        }
+    }
+
+    fn check_stringly_params(&mut self, sig: &syn::Signature, words: &[String]) {
+        let strings: Vec<(&syn::Ident, &syn::Type)> = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(arg) if is_string(&arg.ty) => match arg.pat.as_ref() {
+                    syn::Pat::Ident(pat) => Some((&pat.ident, arg.ty.as_ref())),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        // `set_mode(value: &str)` names the domain in the function rather than the parameter
+        let named_by_function = strings.len() == 1 && names_enum(&sig.ident, words);
+
+        let function = sig.ident.to_string();
+        for (ident, ty) in strings {
+            if named_by_function || names_enum(ident, words) {
+                let ty = quote::ToTokens::to_token_stream(ty).to_string();
+                let context = format!("pub fn {function} takes {ident}: {}", ty.replace(' ', ""));
+                self.matches
+                    .push((ident.span(), ident.to_string(), context));
+            }
+        }
+    }
+
+    fn check_stringly_fields(&mut self, item: &syn::ItemStruct) {
+        let ApiCheck::StringlyTyped { words } = self.check else {
+            return;
+        };
+        for field in &item.fields {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            if matches!(field.vis, syn::Visibility::Public(_))
+                && is_string(&field.ty)
+                && names_enum(ident, words)
+            {
+                let ty = quote::ToTokens::to_token_stream(&field.ty).to_string();
+                let context = format!(
+                    "pub struct {} has field {ident}: {}",
+                    item.ident,
+                    ty.replace(' ', "")
+                );
+                self.matches
+                    .push((ident.span(), ident.to_string(), context));
+            }
+        }
+    }
+
+    fn check_type(&mut self, kind: &str, attrs: &[syn::Attribute], ident: &syn::Ident) {
+        let requirements = match self.check {
+            ApiCheck::Derives(requirements) => requirements,
+            _ => return,
+        };
+
+        let name = ident.to_string();
+        let derived = derived_traits(attrs);
+        let mut missing: Vec<&str> = Vec::new();
+        for requirement in requirements {
+            if !requirement.types.matches(&name) {
+                continue;
+            }
+            for derive in &requirement.derives {
+                let implemented = derived.contains(derive)
+                    || self.trait_impls.contains(&(derive.clone(), name.clone()));
+                if !implemented && !missing.contains(&derive.as_str()) {
+                    missing.push(derive);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let context = format!("pub {kind} {name} does not derive {}", missing.join(", "));
+            self.matches.push((ident.span(), name, context));
+        }
+    }
+}
+
+impl Visit<'_> for ApiVisitor<'_> {
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        if matches!(item.vis, syn::Visibility::Public(_)) {
+            self.check_function(&item.attrs, &item.sig);
+        }
+        syn::visit::visit_item_fn(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &syn::ItemImpl) {
+        // Trait methods take their attributes from the trait, not the impl
+        if item.trait_.is_some() {
+            return;
+        }
+        let outer = std::mem::replace(&mut self.self_type, type_name(&item.self_ty));
+        syn::visit::visit_item_impl(self, item);
+        self.self_type = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        if matches!(item.vis, syn::Visibility::Public(_)) {
+            self.check_function(&item.attrs, &item.sig);
+        }
+        syn::visit::visit_impl_item_fn(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &syn::ItemStruct) {
+        if matches!(item.vis, syn::Visibility::Public(_)) {
+            self.check_type("struct", &item.attrs, &item.ident);
+            self.check_stringly_fields(item);
+        }
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &syn::ItemEnum) {
+        if matches!(item.vis, syn::Visibility::Public(_)) {
+            self.check_type("enum", &item.attrs, &item.ident);
+        }
+        syn::visit::visit_item_enum(self, item);
+    }
+}
+
+/// Collects `(trait, type)` pairs of trait impls, e.g. `("Debug", "Config")`
+struct TraitImplFinder {
+    impls: HashSet<(String, String)>,
+}
+
+impl Visit<'_> for TraitImplFinder {
+    fn visit_item_impl(&mut self, item: &syn::ItemImpl) {
+        if let Some((_, path, _)) = &item.trait_ {
+            let trait_name = path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string());
+            if let (Some(trait_name), Some(type_name)) = (trait_name, type_name(&item.self_ty)) {
+                self.impls.insert((trait_name, type_name));
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+}
+
+/// Last path segment of a type, e.g. `Result` for `std::io::Result<()>`
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a type is `String`, `str`, or a reference to either
+fn is_string(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => is_string(&reference.elem),
+        ty => type_name(ty).is_some_and(|name| name == "String" || name == "str"),
+    }
+}
+
+/// Whether the last word of a snake_case name is one of the enum-like words
+fn names_enum(ident: &syn::Ident, words: &[String]) -> bool {
+    let name = ident.to_string().to_lowercase();
+    let last = name.rsplit('_').next().unwrap_or(&name);
+    words.iter().any(|word| word == last)
+}
+
+/// Traits named in `#[derive(...)]` attributes, by last path segment
+fn derived_traits(attrs: &[syn::Attribute]) -> HashSet<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violations(source: &str, pattern: &str) -> Vec<String> {
+        let check = ApiCheck::parse(pattern)
+            .expect("pattern should be recognized")
+            .expect("pattern should parse");
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_api_violations(&syntax_tree, &check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(ApiCheck::parse("missing_must_uses").is_none());
+        assert!(ApiCheck::parse("missing_derive:[=Debug").is_some_and(|r| r.is_err()));
+
+        let check = ApiCheck::parse("missing_derive:*Config=Debug, Clone;Eq")
+            .expect("pattern should be recognized")
+            .expect("pattern should parse");
+        assert_eq!(
+            check,
+            ApiCheck::Derives(vec![
+                DeriveRequirement {
+                    types: glob::Pattern::new("*Config").expect("valid glob"),
+                    derives: vec!["Debug".to_string(), "Clone".to_string()],
+                },
+                DeriveRequirement {
+                    types: glob::Pattern::new("*").expect("valid glob"),
+                    derives: vec!["Eq".to_string()],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_missing_must_use() {
+        let source = r#"
+pub fn load() -> Result<Config, Error> { todo!() }
+
+#[must_use]
+pub fn parse() -> io::Result<()> { todo!() }
+
+fn private() -> Result<(), Error> { todo!() }
+
+pub fn count() -> usize { 0 }
+
+impl ClientBuilder {
+    pub fn timeout(self, timeout: Duration) -> Self { self }
+    pub fn build(self) -> GuardianResult<Client> { todo!() }
+}
+
+impl Client {
+    pub fn builder() -> ClientBuilder { todo!() }
+}
+
+impl FromStr for Client {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { todo!() }
+}
+"#;
+        assert_eq!(
+            violations(source, "missing_must_use"),
+            vec![
+                "pub fn load returns Result without #[must_use]",
+                "pub fn timeout returns ClientBuilder without #[must_use]",
+                "pub fn build returns GuardianResult without #[must_use]",
+                "pub fn builder returns ClientBuilder without #[must_use]",
+            ]
+        );
+        assert_eq!(violations(source, "missing_must_use:Result").len(), 1);
+    }
+
+    #[test]
+    fn test_bool_params() {
+        let source = r#"
+pub fn render(path: &Path, verbose: bool, color: bool) {}
+pub fn toggle(enabled: bool) {}
+fn private(a: bool, b: bool) {}
+
+impl Exporter {
+    pub fn export(&self, pretty: bool, compact: &bool, sorted: std::primitive::bool) {}
+}
+
+impl Visitor for Exporter {
+    fn visit(&mut self, first: bool, last: bool) {}
+}
+"#;
+        assert_eq!(
+            violations(source, "bool_params"),
+            vec![
+                "pub fn render takes 2 bool parameters",
+                "pub fn export takes 2 bool parameters",
+            ]
+        );
+        assert!(violations(source, "bool_params:2").is_empty());
+        assert!(ApiCheck::parse("bool_params:two").is_some_and(|r| r.is_err()));
+    }
+
+    #[test]
+    fn test_string_params() {
+        let source = r#"
+pub fn connect(host: &str, user: &str, password: String, port: u16) {}
+pub fn rename(from: &str, port: u16, to: &str) {}
+
+impl Client {
+    pub fn login(&self, user: &String, token: &'static str, realm: &str) {}
+}
+"#;
+        assert_eq!(
+            violations(source, "string_params"),
+            vec![
+                "pub fn connect takes 3 string parameters in a row",
+                "pub fn login takes 3 string parameters in a row",
+            ]
+        );
+        assert!(violations(source, "string_params:3").is_empty());
+    }
+
+    #[test]
+    fn test_stringly_typed() {
+        let source = r#"
+pub fn set_mode(value: &str) {}
+pub fn export(path: &str, output_format: String, verbose: bool) {}
+pub fn describe(name: &str, kind: Kind) {}
+fn private(mode: &str) {}
+
+pub struct Job {
+    pub kind: String,
+    pub name: String,
+    state: String,
+}
+"#;
+        assert_eq!(
+            violations(source, "stringly_typed"),
+            vec![
+                "pub fn set_mode takes value: &str",
+                "pub fn export takes output_format: String",
+                "pub struct Job has field kind: String",
+            ]
+        );
+        assert_eq!(
+            violations(source, "stringly_typed:name"),
+            vec![
+                "pub fn describe takes name: &str",
+                "pub struct Job has field name: String",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_derives() {
+        let source = r#"
+#[derive(Debug, Clone)]
+pub struct AppConfig;
+
+pub struct ServerConfig;
+
+pub enum Mode { Fast }
+
+struct Private;
+
+#[derive(serde::Serialize)]
+pub struct Token;
+
+impl std::fmt::Debug for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+}
+"#;
+        assert_eq!(
+            violations(source, "missing_derive"),
+            vec![
+                "pub struct ServerConfig does not derive Debug",
+                "pub enum Mode does not derive Debug",
+            ]
+        );
+        assert_eq!(
+            violations(source, "missing_derive:*Config=Debug,Clone;Debug"),
+            vec![
+                "pub struct ServerConfig does not derive Debug, Clone",
+                "pub enum Mode does not derive Debug",
+            ]
+        );
+    }
+}
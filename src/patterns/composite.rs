@@ -0,0 +1,373 @@
+//! Composite rules combining several sub-patterns over one unit of code
+//!
+//! Architecture: Composite Pattern - A rule is assembled from conditions run by ordinary matchers
+//! - Each condition is compiled into its own PatternEngine, so every rule type can take part
+//! - Conditions are combined per unit: each function, or the whole file
+//! - A unit is reported when every `all_of`, some `any_of` and no `none_of` condition matches in it
+
+use super::{FileContext, PatternEngine, SourceLocation};
+use crate::config::{PatternRule, RuleCondition, RuleType};
+use crate::domain::violations::{GuardianError, GuardianResult, Severity};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Part of a file over which the conditions of a composite rule are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeUnit {
+    /// Each free function, method and default trait method, attributes included
+    Function,
+    /// The whole file
+    File,
+}
+
+impl CompositeUnit {
+    /// Parse the `pattern` of a composite rule
+    pub fn parse(pattern: &str) -> Option<Self> {
+        match pattern.trim() {
+            "function" | "fn" => Some(Self::Function),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+}
+
+/// Compiled conditions of a composite rule
+#[derive(Debug)]
+pub struct CompositeMatcher {
+    unit: CompositeUnit,
+    all_of: Vec<PatternEngine>,
+    any_of: Vec<PatternEngine>,
+    none_of: Vec<PatternEngine>,
+}
+
+/// A unit in which the conditions of a composite rule hold
+#[derive(Debug, Clone)]
+pub struct CompositeHit {
+    /// Where to report the unit: a function's name, or the first matching condition in a file
+    pub location: SourceLocation,
+    /// Name of the function, for function units
+    pub function: Option<String>,
+    /// Source line at the reported location
+    pub context: String,
+}
+
+/// Lines a unit spans, with the place it is reported at
+struct Unit {
+    lines: std::ops::RangeInclusive<u32>,
+    anchor: Option<(SourceLocation, String)>,
+}
+
+impl CompositeMatcher {
+    /// Compile the conditions of a composite rule
+    pub fn compile(rule: &PatternRule) -> GuardianResult<Self> {
+        let unit = CompositeUnit::parse(&rule.pattern).ok_or_else(|| {
+            GuardianError::pattern(format!(
+                "Composite rule '{}' must use pattern 'function' or 'file', not '{}'",
+                rule.id, rule.pattern
+            ))
+        })?;
+        if rule.all_of.is_empty() && rule.any_of.is_empty() {
+            return Err(GuardianError::pattern(format!(
+                "Composite rule '{}' needs at least one all_of or any_of condition",
+                rule.id
+            )));
+        }
+
+        Self::with_unit(rule, unit)
+    }
+
+    /// Compile the conditions of any rule over whole files
+    ///
+    /// Unlike composite rules, the conditions may all be `none_of`, or absent, in which case
+    /// every file is a hit.
+    pub fn file_conditions(rule: &PatternRule) -> GuardianResult<Self> {
+        Self::with_unit(rule, CompositeUnit::File)
+    }
+
+    fn with_unit(rule: &PatternRule, unit: CompositeUnit) -> GuardianResult<Self> {
+        let compile = |conditions: &[RuleCondition]| {
+            conditions
+                .iter()
+                .map(|condition| compile_condition(&rule.id, condition))
+                .collect::<GuardianResult<Vec<_>>>()
+        };
+
+        Ok(Self {
+            unit,
+            all_of: compile(&rule.all_of)?,
+            any_of: compile(&rule.any_of)?,
+            none_of: compile(&rule.none_of)?,
+        })
+    }
+
+    /// Find the units of a file in which the conditions hold, in source order
+    pub fn find(&self, file: &FileContext) -> GuardianResult<Vec<CompositeHit>> {
+        let units = match self.unit {
+            CompositeUnit::File => vec![Unit {
+                lines: 1..=u32::MAX,
+                anchor: None,
+            }],
+            CompositeUnit::Function => match file.syntax_tree() {
+                Some(tree) => {
+                    let mut collector = FunctionCollector {
+                        file,
+                        units: Vec::new(),
+                    };
+                    collector.visit_file(tree);
+                    collector.units
+                }
+                None => return Ok(Vec::new()),
+            },
+        };
+        if units.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Each condition runs once per file; units only look up the lines it matched
+        let matched_lines = |engines: &[PatternEngine]| {
+            engines
+                .iter()
+                .map(|engine| {
+                    let mut matches = engine.analyze_context(file)?;
+                    matches.sort_by_key(|m| m.byte_range.as_ref().map(|range| range.start));
+                    Ok(matches
+                        .into_iter()
+                        .filter_map(|m| {
+                            let line = m.line_number?;
+                            let range = m.byte_range?;
+                            Some((line, range))
+                        })
+                        .collect::<Vec<_>>())
+                })
+                .collect::<GuardianResult<Vec<_>>>()
+        };
+        let all_of = matched_lines(&self.all_of)?;
+        let any_of = matched_lines(&self.any_of)?;
+        let none_of = matched_lines(&self.none_of)?;
+
+        let mut hits = Vec::new();
+        for unit in units {
+            let first_in_unit = |matches: &Vec<(u32, std::ops::Range<usize>)>| {
+                matches
+                    .iter()
+                    .find(|(line, _)| unit.lines.contains(line))
+                    .map(|(_, range)| range.clone())
+            };
+
+            let required: Vec<_> = all_of.iter().map(first_in_unit).collect();
+            let alternatives: Vec<_> = any_of.iter().filter_map(first_in_unit).collect();
+            let holds = required.iter().all(Option::is_some)
+                && (any_of.is_empty() || !alternatives.is_empty())
+                && none_of
+                    .iter()
+                    .all(|matches| first_in_unit(matches).is_none());
+            if !holds {
+                continue;
+            }
+
+            let (location, function) = match unit.anchor {
+                Some((location, function)) => (location, Some(function)),
+                None => {
+                    let range = required
+                        .into_iter()
+                        .flatten()
+                        .chain(alternatives)
+                        .min_by_key(|range| range.start)
+                        .unwrap_or(0..0);
+                    (file.location(range), None)
+                }
+            };
+            hits.push(CompositeHit {
+                context: file.line_text(location.line).trim().to_string(),
+                location,
+                function,
+            });
+        }
+
+        Ok(hits)
+    }
+}
+
+/// Compile one condition as a single-rule engine
+fn compile_condition(rule_id: &str, condition: &RuleCondition) -> GuardianResult<PatternEngine> {
+    if matches!(
+        condition.rule_type,
+        RuleType::Composite | RuleType::FilePair
+    ) {
+        return Err(GuardianError::pattern(format!(
+            "Rule '{rule_id}' cannot use composite or file_pair rules as conditions"
+        )));
+    }
+
+    let rule = PatternRule {
+        id: rule_id.to_string(),
+        rule_type: condition.rule_type.clone(),
+        pattern: condition.pattern.clone(),
+        message: String::new(),
+        severity: None,
+        enabled: true,
+        case_sensitive: condition.case_sensitive,
+        exclude_if: None,
+        tags: Vec::new(),
+        docs_url: None,
+        rationale: None,
+        applies_to: Vec::new(),
+        deprecated: false,
+        replaced_by: None,
+        only_cfg: None,
+        all_of: Vec::new(),
+        any_of: Vec::new(),
+        none_of: Vec::new(),
+        near: None,
+        good_example: None,
+        bad_example: None,
+        fix: None,
+    };
+    let mut engine = PatternEngine::new();
+    engine.add_rule(&rule, Severity::Info)?;
+    Ok(engine)
+}
+
+/// Collects every function with a body as a unit
+struct FunctionCollector<'a> {
+    file: &'a FileContext<'a>,
+    units: Vec<Unit>,
+}
+
+impl FunctionCollector<'_> {
+    fn push(&mut self, item: &impl Spanned, ident: &syn::Ident) {
+        let span = self.file.span_location(item.span());
+        self.units.push(Unit {
+            lines: span.line..=span.end_line,
+            anchor: Some((self.file.span_location(ident.span()), ident.to_string())),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for FunctionCollector<'_> {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.push(node, &node.sig.ident);
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.push(node, &node.sig.ident);
+        syn::visit::visit_impl_item_fn(self, node);
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast syn::TraitItemFn) {
+        if node.default.is_some() {
+            self.push(node, &node.sig.ident);
+        }
+        syn::visit::visit_trait_item_fn(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn condition(rule_type: RuleType, pattern: &str) -> RuleCondition {
+        RuleCondition {
+            rule_type,
+            pattern: pattern.to_string(),
+            case_sensitive: true,
+        }
+    }
+
+    fn composite(pattern: &str) -> PatternRule {
+        let mut rule = template();
+        rule.rule_type = RuleType::Composite;
+        rule.pattern = pattern.to_string();
+        rule
+    }
+
+    fn template() -> PatternRule {
+        crate::config::GuardianConfig::default()
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .find(|rule| rule.id == "todo_comments")
+            .expect("default config should define todo_comments")
+            .clone()
+    }
+
+    const SOURCE: &str = r#"
+async fn load() -> String {
+    std::fs::read_to_string("a").unwrap_or_default()
+}
+
+async fn fetch() {
+    todo!()
+}
+
+#[tokio::test]
+async fn reads_fixture() {
+    std::fs::read("fixture").ok();
+}
+
+fn sync_read() {
+    std::fs::read("b").ok();
+}
+"#;
+
+    #[test]
+    fn test_function_units() {
+        let mut rule = composite("function");
+        rule.all_of = vec![
+            condition(RuleType::Regex, r"\basync\s+fn\b"),
+            condition(RuleType::Regex, r"std::fs::"),
+        ];
+        rule.none_of = vec![condition(RuleType::Regex, r"#\[(tokio::)?test\]")];
+
+        let matcher = CompositeMatcher::compile(&rule).expect("rule should compile");
+        let hits = matcher
+            .find(&FileContext::new(Path::new("src/lib.rs"), SOURCE))
+            .expect("analysis should succeed");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].function.as_deref(), Some("load"));
+        assert_eq!((hits[0].location.line, hits[0].location.column), (2, 10));
+    }
+
+    #[test]
+    fn test_any_of_and_file_units() {
+        let mut rule = composite("file");
+        rule.any_of = vec![
+            condition(RuleType::Ast, "macro_call:todo"),
+            condition(RuleType::Regex, "FIXME"),
+        ];
+        rule.none_of = vec![condition(
+            RuleType::Regex,
+            "#!\\[allow\\(placeholders\\)\\]",
+        )];
+
+        let matcher = CompositeMatcher::compile(&rule).expect("rule should compile");
+        let hits = matcher
+            .find(&FileContext::new(Path::new("src/lib.rs"), SOURCE))
+            .expect("analysis should succeed");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].location.line, 7);
+        assert_eq!(hits[0].context, "todo!()");
+
+        let allowed = format!("#![allow(placeholders)]\n{SOURCE}");
+        let hits = matcher
+            .find(&FileContext::new(Path::new("src/lib.rs"), &allowed))
+            .expect("analysis should succeed");
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_composites() {
+        assert!(CompositeMatcher::compile(&composite("function")).is_err());
+
+        let mut rule = composite("module");
+        rule.all_of = vec![condition(RuleType::Regex, "x")];
+        assert!(CompositeMatcher::compile(&rule).is_err());
+
+        let mut rule = composite("file");
+        rule.all_of = vec![condition(RuleType::Composite, "file")];
+        assert!(CompositeMatcher::compile(&rule).is_err());
+    }
+}
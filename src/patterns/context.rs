@@ -0,0 +1,411 @@
+//! Per-file analysis context shared by every rule
+//!
+//! Architecture: Value Objects - FileContext is computed once per file and handed to all rules
+//! - The line index, comment/string map, syntax tree and target info are derived lazily
+//! - Each derived view is computed at most once, however many rules ask for it
+//! - Rules resolve locations through the context instead of rescanning the content
+
+use crate::patterns::location::{LineIndex, SourceLocation};
+use crate::patterns::syntax_cache::SyntaxTreeCache;
+use proc_macro2::Span;
+use std::cell::OnceCell;
+use std::ops::Range;
+use std::path::{Component, Path};
+use std::rc::Rc;
+
+/// Kind of Cargo target a file belongs to, inferred from its path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    /// Under `src/`, other than binary entry points
+    Library,
+    /// `src/main.rs` or under `src/bin/`
+    Binary,
+    /// Under `tests/`
+    Test,
+    /// Under `examples/`
+    Example,
+    /// Under `benches/`
+    Bench,
+    /// The crate's `build.rs`
+    BuildScript,
+    /// Outside any recognized target directory
+    Unknown,
+}
+
+/// Crate and target a file belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetInfo {
+    /// Crate name from the nearest `Cargo.toml`, with `-` replaced by `_`
+    pub crate_name: Option<String>,
+    /// Kind of target within the crate
+    pub kind: TargetKind,
+}
+
+impl TargetInfo {
+    /// Infer the crate and target of a file from the nearest manifest above it
+    pub fn for_path(path: &Path) -> Self {
+        let crate_dir = path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.join("Cargo.toml").is_file());
+
+        let relative = match crate_dir {
+            Some(dir) => path.strip_prefix(dir).unwrap_or(path),
+            None => path,
+        };
+
+        Self {
+            crate_name: crate_dir.map(crate::graph::crate_name_for),
+            kind: target_kind(relative),
+        }
+    }
+}
+
+/// Comment and string literal regions of a Rust file, as sorted byte ranges
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeMap {
+    comments: Vec<Range<usize>>,
+    strings: Vec<Range<usize>>,
+}
+
+impl CodeMap {
+    /// Classify the comments and string literals of Rust source
+    pub fn new(content: &str) -> Self {
+        let bytes = content.as_bytes();
+        let mut map = Self::default();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let start = i;
+            match bytes[i] {
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = content[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                    map.comments.push(start..i);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = block_comment_end(bytes, i);
+                    map.comments.push(start..i);
+                }
+                b'"' => {
+                    i = quoted_end(bytes, i + 1, b'"');
+                    map.strings.push(start..i);
+                }
+                b'\'' => match char_literal_end(content, i) {
+                    Some(end) => {
+                        map.strings.push(start..end);
+                        i = end;
+                    }
+                    // A lifetime or label
+                    None => i += 1,
+                },
+                byte if byte == b'_' || byte.is_ascii_alphanumeric() => {
+                    match raw_string_end(bytes, i) {
+                        Some(end) => {
+                            map.strings.push(start..end);
+                            i = end;
+                        }
+                        None => {
+                            while i < bytes.len()
+                                && (bytes[i] == b'_' || bytes[i].is_ascii_alphanumeric())
+                            {
+                                i += 1;
+                            }
+                        }
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+
+        map
+    }
+
+    /// Whether a byte offset lies inside a comment
+    pub fn is_comment(&self, offset: usize) -> bool {
+        contains(&self.comments, offset)
+    }
+
+    /// Whether a byte offset lies inside a string or character literal
+    pub fn is_string(&self, offset: usize) -> bool {
+        contains(&self.strings, offset)
+    }
+
+    /// Whether a byte offset lies in code, outside comments and literals
+    pub fn is_code(&self, offset: usize) -> bool {
+        !self.is_comment(offset) && !self.is_string(offset)
+    }
+
+    /// Byte ranges of all comments, in file order
+    pub fn comments(&self) -> &[Range<usize>] {
+        &self.comments
+    }
+
+    /// Byte ranges of all string and character literals, in file order
+    pub fn strings(&self) -> &[Range<usize>] {
+        &self.strings
+    }
+}
+
+/// A source file and everything derived from it during analysis
+///
+/// Derived views are computed on first use and shared by the configured patterns, the Rust
+/// analyzer and procedural rules, so no rule re-parses the file or rescans it for locations.
+pub struct FileContext<'a> {
+    path: &'a Path,
+    content: &'a str,
+    line_index: OnceCell<LineIndex>,
+    code_map: OnceCell<CodeMap>,
+    syntax_tree: OnceCell<Option<Rc<syn::File>>>,
+    syntax_trees: Option<&'a SyntaxTreeCache>,
+    target: OnceCell<TargetInfo>,
+}
+
+impl<'a> FileContext<'a> {
+    /// Describe a file and its decoded content
+    pub fn new(path: &'a Path, content: &'a str) -> Self {
+        Self {
+            path,
+            content,
+            line_index: OnceCell::new(),
+            code_map: OnceCell::new(),
+            syntax_tree: OnceCell::new(),
+            syntax_trees: None,
+            target: OnceCell::new(),
+        }
+    }
+
+    /// Take the syntax tree from a cache of trees parsed by earlier runs, when given one
+    pub fn with_syntax_trees(mut self, syntax_trees: Option<&'a SyntaxTreeCache>) -> Self {
+        self.syntax_trees = syntax_trees;
+        self
+    }
+
+    /// Path of the file
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    /// Decoded file content
+    pub fn content(&self) -> &'a str {
+        self.content
+    }
+
+    /// Whether the file is Rust source
+    pub fn is_rust(&self) -> bool {
+        self.path.extension().and_then(|ext| ext.to_str()) == Some("rs")
+    }
+
+    /// Line start offsets of the content
+    pub fn line_index(&self) -> &LineIndex {
+        self.line_index.get_or_init(|| LineIndex::new(self.content))
+    }
+
+    /// Text of a 1-indexed line without its line terminator
+    pub fn line_text(&self, line: u32) -> &'a str {
+        self.line_index().line_text(self.content, line as usize)
+    }
+
+    /// Resolve a byte range of the content to a location
+    pub fn location(&self, range: Range<usize>) -> SourceLocation {
+        self.line_index().location(self.content, range)
+    }
+
+    /// Resolve a span of the syntax tree to a location
+    pub fn span_location(&self, span: Span) -> SourceLocation {
+        self.line_index().span_location(self.content, span)
+    }
+
+    /// Comment and string literal regions; empty for non-Rust files
+    pub fn code_map(&self) -> &CodeMap {
+        self.code_map.get_or_init(|| {
+            if self.is_rust() {
+                CodeMap::new(self.content)
+            } else {
+                CodeMap::default()
+            }
+        })
+    }
+
+    /// Parsed syntax tree, or `None` for non-Rust files and files that do not parse
+    pub fn syntax_tree(&self) -> Option<&syn::File> {
+        self.syntax_tree
+            .get_or_init(|| {
+                if !self.is_rust() {
+                    return None;
+                }
+                match self.syntax_trees {
+                    Some(syntax_trees) => syntax_trees.parse(self.path, self.content),
+                    None => parse_syntax_tree(self.path, self.content).map(Rc::new),
+                }
+            })
+            .as_deref()
+    }
+
+    /// Crate and target the file belongs to
+    pub fn target(&self) -> &TargetInfo {
+        self.target.get_or_init(|| TargetInfo::for_path(self.path))
+    }
+}
+
+/// Parse Rust source, or `None` if it does not parse
+pub(crate) fn parse_syntax_tree(path: &Path, content: &str) -> Option<syn::File> {
+    match syn::parse_file(content) {
+        Ok(tree) => Some(tree),
+        Err(e) => {
+            // Rules skip AST analysis for the file, but the run doesn't fail
+            tracing::debug!("Failed to parse Rust file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Infer the target kind from a path relative to its crate directory
+fn target_kind(relative: &Path) -> TargetKind {
+    let components: Vec<&str> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    match components.as_slice() {
+        ["build.rs"] => TargetKind::BuildScript,
+        ["src", "main.rs"] | ["src", "bin", ..] => TargetKind::Binary,
+        ["src", ..] => TargetKind::Library,
+        ["tests", ..] => TargetKind::Test,
+        ["examples", ..] => TargetKind::Example,
+        ["benches", ..] => TargetKind::Bench,
+        _ => TargetKind::Unknown,
+    }
+}
+
+/// Whether any of the sorted, non-overlapping ranges contains an offset
+fn contains(ranges: &[Range<usize>], offset: usize) -> bool {
+    let index = ranges.partition_point(|range| range.end <= offset);
+    ranges.get(index).is_some_and(|range| range.start <= offset)
+}
+
+/// End of a (possibly nested) block comment starting at `start`
+fn block_comment_end(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    bytes.len()
+}
+
+/// End (past the closing quote) of an escaped literal whose body starts at `start`
+fn quoted_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            byte if byte == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// End of a character literal at `start`, or `None` for a lifetime or label
+fn char_literal_end(content: &str, start: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    if bytes.get(start + 1) == Some(&b'\\') {
+        return Some(quoted_end(bytes, start + 1, b'\''));
+    }
+
+    let ch = content.get(start + 1..)?.chars().next()?;
+    let close = start + 1 + ch.len_utf8();
+    (bytes.get(close) == Some(&b'\'')).then_some(close + 1)
+}
+
+/// End of a raw string (`r"…"`, `br#"…"#`, …) starting at `start`, if one starts there
+fn raw_string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    if bytes[i] == b'b' || bytes[i] == b'c' {
+        i += 1;
+    }
+    if bytes.get(i) != Some(&b'r') {
+        return None;
+    }
+    i += 1;
+
+    let hashes = bytes[i..].iter().take_while(|&&byte| byte == b'#').count();
+    i += hashes;
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+
+    while i < bytes.len() {
+        if bytes[i] == b'"'
+            && bytes[i + 1..]
+                .iter()
+                .take_while(|&&byte| byte == b'#')
+                .count()
+                >= hashes
+        {
+            return Some(i + 1 + hashes);
+        }
+        i += 1;
+    }
+
+    Some(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index_and_code_map() {
+        let content = "// note: café\nfn main() {\n    let s = \"// not a comment\";\n    let c = '\\'';\n    let r = r#\"a \"quoted\" b\"#; /* outer /* inner */ still */\n    fn f<'a>(x: &'a str) {}\n}\n";
+        let file = FileContext::new(Path::new("src/main.rs"), content);
+
+        assert_eq!(file.line_index().line_count(), 8);
+        assert_eq!(file.line_text(2), "fn main() {");
+        let offset = content.find("let s").expect("let s should be present");
+        assert_eq!(file.line_index().line_column(content, offset), (3, 5));
+        let location = file.location(offset..offset + 5);
+        assert_eq!(
+            location,
+            SourceLocation::from_byte_range(content, offset..offset + 5)
+        );
+
+        let map = file.code_map();
+        assert!(map.is_comment(content.find("café").expect("comment text")));
+        assert!(map.is_string(content.find("// not a").expect("string text")));
+        assert!(map.is_string(content.find("quoted").expect("raw string text")));
+        assert!(map.is_comment(content.find("still").expect("nested comment text")));
+        assert!(map.is_code(content.find("fn f").expect("inner fn")));
+        assert!(map.is_code(content.find("str)").expect("lifetime use")));
+        assert_eq!(map.strings().len(), 3);
+        assert_eq!(map.comments().len(), 2);
+
+        assert!(file.syntax_tree().is_some());
+        assert_eq!(
+            target_kind(Path::new("src/bin/tool.rs")),
+            TargetKind::Binary
+        );
+        assert_eq!(target_kind(Path::new("src/lib.rs")), TargetKind::Library);
+        assert_eq!(target_kind(Path::new("tests/it.rs")), TargetKind::Test);
+        assert_eq!(target_kind(Path::new("build.rs")), TargetKind::BuildScript);
+    }
+}
@@ -0,0 +1,161 @@
+//! File-pair rules: every file matching one template needs a companion file
+//!
+//! Architecture: Specification Pattern - A pair template states which file must accompany another
+//! - Templates are globs with `{name}` captures, e.g. `src/{stem}.rs -> docs/{stem}.md`
+//! - The companion path is built from the captures and looked up on disk
+//! - Checked once per run over all analyzed files, like other cross-file rules
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Separator between the source and companion templates of a pattern
+const PAIR_SEPARATOR: &str = "->";
+
+/// A compiled `source -> companion` template pair
+#[derive(Debug, Clone)]
+pub struct FilePairTemplate {
+    source: Regex,
+    companion: String,
+}
+
+impl FilePairTemplate {
+    /// Parse a `source -> companion` pattern
+    ///
+    /// In the source template, `{name}` captures one path segment or part of one, `*` matches
+    /// within a segment and `**/` any number of directories. The companion template may only use
+    /// `{name}` captures of the source.
+    pub fn parse(pattern: &str) -> GuardianResult<Self> {
+        let (source, companion) = pattern.split_once(PAIR_SEPARATOR).ok_or_else(|| {
+            GuardianError::pattern(format!(
+                "File pair pattern '{pattern}' must have the form 'source -> companion'"
+            ))
+        })?;
+        let (source, companion) = (source.trim(), companion.trim());
+        if source.is_empty() || companion.is_empty() {
+            return Err(GuardianError::pattern(format!(
+                "File pair pattern '{pattern}' needs both a source and a companion template"
+            )));
+        }
+
+        let mut regex = String::from("(?:^|/)");
+        let mut captures = Vec::new();
+        let mut rest = source;
+        while let Some(c) = rest.chars().next() {
+            if let Some(after) = rest.strip_prefix("**/") {
+                regex.push_str("(?:.*/)?");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("**") {
+                regex.push_str(".*");
+                rest = after;
+            } else if c == '*' {
+                regex.push_str("[^/]*");
+                rest = &rest[1..];
+            } else if c == '?' {
+                regex.push_str("[^/]");
+                rest = &rest[1..];
+            } else if c == '{' {
+                let end = rest.find('}').ok_or_else(|| {
+                    GuardianError::pattern(format!("Unclosed capture in '{source}'"))
+                })?;
+                let name = &rest[1..end];
+                let valid = !name.is_empty()
+                    && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+                    && !captures.contains(&name);
+                if !valid {
+                    return Err(GuardianError::pattern(format!(
+                        "Invalid or repeated capture '{{{name}}}' in '{source}'"
+                    )));
+                }
+                regex.push_str(&format!("(?P<{name}>[^/]+?)"));
+                captures.push(name);
+                rest = &rest[end + 1..];
+            } else {
+                regex.push_str(&regex::escape(&c.to_string()));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        regex.push('$');
+
+        let mut unknown = companion.split('{').skip(1).filter_map(|part| {
+            let name = part.split('}').next()?;
+            (!captures.contains(&name)).then_some(name)
+        });
+        if let Some(name) = unknown.next() {
+            return Err(GuardianError::pattern(format!(
+                "Companion template '{companion}' uses '{{{name}}}', which '{source}' does not capture"
+            )));
+        }
+
+        Ok(Self {
+            source: Regex::new(&regex).map_err(|e| GuardianError::pattern(e.to_string()))?,
+            companion: companion.to_string(),
+        })
+    }
+
+    /// Companion a file needs, or `None` if the file does not match the source template
+    ///
+    /// The template may match the end of the path only, so the companion is placed under the
+    /// same leading directories as the file.
+    pub fn companion_for(&self, file: &Path) -> Option<PathBuf> {
+        let path = file.to_string_lossy().replace('\\', "/");
+        let captures = self.source.captures(&path)?;
+        let whole = captures.get(0)?;
+        let prefix = path[..whole.start()].trim_end_matches('/');
+
+        let mut companion = self.companion.clone();
+        for name in self.source.capture_names().flatten() {
+            if let Some(value) = captures.name(name) {
+                companion = companion.replace(&format!("{{{name}}}"), value.as_str());
+            }
+        }
+
+        Some(if prefix.is_empty() && !path.starts_with('/') {
+            PathBuf::from(companion)
+        } else {
+            Path::new(if prefix.is_empty() { "/" } else { prefix }).join(companion)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_companion_paths() {
+        let docs = FilePairTemplate::parse("src/{stem}.rs -> docs/{stem}.md")
+            .expect("template should parse");
+        assert_eq!(
+            docs.companion_for(Path::new("src/parser.rs")),
+            Some(PathBuf::from("docs/parser.md"))
+        );
+        assert_eq!(
+            docs.companion_for(Path::new("./crates/app/src/parser.rs")),
+            Some(PathBuf::from("./crates/app/docs/parser.md"))
+        );
+        assert_eq!(docs.companion_for(Path::new("src/nested/parser.rs")), None);
+        assert_eq!(docs.companion_for(Path::new("mysrc/parser.rs")), None);
+
+        let migrations =
+            FilePairTemplate::parse("migrations/**/{name}_up.sql -> migrations/{name}_down.sql")
+                .expect("template should parse");
+        assert_eq!(
+            migrations.companion_for(Path::new("/repo/migrations/2024/001_users_up.sql")),
+            Some(PathBuf::from("/repo/migrations/001_users_down.sql"))
+        );
+        assert_eq!(
+            migrations.companion_for(Path::new("migrations/002_orders_up.sql")),
+            Some(PathBuf::from("migrations/002_orders_down.sql"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(FilePairTemplate::parse("src/{stem}.rs").is_err());
+        assert!(FilePairTemplate::parse("src/{stem.rs -> docs/{stem}.md").is_err());
+        assert!(FilePairTemplate::parse("src/{stem}.rs -> docs/{name}.md").is_err());
+        assert!(FilePairTemplate::parse("{a}/{a}.rs -> {a}.md").is_err());
+        assert!(FilePairTemplate::parse(" -> docs/x.md").is_err());
+    }
+}
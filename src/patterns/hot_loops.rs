@@ -0,0 +1,274 @@
+//! Detection of allocations inside loops on hot paths
+//!
+//! Architecture: Domain Services - Hot paths are declared by the code or the configuration
+//! - A function is hot when it is `#[inline]`, named `*_hot`, or lives in a configured module
+//! - Only allocations evaluated on every iteration count; a `for` loop's iterator runs once
+//! - Nested functions are judged on their own, closures inherit the loop they run in
+
+use super::scope::{self, Scope, Scoped};
+use proc_macro2::Span;
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Constructors that allocate when called, as `Type::function`
+const ALLOCATING_CONSTRUCTORS: &[(&str, &str)] = &[("Vec", "new"), ("String", "new")];
+
+/// Configuration of the hot loop allocation check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HotLoopCheck {
+    /// Module paths, relative to the crate root, whose functions are all hot
+    pub hot_modules: Vec<Vec<String>>,
+}
+
+impl HotLoopCheck {
+    /// Parse `alloc_in_hot_loop` or `alloc_in_hot_loop:<module>|<module>`
+    ///
+    /// Listed modules include their submodules. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let modules = match pattern.strip_prefix("alloc_in_hot_loop") {
+            Some("") => "",
+            Some(rest) => rest.strip_prefix(':')?,
+            None => return None,
+        };
+
+        Some(Self {
+            hot_modules: super::parse_module_list(modules),
+        })
+    }
+}
+
+/// Find allocations inside loops of hot functions
+///
+/// Returns the allocation span, the allocation as written, and a short description as context.
+pub fn find_hot_loop_allocations(
+    syntax_tree: &syn::File,
+    check: &HotLoopCheck,
+    file_path: &Path,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = HotLoopVisitor {
+        check,
+        scope: Scope::for_file(file_path),
+        hot_function: None,
+        loop_depth: 0,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct HotLoopVisitor<'a> {
+    check: &'a HotLoopCheck,
+    scope: Scope,
+    /// Name of the enclosing function, if it is hot
+    hot_function: Option<String>,
+    loop_depth: usize,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl HotLoopVisitor<'_> {
+    /// Visit a function body with the hotness and loop depth of that function
+    fn visit_function(
+        &mut self,
+        attrs: &[syn::Attribute],
+        sig: &syn::Signature,
+        body: &syn::Block,
+    ) {
+        let name = sig.ident.to_string();
+        let hot = attrs.iter().any(|attr| attr.path().is_ident("inline"))
+            || name.ends_with("_hot")
+            || super::module_listed(&self.check.hot_modules, &self.scope.module);
+
+        let outer_function = std::mem::replace(&mut self.hot_function, hot.then_some(name));
+        let outer_depth = std::mem::replace(&mut self.loop_depth, 0);
+        self.visit_block(body);
+        self.hot_function = outer_function;
+        self.loop_depth = outer_depth;
+    }
+
+    /// Visit the part of a loop evaluated on every iteration
+    fn visit_loop_body(&mut self, body: &syn::Block) {
+        self.loop_depth += 1;
+        self.visit_block(body);
+        self.loop_depth -= 1;
+    }
+
+    fn report(&mut self, span: Span, allocation: String) {
+        if self.loop_depth == 0 {
+            return;
+        }
+        if let Some(function) = &self.hot_function {
+            let context = format!("{allocation} allocates in a loop in hot fn {function}");
+            self.matches.push((span, allocation, context));
+        }
+    }
+}
+
+impl Scoped for HotLoopVisitor<'_> {
+    fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+}
+
+impl Visit<'_> for HotLoopVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &syn::ItemMod) {
+        scope::visit_item_mod(self, item);
+    }
+
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        self.visit_function(&item.attrs, &item.sig, &item.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        self.visit_function(&item.attrs, &item.sig, &item.block);
+    }
+
+    fn visit_expr_loop(&mut self, expr: &syn::ExprLoop) {
+        self.visit_loop_body(&expr.body);
+    }
+
+    fn visit_expr_while(&mut self, expr: &syn::ExprWhile) {
+        self.loop_depth += 1;
+        self.visit_expr(&expr.cond);
+        self.loop_depth -= 1;
+        self.visit_loop_body(&expr.body);
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &syn::ExprForLoop) {
+        // The iterator expression runs once, before the first iteration
+        self.visit_expr(&expr.expr);
+        self.visit_loop_body(&expr.body);
+    }
+
+    fn visit_expr_call(&mut self, call: &syn::ExprCall) {
+        if let syn::Expr::Path(path) = call.func.as_ref() {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            if let [.., owner, function] = segments.as_slice() {
+                if ALLOCATING_CONSTRUCTORS.contains(&(owner.as_str(), function.as_str())) {
+                    self.report(call.span(), format!("{owner}::{function}()"));
+                }
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &syn::ExprMethodCall) {
+        if call.method == "collect" {
+            self.report(call.method.span(), "collect()".to_string());
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &syn::Macro) {
+        if mac.path.is_ident("format") {
+            self.report(mac.span(), "format!".to_string());
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocations(source: &str, check: &HotLoopCheck, path: &str) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_hot_loop_allocations(&syntax_tree, check, Path::new(path))
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            HotLoopCheck::parse("alloc_in_hot_loop"),
+            Some(HotLoopCheck::default())
+        );
+        assert_eq!(
+            HotLoopCheck::parse("alloc_in_hot_loop:crate::render|codec::simd"),
+            Some(HotLoopCheck {
+                hot_modules: vec![
+                    vec!["render".to_string()],
+                    vec!["codec".to_string(), "simd".to_string()],
+                ],
+            })
+        );
+        assert_eq!(HotLoopCheck::parse("alloc_in_hot_loops"), None);
+    }
+
+    #[test]
+    fn test_hot_functions() {
+        let source = r#"
+#[inline]
+fn encode(items: &[Item]) -> usize {
+    let mut total = 0;
+    for item in items.iter().collect::<Vec<_>>() {
+        let name = format!("{}", item.name);
+        let parts: Vec<_> = name.split(',').collect();
+        total += parts.len();
+    }
+    total
+}
+
+fn checksum_hot(data: &[u8]) {
+    while data.iter().map(|b| *b).collect::<Vec<u8>>().is_empty() {
+        let mut buffer = Vec::new();
+        buffer.push(1);
+    }
+}
+
+fn cold(items: &[Item]) {
+    for item in items {
+        let label = String::new();
+    }
+}
+
+#[inline(always)]
+fn no_loop() -> String {
+    format!("{}", 1)
+}
+"#;
+        assert_eq!(
+            allocations(source, &HotLoopCheck::default(), "src/codec.rs"),
+            vec![
+                "format! allocates in a loop in hot fn encode",
+                "collect() allocates in a loop in hot fn encode",
+                "collect() allocates in a loop in hot fn checksum_hot",
+                "Vec::new() allocates in a loop in hot fn checksum_hot",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hot_modules() {
+        let source = r#"
+fn render(rows: &[Row]) {
+    for row in rows {
+        let line = String::new();
+    }
+}
+
+mod cache {
+    fn fill(rows: &[Row]) {
+        loop {
+            let line = String::new();
+        }
+    }
+}
+"#;
+        let check = HotLoopCheck::parse("alloc_in_hot_loop:render::cache").expect("pattern");
+
+        assert_eq!(
+            allocations(source, &check, "src/render.rs"),
+            vec!["String::new() allocates in a loop in hot fn fill"]
+        );
+        assert_eq!(allocations(source, &check, "src/render/cache.rs").len(), 2);
+        assert!(allocations(source, &check, "src/server.rs").is_empty());
+    }
+}
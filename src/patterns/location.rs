@@ -0,0 +1,279 @@
+//! Source locations for pattern matches
+//!
+//! Architectural Principle: Value Objects - SourceLocation pins a match to an exact span of a file
+//! - Resolves regex byte offsets and syn spans into one shared representation
+//! - Lines and columns are 1-indexed and columns count characters, not bytes
+//! - End positions are exclusive, matching SARIF and LSP conventions
+
+use proc_macro2::Span;
+use std::ops::Range;
+
+/// Start and end position of a match within a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Line where the match starts (1-indexed)
+    pub line: u32,
+    /// Column where the match starts (1-indexed, in characters)
+    pub column: u32,
+    /// Line where the match ends (1-indexed)
+    pub end_line: u32,
+    /// Column just past the end of the match (1-indexed, in characters)
+    pub end_column: u32,
+    /// Byte offsets of the match within the file content
+    pub byte_range: Range<usize>,
+}
+
+impl SourceLocation {
+    /// Resolve a byte range (e.g. from a regex match) to a location
+    ///
+    /// Builds a throwaway [`LineIndex`]; resolve many locations in one file through
+    /// [`LineIndex::location`] instead.
+    pub fn from_byte_range(content: &str, range: Range<usize>) -> Self {
+        LineIndex::new(content).location(content, range)
+    }
+
+    /// Resolve a syn/proc-macro2 span to a location
+    ///
+    /// Spans without location information (line 0) resolve to the start of the file.
+    pub fn from_span(content: &str, span: Span) -> Self {
+        LineIndex::new(content).span_location(content, span)
+    }
+}
+
+/// Byte offsets of the start of every line in a file
+///
+/// Built once per file so locations resolve with a binary search instead of a scan
+/// from the start of the content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Index the lines of a file's content
+    pub fn new(content: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { line_starts }
+    }
+
+    /// Number of lines, counting an empty last line after a trailing newline
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Get the 1-indexed line and character column of a byte offset
+    pub fn line_column(&self, content: &str, byte_offset: usize) -> (u32, u32) {
+        let byte_offset = byte_offset.min(content.len());
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= byte_offset);
+        let line_start = self.line_starts[line - 1];
+        let column = content[line_start..]
+            .char_indices()
+            .take_while(|(i, _)| line_start + i < byte_offset)
+            .count();
+
+        (line as u32, column as u32 + 1)
+    }
+
+    /// Get the byte offset of a 1-indexed line and 0-indexed character column
+    ///
+    /// Positions past the end of a line clamp to the line end, and lines past the end of
+    /// the file clamp to the end of the content.
+    pub fn offset(&self, content: &str, line: usize, column: usize) -> usize {
+        let line_start = match self.line_starts.get(line.max(1) - 1) {
+            Some(&start) => start,
+            None => return content.len(),
+        };
+        let line_end = line_end(content, line_start);
+
+        content[line_start..line_end]
+            .char_indices()
+            .nth(column)
+            .map(|(i, _)| line_start + i)
+            .unwrap_or(line_end)
+    }
+
+    /// Text of a 1-indexed line without its line terminator
+    pub fn line_text<'c>(&self, content: &'c str, line: usize) -> &'c str {
+        match self.line_starts.get(line.max(1) - 1) {
+            Some(&start) => content[start..line_end(content, start)].trim_end_matches('\r'),
+            None => "",
+        }
+    }
+
+    /// Resolve a byte range to a location
+    pub fn location(&self, content: &str, range: Range<usize>) -> SourceLocation {
+        let (line, column) = self.line_column(content, range.start);
+        let (end_line, end_column) = self.line_column(content, range.end);
+
+        SourceLocation {
+            line,
+            column,
+            end_line,
+            end_column,
+            byte_range: range,
+        }
+    }
+
+    /// Resolve a syn/proc-macro2 span to a location
+    pub fn span_location(&self, content: &str, span: Span) -> SourceLocation {
+        let start = span.start();
+        let end = span.end();
+
+        if start.line == 0 {
+            return self.location(content, 0..0);
+        }
+
+        let start_offset = self.offset(content, start.line, start.column);
+        let end_offset = self.offset(content, end.line, end.column).max(start_offset);
+
+        SourceLocation {
+            line: start.line as u32,
+            column: start.column as u32 + 1,
+            end_line: end.line.max(start.line) as u32,
+            end_column: end.column as u32 + 1,
+            byte_range: start_offset..end_offset,
+        }
+    }
+}
+
+/// Byte offset of the end of the line starting at `line_start`
+fn line_end(content: &str, line_start: usize) -> usize {
+    content[line_start..]
+        .find('\n')
+        .map(|i| line_start + i)
+        .unwrap_or(content.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::spanned::Spanned;
+    use syn::visit::Visit;
+
+    /// Span of the first macro invocation in a source file
+    fn macro_span(content: &str) -> Span {
+        struct FirstMacro(Option<Span>);
+
+        impl Visit<'_> for FirstMacro {
+            fn visit_macro(&mut self, mac: &syn::Macro) {
+                self.0.get_or_insert(mac.span());
+            }
+        }
+
+        let mut visitor = FirstMacro(None);
+        visitor.visit_file(&syn::parse_file(content).expect("source should parse"));
+        visitor.0.expect("source should contain a macro")
+    }
+
+    fn range_of(content: &str, needle: &str) -> Range<usize> {
+        let start = content.find(needle).expect("content should contain needle");
+        start..start + needle.len()
+    }
+
+    #[test]
+    fn test_byte_range_after_multibyte_characters() {
+        let content = "// café über TODO fix\n";
+        let location = SourceLocation::from_byte_range(content, range_of(content, "TODO"));
+
+        assert_eq!(location.byte_range, 15..19);
+        assert_eq!((location.line, location.column), (1, 14));
+        assert_eq!((location.end_line, location.end_column), (1, 18));
+    }
+
+    #[test]
+    fn test_byte_range_with_crlf_line_endings() {
+        let content = "// first\r\n// TODO second\r\n";
+        let location = SourceLocation::from_byte_range(content, range_of(content, "TODO"));
+
+        assert_eq!((location.line, location.column), (2, 4));
+        assert_eq!((location.end_line, location.end_column), (2, 8));
+        assert_eq!(
+            LineIndex::new(content).line_text(content, 2),
+            "// TODO second"
+        );
+    }
+
+    #[test]
+    fn test_byte_range_at_end_of_file() {
+        let content = "fn main() {}\n// TODO";
+        let location = SourceLocation::from_byte_range(content, range_of(content, "TODO"));
+
+        assert_eq!(location.byte_range, 16..content.len());
+        assert_eq!((location.line, location.column), (2, 4));
+        assert_eq!((location.end_line, location.end_column), (2, 8));
+    }
+
+    #[test]
+    fn test_multi_line_byte_range() {
+        let content = "/* TODO: one\n   two */\n";
+        let location =
+            SourceLocation::from_byte_range(content, range_of(content, "TODO: one\n   two"));
+
+        assert_eq!((location.line, location.column), (1, 4));
+        assert_eq!((location.end_line, location.end_column), (2, 7));
+    }
+
+    #[test]
+    fn test_span_after_multibyte_characters() {
+        let content = "fn f() { let s = \"é\"; todo!() }\n";
+        let location = SourceLocation::from_span(content, macro_span(content));
+
+        assert_eq!(location.byte_range, range_of(content, "todo!()"));
+        assert_eq!((location.line, location.column), (1, 23));
+        assert_eq!((location.end_line, location.end_column), (1, 30));
+    }
+
+    #[test]
+    fn test_multi_line_span_with_crlf_line_endings() {
+        let content = "fn f() {\r\n    todo!(\r\n        \"ü\"\r\n    )\r\n}\r\n";
+        let location = SourceLocation::from_span(content, macro_span(content));
+
+        assert_eq!(
+            location.byte_range,
+            range_of(content, "todo!(\r\n        \"ü\"\r\n    )")
+        );
+        assert_eq!((location.line, location.column), (2, 5));
+        assert_eq!((location.end_line, location.end_column), (4, 6));
+    }
+
+    #[test]
+    fn test_span_at_end_of_file() {
+        let content = "const NAME: &str = \"ß\";\nregister! { NAME }";
+        let location = SourceLocation::from_span(content, macro_span(content));
+
+        assert_eq!(location.byte_range, range_of(content, "register! { NAME }"));
+        assert_eq!(location.byte_range.end, content.len());
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!((location.end_line, location.end_column), (2, 19));
+    }
+
+    #[test]
+    fn test_line_column_and_offset_round_trip() {
+        let content = "// ä\r\nlet ö = 1;\nend";
+        let index = LineIndex::new(content);
+
+        assert_eq!(index.line_count(), 3);
+        for (line, column) in [(1, 0), (1, 3), (2, 0), (2, 4), (2, 5), (3, 3)] {
+            let offset = index.offset(content, line, column);
+            assert_eq!(
+                index.line_column(content, offset),
+                (line as u32, column as u32 + 1),
+                "line {line}, column {column}"
+            );
+        }
+
+        assert_eq!(
+            index.offset(content, 2, 5),
+            content
+                .find(" = 1")
+                .expect("content should contain assignment")
+        );
+        assert_eq!(index.line_column(content, content.len() + 10), (3, 4));
+        assert_eq!(index.offset(content, 9, 0), content.len());
+        assert_eq!(index.offset(content, 3, 99), content.len());
+    }
+}
@@ -0,0 +1,145 @@
+//! Detection of magic numbers
+//!
+//! Architecture: Domain Services - Unexplained numeric literals are found structurally
+//! - Literals of magnitude 0, 1, or 2 are always allowed, larger ones up to a threshold on request
+//! - Const and static items, enum discriminants, and array lengths name or size values already
+//! - Test code is skipped, since expected values are meant to be spelled out there
+
+use super::scope::is_test_code;
+use proc_macro2::Span;
+use syn::visit::Visit;
+
+/// Find numeric literals used inline in expressions
+///
+/// Returns the literal span, its text, and the literal with its enclosing function as context.
+pub fn find_magic_numbers(syntax_tree: &syn::File, threshold: u32) -> Vec<(Span, String, String)> {
+    let mut visitor = MagicNumberVisitor {
+        threshold: f64::from(threshold),
+        current_fn: None,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct MagicNumberVisitor {
+    threshold: f64,
+    current_fn: Option<String>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl Visit<'_> for MagicNumberVisitor {
+    fn visit_item_fn(&mut self, func: &syn::ItemFn) {
+        if is_test_code(&func.attrs) {
+            return;
+        }
+        let previous = self.current_fn.replace(func.sig.ident.to_string());
+        syn::visit::visit_item_fn(self, func);
+        self.current_fn = previous;
+    }
+
+    fn visit_impl_item_fn(&mut self, func: &syn::ImplItemFn) {
+        if is_test_code(&func.attrs) {
+            return;
+        }
+        let previous = self.current_fn.replace(func.sig.ident.to_string());
+        syn::visit::visit_impl_item_fn(self, func);
+        self.current_fn = previous;
+    }
+
+    fn visit_item_mod(&mut self, item_mod: &syn::ItemMod) {
+        if !is_test_code(&item_mod.attrs) {
+            syn::visit::visit_item_mod(self, item_mod);
+        }
+    }
+
+    // Named constants are the fix, not the problem
+    fn visit_item_const(&mut self, _item: &syn::ItemConst) {}
+    fn visit_item_static(&mut self, _item: &syn::ItemStatic) {}
+    fn visit_impl_item_const(&mut self, _item: &syn::ImplItemConst) {}
+    fn visit_trait_item_const(&mut self, _item: &syn::TraitItemConst) {}
+    fn visit_variant(&mut self, _variant: &syn::Variant) {}
+
+    fn visit_type_array(&mut self, array: &syn::TypeArray) {
+        self.visit_type(&array.elem);
+    }
+
+    fn visit_expr_repeat(&mut self, repeat: &syn::ExprRepeat) {
+        self.visit_expr(&repeat.expr);
+    }
+
+    fn visit_lit(&mut self, lit: &syn::Lit) {
+        let value = match lit {
+            syn::Lit::Int(int) => int.base10_parse::<f64>().ok(),
+            syn::Lit::Float(float) => float.base10_parse::<f64>().ok(),
+            _ => None,
+        };
+
+        // 0, 1, and 2 are never magic
+        match value {
+            Some(value) if value > self.threshold.max(2.0) => {}
+            _ => return,
+        }
+
+        let text = quote::ToTokens::to_token_stream(lit).to_string();
+        let context = match &self.current_fn {
+            Some(fn_name) => format!("{} in fn {}", text, fn_name),
+            None => text.clone(),
+        };
+        self.matches.push((lit.span(), text, context));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magic_numbers(source: &str, threshold: u32) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_magic_numbers(&syntax_tree, threshold)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    #[test]
+    fn test_constants_array_lengths_and_tests_skipped() {
+        let source = r#"
+const TIMEOUT_SECS: u64 = 30;
+
+enum Code {
+    NotFound = 404,
+}
+
+fn schedule(buffer: [u8; 64]) -> u64 {
+    let padding = [0u8; 16];
+    let retries = 2;
+    TIMEOUT_SECS * 60 + retries
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_schedule() {
+        assert_eq!(super::schedule([0; 64]), 1802);
+    }
+}
+"#;
+        assert_eq!(magic_numbers(source, 2), vec!["60 in fn schedule"]);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let source = r#"
+struct Backoff;
+
+impl Backoff {
+    fn delay(attempt: u32) -> f64 {
+        attempt as f64 * 1.5 + 10.0
+    }
+}
+"#;
+        assert_eq!(magic_numbers(source, 2), vec!["10.0 in fn delay"]);
+        assert!(magic_numbers(source, 10).is_empty());
+    }
+}
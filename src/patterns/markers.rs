@@ -0,0 +1,193 @@
+//! Attribute-driven placeholder detection
+//!
+//! Architecture: Domain Services - Explicit incompleteness markers are tracked like any other finding
+//! - Teams annotate known-incomplete items (`#[guardian::stub]`, `#[deprecated(note = "stub")]`)
+//! - A marker names an attribute path and, optionally, text its arguments must contain
+//! - Every annotated item is reported once per matching attribute
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// An attribute that marks an item as a known placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeMarker {
+    /// Attribute path, e.g. `guardian::stub` or `deprecated`
+    pub path: String,
+    /// Text the attribute arguments must contain, e.g. `stub`
+    pub contains: Option<String>,
+}
+
+impl AttributeMarker {
+    /// Parse a `|`-separated marker list such as `guardian::stub|deprecated(stub)`
+    ///
+    /// Surrounding `#[` `]` are accepted, so `#[guardian::stub]` works as written in code.
+    pub fn parse_list(list: &str) -> Vec<Self> {
+        list.split('|')
+            .map(str::trim)
+            .map(|spec| {
+                spec.strip_prefix("#[")
+                    .and_then(|spec| spec.strip_suffix(']'))
+                    .unwrap_or(spec)
+            })
+            .filter(|spec| !spec.is_empty())
+            .map(|spec| match spec.split_once('(') {
+                Some((path, args)) => Self {
+                    path: normalize_path(path),
+                    contains: Some(args.trim_end_matches(')').trim().to_string())
+                        .filter(|text| !text.is_empty()),
+                },
+                None => Self {
+                    path: normalize_path(spec),
+                    contains: None,
+                },
+            })
+            .collect()
+    }
+
+    fn matches(&self, attr: &syn::Attribute) -> bool {
+        if attribute_path(attr) != self.path {
+            return false;
+        }
+
+        match &self.contains {
+            Some(text) => attribute_arguments(attr).contains(text.as_str()),
+            None => true,
+        }
+    }
+}
+
+/// Find items annotated with any of `markers`
+///
+/// Returns the attribute span, the attribute as written, and the annotated item as context.
+pub fn find_marked_items(
+    syntax_tree: &syn::File,
+    markers: &[AttributeMarker],
+) -> Vec<(Span, String, String)> {
+    let mut visitor = MarkerVisitor {
+        markers,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct MarkerVisitor<'a> {
+    markers: &'a [AttributeMarker],
+    matches: Vec<(Span, String, String)>,
+}
+
+impl MarkerVisitor<'_> {
+    fn check(&mut self, attrs: &[syn::Attribute], item: String) {
+        for attr in attrs {
+            if self.markers.iter().any(|marker| marker.matches(attr)) {
+                let written = attr.to_token_stream().to_string();
+                self.matches.push((attr.span(), written, item.clone()));
+            }
+        }
+    }
+}
+
+impl Visit<'_> for MarkerVisitor<'_> {
+    fn visit_item(&mut self, item: &syn::Item) {
+        let described = match item {
+            syn::Item::Fn(f) => Some((&f.attrs, format!("fn {}", f.sig.ident))),
+            syn::Item::Struct(s) => Some((&s.attrs, format!("struct {}", s.ident))),
+            syn::Item::Enum(e) => Some((&e.attrs, format!("enum {}", e.ident))),
+            syn::Item::Trait(t) => Some((&t.attrs, format!("trait {}", t.ident))),
+            syn::Item::Mod(m) => Some((&m.attrs, format!("mod {}", m.ident))),
+            syn::Item::Const(c) => Some((&c.attrs, format!("const {}", c.ident))),
+            syn::Item::Static(s) => Some((&s.attrs, format!("static {}", s.ident))),
+            syn::Item::Type(t) => Some((&t.attrs, format!("type {}", t.ident))),
+            syn::Item::Impl(i) => Some((&i.attrs, format!("impl {}", i.self_ty.to_token_stream()))),
+            _ => None,
+        };
+        if let Some((attrs, name)) = described {
+            self.check(attrs, name);
+        }
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_impl_item_fn(&mut self, method: &syn::ImplItemFn) {
+        self.check(&method.attrs, format!("fn {}", method.sig.ident));
+        syn::visit::visit_impl_item_fn(self, method);
+    }
+
+    fn visit_trait_item_fn(&mut self, method: &syn::TraitItemFn) {
+        self.check(&method.attrs, format!("fn {}", method.sig.ident));
+        syn::visit::visit_trait_item_fn(self, method);
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.split("::")
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn attribute_path(attr: &syn::Attribute) -> String {
+    attr.path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn attribute_arguments(attr: &syn::Attribute) -> String {
+    match &attr.meta {
+        syn::Meta::Path(_) => String::new(),
+        syn::Meta::List(list) => list.tokens.to_string(),
+        syn::Meta::NameValue(name_value) => name_value.value.to_token_stream().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_markers() {
+        let markers = AttributeMarker::parse_list("#[guardian::stub] | deprecated(stub)");
+        assert_eq!(
+            markers,
+            [
+                AttributeMarker {
+                    path: "guardian::stub".to_string(),
+                    contains: None,
+                },
+                AttributeMarker {
+                    path: "deprecated".to_string(),
+                    contains: Some("stub".to_string()),
+                },
+            ]
+        );
+
+        let syntax_tree = syn::parse_file(
+            r#"
+#[guardian::stub]
+fn pending() {}
+
+#[deprecated(note = "stub until v2")]
+struct Legacy;
+
+#[deprecated(note = "use Modern")]
+struct Retired;
+
+impl Legacy {
+    #[guardian::stub]
+    fn convert(&self) {}
+}
+"#,
+        )
+        .expect("source should parse");
+
+        let items: Vec<String> = find_marked_items(&syntax_tree, &markers)
+            .into_iter()
+            .map(|(_, _, item)| item)
+            .collect();
+        assert_eq!(items, ["fn pending", "struct Legacy", "fn convert"]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Detection of oversized `match` expressions
+//!
+//! Architecture: Domain Services - Dispatch by `match` is measured where it grows
+//! - A match with many arms is a lookup table or a missing trait written out by hand
+//! - A long arm hides a function that deserves a name; it is measured like a function body
+//! - Cyclomatic complexity counts arms too, but reports the whole function rather than the match
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Which size limit a rule enforces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchCheck {
+    /// Matches with more than the given number of arms
+    ArmsGt(u32),
+    /// Arms whose body spans more than the given number of code lines
+    ArmLinesGt(u32),
+}
+
+/// Find matches, or match arms, over the configured size
+///
+/// Returns the reported span, the measured size, and a short description as context.
+pub fn find_large_matches(
+    syntax_tree: &syn::File,
+    content: &str,
+    check: MatchCheck,
+) -> Vec<(Span, u32, String)> {
+    let mut visitor = MatchVisitor {
+        lines: content.lines().collect(),
+        check,
+        function: None,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct MatchVisitor<'a> {
+    lines: Vec<&'a str>,
+    check: MatchCheck,
+    function: Option<String>,
+    matches: Vec<(Span, u32, String)>,
+}
+
+impl MatchVisitor<'_> {
+    fn report(&mut self, span: Span, size: u32, what: String) {
+        let context = match &self.function {
+            Some(function) => format!("{what} in fn {function}"),
+            None => what,
+        };
+        self.matches.push((span, size, context));
+    }
+
+    /// Code lines of an arm body; a block counts the lines between its braces, like a function
+    fn arm_lines(&self, body: &syn::Expr) -> u32 {
+        let (first, last) = match body {
+            syn::Expr::Block(block) => {
+                let braces = block.block.brace_token.span;
+                let (open, close) = (braces.open().start().line, braces.close().end().line);
+                if open >= close {
+                    return 1;
+                }
+                (open + 1, close - 1)
+            }
+            body => (body.span().start().line, body.span().end().line),
+        };
+
+        let mut in_block_comment = false;
+        self.lines
+            .iter()
+            .take(last)
+            .skip(first.saturating_sub(1))
+            .filter(|line| super::is_code_line(line, &mut in_block_comment))
+            .count() as u32
+    }
+}
+
+impl Visit<'_> for MatchVisitor<'_> {
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_impl_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_expr_match(&mut self, expr: &syn::ExprMatch) {
+        match self.check {
+            MatchCheck::ArmsGt(max) => {
+                let arms = expr.arms.len() as u32;
+                if arms > max {
+                    self.report(
+                        expr.match_token.span,
+                        arms,
+                        format!("match with {arms} arms"),
+                    );
+                }
+            }
+            MatchCheck::ArmLinesGt(max) => {
+                for arm in &expr.arms {
+                    let lines = self.arm_lines(&arm.body);
+                    if lines > max {
+                        let what = format!("match arm of {lines} lines");
+                        self.report(arm.pat.span(), lines, what);
+                    }
+                }
+            }
+        }
+        syn::visit::visit_expr_match(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large_matches(source: &str, check: MatchCheck) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_large_matches(&syntax_tree, source, check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+fn handle(command: Command) -> u32 {
+    match command {
+        Command::Start => 1,
+        Command::Stop => {
+            // Flush before stopping
+            flush();
+
+            stop();
+            2
+        }
+        Command::Restart => restart(
+            true,
+            false,
+        ),
+        Command::Status => match status() {
+            Status::Up => 3,
+            Status::Down => 4,
+        },
+    }
+}
+"#;
+
+    #[test]
+    fn test_match_arms() {
+        assert_eq!(
+            large_matches(SOURCE, MatchCheck::ArmsGt(3)),
+            vec!["match with 4 arms in fn handle"]
+        );
+        assert_eq!(large_matches(SOURCE, MatchCheck::ArmsGt(1)).len(), 2);
+    }
+
+    #[test]
+    fn test_match_arm_lines() {
+        assert_eq!(
+            large_matches(SOURCE, MatchCheck::ArmLinesGt(2)),
+            vec![
+                "match arm of 3 lines in fn handle",
+                "match arm of 4 lines in fn handle",
+                "match arm of 4 lines in fn handle",
+            ]
+        );
+    }
+}
@@ -5,24 +5,104 @@
 //! - Each pattern type implements the PatternMatcher trait for clean polymorphism
 //! - Pattern results are translated to quality violations at the boundary
 
+pub mod api;
+pub mod composite;
+pub mod context;
+pub mod file_pair;
+pub mod hot_loops;
+pub mod location;
+pub mod magic_numbers;
+pub mod markers;
+pub mod match_arms;
+pub mod naming;
 pub mod path_filter;
-
-use crate::config::{ExcludeConditions, PatternRule, RuleType};
-use crate::domain::violations::{GuardianError, GuardianResult, Severity, Violation};
+pub mod polling;
+pub mod proximity;
+pub mod results;
+pub mod rule;
+pub mod scope;
+pub mod shadowing;
+pub mod stubs;
+pub mod syntax_cache;
+pub mod tasks;
+pub mod todo;
+pub mod usage;
+pub mod visibility;
+pub mod wildcards;
+
+use crate::config::{ExcludeConditions, FixTemplate, GuardianConfig, PatternRule, RuleType};
+use crate::domain::violations::{
+    Fix, GuardianError, GuardianResult, RuleUsage, Severity, Violation,
+};
+use crate::graph::{ImportIndex, LiteralIndex, StringLiteral};
+use proc_macro2::Span;
 use regex::{Regex, RegexBuilder};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
 use syn::spanned::Spanned;
-
-pub use path_filter::PathFilter;
+use usage::{RuleRun, UsageRecorder};
+
+pub use api::{ApiCheck, DeriveRequirement};
+pub use composite::{CompositeHit, CompositeMatcher, CompositeUnit};
+pub use context::{CodeMap, FileContext, TargetInfo, TargetKind};
+pub use file_pair::FilePairTemplate;
+pub use hot_loops::HotLoopCheck;
+pub use location::{LineIndex, SourceLocation};
+pub use markers::AttributeMarker;
+pub use match_arms::MatchCheck;
+pub use naming::NamingPolicy;
+pub use path_filter::{
+    Divergence, EquivalenceCheck, IgnoreFileMatch, PathExplanation, PathFilter, PatternOutcome,
+};
+pub use polling::PollingCheck;
+pub use proximity::NearCheck;
+pub use results::IgnoredResultCheck;
+pub use rule::{Rule, RuleInfo};
+pub use shadowing::ShadowingCheck;
+pub use stubs::StubImplCheck;
+pub use syntax_cache::SyntaxTreeCache;
+pub use tasks::DetachedTaskCheck;
+pub use todo::{TodoCheck, TodoCheckKind};
+pub use visibility::VisibilityCheck;
+pub use wildcards::{WildcardCheck, WildcardKind};
 
 /// Core pattern engine that coordinates different types of pattern matching
+///
+/// The engine runs an immutable, shareable [`CompiledRules`] set; the only state it owns
+/// itself is the rule usage tallied by its analyses.
 #[derive(Debug)]
 pub struct PatternEngine {
+    /// Rules the engine runs, possibly shared with other engines
+    rules: Arc<CompiledRules>,
+    /// How many files each rule examined and matched, and how long it took
+    usage: UsageRecorder,
+}
+
+/// Rules compiled from a configuration, plus registered procedural rules
+///
+/// Nothing in a compiled set changes once it is shared, so it is read by any number of
+/// threads without locking. See [`PatternEngine::compile`].
+#[derive(Debug, Default)]
+pub struct CompiledRules {
     /// Compiled regex patterns for fast matching
     regex_patterns: HashMap<String, CompiledRegex>,
+    /// Regexes that every file in scope must contain somewhere
+    required_patterns: HashMap<String, CompiledRegex>,
     /// AST patterns for semantic analysis
     ast_patterns: HashMap<String, AstPattern>,
+    /// Rules combining several sub-patterns
+    composite_patterns: HashMap<String, CompositePattern>,
+    /// Rules requiring companion files, checked across all analyzed files
+    file_pair_patterns: HashMap<String, FilePairPattern>,
+    /// Proximity requirements deciding which matches of a rule are reported
+    near_checks: HashMap<String, NearCheck>,
+    /// Procedural rules registered by downstream crates
+    procedural_rules: Vec<Arc<dyn Rule>>,
+    /// Fingerprint of the configuration the rules were compiled from
+    config_fingerprint: Option<String>,
 }
 
 /// A compiled regex pattern with metadata
@@ -33,6 +113,8 @@ struct CompiledRegex {
     message_template: String,
     severity: Severity,
     exclude_conditions: Option<ExcludeConditions>,
+    scope: Vec<glob::Pattern>,
+    metadata: RuleMetadata,
 }
 
 /// An AST pattern for structural code analysis
@@ -43,6 +125,43 @@ struct AstPattern {
     message_template: String,
     severity: Severity,
     exclude_conditions: Option<ExcludeConditions>,
+    scope: Vec<glob::Pattern>,
+    metadata: RuleMetadata,
+}
+
+/// A composite rule with its compiled conditions
+#[derive(Debug)]
+struct CompositePattern {
+    matcher: CompositeMatcher,
+    rule_id: String,
+    message_template: String,
+    severity: Severity,
+    exclude_conditions: Option<ExcludeConditions>,
+    scope: Vec<glob::Pattern>,
+    metadata: RuleMetadata,
+}
+
+/// A file-pair rule, with the conditions a file must meet to need its companion
+#[derive(Debug)]
+struct FilePairPattern {
+    template: FilePairTemplate,
+    conditions: Option<CompositeMatcher>,
+    rule_id: String,
+    message_template: String,
+    severity: Severity,
+    exclude_conditions: Option<ExcludeConditions>,
+    scope: Vec<glob::Pattern>,
+    metadata: RuleMetadata,
+}
+
+/// Descriptive rule metadata copied onto every violation the rule produces
+#[derive(Debug, Clone, Default)]
+struct RuleMetadata {
+    category: Option<String>,
+    tags: Vec<String>,
+    docs_url: Option<String>,
+    rationale: Option<String>,
+    fix: Option<FixTemplate>,
 }
 
 /// Types of AST patterns we can detect
@@ -50,6 +169,10 @@ struct AstPattern {
 enum AstPatternType {
     /// Look for specific macro calls (unimplemented!, todo!, panic!)
     MacroCall(Vec<String>),
+    /// Look for calls to functions or methods that stand in for real implementations
+    PlaceholderCall(Vec<String>),
+    /// Look for items explicitly annotated as incomplete
+    AttributeMarker(Vec<AttributeMarker>),
     /// Look for functions that return Ok(()) with no meaningful implementation
     EmptyOkReturn,
     /// Look for missing architectural headers in files
@@ -66,6 +189,8 @@ enum AstPatternType {
     FunctionLinesGt(u32),
     NestingDepthGt(u32),
     FunctionArgsGt(u32),
+    /// Matches with too many arms, or arms with too many lines
+    LargeMatch(MatchCheck),
     BlockingCallInAsync,
     FutureNotAwaited,
     SelectWithoutBiased,
@@ -74,6 +199,39 @@ enum AstPatternType {
     ImplWithoutTrait,
     UnsafeBlock,
     IgnoredTestAttribute,
+    /// Identifiers violating a naming policy
+    Naming(NamingPolicy),
+    /// Spawned tasks whose handle is dropped immediately
+    DetachedTask(DetachedTaskCheck),
+    /// Loops polling with fixed sleeps or spinning on a condition
+    Polling(PollingCheck),
+    /// Allocations inside loops of hot functions
+    HotLoopAllocation(HotLoopCheck),
+    /// Public items missing `#[must_use]` or required derives
+    Api(ApiCheck),
+    /// Glob imports or glob re-exports outside preludes and allow-listed modules
+    Wildcard(WildcardCheck),
+    /// Bare `pub` items in modules configured as internal
+    Visibility(VisibilityCheck),
+    /// Methods of configured trait impls that only stand in for an implementation
+    StubImpl(StubImplCheck),
+    /// Calls to fallible functions whose `Result` is discarded
+    IgnoredResult(IgnoredResultCheck),
+    /// Names shadowed too often, or rebound to a different type
+    Shadowing(ShadowingCheck),
+    /// TODO-style comments missing an issue reference or past their deadline
+    TodoMetadata(TodoCheck),
+    /// String or byte-string literals longer than the given number of bytes
+    LargeLiteral(u32),
+    /// String literals containing a base64 run of at least the given length
+    Base64Blob(u32),
+    /// Inline numeric literals with a magnitude above the threshold
+    MagicNumber(u32),
+    /// String literals repeated more than `max_repeats` times across a crate
+    DuplicateStringLiteral {
+        max_repeats: u32,
+        min_length: u32,
+    },
 }
 
 /// A match found by a pattern
@@ -83,19 +241,141 @@ pub struct PatternMatch {
     pub file_path: PathBuf,
     pub line_number: Option<u32>,
     pub column_number: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
     pub matched_text: String,
     pub message: String,
     pub severity: Severity,
     pub context: Option<String>,
+    pub byte_range: Option<std::ops::Range<usize>>,
 }
 
 impl PatternEngine {
     /// Create a new pattern engine
     pub fn new() -> Self {
+        Self::with_rules(Arc::new(CompiledRules::default()))
+    }
+
+    /// Compile the enabled rules of a configuration, once, for any number of engines
+    ///
+    /// The compiled rules are immutable and `Send + Sync`, so long-running modes (daemon,
+    /// language server) can share one set across concurrent analyses instead of compiling
+    /// the configuration per request.
+    pub fn compile(config: &GuardianConfig) -> GuardianResult<Arc<CompiledRules>> {
+        let mut rules = CompiledRules {
+            config_fingerprint: Some(config.fingerprint()),
+            ..CompiledRules::default()
+        };
+        for (category_name, category) in &config.patterns {
+            if !category.enabled {
+                continue;
+            }
+
+            for rule in category.rules.iter().filter(|rule| rule.enabled) {
+                let effective_severity = config.effective_severity(category, rule);
+                rules
+                    .insert_rule(rule, effective_severity, Some(category_name))
+                    .map_err(|e| {
+                        GuardianError::config(format!(
+                            "Failed to add rule '{}' in category '{}': {}",
+                            rule.id, category_name, e
+                        ))
+                    })?;
+            }
+        }
+        Ok(Arc::new(rules))
+    }
+
+    /// Engine running a compiled rule set, which may be shared with other engines
+    ///
+    /// Each engine tallies its own rule usage, so concurrent analyses sharing the rules do
+    /// not mix their statistics.
+    pub fn with_rules(rules: Arc<CompiledRules>) -> Self {
         Self {
-            regex_patterns: HashMap::new(),
-            ast_patterns: HashMap::new(),
+            usage: UsageRecorder::new(rules.rule_ids()),
+            rules,
+        }
+    }
+
+    /// The compiled rules this engine runs
+    pub fn rules(&self) -> &Arc<CompiledRules> {
+        &self.rules
+    }
+
+    /// The compiled rules, for adding to them while no other engine shares them
+    fn rules_mut(&mut self, rule_id: &str) -> GuardianResult<&mut CompiledRules> {
+        Arc::get_mut(&mut self.rules).ok_or_else(|| {
+            GuardianError::rule_compilation(
+                rule_id,
+                "the engine's rules are shared with other engines and can no longer change",
+            )
+        })
+    }
+
+    /// Register a procedural rule compiled into the calling crate
+    ///
+    /// Fails if another rule, configured or procedural, already uses the same id.
+    pub fn register_rule(&mut self, rule: Box<dyn Rule>) -> GuardianResult<()> {
+        self.register_shared_rule(Arc::from(rule))
+    }
+
+    /// Register a procedural rule that may also be registered on other engines
+    pub(crate) fn register_shared_rule(&mut self, rule: Arc<dyn Rule>) -> GuardianResult<()> {
+        let id = rule.info().id;
+        self.rules_mut(&id)?.register_shared_rule(rule)?;
+        self.usage.track(id);
+        Ok(())
+    }
+
+    /// Metadata of the registered procedural rules, in registration order
+    pub fn procedural_rules(&self) -> Vec<RuleInfo> {
+        self.rules
+            .procedural_rules
+            .iter()
+            .map(|rule| rule.info())
+            .collect()
+    }
+
+    /// Run the procedural rules that apply to a file
+    pub fn check_procedural_rules(&self, file: &FileContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut runs = Vec::new();
+        let infos: Vec<RuleInfo> = self
+            .rules
+            .procedural_rules
+            .iter()
+            .map(|rule| rule.info())
+            .collect();
+
+        for (rule, info) in self.rules.procedural_rules.iter().zip(&infos) {
+            if !rule.applies_to(file.path()) {
+                continue;
+            }
+            let _span = tracing::info_span!("guardian.rule", rule_id = %info.id).entered();
+            let started = Instant::now();
+            let found = rule.check(file);
+            runs.push((info.id.as_str(), found.len(), started.elapsed()));
+            violations.extend(
+                found
+                    .into_iter()
+                    .map(|violation| rule::apply_info(info, violation)),
+            );
         }
+
+        self.usage.record_file(&runs);
+        violations
+    }
+
+    /// Take the rule usage recorded since the last call, or since the last reset
+    ///
+    /// Every rule in the engine is listed, including rules that never ran.
+    pub fn take_rule_usage(&self) -> Vec<RuleUsage> {
+        self.usage.take()
+    }
+
+    /// Forget the rule usage recorded so far, before starting a run
+    pub fn reset_rule_usage(&self) {
+        self.usage.reset();
     }
 
     /// Add a pattern rule to the engine
@@ -103,6 +383,97 @@ impl PatternEngine {
         &mut self,
         rule: &PatternRule,
         effective_severity: Severity,
+    ) -> GuardianResult<()> {
+        let rules = self.rules_mut(&rule.id)?;
+        rules.insert_rule(rule, effective_severity, None)?;
+        rules.config_fingerprint = None;
+        self.usage.track(rule.id.clone());
+        Ok(())
+    }
+
+    /// Add a pattern rule belonging to a configuration category
+    ///
+    /// The category is reported on every violation the rule produces.
+    pub fn add_categorized_rule(
+        &mut self,
+        category: &str,
+        rule: &PatternRule,
+        effective_severity: Severity,
+    ) -> GuardianResult<()> {
+        let rules = self.rules_mut(&rule.id)?;
+        rules.insert_rule(rule, effective_severity, Some(category))?;
+        rules.config_fingerprint = None;
+        self.usage.track(rule.id.clone());
+        Ok(())
+    }
+}
+
+impl CompiledRules {
+    /// Fingerprint of the configuration the rules were compiled from, if any
+    ///
+    /// Rules assembled rule by rule on an engine have none, as do compiled rules that
+    /// configured rules were added to afterwards.
+    pub fn config_fingerprint(&self) -> Option<&str> {
+        self.config_fingerprint.as_deref()
+    }
+
+    /// Register a procedural rule, rejecting ids already in use
+    fn register_shared_rule(&mut self, rule: Arc<dyn Rule>) -> GuardianResult<()> {
+        let id = rule.info().id;
+        let taken = self.regex_patterns.contains_key(&id)
+            || self.required_patterns.contains_key(&id)
+            || self.ast_patterns.contains_key(&id)
+            || self.composite_patterns.contains_key(&id)
+            || self.file_pair_patterns.contains_key(&id)
+            || self
+                .procedural_rules
+                .iter()
+                .any(|existing| existing.info().id == id);
+        if taken {
+            return Err(GuardianError::rule_compilation(
+                &id,
+                "a rule with this id is already registered",
+            ));
+        }
+
+        self.procedural_rules.push(rule);
+        Ok(())
+    }
+
+    /// Ids of every rule, configured or procedural
+    fn rule_ids(&self) -> Vec<String> {
+        self.regex_patterns
+            .keys()
+            .chain(self.required_patterns.keys())
+            .chain(self.ast_patterns.keys())
+            .chain(self.composite_patterns.keys())
+            .chain(self.file_pair_patterns.keys())
+            .cloned()
+            .chain(self.procedural_rules.iter().map(|rule| rule.info().id))
+            .collect()
+    }
+
+    /// Ids of the rules evaluated across all files rather than file by file
+    fn workspace_rule_ids(&self) -> HashSet<String> {
+        self.ast_patterns
+            .values()
+            .filter(|pattern| {
+                matches!(
+                    pattern.pattern_type,
+                    AstPatternType::DuplicateStringLiteral { .. }
+                )
+            })
+            .map(|pattern| pattern.rule_id.clone())
+            .chain(self.file_pair_patterns.keys().cloned())
+            .collect()
+    }
+
+    /// Compile a rule and register it with the matching backend
+    fn insert_rule(
+        &mut self,
+        rule: &PatternRule,
+        effective_severity: Severity,
+        category: Option<&str>,
     ) -> GuardianResult<()> {
         tracing::debug!(
             "Adding rule '{}' of type {:?} with pattern '{}' and severity {:?}",
@@ -112,8 +483,39 @@ impl PatternEngine {
             effective_severity
         );
 
+        let metadata = RuleMetadata {
+            category: category.map(str::to_string),
+            tags: rule.tags.clone(),
+            docs_url: rule.docs_url.clone(),
+            rationale: rule.rationale.clone(),
+            fix: rule.fix.clone(),
+        };
+
+        let scope = rule
+            .applies_to
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern).map_err(|e| {
+                    GuardianError::rule_compilation(
+                        &rule.id,
+                        format!("invalid applies_to pattern '{pattern}': {e}"),
+                    )
+                })
+            })
+            .collect::<GuardianResult<Vec<_>>>()?;
+
+        match &rule.near {
+            Some(near) => {
+                self.near_checks
+                    .insert(rule.id.clone(), NearCheck::compile(&rule.id, near)?);
+            }
+            None => {
+                self.near_checks.remove(&rule.id);
+            }
+        }
+
         match rule.rule_type {
-            RuleType::Regex => {
+            RuleType::Regex | RuleType::Required => {
                 tracing::debug!(
                     "Compiling regex pattern '{}' for rule '{}'",
                     rule.pattern,
@@ -126,11 +528,14 @@ impl PatternEngine {
                         .case_insensitive(true)
                         .build()
                 }
-                .map_err(|e| {
-                    GuardianError::pattern(format!("Invalid regex '{}': {}", rule.pattern, e))
-                })?;
+                .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
 
-                self.regex_patterns.insert(
+                let patterns = if matches!(rule.rule_type, RuleType::Required) {
+                    &mut self.required_patterns
+                } else {
+                    &mut self.regex_patterns
+                };
+                patterns.insert(
                     rule.id.clone(),
                     CompiledRegex {
                         regex,
@@ -138,11 +543,15 @@ impl PatternEngine {
                         message_template: rule.message.clone(),
                         severity: effective_severity,
                         exclude_conditions: rule.exclude_if.clone(),
+                        scope,
+                        metadata,
                     },
                 );
             }
             RuleType::Ast => {
-                let pattern_type = self.parse_ast_pattern(&rule.pattern, &rule.id)?;
+                let pattern_type = self
+                    .parse_ast_pattern(&rule.pattern, &rule.id)
+                    .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
 
                 self.ast_patterns.insert(
                     rule.id.clone(),
@@ -152,11 +561,15 @@ impl PatternEngine {
                         message_template: rule.message.clone(),
                         severity: effective_severity,
                         exclude_conditions: rule.exclude_if.clone(),
+                        scope,
+                        metadata,
                     },
                 );
             }
             RuleType::Semantic | RuleType::ImportAnalysis => {
-                let pattern_type = self.parse_semantic_pattern(&rule.pattern, &rule.id)?;
+                let pattern_type = self
+                    .parse_semantic_pattern(&rule.pattern, &rule.id)
+                    .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
 
                 self.ast_patterns.insert(
                     rule.id.clone(),
@@ -166,6 +579,47 @@ impl PatternEngine {
                         message_template: rule.message.clone(),
                         severity: effective_severity,
                         exclude_conditions: rule.exclude_if.clone(),
+                        scope,
+                        metadata,
+                    },
+                );
+            }
+            RuleType::Composite => {
+                self.composite_patterns.insert(
+                    rule.id.clone(),
+                    CompositePattern {
+                        matcher: CompositeMatcher::compile(rule)?,
+                        rule_id: rule.id.clone(),
+                        message_template: rule.message.clone(),
+                        severity: effective_severity,
+                        exclude_conditions: rule.exclude_if.clone(),
+                        scope,
+                        metadata,
+                    },
+                );
+            }
+            RuleType::FilePair => {
+                let template = FilePairTemplate::parse(&rule.pattern)
+                    .map_err(|e| GuardianError::rule_compilation(&rule.id, e))?;
+                let has_conditions =
+                    !(rule.all_of.is_empty() && rule.any_of.is_empty() && rule.none_of.is_empty());
+                let conditions = if has_conditions {
+                    Some(CompositeMatcher::file_conditions(rule)?)
+                } else {
+                    None
+                };
+
+                self.file_pair_patterns.insert(
+                    rule.id.clone(),
+                    FilePairPattern {
+                        template,
+                        conditions,
+                        rule_id: rule.id.clone(),
+                        message_template: rule.message.clone(),
+                        severity: effective_severity,
+                        exclude_conditions: rule.exclude_if.clone(),
+                        scope,
+                        metadata,
                     },
                 );
             }
@@ -176,14 +630,20 @@ impl PatternEngine {
 
     /// Parse AST pattern string into typed pattern
     fn parse_ast_pattern(&self, pattern: &str, rule_id: &str) -> GuardianResult<AstPatternType> {
-        if pattern.starts_with("macro_call:") {
-            let macros = pattern
-                .strip_prefix("macro_call:")
-                .expect("pattern starts with 'macro_call:' - prefix strip should not fail")
-                .split('|')
-                .map(|s| s.trim().to_string())
-                .collect();
+        if let Some(list) = pattern.strip_prefix("macro_call:") {
+            let macros = parse_name_list(list, "!", rule_id)?;
             Ok(AstPatternType::MacroCall(macros))
+        } else if let Some(list) = pattern.strip_prefix("function_call:") {
+            let functions = parse_name_list(list, "()", rule_id)?;
+            Ok(AstPatternType::PlaceholderCall(functions))
+        } else if let Some(list) = pattern.strip_prefix("attribute_marker:") {
+            let markers = AttributeMarker::parse_list(list);
+            if markers.is_empty() {
+                return Err(GuardianError::pattern(format!(
+                    "Rule '{rule_id}' lists no attributes to match"
+                )));
+            }
+            Ok(AstPatternType::AttributeMarker(markers))
         } else if pattern == "return_ok_unit_with_no_logic" {
             Ok(AstPatternType::EmptyOkReturn)
         } else if pattern.contains("Architectural Principle:") {
@@ -238,6 +698,137 @@ impl PatternEngine {
             return Ok(AstPatternType::FunctionArgsGt(threshold));
         }
 
+        if let Some(param) = pattern.strip_prefix("match_arms_gt:") {
+            let threshold = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::LargeMatch(MatchCheck::ArmsGt(threshold)));
+        }
+
+        if let Some(param) = pattern.strip_prefix("match_arm_lines_gt:") {
+            let threshold = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::LargeMatch(MatchCheck::ArmLinesGt(
+                threshold,
+            )));
+        }
+
+        if let Some(param) = pattern.strip_prefix("shadowing_gt:") {
+            let threshold = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::Shadowing(ShadowingCheck::RebindsGt(
+                threshold,
+            )));
+        }
+
+        if pattern == "shadowing_type_change" {
+            return Ok(AstPatternType::Shadowing(ShadowingCheck::TypeChange));
+        }
+
+        if pattern == "magic_number" {
+            return Ok(AstPatternType::MagicNumber(2));
+        }
+
+        if let Some(param) = pattern.strip_prefix("magic_number:") {
+            let threshold = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::MagicNumber(threshold));
+        }
+
+        if let Some(params) = pattern.strip_prefix("duplicate_string_literal:") {
+            let (max_repeats, min_length) = match params.split_once(':') {
+                Some((max_repeats, min_length)) => (max_repeats, min_length),
+                None => (params, "5"),
+            };
+            let parse = |param: &str| {
+                param.trim().parse::<u32>().map_err(|_| {
+                    GuardianError::pattern(format!(
+                        "Invalid threshold in rule '{rule_id}': {param}"
+                    ))
+                })
+            };
+            return Ok(AstPatternType::DuplicateStringLiteral {
+                max_repeats: parse(max_repeats)?,
+                min_length: parse(min_length)?,
+            });
+        }
+
+        if let Some(param) = pattern.strip_prefix("large_literal:") {
+            let max_bytes = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::LargeLiteral(max_bytes));
+        }
+
+        if let Some(param) = pattern.strip_prefix("base64_blob:") {
+            let min_length = param.parse::<u32>().map_err(|_| {
+                GuardianError::pattern(format!("Invalid threshold in rule '{rule_id}': {param}"))
+            })?;
+            return Ok(AstPatternType::Base64Blob(min_length));
+        }
+
+        if let Some(check) = TodoCheck::parse(pattern) {
+            let check = check.map_err(|e| {
+                GuardianError::pattern(format!("Invalid TODO pattern in rule '{rule_id}': {e}"))
+            })?;
+            return Ok(AstPatternType::TodoMetadata(check));
+        }
+
+        if let Some(policy) = NamingPolicy::parse(pattern) {
+            return Ok(AstPatternType::Naming(policy));
+        }
+
+        if let Some(check) = DetachedTaskCheck::parse(pattern) {
+            return Ok(AstPatternType::DetachedTask(check));
+        }
+
+        if let Some(check) = ApiCheck::parse(pattern) {
+            let check = check.map_err(|e| {
+                GuardianError::pattern(format!("Invalid API pattern in rule '{rule_id}': {e}"))
+            })?;
+            return Ok(AstPatternType::Api(check));
+        }
+
+        if let Some(check) = HotLoopCheck::parse(pattern) {
+            return Ok(AstPatternType::HotLoopAllocation(check));
+        }
+
+        if let Some(check) = WildcardCheck::parse(pattern) {
+            return Ok(AstPatternType::Wildcard(check));
+        }
+
+        if let Some(check) = VisibilityCheck::parse(pattern) {
+            let check = check.map_err(|e| {
+                GuardianError::pattern(format!(
+                    "Invalid visibility pattern in rule '{rule_id}': {e}"
+                ))
+            })?;
+            return Ok(AstPatternType::Visibility(check));
+        }
+
+        if let Some(check) = StubImplCheck::parse(pattern) {
+            let check = check.map_err(|e| {
+                GuardianError::pattern(format!(
+                    "Invalid trait impl pattern in rule '{rule_id}': {e}"
+                ))
+            })?;
+            return Ok(AstPatternType::StubImpl(check));
+        }
+
+        if let Some(check) = IgnoredResultCheck::parse(pattern) {
+            return Ok(AstPatternType::IgnoredResult(check));
+        }
+
+        if let Some(check) = PollingCheck::parse(pattern) {
+            let check = check.map_err(|e| {
+                GuardianError::pattern(format!("Invalid polling pattern in rule '{rule_id}': {e}"))
+            })?;
+            return Ok(AstPatternType::Polling(check));
+        }
+
         // Handle non-parametric semantic patterns
         match pattern {
             "public_without_docs" => Ok(AstPatternType::PublicWithoutDocs),
@@ -247,6 +838,8 @@ impl PatternEngine {
             "generic_without_bounds" => Ok(AstPatternType::GenericWithoutBounds),
             "test_fn_without_assertion" => Ok(AstPatternType::TestFnWithoutAssertion),
             "impl_without_trait" => Ok(AstPatternType::ImplWithoutTrait),
+            "large_literal" => Ok(AstPatternType::LargeLiteral(10 * 1024)),
+            "base64_blob" => Ok(AstPatternType::Base64Blob(256)),
             _ => {
                 // For unrecognized semantic patterns, check if they look like import patterns
                 // This allows users to define custom layering import checking
@@ -283,27 +876,67 @@ impl PatternEngine {
             }
         }
     }
+}
 
+impl PatternEngine {
     /// Analyze a file and return all pattern matches
     pub fn analyze_file<P: AsRef<Path>>(
         &self,
         file_path: P,
         content: &str,
     ) -> GuardianResult<Vec<PatternMatch>> {
-        let file_path = file_path.as_ref();
+        self.analyze_context(&FileContext::new(file_path.as_ref(), content))
+    }
+
+    /// Analyze a file through its shared context and return all pattern matches
+    ///
+    /// The file is parsed at most once, however many AST patterns apply to it.
+    pub fn analyze_context(&self, file: &FileContext) -> GuardianResult<Vec<PatternMatch>> {
+        self.analyze_selected(file, None)
+    }
+
+    /// Analyze a file with only the given rules
+    ///
+    /// Lets callers refresh the results of rules whose definition changed without re-running
+    /// every other rule.
+    pub fn analyze_context_rules(
+        &self,
+        file: &FileContext,
+        rule_ids: &HashSet<String>,
+    ) -> GuardianResult<Vec<PatternMatch>> {
+        self.analyze_selected(file, Some(rule_ids))
+    }
+
+    /// Apply all patterns, or only the selected rules, to a file
+    fn analyze_selected(
+        &self,
+        file: &FileContext,
+        rule_ids: Option<&HashSet<String>>,
+    ) -> GuardianResult<Vec<PatternMatch>> {
+        let file_path = file.path();
+        let selected = |rule_id: &String| rule_ids.is_none_or(|ids| ids.contains(rule_id));
         let mut matches = Vec::new();
+        // Rules run on this file with their run time; match counts are filled in at the end
+        let mut runs: Vec<RuleRun> = Vec::new();
 
         tracing::debug!(
-            "Analyzing file '{}' with {} regex patterns and {} AST patterns",
+            "Analyzing file '{}' with {} regex, {} AST and {} composite patterns",
             file_path.display(),
-            self.regex_patterns.len(),
-            self.ast_patterns.len()
+            self.rules.regex_patterns.len(),
+            self.rules.ast_patterns.len(),
+            self.rules.composite_patterns.len()
         );
 
         // Apply regex patterns
-        for pattern in self.regex_patterns.values() {
+        for pattern in self.rules.regex_patterns.values() {
+            if !selected(&pattern.rule_id) || !in_scope(&pattern.scope, file_path) {
+                continue;
+            }
+            let _span = tracing::info_span!("guardian.rule", rule_id = %pattern.rule_id).entered();
             tracing::debug!("Processing regex pattern '{}'", pattern.rule_id);
-            let pattern_matches = self.apply_regex_pattern(pattern, file_path, content)?;
+            let started = Instant::now();
+            let pattern_matches = self.apply_regex_pattern(pattern, file)?;
+            runs.push((&pattern.rule_id, 0, started.elapsed()));
             tracing::debug!(
                 "Pattern '{}' found {} matches",
                 pattern.rule_id,
@@ -312,14 +945,261 @@ impl PatternEngine {
             matches.extend(pattern_matches);
         }
 
+        // Report required patterns that appear nowhere in the file
+        for pattern in self.rules.required_patterns.values() {
+            if !selected(&pattern.rule_id)
+                || !in_scope(&pattern.scope, file_path)
+                || self.excludes_file(pattern.exclude_conditions.as_ref(), file_path)
+            {
+                continue;
+            }
+            let started = Instant::now();
+            let present = pattern.regex.is_match(file.content());
+            runs.push((&pattern.rule_id, 0, started.elapsed()));
+            if present {
+                continue;
+            }
+            matches.push(PatternMatch {
+                rule_id: pattern.rule_id.clone(),
+                file_path: file_path.to_path_buf(),
+                line_number: Some(1),
+                column_number: Some(1),
+                end_line: None,
+                end_column: None,
+                matched_text: String::new(),
+                message: pattern
+                    .message_template
+                    .replace("{pattern}", pattern.regex.as_str()),
+                severity: pattern.severity,
+                context: None,
+                byte_range: None,
+            });
+        }
+
         // Apply AST patterns for Rust files
-        if file_path.extension().and_then(|s| s.to_str()) == Some("rs") {
-            for pattern in self.ast_patterns.values() {
-                let pattern_matches = self.apply_ast_pattern(pattern, file_path, content)?;
+        if file.is_rust() {
+            for pattern in self.rules.ast_patterns.values() {
+                if !selected(&pattern.rule_id) || !in_scope(&pattern.scope, file_path) {
+                    continue;
+                }
+                let _span =
+                    tracing::info_span!("guardian.rule", rule_id = %pattern.rule_id).entered();
+                let started = Instant::now();
+                let pattern_matches = self.apply_ast_pattern(pattern, file)?;
+                runs.push((&pattern.rule_id, 0, started.elapsed()));
                 matches.extend(pattern_matches);
             }
         }
 
+        // Apply composite patterns, whose conditions decide per file which backends run
+        for pattern in self.rules.composite_patterns.values() {
+            if !selected(&pattern.rule_id)
+                || !in_scope(&pattern.scope, file_path)
+                || self.excludes_file(pattern.exclude_conditions.as_ref(), file_path)
+            {
+                continue;
+            }
+            let _span = tracing::info_span!("guardian.rule", rule_id = %pattern.rule_id).entered();
+            let started = Instant::now();
+            let hits = pattern.matcher.find(file)?;
+            runs.push((&pattern.rule_id, 0, started.elapsed()));
+            for hit in hits {
+                let function = hit.function.as_deref().unwrap_or("");
+                matches.push(PatternMatch {
+                    rule_id: pattern.rule_id.clone(),
+                    file_path: file_path.to_path_buf(),
+                    line_number: Some(hit.location.line),
+                    column_number: Some(hit.location.column),
+                    end_line: Some(hit.location.end_line),
+                    end_column: Some(hit.location.end_column),
+                    matched_text: function.to_string(),
+                    message: pattern.message_template.replace("{function}", function),
+                    severity: pattern.severity,
+                    context: Some(hit.context),
+                    byte_range: Some(hit.location.byte_range),
+                });
+            }
+        }
+
+        // Matches that satisfy their rule's proximity requirement are not violations
+        if !self.rules.near_checks.is_empty() {
+            matches.retain(|m| {
+                self.rules
+                    .near_checks
+                    .get(&m.rule_id)
+                    .is_none_or(|check| check.is_violated(file, m))
+            });
+        }
+
+        for m in &matches {
+            if let Some(run) = runs.iter_mut().find(|run| run.0 == m.rule_id) {
+                run.1 += 1;
+            }
+        }
+        self.usage.record_file(&runs);
+
+        Ok(matches)
+    }
+
+    /// Ids of the cross-file rules applied by `analyze_workspace`
+    ///
+    /// Their results depend on every file of a run, so they cannot be cached per file.
+    pub fn workspace_rule_ids(&self) -> HashSet<String> {
+        self.rules.workspace_rule_ids()
+    }
+
+    /// Apply cross-file patterns to a set of files
+    ///
+    /// Patterns that need to see a whole crate (such as duplicated string literals) are
+    /// skipped by `analyze_file` and evaluated here once per analysis run.
+    pub fn analyze_workspace(&self, files: &[PathBuf]) -> GuardianResult<Vec<PatternMatch>> {
+        let mut matches = self.missing_companions(files)?;
+
+        let has_workspace_patterns = self.rules.ast_patterns.values().any(|pattern| {
+            matches!(
+                pattern.pattern_type,
+                AstPatternType::DuplicateStringLiteral { .. }
+            )
+        });
+        if !has_workspace_patterns {
+            return Ok(matches);
+        }
+        let import_index = ImportIndex::build(files)?;
+
+        for pattern in self.rules.ast_patterns.values() {
+            let (max_repeats, min_length) = match pattern.pattern_type {
+                AstPatternType::DuplicateStringLiteral {
+                    max_repeats,
+                    min_length,
+                } => (max_repeats as usize, min_length as usize),
+                _ => continue,
+            };
+
+            let in_rule_scope: HashSet<&PathBuf> = files
+                .iter()
+                .filter(|file| in_scope(&pattern.scope, file))
+                .filter(|file| !self.excludes_file(pattern.exclude_conditions.as_ref(), file))
+                .collect();
+
+            let started = Instant::now();
+            let matches_before = matches.len();
+            let literals = LiteralIndex::build(&import_index, min_length)?;
+
+            for occurrences in literals.duplicates(0) {
+                let occurrences: Vec<&StringLiteral> = occurrences
+                    .iter()
+                    .filter(|literal| in_rule_scope.contains(&literal.file_path))
+                    .collect();
+                if occurrences.len() <= max_repeats {
+                    continue;
+                }
+
+                let first = occurrences[0];
+                let others: Vec<String> = occurrences[1..]
+                    .iter()
+                    .map(|literal| {
+                        format!("{}:{}", literal.file_path.display(), literal.location.line)
+                    })
+                    .collect();
+
+                let message = pattern
+                    .message_template
+                    .replace("{value}", &first.value)
+                    .replace("{count}", &occurrences.len().to_string());
+
+                matches.push(PatternMatch {
+                    rule_id: pattern.rule_id.clone(),
+                    file_path: first.file_path.clone(),
+                    line_number: Some(first.location.line),
+                    column_number: Some(first.location.column),
+                    end_line: Some(first.location.end_line),
+                    end_column: Some(first.location.end_column),
+                    matched_text: format!("{:?}", first.value),
+                    message,
+                    severity: pattern.severity,
+                    context: Some(format!("also at {}", others.join(", "))),
+                    byte_range: Some(first.location.byte_range.clone()),
+                });
+            }
+
+            let found = &matches[matches_before..];
+            let matched_files: HashSet<&PathBuf> = found.iter().map(|m| &m.file_path).collect();
+            self.usage.record_workspace(
+                &pattern.rule_id,
+                in_rule_scope.len(),
+                matched_files.len(),
+                found.len(),
+                started.elapsed(),
+            );
+        }
+
+        Ok(matches)
+    }
+
+    /// Report files that lack the companion a file-pair rule requires
+    ///
+    /// Companions are looked up on disk, so they need not be analyzed files themselves.
+    fn missing_companions(&self, files: &[PathBuf]) -> GuardianResult<Vec<PatternMatch>> {
+        let mut matches = Vec::new();
+
+        for pattern in self.rules.file_pair_patterns.values() {
+            let _span = tracing::info_span!("guardian.rule", rule_id = %pattern.rule_id).entered();
+            let started = Instant::now();
+            let matches_before = matches.len();
+            let mut examined = 0;
+            for file_path in files {
+                if !in_scope(&pattern.scope, file_path)
+                    || self.excludes_file(pattern.exclude_conditions.as_ref(), file_path)
+                {
+                    continue;
+                }
+                examined += 1;
+                let companion = match pattern.template.companion_for(file_path) {
+                    Some(companion) if !companion.exists() => companion,
+                    _ => continue,
+                };
+
+                if let Some(conditions) = &pattern.conditions {
+                    let content = std::fs::read_to_string(file_path)
+                        .map_err(|e| GuardianError::io(file_path, e))?;
+                    if conditions
+                        .find(&FileContext::new(file_path, &content))?
+                        .is_empty()
+                    {
+                        continue;
+                    }
+                }
+
+                let companion = companion.display().to_string();
+                matches.push(PatternMatch {
+                    rule_id: pattern.rule_id.clone(),
+                    file_path: file_path.clone(),
+                    line_number: Some(1),
+                    column_number: Some(1),
+                    end_line: None,
+                    end_column: None,
+                    message: pattern
+                        .message_template
+                        .replace("{file}", &file_path.display().to_string())
+                        .replace("{companion}", &companion),
+                    matched_text: companion,
+                    severity: pattern.severity,
+                    context: None,
+                    byte_range: None,
+                });
+            }
+
+            // Each file misses at most one companion per rule
+            let found = matches.len() - matches_before;
+            self.usage.record_workspace(
+                &pattern.rule_id,
+                examined,
+                found,
+                found,
+                started.elapsed(),
+            );
+        }
+
         Ok(matches)
     }
 
@@ -327,9 +1207,9 @@ impl PatternEngine {
     fn apply_regex_pattern(
         &self,
         pattern: &CompiledRegex,
-        file_path: &Path,
-        content: &str,
+        file: &FileContext,
     ) -> GuardianResult<Vec<PatternMatch>> {
+        let (file_path, content) = (file.path(), file.content());
         tracing::debug!(
             "Applying regex pattern '{}' to file '{}'",
             pattern.rule_id,
@@ -348,8 +1228,8 @@ impl PatternEngine {
                 regex_match.start()
             );
             let matched_text = regex_match.as_str().to_string();
-            let (line_num, col_num, context) =
-                self.get_match_location(content, regex_match.start());
+            let location = file.location(regex_match.range());
+            let context = file.line_text(location.line).trim().to_string();
 
             // Check exclude conditions
             if self.should_exclude_match(
@@ -368,12 +1248,15 @@ impl PatternEngine {
             matches.push(PatternMatch {
                 rule_id: pattern.rule_id.clone(),
                 file_path: file_path.to_path_buf(),
-                line_number: Some(line_num),
-                column_number: Some(col_num),
+                line_number: Some(location.line),
+                column_number: Some(location.column),
+                end_line: Some(location.end_line),
+                end_column: Some(location.end_column),
                 matched_text,
                 message,
                 severity: pattern.severity,
                 context: Some(context),
+                byte_range: Some(location.byte_range),
             });
         }
 
@@ -384,31 +1267,28 @@ impl PatternEngine {
     fn apply_ast_pattern(
         &self,
         pattern: &AstPattern,
-        file_path: &Path,
-        content: &str,
+        file: &FileContext,
     ) -> GuardianResult<Vec<PatternMatch>> {
+        let (file_path, content) = (file.path(), file.content());
         let mut matches = Vec::new();
 
-        // Parse Rust syntax
-        let syntax_tree = match syn::parse_file(content) {
-            Ok(tree) => tree,
-            Err(e) => {
-                // If we can't parse the file, skip AST analysis but don't fail
-                tracing::debug!("Failed to parse Rust file {}: {}", file_path.display(), e);
-                return Ok(matches);
-            }
+        // Files that don't parse skip AST analysis without failing
+        let syntax_tree = match file.syntax_tree() {
+            Some(tree) => tree,
+            None => return Ok(matches),
         };
 
         match &pattern.pattern_type {
             AstPatternType::MacroCall(macro_names) => {
-                let found_matches = self.find_macro_calls(&syntax_tree, macro_names);
-                for (line, col, macro_name, context) in found_matches {
+                let found_matches = self.find_macro_calls(syntax_tree, macro_names);
+                for (span, macro_name, context) in found_matches {
+                    let location = file.span_location(span);
                     // Check exclude conditions
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -420,75 +1300,152 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: format!("{macro_name}!()"),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::CyclomaticComplexity(threshold) => {
-                let found_matches = self.find_cyclomatic_complexity(&syntax_tree, *threshold);
-                for (line, col, fn_name, complexity, context) in found_matches {
+            AstPatternType::PlaceholderCall(function_names) => {
+                let found_matches = self.find_placeholder_calls(syntax_tree, function_names);
+                for (span, function_name, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
                     let message = pattern
                         .message_template
-                        .replace("{value}", &complexity.to_string());
+                        .replace("{function_name}", &function_name);
 
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: format!("fn {}", fn_name),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: format!("{function_name}()"),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::PublicWithoutDocs => {
-                let found_matches = self.find_public_without_docs(&syntax_tree);
-                for (line, col, item_name, context) in found_matches {
+            AstPatternType::AttributeMarker(attribute_markers) => {
+                let found_matches = markers::find_marked_items(syntax_tree, attribute_markers);
+                for (span, attribute, item) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let message = pattern
+                        .message_template
+                        .replace("{attribute}", &attribute)
+                        .replace("{item}", &item);
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: attribute,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(item),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::CyclomaticComplexity(threshold) => {
+                let found_matches = self.find_cyclomatic_complexity(syntax_tree, *threshold);
+                for (span, fn_name, complexity, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern
+                        .message_template
+                        .replace("{value}", &complexity.to_string());
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: format!("fn {}", fn_name),
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::PublicWithoutDocs => {
+                let found_matches = self.find_public_without_docs(syntax_tree);
+                for (span, item_name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: item_name,
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::FunctionLinesGt(threshold) => {
-                let found_matches = self.find_long_functions(&syntax_tree, content, *threshold);
-                for (line, col, fn_name, line_count, context) in found_matches {
+                let found_matches = self.find_long_functions(syntax_tree, content, *threshold);
+                for (span, fn_name, line_count, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -500,23 +1457,27 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: format!("fn {}", fn_name),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::NestingDepthGt(threshold) => {
-                let found_matches = self.find_deep_nesting(&syntax_tree, *threshold);
-                for (line, col, depth, context) in found_matches {
+                let found_matches = self.find_deep_nesting(syntax_tree, *threshold);
+                for (span, depth, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -528,23 +1489,27 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: "nested block".to_string(),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::FunctionArgsGt(threshold) => {
-                let found_matches = self.find_functions_with_many_args(&syntax_tree, *threshold);
-                for (line, col, fn_name, arg_count, context) in found_matches {
+                let found_matches = self.find_functions_with_many_args(syntax_tree, *threshold);
+                for (span, fn_name, arg_count, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -556,23 +1521,438 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: format!("fn {}", fn_name),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::LargeMatch(check) => {
+                let found_matches = match_arms::find_large_matches(syntax_tree, content, *check);
+                for (span, size, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let (placeholder, matched_text) = match check {
+                        MatchCheck::ArmsGt(_) => ("{count}", "match"),
+                        MatchCheck::ArmLinesGt(_) => ("{lines}", "match arm"),
+                    };
+                    let message = pattern
+                        .message_template
+                        .replace(placeholder, &size.to_string());
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: matched_text.to_string(),
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::BlockingCallInAsync => {
-                let found_matches = self.find_blocking_in_async(&syntax_tree);
-                for (line, col, call_name, context) in found_matches {
+                let found_matches = self.find_blocking_in_async(syntax_tree);
+                for (span, call_name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: call_name,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::FutureNotAwaited => {
+                let found_matches = self.find_futures_not_awaited(syntax_tree);
+                for (span, expr, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: expr,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::DetachedTask(check) => {
+                let found_matches = tasks::find_detached_tasks(syntax_tree, check, file_path);
+                for (span, spawn, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: spawn,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::Wildcard(check) => {
+                let found_matches = wildcards::find_wildcards(syntax_tree, check, file_path);
+                for (span, path, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern.message_template.replace("{path}", &path);
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: path,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::Visibility(check) => {
+                let found_matches = visibility::find_public_leaks(syntax_tree, check, file_path);
+                for (span, name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern.message_template.replace("{name}", &name);
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: name,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::StubImpl(check) => {
+                let found_matches = stubs::find_stub_impls(syntax_tree, check);
+                for (span, name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern.message_template.replace("{name}", &name);
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: name,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::IgnoredResult(check) => {
+                let found_matches = results::find_ignored_results(syntax_tree, check);
+                for (span, callee, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern.message_template.replace("{name}", &callee);
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: callee,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::Shadowing(check) => {
+                let found_matches = shadowing::find_shadowing(syntax_tree, *check);
+                for (span, name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    let message = pattern.message_template.replace("{name}", &name);
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: name,
+                        message,
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::HotLoopAllocation(check) => {
+                let found_matches =
+                    hot_loops::find_hot_loop_allocations(syntax_tree, check, file_path);
+                for (span, allocation, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: allocation,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::Polling(check) => {
+                let found_matches = polling::find_polling(syntax_tree, *check);
+                for (span, matched, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: matched,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::SelectWithoutBiased => {
+                let found_matches = self.find_select_without_biased(syntax_tree);
+                for (span, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: "tokio::select!".to_string(),
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::GenericWithoutBounds => {
+                let found_matches = self.find_generics_without_bounds(syntax_tree);
+                for (span, generic_name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: generic_name,
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::TestFnWithoutAssertion => {
+                let found_matches = self.find_test_functions_without_assertions(syntax_tree);
+                for (span, fn_name, context) in found_matches {
+                    let location = file.span_location(span);
+                    if self.should_exclude_ast_match(
+                        pattern.exclude_conditions.as_ref(),
+                        file_path,
+                        syntax_tree,
+                        location.line,
+                    ) {
+                        continue;
+                    }
+
+                    matches.push(PatternMatch {
+                        rule_id: pattern.rule_id.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: format!("fn {}", fn_name),
+                        message: pattern.message_template.clone(),
+                        severity: pattern.severity,
+                        context: Some(context),
+                        byte_range: Some(location.byte_range),
+                    });
+                }
+            }
+            AstPatternType::ImplWithoutTrait => {
+                let found_matches = self.find_impl_without_trait(syntax_tree);
+                for (span, impl_name, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -580,23 +1960,27 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: call_name,
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: format!("impl {}", impl_name),
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::FutureNotAwaited => {
-                let found_matches = self.find_futures_not_awaited(&syntax_tree);
-                for (line, col, expr, context) in found_matches {
+            AstPatternType::UnsafeBlock => {
+                let found_matches = self.find_unsafe_blocks(syntax_tree);
+                for (span, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -604,23 +1988,27 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: expr,
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: "unsafe".to_string(),
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::SelectWithoutBiased => {
-                let found_matches = self.find_select_without_biased(&syntax_tree);
-                for (line, col, context) in found_matches {
+            AstPatternType::IgnoredTestAttribute => {
+                let found_matches = self.find_ignored_tests(syntax_tree);
+                for (span, fn_name, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -628,145 +2016,220 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: "tokio::select!".to_string(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: format!("#[ignore] fn {}", fn_name),
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::GenericWithoutBounds => {
-                let found_matches = self.find_generics_without_bounds(&syntax_tree);
-                for (line, col, generic_name, context) in found_matches {
+
+            AstPatternType::Naming(policy) => {
+                let found_matches = naming::find_naming_violations(syntax_tree, policy);
+                for (span, name, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let message = pattern.message_template.replace("{name}", &name);
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: generic_name,
-                        message: pattern.message_template.clone(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: name,
+                        message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::TestFnWithoutAssertion => {
-                let found_matches = self.find_test_functions_without_assertions(&syntax_tree);
-                for (line, col, fn_name, context) in found_matches {
+            AstPatternType::Api(check) => {
+                let found_matches = api::find_api_violations(syntax_tree, check);
+                for (span, name, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let message = pattern.message_template.replace("{name}", &name);
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: format!("fn {}", fn_name),
-                        message: pattern.message_template.clone(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: name,
+                        message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::ImplWithoutTrait => {
-                let found_matches = self.find_impl_without_trait(&syntax_tree);
-                for (line, col, impl_name, context) in found_matches {
+            AstPatternType::MagicNumber(threshold) => {
+                let found_matches = magic_numbers::find_magic_numbers(syntax_tree, *threshold);
+                for (span, literal, context) in found_matches {
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let message = pattern.message_template.replace("{value}", &literal);
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: format!("impl {}", impl_name),
-                        message: pattern.message_template.clone(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: literal,
+                        message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::UnsafeBlock => {
-                let found_matches = self.find_unsafe_blocks(&syntax_tree);
-                for (line, col, context) in found_matches {
+            AstPatternType::TodoMetadata(check) => {
+                let today = chrono::Local::now().date_naive();
+                for marker in check.find_violations(content, today) {
+                    let location = file.location(marker.byte_range);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let matched_text = content[location.byte_range.clone()].trim().to_string();
+                    let metadata = marker.metadata.as_ref();
+                    let message = pattern
+                        .message_template
+                        .replace("{tag}", &marker.tag)
+                        .replace("{owner}", metadata.map_or("", |m| m.owner.as_str()))
+                        .replace("{issue}", metadata.map_or("", |m| m.issue.as_str()))
+                        .replace(
+                            "{due}",
+                            &metadata
+                                .and_then(|m| m.due)
+                                .map(|due| due.to_string())
+                                .unwrap_or_default(),
+                        );
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: "unsafe".to_string(),
-                        message: pattern.message_template.clone(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: matched_text.clone(),
+                        message,
                         severity: pattern.severity,
-                        context: Some(context),
+                        context: Some(matched_text),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-            AstPatternType::IgnoredTestAttribute => {
-                let found_matches = self.find_ignored_tests(&syntax_tree);
-                for (line, col, fn_name, context) in found_matches {
+            AstPatternType::LargeLiteral(_) | AstPatternType::Base64Blob(_) => {
+                let found_matches = self.find_string_literals(syntax_tree);
+                for (span, kind, value) in found_matches {
+                    let (size, context) = match &pattern.pattern_type {
+                        AstPatternType::LargeLiteral(max_bytes) => {
+                            if value.len() <= *max_bytes as usize {
+                                continue;
+                            }
+                            (
+                                value.len(),
+                                format!("{kind} literal of {} bytes", value.len()),
+                            )
+                        }
+                        AstPatternType::Base64Blob(min_length) => {
+                            let length = base64_length(&value);
+                            if length < *min_length as usize {
+                                continue;
+                            }
+                            (
+                                length,
+                                format!("base64-looking {kind} literal of {length} characters"),
+                            )
+                        }
+                        _ => continue,
+                    };
+
+                    let location = file.span_location(span);
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
 
+                    let message = pattern
+                        .message_template
+                        .replace("{value}", &size.to_string());
+
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
-                        matched_text: format!("#[ignore] fn {}", fn_name),
-                        message: pattern.message_template.clone(),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
+                        matched_text: context.clone(),
+                        message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
-
+            AstPatternType::DuplicateStringLiteral { .. } => {
+                // Cross-file pattern, evaluated by analyze_workspace
+            }
             AstPatternType::EmptyOkReturn => {
-                let found_matches = self.find_empty_ok_returns(&syntax_tree);
-                for (line, col, context) in found_matches {
+                let found_matches = self.find_empty_ok_returns(syntax_tree);
+                for (span, context) in found_matches {
+                    let location = file.span_location(span);
                     // Check exclude conditions
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -774,12 +2237,15 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: "Ok(())".to_string(),
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
@@ -790,22 +2256,26 @@ impl PatternEngine {
                         file_path: file_path.to_path_buf(),
                         line_number: Some(1),
                         column_number: Some(1),
+                        end_line: None,
+                        end_column: None,
                         matched_text: "".to_string(),
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: None,
+                        byte_range: None,
                     });
                 }
             }
             AstPatternType::EmptyFunctionBody => {
-                let found_matches = self.find_empty_function_bodies(&syntax_tree);
-                for (line, col, fn_name, context) in found_matches {
+                let found_matches = self.find_empty_function_bodies(syntax_tree);
+                for (span, fn_name, context) in found_matches {
+                    let location = file.span_location(span);
                     // Check exclude conditions
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -817,24 +2287,28 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: format!("fn {}", fn_name),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::UnwrapOrExpectWithoutMessage => {
-                let found_matches = self.find_unwrap_without_message(&syntax_tree);
-                for (line, col, method_name, context) in found_matches {
+                let found_matches = self.find_unwrap_without_message(syntax_tree);
+                for (span, method_name, context) in found_matches {
+                    let location = file.span_location(span);
                     // Check exclude conditions
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -844,24 +2318,28 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: format!(".{}()", method_name),
                         message,
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
             AstPatternType::AbstractionLayerViolation(regex) => {
-                let found_matches = self.find_import_pattern_matches(&syntax_tree, content, regex);
-                for (line, col, import_text, context) in found_matches {
+                let found_matches = self.find_import_pattern_matches(syntax_tree, content, regex);
+                for (span, import_text, context) in found_matches {
+                    let location = file.span_location(span);
                     // Check exclude conditions
                     if self.should_exclude_ast_match(
                         pattern.exclude_conditions.as_ref(),
                         file_path,
-                        &syntax_tree,
-                        line,
+                        syntax_tree,
+                        location.line,
                     ) {
                         continue;
                     }
@@ -869,12 +2347,15 @@ impl PatternEngine {
                     matches.push(PatternMatch {
                         rule_id: pattern.rule_id.clone(),
                         file_path: file_path.to_path_buf(),
-                        line_number: Some(line),
-                        column_number: Some(col),
+                        line_number: Some(location.line),
+                        column_number: Some(location.column),
+                        end_line: Some(location.end_line),
+                        end_column: Some(location.end_column),
                         matched_text: import_text,
                         message: pattern.message_template.clone(),
                         severity: pattern.severity,
                         context: Some(context),
+                        byte_range: Some(location.byte_range),
                     });
                 }
             }
@@ -888,25 +2369,19 @@ impl PatternEngine {
         &self,
         syntax_tree: &syn::File,
         target_macros: &[String],
-    ) -> Vec<(u32, u32, String, String)> {
+    ) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct MacroVisitor<'a> {
             target_macros: &'a [String],
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for MacroVisitor<'_> {
             fn visit_macro(&mut self, mac: &syn::Macro) {
-                if let Some(ident) = mac.path.get_ident() {
-                    let macro_name = ident.to_string();
-                    if self.target_macros.contains(&macro_name) {
-                        let _span = mac.path.span();
-                        // proc_macro2::Span doesn't provide direct line/column access in stable Rust
-                        // Use line 1 with improved context for macro location
-                        let context = format!("{}!()", macro_name);
-                        self.matches.push((1, 1, macro_name, context));
-                    }
+                if let Some(macro_name) = matching_path(&mac.path, self.target_macros) {
+                    let context = format!("{}!()", macro_name);
+                    self.matches.push((mac.span(), macro_name, context));
                 }
                 syn::visit::visit_macro(self, mac);
             }
@@ -920,12 +2395,57 @@ impl PatternEngine {
         visitor.visit_file(syntax_tree);
         visitor.matches
     }
+
+    /// Find calls to placeholder functions and methods
+    fn find_placeholder_calls(
+        &self,
+        syntax_tree: &syn::File,
+        target_functions: &[String],
+    ) -> Vec<(Span, String, String)> {
+        use syn::visit::Visit;
+
+        struct CallVisitor<'a> {
+            target_functions: &'a [String],
+            matches: Vec<(Span, String, String)>,
+        }
+
+        impl Visit<'_> for CallVisitor<'_> {
+            fn visit_expr_call(&mut self, call: &syn::ExprCall) {
+                if let syn::Expr::Path(func) = call.func.as_ref() {
+                    if let Some(function_name) = matching_path(&func.path, self.target_functions) {
+                        let context = format!("{}()", function_name);
+                        self.matches.push((call.span(), function_name, context));
+                    }
+                }
+                syn::visit::visit_expr_call(self, call);
+            }
+
+            fn visit_expr_method_call(&mut self, call: &syn::ExprMethodCall) {
+                let method_name = call.method.to_string();
+                if self.target_functions.contains(&method_name) {
+                    let context = format!(".{}()", method_name);
+                    self.matches
+                        .push((call.method.span(), method_name, context));
+                }
+                syn::visit::visit_expr_method_call(self, call);
+            }
+        }
+
+        let mut visitor = CallVisitor {
+            target_functions,
+            matches: Vec::new(),
+        };
+
+        visitor.visit_file(syntax_tree);
+        visitor.matches
+    }
+
     /// Find functions that return empty Ok(()) responses
-    fn find_empty_ok_returns(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String)> {
+    fn find_empty_ok_returns(&self, syntax_tree: &syn::File) -> Vec<(Span, String)> {
         use syn::visit::Visit;
 
         struct EmptyOkVisitor {
-            matches: Vec<(u32, u32, String)>,
+            matches: Vec<(Span, String)>,
         }
 
         impl Visit<'_> for EmptyOkVisitor {
@@ -935,11 +2455,7 @@ impl PatternEngine {
                     if self.is_result_type(return_type) {
                         // Check if body is just Ok(()) or similar
                         if let Some(ok_expr) = self.find_ok_unit_return(&func.block) {
-                            let _span = ok_expr.span();
-                            // Use a simple line-based location since proc_macro2::Span doesn't have start() method
-                            // Use a simple line-based location since proc_macro2::Span doesn't have start() method
-                            let (line, col, context) = (1, 1, String::new());
-                            self.matches.push((line, col, context));
+                            self.matches.push((ok_expr.span(), String::new()));
                         }
                     }
                 }
@@ -1005,14 +2521,11 @@ impl PatternEngine {
     }
 
     /// Find functions with empty bodies
-    fn find_empty_function_bodies(
-        &self,
-        syntax_tree: &syn::File,
-    ) -> Vec<(u32, u32, String, String)> {
+    fn find_empty_function_bodies(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct EmptyBodyVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for EmptyBodyVisitor {
@@ -1022,15 +2535,15 @@ impl PatternEngine {
                 // Check if function body is empty or has only comments/whitespace
                 if func.block.stmts.is_empty() {
                     // Function has completely empty body
-                    let (line, col, context) = (1, 1, format!("fn {} {{ }}", fn_name));
-                    self.matches.push((line, col, fn_name, context));
+                    let context = format!("fn {} {{ }}", fn_name);
+                    self.matches.push((func.sig.span(), fn_name, context));
                 } else if func.block.stmts.len() == 1 {
                     // Check if the single statement is just a comment or empty expression
                     if let syn::Stmt::Expr(expr, _) = &func.block.stmts[0] {
                         if matches!(expr, syn::Expr::Tuple(tuple) if tuple.elems.is_empty()) {
                             // Function body contains only ()
-                            let (line, col, context) = (1, 1, format!("fn {} {{ () }}", fn_name));
-                            self.matches.push((line, col, fn_name, context));
+                            let context = format!("fn {} {{ () }}", fn_name);
+                            self.matches.push((func.sig.span(), fn_name, context));
                         }
                     }
                 }
@@ -1048,14 +2561,11 @@ impl PatternEngine {
     }
 
     /// Find unwrap() or expect() calls without meaningful error messages
-    fn find_unwrap_without_message(
-        &self,
-        syntax_tree: &syn::File,
-    ) -> Vec<(u32, u32, String, String)> {
+    fn find_unwrap_without_message(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct UnwrapVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for UnwrapVisitor {
@@ -1065,17 +2575,23 @@ impl PatternEngine {
                 match method_name.as_str() {
                     "unwrap" => {
                         // unwrap() calls are always problematic
-                        let (line, col, context) = (1, 1, ".unwrap()".to_string());
-                        self.matches
-                            .push((line, col, "unwrap".to_string(), context));
+                        let context = ".unwrap()".to_string();
+                        self.matches.push((
+                            method_call.method.span(),
+                            "unwrap".to_string(),
+                            context,
+                        ));
                     }
                     "expect" => {
                         // Check if expect() has a meaningful message
                         if method_call.args.is_empty() {
                             // expect() without any message
-                            let (line, col, context) = (1, 1, ".expect()".to_string());
-                            self.matches
-                                .push((line, col, "expect".to_string(), context));
+                            let context = ".expect()".to_string();
+                            self.matches.push((
+                                method_call.method.span(),
+                                "expect".to_string(),
+                                context,
+                            ));
                         } else if let syn::Expr::Lit(syn::ExprLit {
                             lit: syn::Lit::Str(lit_str),
                             ..
@@ -1087,10 +2603,12 @@ impl PatternEngine {
                                 || message.len() < 5
                                 || message.to_lowercase().contains("error") && message.len() < 10
                             {
-                                let (line, col, context) =
-                                    (1, 1, format!(".expect(\"{}\")", message));
-                                self.matches
-                                    .push((line, col, "expect".to_string(), context));
+                                let context = format!(".expect(\"{}\")", message);
+                                self.matches.push((
+                                    method_call.method.span(),
+                                    "expect".to_string(),
+                                    context,
+                                ));
                             }
                         }
                     }
@@ -1115,12 +2633,12 @@ impl PatternEngine {
         syntax_tree: &syn::File,
         _content: &str,
         regex: &regex::Regex,
-    ) -> Vec<(u32, u32, String, String)> {
+    ) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct ImportVisitor<'a> {
             regex: &'a regex::Regex,
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for ImportVisitor<'_> {
@@ -1134,11 +2652,8 @@ impl PatternEngine {
                 );
 
                 if self.regex.is_match(&use_string) {
-                    // Extract line information from the use statement
-                    // Use simple line tracking for AST span location
-                    // we'd use syn span information for precise location
-                    let (line, col, context) = (1, 1, use_string.clone());
-                    self.matches.push((line, col, use_string, context));
+                    let context = use_string.clone();
+                    self.matches.push((use_item.span(), use_string, context));
                 }
 
                 syn::visit::visit_item_use(self, use_item);
@@ -1154,36 +2669,6 @@ impl PatternEngine {
         visitor.matches
     }
 
-    /// Get line and column number from byte offset in content
-    fn get_match_location(&self, content: &str, byte_offset: usize) -> (u32, u32, String) {
-        let mut line = 1;
-        let mut col = 1;
-        let mut line_start = 0;
-
-        for (i, ch) in content.char_indices() {
-            if i >= byte_offset {
-                break;
-            }
-            if ch == '\n' {
-                line += 1;
-                col = 1;
-                line_start = i + 1;
-            } else {
-                col += 1;
-            }
-        }
-
-        // Extract context line
-        let line_end = content[line_start..]
-            .find('\n')
-            .map(|pos| line_start + pos)
-            .unwrap_or(content.len());
-
-        let context = content[line_start..line_end].trim().to_string();
-
-        (line, col, context)
-    }
-
     /// Check if a regex match should be excluded based on conditions
     fn should_exclude_match(
         &self,
@@ -1235,6 +2720,12 @@ impl PatternEngine {
         _syntax_tree: &syn::File,
         _line: u32,
     ) -> bool {
+        // Future enhancement: Check for specific attributes like #[test] on functions
+        self.excludes_file(conditions, file_path)
+    }
+
+    /// Check if exclude conditions rule out a whole file
+    fn excludes_file(&self, conditions: Option<&ExcludeConditions>, file_path: &Path) -> bool {
         if let Some(conditions) = conditions {
             // Check if in test files
             if conditions.in_tests && self.is_test_file(file_path) {
@@ -1251,13 +2742,83 @@ impl PatternEngine {
                     }
                 }
             }
-
-            // Future enhancement: Check for specific attributes like #[test] on functions
         }
 
         false
     }
 
+    /// Rules that never apply to a file, with the reason, sorted by rule id
+    ///
+    /// Covers `applies_to` scopes and file-level `exclude_if` conditions; exclusions that
+    /// depend on a match's surroundings are not listed.
+    pub fn rule_exclusions(&self, file_path: &Path) -> Vec<(String, String)> {
+        let rules = self
+            .rules
+            .regex_patterns
+            .values()
+            .map(|p| (&p.rule_id, &p.scope, p.exclude_conditions.as_ref()))
+            .chain(
+                self.rules
+                    .required_patterns
+                    .values()
+                    .map(|p| (&p.rule_id, &p.scope, p.exclude_conditions.as_ref())),
+            )
+            .chain(
+                self.rules
+                    .ast_patterns
+                    .values()
+                    .map(|p| (&p.rule_id, &p.scope, p.exclude_conditions.as_ref())),
+            )
+            .chain(
+                self.rules
+                    .composite_patterns
+                    .values()
+                    .map(|p| (&p.rule_id, &p.scope, p.exclude_conditions.as_ref())),
+            )
+            .chain(
+                self.rules
+                    .file_pair_patterns
+                    .values()
+                    .map(|p| (&p.rule_id, &p.scope, p.exclude_conditions.as_ref())),
+            );
+
+        let mut exclusions: Vec<(String, String)> = rules
+            .filter_map(|(rule_id, scope, conditions)| {
+                self.file_exclusion_reason(scope, conditions, file_path)
+                    .map(|reason| (rule_id.clone(), reason))
+            })
+            .collect();
+        exclusions.sort();
+        exclusions
+    }
+
+    /// Why a rule's scope or exclude conditions rule out a whole file
+    fn file_exclusion_reason(
+        &self,
+        scope: &[glob::Pattern],
+        conditions: Option<&ExcludeConditions>,
+        file_path: &Path,
+    ) -> Option<String> {
+        if !in_scope(scope, file_path) {
+            let globs: Vec<&str> = scope.iter().map(glob::Pattern::as_str).collect();
+            return Some(format!("outside applies_to [{}]", globs.join(", ")));
+        }
+
+        let conditions = conditions?;
+        if conditions.in_tests && self.is_test_file(file_path) {
+            return Some("exclude_if.in_tests: file is in a test directory".to_string());
+        }
+
+        conditions
+            .file_patterns
+            .as_ref()?
+            .iter()
+            .find(|pattern| {
+                glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches_path(file_path))
+            })
+            .map(|pattern| format!("exclude_if.file_patterns matches '{pattern}'"))
+    }
+
     /// Check if a file path indicates it's a test file
     fn is_test_file(&self, file_path: &Path) -> bool {
         file_path.components().any(|component| {
@@ -1278,12 +2839,12 @@ impl PatternEngine {
         &self,
         syntax_tree: &syn::File,
         threshold: u32,
-    ) -> Vec<(u32, u32, String, u32, String)> {
+    ) -> Vec<(Span, String, u32, String)> {
         use syn::visit::Visit;
 
         struct ComplexityVisitor {
             threshold: u32,
-            matches: Vec<(u32, u32, String, u32, String)>,
+            matches: Vec<(Span, String, u32, String)>,
         }
 
         impl Visit<'_> for ComplexityVisitor {
@@ -1292,9 +2853,9 @@ impl PatternEngine {
                 let complexity = self.calculate_complexity(&func.block);
 
                 if complexity > self.threshold {
-                    let (line, col, context) =
-                        (1, 1, format!("fn {} (complexity: {})", fn_name, complexity));
-                    self.matches.push((line, col, fn_name, complexity, context));
+                    let context = format!("fn {} (complexity: {})", fn_name, complexity);
+                    self.matches
+                        .push((func.sig.ident.span(), fn_name, complexity, context));
                 }
 
                 syn::visit::visit_item_fn(self, func);
@@ -1360,11 +2921,11 @@ impl PatternEngine {
     }
 
     /// Find public items without documentation
-    fn find_public_without_docs(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String, String)> {
+    fn find_public_without_docs(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct PublicDocsVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for PublicDocsVisitor {
@@ -1373,9 +2934,9 @@ impl PatternEngine {
                     && !self.has_doc_comment(&func.attrs)
                 {
                     let fn_name = func.sig.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("pub fn {}", fn_name));
+                    let context = format!("pub fn {}", fn_name);
                     self.matches
-                        .push((line, col, format!("fn {}", fn_name), context));
+                        .push((func.sig.ident.span(), format!("fn {}", fn_name), context));
                 }
                 syn::visit::visit_item_fn(self, func);
             }
@@ -1385,9 +2946,12 @@ impl PatternEngine {
                     && !self.has_doc_comment(&item_struct.attrs)
                 {
                     let struct_name = item_struct.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("pub struct {}", struct_name));
-                    self.matches
-                        .push((line, col, format!("struct {}", struct_name), context));
+                    let context = format!("pub struct {}", struct_name);
+                    self.matches.push((
+                        item_struct.ident.span(),
+                        format!("struct {}", struct_name),
+                        context,
+                    ));
                 }
                 syn::visit::visit_item_struct(self, item_struct);
             }
@@ -1397,9 +2961,12 @@ impl PatternEngine {
                     && !self.has_doc_comment(&item_enum.attrs)
                 {
                     let enum_name = item_enum.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("pub enum {}", enum_name));
-                    self.matches
-                        .push((line, col, format!("enum {}", enum_name), context));
+                    let context = format!("pub enum {}", enum_name);
+                    self.matches.push((
+                        item_enum.ident.span(),
+                        format!("enum {}", enum_name),
+                        context,
+                    ));
                 }
                 syn::visit::visit_item_enum(self, item_enum);
             }
@@ -1409,9 +2976,12 @@ impl PatternEngine {
                     && !self.has_doc_comment(&item_trait.attrs)
                 {
                     let trait_name = item_trait.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("pub trait {}", trait_name));
-                    self.matches
-                        .push((line, col, format!("trait {}", trait_name), context));
+                    let context = format!("pub trait {}", trait_name);
+                    self.matches.push((
+                        item_trait.ident.span(),
+                        format!("trait {}", trait_name),
+                        context,
+                    ));
                 }
                 syn::visit::visit_item_trait(self, item_trait);
             }
@@ -1426,9 +2996,7 @@ impl PatternEngine {
                                 .path()
                                 .segments
                                 .first()
-                                .expect("segments.len() == 1 - first element must exist")
-                                .ident
-                                == "doc")
+                                .is_some_and(|segment| segment.ident == "doc"))
                 })
             }
         }
@@ -1445,17 +3013,18 @@ impl PatternEngine {
     fn find_long_functions(
         &self,
         syntax_tree: &syn::File,
-        _content: &str,
+        content: &str,
         threshold: u32,
-    ) -> Vec<(u32, u32, String, u32, String)> {
+    ) -> Vec<(Span, String, u32, String)> {
         use syn::visit::Visit;
 
-        struct LongFunctionVisitor {
+        struct LongFunctionVisitor<'a> {
+            lines: Vec<&'a str>,
             threshold: u32,
-            matches: Vec<(u32, u32, String, u32, String)>,
+            matches: Vec<(Span, String, u32, String)>,
         }
 
-        impl Visit<'_> for LongFunctionVisitor {
+        impl Visit<'_> for LongFunctionVisitor<'_> {
             fn visit_item_fn(&mut self, func: &syn::ItemFn) {
                 let fn_name = func.sig.ident.to_string();
 
@@ -1463,27 +3032,38 @@ impl PatternEngine {
                 let line_count = self.count_function_lines(&func.block);
 
                 if line_count > self.threshold {
-                    let (line, col, context) =
-                        (1, 1, format!("fn {} ({} lines)", fn_name, line_count));
-                    self.matches.push((line, col, fn_name, line_count, context));
+                    let context = format!("fn {} ({} lines)", fn_name, line_count);
+                    self.matches
+                        .push((func.sig.ident.span(), fn_name, line_count, context));
                 }
 
                 syn::visit::visit_item_fn(self, func);
             }
         }
 
-        impl LongFunctionVisitor {
+        impl LongFunctionVisitor<'_> {
+            /// Count code lines between the braces of the body as written in the source
+            ///
+            /// Blank and comment-only lines are skipped; a body on a single line counts as one.
             fn count_function_lines(&self, block: &syn::Block) -> u32 {
-                // Simple line counting - count non-empty, non-comment lines
-                let block_str = format!("{}", quote::quote!(#block));
-                block_str
-                    .lines()
-                    .filter(|line| !line.trim().is_empty() && !line.trim().starts_with("//"))
+                let open_line = block.brace_token.span.open().start().line;
+                let close_line = block.brace_token.span.close().end().line;
+                if open_line >= close_line {
+                    return 1;
+                }
+
+                let mut in_block_comment = false;
+                self.lines
+                    .iter()
+                    .take(close_line - 1)
+                    .skip(open_line)
+                    .filter(|line| is_code_line(line, &mut in_block_comment))
                     .count() as u32
             }
         }
 
         let mut visitor = LongFunctionVisitor {
+            lines: content.lines().collect(),
             threshold,
             matches: Vec::new(),
         };
@@ -1497,13 +3077,13 @@ impl PatternEngine {
         &self,
         syntax_tree: &syn::File,
         threshold: u32,
-    ) -> Vec<(u32, u32, u32, String)> {
+    ) -> Vec<(Span, u32, String)> {
         use syn::visit::Visit;
 
         struct NestingVisitor {
             threshold: u32,
             current_depth: u32,
-            matches: Vec<(u32, u32, u32, String)>,
+            matches: Vec<(Span, u32, String)>,
         }
 
         impl Visit<'_> for NestingVisitor {
@@ -1511,12 +3091,9 @@ impl PatternEngine {
                 self.current_depth += 1;
 
                 if self.current_depth > self.threshold {
-                    let (line, col, context) = (
-                        1,
-                        1,
-                        format!("nested block at depth {}", self.current_depth),
-                    );
-                    self.matches.push((line, col, self.current_depth, context));
+                    let context = format!("nested block at depth {}", self.current_depth);
+                    self.matches
+                        .push((block.brace_token.span.open(), self.current_depth, context));
                 }
 
                 syn::visit::visit_block(self, block);
@@ -1527,12 +3104,9 @@ impl PatternEngine {
                 self.current_depth += 1;
 
                 if self.current_depth > self.threshold {
-                    let (line, col, context) = (
-                        1,
-                        1,
-                        format!("if statement at depth {}", self.current_depth),
-                    );
-                    self.matches.push((line, col, self.current_depth, context));
+                    let context = format!("if statement at depth {}", self.current_depth);
+                    self.matches
+                        .push((expr_if.if_token.span, self.current_depth, context));
                 }
 
                 syn::visit::visit_expr_if(self, expr_if);
@@ -1543,12 +3117,9 @@ impl PatternEngine {
                 self.current_depth += 1;
 
                 if self.current_depth > self.threshold {
-                    let (line, col, context) = (
-                        1,
-                        1,
-                        format!("match statement at depth {}", self.current_depth),
-                    );
-                    self.matches.push((line, col, self.current_depth, context));
+                    let context = format!("match statement at depth {}", self.current_depth);
+                    self.matches
+                        .push((expr_match.match_token.span, self.current_depth, context));
                 }
 
                 syn::visit::visit_expr_match(self, expr_match);
@@ -1571,12 +3142,12 @@ impl PatternEngine {
         &self,
         syntax_tree: &syn::File,
         threshold: u32,
-    ) -> Vec<(u32, u32, String, u32, String)> {
+    ) -> Vec<(Span, String, u32, String)> {
         use syn::visit::Visit;
 
         struct ManyArgsVisitor {
             threshold: u32,
-            matches: Vec<(u32, u32, String, u32, String)>,
+            matches: Vec<(Span, String, u32, String)>,
         }
 
         impl Visit<'_> for ManyArgsVisitor {
@@ -1585,9 +3156,9 @@ impl PatternEngine {
                 let arg_count = func.sig.inputs.len() as u32;
 
                 if arg_count > self.threshold {
-                    let (line, col, context) =
-                        (1, 1, format!("fn {} ({} args)", fn_name, arg_count));
-                    self.matches.push((line, col, fn_name, arg_count, context));
+                    let context = format!("fn {} ({} args)", fn_name, arg_count);
+                    self.matches
+                        .push((func.sig.ident.span(), fn_name, arg_count, context));
                 }
 
                 syn::visit::visit_item_fn(self, func);
@@ -1604,12 +3175,12 @@ impl PatternEngine {
     }
 
     /// Find blocking calls in async functions
-    fn find_blocking_in_async(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String, String)> {
+    fn find_blocking_in_async(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct BlockingInAsyncVisitor {
             in_async_fn: bool,
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for BlockingInAsyncVisitor {
@@ -1639,8 +3210,9 @@ impl PatternEngine {
                     .contains(&method_name.as_str())
                     {
                         // Check if it's not awaited
-                        let (line, col, context) = (1, 1, format!(".{}()", method_name));
-                        self.matches.push((line, col, method_name, context));
+                        let context = format!(".{}()", method_name);
+                        self.matches
+                            .push((method_call.method.span(), method_name, context));
                     }
                 }
 
@@ -1657,8 +3229,8 @@ impl PatternEngine {
                             if ["thread::sleep", "std::thread::sleep", "sleep"]
                                 .contains(&fn_name.as_str())
                             {
-                                let (line, col, context) = (1, 1, format!("{}()", fn_name));
-                                self.matches.push((line, col, fn_name, context));
+                                let context = format!("{}()", fn_name);
+                                self.matches.push((call.func.span(), fn_name, context));
                             }
                         }
                     }
@@ -1678,11 +3250,11 @@ impl PatternEngine {
     }
 
     /// Find futures that are not awaited
-    fn find_futures_not_awaited(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String, String)> {
+    fn find_futures_not_awaited(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct FutureNotAwaitedVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for FutureNotAwaitedVisitor {
@@ -1697,9 +3269,9 @@ impl PatternEngine {
                             || ["spawn", "spawn_blocking", "timeout", "sleep"]
                                 .contains(&fn_name.as_str())
                         {
-                            let (line, col, context) = (1, 1, format!("{}() not awaited", fn_name));
+                            let context = format!("{}() not awaited", fn_name);
                             self.matches
-                                .push((line, col, format!("{}()", fn_name), context));
+                                .push((call.span(), format!("{}()", fn_name), context));
                         }
                     }
                 }
@@ -1717,11 +3289,11 @@ impl PatternEngine {
     }
 
     /// Find tokio::select! without biased
-    fn find_select_without_biased(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String)> {
+    fn find_select_without_biased(&self, syntax_tree: &syn::File) -> Vec<(Span, String)> {
         use syn::visit::Visit;
 
         struct SelectVisitor {
-            matches: Vec<(u32, u32, String)>,
+            matches: Vec<(Span, String)>,
         }
 
         impl Visit<'_> for SelectVisitor {
@@ -1731,9 +3303,8 @@ impl PatternEngine {
                         // Check if it's tokio::select!
                         let macro_str = format!("{}", quote::quote!(#mac));
                         if macro_str.contains("select!") && !macro_str.contains("biased") {
-                            let (line, col, context) =
-                                (1, 1, "tokio::select! without biased".to_string());
-                            self.matches.push((line, col, context));
+                            let context = "tokio::select! without biased".to_string();
+                            self.matches.push((mac.span(), context));
                         }
                     }
                 }
@@ -1750,14 +3321,11 @@ impl PatternEngine {
     }
 
     /// Find generics without trait bounds
-    fn find_generics_without_bounds(
-        &self,
-        syntax_tree: &syn::File,
-    ) -> Vec<(u32, u32, String, String)> {
+    fn find_generics_without_bounds(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct GenericBoundsVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for GenericBoundsVisitor {
@@ -1766,8 +3334,9 @@ impl PatternEngine {
                     if let syn::GenericParam::Type(type_param) = param {
                         if type_param.bounds.is_empty() {
                             let generic_name = type_param.ident.to_string();
-                            let (line, col, context) = (1, 1, format!("<{}>", generic_name));
-                            self.matches.push((line, col, generic_name, context));
+                            let context = format!("<{}>", generic_name);
+                            self.matches
+                                .push((type_param.ident.span(), generic_name, context));
                         }
                     }
                 }
@@ -1780,12 +3349,9 @@ impl PatternEngine {
                     if let syn::GenericParam::Type(type_param) = param {
                         if type_param.bounds.is_empty() {
                             let generic_name = type_param.ident.to_string();
-                            let (line, col, context) = (
-                                1,
-                                1,
-                                format!("struct {}<{}>", item_struct.ident, generic_name),
-                            );
-                            self.matches.push((line, col, generic_name, context));
+                            let context = format!("struct {}<{}>", item_struct.ident, generic_name);
+                            self.matches
+                                .push((type_param.ident.span(), generic_name, context));
                         }
                     }
                 }
@@ -1806,11 +3372,11 @@ impl PatternEngine {
     fn find_test_functions_without_assertions(
         &self,
         syntax_tree: &syn::File,
-    ) -> Vec<(u32, u32, String, String)> {
+    ) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct TestAssertionVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for TestAssertionVisitor {
@@ -1823,8 +3389,8 @@ impl PatternEngine {
 
                     // Check if function body contains assertions
                     if !self.has_assertions(&func.block) {
-                        let (line, col, context) = (1, 1, format!("#[test] fn {}", fn_name));
-                        self.matches.push((line, col, fn_name, context));
+                        let context = format!("#[test] fn {}", fn_name);
+                        self.matches.push((func.sig.ident.span(), fn_name, context));
                     }
                 }
 
@@ -1879,11 +3445,11 @@ impl PatternEngine {
     }
 
     /// Find impl blocks without traits
-    fn find_impl_without_trait(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String, String)> {
+    fn find_impl_without_trait(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct ImplTraitVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for ImplTraitVisitor {
@@ -1900,8 +3466,9 @@ impl PatternEngine {
                         _ => "Unknown".to_string(),
                     };
 
-                    let (line, col, context) = (1, 1, format!("impl {}", type_name));
-                    self.matches.push((line, col, type_name, context));
+                    let context = format!("impl {}", type_name);
+                    self.matches
+                        .push((impl_item.self_ty.span(), type_name, context));
                 }
 
                 syn::visit::visit_item_impl(self, impl_item);
@@ -1917,17 +3484,17 @@ impl PatternEngine {
     }
 
     /// Find unsafe blocks
-    fn find_unsafe_blocks(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String)> {
+    fn find_unsafe_blocks(&self, syntax_tree: &syn::File) -> Vec<(Span, String)> {
         use syn::visit::Visit;
 
         struct UnsafeVisitor {
-            matches: Vec<(u32, u32, String)>,
+            matches: Vec<(Span, String)>,
         }
 
         impl Visit<'_> for UnsafeVisitor {
             fn visit_expr_unsafe(&mut self, expr: &syn::ExprUnsafe) {
-                let (line, col, context) = (1, 1, "unsafe block".to_string());
-                self.matches.push((line, col, context));
+                let context = "unsafe block".to_string();
+                self.matches.push((expr.unsafe_token.span, context));
 
                 syn::visit::visit_expr_unsafe(self, expr);
             }
@@ -1935,8 +3502,8 @@ impl PatternEngine {
             fn visit_item_fn(&mut self, func: &syn::ItemFn) {
                 if func.sig.unsafety.is_some() {
                     let fn_name = func.sig.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("unsafe fn {}", fn_name));
-                    self.matches.push((line, col, context));
+                    let context = format!("unsafe fn {}", fn_name);
+                    self.matches.push((func.sig.unsafety.span(), context));
                 }
 
                 syn::visit::visit_item_fn(self, func);
@@ -1951,12 +3518,65 @@ impl PatternEngine {
         visitor.matches
     }
 
+    /// Find string and byte-string literals, including those inside macro invocations
+    ///
+    /// Returns the literal span, its kind (`string` or `byte string`), and its value bytes.
+    fn find_string_literals(&self, syntax_tree: &syn::File) -> Vec<(Span, &'static str, Vec<u8>)> {
+        use syn::visit::Visit;
+
+        struct StringLiteralVisitor {
+            matches: Vec<(Span, &'static str, Vec<u8>)>,
+        }
+
+        impl StringLiteralVisitor {
+            fn record(&mut self, lit: &syn::Lit) {
+                match lit {
+                    syn::Lit::Str(s) => {
+                        self.matches
+                            .push((s.span(), "string", s.value().into_bytes()))
+                    }
+                    syn::Lit::ByteStr(b) => self.matches.push((b.span(), "byte string", b.value())),
+                    _ => {}
+                }
+            }
+
+            fn record_tokens(&mut self, tokens: proc_macro2::TokenStream) {
+                for token in tokens {
+                    match token {
+                        proc_macro2::TokenTree::Literal(literal) => {
+                            self.record(&syn::Lit::new(literal))
+                        }
+                        proc_macro2::TokenTree::Group(group) => self.record_tokens(group.stream()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        impl Visit<'_> for StringLiteralVisitor {
+            fn visit_lit(&mut self, lit: &syn::Lit) {
+                self.record(lit);
+            }
+
+            fn visit_macro(&mut self, mac: &syn::Macro) {
+                self.record_tokens(mac.tokens.clone());
+            }
+        }
+
+        let mut visitor = StringLiteralVisitor {
+            matches: Vec::new(),
+        };
+
+        visitor.visit_file(syntax_tree);
+        visitor.matches
+    }
+
     /// Find ignored test functions
-    fn find_ignored_tests(&self, syntax_tree: &syn::File) -> Vec<(u32, u32, String, String)> {
+    fn find_ignored_tests(&self, syntax_tree: &syn::File) -> Vec<(Span, String, String)> {
         use syn::visit::Visit;
 
         struct IgnoredTestVisitor {
-            matches: Vec<(u32, u32, String, String)>,
+            matches: Vec<(Span, String, String)>,
         }
 
         impl Visit<'_> for IgnoredTestVisitor {
@@ -1967,8 +3587,8 @@ impl PatternEngine {
 
                 if is_test && is_ignored {
                     let fn_name = func.sig.ident.to_string();
-                    let (line, col, context) = (1, 1, format!("#[ignore] #[test] fn {}", fn_name));
-                    self.matches.push((line, col, fn_name, context));
+                    let context = format!("#[ignore] #[test] fn {}", fn_name);
+                    self.matches.push((func.sig.ident.span(), fn_name, context));
                 }
 
                 syn::visit::visit_item_fn(self, func);
@@ -1988,6 +3608,7 @@ impl PatternEngine {
         matches
             .into_iter()
             .map(|m| {
+                let metadata = self.rule_metadata(&m.rule_id).cloned();
                 let mut violation = Violation::new(m.rule_id, m.severity, m.file_path, m.message);
 
                 if let Some(line) = m.line_number {
@@ -1996,14 +3617,110 @@ impl PatternEngine {
                     }
                 }
 
+                if let (Some(end_line), Some(end_column)) = (m.end_line, m.end_column) {
+                    violation = violation.with_end_position(end_line, end_column);
+                }
+
                 if let Some(context) = m.context {
                     violation = violation.with_context(context);
                 }
 
+                if let Some(range) = m.byte_range {
+                    violation = violation.with_byte_range(range);
+                }
+
+                if let Some(metadata) = metadata {
+                    if let Some(category) = metadata.category {
+                        violation = violation.with_category(category);
+                    }
+                    if let Some(docs_url) = metadata.docs_url {
+                        violation = violation.with_docs_url(docs_url);
+                    }
+                    if let Some(rationale) = metadata.rationale {
+                        violation = violation.with_rationale(rationale);
+                    }
+                    violation = violation.with_tags(metadata.tags);
+                }
+
                 violation
             })
             .collect()
     }
+
+    /// Attach the fixes that rule fix templates give violations found in content
+    ///
+    /// Violations that already carry a fix, or have no matched range, are left alone. An
+    /// insertion is given once per line and rule, so several matches on a line insert once.
+    pub fn attach_fixes(&self, violations: &mut [Violation], content: &str) {
+        let mut inserted = HashSet::new();
+        for violation in violations.iter_mut() {
+            if violation.fix.is_some() {
+                continue;
+            }
+            let Some(template) = self
+                .rule_metadata(&violation.rule_id)
+                .and_then(|metadata| metadata.fix.as_ref())
+            else {
+                continue;
+            };
+            let Some(range) = violation.byte_range.clone() else {
+                continue;
+            };
+            if range.end > content.len() || !content.is_char_boundary(range.start) {
+                continue;
+            }
+
+            if let Some(replacement) = &template.replace_match_with {
+                let replacement = match self.rules.regex_patterns.get(&violation.rule_id) {
+                    Some(pattern) => expand_captures(&pattern.regex, content, &range, replacement),
+                    None => replacement.clone(),
+                };
+                violation.fix = Some(Fix::replace(range, replacement));
+            } else if let Some(line) = &template.insert_before_line {
+                let line_start = content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+                if !inserted.insert((violation.rule_id.clone(), line_start)) {
+                    continue;
+                }
+                let indent: String = content[line_start..]
+                    .chars()
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .collect();
+                violation.fix = Some(Fix::insert(line_start, format!("{indent}{line}\n")));
+            }
+        }
+    }
+
+    /// Look up the metadata of a registered rule
+    fn rule_metadata(&self, rule_id: &str) -> Option<&RuleMetadata> {
+        self.rules
+            .regex_patterns
+            .get(rule_id)
+            .map(|pattern| &pattern.metadata)
+            .or_else(|| {
+                self.rules
+                    .required_patterns
+                    .get(rule_id)
+                    .map(|pattern| &pattern.metadata)
+            })
+            .or_else(|| {
+                self.rules
+                    .ast_patterns
+                    .get(rule_id)
+                    .map(|pattern| &pattern.metadata)
+            })
+            .or_else(|| {
+                self.rules
+                    .composite_patterns
+                    .get(rule_id)
+                    .map(|pattern| &pattern.metadata)
+            })
+            .or_else(|| {
+                self.rules
+                    .file_pair_patterns
+                    .get(rule_id)
+                    .map(|pattern| &pattern.metadata)
+            })
+    }
 }
 
 impl Default for PatternEngine {
@@ -2012,6 +3729,137 @@ impl Default for PatternEngine {
     }
 }
 
+/// Length of a literal if it looks like base64 data, or 0 if it does not
+///
+/// Whitespace is ignored so line-wrapped blobs count. Plain words and identifiers are
+/// excluded by requiring digits and both letter cases.
+fn base64_length(value: &[u8]) -> usize {
+    let data: Vec<u8> = value
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    let is_base64 = data
+        .iter()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'))
+        && data.iter().any(u8::is_ascii_digit)
+        && data.iter().any(u8::is_ascii_uppercase)
+        && data.iter().any(u8::is_ascii_lowercase);
+
+    if is_base64 {
+        data.len()
+    } else {
+        0
+    }
+}
+
+/// Whether a file falls within a rule's `applies_to` globs (no globs means every file)
+fn in_scope(scope: &[glob::Pattern], file_path: &Path) -> bool {
+    scope.is_empty() || scope.iter().any(|pattern| pattern.matches_path(file_path))
+}
+
+/// Expand the capture groups of the regex match at a range into a replacement template
+///
+/// The template is used as is when the regex no longer matches exactly that range.
+fn expand_captures(regex: &Regex, content: &str, range: &Range<usize>, template: &str) -> String {
+    match regex.captures_at(content, range.start) {
+        Some(captures) if captures.get(0).is_some_and(|m| m.range() == *range) => {
+            let mut expanded = String::new();
+            captures.expand(template, &mut expanded);
+            expanded
+        }
+        _ => template.to_string(),
+    }
+}
+
+/// Whether a source line holds code, tracking `/* */` comments across lines
+fn is_code_line(line: &str, in_block_comment: &mut bool) -> bool {
+    let mut rest = line.trim();
+    let mut has_code = false;
+
+    loop {
+        if *in_block_comment {
+            match rest.find("*/") {
+                Some(end) => {
+                    *in_block_comment = false;
+                    rest = rest[end + 2..].trim_start();
+                }
+                None => return has_code,
+            }
+        }
+
+        let next_comment = [rest.find("//"), rest.find("/*")]
+            .into_iter()
+            .flatten()
+            .min();
+        match next_comment {
+            Some(start) => {
+                has_code |= !rest[..start].trim().is_empty();
+                if rest[start..].starts_with("//") {
+                    return has_code;
+                }
+                *in_block_comment = true;
+                rest = &rest[start + 2..];
+            }
+            None => return has_code || !rest.is_empty(),
+        }
+    }
+}
+
+/// Parse a `|`-separated list of module paths such as `telemetry|crate::jobs::background`
+///
+/// Paths are relative to the crate root, so a leading `crate::` is dropped.
+fn parse_module_list(list: &str) -> Vec<Vec<String>> {
+    list.split('|')
+        .map(str::trim)
+        .map(|module| module.strip_prefix("crate::").unwrap_or(module))
+        .filter(|module| !module.is_empty())
+        .map(|module| module.split("::").map(str::to_string).collect())
+        .collect()
+}
+
+/// Whether `module` is one of `modules` or nested inside one of them
+fn module_listed(modules: &[Vec<String>], module: &[String]) -> bool {
+    modules.iter().any(|listed| module.starts_with(listed))
+}
+
+/// Parse a `|`-separated list of macro or function names
+///
+/// A trailing `suffix` (`!` for macros, `()` for functions) is accepted and dropped, so
+/// `stub!` and `stub` configure the same macro.
+fn parse_name_list(list: &str, suffix: &str, rule_id: &str) -> GuardianResult<Vec<String>> {
+    let names: Vec<String> = list
+        .split('|')
+        .map(|name| name.trim())
+        .map(|name| name.strip_suffix(suffix).unwrap_or(name).trim_end())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if names.is_empty() {
+        return Err(GuardianError::pattern(format!(
+            "Rule '{rule_id}' lists no names to match"
+        )));
+    }
+    Ok(names)
+}
+
+/// The written form of `path` if it names one of `targets`
+///
+/// A target matches either the full path (`testing::stub`) or its last segment (`stub`).
+fn matching_path(path: &syn::Path, targets: &[String]) -> Option<String> {
+    let last = path.segments.last()?.ident.to_string();
+    let full = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+
+    (targets.contains(&last) || targets.contains(&full)).then_some(full)
+}
+
 /// Architecture-compliant validation functions for integration testing
 #[allow(dead_code)]
 pub mod validation {
@@ -2031,6 +3879,20 @@ pub mod validation {
             enabled: true,
             case_sensitive: true,
             exclude_if: None,
+            tags: Vec::new(),
+            docs_url: None,
+            rationale: None,
+            applies_to: Vec::new(),
+            deprecated: false,
+            replaced_by: None,
+            only_cfg: None,
+            all_of: Vec::new(),
+            any_of: Vec::new(),
+            none_of: Vec::new(),
+            near: None,
+            good_example: None,
+            bad_example: None,
+            fix: None,
         };
 
         engine.add_rule(&rule, Severity::Warning)?;
@@ -2063,6 +3925,20 @@ pub mod validation {
             enabled: true,
             case_sensitive: true,
             exclude_if: None,
+            tags: Vec::new(),
+            docs_url: None,
+            rationale: None,
+            applies_to: Vec::new(),
+            deprecated: false,
+            replaced_by: None,
+            only_cfg: None,
+            all_of: Vec::new(),
+            any_of: Vec::new(),
+            none_of: Vec::new(),
+            near: None,
+            good_example: None,
+            bad_example: None,
+            fix: None,
         };
 
         engine.add_rule(&rule, Severity::Error)?;
@@ -2100,6 +3976,20 @@ pub mod validation {
                 in_tests: true,
                 file_patterns: None,
             }),
+            tags: Vec::new(),
+            docs_url: None,
+            rationale: None,
+            applies_to: Vec::new(),
+            deprecated: false,
+            replaced_by: None,
+            only_cfg: None,
+            all_of: Vec::new(),
+            any_of: Vec::new(),
+            none_of: Vec::new(),
+            near: None,
+            good_example: None,
+            bad_example: None,
+            fix: None,
         };
 
         engine.add_rule(&rule, Severity::Warning)?;
@@ -2125,3 +4015,253 @@ pub mod validation {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, rule_type: RuleType, pattern: &str, message: &str) -> PatternRule {
+        let mut rule = GuardianConfig::default()
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .find(|rule| rule.id == "todo_comments")
+            .expect("default config should define todo_comments")
+            .clone();
+        rule.id = id.to_string();
+        rule.rule_type = rule_type;
+        rule.pattern = pattern.to_string();
+        rule.message = message.to_string();
+        rule
+    }
+
+    #[test]
+    fn test_naming_rule_scope() {
+        let mut engine = PatternEngine::new();
+        let mut rule = rule(
+            "repository_outside_infra",
+            RuleType::Semantic,
+            "type_suffix:Repository",
+            "{name} belongs in the infrastructure layer",
+        );
+        rule.applies_to = vec!["**/domain/**".to_string()];
+        engine
+            .add_rule(&rule, Severity::Warning)
+            .expect("rule should compile");
+
+        let content = "pub struct UserRepository;\n";
+        let in_domain = engine
+            .analyze_file(Path::new("src/domain/user.rs"), content)
+            .expect("analysis should succeed");
+        let in_infra = engine
+            .analyze_file(Path::new("src/infra/user.rs"), content)
+            .expect("analysis should succeed");
+
+        assert_eq!(in_domain.len(), 1);
+        assert_eq!(
+            in_domain[0].message,
+            "UserRepository belongs in the infrastructure layer"
+        );
+        assert!(in_infra.is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_lists() {
+        let mut engine = PatternEngine::new();
+        for (id, pattern) in [
+            (
+                "placeholder_macros",
+                "macro_call:stub!|not_yet|testing::debug_todo",
+            ),
+            (
+                "placeholder_functions",
+                "function_call:unimplemented_stub()|fake_value",
+            ),
+        ] {
+            let rule = rule(
+                id,
+                RuleType::Ast,
+                pattern,
+                "Placeholder implementation found",
+            );
+            engine
+                .add_rule(&rule, Severity::Error)
+                .expect("rule should compile");
+        }
+
+        let content = r#"
+fn handler() -> u32 {
+    stub!();
+    self::not_yet!("later");
+    testing::debug_todo!();
+    let value = unimplemented_stub();
+    value + config.fake_value() + real_value()
+}
+"#;
+        let mut matches = engine
+            .analyze_file(Path::new("src/handler.rs"), content)
+            .expect("analysis should succeed");
+        matches.sort_by_key(|m| (m.line_number, m.column_number));
+
+        let texts: Vec<&str> = matches.iter().map(|m| m.matched_text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "stub!()",
+                "self::not_yet!()",
+                "testing::debug_todo!()",
+                "unimplemented_stub()",
+                "fake_value()",
+            ]
+        );
+        assert_eq!(matches[3].rule_id, "placeholder_functions");
+    }
+
+    #[test]
+    fn test_match_locations() {
+        let mut engine = PatternEngine::new();
+        engine
+            .add_rule(
+                &rule("todo_note", RuleType::Regex, r"TODO: \S+", "TODO found"),
+                Severity::Warning,
+            )
+            .expect("rule should compile");
+        engine
+            .add_rule(
+                &rule(
+                    "todo_macro",
+                    RuleType::Ast,
+                    "macro_call:todo",
+                    "todo! found",
+                ),
+                Severity::Error,
+            )
+            .expect("rule should compile");
+
+        // CRLF line endings, multi-byte characters before both matches, a macro spanning
+        // lines, and a comment ending the file without a trailing newline
+        let content = "// Grüße\r\nfn f() {\r\n    let é = 1; todo!(\r\n        \"später\"\r\n    )\r\n}\r\n// TODO: ünïcode";
+        let range_of = |needle: &str| {
+            let start = content.find(needle).expect("content should contain needle");
+            start..start + needle.len()
+        };
+        let matches = engine
+            .analyze_file(Path::new("src/lib.rs"), content)
+            .expect("analysis should succeed");
+        let location = |rule_id: &str| {
+            let found = matches
+                .iter()
+                .find(|m| m.rule_id == rule_id)
+                .expect("rule should match");
+            (
+                found.line_number,
+                found.column_number,
+                found.end_line,
+                found.end_column,
+                found.byte_range.clone(),
+            )
+        };
+
+        assert_eq!(
+            location("todo_note"),
+            (
+                Some(7),
+                Some(4),
+                Some(7),
+                Some(17),
+                Some(range_of("TODO: ünïcode"))
+            )
+        );
+        assert_eq!(range_of("TODO: ünïcode").end, content.len());
+        assert_eq!(
+            location("todo_macro"),
+            (
+                Some(3),
+                Some(16),
+                Some(5),
+                Some(6),
+                Some(range_of("todo!(\r\n        \"später\"\r\n    )"))
+            )
+        );
+    }
+
+    #[test]
+    fn test_function_length_counts_code_lines() {
+        let mut engine = PatternEngine::new();
+        let rule = rule(
+            "long_function",
+            RuleType::Semantic,
+            "function_lines_gt:3",
+            "Function has {lines} lines",
+        );
+        engine
+            .add_rule(&rule, Severity::Warning)
+            .expect("rule should compile");
+
+        // Blank and comment-only lines do not count, however the body is formatted
+        let content = r#"
+fn documented() {
+    // Step one
+    let a = 1;
+
+    /* Step two
+       spans lines */
+    let b = 2;
+    let c = a + b; // trailing comment
+}
+
+fn dense() {
+    let a = 1;
+    let b = 2;
+    let c = a + b;
+    println!("{c}");
+}
+
+fn inline() { let a = 1; let b = 2; let c = 3; let d = 4; }
+"#;
+        let matches = engine
+            .analyze_file(Path::new("src/steps.rs"), content)
+            .expect("analysis should succeed");
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].matched_text, "fn dense");
+        assert_eq!(matches[0].message, "Function has 4 lines");
+    }
+
+    #[test]
+    fn test_literal_blobs() {
+        let mut engine = PatternEngine::new();
+        for (id, pattern) in [
+            ("large_literal", "large_literal:64"),
+            ("base64_blob", "base64_blob:32"),
+        ] {
+            let rule = rule(
+                id,
+                RuleType::Semantic,
+                pattern,
+                "Embedded blob ({value}) - move it to an asset file",
+            );
+            engine
+                .add_rule(&rule, Severity::Warning)
+                .expect("rule should compile");
+        }
+
+        let content = format!(
+            "const FIXTURE: &[u8] = b\"{}\";\nconst KEY: &str = \"QUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVo0NTY3ODk=\";\nconst NOTE: &str = \"short and readable\";\n",
+            "x".repeat(100)
+        );
+        let mut matches = engine
+            .analyze_file(Path::new("src/assets.rs"), &content)
+            .expect("analysis should succeed");
+        matches.sort_by_key(|m| m.line_number);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].rule_id, "large_literal");
+        assert_eq!(
+            matches[0].message,
+            "Embedded blob (100) - move it to an asset file"
+        );
+        assert_eq!(matches[1].rule_id, "base64_blob");
+        assert_eq!(matches[1].line_number, Some(2));
+    }
+}
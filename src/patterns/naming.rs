@@ -0,0 +1,288 @@
+//! Naming convention checks over Rust item identifiers
+//!
+//! Architecture: Domain Services - Naming policies are pure functions of the syntax tree
+//! - Each policy inspects declared identifiers only; uses and call sites are never flagged
+//! - Identifiers are split into words so policies match whole words, not substrings
+//! - Scoping to parts of the tree is left to the rule's path globs
+
+use super::scope::is_test_fn;
+use proc_macro2::Span;
+use syn::visit::Visit;
+
+/// A configurable naming policy
+#[derive(Debug, Clone)]
+pub enum NamingPolicy {
+    /// Module names must be snake_case
+    ModuleSnakeCase,
+    /// Type names must not end with this suffix (e.g. `Repository`)
+    TypeSuffix(String),
+    /// Type names must not start with this prefix followed by a capitalized word (e.g. `I`)
+    TypePrefix(String),
+    /// Item names must not contain any of these words (e.g. `Manager`, `Util`)
+    ForbiddenWords(Vec<String>),
+    /// Test function names must start with this prefix
+    TestFnPrefix(String),
+}
+
+impl NamingPolicy {
+    /// Parse a semantic pattern into a naming policy
+    ///
+    /// Returns `None` when the pattern is not a naming pattern.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        if pattern == "module_snake_case" {
+            return Some(NamingPolicy::ModuleSnakeCase);
+        }
+        if let Some(suffix) = pattern.strip_prefix("type_suffix:") {
+            return Some(NamingPolicy::TypeSuffix(suffix.trim().to_string()));
+        }
+        if let Some(prefix) = pattern.strip_prefix("type_prefix:") {
+            return Some(NamingPolicy::TypePrefix(prefix.trim().to_string()));
+        }
+        if let Some(words) = pattern.strip_prefix("forbidden_names:") {
+            return Some(NamingPolicy::ForbiddenWords(
+                words
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ));
+        }
+        if let Some(prefix) = pattern.strip_prefix("test_fn_prefix:") {
+            return Some(NamingPolicy::TestFnPrefix(prefix.trim().to_string()));
+        }
+        None
+    }
+}
+
+/// Find identifiers violating a naming policy
+///
+/// Returns the identifier span, the identifier, and a short declaration as context.
+pub fn find_naming_violations(
+    syntax_tree: &syn::File,
+    policy: &NamingPolicy,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = NamingVisitor {
+        policy,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct NamingVisitor<'a> {
+    policy: &'a NamingPolicy,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl NamingVisitor<'_> {
+    fn check_module(&mut self, ident: &syn::Ident) {
+        let name = ident_name(ident);
+        let violates = match self.policy {
+            NamingPolicy::ModuleSnakeCase => !is_snake_case(&name),
+            NamingPolicy::ForbiddenWords(words) => contains_word(&name, words),
+            _ => false,
+        };
+        if violates {
+            self.push(ident, "mod", name);
+        }
+    }
+
+    fn check_type(&mut self, ident: &syn::Ident, keyword: &str) {
+        let name = ident_name(ident);
+        let violates = match self.policy {
+            NamingPolicy::TypeSuffix(suffix) => {
+                name.len() > suffix.len() && name.ends_with(suffix.as_str())
+            }
+            NamingPolicy::TypePrefix(prefix) => name
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(char::is_uppercase),
+            NamingPolicy::ForbiddenWords(words) => contains_word(&name, words),
+            _ => false,
+        };
+        if violates {
+            self.push(ident, keyword, name);
+        }
+    }
+
+    fn check_fn(&mut self, sig: &syn::Signature, attrs: &[syn::Attribute]) {
+        let name = ident_name(&sig.ident);
+        match self.policy {
+            NamingPolicy::ForbiddenWords(words) if contains_word(&name, words) => {
+                self.push(&sig.ident, "fn", name);
+            }
+            NamingPolicy::TestFnPrefix(prefix)
+                if is_test_fn(attrs) && !name.starts_with(prefix.as_str()) =>
+            {
+                self.push(&sig.ident, "#[test] fn", name);
+            }
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, ident: &syn::Ident, keyword: &str, name: String) {
+        let context = format!("{keyword} {name}");
+        self.matches.push((ident.span(), name, context));
+    }
+}
+
+impl Visit<'_> for NamingVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &syn::ItemMod) {
+        self.check_module(&item.ident);
+        syn::visit::visit_item_mod(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &syn::ItemStruct) {
+        self.check_type(&item.ident, "struct");
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &syn::ItemEnum) {
+        self.check_type(&item.ident, "enum");
+        syn::visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_union(&mut self, item: &syn::ItemUnion) {
+        self.check_type(&item.ident, "union");
+        syn::visit::visit_item_union(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &syn::ItemTrait) {
+        self.check_type(&item.ident, "trait");
+        syn::visit::visit_item_trait(self, item);
+    }
+
+    fn visit_item_type(&mut self, item: &syn::ItemType) {
+        self.check_type(&item.ident, "type");
+        syn::visit::visit_item_type(self, item);
+    }
+
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        self.check_fn(&item.sig, &item.attrs);
+        syn::visit::visit_item_fn(self, item);
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        self.check_fn(&item.sig, &item.attrs);
+        syn::visit::visit_impl_item_fn(self, item);
+    }
+}
+
+/// Identifier text without a raw identifier prefix
+fn ident_name(ident: &syn::Ident) -> String {
+    let name = ident.to_string();
+    match name.strip_prefix("r#") {
+        Some(stripped) => stripped.to_string(),
+        None => name,
+    }
+}
+
+/// Whether a name is lowercase words separated by single underscores
+fn is_snake_case(name: &str) -> bool {
+    let trimmed = name.trim_start_matches('_');
+    !trimmed.is_empty()
+        && !trimmed.contains("__")
+        && trimmed
+            .chars()
+            .all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Whether any word of `name` equals one of `words`, ignoring case
+fn contains_word(name: &str, words: &[String]) -> bool {
+    split_words(name).iter().any(|word| {
+        words
+            .iter()
+            .any(|forbidden| word.eq_ignore_ascii_case(forbidden))
+    })
+}
+
+/// Split a snake_case or CamelCase identifier into words
+///
+/// Acronyms stay together: `HTTPServerManager` splits into `HTTP`, `Server`, `Manager`.
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "mod HttpClient {}\nmod http_client {}\nstruct UserRepository;\nstruct Repository;\nstruct IService;\nstruct Index;\nfn string_util() {}\nfn utility() {}\n#[test]\nfn works() {}\n#[tokio::test]\nasync fn test_async() {}\n";
+
+    fn names(policy: NamingPolicy) -> Vec<String> {
+        let syntax_tree = syn::parse_file(SOURCE).expect("source should parse");
+        find_naming_violations(&syntax_tree, &policy)
+            .into_iter()
+            .map(|(_, name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn test_module_snake_case() {
+        assert_eq!(names(NamingPolicy::ModuleSnakeCase), ["HttpClient"]);
+    }
+
+    #[test]
+    fn test_type_suffix_and_prefix() {
+        // A bare suffix names the concept itself, not a type carrying it
+        assert_eq!(
+            names(NamingPolicy::TypeSuffix("Repository".to_string())),
+            ["UserRepository"]
+        );
+        // `Index` starts with `I` but not with `I` plus a capitalized word
+        assert_eq!(
+            names(NamingPolicy::TypePrefix("I".to_string())),
+            ["IService"]
+        );
+    }
+
+    #[test]
+    fn test_forbidden_words() {
+        assert_eq!(
+            names(NamingPolicy::ForbiddenWords(vec!["Util".to_string()])),
+            ["string_util"]
+        );
+        assert_eq!(
+            split_words("HTTPServerManager"),
+            ["HTTP", "Server", "Manager"]
+        );
+    }
+
+    #[test]
+    fn test_test_fn_prefix() {
+        assert_eq!(
+            names(NamingPolicy::TestFnPrefix("test_".to_string())),
+            ["works"]
+        );
+    }
+}
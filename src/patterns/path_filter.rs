@@ -6,62 +6,205 @@
 //! - Handles .guardianignore file discovery and parsing
 
 use crate::domain::violations::{GuardianError, GuardianResult};
+use ignore::gitignore::{Gitignore, GitignoreBuilder, Glob};
+use ignore::Match;
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Directories that are never descended into during file discovery
+const SKIPPED_DIRECTORIES: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    ".venv",
+    "venv",
+    ".idea",
+    ".vscode",
+];
+
 /// Manages path filtering using .gitignore-style patterns
+///
+/// Patterns follow gitignore semantics, backed by the `ignore` crate: patterns containing a
+/// slash are anchored to the project root, trailing-slash patterns match directories, and
+/// the last matching pattern wins. A file is checked before its parent directories, so a
+/// `!` pattern naming a file re-includes it even inside an excluded directory.
 #[derive(Debug, Clone)]
 pub struct PathFilter {
     /// Include/exclude patterns
     patterns: Vec<FilterPattern>,
+    /// All configured patterns compiled together
+    matcher: Gitignore,
+    /// Directory configured patterns are anchored to
+    root: PathBuf,
+    /// Directory relative paths are resolved against
+    base: PathBuf,
     /// Whether to process .guardianignore files
     process_ignore_files: bool,
     /// Name of ignore files to process
     ignore_filename: String,
+    /// Whether to process .gitignore files inside the repository
+    use_gitignore: bool,
+    /// Whether discovery descends into symlinked directories
+    follow_symlinks: bool,
+    /// Whether files reachable by several paths are discovered only once
+    dedupe_files: bool,
+}
+
+/// Step-by-step account of how a path was filtered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathExplanation {
+    /// Every configured pattern with its effect on the path, in order
+    pub patterns: Vec<PatternOutcome>,
+    /// Ignore file pattern that decided the path, if any
+    pub ignore_file: Option<IgnoreFileMatch>,
+    /// Directory that directory discovery never enters, if the path is inside one
+    pub skipped_directory: Option<String>,
+    /// Whether the path passes filtering
+    pub analyzed: bool,
+}
+
+/// Effect of one configured pattern on a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternOutcome {
+    /// Pattern as configured, including any leading `!`
+    pub pattern: String,
+    /// `Some(true)` if it excludes the path, `Some(false)` if it re-includes it
+    pub excludes: Option<bool>,
+}
+
+/// An ignore file pattern that decided whether a path is ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreFileMatch {
+    /// The .guardianignore or .gitignore file
+    pub file: PathBuf,
+    /// The deciding pattern, including any leading `!`
+    pub pattern: String,
+    /// Whether the pattern ignores the path
+    pub ignored: bool,
+}
+
+/// Comparison of Guardian's matching with a directory walk by the `ignore` crate
+///
+/// Built by [`PathFilter::equivalence_check`] from ignore file content.
+#[derive(Debug, Clone)]
+pub struct EquivalenceCheck {
+    /// The ignore content compiled relative to the current directory
+    matcher: Gitignore,
+}
+
+/// A path that Guardian and a directory walk decide differently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The path compared
+    pub path: PathBuf,
+    /// Whether Guardian excludes the path
+    pub guardian_ignores: bool,
+    /// Pattern deciding Guardian's result, including any leading `!`
+    pub guardian_pattern: Option<String>,
+    /// Excluded directory that keeps a walk from reaching the path, if any
+    pub excluded_directory: Option<PathBuf>,
+    /// Pattern deciding the walk's result, including any leading `!`
+    pub reference_pattern: Option<String>,
+}
+
+/// Identity of a file independent of the path used to reach it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FileIdentity {
+    /// Device and inode, which also identify hardlinks
+    Inode { dev: u64, ino: u64 },
+    /// Canonical path, used where inodes are unavailable
+    Canonical(PathBuf),
 }
 
 /// A single path filter pattern
 #[derive(Debug, Clone)]
 struct FilterPattern {
-    /// The glob pattern
-    pattern: glob::Pattern,
-    /// Whether this is an include pattern (starts with !)
-    is_include: bool,
-    /// Original pattern string for debugging
+    /// The pattern compiled on its own, for debugging
+    matcher: Gitignore,
+    /// Original pattern string, including any leading `!`
     original: String,
 }
 
 impl PathFilter {
     /// Create a new path filter with the given patterns
+    ///
+    /// Patterns are anchored to the current working directory; see [`PathFilter::with_root`].
     pub fn new(patterns: Vec<String>, ignore_filename: Option<String>) -> GuardianResult<Self> {
-        let mut filter_patterns = Vec::new();
-
-        for pattern_str in patterns {
-            let (is_include, pattern_str) = if let Some(stripped) = pattern_str.strip_prefix('!') {
-                (true, stripped.to_string())
-            } else {
-                (false, pattern_str)
-            };
-
-            let pattern = glob::Pattern::new(&pattern_str).map_err(|e| {
-                GuardianError::pattern(format!("Invalid pattern '{pattern_str}': {e}"))
-            })?;
+        let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
 
+        let mut filter_patterns = Vec::new();
+        for pattern in patterns {
             filter_patterns.push(FilterPattern {
-                pattern,
-                is_include,
-                original: pattern_str,
+                matcher: build_matcher(&root, std::slice::from_ref(&pattern))?,
+                original: pattern,
             });
         }
 
+        let originals: Vec<String> = filter_patterns.iter().map(|p| p.original.clone()).collect();
+        let matcher = build_matcher(&root, &originals)?;
+
         Ok(Self {
             patterns: filter_patterns,
+            matcher,
+            base: root.clone(),
+            root,
             process_ignore_files: ignore_filename.is_some(),
             ignore_filename: ignore_filename.unwrap_or_else(|| ".guardianignore".to_string()),
+            use_gitignore: false,
+            follow_symlinks: false,
+            dedupe_files: true,
         })
     }
 
+    /// Anchor patterns to a project root instead of the working directory
+    ///
+    /// Relative paths passed to the filter are still resolved against the working directory,
+    /// so a filter rooted at the repository behaves the same from any subdirectory.
+    pub fn with_root(mut self, root: PathBuf) -> GuardianResult<Self> {
+        let originals: Vec<String> = self.patterns.iter().map(|p| p.original.clone()).collect();
+        for pattern in &mut self.patterns {
+            pattern.matcher = build_matcher(&root, std::slice::from_ref(&pattern.original))?;
+        }
+        self.matcher = build_matcher(&root, &originals)?;
+        self.root = root;
+        Ok(self)
+    }
+
+    /// Resolve a path for matching against root-anchored patterns
+    fn anchored<'p>(&self, path: &'p Path) -> Cow<'p, Path> {
+        if path.is_relative() && self.root != self.base {
+            Cow::Owned(self.base.join(path))
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    /// Set whether `.gitignore` files exclude paths, as they do for git
+    ///
+    /// Only `.gitignore` files inside the repository apply; the search stops at the
+    /// directory containing `.git`.
+    pub fn with_gitignore(mut self, use_gitignore: bool) -> Self {
+        self.use_gitignore = use_gitignore;
+        self
+    }
+
+    /// Set whether discovery descends into symlinked directories
+    ///
+    /// Symlink cycles are detected and skipped with a warning.
+    pub fn with_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Set whether files reachable by several paths (symlinks, hardlinks) are discovered once
+    pub fn with_deduplication(mut self, dedupe: bool) -> Self {
+        self.dedupe_files = dedupe;
+        self
+    }
+
     /// Create a default path filter with sensible exclusions
     pub fn with_defaults() -> GuardianResult<Self> {
         Self::new(
@@ -81,27 +224,14 @@ impl PathFilter {
     /// Check if a file should be analyzed based on all patterns and ignore files
     pub fn should_analyze<P: AsRef<Path>>(&self, path: P) -> GuardianResult<bool> {
         let path = path.as_ref();
-        let _path_str = path.to_string_lossy();
-
-        // Start with default: include all files
-        let mut should_include = true;
-
-        // Apply patterns in order (like .gitignore)
-        for pattern in &self.patterns {
-            let matches = self.pattern_matches_path(pattern, path);
-
-            if matches {
-                should_include = pattern.is_include;
-            }
-        }
 
         // If excluded by configured patterns, return false
-        if !should_include {
+        if matched_with_parents(&self.matcher, &self.anchored(path)).is_ignore() {
             return Ok(false);
         }
 
-        // Check .guardianignore files if enabled
-        if self.process_ignore_files {
+        // Check .guardianignore and .gitignore files if enabled
+        if self.process_ignore_files || self.use_gitignore {
             let ignored_by_files = self.is_ignored_by_files(path)?;
             if ignored_by_files {
                 return Ok(false);
@@ -111,125 +241,204 @@ impl PathFilter {
         Ok(true)
     }
 
-    /// Check if path is ignored by .guardianignore files
+    /// Check if path is ignored by .guardianignore or .gitignore files
     fn is_ignored_by_files<P: AsRef<Path>>(&self, path: P) -> GuardianResult<bool> {
-        let path = path.as_ref();
+        Ok(self
+            .ignore_file_match(path.as_ref())?
+            .is_some_and(|found| found.ignored))
+    }
+
+    /// Find the ignore file pattern deciding a path
+    ///
+    /// As with nested .gitignore files, the ignore file closest to the path decides. Within
+    /// one directory, .guardianignore takes precedence over .gitignore.
+    fn ignore_file_match(&self, path: &Path) -> GuardianResult<Option<IgnoreFileMatch>> {
         let mut current_dir = path.parent();
-        let mut is_ignored = false;
+        let mut inside_repository = true;
 
-        // Walk up the directory tree looking for .guardianignore files
+        // Walk up the directory tree looking for ignore files
         while let Some(dir) = current_dir {
-            let ignore_file = dir.join(&self.ignore_filename);
+            let mut ignore_files = Vec::new();
+            if self.process_ignore_files {
+                ignore_files.push(dir.join(&self.ignore_filename));
+            }
+            if self.use_gitignore && inside_repository {
+                ignore_files.push(dir.join(".gitignore"));
+            }
 
-            if ignore_file.exists() {
-                let patterns = self.load_ignore_file(&ignore_file)?;
+            for ignore_file in ignore_files {
+                if !ignore_file.exists() {
+                    continue;
+                }
 
-                // Check if any pattern in this file matches
-                for pattern in patterns {
-                    // Make path relative to the ignore file's directory
-                    if let Ok(relative_path) = path.strip_prefix(dir) {
-                        let matches = self.pattern_matches_path(&pattern, relative_path);
+                let matcher = self.load_ignore_file(dir, &ignore_file)?;
+                let (glob, ignored) = match matched_with_parents(&matcher, path) {
+                    Match::Ignore(glob) => (glob, true),
+                    Match::Whitelist(glob) => (glob, false),
+                    Match::None => continue,
+                };
+
+                return Ok(Some(IgnoreFileMatch {
+                    file: ignore_file,
+                    pattern: glob.original().to_string(),
+                    ignored,
+                }));
+            }
 
-                        if matches {
-                            is_ignored = !pattern.is_include;
-                        }
-                    }
-                }
+            // .gitignore files above the repository root do not apply
+            if dir.join(".git").exists() {
+                inside_repository = false;
             }
 
             current_dir = dir.parent();
         }
 
-        Ok(is_ignored)
+        Ok(None)
     }
 
-    /// Load patterns from a .guardianignore file
-    fn load_ignore_file<P: AsRef<Path>>(&self, path: P) -> GuardianResult<Vec<FilterPattern>> {
-        let content = fs::read_to_string(&path).map_err(|e| {
+    /// Load patterns from an ignore file, anchored to its directory
+    fn load_ignore_file(&self, dir: &Path, path: &Path) -> GuardianResult<Gitignore> {
+        let bytes = fs::read(path).map_err(|e| GuardianError::io(path, e))?;
+        let content = String::from_utf8_lossy(&bytes);
+        let mut builder = ignore_builder(dir);
+
+        // Invalid lines are reported but the remaining patterns still apply
+        for error in add_ignore_lines(&mut builder, Some(path), &content) {
+            tracing::warn!("Invalid pattern in {}: {}", path.display(), error);
+        }
+
+        builder.build().map_err(|e| {
             GuardianError::config(format!(
                 "Failed to read ignore file '{}': {}",
-                path.as_ref().display(),
+                path.display(),
                 e
             ))
-        })?;
-
-        let mut patterns = Vec::new();
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Skip empty lines and comments
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-
-            let (is_include, pattern_str) = if let Some(stripped) = line.strip_prefix('!') {
-                (true, stripped.to_string())
-            } else {
-                (false, line.to_string())
-            };
+        })
+    }
 
-            match glob::Pattern::new(&pattern_str) {
-                Ok(pattern) => {
-                    patterns.push(FilterPattern {
-                        pattern,
-                        is_include,
-                        original: pattern_str,
-                    });
-                }
-                Err(e) => {
-                    // Log warning but don't fail - just skip invalid patterns
-                    tracing::warn!(
-                        "Invalid pattern '{}' in {}: {}",
-                        pattern_str,
-                        path.as_ref().display(),
-                        e
-                    );
-                }
-            }
+    /// Check the contents of an ignore file, returning one message per invalid pattern
+    ///
+    /// Lines go through the same parser as `.guardianignore` files, without touching the
+    /// filesystem.
+    pub fn check_ignore_content(content: &str) -> Vec<String> {
+        let mut builder = ignore_builder(".");
+        let mut errors = add_ignore_lines(&mut builder, None, content);
+        if let Err(e) = builder.build() {
+            errors.push(e.to_string());
         }
-
-        Ok(patterns)
+        errors
     }
 
     /// Get all files that should be analyzed in a directory tree
+    ///
+    /// Entries are visited in file name order, so when duplicates are removed the first
+    /// path in that order is kept.
     pub fn find_files<P: AsRef<Path>>(&self, root: P) -> GuardianResult<Vec<PathBuf>> {
         let root = root.as_ref();
         let mut files = Vec::new();
+        let mut seen = HashSet::new();
 
         // OPTIMIZATION: Use filter_entry to skip massive directories BEFORE entering them
         let walker = WalkDir::new(root)
-            .follow_links(false)
+            .follow_links(self.follow_symlinks)
+            .sort_by_file_name()
             .into_iter()
             .filter_entry(|e| {
                 let name = e.file_name().to_string_lossy();
 
                 // SKIP common massive directories to prevent IO floods
-                if name == ".git"
-                    || name == "target"
-                    || name == "node_modules"
-                    || name == ".venv"
-                    || name == "venv"
-                    || name == ".idea"
-                    || name == ".vscode"
-                {
-                    return false;
-                }
-                true
+                !SKIPPED_DIRECTORIES.contains(&name.as_ref())
             });
 
-        for entry in walker.filter_map(|e| e.ok()) {
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    if let Some(ancestor) = e.loop_ancestor() {
+                        tracing::warn!(
+                            "Skipping symlink cycle at {} (loops back to {})",
+                            e.path().unwrap_or(root).display(),
+                            ancestor.display()
+                        );
+                    }
+                    continue;
+                }
+            };
             let path = entry.path();
 
             // Only process files, not directories
-            if path.is_file() && self.should_analyze(path)? {
-                files.push(path.to_path_buf());
+            if !path.is_file() || !self.should_analyze(path)? {
+                continue;
+            }
+
+            if self.dedupe_files {
+                if let Some(identity) = file_identity(path) {
+                    if !seen.insert(identity) {
+                        tracing::debug!("Skipping {} - already discovered", path.display());
+                        continue;
+                    }
+                }
             }
+
+            files.push(path.to_path_buf());
         }
 
         Ok(files)
     }
 
+    /// Check if `find_files` would discover a path
+    ///
+    /// In addition to `should_analyze`, rejects paths inside directories that discovery
+    /// never enters (`target/`, `.git/`, ...). Use this to filter file watcher events.
+    pub fn would_discover<P: AsRef<Path>>(&self, path: P) -> GuardianResult<bool> {
+        let path = path.as_ref();
+
+        if skipped_directory(path).is_some() {
+            return Ok(false);
+        }
+
+        self.should_analyze(path)
+    }
+
+    /// Explain each filtering step applied to a path, in evaluation order
+    ///
+    /// The final decision matches `should_analyze`. A skipped directory is reported
+    /// separately since it only affects directory discovery, not explicitly named files.
+    pub fn explain<P: AsRef<Path>>(&self, path: P) -> GuardianResult<PathExplanation> {
+        let path = path.as_ref();
+        let anchored = self.anchored(path);
+
+        let patterns = self
+            .patterns
+            .iter()
+            .map(|pattern| PatternOutcome {
+                pattern: pattern.original.clone(),
+                excludes: match matched_with_parents(&pattern.matcher, &anchored) {
+                    Match::Ignore(_) => Some(true),
+                    Match::Whitelist(_) => Some(false),
+                    Match::None => None,
+                },
+            })
+            .collect();
+
+        let excluded_by_patterns = matched_with_parents(&self.matcher, &anchored).is_ignore();
+        let ignore_file =
+            if excluded_by_patterns || !(self.process_ignore_files || self.use_gitignore) {
+                None
+            } else {
+                self.ignore_file_match(path)?
+            };
+
+        let analyzed = !excluded_by_patterns && ignore_file.as_ref().is_none_or(|m| !m.ignored);
+
+        Ok(PathExplanation {
+            patterns,
+            ignore_file,
+            skipped_directory: skipped_directory(path),
+            analyzed,
+        })
+    }
+
     /// Filter a list of paths to only those that should be analyzed
     pub fn filter_paths<P: AsRef<Path>>(&self, paths: &[P]) -> GuardianResult<Vec<PathBuf>> {
         let mut filtered = Vec::new();
@@ -245,37 +454,29 @@ impl PathFilter {
 
     /// Add a pattern to the filter
     pub fn add_pattern(&mut self, pattern: String) -> GuardianResult<()> {
-        let (is_include, pattern_str) = if let Some(stripped) = pattern.strip_prefix('!') {
-            (true, stripped.to_string())
-        } else {
-            (false, pattern)
+        let pattern = FilterPattern {
+            matcher: build_matcher(&self.root, std::slice::from_ref(&pattern))?,
+            original: pattern,
         };
+        self.patterns.push(pattern);
 
-        let glob_pattern = glob::Pattern::new(&pattern_str)
-            .map_err(|e| GuardianError::pattern(format!("Invalid pattern '{pattern_str}': {e}")))?;
-
-        self.patterns.push(FilterPattern {
-            pattern: glob_pattern,
-            is_include,
-            original: pattern_str,
-        });
+        let originals: Vec<String> = self.patterns.iter().map(|p| p.original.clone()).collect();
+        self.matcher = build_matcher(&self.root, &originals)?;
 
         Ok(())
     }
 
     /// Get debug information about patterns and their matches
     pub fn debug_patterns<P: AsRef<Path>>(&self, path: P) -> Vec<String> {
-        let path = path.as_ref();
+        let path = self.anchored(path.as_ref());
         let mut debug_info = Vec::new();
 
         for (i, pattern) in self.patterns.iter().enumerate() {
-            let matches = self.pattern_matches_path(pattern, path);
-            let prefix = if pattern.is_include { "!" } else { "" };
+            let matches = !matched_with_parents(&pattern.matcher, &path).is_none();
 
             debug_info.push(format!(
-                "Pattern {}: {}{} -> {}",
+                "Pattern {}: {} -> {}",
                 i,
-                prefix,
                 pattern.original,
                 if matches { "MATCH" } else { "no match" }
             ));
@@ -284,48 +485,188 @@ impl PathFilter {
         debug_info
     }
 
-    /// Check if a pattern matches a path using .gitignore-style rules
-    fn pattern_matches_path(&self, pattern: &FilterPattern, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
+    /// Compare Guardian's matching of ignore file content with the `ignore` crate's walker
+    ///
+    /// The walker, like git, never enters an excluded directory, so nothing inside it can be
+    /// re-included. Guardian checks a file before its directories, letting a `!` pattern
+    /// re-include a file in an excluded directory. Those re-included paths are the only
+    /// divergences; use the check to tell them apart from files the content itself excludes.
+    pub fn equivalence_check(gitignore_content: &str) -> GuardianResult<EquivalenceCheck> {
+        let mut builder = ignore_builder(".");
+        let errors = add_ignore_lines(&mut builder, None, gitignore_content);
+        if !errors.is_empty() {
+            return Err(GuardianError::pattern(format!(
+                "Invalid ignore content: {}",
+                errors.join("; ")
+            )));
+        }
 
-        // Handle different pattern types
-        if pattern.original.ends_with('/') {
-            // Directory pattern - only match directories
-            if !path.is_dir() {
-                return false;
-            }
-            // Remove trailing slash and match
-            let dir_pattern = pattern.original.trim_end_matches('/');
-            return glob::Pattern::new(dir_pattern)
-                .map(|p| p.matches(&path_str))
-                .unwrap_or(false);
-        }
-
-        if pattern.original.starts_with('/') {
-            // Absolute pattern from root - remove leading slash and match from beginning
-            let absolute_pattern = pattern
-                .original
-                .strip_prefix('/')
-                .unwrap_or(&pattern.original);
-            return glob::Pattern::new(absolute_pattern)
-                .map(|p| p.matches(&path_str))
-                .unwrap_or(false);
-        }
-
-        if pattern.original.contains('/') {
-            // Pattern contains slash - match full path
-            return pattern.pattern.matches(&path_str);
-        } else {
-            // No slash - match filename only
-            if let Some(filename) = path.file_name() {
-                return pattern.pattern.matches(&filename.to_string_lossy());
-            }
+        let matcher = builder.build().map_err(|e| {
+            GuardianError::pattern(format!("Failed to compile ignore content: {e}"))
+        })?;
+        Ok(EquivalenceCheck { matcher })
+    }
+}
+
+impl EquivalenceCheck {
+    /// Compare the decisions for one path, relative to the ignore file's directory
+    pub fn compare<P: AsRef<Path>>(&self, path: P) -> Option<Divergence> {
+        let path = path.as_ref();
+        let guardian = matched_with_parents(&self.matcher, path);
+        let (excluded_directory, reference) = walked_match(&self.matcher, path);
+
+        if guardian.is_ignore() == reference.is_ignore() {
+            return None;
+        }
+
+        Some(Divergence {
+            path: path.to_path_buf(),
+            guardian_ignores: guardian.is_ignore(),
+            guardian_pattern: deciding_pattern(&guardian),
+            excluded_directory,
+            reference_pattern: deciding_pattern(&reference),
+        })
+    }
+
+    /// Compare the decisions for many paths, returning only the divergent ones
+    pub fn divergences<P: AsRef<Path>>(&self, paths: &[P]) -> Vec<Divergence> {
+        paths.iter().filter_map(|path| self.compare(path)).collect()
+    }
+}
+
+/// Compile gitignore-style patterns anchored to `root`
+fn build_matcher(root: &Path, patterns: &[String]) -> GuardianResult<Gitignore> {
+    let mut builder = ignore_builder(root);
+
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| GuardianError::pattern(format!("Invalid pattern '{pattern}': {e}")))?;
+    }
+
+    builder
+        .build()
+        .map_err(|e| GuardianError::pattern(format!("Failed to compile path patterns: {e}")))
+}
+
+/// Start an ignore matcher rooted at a directory
+///
+/// Unclosed character classes such as `[abc` are rejected rather than matched literally, so
+/// typos in patterns surface as errors.
+fn ignore_builder<P: AsRef<Path>>(root: P) -> GitignoreBuilder {
+    let mut builder = GitignoreBuilder::new(root);
+    builder.allow_unclosed_class(false);
+    builder
+}
+
+/// Add each line of an ignore file to a builder, returning messages for invalid lines
+fn add_ignore_lines(
+    builder: &mut GitignoreBuilder,
+    source: Option<&Path>,
+    content: &str,
+) -> Vec<String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            builder
+                .add_line(source.map(Path::to_path_buf), line)
+                .err()
+                .map(|e| format!("line {}: {e}", index + 1))
+        })
+        .collect()
+}
+
+/// Match a path, then each of its parent directories from the deepest up
+///
+/// Paths under the matcher's root are made relative to it first. The first pattern decision
+/// found wins, so a decision about the file itself takes precedence over its directories.
+fn matched_with_parents<'a>(matcher: &'a Gitignore, path: &Path) -> Match<&'a Glob> {
+    let relative = path.strip_prefix(matcher.path()).unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+
+    let decision = matcher.matched(relative, path.is_dir());
+    if !decision.is_none() {
+        return decision;
+    }
+
+    for dir in relative.ancestors().skip(1) {
+        if dir.as_os_str().is_empty() {
+            break;
+        }
+
+        // Every ancestor is a directory, even for paths not on disk
+        let decision = matcher.matched(dir, true);
+        if !decision.is_none() {
+            return decision;
+        }
+    }
+
+    Match::None
+}
+
+/// Match a path the way a directory walk reaches it
+///
+/// Directories are checked from the top down and an excluded one is never entered, so its
+/// exclusion decides everything below it. Returns that directory along with the decision.
+fn walked_match<'a>(matcher: &'a Gitignore, path: &Path) -> (Option<PathBuf>, Match<&'a Glob>) {
+    let relative = path.strip_prefix(matcher.path()).unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+
+    let mut ancestors: Vec<&Path> = relative
+        .ancestors()
+        .skip(1)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .collect();
+    ancestors.reverse();
+
+    for dir in ancestors {
+        let decision = matcher.matched(dir, true);
+        if decision.is_ignore() {
+            return (Some(dir.to_path_buf()), decision);
         }
+    }
 
-        false
+    (None, matcher.matched(relative, path.is_dir()))
+}
+
+/// The pattern behind a match decision, including any leading `!`
+fn deciding_pattern(decision: &Match<&Glob>) -> Option<String> {
+    match decision {
+        Match::Ignore(glob) | Match::Whitelist(glob) => Some(glob.original().to_string()),
+        Match::None => None,
     }
 }
 
+/// Name of the discovery-skipped directory containing a path, if any
+fn skipped_directory(path: &Path) -> Option<String> {
+    path.components().find_map(|component| match component {
+        Component::Normal(name)
+            if SKIPPED_DIRECTORIES.contains(&name.to_string_lossy().as_ref()) =>
+        {
+            Some(name.to_string_lossy().into_owned())
+        }
+        _ => None,
+    })
+}
+
+/// Identify a file by device and inode where available, else by canonical path
+fn file_identity(path: &Path) -> Option<FileIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if let Ok(metadata) = fs::metadata(path) {
+            return Some(FileIdentity::Inode {
+                dev: metadata.dev(),
+                ino: metadata.ino(),
+            });
+        }
+    }
+
+    fs::canonicalize(path).ok().map(FileIdentity::Canonical)
+}
+
 /// Architecture-compliant validation functions for integration testing
 #[cfg(test)]
 #[allow(dead_code)]
@@ -503,4 +844,346 @@ pub mod validation {
 
         Ok(())
     }
+
+    /// Validate that discovery filtering rejects paths in pruned directories
+    pub fn validate_discovery_filter() -> GuardianResult<()> {
+        let filter = PathFilter::new(vec![], None)?;
+
+        if filter.would_discover(Path::new("/project/target/debug/build.rs"))? {
+            return Err(GuardianError::pattern(
+                "Discovery filter validation failed - should skip target directory",
+            ));
+        }
+
+        if !filter.would_discover(Path::new("/project/src/lib.rs"))? {
+            return Err(GuardianError::pattern(
+                "Discovery filter validation failed - should include source files",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate symlink following, cycle detection, and deduplication
+    #[cfg(unix)]
+    pub fn validate_link_handling() -> GuardianResult<()> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()
+            .map_err(|e| GuardianError::config(format!("Failed to create temp dir: {}", e)))?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("shared/src"))?;
+        fs::create_dir_all(root.join("app"))?;
+        fs::write(root.join("shared/src/lib.rs"), "")?;
+        fs::hard_link(root.join("shared/src/lib.rs"), root.join("app/copy.rs"))?;
+        symlink(root.join("shared/src"), root.join("app/shared"))?;
+        symlink(root, root.join("shared/loop"))?;
+
+        let count =
+            |filter: &PathFilter| -> GuardianResult<usize> { Ok(filter.find_files(root)?.len()) };
+
+        // Without following links, the hardlink duplicates the original
+        if count(&PathFilter::new(vec![], None)?)? != 1 {
+            return Err(GuardianError::pattern(
+                "Link validation failed - hardlinked file should be discovered once",
+            ));
+        }
+
+        if count(&PathFilter::new(vec![], None)?.with_deduplication(false))? != 2 {
+            return Err(GuardianError::pattern(
+                "Link validation failed - hardlink should be kept without deduplication",
+            ));
+        }
+
+        // Following links must terminate despite the cycle and still deduplicate
+        if count(&PathFilter::new(vec![], None)?.with_symlinks(true))? != 1 {
+            return Err(GuardianError::pattern(
+                "Link validation failed - symlinked directory should not duplicate files",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use tempfile::TempDir;
+
+    fn analyzed(patterns: &[&str], path: &str) -> bool {
+        let patterns = patterns.iter().map(|p| p.to_string()).collect();
+        PathFilter::new(patterns, None)
+            .expect("patterns should compile")
+            .should_analyze(Path::new(path))
+            .expect("matching should succeed")
+    }
+
+    #[test]
+    fn test_gitignore_compatibility() {
+        // (patterns, path, analyzed)
+        let cases: &[(&[&str], &str, bool)] = &[
+            // Patterns without a slash match at any depth
+            (&["*.log"], "debug.log", false),
+            (&["*.log"], "logs/debug.log", false),
+            (&["debug"], "src/debug/lib.rs", false),
+            // A leading or middle slash anchors the pattern to the root
+            (&["/build.rs"], "build.rs", false),
+            (&["/build.rs"], "src/build.rs", true),
+            (&["doc/frotz"], "doc/frotz", false),
+            (&["doc/frotz"], "a/doc/frotz", true),
+            // Double-star forms
+            (&["**/foo"], "foo", false),
+            (&["**/foo"], "a/b/foo", false),
+            (&["abc/**"], "abc/x/y.rs", false),
+            (&["abc/**"], "abc.rs", true),
+            (&["a/**/b"], "a/b", false),
+            (&["a/**/b"], "a/x/y/b", false),
+            (&["a/**/b"], "x/a/b", true),
+            // Trailing-slash patterns match directories, including paths not on disk
+            (&["generated/"], "generated/debug/lib.rs", false),
+            (&["generated/"], "src/generated", true),
+            (&["/generated/"], "src/generated/lib.rs", true),
+            // The last matching pattern wins
+            (&["*.rs", "!keep.rs"], "keep.rs", true),
+            (&["*.rs", "!keep.rs"], "src/keep.rs", true),
+            (&["*.rs", "!keep.rs"], "main.rs", false),
+            (&["!keep.rs", "*.rs"], "keep.rs", false),
+            // A file-level decision takes precedence over its directory
+            (
+                &["vendor/", "!vendor/patched.rs"],
+                "vendor/patched.rs",
+                true,
+            ),
+            (&["vendor/", "!vendor/patched.rs"], "vendor/other.rs", false),
+            // Character classes and single-character wildcards
+            (&["?.rs"], "a.rs", false),
+            (&["?.rs"], "ab.rs", true),
+            (&["[ab].rs"], "b.rs", false),
+            (&["[ab].rs"], "c.rs", true),
+            // Escapes, comments, and leading ./ on candidate paths
+            (&["\\!important.rs"], "!important.rs", false),
+            (&["#notes.rs"], "#notes.rs", true),
+            (&["src/"], "./src/lib.rs", false),
+        ];
+
+        for (patterns, path, expected) in cases {
+            assert_eq!(
+                analyzed(patterns, path),
+                *expected,
+                "patterns {patterns:?} on {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_nested_ignore_files_closest_wins() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("sub")).expect("sub dir should be creatable");
+        fs::write(root.join(".guardianignore"), "*.rs\n# comment\n")
+            .expect("root ignore file should be writable");
+        fs::write(root.join("sub/.guardianignore"), "!keep.rs\n")
+            .expect("nested ignore file should be writable");
+
+        let filter = PathFilter::new(vec![], Some(".guardianignore".to_string()))
+            .expect("filter should build");
+
+        assert!(!filter
+            .should_analyze(root.join("lib.rs"))
+            .expect("matching should succeed"));
+        assert!(!filter
+            .should_analyze(root.join("sub/other.rs"))
+            .expect("matching should succeed"));
+        assert!(filter
+            .should_analyze(root.join("sub/keep.rs"))
+            .expect("matching should succeed"));
+        assert!(filter
+            .should_analyze(root.join("README.md"))
+            .expect("matching should succeed"));
+    }
+
+    #[test]
+    fn test_gitignore_files_stop_at_repository_root() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let outer = temp_dir.path();
+        let repo = outer.join("repo");
+        fs::create_dir_all(repo.join(".git")).expect("git dir should be creatable");
+        fs::create_dir_all(repo.join("src")).expect("src dir should be creatable");
+        fs::write(outer.join(".gitignore"), "*.rs\n").expect("outer gitignore should be writable");
+        fs::write(repo.join(".gitignore"), "vendor/\n").expect("repo gitignore should be writable");
+        fs::write(repo.join(".guardianignore"), "!vendor/keep.rs\n")
+            .expect("guardianignore should be writable");
+
+        let filter = PathFilter::new(vec![], Some(".guardianignore".to_string()))
+            .expect("filter should build")
+            .with_gitignore(true);
+
+        assert!(!filter
+            .should_analyze(repo.join("vendor/dep/lib.rs"))
+            .expect("matching should succeed"));
+        assert!(filter
+            .should_analyze(repo.join("vendor/keep.rs"))
+            .expect("matching should succeed"));
+        // The outer .gitignore lies above the repository and is not applied
+        assert!(filter
+            .should_analyze(repo.join("src/lib.rs"))
+            .expect("matching should succeed"));
+
+        let without = PathFilter::new(vec![], None).expect("filter should build");
+        assert!(without
+            .should_analyze(repo.join("vendor/dep/lib.rs"))
+            .expect("matching should succeed"));
+    }
+
+    #[test]
+    fn test_explain_reports_each_step() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+        fs::write(root.join(".guardianignore"), "legacy/\n")
+            .expect("ignore file should be writable");
+
+        let filter = PathFilter::new(
+            vec!["*.md".to_string(), "!docs/*.md".to_string()],
+            Some(".guardianignore".to_string()),
+        )
+        .expect("filter should build");
+
+        let explanation = filter
+            .explain(root.join("legacy/old.rs"))
+            .expect("explanation should succeed");
+        assert_eq!(explanation.patterns.len(), 2);
+        assert!(explanation.patterns.iter().all(|p| p.excludes.is_none()));
+        let found = explanation.ignore_file.expect("ignore file should decide");
+        assert_eq!(found.file, root.join(".guardianignore"));
+        assert_eq!(found.pattern, "legacy/");
+        assert!(found.ignored);
+        assert!(!explanation.analyzed);
+
+        let explanation = filter
+            .explain(Path::new("target/docs/guide.md"))
+            .expect("explanation should succeed");
+        assert_eq!(explanation.patterns[0].excludes, Some(true));
+        assert_eq!(explanation.patterns[1].excludes, None);
+        assert_eq!(explanation.skipped_directory.as_deref(), Some("target"));
+        assert!(!explanation.analyzed);
+    }
+
+    #[test]
+    fn test_patterns_anchored_to_project_root() {
+        let temp_dir = TempDir::new().expect("temp dir should be creatable");
+        let root = temp_dir.path();
+
+        let filter = PathFilter::new(vec!["/legacy/".to_string()], None)
+            .expect("filter should build")
+            .with_root(root.to_path_buf())
+            .expect("filter should re-anchor");
+
+        assert!(!filter
+            .should_analyze(root.join("legacy/old.rs"))
+            .expect("should_analyze should succeed"));
+        assert!(filter
+            .should_analyze(root.join("crates/app/legacy/old.rs"))
+            .expect("should_analyze should succeed"));
+    }
+
+    #[test]
+    fn test_validation_functions() {
+        validation::validate_basic_pattern_matching().expect("validation should pass");
+        validation::validate_include_override().expect("validation should pass");
+        validation::validate_pattern_order().expect("validation should pass");
+        validation::validate_guardianignore_file().expect("validation should pass");
+        validation::validate_invalid_pattern_handling().expect("validation should pass");
+        validation::validate_default_filter().expect("validation should pass");
+        validation::validate_discovery_filter().expect("validation should pass");
+        #[cfg(unix)]
+        validation::validate_link_handling().expect("validation should pass");
+    }
+
+    #[test]
+    fn test_equivalence_check() {
+        let check = PathFilter::equivalence_check("vendor/\n!vendor/patched.rs\n*.log\n")
+            .expect("content should compile");
+
+        assert_eq!(check.compare("vendor/other.rs"), None);
+        assert_eq!(check.compare("logs/debug.log"), None);
+        assert_eq!(check.compare("src/lib.rs"), None);
+
+        let divergence = check
+            .compare("vendor/patched.rs")
+            .expect("re-included file should diverge");
+        assert!(!divergence.guardian_ignores);
+        assert_eq!(
+            divergence.guardian_pattern.as_deref(),
+            Some("!vendor/patched.rs")
+        );
+        assert_eq!(
+            divergence.excluded_directory.as_deref(),
+            Some(Path::new("vendor"))
+        );
+        assert_eq!(divergence.reference_pattern.as_deref(), Some("vendor/"));
+
+        assert!(PathFilter::equivalence_check("a/**/b/[").is_err());
+    }
+
+    fn pattern_strategy() -> impl Strategy<Value = String> {
+        let segment = prop::sample::select(vec!["a", "b", "src", "*.rs", "*", "**", "x?.rs"]);
+        (
+            any::<bool>(),
+            any::<bool>(),
+            prop::collection::vec(segment, 1..4),
+            any::<bool>(),
+        )
+            .prop_map(|(negated, anchored, segments, directory)| {
+                format!(
+                    "{}{}{}{}",
+                    if negated { "!" } else { "" },
+                    if anchored { "/" } else { "" },
+                    segments.join("/"),
+                    if directory { "/" } else { "" }
+                )
+            })
+    }
+
+    fn path_strategy() -> impl Strategy<Value = PathBuf> {
+        let segment = prop::sample::select(vec!["a", "b", "src", "x1.rs", "lib.rs", "notes.txt"]);
+        prop::collection::vec(segment, 1..5).prop_map(|segments| segments.iter().collect())
+    }
+
+    proptest! {
+        // Guardian only ever analyzes more than a walk: it never excludes a reachable file
+        #[test]
+        fn prop_guardian_excludes_only_what_a_walk_excludes(
+            patterns in prop::collection::vec(pattern_strategy(), 0..6),
+            paths in prop::collection::vec(path_strategy(), 1..20),
+        ) {
+            let check = PathFilter::equivalence_check(&patterns.join("\n"))
+                .expect("generated patterns should compile");
+            for divergence in check.divergences(&paths) {
+                prop_assert!(!divergence.guardian_ignores, "{divergence:?}");
+                prop_assert!(divergence.excluded_directory.is_some(), "{divergence:?}");
+                prop_assert!(
+                    divergence.guardian_pattern.as_deref().is_some_and(|p| p.starts_with('!')),
+                    "{divergence:?}"
+                );
+            }
+        }
+
+        // Without re-inclusion the two semantics are identical
+        #[test]
+        fn prop_exclusions_match_a_walk(
+            patterns in prop::collection::vec(pattern_strategy(), 0..6),
+            paths in prop::collection::vec(path_strategy(), 1..20),
+        ) {
+            let patterns: Vec<String> = patterns
+                .into_iter()
+                .map(|p| p.trim_start_matches('!').to_string())
+                .collect();
+            let check = PathFilter::equivalence_check(&patterns.join("\n"))
+                .expect("generated patterns should compile");
+            prop_assert_eq!(check.divergences(&paths), Vec::new());
+        }
+    }
 }
@@ -0,0 +1,459 @@
+//! Detection of polling loops and busy waits
+//!
+//! Architecture: Domain Services - Waiting strategies are judged from the shape of the loop
+//! - A loop that sleeps for a fixed short interval is polling for a condition a channel could signal
+//! - Sleeps that grow inside the loop, or are computed by a backoff policy, are retries and allowed
+//! - A loop with no body but a condition check spins a CPU core until another thread acts
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Longest fixed sleep, in milliseconds, still treated as a polling interval
+pub const DEFAULT_MAX_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Calls that only hint the CPU or scheduler while spinning
+const SPIN_HINTS: &[&str] = &["spin_loop", "spin_loop_hint", "yield_now"];
+
+/// Which waiting pattern a rule reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollingCheck {
+    /// `loop`/`while` loops sleeping for a fixed interval of at most `max_interval_ms`
+    SleepInLoop { max_interval_ms: u64 },
+    /// Loops doing nothing but re-checking a condition
+    BusyWait,
+}
+
+impl PollingCheck {
+    /// Parse a semantic pattern into a polling check
+    ///
+    /// Accepts `busy_wait`, `sleep_in_loop` and `sleep_in_loop:<ms>`; the value is the longest
+    /// fixed sleep still reported. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<GuardianResult<Self>> {
+        if pattern == "busy_wait" {
+            return Some(Ok(Self::BusyWait));
+        }
+
+        let max_interval_ms = match pattern.strip_prefix("sleep_in_loop")? {
+            "" => DEFAULT_MAX_POLL_INTERVAL_MS,
+            custom => {
+                let value = custom.strip_prefix(':')?;
+                match value.trim().parse() {
+                    Ok(ms) => ms,
+                    Err(_) => {
+                        return Some(Err(GuardianError::pattern(format!(
+                            "Invalid sleep interval '{value}': expected milliseconds"
+                        ))))
+                    }
+                }
+            }
+        };
+        Some(Ok(Self::SleepInLoop { max_interval_ms }))
+    }
+}
+
+/// Find polling loops and busy waits
+///
+/// Returns the reported span, the call or loop as written, and a short description as context.
+pub fn find_polling(syntax_tree: &syn::File, check: PollingCheck) -> Vec<(Span, String, String)> {
+    let mut visitor = PollingVisitor {
+        check,
+        function: None,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct PollingVisitor {
+    check: PollingCheck,
+    function: Option<String>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl PollingVisitor {
+    fn describe(&self, what: &str) -> String {
+        match &self.function {
+            Some(function) => format!("{what} in fn {function}"),
+            None => what.to_string(),
+        }
+    }
+
+    fn check_loop(&mut self, body: &syn::Block, condition: Option<&syn::Expr>, span: Span) {
+        match self.check {
+            PollingCheck::SleepInLoop { max_interval_ms } => {
+                if let Some((span, name)) = polling_sleep(body, max_interval_ms) {
+                    let context = self.describe(&format!("{name}() with a fixed interval polls"));
+                    self.matches.push((span, format!("{name}()"), context));
+                }
+            }
+            PollingCheck::BusyWait => {
+                let spins = match condition {
+                    Some(_) => body.stmts.iter().all(is_spin_hint),
+                    None => breaks_on_condition(body),
+                };
+                if spins {
+                    let keyword = if condition.is_some() { "while" } else { "loop" };
+                    let context = self.describe(&format!("{keyword} spins until a condition"));
+                    self.matches.push((span, keyword.to_string(), context));
+                }
+            }
+        }
+    }
+}
+
+impl Visit<'_> for PollingVisitor {
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_impl_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_expr_loop(&mut self, expr: &syn::ExprLoop) {
+        self.check_loop(&expr.body, None, expr.span());
+        syn::visit::visit_expr_loop(self, expr);
+    }
+
+    fn visit_expr_while(&mut self, expr: &syn::ExprWhile) {
+        // `while let` loops consume something on each pass, so they are not busy waits
+        if !matches!(*expr.cond, syn::Expr::Let(_)) {
+            self.check_loop(&expr.body, Some(&expr.cond), expr.span());
+        }
+        syn::visit::visit_expr_while(self, expr);
+    }
+}
+
+/// The first sleep in a loop body that waits for a short fixed interval
+fn polling_sleep(body: &syn::Block, max_interval_ms: u64) -> Option<(Span, String)> {
+    let mut sleeps = SleepFinder { sleeps: Vec::new() };
+    sleeps.visit_block(body);
+
+    let mut assigned = AssignmentFinder {
+        names: HashSet::new(),
+    };
+    assigned.visit_block(body);
+
+    sleeps.sleeps.into_iter().find_map(|(call, name)| {
+        let duration = call.args.first()?;
+        let polls = match literal_duration_ms(duration) {
+            Some(ms) => ms <= max_interval_ms,
+            None => !is_backoff(duration, &assigned.names),
+        };
+        polls.then(|| (call.span(), name))
+    })
+}
+
+/// Whether a sleep duration changes between iterations
+///
+/// Durations computed by a method call (`backoff.next_delay()`) or from a variable the loop
+/// assigns to (`delay *= 2`) are treated as backoff.
+fn is_backoff(duration: &syn::Expr, assigned: &HashSet<String>) -> bool {
+    struct DurationInputs<'a> {
+        assigned: &'a HashSet<String>,
+        varies: bool,
+    }
+
+    impl Visit<'_> for DurationInputs<'_> {
+        fn visit_expr_method_call(&mut self, call: &syn::ExprMethodCall) {
+            self.varies = true;
+            syn::visit::visit_expr_method_call(self, call);
+        }
+
+        fn visit_expr_path(&mut self, path: &syn::ExprPath) {
+            if let Some(ident) = path.path.get_ident() {
+                self.varies |= self.assigned.contains(&ident.to_string());
+            }
+        }
+    }
+
+    let mut inputs = DurationInputs {
+        assigned,
+        varies: false,
+    };
+    inputs.visit_expr(duration);
+    inputs.varies
+}
+
+/// Milliseconds of a literal `Duration::from_*` constructor
+fn literal_duration_ms(duration: &syn::Expr) -> Option<u64> {
+    let call = match duration {
+        syn::Expr::Call(call) => call,
+        _ => return None,
+    };
+    let constructor = match call.func.as_ref() {
+        syn::Expr::Path(path) => path.path.segments.last()?.ident.to_string(),
+        _ => return None,
+    };
+    let value = match call.args.first()? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(value),
+            ..
+        }) => value.base10_parse::<u64>().ok()?,
+        _ => return None,
+    };
+
+    match constructor.as_str() {
+        "from_secs" => Some(value.saturating_mul(1000)),
+        "from_millis" => Some(value),
+        "from_micros" => Some(value / 1000),
+        "from_nanos" => Some(value / 1_000_000),
+        _ => None,
+    }
+}
+
+/// Collects `sleep(...)` calls made directly by a loop body
+///
+/// Nested loops, closures and items are checked on their own.
+struct SleepFinder<'ast> {
+    sleeps: Vec<(&'ast syn::ExprCall, String)>,
+}
+
+impl<'ast> Visit<'ast> for SleepFinder<'ast> {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(path) = call.func.as_ref() {
+            if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "sleep")
+            {
+                let name = path
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                self.sleeps.push((call, name));
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_loop(&mut self, _: &'ast syn::ExprLoop) {}
+    fn visit_expr_while(&mut self, _: &'ast syn::ExprWhile) {}
+    fn visit_expr_for_loop(&mut self, _: &'ast syn::ExprForLoop) {}
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+    fn visit_item(&mut self, _: &'ast syn::Item) {}
+}
+
+/// Collects the names of variables a loop body assigns to
+struct AssignmentFinder {
+    names: HashSet<String>,
+}
+
+impl Visit<'_> for AssignmentFinder {
+    fn visit_expr_assign(&mut self, assign: &syn::ExprAssign) {
+        if let syn::Expr::Path(path) = assign.left.as_ref() {
+            if let Some(ident) = path.path.get_ident() {
+                self.names.insert(ident.to_string());
+            }
+        }
+        syn::visit::visit_expr_assign(self, assign);
+    }
+
+    fn visit_expr_binary(&mut self, binary: &syn::ExprBinary) {
+        let compound = matches!(
+            binary.op,
+            syn::BinOp::AddAssign(_)
+                | syn::BinOp::SubAssign(_)
+                | syn::BinOp::MulAssign(_)
+                | syn::BinOp::DivAssign(_)
+                | syn::BinOp::ShlAssign(_)
+        );
+        if compound {
+            if let syn::Expr::Path(path) = binary.left.as_ref() {
+                if let Some(ident) = path.path.get_ident() {
+                    self.names.insert(ident.to_string());
+                }
+            }
+        }
+        syn::visit::visit_expr_binary(self, binary);
+    }
+}
+
+/// Whether a statement only hints the CPU or scheduler, like `std::hint::spin_loop();`
+fn is_spin_hint(stmt: &syn::Stmt) -> bool {
+    let call = match stmt {
+        syn::Stmt::Expr(syn::Expr::Call(call), _) => call,
+        _ => return false,
+    };
+    match call.func.as_ref() {
+        syn::Expr::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| SPIN_HINTS.contains(&segment.ident.to_string().as_str())),
+        _ => false,
+    }
+}
+
+/// Whether a `loop` body is a bare `if <condition> { break; }`, optionally with spin hints
+fn breaks_on_condition(body: &syn::Block) -> bool {
+    let mut checks = body.stmts.iter().filter_map(|stmt| match stmt {
+        syn::Stmt::Expr(syn::Expr::If(check), _) => Some(check),
+        _ => None,
+    });
+    let check = match (checks.next(), checks.next()) {
+        (Some(check), None) => check,
+        _ => return false,
+    };
+
+    let breaks = matches!(
+        check.then_branch.stmts.as_slice(),
+        [syn::Stmt::Expr(syn::Expr::Break(_), _)]
+    );
+    let only_hints = body
+        .stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, syn::Stmt::Expr(syn::Expr::If(_), _)))
+        .all(is_spin_hint);
+
+    breaks && check.else_branch.is_none() && only_hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polling(source: &str, check: PollingCheck) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_polling(&syntax_tree, check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            PollingCheck::parse("sleep_in_loop").map(Result::ok),
+            Some(Some(PollingCheck::SleepInLoop {
+                max_interval_ms: DEFAULT_MAX_POLL_INTERVAL_MS
+            }))
+        );
+        assert_eq!(
+            PollingCheck::parse("sleep_in_loop:250").map(Result::ok),
+            Some(Some(PollingCheck::SleepInLoop {
+                max_interval_ms: 250
+            }))
+        );
+        assert_eq!(
+            PollingCheck::parse("busy_wait").map(Result::ok),
+            Some(Some(PollingCheck::BusyWait))
+        );
+        assert!(PollingCheck::parse("sleep_in_loop:soon").is_some_and(|r| r.is_err()));
+        assert!(PollingCheck::parse("sleep_in_loops").is_none());
+        assert!(PollingCheck::parse("sleep").is_none());
+    }
+
+    #[test]
+    fn test_sleep_in_loop() {
+        let source = r#"
+async fn wait_ready(client: &Client) {
+    loop {
+        if client.ready().await {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+fn wait_file(path: &Path, interval: Duration) {
+    while !path.exists() {
+        std::thread::sleep(interval);
+    }
+}
+
+async fn retry(client: &Client) {
+    let mut delay = Duration::from_millis(100);
+    loop {
+        if client.send().await.is_ok() {
+            return;
+        }
+        sleep(delay).await;
+        delay *= 2;
+    }
+}
+
+async fn retry_policy(client: &Client, backoff: &mut Backoff) {
+    loop {
+        if client.send().await.is_ok() {
+            return;
+        }
+        sleep(backoff.next_delay()).await;
+    }
+}
+
+async fn hourly_report() {
+    loop {
+        send_report().await;
+        sleep(Duration::from_secs(3600)).await;
+    }
+}
+
+fn startup() {
+    std::thread::sleep(Duration::from_millis(10));
+}
+"#;
+        let check = PollingCheck::SleepInLoop {
+            max_interval_ms: DEFAULT_MAX_POLL_INTERVAL_MS,
+        };
+        assert_eq!(
+            polling(source, check),
+            vec![
+                "tokio::time::sleep() with a fixed interval polls in fn wait_ready",
+                "std::thread::sleep() with a fixed interval polls in fn wait_file",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_busy_wait() {
+        let source = r#"
+fn wait(flag: &AtomicBool) {
+    while !flag.load(Ordering::Acquire) {}
+}
+
+fn wait_hinted(flag: &AtomicBool) {
+    while !flag.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+}
+
+fn wait_loop(flag: &AtomicBool) {
+    loop {
+        if flag.load(Ordering::Acquire) {
+            break;
+        }
+    }
+}
+
+fn drain(rx: &Receiver<u8>) {
+    while let Ok(_) = rx.try_recv() {}
+}
+
+fn work(queue: &Queue) {
+    while !queue.is_empty() {
+        queue.process();
+    }
+}
+"#;
+        assert_eq!(
+            polling(source, PollingCheck::BusyWait),
+            vec![
+                "while spins until a condition in fn wait",
+                "while spins until a condition in fn wait_hinted",
+                "loop spins until a condition in fn wait_loop",
+            ]
+        );
+    }
+}
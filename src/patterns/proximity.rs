@@ -0,0 +1,157 @@
+//! Proximity requirements: text that must or must not appear near a match
+//!
+//! Architecture: Specification Pattern - A requirement decides which matches of a rule stand
+//! - The window is a number of lines above and below the match, on the file's line index
+//! - Nearby text is filtered by the comment/string classification of the source
+//! - Enables "X requires an adjacent Y" policies, like `unsafe` needing a `SAFETY:` comment
+
+use super::{FileContext, PatternMatch};
+use crate::config::{NearConstraint, NearRegion};
+use crate::domain::violations::{GuardianError, GuardianResult};
+use regex::Regex;
+
+/// A compiled proximity requirement
+#[derive(Debug, Clone)]
+pub struct NearCheck {
+    within_lines: u32,
+    must_have: Option<Regex>,
+    must_not_have: Option<Regex>,
+    region: NearRegion,
+}
+
+impl NearCheck {
+    /// Compile the proximity requirement of a rule
+    pub fn compile(rule_id: &str, near: &NearConstraint) -> GuardianResult<Self> {
+        if near.must_have.is_some() == near.must_not_have.is_some() {
+            return Err(GuardianError::rule_compilation(
+                rule_id,
+                "near needs exactly one of must_have and must_not_have",
+            ));
+        }
+
+        let compile = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .map_err(|e| GuardianError::rule_compilation(rule_id, e))
+        };
+
+        Ok(Self {
+            within_lines: near.within_lines,
+            must_have: compile(&near.must_have)?,
+            must_not_have: compile(&near.must_not_have)?,
+            region: near.region,
+        })
+    }
+
+    /// Whether a match breaks the requirement and should be reported
+    ///
+    /// Matches without a line are kept, since there is nothing to measure from.
+    pub fn is_violated(&self, file: &FileContext, m: &PatternMatch) -> bool {
+        let line = match m.line_number {
+            Some(line) => line,
+            None => return true,
+        };
+        let end_line = m.end_line.unwrap_or(line).max(line);
+
+        let (index, content) = (file.line_index(), file.content());
+        let first = line.saturating_sub(self.within_lines).max(1) as usize;
+        let last = end_line.saturating_add(self.within_lines) as usize;
+        let start = index.offset(content, first, 0);
+        let end = index.offset(content, last + 1, 0);
+        let window = &content[start..end];
+
+        let found = |regex: &Regex| {
+            regex.find_iter(window).any(|found| {
+                let offset = start + found.start();
+                match self.region {
+                    NearRegion::Any => true,
+                    NearRegion::Comment => file.code_map().is_comment(offset),
+                    NearRegion::Code => file.code_map().is_code(offset),
+                }
+            })
+        };
+
+        match (&self.must_have, &self.must_not_have) {
+            (Some(required), _) => !found(required),
+            (None, Some(forbidden)) => found(forbidden),
+            (None, None) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PatternEngine;
+    use std::path::Path;
+
+    fn constraint(must_have: Option<&str>, must_not_have: Option<&str>) -> NearConstraint {
+        NearConstraint {
+            within_lines: 2,
+            must_have: must_have.map(str::to_string),
+            must_not_have: must_not_have.map(str::to_string),
+            region: NearRegion::Comment,
+        }
+    }
+
+    const SOURCE: &str = r#"fn read(ptr: *const u8) -> u8 {
+    // SAFETY: callers pass a valid pointer
+    unsafe { *ptr }
+}
+
+fn first(bytes: &[u8]) -> u8 {
+    let note = "SAFETY: not a comment";
+    unsafe { *bytes.as_ptr() }
+}
+"#;
+
+    fn unsafe_lines(check: &NearCheck) -> Vec<u32> {
+        let mut engine = PatternEngine::new();
+        let mut rule = crate::config::GuardianConfig::default()
+            .patterns
+            .values()
+            .flat_map(|category| category.rules.iter())
+            .find(|rule| rule.id == "todo_comments")
+            .expect("default config should define todo_comments")
+            .clone();
+        rule.pattern = r"unsafe\s*\{".to_string();
+        engine
+            .add_rule(&rule, crate::domain::violations::Severity::Error)
+            .expect("rule should compile");
+
+        let file = FileContext::new(Path::new("src/lib.rs"), SOURCE);
+        let mut lines: Vec<u32> = engine
+            .analyze_context(&file)
+            .expect("analysis should succeed")
+            .iter()
+            .filter(|m| check.is_violated(&file, m))
+            .filter_map(|m| m.line_number)
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn test_must_have_in_comment() {
+        let check = NearCheck::compile("unsafe_needs_safety", &constraint(Some("SAFETY:"), None))
+            .expect("constraint should compile");
+        // The string literal does not count as a SAFETY comment
+        assert_eq!(unsafe_lines(&check), vec![8]);
+    }
+
+    #[test]
+    fn test_must_not_have() {
+        let check = NearCheck::compile("no_safety", &constraint(None, Some("SAFETY:")))
+            .expect("constraint should compile");
+        assert_eq!(unsafe_lines(&check), vec![3]);
+    }
+
+    #[test]
+    fn test_invalid_constraints() {
+        assert!(NearCheck::compile("both", &constraint(Some("a"), Some("b"))).is_err());
+        assert!(NearCheck::compile("neither", &constraint(None, None)).is_err());
+        assert!(NearCheck::compile("regex", &constraint(Some("("), None)).is_err());
+    }
+}
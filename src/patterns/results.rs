@@ -0,0 +1,205 @@
+//! Detection of discarded `Result` values
+//!
+//! Architecture: Domain Services - Ignored errors are found from call sites and known signatures
+//! - `let _ = fallible()` and a bare `fallible();` statement both throw an error away unseen
+//! - A callee is fallible when a function of that name in the same file returns a `Result`
+//! - Callees defined elsewhere are recognized through a configured list of names
+
+use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Configuration of the ignored result check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoredResultCheck {
+    /// Function and method names known to return a `Result`
+    pub fallible: Vec<String>,
+}
+
+impl IgnoredResultCheck {
+    /// Parse `ignored_result` or `ignored_result:<name>|<name>`
+    ///
+    /// Names are function or method names such as `send` or `remove_file`; a trailing `()`
+    /// is accepted. Returns `None` when the pattern is not an ignored result pattern.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let names = match pattern.strip_prefix("ignored_result")? {
+            "" => "",
+            rest => rest.strip_prefix(':')?,
+        };
+
+        Some(Self {
+            fallible: names
+                .split('|')
+                .map(str::trim)
+                .map(|name| name.strip_suffix("()").unwrap_or(name))
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
+
+/// Find calls to fallible functions whose `Result` is discarded
+///
+/// Returns the call span, the callee name, and how the result is discarded as context.
+pub fn find_ignored_results(
+    syntax_tree: &syn::File,
+    check: &IgnoredResultCheck,
+) -> Vec<(Span, String, String)> {
+    let mut signatures = FallibleSignatures::default();
+    signatures.visit_file(syntax_tree);
+    let mut fallible = signatures.names;
+    fallible.extend(check.fallible.iter().cloned());
+
+    let mut visitor = IgnoredResultVisitor {
+        fallible,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+/// Names of functions and methods in a file that return a `Result`
+#[derive(Default)]
+struct FallibleSignatures {
+    names: HashSet<String>,
+}
+
+impl FallibleSignatures {
+    fn record(&mut self, sig: &syn::Signature) {
+        if let syn::ReturnType::Type(_, ty) = &sig.output {
+            if returns_result(ty) {
+                self.names.insert(sig.ident.to_string());
+            }
+        }
+    }
+}
+
+impl Visit<'_> for FallibleSignatures {
+    fn visit_signature(&mut self, sig: &syn::Signature) {
+        self.record(sig);
+    }
+}
+
+/// Whether a return type is `Result` or an alias named like one (`io::Result`, `GuardianResult`)
+fn returns_result(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident.to_string().ends_with("Result")),
+        syn::Type::Paren(paren) => returns_result(&paren.elem),
+        _ => false,
+    }
+}
+
+struct IgnoredResultVisitor {
+    fallible: HashSet<String>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl IgnoredResultVisitor {
+    fn report(&mut self, expr: &syn::Expr, how: &str) {
+        if let Some(callee) = callee_name(expr) {
+            if self.fallible.contains(&callee) {
+                let context = format!("Result of {callee}() {how}");
+                self.matches.push((expr.span(), callee, context));
+            }
+        }
+    }
+}
+
+impl Visit<'_> for IgnoredResultVisitor {
+    fn visit_local(&mut self, local: &syn::Local) {
+        if let (syn::Pat::Wild(_), Some(init)) = (&local.pat, &local.init) {
+            self.report(&init.expr, "bound to `let _`");
+        }
+        syn::visit::visit_local(self, local);
+    }
+
+    fn visit_stmt(&mut self, stmt: &syn::Stmt) {
+        if let syn::Stmt::Expr(expr, Some(_)) = stmt {
+            self.report(expr, "discarded by the statement");
+        }
+        syn::visit::visit_stmt(self, stmt);
+    }
+}
+
+/// Name of the function or method an expression calls, looking through `.await`
+fn callee_name(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Call(call) => match call.func.as_ref() {
+            syn::Expr::Path(path) => Some(path.path.segments.last()?.ident.to_string()),
+            _ => None,
+        },
+        syn::Expr::MethodCall(call) => Some(call.method.to_string()),
+        syn::Expr::Await(expr) => callee_name(&expr.base),
+        syn::Expr::Paren(expr) => callee_name(&expr.expr),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(source: &str, pattern: &str) -> Vec<String> {
+        let check = IgnoredResultCheck::parse(pattern).expect("pattern should parse");
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_ignored_results(&syntax_tree, &check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+fn save(path: &Path) -> io::Result<()> { todo!() }
+fn count() -> usize { 0 }
+
+impl Client {
+    async fn connect(&self) -> Result<(), Error> { todo!() }
+}
+
+fn run(client: &Client, tx: Sender<u32>) -> Result<(), Error> {
+    let _ = save(path);
+    save(path);
+    save(path)?;
+    let _saved = save(path);
+    let _ = count();
+    client.connect().await;
+    let _ = tx.send(1);
+    fs::remove_file(path);
+    match save(path) { _ => {} }
+    Ok(())
+}
+"#;
+
+    #[test]
+    fn test_local_fallible_functions() {
+        assert_eq!(
+            ignored(SOURCE, "ignored_result"),
+            vec![
+                "Result of save() bound to `let _`",
+                "Result of save() discarded by the statement",
+                "Result of connect() discarded by the statement",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configured_names() {
+        assert_eq!(
+            ignored(SOURCE, "ignored_result:send|remove_file()"),
+            vec![
+                "Result of save() bound to `let _`",
+                "Result of save() discarded by the statement",
+                "Result of connect() discarded by the statement",
+                "Result of send() bound to `let _`",
+                "Result of remove_file() discarded by the statement",
+            ]
+        );
+        assert!(IgnoredResultCheck::parse("ignored_results").is_none());
+    }
+}
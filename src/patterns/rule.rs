@@ -0,0 +1,102 @@
+//! Procedural rules compiled into downstream crates
+//!
+//! Architecture: Extension Point - Typed rules run beside configured patterns
+//! - A [`Rule`] describes itself with [`RuleInfo`] and checks one [`FileContext`] at a time
+//! - Rules are registered on the engine and run for every analyzed file in their scope
+//! - Category, tags and documentation links from the rule's metadata are copied onto its
+//!   violations unless the rule set them itself
+
+use crate::domain::violations::{Severity, Violation};
+use crate::patterns::context::FileContext;
+use std::fmt;
+use std::path::Path;
+
+/// Descriptive metadata of a procedural rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleInfo {
+    /// Unique identifier, shared with configured rules
+    pub id: String,
+    /// What the rule detects, shown by `rules` and `explain`
+    pub description: String,
+    /// Default severity of the rule's violations
+    pub severity: Severity,
+    /// Category reported on violations
+    pub category: Option<String>,
+    /// Free-form tags for grouping and filtering
+    pub tags: Vec<String>,
+    /// Link to documentation explaining the rule
+    pub docs_url: Option<String>,
+}
+
+impl RuleInfo {
+    /// Create rule metadata with the required fields
+    pub fn new(id: impl Into<String>, description: impl Into<String>, severity: Severity) -> Self {
+        Self {
+            id: id.into(),
+            description: description.into(),
+            severity,
+            category: None,
+            tags: Vec::new(),
+            docs_url: None,
+        }
+    }
+
+    /// Set the category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Set the tags
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Set the documentation link
+    pub fn with_docs_url(mut self, docs_url: impl Into<String>) -> Self {
+        self.docs_url = Some(docs_url.into());
+        self
+    }
+}
+
+/// A typed rule compiled into a downstream crate
+pub trait Rule: Send + Sync {
+    /// Metadata describing the rule; the id must not clash with another registered rule
+    fn info(&self) -> RuleInfo;
+
+    /// Check one file, returning its violations
+    fn check(&self, file: &FileContext) -> Vec<Violation>;
+
+    /// Whether the rule applies to a file; defaults to Rust source files
+    fn applies_to(&self, path: &Path) -> bool {
+        path.extension().and_then(|ext| ext.to_str()) == Some("rs")
+    }
+}
+
+impl fmt::Debug for dyn Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rule").field("id", &self.info().id).finish()
+    }
+}
+
+/// Fill in metadata the rule left unset on its violations
+pub(crate) fn apply_info(info: &RuleInfo, mut violation: Violation) -> Violation {
+    if violation.category.is_none() {
+        if let Some(category) = &info.category {
+            violation = violation.with_category(category.clone());
+        }
+    }
+    if violation.tags.is_empty() {
+        violation = violation.with_tags(info.tags.clone());
+    }
+    if violation.docs_url.is_none() {
+        if let Some(docs_url) = &info.docs_url {
+            violation = violation.with_docs_url(docs_url.clone());
+        }
+    }
+    if violation.rationale.is_none() && !info.description.is_empty() {
+        violation = violation.with_rationale(info.description.clone());
+    }
+    violation
+}
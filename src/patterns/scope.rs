@@ -0,0 +1,168 @@
+//! Scope information shared by syntax visitors
+//!
+//! Architecture: Domain Services - Structural checks agree on where an item lives
+//! - `#[test]`, runtime test attributes such as `#[tokio::test]`, and `#[cfg(test)]` mark test code
+//! - Module paths start from the file's own module, so allow-lists match inline and file modules alike
+//! - Visitors embed a `Scope` and route module and function items through the helpers below
+
+use std::path::Path;
+use syn::visit::Visit;
+
+/// Module path and test context of the item a visitor is in
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Scope {
+    /// Module path of the item being visited, relative to the crate root
+    pub(crate) module: Vec<String>,
+    /// Whether the item is inside `#[cfg(test)]` or `#[test]` code
+    pub(crate) in_test: bool,
+}
+
+impl Scope {
+    /// Scope at the top of a source file
+    pub(crate) fn for_file(file_path: &Path) -> Self {
+        Self {
+            module: crate::graph::module_segments_for(file_path),
+            in_test: false,
+        }
+    }
+}
+
+/// A syntax visitor that keeps track of its scope
+pub(crate) trait Scoped {
+    fn scope_mut(&mut self) -> &mut Scope;
+}
+
+/// Visit a module item inside its own module path and test context
+pub(crate) fn visit_item_mod<'ast, V>(visitor: &mut V, item: &'ast syn::ItemMod)
+where
+    V: Visit<'ast> + Scoped,
+{
+    let scope = visitor.scope_mut();
+    let outer = scope.in_test;
+    scope.in_test |= is_test_code(&item.attrs);
+    scope.module.push(item.ident.to_string());
+    syn::visit::visit_item_mod(visitor, item);
+    let scope = visitor.scope_mut();
+    scope.module.pop();
+    scope.in_test = outer;
+}
+
+/// Visit a function item inside its own test context
+pub(crate) fn visit_item_fn<'ast, V>(visitor: &mut V, item: &'ast syn::ItemFn)
+where
+    V: Visit<'ast> + Scoped,
+{
+    let outer = visitor.scope_mut().in_test;
+    visitor.scope_mut().in_test |= is_test_code(&item.attrs);
+    syn::visit::visit_item_fn(visitor, item);
+    visitor.scope_mut().in_test = outer;
+}
+
+/// Whether attributes mark a test function: `#[test]` or a `*::test` such as `#[tokio::test]`
+pub(crate) fn is_test_fn(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "test")
+    })
+}
+
+/// Whether attributes mark an item as test-only: `#[test]`, `#[cfg(test)]`, or a `*::test`
+pub(crate) fn is_test_code(attrs: &[syn::Attribute]) -> bool {
+    is_test_fn(attrs)
+        || attrs.iter().any(|attr| {
+            attr.path().is_ident("cfg")
+                && attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "test")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(source: &str) -> Vec<syn::Attribute> {
+        syn::parse_str::<syn::ItemFn>(source)
+            .expect("source should parse")
+            .attrs
+    }
+
+    #[test]
+    fn test_test_attributes() {
+        assert!(is_test_code(&attrs("#[test] fn check() {}")));
+        assert!(is_test_code(&attrs("#[tokio::test] async fn check() {}")));
+        assert!(is_test_code(&attrs("#[cfg(test)] fn helper() {}")));
+        assert!(!is_test_fn(&attrs("#[cfg(test)] fn helper() {}")));
+        assert!(!is_test_code(&attrs("#[cfg(unix)] fn helper() {}")));
+        assert!(!is_test_code(&attrs("#[inline] fn helper() {}")));
+    }
+
+    #[derive(Default)]
+    struct UseScopes {
+        scope: Scope,
+        uses: Vec<(String, bool)>,
+    }
+
+    impl Scoped for UseScopes {
+        fn scope_mut(&mut self) -> &mut Scope {
+            &mut self.scope
+        }
+    }
+
+    impl Visit<'_> for UseScopes {
+        fn visit_item_mod(&mut self, item: &syn::ItemMod) {
+            visit_item_mod(self, item);
+        }
+
+        fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+            visit_item_fn(self, item);
+        }
+
+        fn visit_item_use(&mut self, _item: &syn::ItemUse) {
+            self.uses
+                .push((self.scope.module.join("::"), self.scope.in_test));
+        }
+    }
+
+    #[test]
+    fn test_scope_tracking() {
+        let source = r#"
+use a::A;
+mod jobs {
+    use b::B;
+    #[cfg(test)]
+    mod tests {
+        use c::C;
+    }
+    #[test]
+    fn check() {
+        use d::D;
+    }
+}
+use e::E;
+"#;
+        let mut visitor = UseScopes {
+            scope: Scope::for_file(Path::new("src/worker.rs")),
+            ..UseScopes::default()
+        };
+        visitor.visit_file(&syn::parse_file(source).expect("source should parse"));
+
+        let uses: Vec<(&str, bool)> = visitor
+            .uses
+            .iter()
+            .map(|(module, in_test)| (module.as_str(), *in_test))
+            .collect();
+        assert_eq!(
+            uses,
+            [
+                ("worker", false),
+                ("worker::jobs", false),
+                ("worker::jobs::tests", true),
+                ("worker::jobs", true),
+                ("worker", false),
+            ]
+        );
+    }
+}
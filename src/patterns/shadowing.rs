@@ -0,0 +1,257 @@
+//! Detection of shadowing abuse
+//!
+//! Architecture: Domain Services - Rebindings are tracked per function from `let` statements and parameters
+//! - One `let x = x.trim()` is idiomatic; rebinding the same name again and again hides the data flow
+//! - Rebinding a name to a different type is reported when both types can be read from the source
+//! - Types come from annotations, literals, constructors and a few well-known macros and methods
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::visit::Visit;
+
+/// Which shadowing smell a rule reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowingCheck {
+    /// Names shadowed more than the given number of times within one function
+    RebindsGt(u32),
+    /// Names rebound to a value of a different apparent type
+    TypeChange,
+}
+
+/// Find shadowed bindings breaking the check
+///
+/// Returns the rebinding identifier span, the name, and a short description as context.
+pub fn find_shadowing(
+    syntax_tree: &syn::File,
+    check: ShadowingCheck,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = ShadowingVisitor {
+        check,
+        function: None,
+        bindings: HashMap::new(),
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+/// What is known about the current binding of a name
+struct Binding {
+    /// Number of times the name has been shadowed so far
+    rebinds: u32,
+    /// Apparent type of the current binding, when it can be read from the source
+    kind: Option<String>,
+}
+
+struct ShadowingVisitor {
+    check: ShadowingCheck,
+    function: Option<String>,
+    bindings: HashMap<String, Binding>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl ShadowingVisitor {
+    fn enter_function(&mut self, sig: &syn::Signature, body: impl FnOnce(&mut Self)) {
+        let outer_function = self.function.replace(sig.ident.to_string());
+        let outer_bindings = std::mem::take(&mut self.bindings);
+
+        for input in &sig.inputs {
+            if let syn::FnArg::Typed(arg) = input {
+                self.bind(&arg.pat, Some(type_name(&arg.ty)));
+            }
+        }
+        body(self);
+
+        self.function = outer_function;
+        self.bindings = outer_bindings;
+    }
+
+    /// Record the names a pattern binds, each with the apparent type when the pattern is one name
+    fn bind(&mut self, pat: &syn::Pat, kind: Option<String>) {
+        match pat {
+            syn::Pat::Ident(ident) => self.bind_name(&ident.ident, kind),
+            syn::Pat::Type(typed) => self.bind(&typed.pat, Some(type_name(&typed.ty))),
+            syn::Pat::Reference(reference) => self.bind(&reference.pat, None),
+            syn::Pat::Tuple(tuple) => {
+                for pat in &tuple.elems {
+                    self.bind(pat, None);
+                }
+            }
+            syn::Pat::TupleStruct(tuple) => {
+                for pat in &tuple.elems {
+                    self.bind(pat, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn bind_name(&mut self, ident: &syn::Ident, kind: Option<String>) {
+        let name = ident.to_string();
+        if name.starts_with('_') {
+            return;
+        }
+        let Some(binding) = self.bindings.get_mut(&name) else {
+            self.bindings.insert(name, Binding { rebinds: 0, kind });
+            return;
+        };
+
+        binding.rebinds += 1;
+        let reported = match self.check {
+            ShadowingCheck::RebindsGt(max) => {
+                (binding.rebinds == max + 1).then(|| format!("`{name}` shadowed {} times", max + 1))
+            }
+            ShadowingCheck::TypeChange => match (&binding.kind, &kind) {
+                (Some(from), Some(to)) if from != to => {
+                    Some(format!("`{name}` rebound from {from} to {to}"))
+                }
+                _ => None,
+            },
+        };
+        binding.kind = kind;
+
+        if let Some(description) = reported {
+            let context = match &self.function {
+                Some(function) => format!("{description} in fn {function}"),
+                None => description,
+            };
+            self.matches.push((ident.span(), name, context));
+        }
+    }
+}
+
+impl Visit<'_> for ShadowingVisitor {
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        self.enter_function(&item.sig, |visitor| visitor.visit_block(&item.block));
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        self.enter_function(&item.sig, |visitor| visitor.visit_block(&item.block));
+    }
+
+    fn visit_local(&mut self, local: &syn::Local) {
+        // The initializer is evaluated before the new binding exists
+        if let Some(init) = &local.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+        let kind = local.init.as_ref().and_then(|init| value_kind(&init.expr));
+        self.bind(&local.pat, kind);
+    }
+}
+
+/// A type as written, without the spacing of its token stream
+fn type_name(ty: &syn::Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Apparent type of a value, when the expression makes it obvious
+fn value_kind(expr: &syn::Expr) -> Option<String> {
+    let kind = match expr {
+        syn::Expr::Lit(lit) => match &lit.lit {
+            syn::Lit::Str(_) => "&str".to_string(),
+            syn::Lit::ByteStr(_) => "&[u8]".to_string(),
+            syn::Lit::Char(_) => "char".to_string(),
+            syn::Lit::Bool(_) => "bool".to_string(),
+            // Unsuffixed numbers take whatever type inference picks
+            syn::Lit::Int(int) if !int.suffix().is_empty() => int.suffix().to_string(),
+            syn::Lit::Float(float) if !float.suffix().is_empty() => float.suffix().to_string(),
+            _ => return None,
+        },
+        syn::Expr::Struct(expr) => expr.path.segments.last()?.ident.to_string(),
+        syn::Expr::Call(call) => {
+            let syn::Expr::Path(callee) = call.func.as_ref() else {
+                return None;
+            };
+            let segments: Vec<String> = callee
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match segments.as_slice() {
+                [variant] if variant == "Some" => "Option".to_string(),
+                [variant] if variant == "Ok" || variant == "Err" => "Result".to_string(),
+                // A tuple struct constructor, or an associated function such as `Type::new`
+                [.., ty] if is_type_name(ty) => ty.clone(),
+                [.., ty, _] if is_type_name(ty) => ty.clone(),
+                _ => return None,
+            }
+        }
+        syn::Expr::Macro(expr) => match expr.mac.path.segments.last()?.ident.to_string().as_str() {
+            "vec" => "Vec".to_string(),
+            "format" => "String".to_string(),
+            _ => return None,
+        },
+        syn::Expr::MethodCall(call) if call.method == "to_string" => "String".to_string(),
+        syn::Expr::Paren(expr) => return value_kind(&expr.expr),
+        _ => return None,
+    };
+    Some(kind)
+}
+
+fn is_type_name(segment: &str) -> bool {
+    segment.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shadowing(source: &str, check: ShadowingCheck) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_shadowing(&syntax_tree, check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+fn load(path: &Path) -> Config {
+    let path = path.to_path_buf();
+    let text = "default";
+    let text = read(&path);
+    let text = text.trim();
+    let text = text.to_string();
+    let text: Vec<&str> = text.lines().collect();
+    let port = "8080";
+    let port: u16 = port.parse().unwrap();
+    let count = 1;
+    let count = count + 1u32;
+    let (text, rest) = split(text);
+    let _ = rest;
+    let _ = rest;
+    Config { path, text, port }
+}
+
+impl Loader {
+    fn reload(&self, text: String) {
+        let text = Text::new(text);
+    }
+}
+"#;
+
+    #[test]
+    fn test_repeated_shadowing() {
+        assert_eq!(
+            shadowing(SOURCE, ShadowingCheck::RebindsGt(3)),
+            vec!["`text` shadowed 4 times in fn load"]
+        );
+        assert!(shadowing(SOURCE, ShadowingCheck::RebindsGt(5)).is_empty());
+    }
+
+    #[test]
+    fn test_type_changes() {
+        assert_eq!(
+            shadowing(SOURCE, ShadowingCheck::TypeChange),
+            vec![
+                "`text` rebound from String to Vec<&str> in fn load",
+                "`port` rebound from &str to u16 in fn load",
+                "`text` rebound from String to Text in fn reload",
+            ]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Detection of stubbed methods in trait implementations
+//!
+//! Architecture: Domain Services - Half-finished adapters are recognized by the shape of their bodies
+//! - A method whose whole body is `unimplemented!()`, `todo!()` or `Default::default()` is a stub
+//! - So is one that only returns an `Err` naming an unsupported or unimplemented operation
+//! - Only impls of configured traits are checked, since many traits have legitimate no-op methods
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::visit::Visit;
+
+/// Macros that stand in for a missing method body
+const STUB_MACROS: &[&str] = &["unimplemented", "todo"];
+
+/// Error names that report an operation as not implemented
+const UNSUPPORTED_ERRORS: &[&str] = &[
+    "Unsupported",
+    "NotSupported",
+    "NotImplemented",
+    "Unimplemented",
+];
+
+/// Configuration of the stubbed trait implementation check
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubImplCheck {
+    /// Trait name patterns, matched against the last segment of the trait path
+    pub traits: Vec<glob::Pattern>,
+}
+
+impl StubImplCheck {
+    /// Parse `stub_trait_impl:<Trait>|<Trait>`
+    ///
+    /// Trait names may use `*`. At least one is required. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<GuardianResult<Self>> {
+        let traits = match pattern.strip_prefix("stub_trait_impl")? {
+            "" => "",
+            rest => rest.strip_prefix(':')?,
+        };
+
+        let traits = traits
+            .split('|')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                glob::Pattern::new(name).map_err(|e| {
+                    GuardianError::pattern(format!("Invalid trait pattern '{name}': {e}"))
+                })
+            })
+            .collect::<GuardianResult<Vec<_>>>();
+        Some(traits.and_then(|traits| {
+            if traits.is_empty() {
+                return Err(GuardianError::pattern(
+                    "stub_trait_impl needs at least one trait, as in 'stub_trait_impl:Storage|Codec'",
+                ));
+            }
+            Ok(Self { traits })
+        }))
+    }
+
+    fn covers(&self, trait_name: &str) -> bool {
+        self.traits
+            .iter()
+            .any(|pattern| pattern.matches(trait_name))
+    }
+}
+
+/// Find stubbed methods in implementations of the configured traits
+///
+/// Returns the method name span, `Trait::method`, and the stub and implementing type as context.
+pub fn find_stub_impls(
+    syntax_tree: &syn::File,
+    check: &StubImplCheck,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = StubImplVisitor {
+        check,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct StubImplVisitor<'a> {
+    check: &'a StubImplCheck,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl Visit<'_> for StubImplVisitor<'_> {
+    fn visit_item_impl(&mut self, item: &syn::ItemImpl) {
+        let trait_name = item
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last())
+            .map(|segment| segment.ident.to_string());
+        if let Some(trait_name) = trait_name.filter(|name| self.check.covers(name)) {
+            let self_ty = item.self_ty.to_token_stream().to_string().replace(' ', "");
+            for impl_item in &item.items {
+                let syn::ImplItem::Fn(method) = impl_item else {
+                    continue;
+                };
+                if let Some(stub) = stub_body(&method.block) {
+                    let name = format!("{trait_name}::{}", method.sig.ident);
+                    let context = format!("{name} for {self_ty} is {stub}");
+                    self.matches.push((method.sig.ident.span(), name, context));
+                }
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+}
+
+/// Describe a method body that only stands in for an implementation
+fn stub_body(block: &syn::Block) -> Option<String> {
+    let [statement] = block.stmts.as_slice() else {
+        return None;
+    };
+    let expr = match statement {
+        syn::Stmt::Expr(expr, _) => expr,
+        syn::Stmt::Macro(stmt) => return stub_macro(&stmt.mac),
+        _ => return None,
+    };
+
+    match expr {
+        syn::Expr::Macro(expr) => stub_macro(&expr.mac),
+        syn::Expr::Return(expr) => {
+            let returned = expr.expr.as_deref()?;
+            stub_value(returned)
+        }
+        expr => stub_value(expr),
+    }
+}
+
+fn stub_macro(mac: &syn::Macro) -> Option<String> {
+    let name = mac.path.segments.last()?.ident.to_string();
+    STUB_MACROS
+        .contains(&name.as_str())
+        .then(|| format!("{name}!()"))
+}
+
+/// `Default::default()`, or an `Err` whose value names an unsupported operation
+fn stub_value(expr: &syn::Expr) -> Option<String> {
+    let syn::Expr::Call(call) = expr else {
+        return None;
+    };
+    let syn::Expr::Path(callee) = call.func.as_ref() else {
+        return None;
+    };
+    let segments: Vec<String> = callee
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+
+    match segments.as_slice() {
+        [.., default, function] if default == "Default" && function == "default" => {
+            Some("Default::default()".to_string())
+        }
+        [err] if err == "Err" => {
+            let value = call.args.to_token_stream().to_string();
+            let unsupported = value
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| UNSUPPORTED_ERRORS.contains(&word));
+            unsupported.then(|| format!("Err({})", value.replace(' ', "")))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stubs(source: &str, pattern: &str) -> Vec<String> {
+        let check = StubImplCheck::parse(pattern)
+            .expect("pattern should be recognized")
+            .expect("pattern should parse");
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_stub_impls(&syntax_tree, &check)
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+impl Storage for S3Store {
+    fn read(&self, key: &str) -> Result<Vec<u8>, Error> {
+        self.client.get(key)
+    }
+    fn write(&self, key: &str, data: &[u8]) -> Result<(), Error> {
+        unimplemented!("writes")
+    }
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        Err(Error::Unsupported("delete"))
+    }
+    fn list(&self) -> Result<Vec<String>, Error> {
+        return Err(io::ErrorKind::Unsupported.into());
+    }
+    fn stats(&self) -> Stats {
+        Default::default()
+    }
+    fn close(&self) -> Result<(), Error> {
+        Err(Error::Closed)
+    }
+}
+
+impl storage::Codec<Json> for JsonCodec {
+    fn encode(&self) -> Vec<u8> { todo!() }
+}
+
+impl Drop for S3Store {
+    fn drop(&mut self) { unimplemented!() }
+}
+"#;
+
+    #[test]
+    fn test_stubbed_methods() {
+        assert_eq!(
+            stubs(SOURCE, "stub_trait_impl:Storage|Codec"),
+            vec![
+                "Storage::write for S3Store is unimplemented!()",
+                "Storage::delete for S3Store is Err(Error::Unsupported(\"delete\"))",
+                "Storage::list for S3Store is Err(io::ErrorKind::Unsupported.into())",
+                "Storage::stats for S3Store is Default::default()",
+                "Codec::encode for JsonCodec is todo!()",
+            ]
+        );
+        assert_eq!(stubs(SOURCE, "stub_trait_impl:*Store*|Dr?p").len(), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(StubImplCheck::parse("stub_trait_impl")
+            .expect("pattern should be recognized")
+            .is_err());
+        assert!(StubImplCheck::parse("stub_trait_impl:[")
+            .expect("pattern should be recognized")
+            .is_err());
+        assert!(StubImplCheck::parse("stub_trait_impls:Storage").is_none());
+    }
+}
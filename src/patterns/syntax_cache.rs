@@ -0,0 +1,227 @@
+//! Syntax trees reused across analysis runs
+//!
+//! Architecture: Infrastructure - Long-running modes re-analyze files whose content did not change
+//! - Trees are keyed by a SHA-256 hash of the content, so reverts and renamed files still hit
+//! - Each cache owns its trees, so caches never evict each other's; a dropped cache's trees are
+//!   freed on each thread's next lookup
+//! - Span locations only resolve on the thread that parsed them, so a tree is only reused on
+//!   that thread, while the capacity bounds the trees held by all threads together
+//! - One-shot runs do not use it; watch and daemon modes opt in through the analyzer
+
+use super::context::parse_syntax_tree;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "cache")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "cache")]
+use std::cell::RefCell;
+#[cfg(feature = "cache")]
+use std::collections::HashMap;
+#[cfg(feature = "cache")]
+use std::sync::atomic::AtomicU64;
+#[cfg(feature = "cache")]
+use std::sync::Weak;
+
+/// Source of unique cache ids, which key the per-thread storage
+#[cfg(feature = "cache")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Trees one thread holds for one cache
+#[cfg(feature = "cache")]
+struct LocalTrees {
+    /// The owning cache, gone once every handle to it is dropped
+    store: Weak<TreeStore>,
+    /// Trees by content hash; `None` records content that does not parse
+    trees: lru::LruCache<[u8; 32], Option<Rc<syn::File>>>,
+}
+
+#[cfg(feature = "cache")]
+impl Drop for LocalTrees {
+    /// Give the slots back when the thread exits, unless the cache is gone already
+    fn drop(&mut self) {
+        if let Some(store) = self.store.upgrade() {
+            store.len.fetch_sub(self.trees.len(), Ordering::AcqRel);
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+thread_local! {
+    /// Trees parsed on this thread, by the id of the cache holding them
+    static TREES: RefCell<HashMap<u64, LocalTrees>> = RefCell::new(HashMap::new());
+}
+
+/// State shared by every handle to one cache
+#[derive(Debug)]
+struct TreeStore {
+    #[cfg(feature = "cache")]
+    id: u64,
+    capacity: usize,
+    /// Trees held across all threads
+    len: AtomicUsize,
+}
+
+#[cfg(feature = "cache")]
+impl TreeStore {
+    /// Claim room for one more tree, if the capacity allows
+    fn reserve(&self) -> bool {
+        self.len
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |len| {
+                (len < self.capacity).then_some(len + 1)
+            })
+            .is_ok()
+    }
+}
+
+/// Bounded cache of parsed syntax trees, owned by a validator and shared by its analyzers
+///
+/// Clones share the same trees. Without the `cache` feature files are parsed anew.
+#[derive(Debug, Clone)]
+pub struct SyntaxTreeCache {
+    store: Arc<TreeStore>,
+}
+
+impl SyntaxTreeCache {
+    /// Trees kept by default
+    pub const DEFAULT_CAPACITY: usize = 512;
+
+    /// Cache keeping at most `capacity` trees
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            store: Arc::new(TreeStore {
+                #[cfg(feature = "cache")]
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                capacity: capacity.max(1),
+                len: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Most trees kept at once
+    pub fn capacity(&self) -> usize {
+        self.store.capacity
+    }
+
+    /// Trees currently kept, over all threads
+    pub fn len(&self) -> usize {
+        self.store.len.load(Ordering::Acquire)
+    }
+
+    /// Whether no trees are kept
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Syntax tree of Rust source, reused when this thread parsed the same content before
+    ///
+    /// A full cache makes room by evicting this thread's least recently used tree; a thread
+    /// holding none parses without caching.
+    pub fn parse(&self, path: &Path, content: &str) -> Option<Rc<syn::File>> {
+        #[cfg(feature = "cache")]
+        {
+            let key: [u8; 32] = Sha256::digest(content.as_bytes()).into();
+            TREES.with_borrow_mut(|stores| {
+                // Trees of dropped caches are released on the thread's next lookup
+                stores.retain(|_, local| local.store.strong_count() > 0);
+                let local = stores.entry(self.store.id).or_insert_with(|| LocalTrees {
+                    store: Arc::downgrade(&self.store),
+                    trees: lru::LruCache::unbounded(),
+                });
+                if let Some(tree) = local.trees.get(&key) {
+                    return tree.clone();
+                }
+
+                let tree = parse_syntax_tree(path, content).map(Rc::new);
+                if self.store.reserve() || local.trees.pop_lru().is_some() {
+                    local.trees.put(key, tree.clone());
+                }
+                tree
+            })
+        }
+        #[cfg(not(feature = "cache"))]
+        {
+            parse_syntax_tree(path, content).map(Rc::new)
+        }
+    }
+}
+
+impl Default for SyntaxTreeCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(all(test, feature = "cache"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trees_reused_by_content() {
+        let cache = SyntaxTreeCache::new(2);
+        let path = Path::new("src/lib.rs");
+        let first = cache.parse(path, "fn one() {}\n").unwrap();
+        let again = cache
+            .parse(Path::new("src/renamed.rs"), "fn one() {}\n")
+            .unwrap();
+        assert!(Rc::ptr_eq(&first, &again));
+        assert!(cache.parse(path, "fn (").is_none());
+
+        // The least recently used tree is evicted
+        cache.parse(path, "fn two() {}\n").unwrap();
+        cache.parse(path, "fn three() {}\n").unwrap();
+        assert_eq!(cache.len(), 2);
+        let reparsed = cache.parse(path, "fn one() {}\n").unwrap();
+        assert!(!Rc::ptr_eq(&first, &reparsed));
+    }
+
+    #[test]
+    fn test_caches_keep_their_own_trees() {
+        let path = Path::new("src/lib.rs");
+        let small = SyntaxTreeCache::new(1);
+        let large = SyntaxTreeCache::new(4);
+        let tree = large.parse(path, "fn one() {}\n").unwrap();
+
+        // Filling another cache neither evicts nor resizes this one
+        small.parse(path, "fn two() {}\n").unwrap();
+        small.parse(path, "fn three() {}\n").unwrap();
+        assert!(Rc::ptr_eq(
+            &tree,
+            &large.parse(path, "fn one() {}\n").unwrap()
+        ));
+        assert_eq!((small.len(), large.len()), (1, 1));
+
+        // Clones share trees
+        assert!(Rc::ptr_eq(
+            &tree,
+            &large.clone().parse(path, "fn one() {}\n").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_capacity_bounds_all_threads() {
+        let cache = SyntaxTreeCache::new(2);
+        let path = Path::new("src/lib.rs");
+        let first = cache.parse(path, "fn one() {}\n").unwrap();
+
+        let other = cache.clone();
+        std::thread::spawn(move || {
+            other.parse(path, "fn two() {}\n").unwrap();
+            assert_eq!(other.len(), 2);
+            // Full, so this thread replaces its own tree rather than another thread's
+            other.parse(path, "fn three() {}\n").unwrap();
+            assert_eq!(other.len(), 2);
+        })
+        .join()
+        .unwrap();
+
+        // Trees of a finished thread are released
+        assert_eq!(cache.len(), 1);
+        assert!(Rc::ptr_eq(
+            &first,
+            &cache.parse(path, "fn one() {}\n").unwrap()
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+//! Detection of detached async tasks
+//!
+//! Architecture: Domain Services - Task lifetimes are checked structurally, like any other finding
+//! - A spawn whose `JoinHandle` is discarded in the same statement can never be awaited or aborted
+//! - Binding the handle to a named variable, returning it, or awaiting it counts as keeping it
+//! - Modules that intentionally fire and forget are allow-listed by module path
+
+use super::scope::{self, Scope, Scoped};
+use proc_macro2::Span;
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Functions returning a `JoinHandle` for a spawned task
+const SPAWN_FUNCTIONS: &[&str] = &["spawn", "spawn_local", "spawn_blocking"];
+
+/// Path segments that qualify a spawn function as a task spawner (`tokio::spawn`, `task::spawn`)
+const SPAWN_QUALIFIERS: &[&str] = &["tokio", "task"];
+
+/// Configuration of the detached task check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetachedTaskCheck {
+    /// Module paths, relative to the crate root, where detached tasks are allowed
+    pub allowed_modules: Vec<Vec<String>>,
+}
+
+impl DetachedTaskCheck {
+    /// Parse `detached_task` or `detached_task:<module>|<module>`
+    ///
+    /// Modules are written as paths such as `telemetry` or `crate::jobs::background` and also
+    /// allow their submodules. Returns `None` when the pattern is not a detached task pattern.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let modules = match pattern.strip_prefix("detached_task") {
+            Some("") => "",
+            Some(rest) => rest.strip_prefix(':')?,
+            None => return None,
+        };
+
+        Some(Self {
+            allowed_modules: super::parse_module_list(modules),
+        })
+    }
+}
+
+/// Find spawned tasks whose handle is dropped immediately
+///
+/// Returns the spawn call span, the spawn path as written, and a short description as context.
+pub fn find_detached_tasks(
+    syntax_tree: &syn::File,
+    check: &DetachedTaskCheck,
+    file_path: &Path,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = DetachedTaskVisitor {
+        check,
+        scope: Scope::for_file(file_path),
+        function: None,
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct DetachedTaskVisitor<'a> {
+    check: &'a DetachedTaskCheck,
+    scope: Scope,
+    function: Option<String>,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl DetachedTaskVisitor<'_> {
+    fn report(&mut self, call: &syn::ExprCall, how: &str) {
+        if super::module_listed(&self.check.allowed_modules, &self.scope.module) {
+            return;
+        }
+        if let Some(spawn) = spawn_path(call) {
+            let context = match &self.function {
+                Some(function) => format!("{spawn}() handle {how} in fn {function}"),
+                None => format!("{spawn}() handle {how}"),
+            };
+            self.matches.push((call.span(), spawn, context));
+        }
+    }
+}
+
+impl Scoped for DetachedTaskVisitor<'_> {
+    fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+}
+
+impl Visit<'_> for DetachedTaskVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &syn::ItemMod) {
+        scope::visit_item_mod(self, item);
+    }
+
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &syn::ImplItemFn) {
+        let outer = self.function.replace(item.sig.ident.to_string());
+        syn::visit::visit_impl_item_fn(self, item);
+        self.function = outer;
+    }
+
+    fn visit_stmt(&mut self, stmt: &syn::Stmt) {
+        match stmt {
+            // `tokio::spawn(work);`
+            syn::Stmt::Expr(syn::Expr::Call(call), Some(_)) => self.report(call, "dropped"),
+            // `let _ = tokio::spawn(work);`
+            syn::Stmt::Local(local) if matches!(local.pat, syn::Pat::Wild(_)) => {
+                if let Some(syn::Expr::Call(call)) =
+                    local.init.as_ref().map(|init| init.expr.as_ref())
+                {
+                    self.report(call, "discarded with `let _`");
+                }
+            }
+            _ => {}
+        }
+        syn::visit::visit_stmt(self, stmt);
+    }
+}
+
+/// The spawn path of a call such as `tokio::spawn(...)`, if it is one
+fn spawn_path(call: &syn::ExprCall) -> Option<String> {
+    let path = match call.func.as_ref() {
+        syn::Expr::Path(path) => &path.path,
+        _ => return None,
+    };
+
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let (name, qualifiers) = segments.split_last()?;
+
+    let is_spawn = SPAWN_FUNCTIONS.contains(&name.as_str())
+        && qualifiers
+            .last()
+            .is_some_and(|qualifier| SPAWN_QUALIFIERS.contains(&qualifier.as_str()));
+    is_spawn.then(|| segments.join("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detached(source: &str, check: &DetachedTaskCheck, path: &str) -> Vec<String> {
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_detached_tasks(&syntax_tree, check, Path::new(path))
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            DetachedTaskCheck::parse("detached_task"),
+            Some(DetachedTaskCheck::default())
+        );
+        assert_eq!(
+            DetachedTaskCheck::parse("detached_task:crate::telemetry | jobs::background"),
+            Some(DetachedTaskCheck {
+                allowed_modules: vec![
+                    vec!["telemetry".to_string()],
+                    vec!["jobs".to_string(), "background".to_string()],
+                ],
+            })
+        );
+        assert_eq!(DetachedTaskCheck::parse("detached_tasks"), None);
+        assert_eq!(DetachedTaskCheck::parse("future_not_awaited"), None);
+    }
+
+    #[test]
+    fn test_finds_dropped_handles() {
+        let source = r#"
+async fn serve() {
+    tokio::spawn(async { work().await });
+    let _ = tokio::task::spawn_blocking(compute);
+    let _handle = tokio::spawn(async {});
+    let handle = tokio::spawn(async {});
+    handle.abort();
+    tokio::spawn(async {}).await.ok();
+    std::thread::spawn(|| {});
+    join_set.spawn(async {});
+}
+
+fn start() -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async {})
+}
+"#;
+        let check = DetachedTaskCheck::default();
+        assert_eq!(
+            detached(source, &check, "src/server.rs"),
+            vec![
+                "tokio::spawn() handle dropped in fn serve",
+                "tokio::task::spawn_blocking() handle discarded with `let _` in fn serve",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowed_modules() {
+        let source = r#"
+fn report() {
+    tokio::spawn(async {});
+}
+
+mod background {
+    fn flush() {
+        task::spawn(async {});
+    }
+}
+"#;
+        let check = DetachedTaskCheck::parse("detached_task:telemetry::background")
+            .expect("pattern should parse");
+
+        // Inline modules extend the module path of the file
+        assert_eq!(
+            detached(source, &check, "src/telemetry.rs"),
+            vec!["tokio::spawn() handle dropped in fn report"]
+        );
+        assert_eq!(
+            detached(source, &check, "src/telemetry/background.rs").len(),
+            0
+        );
+        assert_eq!(detached(source, &check, "src/server.rs").len(), 2);
+    }
+}
@@ -0,0 +1,202 @@
+//! TODO and FIXME markers carrying owner, issue, and due-date metadata
+//!
+//! Architectural Principle: Value Objects - A TODO is acceptable when it is tracked
+//! - Markers must name an owner and an issue, e.g. `TODO(alice, #1234): ...`
+//! - An optional due date turns a tracked TODO into one that can expire
+//! - Only markers inside comments are considered; string contents are left alone
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use chrono::NaiveDate;
+use regex::Regex;
+use std::ops::Range;
+
+/// Default metadata format: `TODO(owner, #issue):` or `TODO(owner, #issue, YYYY-MM-DD):`
+pub const DEFAULT_TODO_FORMAT: &str = r"^(?P<tag>TODO|FIXME)\((?P<owner>[\w.-]+),\s*(?P<issue>#\d+)(?:,\s*(?P<due>\d{4}-\d{2}-\d{2}))?\):";
+
+/// Which TODO markers a rule reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoCheckKind {
+    /// Markers without valid owner and issue metadata
+    MissingMetadata,
+    /// Markers whose due date has passed
+    Expired,
+}
+
+/// A TODO metadata rule with its compiled format
+#[derive(Debug, Clone)]
+pub struct TodoCheck {
+    pub kind: TodoCheckKind,
+    marker: Regex,
+    format: Regex,
+}
+
+/// A TODO or FIXME marker found in a comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoMarker {
+    /// `TODO` or `FIXME`
+    pub tag: String,
+    /// Byte range of the marker up to the end of its line
+    pub byte_range: Range<usize>,
+    /// Parsed metadata, or `None` if the marker does not match the required format
+    pub metadata: Option<TodoMetadata>,
+}
+
+/// Owner, issue, and optional due date captured from a marker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoMetadata {
+    pub owner: String,
+    pub issue: String,
+    pub due: Option<NaiveDate>,
+}
+
+impl TodoCheck {
+    /// Parse a semantic pattern into a TODO check
+    ///
+    /// Accepts `todo_without_metadata` and `todo_expired`, each optionally followed by
+    /// `:<regex>` to replace the default format. A custom format must define `owner` and
+    /// `issue` capture groups and may define `due`. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<GuardianResult<Self>> {
+        let (kind, format) = if let Some(rest) = pattern.strip_prefix("todo_without_metadata") {
+            (TodoCheckKind::MissingMetadata, rest)
+        } else if let Some(rest) = pattern.strip_prefix("todo_expired") {
+            (TodoCheckKind::Expired, rest)
+        } else {
+            return None;
+        };
+
+        let format = match format {
+            "" => DEFAULT_TODO_FORMAT,
+            custom => custom.strip_prefix(':')?,
+        };
+
+        Some(Self::new(kind, format))
+    }
+
+    /// Create a check with a metadata format regex
+    pub fn new(kind: TodoCheckKind, format: &str) -> GuardianResult<Self> {
+        let format = Regex::new(format)
+            .map_err(|e| GuardianError::pattern(format!("Invalid TODO format '{format}': {e}")))?;
+
+        for group in ["owner", "issue"] {
+            if !format.capture_names().flatten().any(|name| name == group) {
+                return Err(GuardianError::pattern(format!(
+                    "TODO format '{}' must define a '{group}' capture group",
+                    format.as_str()
+                )));
+            }
+        }
+
+        let marker = Regex::new(r"\b(TODO|FIXME)\b")
+            .map_err(|e| GuardianError::pattern(format!("Invalid TODO marker pattern: {e}")))?;
+
+        Ok(Self {
+            kind,
+            marker,
+            format,
+        })
+    }
+
+    /// Find markers this check reports, judging due dates against `today`
+    pub fn find_violations(&self, content: &str, today: NaiveDate) -> Vec<TodoMarker> {
+        self.find_markers(content)
+            .into_iter()
+            .filter(|marker| match (self.kind, &marker.metadata) {
+                (TodoCheckKind::MissingMetadata, metadata) => metadata.is_none(),
+                (TodoCheckKind::Expired, Some(metadata)) => {
+                    metadata.due.is_some_and(|due| due < today)
+                }
+                (TodoCheckKind::Expired, None) => false,
+            })
+            .collect()
+    }
+
+    /// Find every TODO and FIXME marker inside a comment
+    pub fn find_markers(&self, content: &str) -> Vec<TodoMarker> {
+        let mut markers = Vec::new();
+
+        for marker in self.marker.find_iter(content) {
+            let line_start = content[..marker.start()]
+                .rfind('\n')
+                .map_or(0, |pos| pos + 1);
+            let line_end = content[marker.start()..]
+                .find('\n')
+                .map_or(content.len(), |pos| marker.start() + pos);
+
+            let prefix = &content[line_start..marker.start()];
+            let in_comment = prefix.contains("//")
+                || prefix.contains("/*")
+                || prefix.trim_start().starts_with('*');
+            if !in_comment {
+                continue;
+            }
+
+            let rest = &content[marker.start()..line_end];
+            markers.push(TodoMarker {
+                tag: marker.as_str().to_string(),
+                byte_range: marker.start()..line_end,
+                metadata: self.parse_metadata(rest),
+            });
+        }
+
+        markers
+    }
+
+    /// Validate the captured groups of a marker
+    fn parse_metadata(&self, text: &str) -> Option<TodoMetadata> {
+        let captures = self.format.captures(text)?;
+        if captures.get(0)?.start() != 0 {
+            return None;
+        }
+
+        let owner = captures.name("owner")?.as_str().trim();
+        let issue = captures.name("issue")?.as_str().trim();
+        if owner.is_empty() || issue.is_empty() {
+            return None;
+        }
+
+        // A due date that is present but not a real date is malformed metadata
+        let due = match captures.name("due") {
+            Some(due) => Some(NaiveDate::parse_from_str(due.as_str(), "%Y-%m-%d").ok()?),
+            None => None,
+        };
+
+        Some(TodoMetadata {
+            owner: owner.to_string(),
+            issue: issue.to_string(),
+            due,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_todo_metadata() {
+        let content = "// TODO: naked\n// TODO(alice, #12): tracked\n// FIXME(bob, #7, 2024-01-31): overdue\n// TODO(carol, #9, 2024-13-01): bad date\nlet s = \"TODO in a string\";\n";
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).expect("date should be valid");
+
+        let missing = TodoCheck::parse("todo_without_metadata")
+            .expect("pattern should be recognized")
+            .expect("pattern should be valid");
+        let lines: Vec<usize> = missing
+            .find_violations(content, today)
+            .iter()
+            .map(|marker| content[..marker.byte_range.start].matches('\n').count() + 1)
+            .collect();
+        assert_eq!(lines, [1, 4]);
+
+        let expired = TodoCheck::parse("todo_expired")
+            .expect("pattern should be recognized")
+            .expect("pattern should be valid");
+        let found = expired.find_violations(content, today);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].tag, "FIXME");
+
+        // Custom formats must capture the owner and issue
+        assert!(TodoCheck::parse("todo_expired:^TODO\\((?P<who>\\w+)\\)")
+            .expect("pattern should be recognized")
+            .is_err());
+    }
+}
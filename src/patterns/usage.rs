@@ -0,0 +1,146 @@
+//! Per-rule usage statistics
+//!
+//! Architecture: Domain Services - Each rule's cost and yield is tallied where it runs
+//! - A file counts as examined by a rule once the rule is in scope for it and actually runs
+//! - Matches are counted after proximity filtering, so they are the matches that get reported
+//! - Every rule has its own atomic counters, so parallel runs record without locking
+
+use crate::domain::violations::RuleUsage;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// One rule's run over one file: the rule, its matches, and how long it took
+pub(crate) type RuleRun<'a> = (&'a str, usize, Duration);
+
+#[derive(Debug, Default)]
+struct Tally {
+    files_examined: AtomicUsize,
+    files_matched: AtomicUsize,
+    matches: AtomicUsize,
+    elapsed_nanos: AtomicU64,
+}
+
+impl Tally {
+    fn add(&self, files_examined: usize, files_matched: usize, matches: usize, elapsed: Duration) {
+        self.files_examined
+            .fetch_add(files_examined, Ordering::Relaxed);
+        self.files_matched
+            .fetch_add(files_matched, Ordering::Relaxed);
+        self.matches.fetch_add(matches, Ordering::Relaxed);
+        self.elapsed_nanos.fetch_add(
+            u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// Accumulates rule usage across the files of an analysis run
+///
+/// Runs of rules the recorder does not track are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct UsageRecorder {
+    tallies: HashMap<String, Tally>,
+}
+
+impl UsageRecorder {
+    /// Recorder for the given rules
+    pub(crate) fn new(rule_ids: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            tallies: rule_ids
+                .into_iter()
+                .map(|rule_id| (rule_id, Tally::default()))
+                .collect(),
+        }
+    }
+
+    /// Start tracking a rule added after the recorder was created
+    pub(crate) fn track(&mut self, rule_id: String) {
+        self.tallies.entry(rule_id).or_default();
+    }
+
+    /// Record the rules run over one file
+    pub(crate) fn record_file(&self, runs: &[RuleRun]) {
+        for &(rule_id, matches, elapsed) in runs {
+            if let Some(tally) = self.tallies.get(rule_id) {
+                tally.add(1, usize::from(matches > 0), matches, elapsed);
+            }
+        }
+    }
+
+    /// Record a cross-file rule, run once over many files
+    pub(crate) fn record_workspace(
+        &self,
+        rule_id: &str,
+        files_examined: usize,
+        matched_files: usize,
+        matches: usize,
+        elapsed: Duration,
+    ) {
+        if let Some(tally) = self.tallies.get(rule_id) {
+            tally.add(files_examined, matched_files, matches, elapsed);
+        }
+    }
+
+    /// Take the usage recorded so far, listing every rule even if it never ran
+    pub(crate) fn take(&self) -> Vec<RuleUsage> {
+        let mut usage: Vec<RuleUsage> = self
+            .tallies
+            .iter()
+            .map(|(rule_id, tally)| RuleUsage {
+                rule_id: rule_id.clone(),
+                files_examined: tally.files_examined.swap(0, Ordering::Relaxed),
+                files_matched: tally.files_matched.swap(0, Ordering::Relaxed),
+                matches: tally.matches.swap(0, Ordering::Relaxed),
+                time_ms: Duration::from_nanos(tally.elapsed_nanos.swap(0, Ordering::Relaxed))
+                    .as_secs_f64()
+                    * 1000.0,
+            })
+            .collect();
+        usage.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+        usage
+    }
+
+    /// Forget the usage recorded so far
+    pub(crate) fn reset(&self) {
+        self.take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_accumulates_and_resets() {
+        let recorder = UsageRecorder::new(
+            ["duplicate_literals", "never_runs", "no_todo", "no_unwrap"].map(String::from),
+        );
+        let millis = Duration::from_millis;
+        recorder.record_file(&[("no_unwrap", 2, millis(3)), ("no_todo", 0, millis(1))]);
+        recorder.record_file(&[("no_unwrap", 0, millis(1))]);
+        recorder.record_workspace("duplicate_literals", 10, 2, 3, millis(5));
+        recorder.record_file(&[("unknown", 1, millis(1))]);
+
+        let usage = recorder.take();
+        let ids: Vec<&str> = usage.iter().map(|rule| rule.rule_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["duplicate_literals", "never_runs", "no_todo", "no_unwrap"]
+        );
+
+        let no_unwrap = &usage[3];
+        assert_eq!(
+            (
+                no_unwrap.files_examined,
+                no_unwrap.files_matched,
+                no_unwrap.matches
+            ),
+            (2, 1, 2)
+        );
+        assert!((no_unwrap.time_ms - 4.0).abs() < 1e-6);
+        assert_eq!(usage[1].files_examined, 0);
+
+        assert!(recorder.take().iter().all(|rule| rule.matches == 0));
+    }
+}
@@ -0,0 +1,182 @@
+//! Visibility policy for internal modules
+//!
+//! Architecture: Specification Pattern - Configured modules are implementation details of a library
+//! - Items in those modules are at most `pub(crate)`, so the public API is chosen on purpose
+//! - Only module-level items are checked; methods and fields are bounded by their type already
+//! - The policy covers submodules, and `pub use` re-exports count as leaks too
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use proc_macro2::Span;
+use std::path::Path;
+use syn::spanned::Spanned;
+
+/// Configuration of the internal visibility check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibilityCheck {
+    /// Module paths, relative to the crate root, whose items must not be bare `pub`
+    pub internal_modules: Vec<Vec<String>>,
+}
+
+impl VisibilityCheck {
+    /// Parse `pub_in_internal:<module>|<module>`
+    ///
+    /// At least one module is required. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<GuardianResult<Self>> {
+        let modules = match pattern.strip_prefix("pub_in_internal")? {
+            "" => "",
+            rest => rest.strip_prefix(':')?,
+        };
+
+        let internal_modules = super::parse_module_list(modules);
+        if internal_modules.is_empty() {
+            return Some(Err(GuardianError::pattern(
+                "pub_in_internal needs at least one module, as in 'pub_in_internal:internal|engine'",
+            )));
+        }
+        Some(Ok(Self { internal_modules }))
+    }
+}
+
+/// Find bare `pub` items declared in internal modules
+///
+/// Returns the `pub` span, the item name, and the item kind and module as context.
+pub fn find_public_leaks(
+    syntax_tree: &syn::File,
+    check: &VisibilityCheck,
+    file_path: &Path,
+) -> Vec<(Span, String, String)> {
+    let mut module = crate::graph::module_segments_for(file_path);
+    let mut matches = Vec::new();
+    check_items(&syntax_tree.items, check, &mut module, &mut matches);
+    matches
+}
+
+fn check_items(
+    items: &[syn::Item],
+    check: &VisibilityCheck,
+    module: &mut Vec<String>,
+    matches: &mut Vec<(Span, String, String)>,
+) {
+    let internal = super::module_listed(&check.internal_modules, module);
+    for item in items {
+        if internal {
+            if let Some((vis, kind, name)) = describe(item) {
+                if matches!(vis, syn::Visibility::Public(_)) {
+                    let location = if module.is_empty() {
+                        "crate".to_string()
+                    } else {
+                        module.join("::")
+                    };
+                    matches.push((vis.span(), name, format!("pub {kind} in {location}")));
+                }
+            }
+        }
+
+        if let syn::Item::Mod(item_mod) = item {
+            if let Some((_, content)) = &item_mod.content {
+                module.push(item_mod.ident.to_string());
+                check_items(content, check, module, matches);
+                module.pop();
+            }
+        }
+    }
+}
+
+/// Visibility, kind and name of an item that can be declared `pub`
+fn describe(item: &syn::Item) -> Option<(&syn::Visibility, &'static str, String)> {
+    let described = match item {
+        syn::Item::Const(item) => (&item.vis, "const", item.ident.to_string()),
+        syn::Item::Enum(item) => (&item.vis, "enum", item.ident.to_string()),
+        syn::Item::ExternCrate(item) => (&item.vis, "extern crate", item.ident.to_string()),
+        syn::Item::Fn(item) => (&item.vis, "fn", item.sig.ident.to_string()),
+        syn::Item::Mod(item) => (&item.vis, "mod", item.ident.to_string()),
+        syn::Item::Static(item) => (&item.vis, "static", item.ident.to_string()),
+        syn::Item::Struct(item) => (&item.vis, "struct", item.ident.to_string()),
+        syn::Item::Trait(item) => (&item.vis, "trait", item.ident.to_string()),
+        syn::Item::TraitAlias(item) => (&item.vis, "trait", item.ident.to_string()),
+        syn::Item::Type(item) => (&item.vis, "type", item.ident.to_string()),
+        syn::Item::Union(item) => (&item.vis, "union", item.ident.to_string()),
+        syn::Item::Use(item) => (&item.vis, "use", use_name(&item.tree)),
+        _ => return None,
+    };
+    Some(described)
+}
+
+/// A `use` tree as written, without the spacing of its token stream
+fn use_name(tree: &syn::UseTree) -> String {
+    match tree {
+        syn::UseTree::Path(path) => format!("{}::{}", path.ident, use_name(&path.tree)),
+        syn::UseTree::Name(name) => name.ident.to_string(),
+        syn::UseTree::Rename(rename) => format!("{} as {}", rename.ident, rename.rename),
+        syn::UseTree::Glob(_) => "*".to_string(),
+        syn::UseTree::Group(group) => {
+            let items: Vec<String> = group.items.iter().map(use_name).collect();
+            format!("{{{}}}", items.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaks(source: &str, pattern: &str, path: &str) -> Vec<String> {
+        let check = VisibilityCheck::parse(pattern)
+            .expect("pattern should be recognized")
+            .expect("pattern should parse");
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_public_leaks(&syntax_tree, &check, Path::new(path))
+            .into_iter()
+            .map(|(_, name, context)| format!("{name}: {context}"))
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+pub struct Engine {
+    pub state: u32,
+}
+
+impl Engine {
+    pub fn run(&self) {}
+}
+
+pub(crate) fn helper() {}
+fn private() {}
+pub use crate::model::{Node, Edge as Link};
+
+pub mod detail {
+    pub const LIMIT: usize = 8;
+    pub(super) fn step() {}
+}
+"#;
+
+    #[test]
+    fn test_public_items_in_internal_modules() {
+        assert_eq!(
+            leaks(SOURCE, "pub_in_internal:engine", "src/engine/mod.rs"),
+            vec![
+                "Engine: pub struct in engine",
+                "crate::model::{Node, Edge as Link}: pub use in engine",
+                "detail: pub mod in engine",
+                "LIMIT: pub const in engine::detail",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_only_listed_modules_are_checked() {
+        assert_eq!(
+            leaks(
+                SOURCE,
+                "pub_in_internal:crate::engine::detail",
+                "src/engine.rs"
+            ),
+            vec!["LIMIT: pub const in engine::detail"]
+        );
+        assert!(leaks(SOURCE, "pub_in_internal:engine", "src/api.rs").is_empty());
+        assert!(VisibilityCheck::parse("pub_in_internal")
+            .expect("pattern should be recognized")
+            .is_err());
+        assert!(VisibilityCheck::parse("pub_in_internals:engine").is_none());
+    }
+}
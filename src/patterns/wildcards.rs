@@ -0,0 +1,210 @@
+//! Wildcard import and glob re-export policy
+//!
+//! Architecture: Domain Services - Glob imports are checked structurally, like any other finding
+//! - `use foo::*` hides where names come from; `pub use foo::*` hides what the API exports
+//! - Preludes are exempt: imports from a `prelude` module, and globs inside one
+//! - Test modules may `use super::*`; other intentional globs are allow-listed by module path
+
+use super::scope::{self, Scope, Scoped};
+use proc_macro2::Span;
+use std::path::Path;
+use syn::visit::Visit;
+
+/// Module name whose globs are intentional on both sides of a `use`
+const PRELUDE: &str = "prelude";
+
+/// Which kind of glob `use` a rule reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WildcardKind {
+    /// Private or crate-visible `use foo::*` outside tests
+    Import,
+    /// `pub use foo::*` re-exports
+    Reexport,
+}
+
+/// Configuration of a wildcard check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WildcardCheck {
+    pub kind: WildcardKind,
+    /// Module paths, relative to the crate root, where globs are allowed
+    pub allowed_modules: Vec<Vec<String>>,
+}
+
+impl WildcardCheck {
+    /// Parse `wildcard_import` or `wildcard_reexport`, optionally followed by
+    /// `:<module>|<module>`
+    ///
+    /// Listed modules also allow their submodules. Returns `None` for other patterns.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let (kind, rest) = if let Some(rest) = pattern.strip_prefix("wildcard_import") {
+            (WildcardKind::Import, rest)
+        } else {
+            (
+                WildcardKind::Reexport,
+                pattern.strip_prefix("wildcard_reexport")?,
+            )
+        };
+        let modules = match rest {
+            "" => "",
+            rest => rest.strip_prefix(':')?,
+        };
+
+        Some(Self {
+            kind,
+            allowed_modules: super::parse_module_list(modules),
+        })
+    }
+}
+
+/// Find glob `use` items the check reports
+///
+/// Returns the `*` span, the globbed path, and the `use` as written for context.
+pub fn find_wildcards(
+    syntax_tree: &syn::File,
+    check: &WildcardCheck,
+    file_path: &Path,
+) -> Vec<(Span, String, String)> {
+    let mut visitor = WildcardVisitor {
+        check,
+        scope: Scope::for_file(file_path),
+        matches: Vec::new(),
+    };
+    visitor.visit_file(syntax_tree);
+    visitor.matches
+}
+
+struct WildcardVisitor<'a> {
+    check: &'a WildcardCheck,
+    scope: Scope,
+    matches: Vec<(Span, String, String)>,
+}
+
+impl WildcardVisitor<'_> {
+    fn collect_globs(&mut self, tree: &syn::UseTree, prefix: &mut Vec<String>, public: bool) {
+        match tree {
+            syn::UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect_globs(&path.tree, prefix, public);
+                prefix.pop();
+            }
+            syn::UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.collect_globs(tree, prefix, public);
+                }
+            }
+            syn::UseTree::Glob(glob) => {
+                if prefix.last().is_some_and(|segment| segment == PRELUDE) {
+                    return;
+                }
+                let path = prefix.join("::");
+                let written = if public {
+                    format!("pub use {path}::*")
+                } else {
+                    format!("use {path}::*")
+                };
+                self.matches.push((glob.star_token.spans[0], path, written));
+            }
+            syn::UseTree::Name(_) | syn::UseTree::Rename(_) => {}
+        }
+    }
+}
+
+impl Scoped for WildcardVisitor<'_> {
+    fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+}
+
+impl Visit<'_> for WildcardVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &syn::ItemMod) {
+        scope::visit_item_mod(self, item);
+    }
+
+    fn visit_item_fn(&mut self, item: &syn::ItemFn) {
+        scope::visit_item_fn(self, item);
+    }
+
+    fn visit_item_use(&mut self, item: &syn::ItemUse) {
+        let public = matches!(item.vis, syn::Visibility::Public(_));
+        let reported = match self.check.kind {
+            WildcardKind::Import => !public && !self.scope.in_test,
+            WildcardKind::Reexport => public,
+        };
+        let module = &self.scope.module;
+        let allowed = module.iter().any(|segment| segment == PRELUDE)
+            || super::module_listed(&self.check.allowed_modules, module);
+        if reported && !allowed {
+            self.collect_globs(&item.tree, &mut Vec::new(), public);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wildcards(source: &str, pattern: &str, path: &str) -> Vec<String> {
+        let check = WildcardCheck::parse(pattern).expect("pattern should parse");
+        let syntax_tree = syn::parse_file(source).expect("source should parse");
+        find_wildcards(&syntax_tree, &check, Path::new(path))
+            .into_iter()
+            .map(|(_, _, context)| context)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+use std::collections::*;
+use std::io::{self, prelude::*, Read};
+use crate::{model::*, util::Helper};
+pub use crate::errors::*;
+pub(crate) use crate::internal::*;
+
+pub mod prelude {
+    pub use crate::model::*;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"#;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            WildcardCheck::parse("wildcard_reexport:crate::api"),
+            Some(WildcardCheck {
+                kind: WildcardKind::Reexport,
+                allowed_modules: vec![vec!["api".to_string()]],
+            })
+        );
+        assert!(WildcardCheck::parse("wildcard_imports").is_none());
+        assert!(WildcardCheck::parse("detached_task").is_none());
+    }
+
+    #[test]
+    fn test_wildcard_imports() {
+        assert_eq!(
+            wildcards(SOURCE, "wildcard_import", "src/lib.rs"),
+            vec![
+                "use std::collections::*",
+                "use crate::model::*",
+                "use crate::internal::*",
+            ]
+        );
+        assert_eq!(
+            wildcards(SOURCE, "wildcard_import:other", "src/legacy/mod.rs").len(),
+            3
+        );
+        assert!(wildcards(SOURCE, "wildcard_import:legacy", "src/legacy/mod.rs").is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_reexports() {
+        assert_eq!(
+            wildcards(SOURCE, "wildcard_reexport", "src/lib.rs"),
+            vec!["pub use crate::errors::*"]
+        );
+        assert!(wildcards(SOURCE, "wildcard_reexport", "src/prelude.rs").is_empty());
+    }
+}
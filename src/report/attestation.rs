@@ -0,0 +1,171 @@
+//! Signed report attestation
+//!
+//! Architecture: Infrastructure Adapter - Signatures travel beside reports, never inside them
+//! - A detached signature covers the exact bytes of a rendered report (JSON, SARIF, ...)
+//! - Keys are raw ed25519 keys, hex-encoded, read from a file or `GUARDIAN_SIGNING_KEY`
+//! - Verification checks the signer against a trusted public key, not the one in the signature
+
+use crate::domain::violations::{GuardianError, GuardianResult};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// Environment variable holding a hex-encoded signing key
+pub const SIGNING_KEY_ENV: &str = "GUARDIAN_SIGNING_KEY";
+
+/// Signature algorithm recorded in signature files
+const ALGORITHM: &str = "ed25519";
+
+/// Detached signature of a rendered report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSignature {
+    /// Signature algorithm, always `ed25519`
+    pub algorithm: String,
+    /// Hex-encoded public key of the signer
+    pub public_key: String,
+    /// Hex-encoded SHA-256 of the signed report bytes
+    pub report_sha256: String,
+    /// Hex-encoded signature over the report bytes
+    pub signature: String,
+}
+
+impl ReportSignature {
+    /// Sign the exact bytes of a rendered report
+    pub fn sign(report: &[u8], key: &SigningKey) -> Self {
+        Self {
+            algorithm: ALGORITHM.to_string(),
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            report_sha256: format!("{:x}", Sha256::digest(report)),
+            signature: hex::encode(key.sign(report).to_bytes()),
+        }
+    }
+
+    /// Verify that `report` is unmodified and was signed by `trusted`
+    pub fn verify(&self, report: &[u8], trusted: &VerifyingKey) -> GuardianResult<()> {
+        if self.algorithm != ALGORITHM {
+            return Err(GuardianError::attestation(format!(
+                "Unsupported signature algorithm '{}'",
+                self.algorithm
+            )));
+        }
+        if self.public_key != hex::encode(trusted.as_bytes()) {
+            return Err(GuardianError::attestation(
+                "Report was signed by a different key than the trusted one",
+            ));
+        }
+        if self.report_sha256 != format!("{:x}", Sha256::digest(report)) {
+            return Err(GuardianError::attestation(
+                "Report contents do not match the signed digest",
+            ));
+        }
+
+        let bytes: [u8; 64] = decode_hex(&self.signature, "signature")?
+            .try_into()
+            .map_err(|_| GuardianError::attestation("Signature must be 64 bytes"))?;
+        // Strict verification also rejects weak keys and non-canonical signatures
+        trusted
+            .verify_strict(report, &Signature::from_bytes(&bytes))
+            .map_err(|_| GuardianError::attestation("Report signature is invalid"))
+    }
+
+    /// Read a signature file
+    pub fn load<P: AsRef<Path>>(path: P) -> GuardianResult<Self> {
+        let contents =
+            fs::read_to_string(&path).map_err(|e| GuardianError::io(path.as_ref(), e))?;
+        serde_json::from_str(&contents).map_err(|e| {
+            GuardianError::attestation(format!(
+                "Invalid signature file '{}': {e}",
+                path.as_ref().display()
+            ))
+        })
+    }
+
+    /// Render as pretty JSON for a signature file
+    pub fn to_json(&self) -> GuardianResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| GuardianError::attestation(format!("Signature serialization failed: {e}")))
+    }
+}
+
+/// Generate a new random signing key
+pub fn generate_signing_key() -> SigningKey {
+    SigningKey::generate(&mut rand_core::OsRng)
+}
+
+/// Load the signing key from `path`, or from `GUARDIAN_SIGNING_KEY` when no path is given
+pub fn load_signing_key(path: Option<&Path>) -> GuardianResult<SigningKey> {
+    let encoded = match path {
+        Some(path) => fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?,
+        None => std::env::var(SIGNING_KEY_ENV).map_err(|_| {
+            GuardianError::attestation(format!(
+                "No signing key given; pass a key file or set {SIGNING_KEY_ENV}"
+            ))
+        })?,
+    };
+
+    let bytes: [u8; 32] = decode_hex(&encoded, "signing key")?
+        .try_into()
+        .map_err(|_| GuardianError::attestation("Signing key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// Parse a hex-encoded public key, given inline or as a file containing it
+pub fn load_public_key(key: &str) -> GuardianResult<VerifyingKey> {
+    let path = Path::new(key);
+    let encoded = if path.is_file() {
+        fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?
+    } else {
+        key.to_string()
+    };
+
+    let bytes: [u8; 32] = decode_hex(&encoded, "public key")?
+        .try_into()
+        .map_err(|_| GuardianError::attestation("Public key must be 32 bytes"))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| GuardianError::attestation(format!("Invalid public key: {e}")))
+}
+
+fn decode_hex(encoded: &str, what: &str) -> GuardianResult<Vec<u8>> {
+    hex::decode(encoded.trim())
+        .map_err(|e| GuardianError::attestation(format!("Invalid hex in {what}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let report = br#"{"summary":{"errors":0}}"#;
+        let signature = ReportSignature::sign(report, &key);
+
+        let trusted = load_public_key(&signature.public_key).expect("public key should parse");
+        signature
+            .verify(report, &trusted)
+            .expect("untouched report should verify");
+
+        let roundtrip: ReportSignature =
+            serde_json::from_str(&signature.to_json().expect("signature should serialize"))
+                .expect("signature should round-trip");
+        assert_eq!(roundtrip, signature);
+
+        // Tampered report
+        assert!(signature
+            .verify(br#"{"summary":{"errors":1}}"#, &trusted)
+            .is_err());
+
+        // Tampered digest and signature together still fail without the key
+        let mut forged = ReportSignature::sign(br#"{"summary":{"errors":1}}"#, &key);
+        forged.signature = signature.signature.clone();
+        assert!(forged
+            .verify(br#"{"summary":{"errors":1}}"#, &trusted)
+            .is_err());
+
+        // Signed by someone else
+        let other = SigningKey::from_bytes(&[9; 32]).verifying_key();
+        assert!(signature.verify(report, &other).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Action results for build-system test rules (Bazel, Buck)
+//!
+//! Architecture: Anti-Corruption Layer - A run is reduced to a small JSON file a build rule can read
+//! - The schema is versioned, and holds nothing that differs between runs on the same inputs
+//! - The file is written even when Guardian fails, since build actions must produce their outputs
+//! - Exit codes are fixed: 0 passed, 1 failed, 2 Guardian could not run
+
+use super::ReportFormatter;
+use crate::domain::violations::{
+    GuardianError, GuardianResult, Severity, ValidationReport, ViolationCounts,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Version of the result schema, raised on incompatible changes
+pub const BUILD_RESULT_VERSION: u32 = 1;
+
+/// Number of violations listed in a result
+const TOP_VIOLATIONS: usize = 10;
+
+/// Outcome of a run, as a build rule sees it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildStatus {
+    /// No errors were found
+    Passed,
+    /// Errors were found
+    Failed,
+    /// Guardian could not complete the run
+    Error,
+}
+
+impl BuildStatus {
+    /// Exit code the run ends with
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Passed => 0,
+            Self::Failed => 1,
+            Self::Error => 2,
+        }
+    }
+}
+
+/// One violation of a result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildViolation {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub file: String,
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+/// Small, stable summary of a run for build-system test rules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildResult {
+    pub version: u32,
+    pub status: BuildStatus,
+    pub exit_code: i32,
+    pub files: usize,
+    pub counts: ViolationCounts,
+    /// Whether the time budget ran out before every file was analyzed
+    pub partial: bool,
+    /// Most severe violations first, then by file, line and rule
+    pub top_violations: Vec<BuildViolation>,
+    /// Why the run failed, when its status is `error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BuildResult {
+    /// Result of a run that could not complete
+    pub fn from_error(error: &GuardianError) -> Self {
+        Self {
+            version: BUILD_RESULT_VERSION,
+            status: BuildStatus::Error,
+            exit_code: BuildStatus::Error.exit_code(),
+            files: 0,
+            counts: ViolationCounts::default(),
+            partial: false,
+            top_violations: Vec::new(),
+            error: Some(error.to_string()),
+        }
+    }
+
+    /// Write the result as pretty-printed JSON, creating the parent directory
+    pub fn write(&self, path: &Path) -> GuardianResult<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| GuardianError::io(parent, e))?;
+        }
+        let mut content = serde_json::to_string_pretty(self)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize build result: {e}")))?;
+        content.push('\n');
+        fs::write(path, content).map_err(|e| GuardianError::io(path, e))
+    }
+}
+
+impl ReportFormatter {
+    /// Summarize a report for a build-system test rule
+    ///
+    /// Paths are shown as in other formats. Run times are left out, so the same inputs
+    /// always give the same file.
+    pub fn build_result(&self, report: &ValidationReport) -> BuildResult {
+        let status = if report.has_errors() {
+            BuildStatus::Failed
+        } else {
+            BuildStatus::Passed
+        };
+
+        let mut violations: Vec<BuildViolation> = report
+            .violations
+            .iter()
+            .map(|violation| BuildViolation {
+                rule_id: violation.rule_id.clone(),
+                severity: violation.severity,
+                file: self.display_path(&violation.file_path),
+                line: violation.line_number,
+                message: violation.message.clone(),
+            })
+            .collect();
+        violations.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.rule_id.cmp(&b.rule_id))
+                .then_with(|| a.message.cmp(&b.message))
+        });
+        violations.truncate(TOP_VIOLATIONS);
+
+        BuildResult {
+            version: BUILD_RESULT_VERSION,
+            status,
+            exit_code: status.exit_code(),
+            files: report.summary.total_files,
+            counts: report.summary.violations_by_severity.clone(),
+            partial: report.is_partial(),
+            top_violations: violations,
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::violations::Violation;
+    use crate::report::ReportOptions;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_build_result() {
+        let mut report = ValidationReport::new();
+        for (severity, file, line) in [
+            (Severity::Warning, "src/a.rs", 3),
+            (Severity::Error, "src/b.rs", 9),
+            (Severity::Error, "src/a.rs", 7),
+        ] {
+            report.add_violation(
+                Violation::new("todo_comments", severity, PathBuf::from(file), "found")
+                    .with_position(line, 1),
+            );
+        }
+        report.set_files_analyzed(2);
+        report.set_execution_time(1234);
+
+        let formatter = ReportFormatter::with_options(ReportOptions::default());
+        let result = formatter.build_result(&report);
+        assert_eq!(result.status, BuildStatus::Failed);
+        assert_eq!(result.exit_code, 1);
+        assert_eq!(result.counts.error, 2);
+        let order: Vec<(&str, Option<u32>)> = result
+            .top_violations
+            .iter()
+            .map(|violation| (violation.file.as_str(), violation.line))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("src/a.rs", Some(7)),
+                ("src/b.rs", Some(9)),
+                ("src/a.rs", Some(3))
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out/guardian_result.json");
+        result.write(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["version"], BUILD_RESULT_VERSION);
+        assert!(json.get("error").is_none());
+        assert!(!fs::read_to_string(&path).unwrap().contains("1234"));
+
+        let error = BuildResult::from_error(&GuardianError::config("bad config"));
+        assert_eq!(error.exit_code, 2);
+    }
+}
@@ -0,0 +1,256 @@
+//! Violation history and severity escalation
+//!
+//! Architecture: Anti-Corruption Layer - Runs are remembered in a small JSON store between checks
+//! - Each violation is recorded by fingerprint with the time it was first seen
+//! - A violation resolved in an analyzed file is forgotten, so reintroducing it starts a new age
+//! - Escalation steps raise the severity of violations that stay unresolved, nudging debt down
+
+use super::{absolute_path, normalize_separators};
+use crate::config::EscalationConfig;
+use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport, Violation};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// When each known violation was first seen, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ViolationHistory {
+    /// Known violations by fingerprint
+    violations: BTreeMap<String, HistoryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    /// File of the violation, relative to the project root
+    file_path: String,
+    first_seen: DateTime<Utc>,
+}
+
+impl ViolationHistory {
+    /// Load the history from a file, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> GuardianResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            GuardianError::config(format!(
+                "Invalid violation history '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Write the history to a file, creating its directory
+    pub fn save(&self, path: &Path) -> GuardianResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| GuardianError::io(parent, e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            GuardianError::config(format!("Failed to serialize violation history: {e}"))
+        })?;
+        fs::write(path, content).map_err(|e| GuardianError::io(path, e))
+    }
+
+    /// Record the violations of a run
+    ///
+    /// New violations are first seen `now`. Known violations missing from an analyzed file
+    /// were resolved and are forgotten; files the run did not analyze keep their history.
+    pub fn record(&mut self, report: &ValidationReport, root: &Path, now: DateTime<Utc>) {
+        let analyzed: HashSet<String> = report
+            .summary
+            .analyzed_files
+            .iter()
+            .map(|file| relative_path(file, root))
+            .collect();
+        let mut current = HashSet::new();
+        for violation in &report.violations {
+            let (fingerprint, file_path) = key(violation, root);
+            self.violations
+                .entry(fingerprint.clone())
+                .or_insert(HistoryEntry {
+                    file_path,
+                    first_seen: now,
+                });
+            current.insert(fingerprint);
+        }
+
+        self.violations.retain(|fingerprint, entry| {
+            current.contains(fingerprint) || !analyzed.contains(&entry.file_path)
+        });
+    }
+
+    /// Whole days since a violation was first seen, if it is known
+    pub fn age_days(&self, violation: &Violation, root: &Path, now: DateTime<Utc>) -> Option<i64> {
+        let (fingerprint, _) = key(violation, root);
+        self.violations
+            .get(&fingerprint)
+            .map(|entry| (now - entry.first_seen).num_days())
+    }
+
+    /// Number of known violations
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Whether no violations are known
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Raise the severity of violations known for longer than the escalation steps allow
+///
+/// Returns the number of violations whose severity was raised.
+pub fn escalate(
+    report: &mut ValidationReport,
+    history: &ViolationHistory,
+    escalation: &EscalationConfig,
+    root: &Path,
+    now: DateTime<Utc>,
+) -> usize {
+    let mut escalated = 0;
+    report.update_severities(|violation| {
+        let raised = history
+            .age_days(violation, root, now)
+            .and_then(|age| escalation.severity_for(&violation.rule_id, age))
+            .filter(|&severity| severity > violation.severity);
+        match raised {
+            Some(severity) => {
+                escalated += 1;
+                severity
+            }
+            None => violation.severity,
+        }
+    });
+    escalated
+}
+
+/// Fingerprint of a violation with its path relative to the project root
+///
+/// Runs started from different directories, or with different path arguments, agree on
+/// this fingerprint, so it can key state kept between runs.
+pub fn project_fingerprint(violation: &Violation, root: &Path) -> String {
+    key(violation, root).0
+}
+
+/// Project fingerprint and root-relative path of a violation
+fn key(violation: &Violation, root: &Path) -> (String, String) {
+    let file_path = relative_path(&violation.file_path, root);
+    let mut relative = violation.clone();
+    relative.file_path = PathBuf::from(&file_path);
+    (relative.fingerprint(), file_path)
+}
+
+/// Path of a violation's file relative to the project root, with `/` separators
+pub(crate) fn relative_path(path: &Path, root: &Path) -> String {
+    let absolute = absolute_path(path);
+    let relative = absolute.strip_prefix(absolute_path(root)).unwrap_or(path);
+    normalize_separators(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EscalationStep;
+    use crate::domain::violations::Severity;
+    use chrono::Duration;
+
+    fn report(messages: &[(&str, &str)]) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (file, message) in messages {
+            report.add_violation(Violation::new(
+                "todo_comments",
+                Severity::Info,
+                PathBuf::from(file),
+                *message,
+            ));
+        }
+        report.set_analyzed_files(vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")]);
+        report
+    }
+
+    #[test]
+    fn test_history_ages_and_forgets() {
+        let root = Path::new(".");
+        let start = Utc::now() - Duration::days(40);
+        let mut history = ViolationHistory::default();
+        history.record(
+            &report(&[("src/a.rs", "TODO: old"), ("src/b.rs", "TODO: fixed")]),
+            root,
+            start,
+        );
+
+        // Later, one TODO is fixed and another added; the old one keeps its first-seen time
+        let now = Utc::now();
+        let later = report(&[("./src/a.rs", "TODO: old"), ("src/a.rs", "TODO: new")]);
+        history.record(&later, root, now);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.age_days(&later.violations[0], root, now), Some(40));
+        assert_eq!(history.age_days(&later.violations[1], root, now), Some(0));
+    }
+
+    #[test]
+    fn test_unanalyzed_files_keep_history() {
+        let root = Path::new(".");
+        let mut history = ViolationHistory::default();
+        history.record(
+            &report(&[("src/c.rs", "TODO: elsewhere")]),
+            root,
+            Utc::now(),
+        );
+        history.record(&report(&[]), root, Utc::now());
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_escalation_steps() {
+        let root = Path::new(".");
+        let escalation = EscalationConfig {
+            history_file: None,
+            steps: vec![
+                EscalationStep {
+                    after_days: 30,
+                    severity: Severity::Warning,
+                    rules: vec!["todo_comments".to_string()],
+                },
+                EscalationStep {
+                    after_days: 90,
+                    severity: Severity::Error,
+                    rules: Vec::new(),
+                },
+            ],
+        };
+
+        let now = Utc::now();
+        let mut history = ViolationHistory::default();
+        history.record(
+            &report(&[("src/a.rs", "TODO: ancient")]),
+            root,
+            now - Duration::days(100),
+        );
+        history.record(
+            &report(&[("src/a.rs", "TODO: ancient"), ("src/b.rs", "TODO: aging")]),
+            root,
+            now - Duration::days(45),
+        );
+
+        let mut current = report(&[
+            ("src/a.rs", "TODO: ancient"),
+            ("src/b.rs", "TODO: aging"),
+            ("src/b.rs", "TODO: fresh"),
+        ]);
+        assert_eq!(escalate(&mut current, &history, &escalation, root, now), 2);
+
+        let severities: Vec<Severity> = current.violations.iter().map(|v| v.severity).collect();
+        assert_eq!(
+            severities,
+            vec![Severity::Error, Severity::Warning, Severity::Info]
+        );
+        assert_eq!(current.summary.violations_by_severity.error, 1);
+        assert_eq!(current.summary.violations_by_severity.info, 1);
+    }
+}
@@ -0,0 +1,268 @@
+//! Markdown job summaries for GitHub Actions
+//!
+//! Architecture: Anti-Corruption Layer - A run is rendered for the `$GITHUB_STEP_SUMMARY` page
+//! - Counts and the noisiest rules give reviewers the shape of a run at a glance
+//! - A baseline report separates findings a change introduced from those already known
+//! - Findings are matched by fingerprint, so shifted lines do not count as new findings
+
+use super::ReportFormatter;
+use crate::domain::violations::{
+    GuardianError, GuardianResult, Severity, ValidationReport, Violation,
+};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of rules listed under "Top rules"
+const TOP_RULES: usize = 5;
+
+/// Number of new findings listed individually
+const NEW_FINDINGS_SHOWN: usize = 20;
+
+/// Findings of an earlier run, loaded from a report written with `--format json`
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// File of each finding, by fingerprint
+    fingerprints: HashMap<String, PathBuf>,
+}
+
+impl Baseline {
+    /// Load a baseline from a JSON report file
+    pub fn load(path: &Path) -> GuardianResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+        Self::from_json_report(&content).map_err(|e| {
+            GuardianError::config(format!("Invalid baseline '{}': {e}", path.display()))
+        })
+    }
+
+    /// Parse the violations of a JSON report
+    pub fn from_json_report(content: &str) -> GuardianResult<Self> {
+        let json: JsonValue = serde_json::from_str(content)
+            .map_err(|e| GuardianError::config(format!("not a JSON report: {e}")))?;
+        let violations = json
+            .get("violations")
+            .and_then(JsonValue::as_array)
+            .ok_or_else(|| GuardianError::config("report has no violations array"))?;
+
+        let field = |violation: &JsonValue, name: &str| {
+            violation
+                .get(name)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+        };
+
+        let mut fingerprints = HashMap::new();
+        for violation in violations {
+            let (rule_id, file_path, message) = match (
+                field(violation, "rule_id"),
+                field(violation, "file_path"),
+                field(violation, "message"),
+            ) {
+                (Some(rule_id), Some(file_path), Some(message)) => (rule_id, file_path, message),
+                _ => {
+                    return Err(GuardianError::config(
+                        "violation is missing rule_id, file_path or message",
+                    ))
+                }
+            };
+            let file_path = PathBuf::from(file_path);
+            let mut finding = Violation::new(rule_id, Severity::Info, file_path.clone(), message);
+            finding.context = field(violation, "context");
+            fingerprints.insert(finding.fingerprint(), file_path);
+        }
+
+        Ok(Self { fingerprints })
+    }
+
+    /// Keep only findings in files the current run covers
+    ///
+    /// Findings in files outside the run's scope, or left out by a sparse checkout, were not
+    /// looked for and must not count as fixed.
+    pub fn retain_files(&mut self, mut in_scope: impl FnMut(&Path) -> bool) {
+        self.fingerprints.retain(|_, file_path| in_scope(file_path));
+    }
+
+    /// Number of findings in the baseline
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Whether the baseline has no findings
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+}
+
+impl ReportFormatter {
+    /// Format a markdown summary of a run for the GitHub Actions job summary
+    ///
+    /// Covers counts by severity, the rules with the most findings and, given a baseline,
+    /// how many findings are new, still present and fixed. Severity filtering and output
+    /// limits apply as in other formats.
+    pub fn format_job_summary(
+        &self,
+        report: &ValidationReport,
+        baseline: Option<&Baseline>,
+    ) -> String {
+        let filtered = self.filter_violations(&report.violations);
+        let violations = &filtered.violations;
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        let (errors, warnings, infos) = (
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info),
+        );
+
+        let status = if errors > 0 { "❌" } else { "✅" };
+        let mut output = format!(
+            "## {status} Rust Guardian\n\n\
+             {errors} error(s), {warnings} warning(s) and {infos} info finding(s) in {} file(s) ({} ms)\n\n\
+             | Severity | Findings |\n\
+             |---|---|\n\
+             | error | {errors} |\n\
+             | warning | {warnings} |\n\
+             | info | {infos} |\n",
+            report.summary.total_files, report.summary.execution_time_ms
+        );
+
+        let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+        for violation in violations {
+            *by_rule.entry(violation.rule_id.as_str()).or_default() += 1;
+        }
+        let mut top_rules: Vec<(&str, usize)> = by_rule.into_iter().collect();
+        top_rules.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        if !top_rules.is_empty() {
+            output.push_str("\n### Top rules\n\n| Rule | Findings |\n|---|---|\n");
+            for (rule_id, findings) in top_rules.iter().take(TOP_RULES) {
+                output.push_str(&format!("| `{rule_id}` | {findings} |\n"));
+            }
+        }
+
+        if let Some(baseline) = baseline {
+            // Baseline reports store displayed paths, so fingerprint the same form
+            let fingerprints: Vec<(String, &Violation)> = violations
+                .iter()
+                .map(|v| {
+                    let mut displayed = (*v).clone();
+                    displayed.file_path = PathBuf::from(self.display_path(&v.file_path));
+                    (displayed.fingerprint(), *v)
+                })
+                .collect();
+            let current: HashSet<&str> = fingerprints.iter().map(|(f, _)| f.as_str()).collect();
+            let new: Vec<&Violation> = fingerprints
+                .iter()
+                .filter(|(fingerprint, _)| !baseline.fingerprints.contains_key(fingerprint))
+                .map(|(_, v)| *v)
+                .collect();
+            let fixed = baseline
+                .fingerprints
+                .keys()
+                .filter(|fingerprint| !current.contains(&fingerprint.as_str()))
+                .count();
+
+            output.push_str(&format!(
+                "\n### Compared with baseline\n\n\
+                 🆕 {} new, ♻️ {} still present, ✅ {fixed} fixed\n",
+                new.len(),
+                violations.len() - new.len()
+            ));
+            if !new.is_empty() {
+                output.push_str(
+                    "\n| Severity | File | Line | Rule | Message |\n|---|---|---|---|---|\n",
+                );
+                for violation in new.iter().take(NEW_FINDINGS_SHOWN) {
+                    output.push_str(&summary_row(self, violation));
+                }
+                if new.len() > NEW_FINDINGS_SHOWN {
+                    output.push_str(&format!(
+                        "\n…and {} more new finding(s)\n",
+                        new.len() - NEW_FINDINGS_SHOWN
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// One markdown table row for a finding
+pub(super) fn summary_row(formatter: &ReportFormatter, violation: &Violation) -> String {
+    format!(
+        "| {} | `{}` | {} | `{}` | {} |\n",
+        violation.severity.as_str(),
+        formatter.display_path(&violation.file_path),
+        violation
+            .line_number
+            .map_or(String::new(), |line| line.to_string()),
+        violation.rule_id,
+        violation.message.replace('|', "\\|")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{OutputFormat, ReportOptions};
+
+    fn report() -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (rule_id, line, context) in [
+            ("todo_comments", 3, "// TODO: old"),
+            ("todo_comments", 9, "// TODO: new"),
+            ("unwrap_calls", 12, "value.unwrap()"),
+        ] {
+            report.add_violation(
+                Violation::new(
+                    rule_id,
+                    Severity::Error,
+                    PathBuf::from("src/lib.rs"),
+                    "found",
+                )
+                .with_position(line, 1)
+                .with_context(context),
+            );
+        }
+        report.set_files_analyzed(4);
+        report
+    }
+
+    #[test]
+    fn test_job_summary_against_baseline() {
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            use_colors: false,
+            ..Default::default()
+        });
+
+        // The baseline knows the old TODO and a finding that has since been fixed
+        let mut previous = report();
+        previous.violations.remove(1);
+        previous.violations[1].context = Some("other.unwrap()".to_string());
+        let json = formatter
+            .format_report(&previous, OutputFormat::Json)
+            .expect("JSON format should succeed");
+        let baseline = Baseline::from_json_report(&json).expect("Failed to parse baseline");
+        assert_eq!(baseline.len(), 2);
+
+        let summary = formatter.format_job_summary(&report(), Some(&baseline));
+        assert!(summary.starts_with("## ❌ Rust Guardian"));
+        assert!(summary.contains("3 error(s), 0 warning(s) and 0 info finding(s) in 4 file(s)"));
+        assert!(summary.contains("| `todo_comments` | 2 |\n| `unwrap_calls` | 1 |"));
+        assert!(summary.contains("🆕 2 new, ♻️ 1 still present, ✅ 1 fixed"));
+        assert!(summary.contains("| error | `src/lib.rs` | 9 | `todo_comments` | found |"));
+        assert!(!summary.contains("| 3 | `todo_comments`"));
+
+        // Findings in files the run did not cover are not fixed
+        let mut scoped = baseline.clone();
+        scoped.retain_files(|file| file != Path::new("src/lib.rs"));
+        assert!(scoped.is_empty());
+        let summary = formatter.format_job_summary(&report(), Some(&scoped));
+        assert!(summary.contains("🆕 3 new, ♻️ 0 still present, ✅ 0 fixed"));
+
+        assert!(Baseline::from_json_report("{}").is_err());
+        assert!(!formatter
+            .format_job_summary(&report(), None)
+            .contains("baseline"));
+    }
+}
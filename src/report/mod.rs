@@ -5,9 +5,18 @@
 //! - Each formatter encapsulates the rules for its specific output format
 //! - Domain logic remains pure while supporting multiple presentation needs
 
+pub mod attestation;
+pub mod build_result;
+pub mod history;
+pub mod job_summary;
+pub mod rulebook;
+pub mod simulation;
+
 use crate::domain::violations::{GuardianResult, Severity, ValidationReport, Violation};
 use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
+use std::path::{Component, Path, PathBuf};
 
 /// Supported output formats for validation reports
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +33,18 @@ pub enum OutputFormat {
     GitHub,
     /// Agent-friendly format for easy parsing: [line:path] <violation>
     Agent,
+    /// One JSON object per line with stable fingerprints and remediation hints
+    JsonLines,
+    /// Comma-separated values, one row per violation, for spreadsheets and BI tools
+    Csv,
+    /// `cargo --message-format=json` diagnostics for editors that already parse cargo output
+    CargoJson,
+    /// Azure DevOps `##vso[task.logissue]` logging commands
+    AzureDevOps,
+    /// Bitbucket Code Insights report and annotations
+    Bitbucket,
+    /// Reviewdog Diagnostic Format, for `reviewdog -f=rdjson`
+    Rdjson,
 }
 
 use std::str::FromStr;
@@ -40,6 +61,12 @@ impl FromStr for OutputFormat {
             "sarif" => Ok(Self::Sarif),
             "github" => Ok(Self::GitHub),
             "agent" => Ok(Self::Agent),
+            "jsonl" | "json-lines" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            "cargo-json" | "cargo" => Ok(Self::CargoJson),
+            "azure-devops" | "azure" => Ok(Self::AzureDevOps),
+            "bitbucket" => Ok(Self::Bitbucket),
+            "rdjson" => Ok(Self::Rdjson),
             _ => Err(format!("Unknown output format: {s}")),
         }
     }
@@ -48,7 +75,20 @@ impl FromStr for OutputFormat {
 impl OutputFormat {
     /// Get all available format names
     pub fn all_formats() -> &'static [&'static str] {
-        &["human", "json", "junit", "sarif", "github", "agent"]
+        &[
+            "human",
+            "json",
+            "junit",
+            "sarif",
+            "github",
+            "agent",
+            "jsonl",
+            "csv",
+            "cargo-json",
+            "azure-devops",
+            "bitbucket",
+            "rdjson",
+        ]
     }
 
     /// Validate that this format is appropriate for the given context
@@ -61,7 +101,10 @@ impl OutputFormat {
                 // This is a warning, not an error
                 Ok(())
             }
-            (Self::Junit | Self::GitHub | Self::Sarif, false) => {
+            (
+                Self::Junit | Self::GitHub | Self::Sarif | Self::AzureDevOps | Self::Bitbucket,
+                false,
+            ) => {
                 // CI formats in interactive use are less optimal but valid
                 Ok(())
             }
@@ -76,10 +119,113 @@ impl OutputFormat {
 
     /// Check if this format produces structured data
     pub fn is_structured(&self) -> bool {
-        matches!(self, Self::Json | Self::Sarif | Self::Junit)
+        matches!(
+            self,
+            Self::Json
+                | Self::Sarif
+                | Self::Junit
+                | Self::Csv
+                | Self::CargoJson
+                | Self::Bitbucket
+                | Self::Rdjson
+        )
+    }
+}
+
+/// A column of the CSV violation export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumn {
+    File,
+    Line,
+    Column,
+    EndLine,
+    EndColumn,
+    Severity,
+    Rule,
+    Category,
+    Message,
+    Context,
+    Tags,
+    DocsUrl,
+    SuggestedFix,
+    Fingerprint,
+}
+
+impl CsvColumn {
+    /// Columns exported when none are configured
+    pub const DEFAULT: &'static [CsvColumn] = &[
+        Self::File,
+        Self::Line,
+        Self::Column,
+        Self::Severity,
+        Self::Rule,
+        Self::Category,
+        Self::Message,
+    ];
+
+    /// Get the header name of this column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Line => "line",
+            Self::Column => "column",
+            Self::EndLine => "end_line",
+            Self::EndColumn => "end_column",
+            Self::Severity => "severity",
+            Self::Rule => "rule",
+            Self::Category => "category",
+            Self::Message => "message",
+            Self::Context => "context",
+            Self::Tags => "tags",
+            Self::DocsUrl => "docs_url",
+            Self::SuggestedFix => "suggested_fix",
+            Self::Fingerprint => "fingerprint",
+        }
+    }
+
+    /// Parse a list of column names, rejecting unknown names
+    pub fn parse_list<S: AsRef<str>>(names: &[S]) -> GuardianResult<Vec<Self>> {
+        names
+            .iter()
+            .map(|name| {
+                name.as_ref()
+                    .parse()
+                    .map_err(|e: String| crate::domain::violations::GuardianError::config(e))
+            })
+            .collect()
+    }
+}
+
+impl FromStr for CsvColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "file" => Ok(Self::File),
+            "line" => Ok(Self::Line),
+            "column" => Ok(Self::Column),
+            "end_line" => Ok(Self::EndLine),
+            "end_column" => Ok(Self::EndColumn),
+            "severity" => Ok(Self::Severity),
+            "rule" | "rule_id" => Ok(Self::Rule),
+            "category" => Ok(Self::Category),
+            "message" => Ok(Self::Message),
+            "context" => Ok(Self::Context),
+            "tags" => Ok(Self::Tags),
+            "docs_url" => Ok(Self::DocsUrl),
+            "suggested_fix" => Ok(Self::SuggestedFix),
+            "fingerprint" => Ok(Self::Fingerprint),
+            _ => Err(format!("Unknown CSV column: {s}")),
+        }
     }
 }
 
+/// Annotations GitHub Actions shows per severity level in one step
+pub const GITHUB_ANNOTATION_LIMIT: usize = 10;
+
+/// Annotations Bitbucket Code Insights keeps per report
+pub const BITBUCKET_ANNOTATION_LIMIT: usize = 1000;
+
 /// Options for customizing report output
 #[derive(Debug, Clone)]
 pub struct ReportOptions {
@@ -87,12 +233,35 @@ pub struct ReportOptions {
     pub use_colors: bool,
     /// Whether to show context lines around violations
     pub show_context: bool,
+    /// Number of source lines to show above and below a violation in code frames
+    pub context_lines: usize,
     /// Whether to show violation suggestions
     pub show_suggestions: bool,
+    /// Whether to show the rule rationale under each violation in human output
+    pub explain: bool,
+    /// Whether to show the rationale, category, tags and documentation link under each
+    /// violation in human output
+    pub verbose_violations: bool,
     /// Maximum number of violations to include
     pub max_violations: Option<usize>,
+    /// Maximum number of violations to include per rule
+    pub max_per_rule: Option<usize>,
+    /// Maximum number of violations to include per file
+    pub max_per_file: Option<usize>,
     /// Minimum severity level to include
     pub min_severity: Option<Severity>,
+    /// Directory that reported paths are made relative to (defaults to the current directory)
+    pub path_base: Option<PathBuf>,
+    /// Columns of the CSV export, in order
+    pub csv_columns: Vec<CsvColumn>,
+}
+
+/// Violations selected for output, with counts of those hidden by per-rule and per-file limits
+#[derive(Debug, Default)]
+struct FilteredViolations<'a> {
+    violations: Vec<&'a Violation>,
+    hidden_by_rule: BTreeMap<&'a str, usize>,
+    hidden_by_file: BTreeMap<&'a Path, usize>,
 }
 
 impl Default for ReportOptions {
@@ -100,9 +269,16 @@ impl Default for ReportOptions {
         Self {
             use_colors: true,
             show_context: true,
+            context_lines: 2,
             show_suggestions: true,
+            explain: false,
+            verbose_violations: false,
             max_violations: None,
+            max_per_rule: None,
+            max_per_file: None,
             min_severity: None,
+            path_base: None,
+            csv_columns: CsvColumn::DEFAULT.to_vec(),
         }
     }
 }
@@ -126,6 +302,18 @@ impl ReportOptions {
             }
         }
 
+        if self.max_per_rule == Some(0) || self.max_per_file == Some(0) {
+            return Err(crate::domain::violations::GuardianError::config(
+                "max_per_rule and max_per_file cannot be zero - disable the rule instead",
+            ));
+        }
+
+        if self.csv_columns.is_empty() {
+            return Err(crate::domain::violations::GuardianError::config(
+                "csv_columns cannot be empty - omit it to use the default columns",
+            ));
+        }
+
         // Validate severity consistency
         if let Some(min_severity) = self.min_severity {
             if min_severity > Severity::Error {
@@ -142,7 +330,11 @@ impl ReportOptions {
     pub fn is_optimized_for(&self, format: OutputFormat) -> bool {
         match format {
             OutputFormat::Human => true, // Human format supports all options
-            OutputFormat::Json | OutputFormat::Sarif => {
+            OutputFormat::Json
+            | OutputFormat::Sarif
+            | OutputFormat::Csv
+            | OutputFormat::Bitbucket
+            | OutputFormat::Rdjson => {
                 // Structured formats don't use colors or context display
                 !self.use_colors && !self.show_context
             }
@@ -150,14 +342,18 @@ impl ReportOptions {
                 // JUnit mainly cares about violations, not display options
                 !self.use_colors
             }
-            OutputFormat::GitHub => {
-                // GitHub format doesn't use colors or suggestions
+            OutputFormat::GitHub | OutputFormat::AzureDevOps => {
+                // CI logging commands don't use colors or suggestions
                 !self.use_colors && !self.show_suggestions
             }
             OutputFormat::Agent => {
                 // Agent format is minimal
                 !self.use_colors && !self.show_context && !self.show_suggestions
             }
+            OutputFormat::JsonLines | OutputFormat::CargoJson => {
+                // JSON lines carry remediation hints but never display context
+                !self.use_colors && !self.show_context && self.show_suggestions
+            }
         }
     }
 
@@ -165,7 +361,11 @@ impl ReportOptions {
     pub fn optimized_for(format: OutputFormat) -> Self {
         match format {
             OutputFormat::Human => Self::default(),
-            OutputFormat::Json | OutputFormat::Sarif => Self {
+            OutputFormat::Json
+            | OutputFormat::Sarif
+            | OutputFormat::Csv
+            | OutputFormat::Bitbucket
+            | OutputFormat::Rdjson => Self {
                 use_colors: false,
                 show_context: false,
                 show_suggestions: false,
@@ -176,7 +376,7 @@ impl ReportOptions {
                 show_suggestions: false,
                 ..Self::default()
             },
-            OutputFormat::GitHub => Self {
+            OutputFormat::GitHub | OutputFormat::AzureDevOps => Self {
                 use_colors: false,
                 show_suggestions: false,
                 ..Self::default()
@@ -187,6 +387,19 @@ impl ReportOptions {
                 show_suggestions: false,
                 ..Self::default()
             },
+            OutputFormat::JsonLines | OutputFormat::CargoJson => Self::agent_mode(),
+        }
+    }
+
+    /// Defaults for autonomous agents consuming JSON-lines output
+    ///
+    /// No colors or context frames; remediation hints are kept since agents act on them.
+    pub fn agent_mode() -> Self {
+        Self {
+            use_colors: false,
+            show_context: false,
+            show_suggestions: true,
+            ..Self::default()
         }
     }
 }
@@ -277,7 +490,15 @@ impl ReportFormatter {
             OutputFormat::Json => self.validate_json_structure(output),
             OutputFormat::Junit => self.validate_junit_structure(output),
             OutputFormat::Sarif => self.validate_sarif_structure(output),
-            OutputFormat::Human | OutputFormat::GitHub | OutputFormat::Agent => {
+            OutputFormat::JsonLines => self.validate_json_lines_structure(output),
+            OutputFormat::CargoJson => self.validate_cargo_json_structure(output),
+            OutputFormat::Csv => self.validate_csv_structure(output),
+            OutputFormat::Bitbucket => self.validate_bitbucket_structure(output),
+            OutputFormat::Rdjson => self.validate_rdjson_structure(output),
+            OutputFormat::Human
+            | OutputFormat::GitHub
+            | OutputFormat::Agent
+            | OutputFormat::AzureDevOps => {
                 // Text formats have basic structure validation
                 if output.is_empty() && !report.violations.is_empty() {
                     return Err(crate::domain::violations::GuardianError::config(
@@ -289,7 +510,106 @@ impl ReportFormatter {
         }
     }
 
+    /// Validate CSV output structure
+    fn validate_csv_structure(&self, output: &str) -> GuardianResult<()> {
+        let header = csv_row(
+            self.options
+                .csv_columns
+                .iter()
+                .map(|c| c.as_str().to_string()),
+        );
+        if !output.starts_with(&header) {
+            return Err(crate::domain::violations::GuardianError::config(
+                "CSV output is missing its header row",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate cargo JSON output structure
+    fn validate_cargo_json_structure(&self, output: &str) -> GuardianResult<()> {
+        let mut last_reason = None;
+        for line in output.lines() {
+            let json: JsonValue = serde_json::from_str(line).map_err(|e| {
+                crate::domain::violations::GuardianError::config(format!(
+                    "Invalid cargo JSON message: {e}"
+                ))
+            })?;
+            last_reason = json
+                .get("reason")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string);
+        }
+
+        if last_reason.as_deref() != Some("build-finished") {
+            return Err(crate::domain::violations::GuardianError::config(
+                "Cargo JSON output must end with a build-finished message",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate Bitbucket Code Insights output structure
+    fn validate_bitbucket_structure(&self, output: &str) -> GuardianResult<()> {
+        let json: JsonValue = serde_json::from_str(output).map_err(|e| {
+            crate::domain::violations::GuardianError::config(format!("Invalid Bitbucket JSON: {e}"))
+        })?;
+
+        if json
+            .get("report")
+            .is_none_or(|report| report.get("result").is_none())
+        {
+            return Err(crate::domain::violations::GuardianError::config(
+                "Bitbucket output must contain a report with a result",
+            ));
+        }
+        if !json.get("annotations").is_some_and(JsonValue::is_array) {
+            return Err(crate::domain::violations::GuardianError::config(
+                "Bitbucket output must contain an annotations array",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate Reviewdog Diagnostic Format output structure
+    fn validate_rdjson_structure(&self, output: &str) -> GuardianResult<()> {
+        let json: JsonValue = serde_json::from_str(output).map_err(|e| {
+            crate::domain::violations::GuardianError::config(format!("Invalid rdjson: {e}"))
+        })?;
+
+        if !json.get("diagnostics").is_some_and(JsonValue::is_array) {
+            return Err(crate::domain::violations::GuardianError::config(
+                "rdjson output must contain a diagnostics array",
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Validate JSON output structure
+    fn validate_json_lines_structure(&self, output: &str) -> GuardianResult<()> {
+        let mut last_type = None;
+        for line in output.lines() {
+            let json: JsonValue = serde_json::from_str(line).map_err(|e| {
+                crate::domain::violations::GuardianError::config(format!("Invalid JSON line: {e}"))
+            })?;
+            last_type = json
+                .get("type")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string);
+        }
+
+        if last_type.as_deref() != Some("summary") {
+            return Err(crate::domain::violations::GuardianError::config(
+                "JSON lines output must end with a summary line",
+            ));
+        }
+
+        Ok(())
+    }
+
     fn validate_json_structure(&self, output: &str) -> GuardianResult<()> {
         let json: JsonValue = serde_json::from_str(output).map_err(|e| {
             crate::domain::violations::GuardianError::config(format!("Invalid JSON structure: {e}"))
@@ -366,15 +686,22 @@ impl ReportFormatter {
         self.validate_capabilities()?;
 
         // Filter violations based on options
-        let filtered_violations = self.filter_violations(&report.violations);
+        let filtered = self.filter_violations(&report.violations);
+        let filtered_violations = &filtered.violations;
 
         let output = match format {
-            OutputFormat::Human => self.format_human(report, &filtered_violations),
-            OutputFormat::Json => self.format_json(report, &filtered_violations),
-            OutputFormat::Junit => self.format_junit(report, &filtered_violations),
-            OutputFormat::Sarif => self.format_sarif(report, &filtered_violations),
-            OutputFormat::GitHub => self.format_github(report, &filtered_violations),
-            OutputFormat::Agent => self.format_agent(report, &filtered_violations),
+            OutputFormat::Human => self.format_human(report, &filtered),
+            OutputFormat::Json => self.format_json(report, &filtered),
+            OutputFormat::Junit => self.format_junit(report, filtered_violations),
+            OutputFormat::Sarif => self.format_sarif(report, filtered_violations),
+            OutputFormat::GitHub => self.format_github(report, filtered_violations),
+            OutputFormat::Agent => self.format_agent(report, filtered_violations),
+            OutputFormat::JsonLines => self.format_json_lines(report, &filtered),
+            OutputFormat::Csv => self.format_csv(filtered_violations),
+            OutputFormat::CargoJson => self.format_cargo_json(report, filtered_violations),
+            OutputFormat::AzureDevOps => self.format_azure_devops(report, filtered_violations),
+            OutputFormat::Bitbucket => self.format_bitbucket(report, filtered_violations),
+            OutputFormat::Rdjson => self.format_rdjson(filtered_violations),
         }?;
 
         // Validate output integrity before returning
@@ -391,41 +718,97 @@ impl ReportFormatter {
         mut writer: W,
     ) -> GuardianResult<()> {
         let formatted = self.format_report(report, format)?;
-        writer
-            .write_all(formatted.as_bytes())
-            .map_err(|e| crate::domain::violations::GuardianError::Io { source: e })?;
+        writer.write_all(formatted.as_bytes())?;
         Ok(())
     }
 
     /// Filter violations based on report options
-    fn filter_violations<'a>(&self, violations: &'a [Violation]) -> Vec<&'a Violation> {
-        let mut filtered: Vec<&Violation> = violations
-            .iter()
-            .filter(|v| {
-                // Filter by minimum severity
-                if let Some(min_severity) = self.options.min_severity {
-                    if v.severity < min_severity {
-                        return false;
-                    }
+    ///
+    /// Per-rule limits apply before per-file limits, and both before `max_violations`.
+    /// A violation hidden by the per-file limit still uses up its rule's allowance.
+    /// Violations hidden by the per-rule and per-file limits are counted so reports can
+    /// say how many were left out.
+    fn filter_violations<'a>(&self, violations: &'a [Violation]) -> FilteredViolations<'a> {
+        let mut filtered = FilteredViolations::default();
+        let mut shown_by_rule: HashMap<&str, usize> = HashMap::new();
+        let mut shown_by_file: HashMap<&Path, usize> = HashMap::new();
+
+        for v in violations {
+            // Filter by minimum severity
+            if let Some(min_severity) = self.options.min_severity {
+                if v.severity < min_severity {
+                    continue;
                 }
-                true
-            })
-            .collect();
+            }
+
+            let rule_count = shown_by_rule.entry(v.rule_id.as_str()).or_default();
+            if self
+                .options
+                .max_per_rule
+                .is_some_and(|max| *rule_count >= max)
+            {
+                *filtered
+                    .hidden_by_rule
+                    .entry(v.rule_id.as_str())
+                    .or_default() += 1;
+                continue;
+            }
+            // A violation hidden by the file limit still counts towards its rule's limit
+            *rule_count += 1;
+
+            let file_count = shown_by_file.entry(v.file_path.as_path()).or_default();
+            if self
+                .options
+                .max_per_file
+                .is_some_and(|max| *file_count >= max)
+            {
+                *filtered
+                    .hidden_by_file
+                    .entry(v.file_path.as_path())
+                    .or_default() += 1;
+                continue;
+            }
+
+            *file_count += 1;
+            filtered.violations.push(v);
+        }
 
         // Limit number of violations if requested
         if let Some(max) = self.options.max_violations {
-            filtered.truncate(max);
+            filtered.violations.truncate(max);
         }
 
         filtered
     }
 
+    /// Render a path for output
+    ///
+    /// Paths inside the path base are made relative to it, and separators are always
+    /// forward slashes so SARIF URIs and CI annotations resolve on every platform.
+    fn display_path(&self, path: &Path) -> String {
+        let absolute = absolute_path(path);
+        let base = self
+            .options
+            .path_base
+            .as_deref()
+            .map(absolute_path)
+            .or_else(|| std::env::current_dir().ok());
+
+        let relative = base
+            .as_deref()
+            .and_then(|base| absolute.strip_prefix(base).ok())
+            .unwrap_or(path);
+
+        normalize_separators(relative)
+    }
+
     /// Format report in human-readable format
     fn format_human(
         &self,
         report: &ValidationReport,
-        violations: &[&Violation],
+        filtered: &FilteredViolations,
     ) -> GuardianResult<String> {
+        let violations = &filtered.violations;
         let mut output = String::new();
 
         if violations.is_empty() {
@@ -459,7 +842,16 @@ impl ReportFormatter {
 
             // Display each file's violations
             for (file_path, file_violations) in by_file {
-                output.push_str(&format!("📁 {}\n", file_path.display()));
+                output.push_str(&format!("📁 {}\n", self.display_path(file_path)));
+
+                // Source is only needed for code frames; a missing file falls back to plain context
+                let source = if self.options.show_context {
+                    crate::analyzer::encoding::read_source(file_path, true)
+                        .ok()
+                        .map(|source| source.content)
+                } else {
+                    None
+                };
 
                 for violation in file_violations {
                     // Format violation with colors
@@ -495,8 +887,22 @@ impl ReportFormatter {
                         ));
                     }
 
-                    // Show context if available and requested
-                    if self.options.show_context {
+                    // Show a code frame when the exact span is known, plain context otherwise
+                    let frame = match (&source, &violation.byte_range) {
+                        (Some(source), Some(range)) if self.options.show_context => {
+                            render_code_frame(
+                                source,
+                                range.clone(),
+                                self.options.context_lines,
+                                self.options.use_colors,
+                            )
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(frame) = frame {
+                        output.push_str(&frame);
+                    } else if self.options.show_context {
                         if let Some(context) = &violation.context {
                             if self.options.use_colors {
                                 output.push_str(&format!("    \x1b[2m│ {context}\x1b[0m\n"));
@@ -506,8 +912,37 @@ impl ReportFormatter {
                         }
                     }
 
+                    // A tracked violation points at the issue where its fix is planned
+                    if let Some(issue_url) = &violation.issue_url {
+                        if self.options.use_colors {
+                            output.push_str(&format!("    \x1b[2m🔗 {issue_url}\x1b[0m\n"));
+                        } else {
+                            output.push_str(&format!("    🔗 {issue_url}\n"));
+                        }
+                    }
+
+                    // Explain why the rule exists, for readers who do not know it yet
+                    let verbose = self.options.verbose_violations;
+                    if self.options.explain || verbose {
+                        if let Some(rationale) = &violation.rationale {
+                            if self.options.use_colors {
+                                output.push_str(&format!("    \x1b[2m❔ {rationale}\x1b[0m\n"));
+                            } else {
+                                output.push_str(&format!("    ❔ {rationale}\n"));
+                            }
+                        }
+                    }
+                    if verbose {
+                        let mut labels: Vec<&str> =
+                            violation.category.iter().map(String::as_str).collect();
+                        labels.extend(violation.tags.iter().map(String::as_str));
+                        if !labels.is_empty() {
+                            output.push_str(&format!("    🏷️  {}\n", labels.join(", ")));
+                        }
+                    }
+
                     // Show suggestions if available and requested
-                    if self.options.show_suggestions {
+                    if self.options.show_suggestions || verbose {
                         if let Some(suggestion) = &violation.suggested_fix {
                             if self.options.use_colors {
                                 output.push_str(&format!("    \x1b[32m💡 {suggestion}\x1b[0m\n"));
@@ -515,13 +950,36 @@ impl ReportFormatter {
                                 output.push_str(&format!("    💡 {suggestion}\n"));
                             }
                         }
+                        if let Some(docs_url) = &violation.docs_url {
+                            if self.options.use_colors {
+                                output.push_str(&format!("    \x1b[2m📖 {docs_url}\x1b[0m\n"));
+                            } else {
+                                output.push_str(&format!("    📖 {docs_url}\n"));
+                            }
+                        }
                     }
 
                     output.push('\n');
                 }
+
+                if let Some(hidden) = filtered.hidden_by_file.get(file_path) {
+                    output.push_str(&format!(
+                        "  … and {hidden} more in this file (max_per_file)\n\n"
+                    ));
+                }
             }
         }
 
+        for (rule_id, hidden) in &filtered.hidden_by_rule {
+            output.push_str(&format!(
+                "… and {hidden} more {rule_id} violation{} (max_per_rule)\n",
+                if *hidden == 1 { "" } else { "s" }
+            ));
+        }
+        if !filtered.hidden_by_rule.is_empty() {
+            output.push('\n');
+        }
+
         // Summary
         output.push_str(&self.format_summary(report));
 
@@ -532,26 +990,39 @@ impl ReportFormatter {
     fn format_json(
         &self,
         report: &ValidationReport,
-        violations: &[&Violation],
+        filtered: &FilteredViolations,
     ) -> GuardianResult<String> {
-        let json_violations: Vec<JsonValue> = violations
+        let json_violations: Vec<JsonValue> = filtered
+            .violations
             .iter()
             .map(|v| {
                 serde_json::json!({
                     "rule_id": v.rule_id,
                     "severity": v.severity.as_str(),
-                    "file_path": v.file_path.display().to_string(),
+                    "file_path": self.display_path(&v.file_path),
+                    "absolute_path": absolute_path(&v.file_path).display().to_string(),
                     "line_number": v.line_number,
                     "column_number": v.column_number,
+                    "end_line": v.end_line,
+                    "end_column": v.end_column,
+                    "byte_range": v.byte_range.as_ref().map(|range| serde_json::json!({
+                        "start": range.start,
+                        "end": range.end
+                    })),
                     "message": v.message,
                     "context": v.context,
                     "suggested_fix": v.suggested_fix,
+                    "cfg": v.cfg,
+                    "category": v.category,
+                    "tags": v.tags,
+                    "docs_url": v.docs_url,
+                    "issue_url": v.issue_url,
                     "detected_at": v.detected_at.to_rfc3339()
                 })
             })
             .collect();
 
-        let json_report = serde_json::json!({
+        let mut json_report = serde_json::json!({
             "violations": json_violations,
             "summary": {
                 "total_files": report.summary.total_files,
@@ -561,10 +1032,66 @@ impl ReportFormatter {
                     "info": report.summary.violations_by_severity.info
                 },
                 "execution_time_ms": report.summary.execution_time_ms,
-                "validated_at": report.summary.validated_at.to_rfc3339()
+                "validated_at": report.summary.validated_at.to_rfc3339(),
+                "skipped_generated": report
+                    .summary
+                    .skipped_generated
+                    .iter()
+                    .map(|path| self.display_path(path))
+                    .collect::<Vec<_>>(),
+                "skipped_undecodable": report
+                    .summary
+                    .skipped_undecodable
+                    .iter()
+                    .map(|path| self.display_path(path))
+                    .collect::<Vec<_>>(),
+                "skipped_oversized": report
+                    .summary
+                    .skipped_oversized
+                    .iter()
+                    .map(|path| self.display_path(path))
+                    .collect::<Vec<_>>(),
+                "file_encodings": report
+                    .summary
+                    .file_encodings
+                    .iter()
+                    .map(|file| serde_json::json!({
+                        "file_path": self.display_path(&file.file_path),
+                        "encoding": file.encoding
+                    }))
+                    .collect::<Vec<_>>(),
+                "partial": report.is_partial(),
+                "unanalyzed": report
+                    .summary
+                    .unanalyzed
+                    .iter()
+                    .map(|path| self.display_path(path))
+                    .collect::<Vec<_>>()
             },
-            "config_fingerprint": report.config_fingerprint
+            "truncated": {
+                "by_rule": filtered.hidden_by_rule,
+                "by_file": filtered
+                    .hidden_by_file
+                    .iter()
+                    .map(|(path, hidden)| (self.display_path(path), *hidden))
+                    .collect::<BTreeMap<_, _>>()
+            },
+            "config_fingerprint": report.config_fingerprint,
+            "rule_pack_version": report.rule_pack_version,
+            "policy_hash": report.policy_hash
         });
+        // Only present when the run collected it, e.g. with `check --stats`
+        if !report.summary.rule_usage.is_empty() {
+            json_report["summary"]["rule_usage"] = serde_json::json!(report.summary.rule_usage);
+        }
+        if !report.summary.false_positives.is_empty() {
+            json_report["summary"]["false_positives"] =
+                serde_json::json!(report.summary.false_positives);
+        }
+        // Only present when the run was restricted with `check --scope`
+        if !report.scope.is_empty() {
+            json_report["scope"] = serde_json::json!(report.scope);
+        }
 
         serde_json::to_string_pretty(&json_report).map_err(|e| {
             crate::domain::violations::GuardianError::config(format!(
@@ -574,54 +1101,126 @@ impl ReportFormatter {
     }
 
     /// Format report in JUnit XML format
+    ///
+    /// Each file is a testsuite. Error violations are failing testcases; a file without
+    /// errors has one passing testcase, so clean files count towards the totals. Warnings and
+    /// info violations do not fail the build and are listed in the file's `<system-out>`.
+    /// Files skipped as generated, undecodable, oversized or out of time are skipped
+    /// testcases.
     fn format_junit(
         &self,
         report: &ValidationReport,
         violations: &[&Violation],
     ) -> GuardianResult<String> {
-        let mut xml = String::new();
-        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-
-        let total_tests = violations.len();
-        let failures = violations
+        let summary = &report.summary;
+        let mut by_file: BTreeMap<&Path, Vec<&Violation>> = summary
+            .analyzed_files
             .iter()
-            .filter(|v| v.severity == Severity::Error)
-            .count();
-        let errors = 0; // Currently using failures count for both failures and errors
-        let execution_time = (report.summary.execution_time_ms as f64) / 1000.0;
-
-        xml.push_str(&format!(
-            "<testsuite name=\"rust-guardian\" tests=\"{total_tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{execution_time:.3}\">\n"
-        ));
-
+            .map(|file| (file.as_path(), Vec::new()))
+            .collect();
         for violation in violations {
-            xml.push_str(&format!(
-                "  <testcase classname=\"{}\" name=\"{}\">\n",
-                violation.rule_id,
-                escape_xml(&violation.file_path.display().to_string())
+            by_file
+                .entry(violation.file_path.as_path())
+                .or_default()
+                .push(violation);
+        }
+
+        let skipped_files: Vec<(&PathBuf, &str)> = [
+            (&summary.skipped_generated, "generated code"),
+            (&summary.skipped_undecodable, "undecodable content"),
+            (&summary.skipped_oversized, "exceeds the memory bound"),
+            (&summary.unanalyzed, "time budget exhausted"),
+        ]
+        .into_iter()
+        .flat_map(|(files, reason)| files.iter().map(move |file| (file, reason)))
+        .collect();
+
+        let mut suites = String::new();
+        let (mut total_tests, mut total_failures) = (0, 0);
+
+        for (file_path, file_violations) in &by_file {
+            let name = escape_xml(&self.display_path(file_path));
+            let (errors, others): (Vec<&Violation>, Vec<&Violation>) = file_violations
+                .iter()
+                .partition(|v| v.severity == Severity::Error);
+            let tests = errors.len().max(1);
+            total_tests += tests;
+            total_failures += errors.len();
+
+            suites.push_str(&format!(
+                "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+                errors.len()
             ));
 
-            if violation.severity == Severity::Error {
-                xml.push_str(&format!(
-                    "    <failure message=\"{}\">\n",
-                    escape_xml(&violation.message)
+            if errors.is_empty() {
+                suites.push_str(&format!(
+                    "    <testcase classname=\"{name}\" name=\"no blocking violations\"/>\n"
                 ));
-                xml.push_str(&format!(
-                    "      File: {}:{}:{}\n",
-                    violation.file_path.display(),
+            }
+            for violation in &errors {
+                let position = format!(
+                    "{}:{}",
                     violation.line_number.unwrap_or(0),
                     violation.column_number.unwrap_or(0)
+                );
+                suites.push_str(&format!(
+                    "    <testcase classname=\"{name}\" name=\"{} at {position}\">\n",
+                    escape_xml(&violation.rule_id)
+                ));
+                suites.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">\n",
+                    escape_xml(&violation.message),
+                    escape_xml(&violation.rule_id)
                 ));
+                suites.push_str(&format!("        File: {name}:{position}\n"));
                 if let Some(context) = &violation.context {
-                    xml.push_str(&format!("      Context: {}\n", escape_xml(context)));
+                    suites.push_str(&format!("        Context: {}\n", escape_xml(context)));
+                }
+                suites.push_str("      </failure>\n");
+                suites.push_str("    </testcase>\n");
+            }
+
+            if !others.is_empty() {
+                suites.push_str("    <system-out>\n");
+                for violation in &others {
+                    suites.push_str(&format!(
+                        "      {}: {} at {}:{}: {}\n",
+                        violation.severity.as_str(),
+                        escape_xml(&violation.rule_id),
+                        violation.line_number.unwrap_or(0),
+                        violation.column_number.unwrap_or(0),
+                        escape_xml(&violation.message)
+                    ));
                 }
-                xml.push_str("    </failure>\n");
+                suites.push_str("    </system-out>\n");
             }
+            suites.push_str("  </testsuite>\n");
+        }
 
-            xml.push_str("  </testcase>\n");
+        if !skipped_files.is_empty() {
+            total_tests += skipped_files.len();
+            suites.push_str(&format!(
+                "  <testsuite name=\"skipped files\" tests=\"{count}\" failures=\"0\" errors=\"0\" skipped=\"{count}\">\n",
+                count = skipped_files.len()
+            ));
+            for (file_path, reason) in &skipped_files {
+                suites.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"analysis\">\n      <skipped message=\"{reason}\"/>\n    </testcase>\n",
+                    escape_xml(&self.display_path(file_path))
+                ));
+            }
+            suites.push_str("  </testsuite>\n");
         }
 
-        xml.push_str("</testsuite>\n");
+        let execution_time = (summary.execution_time_ms as f64) / 1000.0;
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"rust-guardian\" tests=\"{total_tests}\" failures=\"{total_failures}\" errors=\"0\" skipped=\"{}\" time=\"{execution_time:.3}\">\n",
+            skipped_files.len()
+        ));
+        xml.push_str(&suites);
+        xml.push_str("</testsuites>\n");
         Ok(xml)
     }
 
@@ -640,21 +1239,35 @@ impl ReportFormatter {
                     Severity::Info => "note",
                 };
 
+                let mut region = serde_json::json!({
+                    "startLine": v.line_number.unwrap_or(1),
+                    "startColumn": v.column_number.unwrap_or(1)
+                });
+                if let (Some(end_line), Some(end_column)) = (v.end_line, v.end_column) {
+                    region["endLine"] = end_line.into();
+                    region["endColumn"] = end_column.into();
+                }
+                if let Some(range) = &v.byte_range {
+                    region["byteOffset"] = range.start.into();
+                    region["byteLength"] = (range.end - range.start).into();
+                }
+
                 serde_json::json!({
                     "ruleId": v.rule_id,
                     "level": level,
+                    "properties": {
+                        "category": v.category,
+                        "tags": v.tags
+                    },
                     "message": {
                         "text": v.message
                     },
                     "locations": [{
                         "physicalLocation": {
                             "artifactLocation": {
-                                "uri": v.file_path.display().to_string()
-                            },
-                            "region": {
-                                "startLine": v.line_number.unwrap_or(1),
-                                "startColumn": v.column_number.unwrap_or(1)
+                                "uri": self.display_path(&v.file_path)
                             },
+                            "region": region,
                             "contextRegion": v.context.as_ref().map(|c| serde_json::json!({
                                 "snippet": {
                                     "text": c
@@ -666,6 +1279,19 @@ impl ReportFormatter {
             })
             .collect();
 
+        // Describe each reported rule once so viewers can link to its documentation
+        let mut sarif_rules: std::collections::BTreeMap<&str, JsonValue> =
+            std::collections::BTreeMap::new();
+        for v in violations {
+            sarif_rules.entry(&v.rule_id).or_insert_with(|| {
+                let mut rule = serde_json::json!({ "id": v.rule_id });
+                if let Some(docs_url) = &v.docs_url {
+                    rule["helpUri"] = docs_url.as_str().into();
+                }
+                rule
+            });
+        }
+
         let sarif_report = serde_json::json!({
             "version": "2.1.0",
             "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
@@ -674,7 +1300,8 @@ impl ReportFormatter {
                     "driver": {
                         "name": "rust-guardian",
                         "version": "0.1.1",
-                        "informationUri": "https://github.com/cloudfunnels/rust-guardian"
+                        "informationUri": "https://github.com/cloudfunnels/rust-guardian",
+                        "rules": sarif_rules.into_values().collect::<Vec<_>>()
                     }
                 },
                 "results": sarif_results
@@ -688,24 +1315,31 @@ impl ReportFormatter {
         })
     }
 
-    /// Format report for GitHub Actions
+    /// Format report as GitHub Actions workflow commands
+    ///
+    /// GitHub shows at most [`GITHUB_ANNOTATION_LIMIT`] annotations per severity level in a
+    /// step and drops the rest silently. Errors are emitted first, and when findings do not
+    /// fit a closing notice says how many were left out and where to find them.
     fn format_github(
         &self,
         _report: &ValidationReport,
         violations: &[&Violation],
     ) -> GuardianResult<String> {
+        let (annotated, remaining) = github_annotations(violations);
         let mut output = String::new();
 
-        for violation in violations {
-            let level = match violation.severity {
-                Severity::Error => "error",
-                Severity::Warning => "warning",
-                Severity::Info => "notice",
-            };
-
-            let position = match (violation.line_number, violation.column_number) {
-                (Some(line), Some(col)) => format!("line={line},col={col}"),
-                (Some(line), None) => format!("line={line}"),
+        for violation in &annotated {
+            let position = match (
+                violation.line_number,
+                violation.column_number,
+                violation.end_line,
+                violation.end_column,
+            ) {
+                (Some(line), Some(col), Some(end_line), Some(end_col)) => {
+                    format!("line={line},col={col},endLine={end_line},endColumn={end_col}")
+                }
+                (Some(line), Some(col), _, _) => format!("line={line},col={col}"),
+                (Some(line), None, _, _) => format!("line={line}"),
                 _ => String::new(),
             };
 
@@ -717,17 +1351,49 @@ impl ReportFormatter {
 
             output.push_str(&format!(
                 "::{} file={},title={}{}::{}\n",
-                level,
-                violation.file_path.display(),
+                github_level(violation.severity),
+                self.display_path(&violation.file_path),
                 violation.rule_id,
                 position_part,
                 violation.message
             ));
         }
 
+        if !remaining.is_empty() {
+            output.push_str(&format!(
+                "::notice title=rust-guardian::{} of {} finding(s) are not annotated because GitHub \
+                 shows at most {GITHUB_ANNOTATION_LIMIT} annotations per severity; all findings \
+                 are listed in the job summary\n",
+                remaining.len(),
+                violations.len()
+            ));
+        }
+
         Ok(output)
     }
 
+    /// Markdown listing the findings GitHub output could not annotate, for the job summary
+    ///
+    /// Returns `None` when every finding fits in the annotation limits.
+    pub fn format_github_overflow(&self, report: &ValidationReport) -> Option<String> {
+        let filtered = self.filter_violations(&report.violations);
+        let (_, remaining) = github_annotations(&filtered.violations);
+        if remaining.is_empty() {
+            return None;
+        }
+
+        let mut output = format!(
+            "### Rust Guardian: {} finding(s) without annotations\n\n\
+             | Severity | File | Line | Rule | Message |\n\
+             |---|---|---|---|---|\n",
+            remaining.len()
+        );
+        for violation in remaining {
+            output.push_str(&job_summary::summary_row(self, violation));
+        }
+        Some(output)
+    }
+
     /// Format report for agent consumption: [line:path] <violation>
     fn format_agent(
         &self,
@@ -738,7 +1404,7 @@ impl ReportFormatter {
 
         for violation in violations {
             let line_number = violation.line_number.unwrap_or(1);
-            let path = violation.file_path.display();
+            let path = self.display_path(&violation.file_path);
 
             output.push_str(&format!(
                 "[{}:{}]\n{}\n\n",
@@ -749,58 +1415,664 @@ impl ReportFormatter {
         Ok(output)
     }
 
-    /// Format the summary section
-    fn format_summary(&self, report: &ValidationReport) -> String {
-        let mut summary = String::new();
+    /// Format violations as CSV with the configured columns
+    ///
+    /// Rows follow RFC 4180: fields containing commas, quotes or line breaks are quoted.
+    fn format_csv(&self, violations: &[&Violation]) -> GuardianResult<String> {
+        let columns = &self.options.csv_columns;
+        let mut output = csv_row(columns.iter().map(|c| c.as_str().to_string()));
+
+        for v in violations {
+            let fields = columns.iter().map(|column| match column {
+                CsvColumn::File => self.display_path(&v.file_path),
+                CsvColumn::Line => v.line_number.map(|n| n.to_string()).unwrap_or_default(),
+                CsvColumn::Column => v.column_number.map(|n| n.to_string()).unwrap_or_default(),
+                CsvColumn::EndLine => v.end_line.map(|n| n.to_string()).unwrap_or_default(),
+                CsvColumn::EndColumn => v.end_column.map(|n| n.to_string()).unwrap_or_default(),
+                CsvColumn::Severity => v.severity.as_str().to_string(),
+                CsvColumn::Rule => v.rule_id.clone(),
+                CsvColumn::Category => v.category.clone().unwrap_or_default(),
+                CsvColumn::Message => v.message.clone(),
+                CsvColumn::Context => v.context.clone().unwrap_or_default(),
+                CsvColumn::Tags => v.tags.join(";"),
+                CsvColumn::DocsUrl => v.docs_url.clone().unwrap_or_default(),
+                CsvColumn::SuggestedFix => v.suggested_fix.clone().unwrap_or_default(),
+                CsvColumn::Fingerprint => v.fingerprint(),
+            });
+            output.push_str(&csv_row(fields));
+        }
 
-        let total_violations = report.summary.violations_by_severity.total();
-        let execution_time = (report.summary.execution_time_ms as f64) / 1000.0;
+        Ok(output)
+    }
 
-        if self.options.use_colors {
-            summary.push_str("📊 \x1b[1mSummary:\x1b[0m ");
-        } else {
-            summary.push_str("📊 Summary: ");
+    /// Format run metrics in the Prometheus text exposition format
+    ///
+    /// Suitable for the node_exporter textfile collector or a `/metrics` endpoint. Violation
+    /// gauges are labelled with severity, rule and the crate owning the file; per-severity
+    /// totals are always present so alerts see zero rather than a missing series.
+    pub fn format_prometheus(&self, report: &ValidationReport) -> String {
+        let mut crates = CrateLocator::default();
+        let mut series: BTreeMap<(&str, &str, String), usize> = BTreeMap::new();
+        for v in &report.violations {
+            let crate_name = crates.crate_name(&v.file_path).unwrap_or_default();
+            *series
+                .entry((v.severity.as_str(), v.rule_id.as_str(), crate_name))
+                .or_default() += 1;
         }
 
-        if total_violations == 0 {
-            if self.options.use_colors {
-                summary.push_str(&format!(
-                    "\x1b[32m0 violations\x1b[0m in {} files ({:.1}s)\n",
-                    report.summary.total_files, execution_time
-                ));
-            } else {
-                summary.push_str(&format!(
-                    "0 violations in {} files ({:.1}s)\n",
-                    report.summary.total_files, execution_time
-                ));
-            }
-        } else {
-            let mut parts = Vec::new();
+        let summary = &report.summary;
+        let counts = &summary.violations_by_severity;
+        let mut out = String::new();
+
+        out.push_str("# HELP guardian_violations Violations found, by severity, rule and crate\n");
+        out.push_str("# TYPE guardian_violations gauge\n");
+        for ((severity, rule, crate_name), count) in &series {
+            out.push_str(&format!(
+                "guardian_violations{{severity=\"{}\",rule=\"{}\",crate=\"{}\"}} {}\n",
+                escape_label(severity),
+                escape_label(rule),
+                escape_label(crate_name),
+                count
+            ));
+        }
 
-            if report.summary.violations_by_severity.error > 0 {
-                let text = format!(
-                    "{} error{}",
-                    report.summary.violations_by_severity.error,
-                    if report.summary.violations_by_severity.error == 1 {
-                        ""
-                    } else {
-                        "s"
-                    }
-                );
-                if self.options.use_colors {
-                    parts.push(format!("\x1b[31m{text}\x1b[0m"));
-                } else {
-                    parts.push(text);
-                }
+        out.push_str("# HELP guardian_violations_by_severity Violations found, by severity\n");
+        out.push_str("# TYPE guardian_violations_by_severity gauge\n");
+        for (severity, count) in [
+            ("error", counts.error),
+            ("warning", counts.warning),
+            ("info", counts.info),
+        ] {
+            out.push_str(&format!(
+                "guardian_violations_by_severity{{severity=\"{severity}\"}} {count}\n"
+            ));
+        }
+
+        let rule_pack = report.rule_pack_version.as_deref().unwrap_or("unknown");
+        let gauges = [
+            (
+                "guardian_files_analyzed",
+                "Files analyzed in the most recent run",
+                summary.total_files.to_string(),
+            ),
+            (
+                "guardian_run_duration_seconds",
+                "Duration of the most recent run",
+                format!("{:.3}", summary.execution_time_ms as f64 / 1000.0),
+            ),
+            (
+                "guardian_last_run_timestamp_seconds",
+                "Unix time the most recent run completed",
+                summary.validated_at.timestamp().to_string(),
+            ),
+        ];
+        for (name, help, value) in gauges {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n"
+            ));
+        }
+
+        out.push_str("# HELP guardian_rule_pack_info Built-in rule pack version\n");
+        out.push_str("# TYPE guardian_rule_pack_info gauge\n");
+        out.push_str(&format!(
+            "guardian_rule_pack_info{{version=\"{}\"}} 1\n",
+            escape_label(rule_pack)
+        ));
+
+        out
+    }
+
+    /// Format run metrics as CSV with `metric,dimension,value` rows
+    ///
+    /// Counts cover the whole report, before severity filtering and output limits.
+    pub fn format_metrics_csv(&self, report: &ValidationReport) -> String {
+        let summary = &report.summary;
+        let counts = &summary.violations_by_severity;
+        let mut rows: Vec<(&str, String, String)> = vec![
+            (
+                "files_analyzed",
+                String::new(),
+                summary.total_files.to_string(),
+            ),
+            (
+                "execution_time_ms",
+                String::new(),
+                summary.execution_time_ms.to_string(),
+            ),
+            ("violations", "error".to_string(), counts.error.to_string()),
+            (
+                "violations",
+                "warning".to_string(),
+                counts.warning.to_string(),
+            ),
+            ("violations", "info".to_string(), counts.info.to_string()),
+            (
+                "skipped_generated",
+                String::new(),
+                summary.skipped_generated.len().to_string(),
+            ),
+            (
+                "skipped_undecodable",
+                String::new(),
+                summary.skipped_undecodable.len().to_string(),
+            ),
+            (
+                "skipped_oversized",
+                String::new(),
+                summary.skipped_oversized.len().to_string(),
+            ),
+            (
+                "unanalyzed",
+                String::new(),
+                summary.unanalyzed.len().to_string(),
+            ),
+        ];
+
+        let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut by_category: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut by_file: BTreeMap<String, usize> = BTreeMap::new();
+        for v in &report.violations {
+            *by_rule.entry(v.rule_id.as_str()).or_default() += 1;
+            if let Some(category) = &v.category {
+                *by_category.entry(category.as_str()).or_default() += 1;
             }
+            *by_file.entry(self.display_path(&v.file_path)).or_default() += 1;
+        }
 
-            if report.summary.violations_by_severity.warning > 0 {
-                let text = format!(
-                    "{} warning{}",
-                    report.summary.violations_by_severity.warning,
-                    if report.summary.violations_by_severity.warning == 1 {
-                        ""
-                    } else {
+        rows.extend(
+            by_rule
+                .into_iter()
+                .map(|(rule, count)| ("violations_by_rule", rule.to_string(), count.to_string())),
+        );
+        rows.extend(by_category.into_iter().map(|(category, count)| {
+            (
+                "violations_by_category",
+                category.to_string(),
+                count.to_string(),
+            )
+        }));
+        rows.extend(
+            by_file
+                .into_iter()
+                .map(|(file, count)| ("violations_by_file", file, count.to_string())),
+        );
+
+        let mut output = csv_row(["metric", "dimension", "value"].map(String::from));
+        for (metric, dimension, value) in rows {
+            output.push_str(&csv_row([metric.to_string(), dimension, value]));
+        }
+        output
+    }
+
+    /// Format per-rule usage as a table, costliest rules first
+    ///
+    /// Rules that examined files without ever matching are flagged as pruning candidates.
+    pub fn format_rule_usage(&self, report: &ValidationReport) -> String {
+        let mut usage: Vec<_> = report.summary.rule_usage.iter().collect();
+        if usage.is_empty() {
+            return "📊 No rule usage was recorded\n".to_string();
+        }
+        usage.sort_by(|a, b| b.time_ms.total_cmp(&a.time_ms));
+
+        let width = usage
+            .iter()
+            .map(|rule| rule.rule_id.len())
+            .max()
+            .unwrap_or(0)
+            .max("rule".len());
+        let mut output = format!(
+            "📊 Rule usage:\n  {:<width$}  {:>8}  {:>8}  {:>8}  {:>10}\n",
+            "rule", "examined", "matched", "matches", "time (ms)"
+        );
+        for rule in &usage {
+            let note = match (rule.files_examined, rule.matches) {
+                (0, _) => "  (never ran)",
+                (_, 0) => "  (never matched)",
+                _ => "",
+            };
+            output.push_str(&format!(
+                "  {:<width$}  {:>8}  {:>8}  {:>8}  {:>10.2}{note}\n",
+                rule.rule_id, rule.files_examined, rule.files_matched, rule.matches, rule.time_ms
+            ));
+        }
+
+        let idle = usage.iter().filter(|rule| rule.matches == 0).count();
+        if idle > 0 {
+            output.push_str(&format!(
+                "  {idle} of {} rule(s) reported nothing in this run\n",
+                usage.len()
+            ));
+        }
+        output
+    }
+
+    /// Format report as JSON lines for agents
+    ///
+    /// Violations are ordered by file, position and rule, and timestamps are left out, so
+    /// the same findings always produce byte-identical output. A summary line comes last.
+    fn format_json_lines(
+        &self,
+        report: &ValidationReport,
+        filtered: &FilteredViolations,
+    ) -> GuardianResult<String> {
+        let mut violations = filtered.violations.clone();
+        violations.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+                .then_with(|| a.column_number.cmp(&b.column_number))
+                .then_with(|| a.rule_id.cmp(&b.rule_id))
+        });
+
+        let mut output = String::new();
+        for v in violations {
+            let remediation = if self.options.show_suggestions {
+                serde_json::json!({
+                    "suggestion": v.suggested_fix,
+                    "docs_url": v.docs_url
+                })
+            } else {
+                JsonValue::Null
+            };
+            let line = serde_json::json!({
+                "type": "violation",
+                "fingerprint": v.fingerprint(),
+                "rule_id": v.rule_id,
+                "severity": v.severity.as_str(),
+                "file": self.display_path(&v.file_path),
+                "line": v.line_number,
+                "column": v.column_number,
+                "end_line": v.end_line,
+                "end_column": v.end_column,
+                "message": v.message,
+                "remediation": remediation
+            });
+            output.push_str(&line.to_string());
+            output.push('\n');
+        }
+
+        let hidden: usize = filtered.hidden_by_rule.values().sum::<usize>()
+            + filtered.hidden_by_file.values().sum::<usize>();
+        let summary = serde_json::json!({
+            "type": "summary",
+            "files": report.summary.total_files,
+            "errors": report.summary.violations_by_severity.error,
+            "warnings": report.summary.violations_by_severity.warning,
+            "info": report.summary.violations_by_severity.info,
+            "truncated": hidden,
+            "partial": report.is_partial(),
+            "unanalyzed": report.summary.unanalyzed.len(),
+            "rule_pack_version": report.rule_pack_version,
+            "policy_hash": report.policy_hash
+        });
+        output.push_str(&summary.to_string());
+        output.push('\n');
+
+        Ok(output)
+    }
+
+    /// Format report as `cargo --message-format=json` compiler messages
+    ///
+    /// Each violation becomes a `compiler-message` whose `message` follows rustc's diagnostic
+    /// schema, with the rule ID as the diagnostic code, so rust-analyzer's flycheck and other
+    /// cargo-aware editor integrations show findings unchanged. A `build-finished` message
+    /// comes last and fails when there are errors.
+    fn format_cargo_json(
+        &self,
+        report: &ValidationReport,
+        violations: &[&Violation],
+    ) -> GuardianResult<String> {
+        let package_id = concat!("rust-guardian ", env!("CARGO_PKG_VERSION"));
+        let mut output = String::new();
+
+        for v in violations {
+            let level = match v.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+            let file = self.display_path(&v.file_path);
+
+            // rustc columns are 1-based with an exclusive end, like violation columns
+            let spans = match v.line_number {
+                Some(line) => {
+                    let column = v.column_number.unwrap_or(1);
+                    let range = v.byte_range.clone().unwrap_or(0..0);
+                    vec![serde_json::json!({
+                        "file_name": file,
+                        "byte_start": range.start,
+                        "byte_end": range.end,
+                        "line_start": line,
+                        "line_end": v.end_line.unwrap_or(line),
+                        "column_start": column,
+                        "column_end": v.end_column.unwrap_or(column),
+                        "is_primary": true,
+                        "text": [],
+                        "label": null,
+                        "suggested_replacement": null,
+                        "suggestion_applicability": null,
+                        "expansion": null
+                    })]
+                }
+                None => Vec::new(),
+            };
+
+            let mut children = Vec::new();
+            let mut rendered = format!("{level}[{}]: {}\n", v.rule_id, v.message);
+            match (v.line_number, v.column_number) {
+                (Some(line), Some(column)) => {
+                    rendered.push_str(&format!("  --> {file}:{line}:{column}\n"))
+                }
+                (Some(line), None) => rendered.push_str(&format!("  --> {file}:{line}\n")),
+                _ => rendered.push_str(&format!("  --> {file}\n")),
+            }
+            let hints = [("help", &v.suggested_fix), ("note", &v.docs_url)];
+            let hints = hints.into_iter().filter(|_| self.options.show_suggestions);
+            for (hint_level, hint) in hints {
+                if let Some(hint) = hint {
+                    children.push(serde_json::json!({
+                        "message": hint,
+                        "code": null,
+                        "level": hint_level,
+                        "spans": [],
+                        "children": [],
+                        "rendered": null
+                    }));
+                    rendered.push_str(&format!("  = {hint_level}: {hint}\n"));
+                }
+            }
+
+            let message = serde_json::json!({
+                "reason": "compiler-message",
+                "package_id": package_id,
+                "manifest_path": "Cargo.toml",
+                "target": {
+                    "kind": ["lib"],
+                    "crate_types": ["lib"],
+                    "name": "rust-guardian",
+                    "src_path": file,
+                    "edition": "2021",
+                    "doc": false,
+                    "doctest": false,
+                    "test": false
+                },
+                "message": {
+                    "$message_type": "diagnostic",
+                    "message": v.message,
+                    "code": { "code": v.rule_id, "explanation": null },
+                    "level": level,
+                    "spans": spans,
+                    "children": children,
+                    "rendered": rendered
+                }
+            });
+            output.push_str(&message.to_string());
+            output.push('\n');
+        }
+
+        let finished = serde_json::json!({
+            "reason": "build-finished",
+            "success": report.summary.violations_by_severity.error == 0
+        });
+        output.push_str(&finished.to_string());
+        output.push('\n');
+
+        Ok(output)
+    }
+
+    /// Format report as Azure DevOps `##vso[task.logissue]` logging commands
+    ///
+    /// Azure Pipelines only knows errors and warnings, so info findings are logged as
+    /// warnings. The rule ID is the issue code.
+    fn format_azure_devops(
+        &self,
+        _report: &ValidationReport,
+        violations: &[&Violation],
+    ) -> GuardianResult<String> {
+        let mut output = String::new();
+
+        for violation in violations {
+            let issue_type = match violation.severity {
+                Severity::Error => "error",
+                Severity::Warning | Severity::Info => "warning",
+            };
+            let mut properties = format!(
+                "type={issue_type};sourcepath={}",
+                escape_azure_property(&self.display_path(&violation.file_path))
+            );
+            if let Some(line) = violation.line_number {
+                properties.push_str(&format!(";linenumber={line}"));
+            }
+            if let Some(column) = violation.column_number {
+                properties.push_str(&format!(";columnnumber={column}"));
+            }
+            properties.push_str(&format!(
+                ";code={}",
+                escape_azure_property(&violation.rule_id)
+            ));
+
+            output.push_str(&format!(
+                "##vso[task.logissue {properties};]{}\n",
+                escape_azure_message(&violation.message)
+            ));
+        }
+
+        Ok(output)
+    }
+
+    /// Format report as a Bitbucket Code Insights report with its annotations
+    ///
+    /// The output is one JSON object: `report` is the body for the reports endpoint and
+    /// `annotations` the body for the annotations endpoint. Bitbucket keeps at most
+    /// [`BITBUCKET_ANNOTATION_LIMIT`] annotations per report; errors come first, and the
+    /// report details say how many findings were left out. Annotation IDs are violation
+    /// fingerprints, so reruns update annotations instead of duplicating them.
+    fn format_bitbucket(
+        &self,
+        report: &ValidationReport,
+        violations: &[&Violation],
+    ) -> GuardianResult<String> {
+        let counts = &report.summary.violations_by_severity;
+        let mut ordered = violations.to_vec();
+        ordered.sort_by_key(|v| std::cmp::Reverse(v.severity));
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let annotations: Vec<JsonValue> = ordered
+            .iter()
+            .take(BITBUCKET_ANNOTATION_LIMIT)
+            .map(|v| {
+                let fingerprint = v.fingerprint();
+                let occurrence = seen.entry(fingerprint.clone()).or_default();
+                *occurrence += 1;
+                let external_id = match *occurrence {
+                    1 => fingerprint,
+                    n => format!("{fingerprint}-{n}"),
+                };
+                let severity = match v.severity {
+                    Severity::Error => "HIGH",
+                    Severity::Warning => "MEDIUM",
+                    Severity::Info => "LOW",
+                };
+                let mut annotation = serde_json::json!({
+                    "external_id": external_id,
+                    "annotation_type": "CODE_SMELL",
+                    "path": self.display_path(&v.file_path),
+                    "summary": truncate_chars(&format!("{}: {}", v.rule_id, v.message), 450),
+                    "severity": severity,
+                    "result": if v.severity == Severity::Error { "FAILED" } else { "PASSED" }
+                });
+                if let Some(line) = v.line_number {
+                    annotation["line"] = serde_json::json!(line);
+                }
+                let details: Vec<&str> = [&v.suggested_fix, &v.rationale]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect();
+                if !details.is_empty() {
+                    annotation["details"] = serde_json::json!(details.join("\n\n"));
+                }
+                if let Some(docs_url) = &v.docs_url {
+                    annotation["link"] = serde_json::json!(docs_url);
+                }
+                annotation
+            })
+            .collect();
+
+        let mut details = format!(
+            "{} violation(s) in {} file(s)",
+            counts.total(),
+            report.summary.total_files
+        );
+        if violations.len() > annotations.len() {
+            details.push_str(&format!(
+                "; {} finding(s) are not annotated because Bitbucket keeps at most \
+                 {BITBUCKET_ANNOTATION_LIMIT} annotations per report",
+                violations.len() - annotations.len()
+            ));
+        }
+
+        let output = serde_json::json!({
+            "report": {
+                "title": "Rust Guardian",
+                "details": details,
+                "report_type": "BUG",
+                "reporter": "rust-guardian",
+                "result": if counts.error == 0 { "PASSED" } else { "FAILED" },
+                "data": [
+                    { "title": "Errors", "type": "NUMBER", "value": counts.error },
+                    { "title": "Warnings", "type": "NUMBER", "value": counts.warning },
+                    { "title": "Info", "type": "NUMBER", "value": counts.info },
+                    { "title": "Files analyzed", "type": "NUMBER", "value": report.summary.total_files },
+                    { "title": "Duration", "type": "DURATION", "value": report.summary.execution_time_ms }
+                ]
+            },
+            "annotations": annotations
+        });
+
+        serde_json::to_string_pretty(&output).map_err(|e| {
+            crate::domain::violations::GuardianError::config(format!(
+                "Bitbucket serialization failed: {e}"
+            ))
+        })
+    }
+
+    /// Format report in the Reviewdog Diagnostic Format (`reviewdog -f=rdjson`)
+    ///
+    /// The rule ID is the diagnostic code, linked to the rule documentation when there is
+    /// any. Positions are 1-based lines and character columns, as reviewdog expects.
+    fn format_rdjson(&self, violations: &[&Violation]) -> GuardianResult<String> {
+        let diagnostics: Vec<JsonValue> = violations
+            .iter()
+            .map(|v| {
+                let mut location = serde_json::json!({ "path": self.display_path(&v.file_path) });
+                if let Some(line) = v.line_number {
+                    let mut range = serde_json::json!({ "start": { "line": line } });
+                    if let Some(column) = v.column_number {
+                        range["start"]["column"] = serde_json::json!(column);
+                    }
+                    if let Some(end_line) = v.end_line {
+                        range["end"] = serde_json::json!({ "line": end_line });
+                        if let Some(end_column) = v.end_column {
+                            range["end"]["column"] = serde_json::json!(end_column);
+                        }
+                    }
+                    location["range"] = range;
+                }
+
+                let mut code = serde_json::json!({ "value": v.rule_id });
+                if let Some(docs_url) = &v.docs_url {
+                    code["url"] = serde_json::json!(docs_url);
+                }
+
+                let mut message = v.message.clone();
+                if self.options.show_suggestions {
+                    if let Some(fix) = &v.suggested_fix {
+                        message.push_str(&format!("\n\n💡 {fix}"));
+                    }
+                }
+
+                serde_json::json!({
+                    "message": message,
+                    "location": location,
+                    "severity": match v.severity {
+                        Severity::Error => "ERROR",
+                        Severity::Warning => "WARNING",
+                        Severity::Info => "INFO",
+                    },
+                    "code": code,
+                    "original_output": v.format_display()
+                })
+            })
+            .collect();
+
+        let output = serde_json::json!({
+            "source": {
+                "name": "rust-guardian",
+                "url": env!("CARGO_PKG_REPOSITORY")
+            },
+            "diagnostics": diagnostics
+        });
+
+        serde_json::to_string_pretty(&output).map_err(|e| {
+            crate::domain::violations::GuardianError::config(format!(
+                "rdjson serialization failed: {e}"
+            ))
+        })
+    }
+
+    /// Format the summary section
+    fn format_summary(&self, report: &ValidationReport) -> String {
+        let mut summary = String::new();
+
+        let total_violations = report.summary.violations_by_severity.total();
+        let execution_time = (report.summary.execution_time_ms as f64) / 1000.0;
+
+        if self.options.use_colors {
+            summary.push_str("📊 \x1b[1mSummary:\x1b[0m ");
+        } else {
+            summary.push_str("📊 Summary: ");
+        }
+
+        if total_violations == 0 {
+            if self.options.use_colors {
+                summary.push_str(&format!(
+                    "\x1b[32m0 violations\x1b[0m in {} files ({:.1}s)\n",
+                    report.summary.total_files, execution_time
+                ));
+            } else {
+                summary.push_str(&format!(
+                    "0 violations in {} files ({:.1}s)\n",
+                    report.summary.total_files, execution_time
+                ));
+            }
+        } else {
+            let mut parts = Vec::new();
+
+            if report.summary.violations_by_severity.error > 0 {
+                let text = format!(
+                    "{} error{}",
+                    report.summary.violations_by_severity.error,
+                    if report.summary.violations_by_severity.error == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                );
+                if self.options.use_colors {
+                    parts.push(format!("\x1b[31m{text}\x1b[0m"));
+                } else {
+                    parts.push(text);
+                }
+            }
+
+            if report.summary.violations_by_severity.warning > 0 {
+                let text = format!(
+                    "{} warning{}",
+                    report.summary.violations_by_severity.warning,
+                    if report.summary.violations_by_severity.warning == 1 {
+                        ""
+                    } else {
                         "s"
                     }
                 );
@@ -828,78 +2100,811 @@ impl ReportFormatter {
             ));
         }
 
-        summary
-    }
-}
+        let skipped = &report.summary.skipped_generated;
+        if !skipped.is_empty() {
+            summary.push_str(&format!(
+                "⏭️  Skipped {} generated file{}:\n",
+                skipped.len(),
+                if skipped.len() == 1 { "" } else { "s" }
+            ));
+            for file_path in skipped {
+                summary.push_str(&format!("   {}\n", self.display_path(file_path)));
+            }
+        }
+
+        let undecodable = &report.summary.skipped_undecodable;
+        if !undecodable.is_empty() {
+            summary.push_str(&format!(
+                "⏭️  Skipped {} undecodable file{}:\n",
+                undecodable.len(),
+                if undecodable.len() == 1 { "" } else { "s" }
+            ));
+            for file_path in undecodable {
+                summary.push_str(&format!("   {}\n", self.display_path(file_path)));
+            }
+        }
+
+        let oversized = &report.summary.skipped_oversized;
+        if !oversized.is_empty() {
+            summary.push_str(&format!(
+                "⏭️  Skipped {} file{} over the memory bound:\n",
+                oversized.len(),
+                if oversized.len() == 1 { "" } else { "s" }
+            ));
+            for file_path in oversized {
+                summary.push_str(&format!("   {}\n", self.display_path(file_path)));
+            }
+        }
+
+        let unanalyzed = &report.summary.unanalyzed;
+        if !unanalyzed.is_empty() {
+            summary.push_str(&format!(
+                "⏱️  Time budget exceeded, report is partial. {} file{} not analyzed:\n",
+                unanalyzed.len(),
+                if unanalyzed.len() == 1 {
+                    " was"
+                } else {
+                    "s were"
+                }
+            ));
+            for file_path in unanalyzed {
+                summary.push_str(&format!("   {}\n", self.display_path(file_path)));
+            }
+        }
+
+        let encodings = &report.summary.file_encodings;
+        if !encodings.is_empty() {
+            summary.push_str(&format!(
+                "🔤 Decoded {} non-UTF-8 file{}:\n",
+                encodings.len(),
+                if encodings.len() == 1 { "" } else { "s" }
+            ));
+            for file in encodings {
+                summary.push_str(&format!(
+                    "   {} ({})\n",
+                    self.display_path(&file.file_path),
+                    file.encoding
+                ));
+            }
+        }
+
+        summary
+    }
+}
+
+impl Default for ReportFormatter {
+    /// Create a formatter with default options
+    fn default() -> Self {
+        Self::with_options(ReportOptions::default())
+    }
+}
+
+/// Render a code frame showing the lines around `range` with the span underlined
+///
+/// Returns `None` when the range does not fit the source, e.g. because the file
+/// changed between analysis and formatting.
+fn render_code_frame(
+    source: &str,
+    range: std::ops::Range<usize>,
+    context_lines: usize,
+    use_colors: bool,
+) -> Option<String> {
+    if range.start > range.end || source.get(range.clone()).is_none() {
+        return None;
+    }
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let target_line = line_starts.partition_point(|&start| start <= range.start) - 1;
+    let first_line = target_line.saturating_sub(context_lines);
+    let last_line = (target_line + context_lines).min(line_starts.len() - 1);
+
+    let line_text = |index: usize| -> &str {
+        let start = line_starts[index];
+        let end = line_starts
+            .get(index + 1)
+            .map(|next| next - 1)
+            .unwrap_or(source.len());
+        source[start..end].trim_end_matches('\r')
+    };
+
+    let gutter_width = (last_line + 1).to_string().len();
+    let (dim, red, reset) = if use_colors {
+        ("\x1b[2m", "\x1b[31m", "\x1b[0m")
+    } else {
+        ("", "", "")
+    };
+
+    let mut frame = format!("    {dim}{:gutter_width$} │{reset}\n", "");
+    for index in first_line..=last_line {
+        let text = line_text(index);
+        frame.push_str(&format!(
+            "    {dim}{:>gutter_width$} │{reset} {text}\n",
+            index + 1
+        ));
+
+        if index == target_line {
+            // Underline only the part of the span on this line, measured in characters
+            let line_start = line_starts[target_line];
+            let span_start = (range.start - line_start).min(text.len());
+            let span_end = (range.end - line_start).min(text.len()).max(span_start);
+            let padding: String = text[..span_start]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = text[span_start..span_end].chars().count().max(1);
+            frame.push_str(&format!(
+                "    {dim}{:gutter_width$} │{reset} {padding}{red}{}{reset}\n",
+                "",
+                "^".repeat(width)
+            ));
+        }
+    }
+
+    Some(frame)
+}
+
+/// Resolve a path against the current directory without touching the filesystem
+///
+/// `.` and `..` segments are resolved lexically, so `/tmp/tiny/./small.rs` and
+/// `/tmp/tiny/src/../small.rs` both become `/tmp/tiny/small.rs`.
+pub(crate) fn absolute_path(path: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // `..` above the root stays at the root
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Use forward slashes and drop a leading `./`
+pub(crate) fn normalize_separators(path: &Path) -> String {
+    let normalized = path.to_string_lossy().replace('\\', "/");
+    match normalized.strip_prefix("./") {
+        Some(rest) => rest.to_string(),
+        None => normalized,
+    }
+}
+
+/// Escape a property value of an Azure DevOps logging command
+fn escape_azure_property(value: &str) -> String {
+    escape_azure_message(value)
+        .replace(';', "%3B")
+        .replace(']', "%5D")
+}
+
+/// Escape the message of an Azure DevOps logging command
+fn escape_azure_message(message: &str) -> String {
+    message
+        .replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Cut text to at most `max` characters
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Workflow command level of a severity
+fn github_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    }
+}
+
+/// Split violations into those GitHub will show as annotations and those it would drop
+///
+/// Errors come first. When anything is dropped, one notice slot is kept free for the
+/// truncation notice.
+fn github_annotations<'a>(
+    violations: &[&'a Violation],
+) -> (Vec<&'a Violation>, Vec<&'a Violation>) {
+    let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+    let truncated = [Severity::Error, Severity::Warning, Severity::Info]
+        .into_iter()
+        .any(|severity| count(severity) > GITHUB_ANNOTATION_LIMIT);
+
+    let mut annotated = Vec::new();
+    let mut remaining = Vec::new();
+    for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+        let limit = if truncated && severity == Severity::Info {
+            GITHUB_ANNOTATION_LIMIT - 1
+        } else {
+            GITHUB_ANNOTATION_LIMIT
+        };
+        let mut of_severity = violations
+            .iter()
+            .copied()
+            .filter(|v| v.severity == severity);
+        annotated.extend(of_severity.by_ref().take(limit));
+        remaining.extend(of_severity);
+    }
+    (annotated, remaining)
+}
+
+/// Escape XML special characters
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Resolves files to the Cargo package that owns them, caching by directory
+#[derive(Debug, Default)]
+struct CrateLocator {
+    by_dir: HashMap<PathBuf, Option<String>>,
+}
+
+impl CrateLocator {
+    /// Name of the package whose manifest is closest above `file`
+    fn crate_name(&mut self, file: &Path) -> Option<String> {
+        let file = absolute_path(file);
+        let dir = file.parent()?.to_path_buf();
+        if let Some(cached) = self.by_dir.get(&dir) {
+            return cached.clone();
+        }
+
+        let name = dir
+            .ancestors()
+            .find_map(|ancestor| package_name(&ancestor.join("Cargo.toml")));
+        self.by_dir.insert(dir, name.clone());
+        name
+    }
+}
+
+/// The `[package]` name declared by a manifest, if it exists and declares one
+fn package_name(manifest: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(manifest).ok()?;
+    let mut in_package = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            let value = line
+                .strip_prefix("name")
+                .and_then(|rest| rest.trim_start().strip_prefix('='));
+            if let Some(value) = value {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Join fields into a CSV row, quoting those with commas, quotes or line breaks
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut row = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::violations::RuleUsage;
+    use std::path::PathBuf;
+    // Test imports - unused
+
+    fn create_test_report() -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "test_rule",
+                Severity::Error,
+                PathBuf::from("src/main.rs"),
+                "Test violation",
+            )
+            .with_position(42, 15)
+            .with_context("let x = unimplemented!();"),
+        );
+
+        report.set_files_analyzed(10);
+        report.set_execution_time(1200);
+
+        report
+    }
+
+    #[test]
+    fn test_human_format() {
+        let options = ReportOptions {
+            use_colors: false,
+            ..Default::default()
+        };
+
+        // Demonstrate self-validation
+        options.validate().expect("Test options should be valid");
+
+        let formatter = ReportFormatter::with_options(options);
+
+        let report = create_test_report();
+        let output = formatter
+            .format_report(&report, OutputFormat::Human)
+            .expect("Human format should always succeed for valid reports");
+
+        // Verify the formatter self-validated the output
+        formatter
+            .validate_format_integrity(&report, OutputFormat::Human, &output)
+            .expect("Human output should pass integrity validation");
+
+        assert!(output.contains("Code Quality Violations Found"));
+        assert!(output.contains("src/main.rs"));
+        assert!(output.contains("Test violation"));
+        assert!(output.contains("Summary:"));
+    }
+
+    #[test]
+    fn test_summary_lists_skipped_generated_files() {
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            use_colors: false,
+            ..Default::default()
+        });
+        let mut report = create_test_report();
+        report.add_skipped_generated(PathBuf::from("src/proto/messages.rs"));
+
+        let human = formatter
+            .format_report(&report, OutputFormat::Human)
+            .expect("Human format should always succeed for valid reports");
+        assert!(human.contains("Skipped 1 generated file:"));
+        assert!(human.contains("src/proto/messages.rs"));
+
+        let json = formatter
+            .format_report(&report, OutputFormat::Json)
+            .expect("JSON format should always succeed for valid reports");
+        let json: serde_json::Value =
+            serde_json::from_str(&json).expect("JSON output should parse");
+        assert_eq!(
+            json["summary"]["skipped_generated"][0],
+            "src/proto/messages.rs"
+        );
+    }
+
+    #[test]
+    fn test_per_rule_and_per_file_limits() {
+        let mut report = ValidationReport::new();
+        for (file, line) in [
+            ("src/a.rs", 1),
+            ("src/a.rs", 2),
+            ("src/a.rs", 3),
+            ("src/b.rs", 1),
+        ] {
+            report.add_violation(
+                crate::domain::violations::Violation::new(
+                    "todo_comments",
+                    Severity::Warning,
+                    PathBuf::from(file),
+                    "TODO found",
+                )
+                .with_position(line, 1),
+            );
+        }
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "unwrap_usage",
+                Severity::Error,
+                PathBuf::from("src/b.rs"),
+                "unwrap found",
+            )
+            .with_position(5, 1),
+        );
+
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            use_colors: false,
+            max_per_rule: Some(2),
+            max_per_file: Some(1),
+            ..Default::default()
+        });
+
+        let human = formatter
+            .format_report(&report, OutputFormat::Human)
+            .expect("Human format should always succeed for valid reports");
+        assert!(human.contains("… and 2 more todo_comments violations (max_per_rule)"));
+        assert!(human.contains("… and 1 more in this file (max_per_file)"));
+
+        let json = formatter
+            .format_report(&report, OutputFormat::Json)
+            .expect("JSON format should always succeed for valid reports");
+        let json: serde_json::Value =
+            serde_json::from_str(&json).expect("JSON output should parse");
+        assert_eq!(json["truncated"]["by_rule"]["todo_comments"], 2);
+        assert_eq!(json["truncated"]["by_file"]["src/a.rs"], 1);
+        assert!(json["truncated"]["by_file"]["src/b.rs"].is_null());
+
+        let invalid = ReportOptions {
+            max_per_rule: Some(0),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_file_limit_counts_towards_rule_limit() {
+        let mut report = ValidationReport::new();
+        for (file, line) in [
+            ("src/a.rs", 1),
+            ("src/a.rs", 2),
+            ("src/a.rs", 3),
+            ("src/b.rs", 1),
+        ] {
+            report.add_violation(
+                crate::domain::violations::Violation::new(
+                    "todo_comments",
+                    Severity::Warning,
+                    PathBuf::from(file),
+                    "TODO found",
+                )
+                .with_position(line, 1),
+            );
+        }
+
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            use_colors: false,
+            max_per_rule: Some(2),
+            max_per_file: Some(1),
+            ..Default::default()
+        });
+        let filtered = formatter.filter_violations(&report.violations);
 
-impl Default for ReportFormatter {
-    /// Create a formatter with default options
-    fn default() -> Self {
-        Self::with_options(ReportOptions::default())
+        // a.rs:1 is shown and a.rs:2 hits the file limit, which still uses the rule's second
+        // slot, so the rest are hidden by the rule limit even though b.rs has room
+        assert_eq!(filtered.violations.len(), 1);
+        assert_eq!(filtered.hidden_by_file[Path::new("src/a.rs")], 1);
+        assert_eq!(filtered.hidden_by_rule["todo_comments"], 2);
     }
-}
 
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#39;")
-}
+    #[test]
+    fn test_csv_format_and_metrics() {
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            csv_columns: CsvColumn::parse_list(&["file", "line", "rule", "message"])
+                .expect("column names should be valid"),
+            ..ReportOptions::optimized_for(OutputFormat::Csv)
+        });
+        let mut report = create_test_report();
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "todo_comments",
+                Severity::Warning,
+                PathBuf::from("src/lib.rs"),
+                "Found \"TODO\", track it",
+            )
+            .with_position(3, 1),
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
-    // Test imports - unused
+        let output = formatter
+            .format_report(&report, OutputFormat::Csv)
+            .expect("CSV format should always succeed for valid reports");
+        let rows: Vec<&str> = output.lines().collect();
+        assert_eq!(rows[0], "file,line,rule,message");
+        assert!(rows.contains(&"src/main.rs,42,test_rule,Test violation"));
+        assert!(rows.contains(&"src/lib.rs,3,todo_comments,\"Found \"\"TODO\"\", track it\""));
+
+        let metrics = formatter.format_metrics_csv(&report);
+        let rows: Vec<&str> = metrics.lines().collect();
+        assert_eq!(rows[0], "metric,dimension,value");
+        assert!(rows.contains(&"files_analyzed,,10"));
+        assert!(rows.contains(&"violations,error,1"));
+        assert!(rows.contains(&"violations_by_rule,todo_comments,1"));
+        assert!(rows.contains(&"violations_by_file,src/main.rs,1"));
+
+        assert!(CsvColumn::parse_list(&["file", "owner"]).is_err());
+    }
+
+    #[test]
+    fn test_prometheus_exposition() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir should be creatable");
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .expect("manifest should be writable");
 
-    fn create_test_report() -> ValidationReport {
         let mut report = ValidationReport::new();
+        for line in [3, 9] {
+            report.add_violation(
+                crate::domain::violations::Violation::new(
+                    "todo_comments",
+                    Severity::Warning,
+                    temp_dir.path().join("src/lib.rs"),
+                    "TODO found",
+                )
+                .with_position(line, 1),
+            );
+        }
+        report.set_files_analyzed(4);
+        report.set_execution_time(1500);
+        report.set_rule_pack_version("1.0.0");
+
+        let output = ReportFormatter::default().format_prometheus(&report);
+        let samples: Vec<&str> = output.lines().filter(|l| !l.starts_with('#')).collect();
 
+        assert!(samples.contains(
+            &"guardian_violations{severity=\"warning\",rule=\"todo_comments\",crate=\"demo\"} 2"
+        ));
+        assert!(samples.contains(&"guardian_violations_by_severity{severity=\"error\"} 0"));
+        assert!(samples.contains(&"guardian_files_analyzed 4"));
+        assert!(samples.contains(&"guardian_run_duration_seconds 1.500"));
+        assert!(samples.contains(&"guardian_rule_pack_info{version=\"1.0.0\"} 1"));
+        assert!(output.contains("# TYPE guardian_violations gauge"));
+    }
+
+    #[test]
+    fn test_json_lines_format() {
+        let formatter = ReportFormatter::with_options(ReportOptions::agent_mode());
+        let mut report = create_test_report();
         report.add_violation(
             crate::domain::violations::Violation::new(
-                "test_rule",
-                Severity::Error,
-                PathBuf::from("src/main.rs"),
-                "Test violation",
+                "todo_comments",
+                Severity::Warning,
+                PathBuf::from("src/lib.rs"),
+                "TODO found",
             )
-            .with_position(42, 15)
-            .with_context("let x = unimplemented!();"),
+            .with_position(3, 1)
+            .with_suggestion("Track the work in an issue"),
         );
 
-        report.set_files_analyzed(10);
-        report.set_execution_time(1200);
+        let output = formatter
+            .format_report(&report, OutputFormat::JsonLines)
+            .expect("JSON lines format should always succeed for valid reports");
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("every line should be JSON"))
+            .collect();
 
-        report
+        // Ordered by file, so src/lib.rs comes before src/main.rs
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["file"], "src/lib.rs");
+        assert_eq!(
+            lines[0]["remediation"]["suggestion"],
+            "Track the work in an issue"
+        );
+        assert_eq!(lines[0]["fingerprint"], report.violations[1].fingerprint());
+        assert_eq!(lines[1]["file"], "src/main.rs");
+        assert_eq!(lines[2]["type"], "summary");
+        assert_eq!(lines[2]["errors"], 1);
+
+        // No timestamps, so repeated runs are byte-identical
+        let again = formatter
+            .format_report(&report, OutputFormat::JsonLines)
+            .expect("JSON lines format should always succeed for valid reports");
+        assert_eq!(output, again);
     }
 
     #[test]
-    fn test_human_format() {
-        let options = ReportOptions {
+    fn test_cargo_json_format() {
+        let formatter =
+            ReportFormatter::with_options(ReportOptions::optimized_for(OutputFormat::CargoJson));
+        let mut report = ValidationReport::new();
+        let violation = crate::domain::violations::Violation::new(
+            "todo_comments",
+            Severity::Warning,
+            PathBuf::from("src/lib.rs"),
+            "TODO found",
+        )
+        .with_position(3, 5)
+        .with_end_position(3, 9)
+        .with_suggestion("Track the work in an issue");
+        report.add_violation(violation);
+
+        let output = formatter
+            .format_report(&report, OutputFormat::CargoJson)
+            .expect("cargo JSON format should always succeed for valid reports");
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("every line should be JSON"))
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["reason"], "compiler-message");
+        let message = &lines[0]["message"];
+        assert_eq!(message["level"], "warning");
+        assert_eq!(message["code"]["code"], "todo_comments");
+        assert_eq!(message["spans"][0]["file_name"], "src/lib.rs");
+        assert_eq!(message["spans"][0]["line_start"], 3);
+        assert_eq!(message["spans"][0]["column_start"], 5);
+        assert_eq!(message["spans"][0]["column_end"], 9);
+        assert_eq!(message["children"][0]["level"], "help");
+        assert!(message["rendered"]
+            .as_str()
+            .expect("rendered")
+            .starts_with("warning[todo_comments]: TODO found\n  --> src/lib.rs:3:5"));
+        assert_eq!(lines[1]["reason"], "build-finished");
+        assert_eq!(lines[1]["success"], true);
+
+        assert_eq!(
+            "cargo-json".parse::<OutputFormat>(),
+            Ok(OutputFormat::CargoJson)
+        );
+    }
+
+    #[test]
+    fn test_absolute_path_resolves_dot_segments() {
+        let root = std::env::temp_dir().join("tiny");
+        let expected = root.join("small.rs");
+        assert_eq!(absolute_path(&root.join(".").join("small.rs")), expected);
+        assert_eq!(
+            absolute_path(&root.join("src").join("..").join("small.rs")),
+            expected
+        );
+
+        let cwd = std::env::current_dir().expect("current dir should be readable");
+        assert_eq!(
+            absolute_path(Path::new("./src/../lib.rs")),
+            cwd.join("lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_paths_are_normalized_relative_to_base() {
+        let base = std::env::temp_dir().join("guardian-repo");
+        let mut report = ValidationReport::new();
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "todo_comments",
+                Severity::Error,
+                base.join("src").join("lib.rs"),
+                "TODO found",
+            )
+            .with_position(3, 1),
+        );
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "todo_comments",
+                Severity::Error,
+                PathBuf::from(r"src\windows\mod.rs"),
+                "TODO found",
+            )
+            .with_position(1, 1),
+        );
+
+        let formatter = ReportFormatter::with_options(ReportOptions {
             use_colors: false,
+            path_base: Some(base.clone()),
             ..Default::default()
-        };
+        });
 
-        // Demonstrate self-validation
-        options.validate().expect("Test options should be valid");
+        let sarif = formatter
+            .format_report(&report, OutputFormat::Sarif)
+            .expect("SARIF format should always succeed for valid reports");
+        assert!(sarif.contains("\"uri\": \"src/lib.rs\""));
+        assert!(sarif.contains("\"uri\": \"src/windows/mod.rs\""));
 
-        let formatter = ReportFormatter::with_options(options);
+        let github = formatter
+            .format_report(&report, OutputFormat::GitHub)
+            .expect("GitHub format should always succeed for valid reports");
+        assert!(github.contains("file=src/lib.rs,"));
 
-        let report = create_test_report();
+        let json = formatter
+            .format_report(&report, OutputFormat::Json)
+            .expect("JSON format should always succeed for valid reports");
+        let json: serde_json::Value =
+            serde_json::from_str(&json).expect("JSON output should parse");
+        assert_eq!(json["violations"][0]["file_path"], "src/lib.rs");
+        assert_eq!(
+            json["violations"][0]["absolute_path"],
+            base.join("src").join("lib.rs").display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_human_format_code_frame() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir should be creatable");
+        let file_path = temp_dir.path().join("lib.rs");
+        let source = "fn one() {}\nfn two() {\n    let x = unimplemented!();\n}\nfn three() {}\n";
+        std::fs::write(&file_path, source).expect("test source should be writable");
+
+        let start = source.find("unimplemented!()").expect("span should exist");
+        let mut report = ValidationReport::new();
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "unimplemented_macros",
+                Severity::Error,
+                file_path,
+                "Unfinished macro",
+            )
+            .with_position(3, 13)
+            .with_byte_range(start..start + "unimplemented!()".len()),
+        );
+
+        let formatter = ReportFormatter::with_options(ReportOptions {
+            use_colors: false,
+            context_lines: 1,
+            ..Default::default()
+        });
         let output = formatter
             .format_report(&report, OutputFormat::Human)
-            .expect("Human format should always succeed for valid reports");
+            .expect("Human format should succeed with code frames");
+
+        assert!(output.contains("2 │ fn two() {"));
+        assert!(output.contains("3 │     let x = unimplemented!();"));
+        assert!(output.contains("  │             ^^^^^^^^^^^^^^^^\n"));
+        assert!(output.contains("4 │ }"));
+        assert!(!output.contains("fn one()"));
+        assert!(!output.contains("fn three()"));
+    }
 
-        // Verify the formatter self-validated the output
-        formatter
-            .validate_format_integrity(&report, OutputFormat::Human, &output)
-            .expect("Human output should pass integrity validation");
+    #[test]
+    fn test_human_format_explain() {
+        let mut report = ValidationReport::new();
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "todo_comments",
+                Severity::Error,
+                PathBuf::from("src/lib.rs"),
+                "Development marker detected: TODO",
+            )
+            .with_position(3, 8)
+            .with_category("placeholders")
+            .with_docs_url("https://example.com/rules/todo_comments")
+            .with_rationale("Markers stand for unfinished work"),
+        );
+        let format = |explain, verbose_violations| {
+            ReportFormatter::with_options(ReportOptions {
+                use_colors: false,
+                show_suggestions: false,
+                explain,
+                verbose_violations,
+                ..Default::default()
+            })
+            .format_report(&report, OutputFormat::Human)
+            .expect("Human format should succeed")
+        };
 
-        assert!(output.contains("Code Quality Violations Found"));
-        assert!(output.contains("src/main.rs"));
-        assert!(output.contains("Test violation"));
-        assert!(output.contains("Summary:"));
+        let plain = format(false, false);
+        assert!(!plain.contains("Markers stand for unfinished work"));
+
+        let explained = format(true, false);
+        assert!(explained.contains("    ❔ Markers stand for unfinished work\n"));
+        assert!(!explained.contains("example.com"));
+
+        let verbose = format(false, true);
+        assert!(verbose.contains("    ❔ Markers stand for unfinished work\n"));
+        assert!(verbose.contains("    🏷️  placeholders\n"));
+        assert!(verbose.contains("    📖 https://example.com/rules/todo_comments\n"));
     }
 
     #[test]
@@ -927,6 +2932,89 @@ mod tests {
         );
         assert_eq!(json["violations"][0]["rule_id"], "test_rule");
         assert_eq!(json["summary"]["total_files"], 10);
+        assert!(json["summary"]["rule_usage"].is_null());
+        assert!(json["summary"]["false_positives"].is_null());
+        assert!(json["scope"].is_null());
+    }
+
+    #[test]
+    fn test_json_format_rule_usage() {
+        let formatter = ReportFormatter::default();
+        let mut report = create_test_report();
+        report.summary.rule_usage = vec![RuleUsage {
+            rule_id: "test_rule".to_string(),
+            files_examined: 10,
+            files_matched: 1,
+            matches: 1,
+            time_ms: 2.5,
+        }];
+
+        let output = formatter
+            .format_report(&report, OutputFormat::Json)
+            .expect("JSON format should always succeed for valid reports");
+        let json: JsonValue =
+            serde_json::from_str(&output).expect("JSON output should be valid JSON");
+
+        let usage = &json["summary"]["rule_usage"][0];
+        assert_eq!(usage["rule_id"], "test_rule");
+        assert_eq!(usage["files_examined"], 10);
+        assert_eq!(usage["files_matched"], 1);
+        assert_eq!(usage["time_ms"], 2.5);
+    }
+
+    #[test]
+    fn test_rule_usage_table() {
+        let formatter = ReportFormatter::default();
+        let mut report = ValidationReport::new();
+        let usage = |rule_id: &str, files_examined: usize, matches: usize, time_ms| RuleUsage {
+            rule_id: rule_id.to_string(),
+            files_examined,
+            files_matched: matches.min(files_examined),
+            matches,
+            time_ms,
+        };
+        report.summary.rule_usage = vec![
+            usage("cheap", 4, 2, 0.5),
+            usage("costly", 4, 0, 12.0),
+            usage("unused", 0, 0, 0.0),
+        ];
+
+        let table = formatter.format_rule_usage(&report);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[2].starts_with("  costly") && lines[2].ends_with("(never matched)"));
+        assert!(lines[3].starts_with("  cheap") && lines[3].ends_with("0.50"));
+        assert!(lines[4].ends_with("(never ran)"));
+        assert_eq!(lines[5], "  2 of 3 rule(s) reported nothing in this run");
+    }
+
+    #[test]
+    fn test_json_format_rule_metadata() {
+        let formatter = ReportFormatter::default();
+        let mut report = ValidationReport::new();
+        report.add_violation(
+            Violation::new(
+                "test_rule",
+                Severity::Warning,
+                PathBuf::from("src/main.rs"),
+                "Test violation",
+            )
+            .with_category("placeholders")
+            .with_tags(vec!["completeness".to_string()])
+            .with_docs_url("https://example.com/rules/test_rule"),
+        );
+
+        let output = formatter
+            .format_report(&report, OutputFormat::Json)
+            .expect("JSON format should always succeed for valid reports");
+        let json: JsonValue =
+            serde_json::from_str(&output).expect("JSON output should be valid JSON");
+
+        assert_eq!(json["violations"][0]["category"], "placeholders");
+        assert_eq!(json["violations"][0]["tags"][0], "completeness");
+        assert_eq!(
+            json["violations"][0]["docs_url"],
+            "https://example.com/rules/test_rule"
+        );
     }
 
     #[test]
@@ -948,6 +3036,44 @@ mod tests {
         assert!(output.contains("<failure"));
     }
 
+    #[test]
+    fn test_junit_one_suite_per_file() {
+        let formatter = ReportFormatter::default();
+        let mut report = create_test_report();
+        report.set_analyzed_files(vec![
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/clean.rs"),
+        ]);
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "soft_rule",
+                Severity::Warning,
+                PathBuf::from("src/lib.rs"),
+                "Worth a look",
+            )
+            .with_position(3, 1),
+        );
+        report.add_skipped_generated(PathBuf::from("src/generated.rs"));
+
+        let output = formatter
+            .format_report(&report, OutputFormat::Junit)
+            .expect("JUnit format should always succeed for valid reports");
+
+        assert!(output.contains(
+            "<testsuites name=\"rust-guardian\" tests=\"4\" failures=\"1\" errors=\"0\" skipped=\"1\""
+        ));
+        assert!(output.contains(
+            "<testsuite name=\"src/main.rs\" tests=\"1\" failures=\"1\" errors=\"0\" skipped=\"0\">"
+        ));
+        assert!(output.contains("name=\"test_rule at 42:15\""));
+        assert!(output
+            .contains("<testcase classname=\"src/clean.rs\" name=\"no blocking violations\"/>"));
+        assert!(output.contains("      warning: soft_rule at 3:1: Worth a look\n"));
+        assert!(!output.contains("<failure message=\"Worth a look\""));
+        assert!(output.contains("<skipped message=\"generated code\"/>"));
+    }
+
     #[test]
     fn test_github_format() {
         let formatter = ReportFormatter::default();
@@ -967,6 +3093,160 @@ mod tests {
         assert!(output.contains("Test violation"));
     }
 
+    #[test]
+    fn test_rdjson_format() {
+        let formatter = ReportFormatter::default();
+        let report = create_test_report();
+        let output = formatter
+            .format_report(&report, OutputFormat::Rdjson)
+            .expect("rdjson format should always succeed for valid reports");
+        formatter
+            .validate_format_integrity(&report, OutputFormat::Rdjson, &output)
+            .expect("rdjson output should pass integrity validation");
+
+        let json: JsonValue = serde_json::from_str(&output).expect("output should be JSON");
+        assert_eq!(json["source"]["name"], "rust-guardian");
+        let diagnostic = &json["diagnostics"][0];
+        assert_eq!(diagnostic["message"], "Test violation");
+        assert_eq!(diagnostic["severity"], "ERROR");
+        assert_eq!(diagnostic["code"]["value"], "test_rule");
+        assert_eq!(diagnostic["location"]["path"], "src/main.rs");
+        assert_eq!(diagnostic["location"]["range"]["start"]["line"], 42);
+        assert_eq!(diagnostic["location"]["range"]["start"]["column"], 15);
+        assert!(diagnostic["location"]["range"]["end"].is_null());
+    }
+
+    #[test]
+    fn test_azure_devops_format() {
+        let formatter = ReportFormatter::default();
+        let mut report = create_test_report();
+        report.add_violation(crate::domain::violations::Violation::new(
+            "soft_rule",
+            Severity::Info,
+            PathBuf::from("src/a;b.rs"),
+            "50% done\nsee notes",
+        ));
+        let output = formatter
+            .format_report(&report, OutputFormat::AzureDevOps)
+            .expect("Azure DevOps format should always succeed for valid reports");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "##vso[task.logissue type=error;sourcepath=src/main.rs;linenumber=42;columnnumber=15;code=test_rule;]Test violation"
+        );
+        assert_eq!(
+            lines[1],
+            "##vso[task.logissue type=warning;sourcepath=src/a%3Bb.rs;code=soft_rule;]50%AZP25 done%0Asee notes"
+        );
+    }
+
+    #[test]
+    fn test_bitbucket_format() {
+        let formatter = ReportFormatter::default();
+        let mut report = create_test_report();
+        report.add_violation(
+            crate::domain::violations::Violation::new(
+                "soft_rule",
+                Severity::Warning,
+                PathBuf::from("src/lib.rs"),
+                "Worth a look",
+            )
+            .with_position(3, 1),
+        );
+        let output = formatter
+            .format_report(&report, OutputFormat::Bitbucket)
+            .expect("Bitbucket format should always succeed for valid reports");
+        formatter
+            .validate_format_integrity(&report, OutputFormat::Bitbucket, &output)
+            .expect("Bitbucket output should pass integrity validation");
+
+        let json: JsonValue = serde_json::from_str(&output).expect("output should be JSON");
+        assert_eq!(json["report"]["result"], "FAILED");
+        assert_eq!(json["report"]["data"][0]["value"], 1);
+        assert_eq!(json["annotations"][0]["severity"], "HIGH");
+        assert_eq!(json["annotations"][0]["result"], "FAILED");
+        assert_eq!(json["annotations"][0]["line"], 42);
+        assert_eq!(
+            json["annotations"][0]["external_id"],
+            report.violations[0].fingerprint()
+        );
+        assert_eq!(json["annotations"][1]["summary"], "soft_rule: Worth a look");
+        assert_eq!(json["annotations"][1]["result"], "PASSED");
+    }
+
+    #[test]
+    fn test_github_annotation_limit() {
+        let formatter = ReportFormatter::default();
+        let mut report = ValidationReport::new();
+        for (severity, count) in [(Severity::Info, 12), (Severity::Error, 11)] {
+            for line in 1..=count {
+                report.add_violation(
+                    crate::domain::violations::Violation::new(
+                        "test_rule",
+                        severity,
+                        PathBuf::from("src/main.rs"),
+                        "Test violation",
+                    )
+                    .with_position(line, 1),
+                );
+            }
+        }
+
+        let output = formatter
+            .format_report(&report, OutputFormat::GitHub)
+            .expect("GitHub format should always succeed for valid reports");
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Errors come first, and one notice slot is left for the truncation notice
+        assert!(lines[..GITHUB_ANNOTATION_LIMIT]
+            .iter()
+            .all(|line| line.starts_with("::error")));
+        assert_eq!(lines.len(), 2 * GITHUB_ANNOTATION_LIMIT);
+        assert!(lines[lines.len() - 1].contains("4 of 23 finding(s) are not annotated"));
+
+        let overflow = formatter
+            .format_github_overflow(&report)
+            .expect("truncated output should have an overflow summary");
+        assert!(overflow.contains("4 finding(s) without annotations"));
+        assert!(overflow.contains("| error | `src/main.rs` | 11 | `test_rule` | Test violation |"));
+
+        assert!(ReportFormatter::default()
+            .format_github_overflow(&create_test_report())
+            .is_none());
+    }
+
+    #[test]
+    fn test_sarif_format_end_position() {
+        let formatter = ReportFormatter::default();
+        let mut report = ValidationReport::new();
+        report.add_violation(
+            Violation::new(
+                "test_rule",
+                Severity::Error,
+                PathBuf::from("src/main.rs"),
+                "Test violation",
+            )
+            .with_position(42, 15)
+            .with_end_position(42, 30)
+            .with_byte_range(1200..1215),
+        );
+
+        let output = formatter
+            .format_report(&report, OutputFormat::Sarif)
+            .expect("SARIF format should always succeed for valid reports");
+        let sarif: JsonValue =
+            serde_json::from_str(&output).expect("SARIF output should be valid JSON");
+        let region = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+
+        assert_eq!(region["startLine"], 42);
+        assert_eq!(region["startColumn"], 15);
+        assert_eq!(region["endLine"], 42);
+        assert_eq!(region["endColumn"], 30);
+        assert_eq!(region["byteOffset"], 1200);
+        assert_eq!(region["byteLength"], 15);
+    }
+
     #[test]
     fn test_empty_report() {
         let options = ReportOptions {
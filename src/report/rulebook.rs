@@ -0,0 +1,313 @@
+//! Rulebook generation from the loaded configuration
+//!
+//! Architecture: Anti-Corruption Layer - The policy a team enforces is published from the config itself
+//! - Every configured rule, custom or built in, is listed with its severity, scope and rationale
+//! - `good_example` and `bad_example` snippets show what the rule accepts and rejects
+//! - Markdown and HTML are rendered from the same entries, so both always list the same rules
+
+use super::escape_xml;
+use crate::config::{GuardianConfig, PatternRule};
+use crate::domain::violations::Severity;
+use crate::patterns::RuleInfo;
+
+/// Output format of a rulebook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulebookFormat {
+    Markdown,
+    Html,
+}
+
+/// A group of rules, either a configured category or the compiled-in rules
+struct Section {
+    title: String,
+    summary: String,
+    rules: Vec<Entry>,
+}
+
+/// Everything the rulebook shows about one rule
+struct Entry {
+    id: String,
+    description: String,
+    rationale: Option<String>,
+    /// Labelled one-line facts such as severity and scope
+    facts: Vec<(&'static str, String)>,
+    pattern: Option<String>,
+    bad_example: Option<String>,
+    good_example: Option<String>,
+}
+
+/// Render the rulebook of a configuration, including compiled-in procedural rules
+pub fn render_rulebook(
+    config: &GuardianConfig,
+    procedural: &[RuleInfo],
+    format: RulebookFormat,
+) -> String {
+    let sections = sections(config, procedural);
+    match format {
+        RulebookFormat::Markdown => render_markdown(&sections),
+        RulebookFormat::Html => render_html(&sections),
+    }
+}
+
+fn sections(config: &GuardianConfig, procedural: &[RuleInfo]) -> Vec<Section> {
+    let mut categories: Vec<_> = config.patterns.iter().collect();
+    categories.sort_by_key(|(name, _)| name.as_str());
+
+    let mut sections: Vec<Section> = categories
+        .into_iter()
+        .map(|(name, category)| Section {
+            title: name.clone(),
+            summary: format!(
+                "Default severity {}{}.",
+                category.severity.as_str(),
+                if category.enabled { "" } else { ", disabled" }
+            ),
+            rules: category
+                .rules
+                .iter()
+                .map(|rule| configured_entry(rule, category.severity))
+                .collect(),
+        })
+        .collect();
+
+    if !procedural.is_empty() {
+        sections.push(Section {
+            title: "compiled-in".to_string(),
+            summary: "Procedural rules built into this binary.".to_string(),
+            rules: procedural.iter().map(procedural_entry).collect(),
+        });
+    }
+    sections
+}
+
+fn configured_entry(rule: &PatternRule, category_severity: Severity) -> Entry {
+    let mut facts = vec![
+        (
+            "Severity",
+            rule.severity
+                .unwrap_or(category_severity)
+                .as_str()
+                .to_string(),
+        ),
+        ("Type", rule_type_name(rule)),
+    ];
+    if !rule.enabled {
+        facts.push(("Enabled", "no".to_string()));
+    }
+    if !rule.applies_to.is_empty() {
+        facts.push(("Applies to", rule.applies_to.join(", ")));
+    }
+    if !rule.tags.is_empty() {
+        facts.push(("Tags", rule.tags.join(", ")));
+    }
+    if rule.deprecated {
+        let note = match &rule.replaced_by {
+            Some(replacement) => format!("yes, use {replacement}"),
+            None => "yes".to_string(),
+        };
+        facts.push(("Deprecated", note));
+    }
+    if let Some(docs_url) = &rule.docs_url {
+        facts.push(("Documentation", docs_url.clone()));
+    }
+
+    Entry {
+        id: rule.id.clone(),
+        description: rule.message.clone(),
+        rationale: rule.rationale.clone(),
+        facts,
+        pattern: (!rule.pattern.is_empty()).then(|| rule.pattern.clone()),
+        bad_example: rule.bad_example.clone(),
+        good_example: rule.good_example.clone(),
+    }
+}
+
+fn procedural_entry(info: &RuleInfo) -> Entry {
+    let mut facts = vec![
+        ("Severity", info.severity.as_str().to_string()),
+        ("Type", "procedural".to_string()),
+    ];
+    if let Some(category) = &info.category {
+        facts.push(("Category", category.clone()));
+    }
+    if !info.tags.is_empty() {
+        facts.push(("Tags", info.tags.join(", ")));
+    }
+    if let Some(docs_url) = &info.docs_url {
+        facts.push(("Documentation", docs_url.clone()));
+    }
+
+    Entry {
+        id: info.id.clone(),
+        description: info.description.clone(),
+        rationale: None,
+        facts,
+        pattern: None,
+        bad_example: None,
+        good_example: None,
+    }
+}
+
+/// The rule type as written in configuration files, e.g. `file_pair`
+fn rule_type_name(rule: &PatternRule) -> String {
+    serde_json::to_value(&rule.rule_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", rule.rule_type))
+}
+
+fn render_markdown(sections: &[Section]) -> String {
+    let mut output = String::from("# Rulebook\n");
+    for section in sections {
+        output.push_str(&format!("\n## {}\n\n{}\n", section.title, section.summary));
+        for entry in &section.rules {
+            output.push_str(&format!("\n### `{}`\n\n{}\n", entry.id, entry.description));
+            if let Some(rationale) = &entry.rationale {
+                output.push_str(&format!("\n**Why:** {rationale}\n"));
+            }
+            output.push('\n');
+            for (label, value) in &entry.facts {
+                output.push_str(&format!("- **{label}:** {value}\n"));
+            }
+            if let Some(pattern) = &entry.pattern {
+                output.push_str(&format!("\n**Pattern:**\n\n{}", fenced(pattern, "text")));
+            }
+            if let Some(example) = &entry.bad_example {
+                output.push_str(&format!("\n**Bad:**\n\n{}", fenced(example, "rust")));
+            }
+            if let Some(example) = &entry.good_example {
+                output.push_str(&format!("\n**Good:**\n\n{}", fenced(example, "rust")));
+            }
+        }
+    }
+    output
+}
+
+/// A fenced code block, with a fence longer than any backtick run in the code
+fn fenced(code: &str, language: &str) -> String {
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{language}\n{}\n{fence}\n", code.trim_end())
+}
+
+fn render_html(sections: &[Section]) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rulebook</title>\n</head>\n<body>\n<h1>Rulebook</h1>\n",
+    );
+    for section in sections {
+        output.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<p>{}</p>\n",
+            escape_xml(&section.title),
+            escape_xml(&section.summary)
+        ));
+        for entry in &section.rules {
+            let id = escape_xml(&entry.id);
+            output.push_str(&format!(
+                "<article id=\"{id}\">\n<h3><code>{id}</code></h3>\n<p>{}</p>\n",
+                escape_xml(&entry.description)
+            ));
+            if let Some(rationale) = &entry.rationale {
+                output.push_str(&format!(
+                    "<p><strong>Why:</strong> {}</p>\n",
+                    escape_xml(rationale)
+                ));
+            }
+            output.push_str("<ul>\n");
+            for (label, value) in &entry.facts {
+                output.push_str(&format!(
+                    "<li><strong>{label}:</strong> {}</li>\n",
+                    escape_xml(value)
+                ));
+            }
+            output.push_str("</ul>\n");
+            for (label, code) in [
+                ("Pattern", &entry.pattern),
+                ("Bad", &entry.bad_example),
+                ("Good", &entry.good_example),
+            ] {
+                if let Some(code) = code {
+                    output.push_str(&format!(
+                        "<p><strong>{label}:</strong></p>\n<pre><code>{}</code></pre>\n",
+                        escape_xml(code.trim_end())
+                    ));
+                }
+            }
+            output.push_str("</article>\n");
+        }
+        output.push_str("</section>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> GuardianConfig {
+        GuardianConfig::load_from_str(
+            r#"
+version: "1.0"
+paths:
+  patterns: []
+patterns:
+  api:
+    severity: warning
+    enabled: true
+    rules:
+      - id: no_unwrap
+        type: regex
+        pattern: '\.unwrap\(\)'
+        message: "Avoid unwrap in library code"
+        rationale: "Panics take down the caller's process"
+        applies_to: ["src/**"]
+        bad_example: |
+          let port = env::var("PORT").unwrap();
+        good_example: |
+          let port = env::var("PORT")?;
+"#,
+        )
+        .expect("config should load")
+    }
+
+    #[test]
+    fn test_markdown_rulebook() {
+        let procedural = [RuleInfo::new(
+            "no_print",
+            "No println! in libraries",
+            Severity::Info,
+        )];
+        let rulebook = render_rulebook(&config(), &procedural, RulebookFormat::Markdown);
+
+        assert!(rulebook.contains("## api\n\nDefault severity warning.\n"));
+        assert!(rulebook.contains(
+            "### `no_unwrap`\n\nAvoid unwrap in library code\n\n**Why:** Panics take down the caller's process\n"
+        ));
+        assert!(rulebook.contains("- **Applies to:** src/**\n"));
+        assert!(rulebook
+            .contains("**Bad:**\n\n```rust\nlet port = env::var(\"PORT\").unwrap();\n```\n"));
+        assert!(rulebook.contains("**Good:**\n\n```rust\nlet port = env::var(\"PORT\")?;\n```\n"));
+        assert!(rulebook.contains("## compiled-in\n"));
+        assert!(rulebook.contains("- **Type:** procedural\n"));
+    }
+
+    #[test]
+    fn test_html_rulebook() {
+        let rulebook = render_rulebook(&config(), &[], RulebookFormat::Html);
+
+        assert!(rulebook.contains("<article id=\"no_unwrap\">"));
+        assert!(
+            rulebook.contains("<pre><code>let port = env::var(&quot;PORT&quot;)?;</code></pre>")
+        );
+        assert!(rulebook.contains("Panics take down the caller&#39;s process"));
+        assert!(!rulebook.contains("compiled-in"));
+    }
+
+    #[test]
+    fn test_fence_outgrows_backticks() {
+        assert_eq!(
+            fenced("let s = \"```\";", "rust"),
+            "````rust\nlet s = \"```\";\n````\n"
+        );
+    }
+}
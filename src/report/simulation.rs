@@ -0,0 +1,373 @@
+//! Policy simulation against a stored report
+//!
+//! Architecture: Domain Service - Proposed configurations are judged by the violations already found
+//! - A stored report is re-evaluated under the proposed rule severities and enablement
+//! - Nothing is re-analyzed, so policy changes can be tried on large repositories in seconds
+//! - Rules that are new, newly enabled or redefined are listed, since only analysis can measure them
+
+use crate::config::GuardianConfig;
+use crate::domain::violations::{
+    GuardianError, GuardianResult, Severity, ValidationReport, Violation, ViolationCounts,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Change to one rule, given on the command line as `rule=off`, `rule=on` or `rule=<severity>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleOverride {
+    Off,
+    On,
+    Severity(Severity),
+}
+
+/// Parse a `rule=value` override
+pub fn parse_override(spec: &str) -> GuardianResult<(String, RuleOverride)> {
+    let invalid = || {
+        GuardianError::config(format!(
+            "Invalid override '{spec}' (expected rule=off, rule=on or rule=error|warning|info)"
+        ))
+    };
+    let (rule_id, value) = spec.split_once('=').ok_or_else(invalid)?;
+    let rule_override = match value.trim() {
+        "off" => RuleOverride::Off,
+        "on" => RuleOverride::On,
+        "error" => RuleOverride::Severity(Severity::Error),
+        "warning" => RuleOverride::Severity(Severity::Warning),
+        "info" => RuleOverride::Severity(Severity::Info),
+        _ => return Err(invalid()),
+    };
+    Ok((rule_id.trim().to_string(), rule_override))
+}
+
+/// Apply rule overrides to a configuration
+///
+/// Fails on rule ids the configuration does not define.
+pub fn apply_overrides(
+    config: &mut GuardianConfig,
+    overrides: &[(String, RuleOverride)],
+) -> GuardianResult<()> {
+    for (rule_id, rule_override) in overrides {
+        let rule = config
+            .patterns
+            .values_mut()
+            .flat_map(|category| category.rules.iter_mut())
+            .find(|rule| rule.id == *rule_id)
+            .ok_or_else(|| {
+                GuardianError::config(format!("Override names unknown rule '{rule_id}'"))
+            })?;
+        match rule_override {
+            RuleOverride::Off => rule.enabled = false,
+            RuleOverride::On => rule.enabled = true,
+            RuleOverride::Severity(severity) => rule.severity = Some(*severity),
+        }
+    }
+    Ok(())
+}
+
+/// Load the violations of a report saved with `check --format json`
+pub fn load_json_report(path: &Path) -> GuardianResult<ValidationReport> {
+    let invalid =
+        |e: String| GuardianError::config(format!("Invalid JSON report '{}': {e}", path.display()));
+    let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+    let json: JsonValue = serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let violations = json
+        .get("violations")
+        .and_then(JsonValue::as_array)
+        .ok_or_else(|| invalid("report has no violations array".to_string()))?;
+
+    let mut report = ValidationReport::new();
+    for violation in violations {
+        let stored: StoredViolation =
+            serde_json::from_value(violation.clone()).map_err(|e| invalid(e.to_string()))?;
+        let mut violation = Violation::new(
+            stored.rule_id,
+            stored.severity,
+            stored.file_path,
+            stored.message,
+        );
+        violation.line_number = stored.line_number;
+        violation.column_number = stored.column_number;
+        violation.context = stored.context;
+        violation.category = stored.category;
+        report.add_violation(violation);
+    }
+    report.summary.total_files = json["summary"]["total_files"].as_u64().unwrap_or(0) as usize;
+    Ok(report)
+}
+
+/// Fields of a JSON report violation the simulation needs
+#[derive(Deserialize)]
+struct StoredViolation {
+    rule_id: String,
+    severity: Severity,
+    file_path: PathBuf,
+    message: String,
+    line_number: Option<u32>,
+    column_number: Option<u32>,
+    context: Option<String>,
+    category: Option<String>,
+}
+
+/// Violation counts of one rule before and after the proposed change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleChange {
+    pub rule_id: String,
+    pub before: ViolationCounts,
+    pub after: ViolationCounts,
+}
+
+/// Outcome of re-evaluating a report under a proposed configuration
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Simulation {
+    pub before: ViolationCounts,
+    pub after: ViolationCounts,
+    /// Rules whose counts change, by rule id
+    pub rules: Vec<RuleChange>,
+    /// Rules whose violations the stored report cannot contain or predict
+    pub needs_analysis: Vec<String>,
+}
+
+impl Simulation {
+    /// Whether CI passes with the current configuration
+    pub fn passes_before(&self) -> bool {
+        !self.before.has_blocking()
+    }
+
+    /// Whether CI would pass with the proposed configuration
+    pub fn passes_after(&self) -> bool {
+        !self.after.has_blocking()
+    }
+
+    /// Human-readable delta
+    pub fn format_display(&self) -> String {
+        let outcome = |passes| {
+            if passes {
+                "✅ CI passes"
+            } else {
+                "❌ CI fails"
+            }
+        };
+        let mut output = format!(
+            "🧪 Policy simulation (no files re-analyzed)\n  current:  {}  {}\n  proposed: {}  {}\n",
+            describe(&self.before),
+            outcome(self.passes_before()),
+            describe(&self.after),
+            outcome(self.passes_after())
+        );
+
+        if self.rules.is_empty() {
+            output.push_str("  No violation changes severity or disappears\n");
+        } else {
+            output.push_str("\n  Changed rules:\n");
+            let width = self
+                .rules
+                .iter()
+                .map(|rule| rule.rule_id.len())
+                .max()
+                .unwrap_or(0);
+            for rule in &self.rules {
+                output.push_str(&format!(
+                    "    {:<width$}  {} → {}\n",
+                    rule.rule_id,
+                    describe(&rule.before),
+                    describe(&rule.after)
+                ));
+            }
+        }
+
+        if !self.needs_analysis.is_empty() {
+            output.push_str(&format!(
+                "\n  ⚠️  Not simulated, run `check` to measure: {}\n",
+                self.needs_analysis.join(", ")
+            ));
+        }
+        output
+    }
+}
+
+fn describe(counts: &ViolationCounts) -> String {
+    format!(
+        "{} error(s), {} warning(s), {} info",
+        counts.error, counts.warning, counts.info
+    )
+}
+
+/// Effective state of every configured rule: `None` when disabled, else its severity
+fn rule_states(config: &GuardianConfig) -> HashMap<&str, Option<Severity>> {
+    config
+        .patterns
+        .values()
+        .flat_map(|category| {
+            category.rules.iter().map(move |rule| {
+                let enabled = category.enabled && rule.enabled;
+                (
+                    rule.id.as_str(),
+                    enabled.then(|| config.effective_severity(category, rule)),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Definitions of every configured rule, without severity and enablement
+fn rule_definitions(config: &GuardianConfig) -> HashMap<&str, JsonValue> {
+    config
+        .patterns
+        .values()
+        .flat_map(|category| category.rules.iter())
+        .map(|rule| {
+            let mut definition = rule.clone();
+            definition.severity = None;
+            definition.enabled = true;
+            (
+                rule.id.as_str(),
+                serde_json::to_value(definition).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// Re-evaluate a report's violations under a proposed configuration
+///
+/// Violations of rules whose state is unchanged keep their reported severity, including any
+/// escalation or triage adjustment. Violations of rules the proposal disables or removes are
+/// dropped, and the others take the proposed severity.
+pub fn simulate(
+    report: &ValidationReport,
+    current: &GuardianConfig,
+    proposed: &GuardianConfig,
+) -> Simulation {
+    let current_states = rule_states(current);
+    let proposed_states = rule_states(proposed);
+
+    let mut before = ViolationCounts::default();
+    let mut after = ViolationCounts::default();
+    let mut by_rule: BTreeMap<&str, (ViolationCounts, ViolationCounts)> = BTreeMap::new();
+    for violation in &report.violations {
+        let rule_id = violation.rule_id.as_str();
+        let simulated = match (current_states.get(rule_id), proposed_states.get(rule_id)) {
+            (Some(current), Some(proposed)) if current == proposed => Some(violation.severity),
+            (None, None) => Some(violation.severity),
+            (_, Some(Some(severity))) => Some(*severity),
+            _ => None,
+        };
+
+        let counts = by_rule.entry(rule_id).or_default();
+        before.add(violation.severity);
+        counts.0.add(violation.severity);
+        if let Some(severity) = simulated {
+            after.add(severity);
+            counts.1.add(severity);
+        }
+    }
+
+    let rules = by_rule
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(rule_id, (before, after))| RuleChange {
+            rule_id: rule_id.to_string(),
+            before,
+            after,
+        })
+        .collect();
+
+    // Rules that start reporting, or report differently, cannot be predicted from old findings
+    let current_definitions = rule_definitions(current);
+    let mut needs_analysis: Vec<String> = rule_definitions(proposed)
+        .into_iter()
+        .filter(|(rule_id, _)| matches!(proposed_states.get(rule_id), Some(Some(_))))
+        .filter(|(rule_id, definition)| {
+            !matches!(current_states.get(rule_id), Some(Some(_)))
+                || current_definitions.get(rule_id) != Some(definition)
+        })
+        .map(|(rule_id, _)| rule_id.to_string())
+        .collect();
+    needs_analysis.sort();
+
+    Simulation {
+        before,
+        after,
+        rules,
+        needs_analysis,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (rule_id, severity) in [
+            ("todo_comments", Severity::Error),
+            ("todo_comments", Severity::Error),
+            ("temporary_markers", Severity::Error),
+            ("procedural_check", Severity::Warning),
+        ] {
+            report.add_violation(Violation::new(
+                rule_id,
+                severity,
+                PathBuf::from("src/lib.rs"),
+                "found",
+            ));
+        }
+        report
+    }
+
+    #[test]
+    fn test_simulate_overrides() {
+        let current = GuardianConfig::default();
+        let mut proposed = current.clone();
+        let overrides = [
+            parse_override("todo_comments=warning").unwrap(),
+            parse_override("temporary_markers=off").unwrap(),
+        ];
+        apply_overrides(&mut proposed, &overrides).unwrap();
+
+        let simulation = simulate(&report(), &current, &proposed);
+        assert!(!simulation.passes_before());
+        assert!(simulation.passes_after());
+        assert_eq!(simulation.before.error, 3);
+        assert_eq!(simulation.after.warning, 3);
+        assert_eq!(simulation.after.total(), 3);
+        let changed: Vec<&str> = simulation
+            .rules
+            .iter()
+            .map(|rule| rule.rule_id.as_str())
+            .collect();
+        assert_eq!(changed, ["temporary_markers", "todo_comments"]);
+        assert!(simulation.needs_analysis.is_empty());
+        assert!(simulation.format_display().contains("✅ CI passes"));
+    }
+
+    #[test]
+    fn test_simulate_flags_rules_needing_analysis() {
+        let mut current = GuardianConfig::default();
+        apply_overrides(
+            &mut current,
+            &[parse_override("temporary_markers=off").unwrap()],
+        )
+        .unwrap();
+        let mut proposed = GuardianConfig::default();
+        for rule in proposed
+            .patterns
+            .values_mut()
+            .flat_map(|category| category.rules.iter_mut())
+        {
+            if rule.id == "todo_comments" {
+                rule.pattern = r"\bTODO\b".to_string();
+            }
+        }
+
+        let simulation = simulate(&report(), &current, &proposed);
+        assert_eq!(
+            simulation.needs_analysis,
+            ["temporary_markers", "todo_comments"]
+        );
+        assert!(parse_override("todo_comments=loud").is_err());
+        assert!(apply_overrides(&mut proposed, &[parse_override("nope=off").unwrap()]).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Test support for snapshotting Guardian output
+//!
+//! Architecture: Test Fixtures - Deterministic inputs make output comparable across versions
+//! - `ReportBuilder` assembles synthetic reports with fixed timestamps and timings
+//! - `normalize` masks what still varies between runs, such as timestamps and UUIDs
+//! - Golden files are compared by `assert_snapshot` and rewritten on request
+
+use crate::domain::violations::{
+    GuardianError, GuardianResult, Severity, ValidationReport, Violation,
+};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that rewrites golden files instead of comparing against them
+pub const UPDATE_SNAPSHOTS_ENV: &str = "GUARDIAN_UPDATE_SNAPSHOTS";
+
+/// Timestamp given to every report and violation built here, the Unix epoch
+pub fn fixed_timestamp() -> DateTime<Utc> {
+    DateTime::<Utc>::default()
+}
+
+/// Builder for synthetic validation reports with deterministic metadata
+#[derive(Debug, Clone)]
+pub struct ReportBuilder {
+    report: ValidationReport,
+}
+
+impl ReportBuilder {
+    /// Start an empty report validated at [`fixed_timestamp`]
+    pub fn new() -> Self {
+        let mut report = ValidationReport::new();
+        report.summary.validated_at = fixed_timestamp();
+        Self { report }
+    }
+
+    /// Add a violation, pinning its detection time to [`fixed_timestamp`]
+    pub fn violation(mut self, mut violation: Violation) -> Self {
+        violation.detected_at = fixed_timestamp();
+        self.report.add_violation(violation);
+        self
+    }
+
+    /// Add an error-severity violation at the start of a line
+    pub fn error(self, rule_id: &str, file: &str, line: u32, message: &str) -> Self {
+        self.located(rule_id, Severity::Error, file, line, message)
+    }
+
+    /// Add a warning-severity violation at the start of a line
+    pub fn warning(self, rule_id: &str, file: &str, line: u32, message: &str) -> Self {
+        self.located(rule_id, Severity::Warning, file, line, message)
+    }
+
+    /// Add an info-severity violation at the start of a line
+    pub fn info(self, rule_id: &str, file: &str, line: u32, message: &str) -> Self {
+        self.located(rule_id, Severity::Info, file, line, message)
+    }
+
+    fn located(
+        self,
+        rule_id: &str,
+        severity: Severity,
+        file: &str,
+        line: u32,
+        message: &str,
+    ) -> Self {
+        let violation =
+            Violation::new(rule_id, severity, PathBuf::from(file), message).with_position(line, 1);
+        self.violation(violation)
+    }
+
+    /// Set the number of files analyzed
+    pub fn files_analyzed(mut self, count: usize) -> Self {
+        self.report.set_files_analyzed(count);
+        self
+    }
+
+    /// Set the execution time, zero unless set
+    pub fn execution_time_ms(mut self, duration_ms: u64) -> Self {
+        self.report.set_execution_time(duration_ms);
+        self
+    }
+
+    /// Finish the report
+    pub fn build(self) -> ValidationReport {
+        self.report
+    }
+}
+
+impl Default for ReportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mask values that differ between runs of the same analysis
+///
+/// RFC 3339 timestamps become `[timestamp]` and UUIDs become `[uuid]`, so output from
+/// real analyses snapshots as reliably as output from [`ReportBuilder`].
+pub fn normalize(output: &str) -> String {
+    let timestamp =
+        Regex::new(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2}| UTC)?")
+            .expect("timestamp pattern should compile");
+    let uuid =
+        Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+            .expect("uuid pattern should compile");
+
+    let output = timestamp.replace_all(output, "[timestamp]");
+    uuid.replace_all(&output, "[uuid]").into_owned()
+}
+
+/// Compare normalized output with a golden file
+///
+/// The golden file is written instead when it does not exist yet or when
+/// `GUARDIAN_UPDATE_SNAPSHOTS` is set. A mismatch is a validation error naming the
+/// first differing line.
+pub fn check_snapshot(path: impl AsRef<Path>, actual: &str) -> GuardianResult<()> {
+    let path = path.as_ref();
+    let actual = normalize(actual);
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() || !path.exists() {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|e| GuardianError::io(directory, e))?;
+        }
+        return fs::write(path, &actual).map_err(|e| GuardianError::io(path, e));
+    }
+
+    let expected = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+    if expected == actual {
+        return Ok(());
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => line += 1,
+            (expected, actual) => {
+                return Err(GuardianError::validation(format!(
+                    "Snapshot {} differs at line {line}:\n  expected: {}\n  actual:   {}\nSet {UPDATE_SNAPSHOTS_ENV}=1 to update it",
+                    path.display(),
+                    expected.unwrap_or("<end of snapshot>"),
+                    actual.unwrap_or("<end of output>"),
+                )));
+            }
+        }
+    }
+}
+
+/// Assert that output matches a golden file, see [`check_snapshot`]
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    if let Err(e) = check_snapshot(path, actual) {
+        panic!("{e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{OutputFormat, ReportFormatter};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_builder() {
+        let report = ReportBuilder::new()
+            .error(
+                "todo_comments",
+                "src/lib.rs",
+                3,
+                "Development marker detected",
+            )
+            .warning("hardcoded_paths", "src/main.rs", 10, "Hardcoded path")
+            .files_analyzed(2)
+            .build();
+
+        assert_eq!(report.violations.len(), 2);
+        assert_eq!(report.summary.violations_by_severity.error, 1);
+        assert_eq!(report.summary.violations_by_severity.warning, 1);
+        assert_eq!(report.summary.total_files, 2);
+        assert_eq!(report.summary.validated_at, fixed_timestamp());
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| v.detected_at == fixed_timestamp()));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("at 2024-05-01T12:30:45.123Z id 67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            "at [timestamp] id [uuid]"
+        );
+        assert_eq!(normalize("line 12:30"), "line 12:30");
+    }
+
+    #[test]
+    fn test_check_snapshot() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let path = temp_dir.path().join("snapshots/report.json");
+        let report = ReportBuilder::new()
+            .error(
+                "todo_comments",
+                "src/lib.rs",
+                3,
+                "Development marker detected",
+            )
+            .build();
+        let output = ReportFormatter::default()
+            .format_report(&report, OutputFormat::Json)
+            .expect("Failed to format report");
+
+        // The first run records the snapshot, later runs compare against it
+        check_snapshot(&path, &output).expect("Failed to record snapshot");
+        check_snapshot(&path, &output).expect("Snapshot should match");
+
+        let changed = output.replace("src/lib.rs", "src/main.rs");
+        let error = check_snapshot(&path, &changed).expect_err("Snapshot should differ");
+        assert!(error.to_string().contains("src/main.rs"));
+    }
+}
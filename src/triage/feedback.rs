@@ -0,0 +1,164 @@
+//! Opt-in feedback on how often each rule's findings are triaged as false positives
+//!
+//! Architecture: Anti-Corruption Layer - Label counts are aggregated locally, never sent anywhere
+//! - Only rule ids and counts are stored, so the file can be shared without exposing code
+//! - Exports name built-in rules and hash custom rule ids, so upstream can tune defaults
+
+use super::TriageLabel;
+use crate::config::{GuardianConfig, RULE_PACK_VERSION};
+use crate::domain::violations::{GuardianError, GuardianResult};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Triage labels counted for one rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFeedback {
+    pub accepted: usize,
+    pub false_positive: usize,
+    pub wont_fix: usize,
+}
+
+impl RuleFeedback {
+    /// Number of labels counted
+    pub fn total(&self) -> usize {
+        self.accepted + self.false_positive + self.wont_fix
+    }
+
+    /// Share of labels that were false positives
+    pub fn false_positive_rate(&self) -> f64 {
+        self.false_positive as f64 / self.total().max(1) as f64
+    }
+}
+
+/// Label counts by rule, aggregated between runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackLog {
+    /// Day the first label was counted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<NaiveDate>,
+    /// Label counts by rule id
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleFeedback>,
+}
+
+impl FeedbackLog {
+    /// Load the log from a file, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> GuardianResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            GuardianError::config(format!("Invalid feedback file '{}': {e}", path.display()))
+        })
+    }
+
+    /// Write the log to a file, creating its directory
+    pub fn save(&self, path: &Path) -> GuardianResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| GuardianError::io(parent, e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize feedback: {e}")))?;
+        fs::write(path, content).map_err(|e| GuardianError::io(path, e))
+    }
+
+    /// Count a label given to a violation of a rule
+    pub fn record(&mut self, rule_id: &str, label: TriageLabel) {
+        self.since.get_or_insert_with(|| Utc::now().date_naive());
+        let feedback = self.rules.entry(rule_id.to_string()).or_default();
+        match label {
+            TriageLabel::Accepted => feedback.accepted += 1,
+            TriageLabel::FalsePositive => feedback.false_positive += 1,
+            TriageLabel::WontFix => feedback.wont_fix += 1,
+        }
+    }
+
+    /// Summary safe to share, noisiest rules first
+    pub fn export(&self) -> FeedbackExport {
+        let mut rules: Vec<ExportedRule> = self
+            .rules
+            .iter()
+            .map(|(rule_id, feedback)| {
+                let builtin = GuardianConfig::builtin_rule(rule_id).is_some();
+                ExportedRule {
+                    rule: if builtin {
+                        rule_id.clone()
+                    } else {
+                        anonymize(rule_id)
+                    },
+                    builtin,
+                    labels: *feedback,
+                    false_positive_rate: feedback.false_positive_rate(),
+                }
+            })
+            .collect();
+        rules.sort_by(|a, b| {
+            b.false_positive_rate
+                .total_cmp(&a.false_positive_rate)
+                .then_with(|| b.labels.total().cmp(&a.labels.total()))
+                .then_with(|| a.rule.cmp(&b.rule))
+        });
+
+        FeedbackExport {
+            guardian_version: env!("CARGO_PKG_VERSION").to_string(),
+            rule_pack_version: RULE_PACK_VERSION.to_string(),
+            since: self.since,
+            rules,
+        }
+    }
+}
+
+/// Shareable feedback summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackExport {
+    pub guardian_version: String,
+    pub rule_pack_version: String,
+    pub since: Option<NaiveDate>,
+    pub rules: Vec<ExportedRule>,
+}
+
+/// Feedback on one rule in an export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedRule {
+    /// Built-in rule id, or `custom-` and a hash of a custom rule id
+    pub rule: String,
+    pub builtin: bool,
+    pub labels: RuleFeedback,
+    pub false_positive_rate: f64,
+}
+
+/// Stable stand-in for a custom rule id, which may name internal code
+fn anonymize(rule_id: &str) -> String {
+    let digest = Sha256::digest(rule_id.as_bytes());
+    format!("custom-{}", &hex::encode(digest)[..12])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_hides_custom_rule_ids() {
+        let mut log = FeedbackLog::default();
+        log.record("todo_comments", TriageLabel::FalsePositive);
+        log.record("todo_comments", TriageLabel::Accepted);
+        log.record("acme_billing_lock", TriageLabel::FalsePositive);
+
+        let export = log.export();
+        assert_eq!(export.rules.len(), 2);
+        assert!(!export.rules[0].builtin);
+        assert!(export.rules[0].rule.starts_with("custom-"));
+        assert_eq!(export.rules[0].false_positive_rate, 1.0);
+        assert_eq!(export.rules[1].rule, "todo_comments");
+        assert_eq!(export.rules[1].false_positive_rate, 0.5);
+
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(!json.contains("acme_billing_lock"));
+        assert!(log.since.is_some());
+    }
+}
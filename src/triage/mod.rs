@@ -0,0 +1,314 @@
+//! Triage labels for individual violations
+//!
+//! Architecture: Anti-Corruption Layer - Human decisions about findings are kept in a TOML file
+//! - Violations are labeled by project fingerprint, so labels survive moved lines and reruns
+//! - Labels adjust reporting: false positives are hidden and won't-fix findings drop to info
+//! - Per-rule label counts point at rules whose patterns need tightening
+
+pub mod feedback;
+
+use crate::domain::violations::{
+    GuardianError, GuardianResult, Severity, ValidationReport, Violation,
+};
+use crate::report::history::{project_fingerprint, relative_path};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Triage file location, relative to the project root
+pub const TRIAGE_FILE: &str = ".guardian/triage.toml";
+
+/// Decision recorded for a violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageLabel {
+    /// A real finding, reported as usual
+    Accepted,
+    /// The rule matched code it should not have; the violation is hidden
+    FalsePositive,
+    /// A real finding that will not be fixed; reported as info
+    WontFix,
+}
+
+impl TriageLabel {
+    /// Every label, in display order
+    pub const ALL: [TriageLabel; 3] = [Self::Accepted, Self::FalsePositive, Self::WontFix];
+
+    /// Name used in the triage file and on the command line
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::FalsePositive => "false_positive",
+            Self::WontFix => "wont_fix",
+        }
+    }
+}
+
+impl fmt::Display for TriageLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TriageLabel {
+    type Err = GuardianError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|label| label.as_str() == s)
+            .ok_or_else(|| {
+                GuardianError::config(format!(
+                    "Unknown triage label '{s}' (expected accepted, false_positive or wont_fix)"
+                ))
+            })
+    }
+}
+
+/// Label of one violation, with what identifies it to a reader of the file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriageEntry {
+    pub label: TriageLabel,
+    /// Rule that reported the violation
+    pub rule: String,
+    /// File of the violation, relative to the project root
+    pub file: String,
+    /// Why the label was chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub labeled_at: DateTime<Utc>,
+}
+
+impl TriageEntry {
+    /// Label a violation now
+    pub fn new(
+        violation: &Violation,
+        root: &Path,
+        label: TriageLabel,
+        note: Option<String>,
+    ) -> Self {
+        Self {
+            label,
+            rule: violation.rule_id.clone(),
+            file: relative_path(&violation.file_path, root),
+            note,
+            labeled_at: Utc::now(),
+        }
+    }
+}
+
+/// Triage labels by violation fingerprint, persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Triage {
+    /// Labels by project fingerprint
+    #[serde(default)]
+    violations: BTreeMap<String, TriageEntry>,
+}
+
+impl Triage {
+    /// Load labels from a file, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> GuardianResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| GuardianError::io(path, e))?;
+        toml::from_str(&content).map_err(|e| {
+            GuardianError::config(format!("Invalid triage file '{}': {e}", path.display()))
+        })
+    }
+
+    /// Write the labels to a file, creating its directory
+    pub fn save(&self, path: &Path) -> GuardianResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| GuardianError::io(parent, e))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| GuardianError::config(format!("Failed to serialize triage: {e}")))?;
+        fs::write(path, content).map_err(|e| GuardianError::io(path, e))
+    }
+
+    /// Label recorded for a fingerprint
+    pub fn get(&self, fingerprint: &str) -> Option<&TriageEntry> {
+        self.violations.get(fingerprint)
+    }
+
+    /// Label a fingerprint, replacing any earlier label
+    pub fn insert(&mut self, fingerprint: impl Into<String>, entry: TriageEntry) {
+        self.violations.insert(fingerprint.into(), entry);
+    }
+
+    /// Remove the label of a fingerprint, returning it
+    pub fn remove(&mut self, fingerprint: &str) -> Option<TriageEntry> {
+        self.violations.remove(fingerprint)
+    }
+
+    /// Number of labels of each kind, by rule
+    pub fn counts_by_rule(&self) -> BTreeMap<&str, BTreeMap<TriageLabel, usize>> {
+        let mut counts: BTreeMap<&str, BTreeMap<TriageLabel, usize>> = BTreeMap::new();
+        for entry in self.violations.values() {
+            *counts
+                .entry(entry.rule.as_str())
+                .or_default()
+                .entry(entry.label)
+                .or_default() += 1;
+        }
+        counts
+    }
+
+    /// Number of labeled violations
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Whether no violations are labeled
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Violations whose reporting [`apply_triage`] changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TriageSummary {
+    /// Violations labeled accepted, reported unchanged
+    pub accepted: usize,
+    /// Violations hidden as false positives
+    pub false_positives: usize,
+    /// Violations labeled won't fix, lowered to info
+    pub wont_fix: usize,
+}
+
+/// Adjust a report to the triage labels of its violations
+///
+/// False positives are removed and counted per rule in the report summary, won't-fix
+/// violations are lowered to info, and every labeled violation is tagged `triage:<label>`.
+pub fn apply_triage(report: &mut ValidationReport, triage: &Triage, root: &Path) -> TriageSummary {
+    let mut summary = TriageSummary::default();
+    if triage.is_empty() {
+        return summary;
+    }
+
+    // Severities are recounted when the false positives are removed
+    let hidden_tag = format!("triage:{}", TriageLabel::FalsePositive);
+    let mut false_positives = BTreeMap::new();
+    for violation in &mut report.violations {
+        let Some(entry) = triage.get(&project_fingerprint(violation, root)) else {
+            continue;
+        };
+        match entry.label {
+            TriageLabel::Accepted => summary.accepted += 1,
+            TriageLabel::FalsePositive => {
+                *false_positives
+                    .entry(violation.rule_id.clone())
+                    .or_insert(0) += 1;
+                summary.false_positives += 1;
+            }
+            TriageLabel::WontFix => {
+                violation.severity = Severity::Info;
+                summary.wont_fix += 1;
+            }
+        }
+        violation.tags.push(format!("triage:{}", entry.label));
+    }
+    report.retain_violations(|violation| !violation.tags.contains(&hidden_tag));
+    report.summary.false_positives = false_positives;
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn report() -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (rule, context) in [
+            ("todo_comments", "// TODO: retry"),
+            ("todo_comments", "// TODO: cache"),
+            ("no_unwrap", "config.unwrap()"),
+        ] {
+            report.add_violation(
+                Violation::new(rule, Severity::Error, PathBuf::from("src/lib.rs"), "found")
+                    .with_context(context),
+            );
+        }
+        report
+    }
+
+    fn entry(label: TriageLabel, rule: &str) -> TriageEntry {
+        TriageEntry {
+            label,
+            rule: rule.to_string(),
+            file: "src/lib.rs".to_string(),
+            note: None,
+            labeled_at: DateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_apply_triage() {
+        let root = Path::new(".");
+        let mut report = report();
+        let mut triage = Triage::default();
+        let fingerprints: Vec<String> = report
+            .violations
+            .iter()
+            .map(|violation| project_fingerprint(violation, root))
+            .collect();
+        triage.insert(
+            &fingerprints[0],
+            entry(TriageLabel::FalsePositive, "todo_comments"),
+        );
+        triage.insert(
+            &fingerprints[1],
+            entry(TriageLabel::WontFix, "todo_comments"),
+        );
+        triage.insert(&fingerprints[2], entry(TriageLabel::Accepted, "no_unwrap"));
+
+        let summary = apply_triage(&mut report, &triage, root);
+        assert_eq!(
+            summary,
+            TriageSummary {
+                accepted: 1,
+                false_positives: 1,
+                wont_fix: 1
+            }
+        );
+        assert_eq!(report.violations.len(), 2);
+        assert_eq!(report.violations[0].severity, Severity::Info);
+        assert_eq!(report.violations[0].tags, ["triage:wont_fix"]);
+        assert_eq!(report.violations[1].severity, Severity::Error);
+        assert_eq!(report.summary.violations_by_severity.error, 1);
+        assert_eq!(report.summary.false_positives["todo_comments"], 1);
+
+        let counts = triage.counts_by_rule();
+        assert_eq!(counts["todo_comments"][&TriageLabel::FalsePositive], 1);
+        assert_eq!(counts["no_unwrap"][&TriageLabel::Accepted], 1);
+    }
+
+    #[test]
+    fn test_triage_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TRIAGE_FILE);
+        assert!(Triage::load(&path).unwrap().is_empty());
+
+        let mut triage = Triage::default();
+        let mut labeled = entry(TriageLabel::WontFix, "no_unwrap");
+        labeled.note = Some("startup only".to_string());
+        triage.insert("0123456789abcdef", labeled);
+        triage.save(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[violations.0123456789abcdef]"));
+        assert!(content.contains("label = \"wont_fix\""));
+        assert_eq!(Triage::load(&path).unwrap(), triage);
+        assert_eq!(
+            "false_positive".parse::<TriageLabel>().unwrap(),
+            TriageLabel::FalsePositive
+        );
+        assert!("ignored".parse::<TriageLabel>().is_err());
+    }
+}
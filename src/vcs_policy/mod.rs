@@ -0,0 +1,347 @@
+//! Commit message and branch name policy
+//!
+//! Architecture: Domain Services - Commit hygiene is reported as violations, like code findings
+//! - Subjects follow Conventional Commits, within a length limit, when the policy asks for it
+//! - Branch names must match one of the allowed patterns, and neither may carry forbidden words
+//! - Violations land in an ordinary report, so every output format and exit code applies
+
+use crate::config::VcsPolicyConfig;
+use crate::domain::violations::{GuardianError, GuardianResult, ValidationReport, Violation};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Category reported on every policy violation
+const CATEGORY: &str = "vcs_policy";
+
+/// `type(scope)!: description`
+const CONVENTIONAL_SUBJECT: &str =
+    r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()]*)\))?!?: (?P<description>.*)$";
+
+/// Check a commit message and, when given, a branch name
+///
+/// The message comes with the path its violations are reported against, such as the
+/// message file of a `commit-msg` hook.
+pub fn check_commit(
+    policy: &VcsPolicyConfig,
+    message: Option<(&str, &Path)>,
+    branch: Option<&str>,
+) -> GuardianResult<ValidationReport> {
+    let mut report = ValidationReport::new();
+    if let Some((message, source)) = message {
+        for violation in check_commit_message(policy, message, source)? {
+            report.add_violation(violation);
+        }
+    }
+    if let Some(branch) = branch {
+        for violation in check_branch_name(policy, branch)? {
+            report.add_violation(violation);
+        }
+    }
+    Ok(report)
+}
+
+/// Check a commit message against the policy
+///
+/// Comment lines are dropped first, as git does. Subjects git writes itself, for merges
+/// and reverts, are exempt from the Conventional Commits format.
+pub fn check_commit_message(
+    policy: &VcsPolicyConfig,
+    message: &str,
+    source: &Path,
+) -> GuardianResult<Vec<Violation>> {
+    let lines: Vec<&str> = message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    let first = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let lines = &lines[first..];
+    let violation = |rule_id: &str, line: usize, message: String| {
+        Violation::new(rule_id, policy.severity, source.to_path_buf(), message)
+            .with_position(line as u32, 1)
+            .with_category(CATEGORY)
+    };
+
+    let Some(subject) = lines.first().map(|line| line.trim_end()) else {
+        return Ok(vec![violation(
+            "commit_message_empty",
+            1,
+            "Commit message is empty".to_string(),
+        )]);
+    };
+    let subject_line = first + 1;
+    let mut violations = Vec::new();
+
+    if policy.conventional_commits && !is_generated_subject(subject) {
+        if let Some(problem) = conventional_problem(policy, subject) {
+            violations.push(
+                violation("conventional_commit", subject_line, problem).with_context(subject),
+            );
+        }
+        if lines.get(1).is_some_and(|line| !line.trim().is_empty()) {
+            violations.push(violation(
+                "conventional_commit",
+                subject_line + 1,
+                "Separate the commit body from the subject with a blank line".to_string(),
+            ));
+        }
+    }
+
+    if let Some(max) = policy.max_subject_length {
+        let length = subject.chars().count();
+        if length > max {
+            violations.push(
+                violation(
+                    "commit_subject_length",
+                    subject_line,
+                    format!("Commit subject is {length} characters long; at most {max} allowed"),
+                )
+                .with_context(subject),
+            );
+        }
+    }
+
+    for word in &policy.forbidden_words {
+        let pattern = forbidden_word_regex(word)?;
+        for (index, line) in lines.iter().enumerate() {
+            if pattern.is_match(line) {
+                violations.push(
+                    violation(
+                        "forbidden_word",
+                        subject_line + index,
+                        format!("Commit message contains forbidden word '{word}'"),
+                    )
+                    .with_context(line.trim()),
+                );
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Check a branch name against the policy
+///
+/// A detached `HEAD` has no branch name to check.
+pub fn check_branch_name(policy: &VcsPolicyConfig, branch: &str) -> GuardianResult<Vec<Violation>> {
+    if branch == "HEAD" {
+        return Ok(Vec::new());
+    }
+    let location = PathBuf::from(format!("refs/heads/{branch}"));
+    let violation = |rule_id: &str, message: String| {
+        Violation::new(rule_id, policy.severity, location.clone(), message)
+            .with_category(CATEGORY)
+            .with_context(branch)
+    };
+    let mut violations = Vec::new();
+
+    if !policy.branch_patterns.is_empty() {
+        let mut allowed = false;
+        for pattern in &policy.branch_patterns {
+            let glob = glob::Pattern::new(pattern).map_err(|e| {
+                GuardianError::config(format!(
+                    "Invalid vcs_policy.branch_patterns entry '{pattern}': {e}"
+                ))
+            })?;
+            allowed |= glob.matches(branch);
+        }
+        if !allowed {
+            violations.push(violation(
+                "branch_name",
+                format!(
+                    "Branch '{branch}' matches none of the allowed patterns: {}",
+                    policy.branch_patterns.join(", ")
+                ),
+            ));
+        }
+    }
+
+    for word in &policy.forbidden_words {
+        if forbidden_word_regex(word)?.is_match(branch) {
+            violations.push(violation(
+                "forbidden_word",
+                format!("Branch name contains forbidden word '{word}'"),
+            ));
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Message of the latest commit in a repository
+pub fn last_commit_message(repository: &Path) -> GuardianResult<String> {
+    git(repository, &["log", "-1", "--format=%B"])
+}
+
+/// Branch checked out in a repository, or `HEAD` when detached
+pub fn current_branch(repository: &Path) -> GuardianResult<String> {
+    git(repository, &["rev-parse", "--abbrev-ref", "HEAD"]).map(|branch| branch.trim().to_string())
+}
+
+fn git(repository: &Path, args: &[&str]) -> GuardianResult<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .output()
+        .map_err(|e| GuardianError::config(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(GuardianError::config(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Why a subject is not a valid Conventional Commit, if it is not
+fn conventional_problem(policy: &VcsPolicyConfig, subject: &str) -> Option<String> {
+    let conventional =
+        Regex::new(CONVENTIONAL_SUBJECT).expect("conventional subject regex should compile");
+    let Some(captures) = conventional.captures(subject) else {
+        return Some(
+            "Commit subject is not a Conventional Commit; expected `type(scope): description`"
+                .to_string(),
+        );
+    };
+
+    let commit_type = &captures["type"];
+    if !policy
+        .commit_types
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(commit_type))
+    {
+        return Some(format!(
+            "Unknown commit type '{commit_type}'; expected one of {}",
+            policy.commit_types.join(", ")
+        ));
+    }
+    let scope = captures.name("scope").map(|scope| scope.as_str().trim());
+    if scope == Some("") || (policy.require_scope && scope.is_none()) {
+        return Some("Commit subject needs a scope, as in `type(scope): description`".to_string());
+    }
+    if captures["description"].trim().is_empty() {
+        return Some("Commit subject has no description after the type".to_string());
+    }
+    None
+}
+
+/// Subjects git writes itself for merges and reverts
+fn is_generated_subject(subject: &str) -> bool {
+    subject.starts_with("Merge ") || subject.starts_with("Revert \"")
+}
+
+/// A forbidden word, matched case-insensitively where it is not part of a longer word
+fn forbidden_word_regex(word: &str) -> GuardianResult<Regex> {
+    Regex::new(&format!(r"(?i)(^|[^\w]){}($|[^\w])", regex::escape(word))).map_err(|e| {
+        GuardianError::config(format!(
+            "Invalid vcs_policy.forbidden_words entry '{word}': {e}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_rules(policy: &VcsPolicyConfig, message: &str) -> Vec<(String, Option<u32>)> {
+        check_commit_message(policy, message, Path::new("COMMIT_EDITMSG"))
+            .expect("policy should be valid")
+            .into_iter()
+            .map(|violation| (violation.rule_id, violation.line_number))
+            .collect()
+    }
+
+    fn policy() -> VcsPolicyConfig {
+        VcsPolicyConfig {
+            branch_patterns: vec!["main".to_string(), "feat/*".to_string()],
+            forbidden_words: vec!["WIP".to_string(), "fixup!".to_string()],
+            ..VcsPolicyConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_conventional_subjects() {
+        let policy = policy();
+        assert!(message_rules(&policy, "feat(parser): support raw strings\n").is_empty());
+        assert!(message_rules(&policy, "fix!: drop the legacy flag\n\nBody text.").is_empty());
+        assert!(message_rules(&policy, "Merge branch 'main' into feat/x").is_empty());
+        assert!(message_rules(&policy, "# Please enter a message\ndocs: typo\n").is_empty());
+
+        let violation = |rule: &str, line| vec![(rule.to_string(), Some(line))];
+        assert_eq!(
+            message_rules(&policy, "Add raw strings"),
+            violation("conventional_commit", 1)
+        );
+        assert_eq!(
+            message_rules(&policy, "feature: raw strings"),
+            violation("conventional_commit", 1)
+        );
+        assert_eq!(
+            message_rules(&policy, "feat: raw strings\nno blank line"),
+            violation("conventional_commit", 2)
+        );
+        assert_eq!(
+            message_rules(&policy, "# only comments\n"),
+            violation("commit_message_empty", 1)
+        );
+
+        let scoped = VcsPolicyConfig {
+            require_scope: true,
+            ..VcsPolicyConfig::default()
+        };
+        assert_eq!(
+            message_rules(&scoped, "feat: raw strings"),
+            violation("conventional_commit", 1)
+        );
+    }
+
+    #[test]
+    fn test_subject_length_and_forbidden_words() {
+        let policy = policy();
+        let long = format!("feat: {}", "x".repeat(70));
+        assert_eq!(
+            message_rules(&policy, &long),
+            vec![("commit_subject_length".to_string(), Some(1))]
+        );
+        assert_eq!(
+            message_rules(&policy, "fix: parser\n\nStill wip, see notes"),
+            vec![("forbidden_word".to_string(), Some(3))]
+        );
+        // Parts of longer words are not forbidden
+        assert!(message_rules(&policy, "fix: wipe stale cache entries").is_empty());
+    }
+
+    #[test]
+    fn test_branch_names() {
+        let policy = policy();
+        let rules = |branch: &str| -> Vec<String> {
+            check_branch_name(&policy, branch)
+                .expect("policy should be valid")
+                .into_iter()
+                .map(|violation| violation.rule_id)
+                .collect()
+        };
+        assert!(rules("main").is_empty());
+        assert!(rules("feat/raw-strings").is_empty());
+        assert!(rules("HEAD").is_empty());
+        assert_eq!(rules("hotfix"), vec!["branch_name"]);
+        assert_eq!(rules("feat/wip-parser"), vec!["forbidden_word"]);
+
+        let report = check_commit(
+            &policy,
+            Some(("oops", Path::new("COMMIT_EDITMSG"))),
+            Some("hotfix"),
+        )
+        .expect("policy should be valid");
+        assert_eq!(report.summary.violations_by_severity.error, 2);
+        assert_eq!(
+            report.violations[1].file_path,
+            Path::new("refs/heads/hotfix")
+        );
+    }
+}
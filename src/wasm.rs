@@ -0,0 +1,63 @@
+//! WebAssembly bindings for running Guardian in the browser or a serverless worker
+//!
+//! Architecture: Anti-corruption Layer - JavaScript sees strings in and JSON out
+//! - Source code and configuration are passed in memory; nothing touches the filesystem
+//! - Violations are returned as the same JSON the `violations` field of a report uses
+//! - Errors cross the boundary as `Error` objects carrying the Guardian error message
+//!
+//! Build with `cargo rustc --lib --crate-type cdylib --target wasm32-unknown-unknown
+//! --no-default-features --features wasm`, then run `wasm-bindgen --target web` on the output.
+
+use crate::{Analyzer, GuardianConfig, GuardianError};
+use wasm_bindgen::prelude::*;
+
+/// Convert a Guardian error into a JavaScript `Error`
+fn js_error(error: GuardianError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+/// An analyzer configured once and reused for many sources
+#[wasm_bindgen]
+pub struct Guardian {
+    analyzer: Analyzer,
+}
+
+#[wasm_bindgen]
+impl Guardian {
+    /// Create an analyzer from a `guardian.yaml` document, or the default configuration
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_yaml: Option<String>) -> Result<Guardian, JsError> {
+        let config = match config_yaml {
+            Some(yaml) => GuardianConfig::load_from_str(&yaml).map_err(js_error)?,
+            None => GuardianConfig::default(),
+        };
+        let analyzer = Analyzer::new(config).map_err(js_error)?;
+        Ok(Guardian { analyzer })
+    }
+
+    /// Analyze one source file and return its violations as a JSON array
+    ///
+    /// `path` scopes path-specific rules and labels the violations; it is never read.
+    #[wasm_bindgen(js_name = analyzeContent)]
+    pub fn analyze_content(&self, path: &str, content: &str) -> Result<String, JsError> {
+        let violations = self
+            .analyzer
+            .analyze_content(path, content)
+            .map_err(js_error)?;
+        serde_json::to_string(&violations).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
+
+/// Analyze one source file with the default configuration
+#[wasm_bindgen(js_name = analyzeContent)]
+pub fn analyze_content(path: &str, content: &str) -> Result<String, JsError> {
+    Guardian::new(None)?.analyze_content(path, content)
+}
+
+/// Check that a `guardian.yaml` document parses and validates
+#[wasm_bindgen(js_name = validateConfig)]
+pub fn validate_config(config_yaml: &str) -> Result<(), JsError> {
+    GuardianConfig::load_from_str(config_yaml)
+        .map(|_| ())
+        .map_err(js_error)
+}